@@ -0,0 +1,283 @@
+//! Coordinate math for the hex grid abduction is played on
+//!
+//! Kept apart from the server (with no server dependencies) so anything else working with the grid,
+//! like analysis tools or a client compiled to wasm, does the math exactly the same way. Enable the
+//! `ts` feature to generate typescript bindings for these types along with the server's.
+
+use std::{fmt, str::FromStr};
+
+use anyhow::anyhow;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Store an `(q, r)` value for a hex location
+///
+/// see https://www.redblobgames.com/grids/hexagons
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Hash,
+    derive_more::Add,
+    derive_more::AddAssign,
+    derive_more::Sub,
+    derive_more::SubAssign,
+    derive_more::From,
+    derive_more::Into,
+)]
+#[cfg_attr(feature = "ts", qubit::ts)]
+pub struct AxialHex(isize, isize);
+
+impl fmt::Display for AxialHex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.0, self.1)
+    }
+}
+
+impl FromStr for AxialHex {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = s
+            .split_once(",")
+            .ok_or(anyhow!("No comma delimeter in hex"))?;
+        Ok(Self(x.parse()?, y.parse()?))
+    }
+}
+
+impl AxialHex {
+    pub const ZERO: AxialHex = AxialHex(0, 0);
+    pub const EAST: AxialHex = AxialHex(1, 0);
+    pub const WEST: AxialHex = AxialHex(-1, 0);
+    pub const NORTH_EAST: AxialHex = AxialHex(1, -1);
+    pub const NORTH_WEST: AxialHex = AxialHex(0, -1);
+    pub const SOUTH_EAST: AxialHex = AxialHex(0, 1);
+    pub const SOUTH_WEST: AxialHex = AxialHex(-1, 1);
+
+    pub fn all_in_bounds(radius: isize) -> Vec<Self> {
+        Self::spiral(Self::ZERO, radius).collect()
+    }
+
+    /// The hexes exactly some distance from a center hex, going around it
+    /// (a ring of radius 0 is just the center)
+    pub fn ring(center: AxialHex, radius: isize) -> impl Iterator<Item = AxialHex> {
+        if radius <= 0 {
+            return Vec::from_iter((radius == 0).then_some(center)).into_iter();
+        }
+
+        // Start off to the south west and walk along each side in turn
+        let mut hex = center + AxialHex::SOUTH_WEST.scaled(radius);
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for direction in AxialHex::ZERO.neighbours() {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += direction;
+            }
+        }
+        ring.into_iter()
+    }
+
+    /// Every hex within some distance of a center hex, from the center outwards
+    pub fn spiral(center: AxialHex, radius: isize) -> impl Iterator<Item = AxialHex> {
+        (0..=radius).flat_map(move |r| Self::ring(center, r))
+    }
+
+    /// The hexes on a straight line between two hexes (incl. both ends)
+    pub fn line(a: AxialHex, b: AxialHex) -> impl Iterator<Item = AxialHex> {
+        let steps = a.dist_to(b);
+        // (nudged a little so lines along hex edges fall on the same side every time)
+        let (aq, ar) = (a.0 as f64 + 1e-6, a.1 as f64 + 1e-6);
+        let (bq, br) = (b.0 as f64 + 1e-6, b.1 as f64 + 1e-6);
+        (0..=steps).map(move |i| {
+            let t = if steps == 0 {
+                0.0
+            } else {
+                i as f64 / steps as f64
+            };
+            Self::round(aq + (bq - aq) * t, ar + (br - ar) * t)
+        })
+    }
+
+    /// The hexes visible from a center hex out to some distance, where some hexes block the view
+    /// (blocking hexes can be seen themselves, just not past)
+    pub fn fov(
+        center: AxialHex,
+        radius: isize,
+        blocked: impl Fn(AxialHex) -> bool,
+    ) -> impl Iterator<Item = AxialHex> {
+        Self::spiral(center, radius).filter(move |&hex| {
+            let between = (center.dist_to(hex) - 1).max(0) as usize;
+            Self::line(center, hex)
+                .skip(1)
+                .take(between)
+                .all(|h| !blocked(h))
+        })
+    }
+
+    /// The hex some fractional coordinate falls in
+    fn round(q: f64, r: f64) -> Self {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Self(rq as isize, rr as isize)
+    }
+
+    /// This hex as an offset, some number of times over
+    fn scaled(&self, times: isize) -> Self {
+        Self(self.0 * times, self.1 * times)
+    }
+
+    /// Determine if a given hex is adjacent to this hex
+    pub fn is_adjacent(&self, other: AxialHex) -> bool {
+        self.neighbours().contains(&other)
+    }
+
+    /// Return all neighbouring hexes
+    pub fn neighbours(&self) -> [AxialHex; 6] {
+        let AxialHex(q, r) = *self;
+        [
+            AxialHex(q + 1, r),
+            AxialHex(q + 1, r - 1),
+            AxialHex(q, r - 1),
+            AxialHex(q - 1, r),
+            AxialHex(q - 1, r + 1),
+            AxialHex(q, r + 1),
+        ]
+    }
+
+    pub fn random_in_bounds(rng: &mut impl Rng, radius: isize) -> Self {
+        let x = (rng.random_range(0..=2 * (radius as usize)) as isize) - radius;
+        let min_y = isize::max(-radius, -x - radius);
+        let max_y = isize::min(radius, -x + radius);
+        let y = (rng.random_range(0..=(max_y - min_y) as usize) as isize) + min_y;
+        let z = -x - y;
+
+        Self(x, z)
+    }
+
+    /// Get a `(q, r, s)` cube coordinate by deriving the `s` value
+    pub fn as_cube_coordinate(&self) -> (isize, isize, isize) {
+        (self.0, self.1, -self.0 - self.1)
+    }
+
+    pub fn dist_to_origin(&self) -> isize {
+        let (q, r, s) = self.as_cube_coordinate();
+        (q.abs() + r.abs() + s.abs()) / 2 // TODO: do we lose too much accuracy here?
+    }
+
+    pub fn dist_to(&self, other: Self) -> isize {
+        (other - *self).dist_to_origin()
+    }
+
+    pub fn within_bounds(&self, radius: isize) -> bool {
+        self.dist_to_origin() <= radius
+    }
+}
+
+/// Direction you can move on a hex grid
+/// This makes a few assumptions about the grid
+///  - Pointy topped hexagons
+///  - Odd rows are shunted right
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, strum::VariantArray)]
+#[cfg_attr(feature = "ts", qubit::ts)]
+#[serde(rename_all = "snake_case")]
+pub enum AxialHexDirection {
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl AxialHexDirection {
+    /// NOTE: right now this only works with adjacent hexs and returns None in other cases
+    pub fn direction_to(from: AxialHex, to: AxialHex) -> Option<Self> {
+        let delta = to - from;
+
+        match delta {
+            // Same hex
+            AxialHex::ZERO => None,
+
+            // Each direction
+            AxialHex::EAST => Some(AxialHexDirection::East),
+            AxialHex::WEST => Some(AxialHexDirection::West),
+            AxialHex::NORTH_EAST => Some(AxialHexDirection::NorthEast),
+            AxialHex::NORTH_WEST => Some(AxialHexDirection::NorthWest),
+            AxialHex::SOUTH_EAST => Some(AxialHexDirection::SouthEast),
+            AxialHex::SOUTH_WEST => Some(AxialHexDirection::SouthWest),
+
+            // Non-Adjacent
+            _ => None,
+        }
+    }
+}
+
+impl From<AxialHexDirection> for AxialHex {
+    fn from(value: AxialHexDirection) -> Self {
+        match value {
+            AxialHexDirection::East => AxialHex::EAST,
+            AxialHexDirection::West => AxialHex::WEST,
+            AxialHexDirection::NorthEast => AxialHex::NORTH_EAST,
+            AxialHexDirection::NorthWest => AxialHex::NORTH_WEST,
+            AxialHexDirection::SouthEast => AxialHex::SOUTH_EAST,
+            AxialHexDirection::SouthWest => AxialHex::SOUTH_WEST,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn test_rings_and_spirals() {
+        let center = AxialHex(2, -1);
+        assert_eq!(AxialHex::ring(center, 0).collect_vec(), vec![center]);
+        for radius in 1..4 {
+            let ring = AxialHex::ring(center, radius).collect_vec();
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|hex| hex.dist_to(center) == radius));
+            assert!(ring.iter().all_unique());
+        }
+
+        let spiral = AxialHex::spiral(AxialHex::ZERO, 3).collect_vec();
+        assert_eq!(spiral[0], AxialHex::ZERO);
+        assert_eq!(spiral.len(), 37);
+        assert!(spiral.iter().all(|hex| hex.within_bounds(3)));
+    }
+
+    #[test]
+    fn test_lines() {
+        let line = AxialHex::line(AxialHex::ZERO, AxialHex(3, -1)).collect_vec();
+        assert_eq!(line.len(), 4);
+        assert_eq!(line.first(), Some(&AxialHex::ZERO));
+        assert_eq!(line.last(), Some(&AxialHex(3, -1)));
+        assert!(line.iter().tuple_windows().all(|(a, b)| a.is_adjacent(*b)));
+
+        assert_eq!(
+            AxialHex::line(AxialHex::EAST, AxialHex::EAST).collect_vec(),
+            vec![AxialHex::EAST]
+        );
+    }
+
+    #[test]
+    fn test_fov_is_blocked() {
+        let wall = AxialHex::EAST;
+        let visible = AxialHex::fov(AxialHex::ZERO, 2, |hex| hex == wall).collect_vec();
+        assert!(visible.contains(&wall));
+        assert!(!visible.contains(&AxialHex(2, 0)));
+        assert!(visible.contains(&AxialHex(-2, 0)));
+    }
+}
@@ -0,0 +1,19 @@
+CREATE TABLE match_log (
+    -- Unique log id, used to keep logs in the order they were sent
+    log_id INTEGER PRIMARY KEY NOT NULL,
+
+    -- Which match the log happened in
+    match_id TEXT NOT NULL,
+
+    -- The game log itself
+    payload JSONB NOT NULL,
+
+    -- Created at
+    timestamp DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
+
+-- CREATE AN INDEX FOR THE LOGS IN A GIVEN MATCH
+CREATE INDEX match_log_match ON match_log(match_id);
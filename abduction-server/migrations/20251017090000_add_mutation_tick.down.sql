@@ -0,0 +1,3 @@
+DROP TABLE entity_snapshot;
+DROP INDEX entity_mutation_match_tick;
+ALTER TABLE entity_mutation DROP COLUMN tick_id;
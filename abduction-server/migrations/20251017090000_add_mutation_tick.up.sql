@@ -0,0 +1,22 @@
+-- Which tick of the match each mutation happened during
+-- (mutations from before this was tracked are all treated as happening at the start)
+ALTER TABLE entity_mutation ADD COLUMN tick_id INTEGER NOT NULL DEFAULT 0;
+
+-- CREATE AN INDEX FOR FINDING THE MUTATIONS IN A RANGE OF TICKS
+CREATE INDEX entity_mutation_match_tick ON entity_mutation(match_id, tick_id);
+
+CREATE TABLE entity_snapshot (
+    -- Which match the snapshot is of
+    match_id TEXT NOT NULL,
+
+    -- The tick the snapshot was taken at the end of
+    tick_id INTEGER NOT NULL,
+
+    -- Every entity at that point, keyed by entity id
+    entities JSONB NOT NULL,
+
+    PRIMARY KEY (match_id, tick_id),
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
@@ -0,0 +1,19 @@
+CREATE TABLE player_match_stats (
+    -- Which match these stats are for
+    match_id TEXT NOT NULL,
+
+    -- Which player these stats are for
+    entity_id TEXT NOT NULL,
+
+    -- Counters
+    hexes_traveled INTEGER NOT NULL DEFAULT 0,
+    food_eaten INTEGER NOT NULL DEFAULT 0,
+    conversations_held INTEGER NOT NULL DEFAULT 0,
+    near_death_events INTEGER NOT NULL DEFAULT 0,
+    items_crafted INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (match_id, entity_id),
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
@@ -0,0 +1,3 @@
+-- The segments the presenter runs at certain points in the match
+-- (matches from before this have no segments)
+ALTER TABLE match_config ADD COLUMN show_script JSONB NOT NULL DEFAULT '[]';
@@ -0,0 +1,3 @@
+-- The rules the match is played by (see `Ruleset`)
+-- (matches from before this are standard matches)
+ALTER TABLE match_config ADD COLUMN ruleset JSONB NOT NULL DEFAULT '{}';
@@ -0,0 +1 @@
+ALTER TABLE match_config DROP COLUMN started;
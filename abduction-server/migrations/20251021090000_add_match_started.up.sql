@@ -0,0 +1,3 @@
+-- Has the match started? (before then, only its players have been generated)
+-- (matches from before this were started as soon as they were created)
+ALTER TABLE match_config ADD COLUMN started BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,3 @@
+-- The experimental systems the match is played with (see `FeatureFlag`)
+-- (matches from before this had all of them)
+ALTER TABLE match_config ADD COLUMN features JSONB NOT NULL DEFAULT '["looting","trading","flooding","wildcards"]';
@@ -0,0 +1,33 @@
+CREATE TABLE admin_audit (
+    -- Unique id, used to keep interventions in the order they were made
+    audit_id INTEGER PRIMARY KEY NOT NULL,
+
+    -- Who intervened (where the command came from, see `CommandSource`)
+    actor TEXT NOT NULL,
+
+    -- Which match was intervened in
+    match_id TEXT NOT NULL,
+
+    -- Which tick the match was on at the time
+    tick_id INTEGER NOT NULL,
+
+    -- The command that was run (and its parameters)
+    command TEXT NOT NULL,
+
+    -- Created at
+    timestamp DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
+
+-- THE AUDIT IS APPEND ONLY
+CREATE TRIGGER admin_audit_no_update BEFORE UPDATE ON admin_audit
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit is append only');
+END;
+
+CREATE TRIGGER admin_audit_no_delete BEFORE DELETE ON admin_audit
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit is append only');
+END;
@@ -0,0 +1,2 @@
+DROP TABLE season_matches;
+DROP TABLE seasons;
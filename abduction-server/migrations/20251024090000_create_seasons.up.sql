@@ -0,0 +1,28 @@
+CREATE TABLE seasons (
+    -- A unique id identifying the season
+    season_id TEXT NOT NULL PRIMARY KEY,
+
+    -- Which season this is (counting up from 1)
+    number INTEGER NOT NULL,
+
+    -- How many matches are played before the season is over
+    match_count INTEGER NOT NULL,
+
+    -- How many players are generated for the first match of the season
+    seed_player_count INTEGER NOT NULL,
+
+    -- Who comes back for the next match of the season (see `CarryOverRules`)
+    carry_over JSONB NOT NULL,
+
+    -- When the season was created
+    created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE season_matches (
+    season_id TEXT NOT NULL,
+    match_id TEXT NOT NULL PRIMARY KEY,
+
+    -- Link to season and match config
+    FOREIGN KEY (season_id) REFERENCES seasons(season_id),
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
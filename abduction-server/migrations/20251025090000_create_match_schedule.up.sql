@@ -0,0 +1,9 @@
+CREATE TABLE match_schedule (
+    match_id TEXT NOT NULL PRIMARY KEY,
+
+    -- When the (prepared) match should start being played
+    starts_at DATETIME NOT NULL,
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
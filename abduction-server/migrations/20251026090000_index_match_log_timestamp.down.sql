@@ -0,0 +1 @@
+DROP INDEX match_log_timestamp;
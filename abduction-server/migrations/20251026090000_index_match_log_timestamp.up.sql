@@ -0,0 +1,2 @@
+-- CREATE AN INDEX FOR FINDING OLD LOGS TO PRUNE (see `retention`)
+CREATE INDEX match_log_timestamp ON match_log(timestamp);
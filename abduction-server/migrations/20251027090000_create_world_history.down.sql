@@ -0,0 +1 @@
+DROP TABLE world_history;
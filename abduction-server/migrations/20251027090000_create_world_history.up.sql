@@ -0,0 +1,16 @@
+CREATE TABLE world_history (
+    match_id TEXT NOT NULL,
+
+    -- The tick the world changed during
+    tick_id INTEGER NOT NULL,
+
+    -- How the world was from then on
+    day INTEGER NOT NULL,
+    time_of_day TEXT NOT NULL,
+    weather TEXT NOT NULL,
+
+    PRIMARY KEY (match_id, tick_id),
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
@@ -0,0 +1,19 @@
+CREATE TABLE match_event (
+    -- Unique event id, used to keep events in the order they were raised
+    event_id INTEGER PRIMARY KEY NOT NULL,
+
+    -- Which match the event was raised in
+    match_id TEXT NOT NULL,
+
+    -- The tick it was raised during
+    tick_id INTEGER NOT NULL,
+
+    -- The game event itself
+    payload JSONB NOT NULL,
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
+
+-- CREATE AN INDEX FOR THE EVENTS IN A GIVEN MATCH
+CREATE INDEX match_event_match ON match_event(match_id);
@@ -0,0 +1,27 @@
+-- (anything done outside a match is lost)
+CREATE TABLE admin_audit_old (
+    audit_id INTEGER PRIMARY KEY NOT NULL,
+    actor TEXT NOT NULL,
+    match_id TEXT NOT NULL,
+    tick_id INTEGER NOT NULL,
+    command TEXT NOT NULL,
+    timestamp DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
+
+INSERT INTO admin_audit_old (audit_id, actor, match_id, tick_id, command, timestamp)
+SELECT audit_id, actor, match_id, tick_id, command, timestamp FROM admin_audit
+WHERE match_id IS NOT NULL AND tick_id IS NOT NULL;
+
+DROP TABLE admin_audit;
+ALTER TABLE admin_audit_old RENAME TO admin_audit;
+
+CREATE TRIGGER admin_audit_no_update BEFORE UPDATE ON admin_audit
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit is append only');
+END;
+
+CREATE TRIGGER admin_audit_no_delete BEFORE DELETE ON admin_audit
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit is append only');
+END;
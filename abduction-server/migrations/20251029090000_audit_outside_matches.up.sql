@@ -0,0 +1,43 @@
+-- Not everything an admin does happens in a match (e.g blocking a phrase between matches),
+-- so the match and tick are only set if one was running
+-- (sqlite can't loosen a column, so the table is rebuilt)
+CREATE TABLE admin_audit_new (
+    -- Unique id, used to keep interventions in the order they were made
+    audit_id INTEGER PRIMARY KEY NOT NULL,
+
+    -- Who did it (the name of the admin whose key was used, or where the command came from,
+    -- see `CommandSource::actor`)
+    actor TEXT NOT NULL,
+
+    -- Which match was running at the time, if any
+    match_id TEXT,
+
+    -- Which tick the match was on at the time
+    tick_id INTEGER,
+
+    -- What was done (and its parameters)
+    command TEXT NOT NULL,
+
+    -- Created at
+    timestamp DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    -- Link to match config
+    FOREIGN KEY (match_id) REFERENCES match_config(match_id)
+);
+
+INSERT INTO admin_audit_new (audit_id, actor, match_id, tick_id, command, timestamp)
+SELECT audit_id, actor, match_id, tick_id, command, timestamp FROM admin_audit;
+
+DROP TABLE admin_audit;
+ALTER TABLE admin_audit_new RENAME TO admin_audit;
+
+-- THE AUDIT IS (STILL) APPEND ONLY
+CREATE TRIGGER admin_audit_no_update BEFORE UPDATE ON admin_audit
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit is append only');
+END;
+
+CREATE TRIGGER admin_audit_no_delete BEFORE DELETE ON admin_audit
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit is append only');
+END;
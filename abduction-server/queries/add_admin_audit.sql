@@ -0,0 +1,11 @@
+INSERT INTO admin_audit(
+    "actor",
+    "match_id",
+    "tick_id",
+    "command"
+) VALUES (
+    ?,
+    ?,
+    ?,
+    ?
+);
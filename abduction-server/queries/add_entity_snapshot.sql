@@ -0,0 +1,9 @@
+INSERT OR REPLACE INTO entity_snapshot(
+    "match_id",
+    "tick_id",
+    "entities"
+) VALUES (
+    ?,
+    ?,
+    ?
+);
@@ -0,0 +1,9 @@
+INSERT INTO match_event(
+    "match_id",
+    "tick_id",
+    "payload"
+) VALUES (
+    ?,
+    ?,
+    ?
+);
@@ -0,0 +1,7 @@
+INSERT INTO match_log(
+    "match_id",
+    "payload"
+) VALUES (
+    ?,
+    ?
+);
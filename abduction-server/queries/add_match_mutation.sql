@@ -2,10 +2,12 @@ INSERT INTO entity_mutation(
     "entity_id",
     "match_id",
     "mutation_type",
-    "payload"
+    "payload",
+    "tick_id"
 ) VALUES (
     ?,
     ?,
     ?,
+    ?,
     ?
 );
@@ -0,0 +1,23 @@
+INSERT INTO player_match_stats(
+    "match_id",
+    "entity_id",
+    "hexes_traveled",
+    "food_eaten",
+    "conversations_held",
+    "near_death_events",
+    "items_crafted"
+) VALUES (
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?,
+    ?
+)
+ON CONFLICT (match_id, entity_id) DO UPDATE SET
+    hexes_traveled = hexes_traveled + excluded.hexes_traveled,
+    food_eaten = food_eaten + excluded.food_eaten,
+    conversations_held = conversations_held + excluded.conversations_held,
+    near_death_events = near_death_events + excluded.near_death_events,
+    items_crafted = items_crafted + excluded.items_crafted;
@@ -0,0 +1,3 @@
+INSERT INTO season_matches(season_id, match_id)
+VALUES (?, ?)
+ON CONFLICT DO NOTHING;
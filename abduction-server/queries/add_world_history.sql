@@ -0,0 +1,8 @@
+INSERT INTO world_history(match_id, tick_id, day, time_of_day, weather)
+VALUES (?, ?, ?, ?, ?)
+ON CONFLICT (match_id, tick_id)
+DO UPDATE
+SET
+    day = EXCLUDED.day,
+    time_of_day = EXCLUDED.time_of_day,
+    weather = EXCLUDED.weather;
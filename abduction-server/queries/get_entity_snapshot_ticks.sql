@@ -0,0 +1,8 @@
+SELECT
+    match_id,
+    tick_id as "tick_id: i64"
+FROM
+    entity_snapshot
+ORDER BY
+    match_id,
+    tick_id;
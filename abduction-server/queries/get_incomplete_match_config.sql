@@ -7,5 +7,9 @@ SELECT match_id,
     player_count as "player_count: i32",
     preceding_match_id,
     world_radius as "world_radius: i32",
-    complete
+    complete,
+    show_script as "show_script: Json<ShowScript>",
+    ruleset as "ruleset: Json<Ruleset>",
+    started,
+    features as "features: Json<HashSet<FeatureFlag>>"
 FROM latest_match WHERE complete = false
@@ -0,0 +1,11 @@
+SELECT
+    tick_id as "tick_id: i64",
+    entities as "entities: Json<HashMap<EntityId, EntityPayload>>"
+FROM
+    entity_snapshot
+WHERE
+    match_id = ?
+    AND tick_id <= ?
+ORDER BY
+    tick_id DESC
+LIMIT 1;
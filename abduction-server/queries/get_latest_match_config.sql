@@ -0,0 +1,15 @@
+WITH latest_match as (
+    SELECT * FROM match_config
+    ORDER BY created_at DESC
+    LIMIT 1
+)
+SELECT match_id,
+    player_count as "player_count: i32",
+    preceding_match_id,
+    world_radius as "world_radius: i32",
+    complete,
+    show_script as "show_script: Json<ShowScript>",
+    ruleset as "ruleset: Json<Ruleset>",
+    started,
+    features as "features: Json<HashSet<FeatureFlag>>"
+FROM latest_match
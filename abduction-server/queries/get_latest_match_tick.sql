@@ -0,0 +1,6 @@
+SELECT
+    MAX(tick_id) as "tick_id: i64"
+FROM
+    entity_mutation
+WHERE
+    match_id = ?;
@@ -0,0 +1,11 @@
+SELECT
+    season_id,
+    number as "number: i32",
+    match_count as "match_count: i32",
+    seed_player_count as "seed_player_count: i32",
+    carry_over as "carry_over: Json<CarryOverRules>"
+FROM
+    seasons
+ORDER BY
+    number DESC
+LIMIT 1
@@ -0,0 +1,21 @@
+-- Every death in a match, with the last name the dead entity had
+SELECT
+    log.log_id as "log_id: i64",
+    log.timestamp as "timestamp: NaiveDateTime",
+    json_extract(log.payload, '$.involved_entities[0]') as "entity_id: String",
+    (
+        SELECT json_extract(mutation.payload, '$.name')
+        FROM entity_mutation mutation
+        WHERE mutation.match_id = log.match_id
+            AND mutation.entity_id = json_extract(log.payload, '$.involved_entities[0]')
+            AND mutation.payload IS NOT NULL
+        ORDER BY mutation.mutation_id DESC
+        LIMIT 1
+    ) as "name: String"
+FROM
+    match_log log
+WHERE
+    log.match_id = ?
+    AND json_extract(log.payload, '$.kind') = 'entity_death'
+ORDER BY
+    log.log_id;
@@ -0,0 +1,9 @@
+SELECT
+    tick_id,
+    payload as "payload: Json<Value>"
+FROM
+    match_event
+WHERE
+    match_id = ?
+ORDER BY
+    event_id;
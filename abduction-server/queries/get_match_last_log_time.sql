@@ -0,0 +1,6 @@
+SELECT
+    MAX(timestamp) as "timestamp: NaiveDateTime"
+FROM
+    match_log
+WHERE
+    match_id = ?;
@@ -0,0 +1,8 @@
+SELECT
+    payload as "payload: Json<Value>"
+FROM
+    match_log
+WHERE
+    match_id = ?
+ORDER BY
+    log_id;
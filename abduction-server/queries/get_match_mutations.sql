@@ -0,0 +1,11 @@
+SELECT
+    entity_id,
+    mutation_type as "mutation_type: EntityMutationType",
+    payload as "payload: Json<Option<EntityPayload>>",
+    tick_id as "tick_id: i64"
+FROM
+    entity_mutation
+WHERE
+    match_id = ?
+ORDER BY
+    mutation_id;
@@ -0,0 +1,13 @@
+SELECT
+    entity_id,
+    mutation_type as "mutation_type: EntityMutationType",
+    payload as "payload: Json<Option<EntityPayload>>",
+    tick_id as "tick_id: i64"
+FROM
+    entity_mutation
+WHERE
+    match_id = ?
+    AND tick_id > ?
+    AND tick_id <= ?
+ORDER BY
+    mutation_id;
@@ -0,0 +1,11 @@
+SELECT
+    seasons.season_id,
+    number as "number: i32",
+    match_count as "match_count: i32",
+    seed_player_count as "seed_player_count: i32",
+    carry_over as "carry_over: Json<CarryOverRules>"
+FROM
+    season_matches
+    JOIN seasons ON seasons.season_id = season_matches.season_id
+WHERE
+    season_matches.match_id = ?
@@ -0,0 +1,6 @@
+SELECT
+    starts_at as "starts_at: NaiveDateTime"
+FROM
+    match_schedule
+WHERE
+    match_id = ?;
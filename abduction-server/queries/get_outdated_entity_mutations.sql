@@ -0,0 +1,13 @@
+SELECT
+    mutation_id as "mutation_id: i64",
+    payload as "payload: Json<EntityPayload>"
+FROM
+    entity_mutation
+WHERE
+    mutation_id > ?
+    AND mutation_type = 'S'
+    AND payload IS NOT NULL
+    AND COALESCE(json_extract(payload, '$.version'), 0) < ?
+ORDER BY
+    mutation_id
+LIMIT ?;
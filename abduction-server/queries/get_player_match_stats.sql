@@ -0,0 +1,11 @@
+SELECT
+    hexes_traveled,
+    food_eaten,
+    conversations_held,
+    near_death_events,
+    items_crafted
+FROM
+    player_match_stats
+WHERE
+    match_id = ?
+    AND entity_id = ?;
@@ -0,0 +1,11 @@
+SELECT
+    match_id,
+    complete,
+    created_at as "created_at: NaiveDateTime"
+FROM
+    match_config
+WHERE
+    started = true
+ORDER BY
+    created_at DESC
+LIMIT ?;
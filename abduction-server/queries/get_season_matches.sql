@@ -0,0 +1,10 @@
+SELECT
+    season_matches.match_id,
+    match_config.complete
+FROM
+    season_matches
+    JOIN match_config ON match_config.match_id = season_matches.match_id
+WHERE
+    season_matches.season_id = ?
+ORDER BY
+    match_config.created_at ASC
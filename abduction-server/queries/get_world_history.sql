@@ -0,0 +1,11 @@
+SELECT
+    tick_id,
+    day,
+    time_of_day,
+    weather
+FROM
+    world_history
+WHERE
+    match_id = ?
+ORDER BY
+    tick_id;
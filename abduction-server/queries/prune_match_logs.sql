@@ -0,0 +1,4 @@
+DELETE FROM match_log
+WHERE
+    COALESCE(json_extract(payload, '$.tier'), 'normal') = ?
+    AND timestamp < datetime('now', ?);
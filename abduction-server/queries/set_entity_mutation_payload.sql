@@ -0,0 +1,5 @@
+UPDATE entity_mutation
+SET
+    payload = ?
+WHERE
+    mutation_id = ?;
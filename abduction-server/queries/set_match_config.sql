@@ -3,13 +3,21 @@ INSERT INTO match_config(
     player_count,
     preceding_match_id,
     world_radius,
-    complete
+    complete,
+    show_script,
+    ruleset,
+    started,
+    features
 )
-VALUES (?, ?, ?, ?, ?)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
 ON CONFLICT ("match_id")
 DO UPDATE
 SET
     player_count       = EXCLUDED.player_count,
     preceding_match_id = EXCLUDED.preceding_match_id,
     world_radius       = EXCLUDED.world_radius,
-    complete           = EXCLUDED.complete;
+    complete           = EXCLUDED.complete,
+    show_script        = EXCLUDED.show_script,
+    ruleset            = EXCLUDED.ruleset,
+    started            = EXCLUDED.started,
+    features           = EXCLUDED.features;
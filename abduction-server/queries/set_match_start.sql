@@ -0,0 +1,6 @@
+INSERT INTO match_schedule(match_id, starts_at)
+VALUES (?, ?)
+ON CONFLICT ("match_id")
+DO UPDATE
+SET
+    starts_at = EXCLUDED.starts_at;
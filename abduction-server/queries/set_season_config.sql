@@ -0,0 +1,15 @@
+INSERT INTO seasons(
+    season_id,
+    number,
+    match_count,
+    seed_player_count,
+    carry_over
+)
+VALUES (?, ?, ?, ?, ?)
+ON CONFLICT ("season_id")
+DO UPDATE
+SET
+    number            = EXCLUDED.number,
+    match_count       = EXCLUDED.match_count,
+    seed_player_count = EXCLUDED.seed_player_count,
+    carry_over        = EXCLUDED.carry_over;
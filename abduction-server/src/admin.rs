@@ -0,0 +1,167 @@
+//! Making sure operator RPC handlers are only used by operators
+//!
+//! Handlers that change a match (or anything else viewers see) take an `admin_key`, which has to be
+//! one of the keys in `ADMIN_KEYS`. That's a comma separated list of `<name>:<key>` pairs, so that
+//! whoever used a key can be put on the record (see `mtch::intervention`).
+//! With no keys set those handlers turn everyone away, leaving the console (see `command`).
+//!
+//! Everything an admin changes goes in the append only `admin_audit` table, with who did it and
+//! the match and tick it happened during (if there was one running), see `audit`.
+
+use std::{env, fmt, sync::LazyLock};
+
+use anyhow::Context;
+use tracing::warn;
+
+use crate::{
+    entity::EntityId,
+    mtch::{moderation::ModerationTag, poll::PollOption, MatchId, TickId},
+    Db, ServerCtx,
+};
+
+/// Every operator's name and key
+static ADMIN_KEYS: LazyLock<Vec<(String, String)>> = LazyLock::new(|| {
+    env::var("ADMIN_KEYS")
+        .map(|keys| parse_admin_keys(&keys))
+        .unwrap_or_default()
+});
+
+/// An operator who has shown a valid key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Admin {
+    /// Who the key belongs to
+    pub name: String,
+}
+
+impl Admin {
+    /// Find out whose key this is
+    /// (none -> it isn't anyone's)
+    pub fn authenticate(key: &str) -> Option<Self> {
+        Self::authenticate_with(&ADMIN_KEYS, key)
+    }
+
+    fn authenticate_with(keys: &[(String, String)], key: &str) -> Option<Self> {
+        keys.iter()
+            .find(|(_, admin_key)| keys_match(admin_key, key))
+            .map(|(name, _)| Admin { name: name.clone() })
+    }
+
+    /// Who this is on the record
+    pub fn actor(&self) -> String {
+        format!("admin:{}", self.name)
+    }
+
+    /// Put something this admin did on the record
+    /// (it's already been done by now, so failing to record it is only worth a warning)
+    pub async fn record(&self, ctx: &ServerCtx, action: AdminAction) {
+        if let Err(err) = audit(ctx, &self.actor(), &action.to_string()).await {
+            warn!("Failed to audit {action} by {}: {err:?}", self.name);
+        }
+    }
+}
+
+/// Something an admin did through an RPC handler of its own (rather than a `Command`)
+#[derive(Debug)]
+pub enum AdminAction {
+    ReloadContent,
+    ExportMatch {
+        match_id: MatchId,
+    },
+    StartPoll {
+        options: Vec<PollOption>,
+    },
+    TagEntity {
+        entity_id: EntityId,
+        tag: ModerationTag,
+    },
+    UntagEntity {
+        entity_id: EntityId,
+    },
+    BlockPhrase {
+        phrase: String,
+    },
+}
+
+impl fmt::Display for AdminAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdminAction::ReloadContent => write!(f, "reload content"),
+            AdminAction::ExportMatch { match_id } => write!(f, "export {match_id}"),
+            AdminAction::StartPoll { options } => write!(f, "start poll {options:?}"),
+            AdminAction::TagEntity { entity_id, tag } => write!(f, "tag {entity_id} as {tag:?}"),
+            AdminAction::UntagEntity { entity_id } => write!(f, "untag {entity_id}"),
+            AdminAction::BlockPhrase { phrase } => write!(f, "block phrase {phrase:?}"),
+        }
+    }
+}
+
+/// Put something an admin did on the record, against whatever match is running
+pub async fn audit(ctx: &ServerCtx, actor: &str, command: &str) -> anyhow::Result<()> {
+    let snapshot = ctx.current_match.snapshot();
+    let during = snapshot
+        .as_ref()
+        .map(|snapshot| (&snapshot.config.match_id, snapshot.tick_id));
+    audit_during(&ctx.db, actor, during, command).await
+}
+
+/// Put something an admin did on the record, against some match and tick (if any)
+pub async fn audit_during(
+    db: &Db,
+    actor: &str,
+    during: Option<(&MatchId, TickId)>,
+    command: &str,
+) -> anyhow::Result<()> {
+    let match_id = during.map(|(match_id, _)| match_id);
+    let tick_id = during.map(|(_, tick_id)| tick_id as i64);
+    sqlx::query_file!(
+        "queries/add_admin_audit.sql",
+        actor,
+        match_id,
+        tick_id,
+        command,
+    )
+    .execute(db)
+    .await
+    .context("Failed to record admin action")?;
+    Ok(())
+}
+
+/// Read `<name>:<key>` pairs, skipping any that are missing either
+fn parse_admin_keys(keys: &str) -> Vec<(String, String)> {
+    keys.split(',')
+        .filter_map(|pair| {
+            let (name, key) = pair.split_once(':')?;
+            let (name, key) = (name.trim(), key.trim());
+            (!name.is_empty() && !key.is_empty()).then(|| (name.to_owned(), key.to_owned()))
+        })
+        .collect()
+}
+
+/// Compare two keys, without stopping at the first difference
+/// (so how long it takes doesn't give away how much of a key was right)
+fn keys_match(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_only_known_keys_authenticate() {
+        let keys = parse_admin_keys("alice:open-sesame, bob : hunter2,nobody:,:orphan");
+        assert_eq!(keys.len(), 2);
+
+        assert_eq!(
+            Admin::authenticate_with(&keys, "hunter2"),
+            Some(Admin { name: "bob".into() })
+        );
+        assert_eq!(Admin::authenticate_with(&keys, "open-sesam"), None);
+        assert_eq!(Admin::authenticate_with(&keys, ""), None);
+        assert_eq!(Admin::authenticate_with(&[], "hunter2"), None);
+    }
+}
@@ -0,0 +1,83 @@
+//! Everything that decides how the simulation is balanced, in one place
+//!
+//! The manifest is the loaded behaviour config along with the fixed definitions it works with (how
+//! sensitive motivators can be, how likely each characteristic strength is to succeed). It's served
+//! to the site and any analysis tools so they always reflect the live tuning, and its hash is logged
+//! when a match starts so a replay can check it's being run with the same balance.
+
+use serde::Serialize;
+use strum::{IntoEnumIterator, VariantArray};
+
+use crate::{
+    behavior::{behavior, BehaviorConfig},
+    entity::brain::{
+        characteristic::{Characteristic, CharacteristicStrength},
+        motivator::{MotivatorKey, SENSITIVITY_RANGE},
+    },
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct BalanceManifest {
+    /// The currently loaded behaviour config
+    pub behavior: BehaviorConfig,
+
+    /// Every motivator an entity can have
+    pub motivators: Vec<MotivatorKey>,
+
+    /// The lowest and highest sensitivity a motivator can start with
+    pub sensitivity_range: (f32, f32),
+
+    /// Every characteristic, and whether it's influenced by age
+    pub characteristics: Vec<(Characteristic, bool)>,
+
+    /// The rough chance of succeeding at something with each characteristic strength
+    pub success_chances: Vec<(CharacteristicStrength, f64)>,
+}
+
+impl BalanceManifest {
+    /// The balance as it currently is
+    pub fn current() -> Self {
+        use CharacteristicStrength::*;
+        Self {
+            behavior: (*behavior()).clone(),
+            motivators: MotivatorKey::VARIANTS.to_vec(),
+            sensitivity_range: SENSITIVITY_RANGE,
+            characteristics: Characteristic::iter()
+                .map(|characteristic| (characteristic, characteristic.influenced_by_age()))
+                .collect(),
+            success_chances: [Low, Average, High]
+                .map(|strength| (strength, strength.success_chance()))
+                .to_vec(),
+        }
+    }
+
+    /// A short fingerprint of the manifest, which only changes when the balance does
+    pub fn hash(&self) -> String {
+        // (json objects come out with their keys sorted, so the same balance always hashes the same)
+        let json = serde_json::to_value(self)
+            .expect("Balance manifest is always valid json")
+            .to_string();
+
+        // FNV-1a
+        let hash = json.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+        format!("{hash:016x}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hash_follows_balance() {
+        let manifest = BalanceManifest::current();
+        assert_eq!(manifest.hash(), BalanceManifest::current().hash());
+
+        let mut tweaked = manifest.clone();
+        tweaked.behavior.sizing.hexes_per_player += 1.0;
+        assert_ne!(manifest.hash(), tweaked.hash());
+    }
+}
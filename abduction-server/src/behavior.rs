@@ -0,0 +1,163 @@
+//! Tuning for how entities behave that can be tweaked without a rebuild
+//!
+//! Loaded from a json file on startup and again whenever content is reloaded,
+//! anything missing from the file falls back to what's compiled in.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::{
+    entity::brain::motivator::{MotivatorKey, MotivatorTable},
+    location::Biome,
+    mtch::{
+        sizing::MatchSizing,
+        wildcard::{default_wildcard_tables, WildcardChance},
+    },
+};
+
+#[cfg(test)]
+static BEHAVIOR_PATH: LazyLock<PathBuf> = LazyLock::new(|| "content/behavior.json".into());
+
+#[cfg(not(test))]
+static BEHAVIOR_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    std::env::var("BEHAVIOR_PATH")
+        .unwrap_or("content/behavior.json".into())
+        .into()
+});
+
+/// The currently loaded behaviour config
+static BEHAVIOR: LazyLock<RwLock<Arc<BehaviorConfig>>> = LazyLock::new(|| {
+    RwLock::new(Arc::new(BehaviorConfig::load().unwrap_or_else(|err| {
+        warn!("Falling back to built-in behaviour config: {err:?}");
+        BehaviorConfig::default()
+    })))
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+#[serde(default)]
+pub struct BehaviorConfig {
+    /// How each motivator drifts on its own every tick
+    /// (motivators that aren't listed stay put)
+    pub homeostasis: HashMap<MotivatorKey, Homeostasis>,
+
+    /// Rare events that could happen each tick in each biome (see `mtch::wildcard`)
+    pub wildcards: HashMap<Biome, Vec<WildcardChance>>,
+
+    /// How big to make the world for however many players (see `mtch::sizing`)
+    pub sizing: MatchSizing,
+}
+
+/// How a motivator drifts towards some baseline over time
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct Homeostasis {
+    /// Motivation this drifts towards (0-1)
+    pub baseline: f32,
+
+    /// How far it drifts each tick, as a multiple of the motivator's sensitivity
+    pub rate: f32,
+
+    /// Only drift while the entity isn't doing anything
+    #[serde(default)]
+    pub idle_only: bool,
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        use MotivatorKey::*;
+        let towards = |baseline, rate| Homeostasis {
+            baseline,
+            rate,
+            idle_only: false,
+        };
+        Self {
+            homeostasis: HashMap::from([
+                (Hunger, towards(1.0, 0.01)),
+                (Thirst, towards(1.0, 0.01)),
+                (Tiredness, towards(1.0, 0.005)),
+                (Sadness, towards(0.0, 0.05)),
+                (
+                    Boredom,
+                    Homeostasis {
+                        idle_only: true,
+                        ..towards(1.0, 2.0)
+                    },
+                ),
+            ]),
+            wildcards: default_wildcard_tables().into_iter().collect(),
+            sizing: MatchSizing::default(),
+        }
+    }
+}
+
+impl BehaviorConfig {
+    fn load() -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(&*BEHAVIOR_PATH)
+            .context(format!("Reading behaviour from {:?}", &*BEHAVIOR_PATH))?;
+        serde_json::from_str(&contents).context("Parsing behaviour")
+    }
+}
+
+impl MotivatorTable {
+    /// Drift every motivator towards its baseline by one tick's worth
+    /// (`rate_scale` speeds up/slows down every drift, see `Ruleset::motivator_rate`)
+    pub fn apply_homeostasis(&mut self, config: &BehaviorConfig, rate_scale: f32, idle: bool) {
+        for (key, homeostasis) in &config.homeostasis {
+            if homeostasis.idle_only && !idle {
+                continue;
+            }
+            self.drift_key(*key, homeostasis.baseline, homeostasis.rate * rate_scale);
+        }
+    }
+}
+
+/// Get the currently loaded behaviour config
+pub fn behavior() -> Arc<BehaviorConfig> {
+    BEHAVIOR.read().unwrap().clone()
+}
+
+/// Load the behaviour config from disk again, replacing the current one
+/// (if it fails to load, the current config is kept)
+pub fn reload_behavior() -> anyhow::Result<()> {
+    let config = BehaviorConfig::load()?;
+    info!(
+        "Reloaded behaviour config ({} homeostasis rates)",
+        config.homeostasis.len()
+    );
+    *BEHAVIOR.write().unwrap() = Arc::new(config);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::entity::brain::motivator;
+
+    use super::*;
+
+    #[test]
+    fn test_homeostasis_drifts_to_baseline() {
+        let config = BehaviorConfig::load().unwrap();
+        let mut motivators = MotivatorTable::initialise();
+        motivators.bump_scaled::<motivator::Sadness>(1000.0);
+        motivators.clear::<motivator::Boredom>();
+
+        for _ in 0..10_000 {
+            motivators.apply_homeostasis(&config, 1.0, false);
+        }
+        assert_eq!(motivators.get_motivation::<motivator::Sadness>(), Some(0.0));
+        assert_eq!(motivators.get_motivation::<motivator::Boredom>(), Some(0.0));
+        assert_eq!(motivators.get_motivation::<motivator::Hunger>(), Some(1.0));
+
+        motivators.apply_homeostasis(&config, 1.0, true);
+        assert!(motivators.get_motivation::<motivator::Boredom>().unwrap() > 0.0);
+    }
+}
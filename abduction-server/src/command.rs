@@ -1,52 +1,371 @@
-//! Provide commands (just via stdin for now) to administrate the game
+//! Provide commands to administrate the game
+//!
+//! Commands can come in over stdin, the operator console (a unix socket, see `serve_console`)
+//! or the `admin_run_command` RPC handler (with an admin key, see `admin`). They all go through
+//! `Command::parse` and `Command::handle`, so anything you can do from one you can do from the others,
+//! except importing matches, which reads from the server's disk and so is only allowed locally.
+//!
+//! Anything that changes the running match is an intervention, and goes on the record along with
+//! who it came from (see `mtch::intervention`), as does everything else (see `admin::audit`).
+//!
+//! e.g
+//! - `spawn prop supply_drop at 2,-1`
+//! - `set motivator <entity> hunger 0.9`
+//! - `kill <entity>`
+//! - `weather lightning_storm`
 
-use std::{str::FromStr, sync::atomic};
+use std::{path::Path, str::FromStr, sync::atomic};
 
-use tokio::io::{self, AsyncBufReadExt, BufReader};
-use tracing::info;
+use anyhow::{anyhow, bail, Context};
+use futures::FutureExt;
+use tokio::{
+    io::{self, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::UnixListener,
+};
+use tracing::{info, warn};
 
-use crate::ServerCtx;
+use crate::{
+    admin::{self, Admin},
+    behavior::reload_behavior,
+    content::reload_content,
+    entity::{brain::motivator::MotivatorKey, generate::PropGenerator, world::WeatherKind},
+    hex::AxialHex,
+    logs::GameLog,
+    mtch::{archive::MatchArchive, intervention::Intervention, MatchManager},
+    ServerCtx,
+};
+
+/// Where a command came from
+/// (recorded as the actor behind it, see `actor`)
+#[derive(Debug, Clone)]
+pub enum CommandSource {
+    /// Standard input of the server itself
+    Stdin,
+
+    /// The operator console
+    Console,
+
+    /// The `admin_run_command` RPC handler, with whichever admin's key was used
+    Rpc(Admin),
+}
+
+impl CommandSource {
+    /// Who to put on the record for a command from here
+    pub fn actor(&self) -> String {
+        match self {
+            CommandSource::Stdin => "stdin".to_owned(),
+            CommandSource::Console => "console".to_owned(),
+            CommandSource::Rpc(admin) => admin.actor(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, strum::AsRefStr, strum::EnumString)]
 pub enum Command {
     #[strum(serialize = "end match", serialize = "end")]
     EndMatch,
+
+    /// Reload flavour content (barks etc) and behaviour config from disk
+    #[strum(serialize = "reload content", serialize = "reload")]
+    ReloadContent,
+
+    /// Import a match from an archive on disk
+    /// i.e `import data/archives/<match_id>.json.gz`
+    #[strum(disabled)]
+    ImportMatch { path: String },
+
+    /// Put a new prop down in the current match
+    /// i.e `spawn prop <kind> at <q,r>`
+    #[strum(disabled)]
+    SpawnProp { prop: PropGenerator, hex: AxialHex },
+
+    /// Set one of an entity's motivators in the current match
+    /// i.e `set motivator <entity> <motivator> <0-1>`
+    #[strum(disabled)]
+    SetMotivator {
+        entity: String,
+        key: MotivatorKey,
+        motivation: f32,
+    },
+
+    /// Kill an entity in the current match (they die the next time they act)
+    /// i.e `kill <entity>`
+    #[strum(disabled)]
+    Kill { entity: String },
+
+    /// Change the weather in the current match
+    /// i.e `weather <kind>`
+    #[strum(disabled)]
+    SetWeather { weather: WeatherKind },
 }
 
 impl Command {
-    pub async fn handle(&self, ctx: ServerCtx) -> anyhow::Result<()> {
+    /// Parse a command (and its arguments) from a line of input
+    /// (entities can be given by id or by name)
+    pub fn parse(line: &str) -> anyhow::Result<Self> {
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("import ") {
+            return Ok(Command::ImportMatch {
+                path: path.trim().to_owned(),
+            });
+        }
+
+        if let Some(args) = line.strip_prefix("spawn prop ") {
+            let (prop, hex) = args
+                .split_once(" at ")
+                .ok_or(anyhow!("Expected `spawn prop <kind> at <q,r>`"))?;
+            return Ok(Command::SpawnProp {
+                prop: PropGenerator::from_str(prop.trim())
+                    .with_context(|| format!("No such prop {prop}"))?,
+                hex: hex.trim().parse()?,
+            });
+        }
+
+        if let Some(args) = line.strip_prefix("set motivator ") {
+            // (names have spaces in them, so work from the end)
+            let mut parts = args.rsplitn(3, ' ');
+            let (Some(motivation), Some(key), Some(entity)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                bail!("Expected `set motivator <entity> <motivator> <0-1>`");
+            };
+            let motivation: f32 = motivation.parse()?;
+            if !motivation.is_finite() {
+                bail!("Motivation must be a number from 0 to 1");
+            }
+            return Ok(Command::SetMotivator {
+                entity: entity.trim().to_owned(),
+                key: MotivatorKey::from_str(key)
+                    .with_context(|| format!("No such motivator {key}"))?,
+                motivation,
+            });
+        }
+
+        if let Some(entity) = line.strip_prefix("kill ") {
+            return Ok(Command::Kill {
+                entity: entity.trim().to_owned(),
+            });
+        }
+
+        if let Some(weather) = line.strip_prefix("weather ") {
+            return Ok(Command::SetWeather {
+                weather: WeatherKind::from_str(weather.trim())
+                    .with_context(|| format!("No such weather {weather}"))?,
+            });
+        }
+
+        Command::from_str(line).map_err(|_| anyhow!("No such command"))
+    }
+
+    /// Carry out the command
+    /// (returns a short description of what happened, for whoever asked)
+    pub async fn handle(&self, ctx: ServerCtx, source: CommandSource) -> anyhow::Result<String> {
         match self {
             Command::EndMatch => {
                 info!("Match will end after next tick");
                 ctx.flags
                     .force_end_match
                     .store(true, atomic::Ordering::Relaxed);
+                admin::audit(&ctx, &source.actor(), &format!("{self:?}")).await?;
+                Ok("Match will end after next tick".to_owned())
+            }
+            Command::ReloadContent => {
+                reload_content().context("Failed to reload content")?;
+                reload_behavior().context("Failed to reload behaviour config")?;
+                admin::audit(&ctx, &source.actor(), &format!("{self:?}")).await?;
+                Ok("Reloaded content".to_owned())
+            }
+            Command::ImportMatch { path } => {
+                if matches!(source, CommandSource::Rpc(_)) {
+                    bail!("Matches can only be imported from the console");
+                }
+                info!("Importing match from {path}");
+                MatchArchive::import(path, &ctx.db)
+                    .await
+                    .context("Failed to import match")?;
+                admin::audit(&ctx, &source.actor(), &format!("{self:?}")).await?;
+                Ok(format!("Imported {path}"))
+            }
+            Command::SpawnProp { prop, hex } => {
+                let (prop, hex) = (*prop, *hex);
+                let entity_id = self
+                    .intervene(&ctx, &source, move |mm| {
+                        let entity_id = mm.spawn_prop(prop, hex)?;
+                        let entity = mm.entities.get_entity(&entity_id);
+                        Ok((entity_id, Intervention::SpawnProp.log(entity.as_ref())))
+                    })
+                    .await?;
+                Ok(format!("Spawned {prop:?} ({entity_id}) at {hex}"))
+            }
+            Command::SetMotivator {
+                entity,
+                key,
+                motivation,
+            } => {
+                let (name, key, motivation) = (entity.clone(), *key, *motivation);
+                self.intervene(&ctx, &source, move |mm| {
+                    let entity = mm.find_entity(&name).ok_or(anyhow!("No entity {name}"))?;
+                    mm.set_motivation(&entity.entity_id, key, motivation)?;
+                    let intervention = Intervention::SetMotivator {
+                        motivator: key,
+                        motivation,
+                    };
+                    Ok(((), intervention.log(Some(&entity))))
+                })
+                .await?;
+                Ok(format!("Set {key:?} of {entity} to {motivation}"))
+            }
+            Command::Kill { entity } => {
+                let name = entity.clone();
+                self.intervene(&ctx, &source, move |mm| {
+                    let entity = mm.find_entity(&name).ok_or(anyhow!("No entity {name}"))?;
+                    mm.kill_entity(&entity.entity_id)?;
+                    Ok(((), Intervention::Kill.log(Some(&entity))))
+                })
+                .await?;
+                Ok(format!("{entity} will die the next time they act"))
+            }
+            Command::SetWeather { weather } => {
+                let (new_weather, weather_ctx) = (weather.clone(), ctx.clone());
+                self.intervene(&ctx, &source, move |mm| {
+                    mm.set_weather(new_weather.clone(), &weather_ctx)?;
+                    let intervention = Intervention::SetWeather {
+                        weather: new_weather,
+                    };
+                    Ok(((), intervention.log(None)))
+                })
+                .await?;
+                Ok(format!("Weather is now {weather:?}"))
             }
         }
+    }
 
-        Ok(())
+    /// Do something to the match that is currently running (if there is one)
+    /// and put it on the record (with the log for viewers `f` gives back)
+    async fn intervene<T: Send + 'static>(
+        &self,
+        ctx: &ServerCtx,
+        source: &CommandSource,
+        f: impl FnOnce(&mut MatchManager) -> anyhow::Result<(T, GameLog)> + Send + 'static,
+    ) -> anyhow::Result<T> {
+        info!("Running {self:?} from {source:?}");
+        let (command, source) = (self.clone(), source.clone());
+        let ctx = ctx.clone();
+        ctx.current_match
+            .clone()
+            .run(move |mm| {
+                async move {
+                    let (value, log) = f(mm)?;
+                    mm.record_intervention(&source, &command, log, &ctx).await?;
+                    // (so queries see what changed straight away)
+                    ctx.current_match.publish(mm);
+                    Ok(value)
+                }
+                .boxed()
+            })
+            .await
+            .ok_or(anyhow!("No match is running"))?
     }
 }
 
+/// Run a line of input as a command, writing what happened out
+async fn run_command_line(
+    line: &str,
+    ctx: &ServerCtx,
+    source: CommandSource,
+    out: &mut (impl AsyncWrite + Unpin),
+) -> io::Result<()> {
+    let reply = match Command::parse(line) {
+        Ok(command) => match command.handle(ctx.clone(), source).await {
+            Ok(reply) => reply,
+            Err(err) => {
+                warn!("Command failed: {err:?}");
+                format!("Error: {err:#}")
+            }
+        },
+        Err(err) => format!("{err:#}"),
+    };
+    out.write_all(format!("{reply}\n").as_bytes()).await?;
+    out.flush().await
+}
+
 pub async fn process_stdin_commands(ctx: ServerCtx) -> anyhow::Result<()> {
     let stdin = io::stdin();
     let reader = BufReader::new(stdin);
     let mut lines = reader.lines();
+    let mut stderr = io::stderr();
 
     while let Some(line) = lines.next_line().await? {
         if line.trim().is_empty() {
             continue;
         }
+        run_command_line(&line, &ctx, CommandSource::Stdin, &mut stderr).await?;
+    }
 
-        match Command::from_str(&line) {
-            Ok(command) => {
-                command.handle(ctx.clone()).await?;
-            }
-            Err(_) => {
-                eprintln!("No such command");
+    Ok(())
+}
+
+/// Accept operator console connections on a unix socket
+/// (e.g `socat - UNIX-CONNECT:<path>`), each taking commands a line at a time
+pub async fn serve_console(ctx: ServerCtx, path: impl AsRef<Path>) -> anyhow::Result<()> {
+    let path = path.as_ref();
+
+    // Clear out the socket from last time
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    info!("Operator console listening at {}", path.display());
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if let Err(err) =
+                    run_command_line(&line, &ctx, CommandSource::Console, &mut writer).await
+                {
+                    warn!("Operator console disconnected: {err}");
+                    break;
+                }
             }
-        }
+        });
     }
+}
 
-    Ok(())
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert!(matches!(Command::parse("end"), Ok(Command::EndMatch)));
+        assert!(matches!(
+            Command::parse("spawn prop supply_drop at 2,-1"),
+            Ok(Command::SpawnProp {
+                prop: PropGenerator::SupplyDrop,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Command::parse("set motivator John Smith hunger 0.9"),
+            Ok(Command::SetMotivator { entity, key: MotivatorKey::Hunger, motivation })
+                if entity == "John Smith" && motivation == 0.9
+        ));
+        assert!(matches!(
+            Command::parse("weather lightning_storm"),
+            Ok(Command::SetWeather {
+                weather: WeatherKind::LightningStorm
+            })
+        ));
+        assert!(Command::parse("set motivator John Smith hunger NaN").is_err());
+        assert!(Command::parse("set motivator John Smith hunger inf").is_err());
+        assert!(Command::parse("weather frogs").is_err());
+        assert!(Command::parse("spawn prop supply_drop").is_err());
+    }
 }
@@ -0,0 +1,177 @@
+//! Flavour text that writers can iterate on without a rebuild
+//!
+//! Loaded from a json file on startup and again whenever it's reloaded (see `admin_reload_content`),
+//! anything missing from the file falls back to what's compiled in.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use anyhow::Context;
+use rand::seq::IndexedRandom;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::entity::{
+    background::{fear::Fear, hope::Hope},
+    brain::{bark::BarkVariant, motivator::MotivatorKey},
+    generate::background::{EYE_COLOR_WEIGHTS, HAIR_COLOR_WEIGHTS},
+};
+
+#[cfg(test)]
+static CONTENT_PATH: LazyLock<PathBuf> = LazyLock::new(|| "content/flavour.json".into());
+
+#[cfg(not(test))]
+static CONTENT_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    std::env::var("CONTENT_PATH")
+        .unwrap_or("content/flavour.json".into())
+        .into()
+});
+
+/// The currently loaded content
+static CONTENT: LazyLock<RwLock<Arc<Content>>> = LazyLock::new(|| {
+    RwLock::new(Arc::new(Content::load().unwrap_or_else(|err| {
+        warn!("Falling back to built-in content: {err:?}");
+        Content::default()
+    })))
+});
+
+/// Above this motivation, barks use the severe lines
+pub const SEVERE_BARK_MOTIVATION: f32 = 0.75;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Content {
+    /// Lines entities say (or do) when a motivator is high
+    /// `{name}` is replaced with the entities name when shown
+    pub barks: HashMap<MotivatorKey, BarkLines>,
+
+    /// How fears are phrased, keyed by variant (i.e "Spiders")
+    /// otherwise uses the built-in phrasing
+    pub fears: HashMap<String, String>,
+
+    /// How hopes are phrased, keyed by variant (i.e "PeaceOfMind")
+    /// otherwise uses the built-in phrasing
+    pub hopes: HashMap<String, String>,
+
+    /// Weighted eye colours for generated players
+    pub eye_colours: Vec<(usize, String)>,
+
+    /// Weighted hair colours for generated players
+    pub hair_colours: Vec<(usize, String)>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BarkLines {
+    pub moderate: Vec<String>,
+    pub severe: Vec<String>,
+
+    /// Lines for players whose barks have some flavour (see `brain::bark`), however bad it is
+    pub variants: HashMap<BarkVariant, Vec<String>>,
+}
+
+impl Content {
+    fn load() -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(&*CONTENT_PATH)
+            .context(format!("Reading content from {:?}", &*CONTENT_PATH))?;
+        serde_json::from_str(&contents).context("Parsing content")
+    }
+
+    /// Pick a bark line for some motivator (with some flavour, if given)
+    pub fn bark_line(
+        &self,
+        motivator: MotivatorKey,
+        motivation: f32,
+        variant: Option<BarkVariant>,
+    ) -> Option<String> {
+        let lines = self.barks.get(&motivator)?;
+        let lines = if let Some(variant_lines) = variant.and_then(|v| lines.variants.get(&v)) {
+            variant_lines
+        } else if motivation > SEVERE_BARK_MOTIVATION {
+            &lines.severe
+        } else {
+            &lines.moderate
+        };
+        lines.choose(&mut crate::rng::rng()).cloned()
+    }
+
+    /// Whether there's anything to say about some motivator with some flavour
+    pub fn has_bark_variant(&self, motivator: MotivatorKey, variant: BarkVariant) -> bool {
+        self.barks
+            .get(&motivator)
+            .and_then(|lines| lines.variants.get(&variant))
+            .is_some_and(|lines| !lines.is_empty())
+    }
+
+    pub fn fear_text(&self, fear: &Fear) -> String {
+        self.fears
+            .get(&format!("{fear:?}"))
+            .cloned()
+            .unwrap_or_else(|| fear.to_string())
+    }
+
+    pub fn hope_text(&self, hope: &Hope) -> String {
+        self.hopes
+            .get(&format!("{hope:?}"))
+            .cloned()
+            .unwrap_or_else(|| hope.to_string())
+    }
+
+    pub fn eye_colour_weights(&self) -> Vec<(usize, String)> {
+        weights_or_default(&self.eye_colours, EYE_COLOR_WEIGHTS)
+    }
+
+    pub fn hair_colour_weights(&self) -> Vec<(usize, String)> {
+        weights_or_default(&self.hair_colours, HAIR_COLOR_WEIGHTS)
+    }
+}
+
+fn weights_or_default(
+    weights: &[(usize, String)],
+    default: &[(usize, &str)],
+) -> Vec<(usize, String)> {
+    if weights.is_empty() {
+        default.iter().map(|(w, v)| (*w, v.to_string())).collect()
+    } else {
+        weights.to_vec()
+    }
+}
+
+/// Get the currently loaded content
+pub fn content() -> Arc<Content> {
+    CONTENT.read().unwrap().clone()
+}
+
+/// Load the content from disk again, replacing the current content
+/// (if it fails to load, the current content is kept)
+pub fn reload_content() -> anyhow::Result<()> {
+    let content = Content::load()?;
+    info!(
+        "Reloaded content ({} bark motivators, {} fears, {} hopes)",
+        content.barks.len(),
+        content.fears.len(),
+        content.hopes.len()
+    );
+    *CONTENT.write().unwrap() = Arc::new(content);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use strum::VariantArray;
+
+    use super::*;
+
+    #[test]
+    fn test_content_has_every_bark() {
+        let content = Content::load().unwrap();
+        for key in MotivatorKey::VARIANTS {
+            let lines = content.barks.get(key).expect("Missing barks for motivator");
+            assert!(!lines.moderate.is_empty() && !lines.severe.is_empty());
+        }
+    }
+}
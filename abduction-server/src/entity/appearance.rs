@@ -0,0 +1,216 @@
+//! How entities look, for the site to draw them
+//!
+//! The server never sends images, only a few numbers describing an entity's look that the site
+//! turns into a sprite. Everything but the accessories is derived from a seed drawn when the entity
+//! is generated, so it always looks the same. What a player might be wearing or carrying comes from
+//! their background (a chef in a chef's hat, a retiree with a walking stick...), and only falls
+//! back to the seed if nothing about them calls for anything in particular.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::entity::background::EntityBackground;
+
+/// How many body shapes the site has to pick from
+pub const BODY_SHAPE_COUNT: u8 = 6;
+
+/// How many patterns the site has to pick from
+pub const PATTERN_COUNT: u8 = 8;
+
+/// Chance of wearing something anyway if nothing about someone calls for it
+const INCIDENTAL_ACCESSORY_CHANCE: f64 = 0.2;
+
+/// How some entity looks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct EntityAppearance {
+    /// What the rest was derived from
+    pub seed: u32,
+
+    /// Which body shape to draw (< `BODY_SHAPE_COUNT`)
+    pub body_shape: u8,
+
+    /// Which pattern to draw it with (< `PATTERN_COUNT`)
+    pub pattern: u8,
+
+    /// Whatever is worn on the head
+    pub head: Option<Accessory>,
+
+    /// Whatever is worn over the body
+    pub body: Option<Accessory>,
+
+    /// Whatever is carried around
+    pub held: Option<Accessory>,
+}
+
+/// Something drawn on top of an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[qubit::ts]
+pub enum Accessory {
+    // == Head ==
+    Glasses,
+    Hat,
+    ChefsHat,
+    Helmet,
+    Headphones,
+
+    // == Body ==
+    Apron,
+    Badge,
+    Scarf,
+
+    // == Held ==
+    WalkingStick,
+    Camera,
+    Notebook,
+    Clipboard,
+}
+
+/// What people in careers (whose variant names contain some word) tend to wear, in order of precedence
+const HEAD_BY_CAREER: &[(&str, Accessory)] = &[
+    ("Chef", Accessory::ChefsHat),
+    ("Baker", Accessory::ChefsHat),
+    ("Firefighter", Accessory::Helmet),
+    ("Pilot", Accessory::Helmet),
+    ("Astronaut", Accessory::Helmet),
+    ("Mountaineering", Accessory::Helmet),
+    ("Military", Accessory::Helmet),
+    ("Music", Accessory::Headphones),
+    ("DiskJockey", Accessory::Headphones),
+    ("Podcast", Accessory::Headphones),
+    ("Radio", Accessory::Headphones),
+    ("Streamer", Accessory::Headphones),
+    ("Engineer", Accessory::Glasses),
+    ("Developer", Accessory::Glasses),
+    ("Scientist", Accessory::Glasses),
+    ("Researcher", Accessory::Glasses),
+    ("Analyst", Accessory::Glasses),
+    ("Professor", Accessory::Glasses),
+    ("Ranger", Accessory::Hat),
+    ("Guide", Accessory::Hat),
+];
+
+const BODY_BY_CAREER: &[(&str, Accessory)] = &[
+    ("Chef", Accessory::Apron),
+    ("Baker", Accessory::Apron),
+    ("Barista", Accessory::Apron),
+    ("smith", Accessory::Apron),
+    ("Woodworker", Accessory::Apron),
+    ("Potter", Accessory::Apron),
+    ("Glassblower", Accessory::Apron),
+    ("Police", Accessory::Badge),
+    ("Detective", Accessory::Badge),
+    ("Security", Accessory::Badge),
+    ("Officer", Accessory::Badge),
+];
+
+const HELD_BY_CAREER: &[(&str, Accessory)] = &[
+    ("Photographer", Accessory::Camera),
+    ("Cinematographer", Accessory::Camera),
+    ("Film", Accessory::Camera),
+    ("YouTuber", Accessory::Camera),
+    ("Writer", Accessory::Notebook),
+    ("Journalist", Accessory::Notebook),
+    ("Author", Accessory::Notebook),
+    ("Poet", Accessory::Notebook),
+    ("Editor", Accessory::Notebook),
+    ("Manager", Accessory::Clipboard),
+    ("Planner", Accessory::Clipboard),
+    ("Coordinator", Accessory::Clipboard),
+    ("Auditor", Accessory::Clipboard),
+];
+
+/// What someone in some career tends to have in some slot
+fn accessory_for_career(career: &str, by_career: &[(&str, Accessory)]) -> Option<Accessory> {
+    by_career
+        .iter()
+        .find(|(word, _)| career.contains(word))
+        .map(|(_, accessory)| *accessory)
+}
+
+impl EntityAppearance {
+    /// A new look, for someone with some background (or something without one)
+    pub fn random(rng: &mut impl Rng, background: Option<&EntityBackground>) -> Self {
+        Self::from_seed(rng.random(), background)
+    }
+
+    /// The look some seed gives, for someone with some background (or something without one)
+    pub fn from_seed(seed: u32, background: Option<&EntityBackground>) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed as u64);
+        let body_shape = rng.random_range(0..BODY_SHAPE_COUNT);
+        let pattern = rng.random_range(0..PATTERN_COUNT);
+
+        let Some(background) = background else {
+            return Self {
+                seed,
+                body_shape,
+                pattern,
+                head: None,
+                body: None,
+                held: None,
+            };
+        };
+
+        // NOTE: matched on the variant name (e.g `SousChef`) rather than the snake case id
+        let career = format!("{:?}", background.career);
+        let mut incidental = |accessory| {
+            rng.random_bool(INCIDENTAL_ACCESSORY_CHANCE)
+                .then_some(accessory)
+        };
+        let head =
+            accessory_for_career(&career, HEAD_BY_CAREER).or_else(|| incidental(Accessory::Hat));
+        let body =
+            accessory_for_career(&career, BODY_BY_CAREER).or_else(|| incidental(Accessory::Scarf));
+        let held = if background.is_retired {
+            Some(Accessory::WalkingStick)
+        } else {
+            accessory_for_career(&career, HELD_BY_CAREER)
+        };
+
+        Self {
+            seed,
+            body_shape,
+            pattern,
+            head,
+            body,
+            held,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strum::VariantArray;
+
+    use super::*;
+    use crate::entity::background::{career::Career, fear::Fear, hope::Hope};
+
+    #[test]
+    fn test_appearance_follows_from_seed_and_background() {
+        let mut background = EntityBackground {
+            country_name: "Australia".into(),
+            city_name: "Melbourne".into(),
+            career: Career::SousChef,
+            is_retired: false,
+            eye_colour: "brown".into(),
+            hair_colour: "black".into(),
+            fear: Fear::VARIANTS[0].clone(),
+            hope: Hope::VARIANTS[0].clone(),
+        };
+        let appearance = EntityAppearance::random(&mut crate::rng::rng(), Some(&background));
+        assert_eq!(
+            EntityAppearance::from_seed(appearance.seed, Some(&background)),
+            appearance
+        );
+        assert!(appearance.body_shape < BODY_SHAPE_COUNT);
+        assert!(appearance.pattern < PATTERN_COUNT);
+        assert_eq!(appearance.head, Some(Accessory::ChefsHat));
+        assert_eq!(appearance.body, Some(Accessory::Apron));
+
+        background.is_retired = true;
+        let retired = EntityAppearance::from_seed(appearance.seed, Some(&background));
+        assert_eq!(retired.held, Some(Accessory::WalkingStick));
+        assert_eq!(retired.body_shape, appearance.body_shape);
+    }
+}
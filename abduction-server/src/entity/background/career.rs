@@ -1,11 +1,15 @@
 use std::fmt;
 
-use serde::{de::Visitor, Deserialize, Serialize};
-use strum::VariantArray;
+use serde::{Deserialize, Serialize};
 
+use super::BackgroundDetail;
+
+/// NOTE: this is stored by its id (see `BackgroundDetail`)
 #[derive(Debug, Clone, strum::VariantArray, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
 #[allow(clippy::enum_variant_names)]
 #[qubit::ts]
+#[ts(as = "String")]
 pub enum Career {
     // technical / software / industrial
     SoftwareEngineer,
@@ -329,72 +333,26 @@ pub enum Career {
     WildlifeConservationist,
 }
 
-fn lower_with_spaces(s: String) -> String {
-    s.chars()
-        .enumerate()
-        .map(|(i, c)| {
-            if i > 0 && c.is_uppercase() {
-                format!(" {}", c.to_lowercase())
-            } else {
-                c.to_ascii_lowercase().to_string()
-            }
-        })
-        .collect::<String>()
+impl BackgroundDetail for Career {
+    fn id(&self) -> &'static str {
+        self.into()
+    }
 }
 
 impl fmt::Display for Career {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let discriminator: &'static str = self.into();
-        write!(f, "{}", lower_with_spaces(discriminator.to_string()))
+        write!(f, "{}", super::lower_with_spaces(&format!("{self:?}")))
     }
 }
 
-/// I really don't like doing this every time we serialize :(
-/// would love to just have this be a static lookup...
-/// TODO: improve all this
 impl Serialize for Career {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        let display = format!("{self:?}");
-        let with_spaces = lower_with_spaces(display);
-        serializer.serialize_str(&with_spaces)
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_id(self, serializer)
     }
 }
 
-/// This is inefficiently implemented but it should happen rarely
 impl<'de> Deserialize<'de> for Career {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct CareerVisitor;
-
-        impl<'de> Visitor<'de> for CareerVisitor {
-            type Value = Career;
-
-            fn expecting(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
-                fmt.write_str("a career name in lowercase with spaces")
-            }
-
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-            where
-                E: serde::de::Error,
-            {
-                // Consider each variant
-                for variant in Career::VARIANTS.iter() {
-                    let discriminator: &'static str = variant.into();
-                    let with_spaces = lower_with_spaces(discriminator.to_string());
-                    if with_spaces == v {
-                        return Ok(variant.to_owned());
-                    }
-                }
-
-                Err(E::custom(format!("unknown career: {v}")))
-            }
-        }
-
-        deserializer.deserialize_str(CareerVisitor)
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::deserialize_id(deserializer)
     }
 }
@@ -1,247 +1,276 @@
-use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use strum::EnumMessage;
+
+use super::BackgroundDetail;
 
 /// Stylised like "fear of X"
-/// NOTE: this is stored as an int to reduce memory use, then when needed its expanded to a string representation using `Display`.
+/// NOTE: this is stored by its id (see `BackgroundDetail`), then when needed its expanded to its message using `Display`.
 ///       i.e when used in a gamelog, the expanded version should be included
-#[derive(Debug, Clone, strum::Display, Serialize_repr, Deserialize_repr, strum::VariantArray)]
-#[repr(usize)]
+#[derive(Debug, Clone, strum::VariantArray, strum::IntoStaticStr, strum::EnumMessage)]
+#[strum(serialize_all = "snake_case")]
 #[qubit::ts]
-#[ts(as = "usize")]
+#[ts(as = "String")]
 pub enum Fear {
-    #[strum(to_string = "failing when they have responsibility")]
+    #[strum(message = "failing when they have responsibility")]
     Failure,
 
-    #[strum(to_string = "being rejected")]
+    #[strum(message = "being rejected")]
     Rejection,
 
-    #[strum(to_string = "being abandoned")]
+    #[strum(message = "being abandoned")]
     Abandonment,
 
-    #[strum(to_string = "being judged by others")]
+    #[strum(message = "being judged by others")]
     BeingJudged,
 
-    #[strum(to_string = "being left alone")]
+    #[strum(message = "being left alone")]
     Loneliness,
 
-    #[strum(to_string = "being close and intimate with others")]
+    #[strum(message = "being close and intimate with others")]
     Intimacy,
 
-    #[strum(to_string = "losing people close to them")]
+    #[strum(message = "losing people close to them")]
     Loss,
 
-    #[strum(to_string = "things changing and not being able to keep up")]
+    #[strum(message = "things changing and not being able to keep up")]
     Change,
 
-    #[strum(to_string = "being disappointed by the world and by others")]
+    #[strum(message = "being disappointed by the world and by others")]
     Disappointment,
 
-    #[strum(to_string = "being vulnerable to others")]
+    #[strum(message = "being vulnerable to others")]
     Vulnerability,
 
-    #[strum(to_string = "being forgotten when they die")]
+    #[strum(message = "being forgotten when they die")]
     BeingForgotten,
 
-    #[strum(to_string = "succeeding when they dont think its deserved")]
+    #[strum(message = "succeeding when they dont think its deserved")]
     Success,
 
-    #[strum(to_string = "embarassing themselves by trying too hard")]
+    #[strum(message = "embarassing themselves by trying too hard")]
     Embarrassment,
 
-    #[strum(to_string = "not being good enough")]
+    #[strum(message = "not being good enough")]
     NotBeingGoodEnough,
 
-    #[strum(to_string = "letting other people down")]
+    #[strum(message = "letting other people down")]
     LettingPeopleDown,
 
-    #[strum(to_string = "making a severe mistake")]
+    #[strum(message = "making a severe mistake")]
     MakingMistakes,
 
-    #[strum(to_string = "death")]
+    #[strum(message = "death")]
     Death,
 
-    #[strum(to_string = "growing old")]
+    #[strum(message = "growing old")]
     Aging,
 
-    #[strum(to_string = "getting sick as they age")]
+    #[strum(message = "getting sick as they age")]
     Illness,
 
-    #[strum(to_string = "experiencing extreme pain")]
+    #[strum(message = "experiencing extreme pain")]
     Pain,
 
-    #[strum(to_string = "the unknown")]
+    #[strum(message = "the unknown")]
     TheUnknown,
 
-    #[strum(to_string = "losing control of their life")]
+    #[strum(message = "losing control of their life")]
     LosingControl,
 
-    #[strum(to_string = "being trapped somewhere, unable to escape")]
+    #[strum(message = "being trapped somewhere, unable to escape")]
     BeingTrapped,
 
-    #[strum(to_string = "being betrayed by the people they trust")]
+    #[strum(message = "being betrayed by the people they trust")]
     Betrayal,
 
-    #[strum(to_string = "failing to act when their help is needed")]
+    #[strum(message = "failing to act when their help is needed")]
     FailureToAct,
 
-    #[strum(to_string = "being fundamentally misunderstood by the people closest to them")]
+    #[strum(message = "being fundamentally misunderstood by the people closest to them")]
     BeingMisunderstood,
 
-    #[strum(to_string = "never making a real connection with another person before they die")]
+    #[strum(message = "never making a real connection with another person before they die")]
     BeingAloneForever,
 
-    #[strum(to_string = "being inadequate")]
+    #[strum(message = "being inadequate")]
     Inadequacy,
 
-    #[strum(to_string = "being intellectually inferior to everyone around them")]
+    #[strum(message = "being intellectually inferior to everyone around them")]
     IntellectualInferiority,
 
-    #[strum(to_string = "being socially rejected")]
+    #[strum(message = "being socially rejected")]
     SocialRejection,
 
-    #[strum(to_string = "being responsible for others")]
+    #[strum(message = "being responsible for others")]
     Responsibility,
 
-    #[strum(to_string = "being criticised")]
+    #[strum(message = "being criticised")]
     Criticism,
 
-    #[strum(to_string = "being neglected and left to die")]
+    #[strum(message = "being neglected and left to die")]
     Neglect,
 
-    #[strum(to_string = "losing their closest loved ones")]
+    #[strum(message = "losing their closest loved ones")]
     LosingLovedOnes,
 
-    #[strum(to_string = "needing to suffer through emotional pain")]
+    #[strum(message = "needing to suffer through emotional pain")]
     EmotionalPain,
 
-    #[strum(to_string = "not making an impact on the world before they die")]
+    #[strum(message = "not making an impact on the world before they die")]
     BeingForgottenByHistory,
 
-    #[strum(to_string = "feeling like an outsider their whole lives")]
+    #[strum(message = "feeling like an outsider their whole lives")]
     NotFittingIn,
 
-    #[strum(to_string = "finding out some important but disturbing truth")]
+    #[strum(message = "finding out some important but disturbing truth")]
     ExposureToTruth,
 
-    #[strum(to_string = "finding out that they've been lied to by someone close to them")]
+    #[strum(message = "finding out that they've been lied to by someone close to them")]
     BeingLiedTo,
 
-    #[strum(to_string = "introspecting their own thoughts and emotions")]
+    #[strum(message = "introspecting their own thoughts and emotions")]
     Introspection,
 
-    #[strum(to_string = "repeating the mistakes of their parents")]
+    #[strum(message = "repeating the mistakes of their parents")]
     BecomingLikeParents,
 
-    #[strum(to_string = "being manipulated by others")]
+    #[strum(message = "being manipulated by others")]
     Manipulation,
 
-    #[strum(to_string = "failing at their relationships")]
+    #[strum(message = "failing at their relationships")]
     FailureInLove,
 
-    #[strum(to_string = "being emotionally dependent on their friends and families")]
+    #[strum(message = "being emotionally dependent on their friends and families")]
     EmotionalDependence,
 
-    #[strum(to_string = "never fulfilling their potential")]
+    #[strum(message = "never fulfilling their potential")]
     UnfulfilledPotential,
 
-    #[strum(to_string = "becoming irrelevant")]
+    #[strum(message = "becoming irrelevant")]
     Irrelevance,
 
-    #[strum(to_string = "never achieving a semblance of spiritual enlightenment")]
+    #[strum(message = "never achieving a semblance of spiritual enlightenment")]
     SpiritualEmptiness,
 
-    #[strum(to_string = "discovering their own meaninglessness")]
+    #[strum(message = "discovering their own meaninglessness")]
     Meaninglessness,
 
-    #[strum(to_string = "running out of time")]
+    #[strum(message = "running out of time")]
     TimeRunningOut,
 
-    #[strum(to_string = "being judged poorly when they die")]
+    #[strum(message = "being judged poorly when they die")]
     BeingJudgedAfterDeath,
 
-    #[strum(to_string = "their rocky past catching up to them")]
+    #[strum(message = "their rocky past catching up to them")]
     PastCatchingUp,
 
-    #[strum(to_string = "discovering what the future has in store for them")]
+    #[strum(message = "discovering what the future has in store for them")]
     FutureEvents,
 
-    #[strum(to_string = "what new technology will bring")]
+    #[strum(message = "what new technology will bring")]
     Technology,
 
-    #[strum(to_string = "machines gaining sentience")]
+    #[strum(message = "machines gaining sentience")]
     ArtificialIntelligence,
 
-    #[strum(to_string = "being in large crowds")]
+    #[strum(message = "being in large crowds")]
     Crowds,
 
-    #[strum(to_string = "silence")]
+    #[strum(message = "silence")]
     Silence,
 
-    #[strum(to_string = "darkness")]
+    #[strum(message = "darkness")]
     Darkness,
 
-    #[strum(to_string = "large open spaces")]
+    #[strum(message = "large open spaces")]
     OpenSpaces,
 
-    #[strum(to_string = "closed tight spaces")]
+    #[strum(message = "closed tight spaces")]
     EnclosedSpaces,
 
-    #[strum(to_string = "tall heights")]
+    #[strum(message = "tall heights")]
     Heights,
 
-    #[strum(to_string = "falling from a great height")]
+    #[strum(message = "falling from a great height")]
     Falling,
 
-    #[strum(to_string = "drowning")]
+    #[strum(message = "drowning")]
     Drowning,
 
-    #[strum(to_string = "being attacked by insects")]
+    #[strum(message = "being attacked by insects")]
     AttackedByInsects,
 
-    #[strum(to_string = "being covered in insects")]
+    #[strum(message = "being covered in insects")]
     CoveredByInsects,
 
-    #[strum(to_string = "spiders")]
+    #[strum(message = "spiders")]
     Spiders,
 
-    #[strum(to_string = "snakes")]
+    #[strum(message = "snakes")]
     Snakes,
 
-    #[strum(to_string = "worms")]
+    #[strum(message = "worms")]
     Worms,
 
-    #[strum(to_string = "bats")]
+    #[strum(message = "bats")]
     Bats,
 
-    #[strum(to_string = "deep holes")]
+    #[strum(message = "deep holes")]
     DeepHoles,
 
-    #[strum(to_string = "being outside")]
+    #[strum(message = "being outside")]
     Outdoors,
 
-    #[strum(to_string = "birds attacking them")]
+    #[strum(message = "birds attacking them")]
     Birds,
 
-    #[strum(to_string = "seeing another persons blood")]
+    #[strum(message = "seeing another persons blood")]
     Blood,
 
-    #[strum(to_string = "failing to protect the people they love")]
+    #[strum(message = "failing to protect the people they love")]
     FailureToProtect,
 
-    #[strum(to_string = "causing their own destruction")]
+    #[strum(message = "causing their own destruction")]
     SelfDestruction,
 
-    #[strum(to_string = "losing their sanity")]
+    #[strum(message = "losing their sanity")]
     Insanity,
 
-    #[strum(to_string = "seeing something terrifying in their dreams")]
+    #[strum(message = "seeing something terrifying in their dreams")]
     Dreams,
 
-    #[strum(to_string = "never making it back to see their family")]
+    #[strum(message = "never making it back to see their family")]
     NotMakingItBack,
 
-    #[strum(to_string = "paranormal spirits haunting them")]
+    #[strum(message = "paranormal spirits haunting them")]
     BeingHaunted,
 
-    #[strum(to_string = "being controlled by devils")]
+    #[strum(message = "being controlled by devils")]
     Devils,
 }
+
+impl BackgroundDetail for Fear {
+    fn id(&self) -> &'static str {
+        self.into()
+    }
+}
+
+impl fmt::Display for Fear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.get_message().unwrap_or(self.id()))
+    }
+}
+
+impl Serialize for Fear {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_id(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fear {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::deserialize_id(deserializer)
+    }
+}
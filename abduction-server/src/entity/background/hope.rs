@@ -1,262 +1,291 @@
-use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use strum::EnumMessage;
+
+use super::BackgroundDetail;
 
 /// Stylised like "hope that X"
-/// NOTE: this is stored as an int to reduce memory use, then when needed its expanded to a string representation using `Display`.
+/// NOTE: this is stored by its id (see `BackgroundDetail`), then when needed its expanded to its message using `Display`.
 ///       i.e when used in a gamelog, the expanded version should be included
-#[derive(Debug, Clone, strum::Display, Serialize_repr, Deserialize_repr, strum::VariantArray)]
-#[repr(usize)]
+#[derive(Debug, Clone, strum::VariantArray, strum::IntoStaticStr, strum::EnumMessage)]
+#[strum(serialize_all = "snake_case")]
 #[qubit::ts]
-#[ts(as = "usize")]
+#[ts(as = "String")]
 pub enum Hope {
-    #[strum(to_string = "they can find love")]
+    #[strum(message = "they can find love")]
     Love,
 
-    #[strum(to_string = "they can make new personal connections")]
+    #[strum(message = "they can make new personal connections")]
     Connection,
 
-    #[strum(to_string = "they can find belonging")]
+    #[strum(message = "they can find belonging")]
     Belonging,
 
-    #[strum(to_string = "they can find peace of mind")]
+    #[strum(message = "they can find peace of mind")]
     PeaceOfMind,
 
-    #[strum(to_string = "they can find new self-understanding")]
+    #[strum(message = "they can find new self-understanding")]
     Understanding,
 
-    #[strum(to_string = "they can find a community which accepts them")]
+    #[strum(message = "they can find a community which accepts them")]
     Acceptance,
 
-    #[strum(to_string = "they can find forgiveness")]
+    #[strum(message = "they can find forgiveness")]
     Forgiveness,
 
-    #[strum(to_string = "they can be spiritually healed")]
+    #[strum(message = "they can be spiritually healed")]
     Healing,
 
-    #[strum(to_string = "they can grow to be the person they need to be")]
+    #[strum(message = "they can grow to be the person they need to be")]
     Growth,
 
-    #[strum(to_string = "they can find redemption")]
+    #[strum(message = "they can find redemption")]
     Redemption,
 
-    #[strum(to_string = "they can find reconciliation")]
+    #[strum(message = "they can find reconciliation")]
     Reconciliation,
 
-    #[strum(to_string = "they can be happy")]
+    #[strum(message = "they can be happy")]
     Happiness,
 
-    #[strum(to_string = "they can find a new purpose")]
+    #[strum(message = "they can find a new purpose")]
     Purpose,
 
-    #[strum(to_string = "they can find meaning")]
+    #[strum(message = "they can find meaning")]
     Meaning,
 
-    #[strum(to_string = "they can find meaning")]
+    #[strum(message = "they can find meaning")]
     Fulfillment,
 
-    #[strum(to_string = "they can be free again")]
+    #[strum(message = "they can be free again")]
     Freedom,
 
-    #[strum(to_string = "they can find clarity")]
+    #[strum(message = "they can find clarity")]
     Clarity,
 
-    #[strum(to_string = "they can be stable again")]
+    #[strum(message = "they can be stable again")]
     Stability,
 
-    #[strum(to_string = "they can discover something unique")]
+    #[strum(message = "they can discover something unique")]
     Discovery,
 
-    #[strum(to_string = "they can help to inspire the next generation")]
+    #[strum(message = "they can help to inspire the next generation")]
     Inspiration,
 
-    #[strum(to_string = "they can find new confidence")]
+    #[strum(message = "they can find new confidence")]
     Confidence,
 
-    #[strum(to_string = "they can learn to trust themselves")]
+    #[strum(message = "they can learn to trust themselves")]
     SelfTrust,
 
-    #[strum(to_string = "they can find courage")]
+    #[strum(message = "they can find courage")]
     Courage,
 
-    #[strum(to_string = "they can become a more kind person")]
+    #[strum(message = "they can become a more kind person")]
     Kindness,
 
-    #[strum(to_string = "people will treat each other more kindly in the future")]
+    #[strum(message = "people will treat each other more kindly in the future")]
     WorldKindness,
 
-    #[strum(to_string = "they can become a more compassionate person")]
+    #[strum(message = "they can become a more compassionate person")]
     Compassion,
 
-    #[strum(to_string = "people can learn to treat each other with more compassion")]
+    #[strum(message = "people can learn to treat each other with more compassion")]
     WorldCompassion,
 
-    #[strum(to_string = "they grow wise over time")]
+    #[strum(message = "they grow wise over time")]
     Wisdom,
 
-    #[strum(to_string = "they can learn to be patient")]
+    #[strum(message = "they can learn to be patient")]
     Patience,
 
-    #[strum(to_string = "others can learn to be patient with them")]
+    #[strum(message = "others can learn to be patient with them")]
     OthersPatience,
 
-    #[strum(to_string = "they can find inner balance")]
+    #[strum(message = "they can find inner balance")]
     Balance,
 
-    #[strum(to_string = "they can make peace with their past")]
+    #[strum(message = "they can make peace with their past")]
     PeaceWithPast,
 
-    #[strum(to_string = "they can make peace with whatever comes")]
+    #[strum(message = "they can make peace with whatever comes")]
     PeaceWithFuture,
 
-    #[strum(to_string = "they can achieve inner strength")]
+    #[strum(message = "they can achieve inner strength")]
     InnerStrength,
 
-    #[strum(to_string = "they can find simplicity")]
+    #[strum(message = "they can find simplicity")]
     Simplicity,
 
-    #[strum(to_string = "they can find a safe place to just exist")]
+    #[strum(message = "they can find a safe place to just exist")]
     Safety,
 
-    #[strum(to_string = "their friends and family will stay loyal to them")]
+    #[strum(message = "their friends and family will stay loyal to them")]
     Loyalty,
 
-    #[strum(to_string = "they can learn to be more honest with others")]
+    #[strum(message = "they can learn to be more honest with others")]
     Honesty,
 
-    #[strum(to_string = "they can find intimacy with another")]
+    #[strum(message = "they can find intimacy with another")]
     Intimacy,
 
-    #[strum(to_string = "everyone can learn to respect each other and their needs")]
+    #[strum(message = "everyone can learn to respect each other and their needs")]
     WorldMutualRespect,
 
-    #[strum(to_string = "they find long lasting love with another")]
+    #[strum(message = "they find long lasting love with another")]
     LongLastingLove,
 
-    #[strum(to_string = "they can make a new start soon")]
+    #[strum(message = "they can make a new start soon")]
     NewBeginnings,
 
-    #[strum(to_string = "they get a second chance")]
+    #[strum(message = "they get a second chance")]
     SecondChances,
 
-    #[strum(to_string = "there are better days yet to come")]
+    #[strum(message = "there are better days yet to come")]
     BetterDays,
 
-    #[strum(to_string = "they can change")]
+    #[strum(message = "they can change")]
     Change,
 
-    #[strum(to_string = "they can use their creativity to weather the storm")]
+    #[strum(message = "they can use their creativity to weather the storm")]
     Creativity,
 
-    #[strum(to_string = "others will recognise their contributions")]
+    #[strum(message = "others will recognise their contributions")]
     Recognition,
 
-    #[strum(to_string = "they can learn to accept themselves")]
+    #[strum(message = "they can learn to accept themselves")]
     SelfAcceptance,
 
-    #[strum(to_string = "they can improve themselves")]
+    #[strum(message = "they can improve themselves")]
     SelfImprovement,
 
-    #[strum(to_string = "one day they will forgive themselves")]
+    #[strum(message = "one day they will forgive themselves")]
     ForgivingMyself,
 
-    #[strum(to_string = "they can let go of the past")]
+    #[strum(message = "they can let go of the past")]
     LettingGo,
 
-    #[strum(to_string = "they can find peace in this chaos")]
+    #[strum(message = "they can find peace in this chaos")]
     PeaceInChaos,
 
-    #[strum(to_string = "they can get closer to their loved ones")]
+    #[strum(message = "they can get closer to their loved ones")]
     Closeness,
 
-    #[strum(to_string = "they can learn to believe in themselves")]
+    #[strum(message = "they can learn to believe in themselves")]
     BeliefInMyself,
 
-    #[strum(to_string = "they can build a supportive community")]
+    #[strum(message = "they can build a supportive community")]
     Community,
 
-    #[strum(to_string = "their trust is well-placed")]
+    #[strum(message = "their trust is well-placed")]
     Trust,
 
-    #[strum(to_string = "one day they can live in emotional safety")]
+    #[strum(message = "one day they can live in emotional safety")]
     EmotionalSafety,
 
-    #[strum(to_string = "their playfulness is not suppressed by others")]
+    #[strum(message = "their playfulness is not suppressed by others")]
     Playfulness,
 
-    #[strum(to_string = "they can see more of the world")]
+    #[strum(message = "they can see more of the world")]
     SeeTheWorld,
 
-    #[strum(to_string = "their innocence is preserved")]
+    #[strum(message = "their innocence is preserved")]
     Innocence,
 
-    #[strum(to_string = "their faith in humanity is well-placed")]
+    #[strum(message = "their faith in humanity is well-placed")]
     FaithInHumanity,
 
-    #[strum(to_string = "the world can learn to be more generous")]
+    #[strum(message = "the world can learn to be more generous")]
     Generosity,
 
-    #[strum(to_string = "they get the opportunity to show gratitude more often")]
+    #[strum(message = "they get the opportunity to show gratitude more often")]
     Gratitude,
 
-    #[strum(to_string = "they can again experience joy in the small things")]
+    #[strum(message = "they can again experience joy in the small things")]
     JoyInSmallThings,
 
-    #[strum(to_string = "they can return to somewhere cozy in the future")]
+    #[strum(message = "they can return to somewhere cozy in the future")]
     CozyPlace,
 
-    #[strum(to_string = "they can establish a connection with nature")]
+    #[strum(message = "they can establish a connection with nature")]
     ConnectionWithNature,
 
-    #[strum(to_string = "they can establish harmony within their family once they return")]
+    #[strum(message = "they can establish harmony within their family once they return")]
     FamilyHarmony,
 
-    #[strum(to_string = "they can make a new friend")]
+    #[strum(message = "they can make a new friend")]
     Friendship,
 
-    #[strum(to_string = "their love is truly unconditional")]
+    #[strum(message = "their love is truly unconditional")]
     UnconditionalLove,
 
-    #[strum(to_string = "they can remain resilient in the face of danger")]
+    #[strum(message = "they can remain resilient in the face of danger")]
     Resilience,
 
-    #[strum(to_string = "this all ends peacefully")]
+    #[strum(message = "this all ends peacefully")]
     PeacefulEndings,
 
-    #[strum(to_string = "they can reinvent themselves once they return")]
+    #[strum(message = "they can reinvent themselves once they return")]
     Reinvention,
 
-    #[strum(to_string = "they learn from their mistakes")]
+    #[strum(message = "they learn from their mistakes")]
     LearningFromMistakes,
 
-    #[strum(to_string = "there are gentler days on the horizon")]
+    #[strum(message = "there are gentler days on the horizon")]
     GentleDays,
 
-    #[strum(to_string = "a time will come when they no longer have to fear")]
+    #[strum(message = "a time will come when they no longer have to fear")]
     LessFear,
 
-    #[strum(to_string = "they can achieve inner peace")]
+    #[strum(message = "they can achieve inner peace")]
     InnerPeace,
 
-    #[strum(to_string = "soon they will be able to let go of their pain")]
+    #[strum(message = "soon they will be able to let go of their pain")]
     LettingGoOfPain,
 
-    #[strum(to_string = "they talking honestly with others is the right decision")]
+    #[strum(message = "they talking honestly with others is the right decision")]
     HonestConversations,
 
-    #[strum(to_string = "they can get the rest they need to survive")]
+    #[strum(message = "they can get the rest they need to survive")]
     Rest,
 
-    #[strum(to_string = "they are able to recover from this")]
+    #[strum(message = "they are able to recover from this")]
     Recovery,
 
-    #[strum(to_string = "their independence is preserved")]
+    #[strum(message = "their independence is preserved")]
     PreserveIndependence,
 
-    #[strum(to_string = "they can make a difference to other peoples survival")]
+    #[strum(message = "they can make a difference to other peoples survival")]
     MakingADifference,
 
-    #[strum(to_string = "they are able to leave a legacy")]
+    #[strum(message = "they are able to leave a legacy")]
     LeavingALegacy,
 
-    #[strum(to_string = "everyone can experience some joy, even when its challenging")]
+    #[strum(message = "everyone can experience some joy, even when its challenging")]
     JoyWithoutReason,
 }
+
+impl BackgroundDetail for Hope {
+    fn id(&self) -> &'static str {
+        self.into()
+    }
+}
+
+impl fmt::Display for Hope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.get_message().unwrap_or(self.id()))
+    }
+}
+
+impl Serialize for Hope {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_id(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hope {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        super::deserialize_id(deserializer)
+    }
+}
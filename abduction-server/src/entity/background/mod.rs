@@ -1,9 +1,27 @@
-use serde::{Deserialize, Serialize};
+use std::{fmt, marker::PhantomData};
+
+use serde::{de::Visitor, Deserialize, Serialize};
+use strum::VariantArray;
+
+use crate::content::content;
 
 pub mod career;
 pub mod fear;
 pub mod hope;
 
+/// Part of a background that's one of a fixed set of options (i.e a career)
+///
+/// These are saved and sent to the site as a stable snake_case id (i.e "peace_of_mind"),
+/// so their display text can be edited without changing saved entities (see `BackgroundManifest`)
+/// NOTE: never rename a variant's id, add a new one instead
+pub trait BackgroundDetail: VariantArray + Clone + fmt::Debug + fmt::Display + 'static {
+    fn id(&self) -> &'static str;
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::VARIANTS.iter().find(|v| v.id() == id).cloned()
+    }
+}
+
 /// Information on an entity's (prob player) background before they were abducted,
 /// where they were from, who they were etc
 ///
@@ -33,3 +51,129 @@ impl EntityBackground {
         format!("{}, {}", self.city_name, self.country_name)
     }
 }
+
+fn lower_with_spaces(s: &str) -> String {
+    s.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if i > 0 && c.is_uppercase() {
+                format!(" {}", c.to_lowercase())
+            } else {
+                c.to_ascii_lowercase().to_string()
+            }
+        })
+        .collect::<String>()
+}
+
+fn serialize_id<T: BackgroundDetail, S: serde::Serializer>(
+    detail: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(detail.id())
+}
+
+/// Reads a detail by its id, or how it was saved before it had one
+/// (careers in lowercase with spaces, hopes and fears by their index)
+fn deserialize_id<'de, T: BackgroundDetail, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    struct IdVisitor<T>(PhantomData<T>);
+
+    impl<T: BackgroundDetail> Visitor<'_> for IdVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("a background id")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            T::from_id(v)
+                .or_else(|| {
+                    T::VARIANTS
+                        .iter()
+                        .find(|variant| lower_with_spaces(&format!("{variant:?}")) == v)
+                        .cloned()
+                })
+                .ok_or_else(|| E::custom(format!("unknown background id: {v}")))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            T::VARIANTS
+                .get(v as usize)
+                .cloned()
+                .ok_or_else(|| E::custom(format!("unknown background index: {v}")))
+        }
+    }
+
+    deserializer.deserialize_any(IdVisitor(PhantomData))
+}
+
+/// The display text for every background detail, by id
+/// (so the site can show them without its own copy of the text)
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct BackgroundManifest {
+    pub careers: Vec<(String, String)>,
+    pub fears: Vec<(String, String)>,
+    pub hopes: Vec<(String, String)>,
+}
+
+impl BackgroundManifest {
+    /// The text as it's currently phrased (incl. any loaded content)
+    pub fn current() -> Self {
+        let content = content();
+        Self {
+            careers: career::Career::VARIANTS
+                .iter()
+                .map(|career| (career.id().to_string(), career.to_string()))
+                .collect(),
+            fears: fear::Fear::VARIANTS
+                .iter()
+                .map(|fear| (fear.id().to_string(), content.fear_text(fear)))
+                .collect(),
+            hopes: hope::Hope::VARIANTS
+                .iter()
+                .map(|hope| (hope.id().to_string(), content.hope_text(hope)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{career::Career, fear::Fear, hope::Hope, *};
+
+    #[test]
+    fn test_background_ids_are_unique_and_round_trip() {
+        fn check<T: BackgroundDetail + Serialize + for<'de> Deserialize<'de>>() {
+            let mut ids: Vec<_> = T::VARIANTS.iter().map(|v| v.id()).collect();
+            ids.sort();
+            ids.dedup();
+            assert_eq!(ids.len(), T::VARIANTS.len());
+
+            for variant in T::VARIANTS {
+                let json = serde_json::to_string(variant).unwrap();
+                assert_eq!(json, format!("\"{}\"", variant.id()));
+                let back: T = serde_json::from_str(&json).unwrap();
+                assert_eq!(back.id(), variant.id());
+            }
+        }
+        check::<Career>();
+        check::<Fear>();
+        check::<Hope>();
+    }
+
+    #[test]
+    fn test_background_reads_old_saves() {
+        assert_eq!(Career::AIResearcher.id(), "ai_researcher");
+        let career: Career = serde_json::from_str("\"a i researcher\"").unwrap();
+        assert_eq!(career.id(), "ai_researcher");
+
+        let hope: Hope = serde_json::from_str("3").unwrap();
+        assert_eq!(hope.id(), "peace_of_mind");
+        let fear: Fear = serde_json::from_str("0").unwrap();
+        assert_eq!(fear.id(), "failure");
+
+        assert!(serde_json::from_str::<Hope>("\"not_a_hope\"").is_err());
+    }
+}
@@ -1,14 +1,17 @@
 use crate::entity::brain::focus::ActorFocus;
 use crate::entity::brain::meme::Meme;
-use crate::entity::{EntityId, EntityMarker};
+use crate::entity::{Entity, EntityId, EntityMarker, PodPart};
 use crate::hex::{AxialHex, AxialHexDirection};
 use crate::logs::GameLogBody;
 use crate::mtch::crew::PresenterAction;
 
-use super::discussion::DiscussionAction;
+use super::discussion::{DiscussionAction, Opinion};
+use super::idle::IdleBehavior;
 use super::motivator::MotivatorKey;
+use super::plan::Plan;
+use super::throwing::ThrowTarget;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, strum::IntoStaticStr)]
 #[allow(unused)]
 pub enum ActorAction {
     /// No-op
@@ -19,18 +22,85 @@ pub enum ActorAction {
     /// Do some action but always return "NoEffect" so we can chain more
     IgnoreResult(Box<ActorAction>),
 
-    /// Try each action in the list until one works
-    Sequential(Vec<ActorAction>),
+    /// Try each step of some plan until one works
+    Plan(Plan),
 
     /// Remember a given meme
     StoreMeme(Meme),
 
+    /// Forget a given meme (e.g because it's no longer true)
+    ForgetMeme(Meme),
+
+    /// Take on board what someone told us they think of some entity
+    HearOpinion {
+        from: EntityId,
+        about: EntityId,
+        opinion: Opinion,
+    },
+
     /// Add some specific entity to the inventory, if there is room
     PickUpEntity(EntityId),
 
     /// Retrieve some specific entity from the inventory
     RetrieveEntity(EntityId),
 
+    /// Take some specific entity out of the inventory and leave it at our location
+    DropEntity(EntityId),
+
+    /// Use up some healing item (in our inventory or at our location) on ourselves or someone else
+    UseItemOn { item: EntityId, target: EntityId },
+
+    /// Drag some entity at our location out of whatever dangerous thing is here,
+    /// into a safe neighbouring hex (going with them)
+    DragEntity { entity_id: EntityId },
+
+    /// Hand some food we are carrying to some entity at our location, if they have room for it
+    ShareFood { entity_id: EntityId, item: EntityId },
+
+    /// Search through some container at our location, revealing whatever it holds
+    /// (success depends on vision)
+    SearchContainer(EntityId),
+
+    /// Hide a stash at our location to keep things in
+    /// (shared with whoever we are travelling with)
+    CreateCache,
+
+    /// Put something from our inventory in a stash of ours at our location
+    DepositInCache { cache: EntityId, item: EntityId },
+
+    /// Take something out of a stash of ours at our location, if there is room
+    WithdrawFromCache { cache: EntityId, item: EntityId },
+
+    /// Take the warmest clothing off some corpse at our location, if there is room
+    StripCorpse(EntityId),
+
+    /// Take some item from what a corpse at our location was carrying, if there is room
+    /// (unless we can't bring ourselves to)
+    LootCorpse { corpse: EntityId, item: EntityId },
+
+    /// Throw something we are carrying at some hex or entity (within range)
+    /// it lands in that hex, hurting whoever it hits
+    Throw { item: EntityId, target: ThrowTarget },
+
+    /// Get away from some entity (even if they aren't here with us)
+    FleeFrom { entity_id: EntityId },
+
+    /// Follow the tracks some entity left here, to go after them
+    /// (success depends on vision)
+    FollowTracks { entity_id: EntityId },
+
+    /// Go after some player (following their tracks if there are any here), giving up after a while
+    /// (predators only)
+    Stalk { entity_id: EntityId },
+
+    /// Set upon some player at our location
+    /// (predators only)
+    Maul { entity_id: EntityId },
+
+    /// Have a good look around our location for anything we haven't noticed yet
+    /// (success depends on vision)
+    LookAround,
+
     /// Increase some motivator by the sensitivity
     BumpMotivator(MotivatorKey),
 
@@ -48,10 +118,42 @@ pub enum ActorAction {
         body: GameLogBody,
     },
 
+    /// Tell off some entity at our location for their bad deeds
+    ConfrontEntity { entity_id: EntityId },
+
+    /// Move away from some entity at our location that we'd rather not be near
+    AvoidEntity { entity_id: EntityId },
+
+    /// Try to take some food from the inventory of an entity at our location
+    /// (success depends on acrobatics)
+    StealFood { entity_id: EntityId },
+
+    /// Start travelling together with some entity at our location
+    /// (we lead, they follow)
+    StartTravellingWith { entity_id: EntityId },
+
+    /// Keep travelling with whoever we are travelling with
+    /// if leading, wander around, otherwise follow the leader
+    TravelTogether,
+
+    /// Stop travelling with whoever we are travelling with
+    PartWays,
+
     /// Mourn the death of another entity
     /// (get sad, have a little vigil etc)
     MournEntity { entity_id: EntityId },
 
+    /// Hold a memorial for someone we're still grieving, at their corpse (or where they died)
+    /// (heading there first if we aren't there)
+    HoldMemorial { entity_id: EntityId },
+
+    /// Join a memorial someone is holding for someone we cared for
+    JoinMemorial {
+        host: EntityId,
+        deceased: EntityId,
+        name: String,
+    },
+
     /// When in a discussion focus, do related actions
     Discussion(DiscussionAction),
 
@@ -85,6 +187,9 @@ pub enum ActorAction {
     /// Move to a new hex
     Move(AxialHexDirection),
 
+    /// Drift to a new hex without anyone noticing (for spirits)
+    Drift(AxialHexDirection),
+
     /// Eat some specific food entity
     ConsumeFoodEntity(EntityId),
 
@@ -114,6 +219,62 @@ pub enum ActorAction {
     /// Leave current shelter
     LeaveShelter,
 
+    /// Use some building material from our inventory to patch up a shelter at our location
+    RepairShelter,
+
+    /// Claim the unclaimed shelter at our location (and so the hex) as ours
+    ClaimHex,
+
+    /// Fit a part we are carrying into some escape pod at our location
+    /// (success depends on planning, whoever fits the last part escapes in it)
+    RepairPod { pod: EntityId },
+
+    /// Fish at the lake at our location
+    /// if not already in a fishing focus, will enter one
+    /// (success depends on how long we've been at it and what we're fishing with)
+    Fish,
+
+    /// Stop fishing and leave the lake be
+    StopFishing,
+
+    /// Keep watch over some entity sleeping at our location
+    /// if not already in a guarding focus, will enter one
+    StandGuard { entity_id: EntityId },
+
+    /// Stop keeping watch over whoever we are guarding
+    StopGuarding,
+
+    /// Burn some material from our inventory to light a campfire at our location
+    LightCampfire { material: EntityId },
+
+    /// Settle in around some campfire at our location, entering a gathering focus
+    JoinGathering { campfire: EntityId },
+
+    /// Tell everyone gathered around our campfire a story about our life before all this
+    TellStory,
+
+    /// Get up from the campfire and carry on
+    LeaveGathering,
+
+    /// Lash together a raft at our location from building material we are carrying
+    /// (only beside open water, see `rafting`)
+    BuildRaft,
+
+    /// Climb aboard some raft at our location, if there is room
+    /// (taking it for ourselves if it's someone else's and they aren't aboard)
+    BoardRaft { raft: EntityId },
+
+    /// Paddle the raft we are aboard to a neighbouring hex of open water
+    /// (everyone else aboard comes too, and in rough weather it might go over)
+    PaddleRaft(AxialHexDirection),
+
+    /// Climb off the raft we are aboard
+    /// (paddling it ashore first if we are out on the water)
+    DisembarkRaft,
+
+    /// Do something to pass the time (see `idle`)
+    Idle(IdleBehavior),
+
     /// Head towards shelter if we know where some is
     SeekKnownShelter,
 
@@ -122,6 +283,14 @@ pub enum ActorAction {
 
     /// "Warp in" some entity thats currently banished
     WarpInEntity(EntityId),
+
+    /// Vanish and reappear in some other hex
+    /// (crew only)
+    Teleport(AxialHex),
+
+    /// "Warp out" some corpse or prop at our location, adding it to our collection
+    /// (collector only)
+    WarpOutEntity(EntityId),
 }
 
 #[derive(Debug)]
@@ -175,6 +344,102 @@ pub enum ActorActionSideEffect {
         entity_id: EntityId,
         focus: ActorFocus,
     },
+
+    /// Take everything out of some container and place it in the given hex
+    EmptyContainer(EntityId, AxialHex),
+
+    /// Remove an item from the inventory of some other entity
+    /// (it's already been put in ours)
+    TakeFromOther {
+        entity_id: EntityId,
+        item_id: EntityId,
+    },
+
+    /// Put an item in the inventory of some other entity (e.g a stash)
+    /// (it's already been taken out of ours)
+    PutInOther {
+        entity_id: EntityId,
+        item_id: EntityId,
+    },
+
+    /// Take an item out of the inventory of some other entity (e.g a stash)
+    /// (it's already been put in ours)
+    TakeOutOfOther {
+        entity_id: EntityId,
+        item_id: EntityId,
+    },
+
+    /// Update the inventory of some other entity after trading with them
+    ExchangeItems {
+        entity_id: EntityId,
+        gained: EntityId,
+        lost: EntityId,
+    },
+
+    /// Mark a shelter as claimed by us
+    ClaimOther(EntityId),
+
+    /// Take a drink from some water source that can run dry
+    DrinkFromOther(EntityId),
+
+    /// Restore some durability to a shelter (using up some material)
+    RepairOther {
+        entity_id: EntityId,
+        material_id: EntityId,
+        amount: f32,
+    },
+
+    /// Fit a part (used up) into some escape pod, which flies off if that was the last one
+    FitPodPart {
+        pod_id: EntityId,
+        part_id: EntityId,
+        part: PodPart,
+    },
+
+    /// Add a new entity to the world (e.g a fish we've caught)
+    SpawnOther(Box<Entity>),
+
+    /// Use up some material (from our inventory) to light a fire
+    LightFire {
+        material_id: EntityId,
+        fire: Box<Entity>,
+    },
+
+    /// Cheer up everyone who heard a story we told
+    EntertainOthers { entity_ids: Vec<EntityId> },
+
+    /// Use up some material (from our inventory) to build a raft
+    BuildRaft {
+        material_ids: Vec<EntityId>,
+        raft: Box<Entity>,
+    },
+
+    /// Take our place aboard some raft (if there's still room once everyone else has)
+    /// and if we're taking it for ourselves, it's ours now
+    BoardRaft { raft_id: EntityId, claim: bool },
+
+    /// Bring some raft (and everyone else aboard) along with us to the given hex
+    MoveRaft { raft_id: EntityId, hex: AxialHex },
+
+    /// Some raft went over, everyone else aboard is thrown in too
+    CapsizeRaft(EntityId),
+
+    /// Land something we threw in some hex, hurting whoever it hit
+    LandThrown {
+        item_id: EntityId,
+        hex: AxialHex,
+        hit: Option<(EntityId, f32)>,
+    },
+
+    /// Hurt some other entity (e.g when mauling them)
+    HurtOther { entity_id: EntityId, amount: f32 },
+
+    /// Reduce the hurt of some other entity (using up an item)
+    HealOther {
+        entity_id: EntityId,
+        item_id: EntityId,
+        amount: f32,
+    },
 }
 
 impl ActorAction {
@@ -0,0 +1,125 @@
+//! Barks that sound like whoever is barking
+//!
+//! Every player barks about their motivators (see `ActorAction::Bark`) with the same few lines, which
+//! gets stale when the same complaint comes up again and again. So players with something about them
+//! that would colour how they complain sometimes bark with that flavour instead: a comedian makes light
+//! of it, someone with no resolve despairs, someone with plenty shrugs it off and the old go on about
+//! how things were back home. The flavour is picked when the bark is made (and sent along with it),
+//! from whichever suit the player and have lines in the content file (see `BarkLines::variants`).
+
+use rand::{seq::IndexedRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    content::Content,
+    entity::{
+        background::career::Career,
+        brain::{characteristic::Characteristic, motivator::MotivatorKey},
+        Entity,
+    },
+};
+
+/// Chance that a bark takes on the player's flavour, when one suits them
+/// (so they still say the usual things some of the time)
+pub const BARK_VARIANT_CHANCE: f64 = 0.5;
+
+/// From this age on, players are old enough to reminisce
+pub const OLD_AGE: usize = 65;
+
+/// A flavour of bark, depending on who's barking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, strum::VariantArray)]
+#[serde(rename_all = "snake_case")]
+#[qubit::ts]
+pub enum BarkVariant {
+    /// Making light of it (comedians and the like)
+    Joking,
+
+    /// Sure that it's all over (low resolve)
+    Despairing,
+
+    /// Shrugging it off (high resolve)
+    Stoic,
+
+    /// Comparing it to how things were back home (the old)
+    Nostalgic,
+}
+
+impl BarkVariant {
+    /// Whether this flavour suits some entity
+    pub fn suits(&self, entity: &Entity) -> bool {
+        match self {
+            BarkVariant::Joking => entity.attributes.background.as_ref().is_some_and(|b| {
+                matches!(
+                    b.career,
+                    Career::Comedian | Career::Actor | Career::VoiceActor
+                )
+            }),
+            BarkVariant::Despairing => entity.characteristic(Characteristic::Resolve).is_low(),
+            BarkVariant::Stoic => entity.characteristic(Characteristic::Resolve).is_high(),
+            BarkVariant::Nostalgic => entity.attributes.age.is_some_and(|age| age >= OLD_AGE),
+        }
+    }
+}
+
+impl Entity {
+    /// Pick how to phrase a bark about some motivator (none -> the usual way)
+    pub fn choose_bark_variant(
+        &self,
+        motivator: MotivatorKey,
+        content: &Content,
+        rng: &mut impl Rng,
+    ) -> Option<BarkVariant> {
+        let suited: Vec<_> = <BarkVariant as strum::VariantArray>::VARIANTS
+            .iter()
+            .copied()
+            .filter(|variant| variant.suits(self) && content.has_bark_variant(motivator, *variant))
+            .collect();
+        if suited.is_empty() || !rng.random_bool(BARK_VARIANT_CHANCE) {
+            return None;
+        }
+        suited.choose(rng).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::brain::{
+        characteristic::CharacteristicStrength, test_signal::EntityBuilder,
+    };
+
+    #[test]
+    fn test_barks_suit_the_barker() {
+        let content: Content = serde_json::from_value(serde_json::json!({
+            "barks": {
+                "hunger": {
+                    "moderate": ["{name}'s stomach grumbles"],
+                    "variants": { "despairing": ["{name} is sure they'll never eat again"] }
+                }
+            }
+        }))
+        .unwrap();
+        let mut rng = crate::rng::rng();
+
+        // Nothing about the usual player to colour their barks
+        let player = EntityBuilder::player("Plain").build();
+        assert!((0..20).all(|_| player
+            .choose_bark_variant(MotivatorKey::Hunger, &content, &mut rng)
+            .is_none()));
+
+        // but the hopeless despair, sometimes
+        let hopeless = EntityBuilder::player("Hopeless")
+            .characteristic(Characteristic::Resolve, CharacteristicStrength::Low)
+            .build();
+        let variants: Vec<_> = (0..100)
+            .map(|_| hopeless.choose_bark_variant(MotivatorKey::Hunger, &content, &mut rng))
+            .collect();
+        assert!(variants.contains(&Some(BarkVariant::Despairing)));
+        assert!(variants.contains(&None));
+
+        // (as long as there's something to say)
+        assert!((0..20).all(|_| hopeless
+            .choose_bark_variant(MotivatorKey::Thirst, &content, &mut rng)
+            .is_none()));
+    }
+}
@@ -0,0 +1,219 @@
+//! Bonds drifting with time spent together (or apart), and with what we hear from others
+//!
+//! Bonds are mostly made and broken by what people do (talking, trading, fighting...), but every so
+//! often they also drift a little on their own. Spending time peacefully in the same place slowly
+//! brings people closer, up to a point, while going a long time without seeing someone slowly wears
+//! a bond back down to nothing (grudges included). How quickly that happens depends on how loyal
+//! someone is. When an associate was last seen is kept in their `last_together`.
+//!
+//! Bonds also shift with what others tell us about someone (when asked for their opinion in a
+//! discussion). We come round to their way of thinking in proportion to how we feel about whoever
+//! told us, so a close friend's word carries a lot of weight, a stranger's carries none, and
+//! someone we dislike just pushes us the other way.
+
+use crate::{
+    entity::{
+        brain::{characteristic::Characteristic, discussion::Opinion},
+        snapshot::EntityView,
+        Entity, EntityId,
+    },
+    mtch::TickId,
+};
+
+/// How many ticks between each drift of everyone's bonds
+pub const BOND_DRIFT_INTERVAL: usize = 10;
+
+/// How much a bond grows each drift from spending time together
+const PROXIMITY_BOND_GAIN: f32 = 0.005;
+
+/// How far a bond can grow just from spending time together
+/// (anything more has to come from actually getting along)
+const PROXIMITY_BOND_CAP: f32 = 0.5;
+
+/// How many ticks apart before a bond starts to wear down
+const SEPARATION_GRACE_TICKS: usize = 100;
+
+/// How much a bond wears down each drift while apart (for someone of average loyalty)
+const SEPARATION_BOND_DECAY: f32 = 0.005;
+
+/// How much an opinion from someone we're fully bonded with changes how we feel about its subject
+const OPINION_SWAY: f32 = 0.1;
+
+impl Entity {
+    /// Let our bonds drift with whether we've been around our associates lately
+    /// (returns whether any changed)
+    pub fn drift_bonds(&mut self, entity_view: &EntityView, tick_id: TickId) -> bool {
+        let Some(hex) = self.attributes.hex else {
+            return false;
+        };
+
+        // (the loyal hold on to a bond for twice as long as most, the disloyal for a lot less)
+        let loyalty = self
+            .characteristic(Characteristic::Loyalty)
+            .success_chance() as f32;
+        let decay = SEPARATION_BOND_DECAY * (1.0 - loyalty) * 2.0;
+
+        let entity_id = &self.entity_id;
+        let Some(associates) = self.relations.associates.as_mut() else {
+            return false;
+        };
+
+        let mut changed = false;
+        for (associate_id, associate) in associates.iter_mut() {
+            // (the dead are remembered as they were)
+            let Some(other) = entity_view.by_id(associate_id) else {
+                continue;
+            };
+
+            let together = other.attributes.hex == Some(hex);
+            let last_together = match associate.last_together {
+                Some(last_together) if !together => last_together,
+                _ => {
+                    associate.last_together = Some(tick_id);
+                    changed = true;
+                    tick_id
+                }
+            };
+
+            if together {
+                let peaceful = associate.bond >= 0.0 && !other.relations.dislike(entity_id);
+                if peaceful && associate.bond < PROXIMITY_BOND_CAP {
+                    associate.bond = (associate.bond + PROXIMITY_BOND_GAIN).min(PROXIMITY_BOND_CAP);
+                }
+            } else if tick_id.saturating_sub(last_together) >= SEPARATION_GRACE_TICKS
+                && associate.bond != 0.0
+            {
+                associate.bond = if associate.bond > 0.0 {
+                    (associate.bond - decay).max(0.0)
+                } else {
+                    (associate.bond + decay).min(0.0)
+                };
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Take on board what someone told us they think of some entity
+    /// (returns how much our bond with that entity changed)
+    pub fn assimilate_opinion(
+        &mut self,
+        speaker_id: &EntityId,
+        subject_id: &EntityId,
+        opinion: &Opinion,
+    ) -> f32 {
+        self.memes_mut()
+            .remember_opinion(subject_id, opinion.clone());
+
+        // (nobody can talk us out of how we feel about ourselves)
+        if subject_id == &self.entity_id {
+            return 0.0;
+        }
+
+        let leaning = match opinion {
+            Opinion::Positive => 1.0,
+            Opinion::Neutral => 0.0,
+            Opinion::Negative => -1.0,
+        };
+        let trust = self.relations.bond(speaker_id).clamp(-1.0, 1.0);
+        let sway = OPINION_SWAY * leaning * trust;
+        if sway != 0.0 {
+            self.relations.increase_associate_bond_by(subject_id, sway);
+        }
+        sway
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        entity::{brain::characteristic::CharacteristicStrength, snapshot::EntitySnapshot},
+        hex::AxialHex,
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_bonds_drift_with_time_together() {
+        let mut friend = test_player("Friend", AxialHex::ZERO);
+        let mut loyal = test_player("Loyal", AxialHex::ZERO);
+        loyal.attributes.characteristics = Some(HashMap::from([(
+            Characteristic::Loyalty,
+            CharacteristicStrength::High,
+        )]));
+        let mut fickle = test_player("Fickle", AxialHex::ZERO);
+        fickle.attributes.characteristics = Some(HashMap::from([(
+            Characteristic::Loyalty,
+            CharacteristicStrength::Low,
+        )]));
+        loyal
+            .relations
+            .increase_associate_bond_by(&friend.entity_id, 0.2);
+        fickle
+            .relations
+            .increase_associate_bond_by(&friend.entity_id, 0.2);
+
+        // Spending time together brings them closer
+        let snapshot = EntitySnapshot::new(vec![friend.clone()]);
+        assert!(loyal.drift_bonds(&snapshot.view(), 0));
+        assert!(fickle.drift_bonds(&snapshot.view(), 0));
+        assert!(loyal.relations.bond(&friend.entity_id) > 0.2);
+
+        // But a long time apart wears that down, faster for some than others
+        friend.attributes.hex = Some(AxialHex::from((3, 0)));
+        let snapshot = EntitySnapshot::new(vec![friend.clone()]);
+        for tick_id in (0..SEPARATION_GRACE_TICKS * 3).step_by(BOND_DRIFT_INTERVAL) {
+            loyal.drift_bonds(&snapshot.view(), tick_id);
+            fickle.drift_bonds(&snapshot.view(), tick_id);
+        }
+        let loyal_bond = loyal.relations.bond(&friend.entity_id);
+        let fickle_bond = fickle.relations.bond(&friend.entity_id);
+        assert!(loyal_bond < 0.205);
+        assert!(fickle_bond < loyal_bond);
+        assert!(fickle_bond >= 0.0);
+    }
+
+    #[test]
+    fn test_opinions_sway_us_by_how_much_we_trust_who_gave_them() {
+        let mut listener = test_player("Listener", AxialHex::ZERO);
+        let friend = test_player("Friend", AxialHex::ZERO);
+        let stranger = test_player("Stranger", AxialHex::ZERO);
+        let rival = test_player("Rival", AxialHex::ZERO);
+        let subject = test_player("Subject", AxialHex::ZERO);
+        listener
+            .relations
+            .increase_associate_bond_by(&friend.entity_id, 0.8);
+        listener
+            .relations
+            .decrease_associate_bond_by(&rival.entity_id, 0.5);
+
+        // A friend's bad word puts us off them, a stranger's doesn't change a thing
+        let friend_sway =
+            listener.assimilate_opinion(&friend.entity_id, &subject.entity_id, &Opinion::Negative);
+        assert!(friend_sway < 0.0);
+        assert_eq!(
+            listener.assimilate_opinion(
+                &stranger.entity_id,
+                &subject.entity_id,
+                &Opinion::Positive
+            ),
+            0.0
+        );
+        assert_eq!(listener.relations.bond(&subject.entity_id), friend_sway);
+
+        // and someone we dislike speaking well of them only puts us off them more
+        let rival_sway =
+            listener.assimilate_opinion(&rival.entity_id, &subject.entity_id, &Opinion::Positive);
+        assert!(rival_sway < 0.0);
+        assert!(rival_sway.abs() < friend_sway.abs());
+
+        // We only remember the last thing we heard
+        assert_eq!(
+            listener.memes_mut().heard_opinion(&subject.entity_id),
+            Some(&Opinion::Positive)
+        );
+    }
+}
@@ -23,6 +23,15 @@ impl CharacteristicStrength {
     pub fn is_low(&self) -> bool {
         *self == CharacteristicStrength::Low
     }
+
+    /// A rough chance of succeeding at something that relies on this characteristic
+    pub fn success_chance(&self) -> f64 {
+        match self {
+            CharacteristicStrength::Low => 0.25,
+            CharacteristicStrength::Average => 0.5,
+            CharacteristicStrength::High => 0.8,
+        }
+    }
 }
 
 /// An entity can have a set of these with varying strengths
@@ -66,6 +75,10 @@ pub enum Characteristic {
     /// Low -> Stays to what they know
     Curiosity,
 
+    /// High -> Sticks by people, even when they haven't seen them in a long time
+    /// Low -> Out of sight, out of mind
+    Loyalty,
+
     // == Physical Ability ==
     /// High -> Easily muscle through obstacles that require physical strength
     /// Low -> Unable to perform some physical feats
@@ -85,6 +98,10 @@ pub enum Characteristic {
     /// High -> Great hearing, hear quiet things
     /// Low -> Impaired hearing
     Hearing,
+
+    /// High -> Thrown things land where they're meant to
+    /// Low -> Couldn't hit the side of a barn
+    Aim,
 }
 
 impl Characteristic {
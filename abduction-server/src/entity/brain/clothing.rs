@@ -0,0 +1,112 @@
+//! Clothing keeps the weather off
+//!
+//! Anything with `clothing` counts as worn while it's being carried, though only the warmest (and
+//! the most waterproof) thing we have on actually counts, there's no layering. The dead keep their
+//! clothes on, and anyone desperate enough can strip them off.
+
+use crate::entity::{snapshot::EntityView, Entity, EntityClothing};
+
+impl Entity {
+    /// Everything we are wearing
+    fn worn_clothing<'a>(
+        &'a self,
+        entity_view: &'a EntityView<'a>,
+    ) -> impl Iterator<Item = &'a EntityClothing> {
+        self.resolve_inventory(entity_view)
+            .filter_map(|e| e.attributes.clothing.as_ref())
+    }
+
+    /// How much less likely we are to get cold (0-1)
+    pub fn warmth(&self, entity_view: &EntityView) -> f32 {
+        self.worn_clothing(entity_view)
+            .map(|clothing| clothing.warmth)
+            .fold(0.0, f32::max)
+    }
+
+    /// How much less likely we are to get soaked by rain (0-1)
+    pub fn waterproofing(&self, entity_view: &EntityView) -> f32 {
+        self.worn_clothing(entity_view)
+            .map(|clothing| clothing.waterproofing)
+            .fold(0.0, f32::max)
+    }
+
+    /// Some clothing lying around here that would keep us warmer than what we've got on
+    pub fn warmer_clothing_nearby<'a>(
+        &self,
+        entity_view: &'a EntityView<'a>,
+    ) -> Option<&'a Entity> {
+        let warmth = self.warmth(entity_view);
+        entity_view
+            .in_hex(self.attributes.hex?)
+            .filter(|e| self.can_see(e))
+            .filter(|e| {
+                e.attributes
+                    .clothing
+                    .as_ref()
+                    .is_some_and(|clothing| clothing.warmth > warmth)
+            })
+            .max_by(|a, b| Self::clothing_warmth(a).total_cmp(&Self::clothing_warmth(b)))
+    }
+
+    /// A corpse here wearing something that would keep us warmer than what we've got on
+    pub fn warmer_corpse_nearby<'a>(&self, entity_view: &'a EntityView<'a>) -> Option<&'a Entity> {
+        let warmth = self.warmth(entity_view);
+        entity_view
+            .in_hex(self.attributes.hex?)
+            .filter(|e| e.attributes.corpse.is_some())
+            .find(|e| e.warmth(entity_view) > warmth)
+    }
+
+    fn clothing_warmth(entity: &Entity) -> f32 {
+        entity
+            .attributes
+            .clothing
+            .as_ref()
+            .map_or(0.0, |clothing| clothing.warmth)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        entity::{snapshot::EntitySnapshot, EntityAttributes, EntityItem},
+        hex::AxialHex,
+        mtch::test_match::test_player,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_only_best_clothing_counts() {
+        let clothing = |warmth: f32| Entity {
+            entity_id: Entity::id(),
+            name: "Jumper".into(),
+            attributes: EntityAttributes {
+                item: Some(EntityItem::default()),
+                clothing: Some(EntityClothing {
+                    warmth,
+                    waterproofing: 0.0,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let thin = clothing(0.2);
+        let thick = clothing(0.6);
+
+        let mut player = test_player("Wearer", AxialHex::ZERO);
+        player
+            .relations
+            .inventory_mut()
+            .insert(thin.entity_id.clone());
+        player
+            .relations
+            .inventory_mut()
+            .insert(thick.entity_id.clone());
+
+        let snapshot = EntitySnapshot::new(vec![thin, thick]);
+        let view = snapshot.view();
+        assert_eq!(player.warmth(&view), 0.6);
+        assert_eq!(player.waterproofing(&view), 0.0);
+    }
+}
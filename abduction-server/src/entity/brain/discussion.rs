@@ -81,11 +81,16 @@ use tracing::{info, warn};
 
 use crate::{
     entity::{
-        brain::{actor_action::ActorAction, focus::ActorFocus, meme::Meme, ActorActionResult},
+        brain::{
+            actor_action::{ActorAction, ActorActionSideEffect},
+            focus::ActorFocus,
+            meme::{Meme, Witnessed},
+            ActorActionResult,
+        },
         Entity, EntityId,
     },
     event::{builder::GameEventBuilder, GameEventKind, GameEventTarget},
-    logs::{AsEntityId, GameLog, GameLogBody},
+    logs::{AsEntityId, GameLog, GameLogBody, LogChannel},
     mtch::ActionCtx,
 };
 
@@ -126,6 +131,15 @@ pub enum DiscussionLeadAction {
     AskPersonal { topic: PersonalTopic },
     #[strum(to_string = "info:{topic}")]
     AskForInfo { topic: InfoTopic },
+    /// Offer one of our items in exchange for one of theirs
+    #[strum(to_string = "trade:{offer},{want}")]
+    ProposeTrade { offer: EntityId, want: EntityId },
+    /// Bring up something we saw happen (e.g "did you see that storm?")
+    #[strum(to_string = "recall:{event}")]
+    RecallEvent { event: Witnessed },
+    /// Talk about someone we lost (see `grief`)
+    #[strum(to_string = "remember:{entity_id},{name}")]
+    RememberLost { entity_id: EntityId, name: String },
 }
 
 impl FromStr for DiscussionLeadAction {
@@ -145,6 +159,27 @@ impl FromStr for DiscussionLeadAction {
             "info" => Ok(DiscussionLeadAction::AskForInfo {
                 topic: rest.parse()?,
             }),
+            "trade" => {
+                let (offer, want) = rest
+                    .split_once(",")
+                    .ok_or(anyhow!("Malformed trade proposal"))?;
+                Ok(DiscussionLeadAction::ProposeTrade {
+                    offer: offer.parse()?,
+                    want: want.parse()?,
+                })
+            }
+            "recall" => Ok(DiscussionLeadAction::RecallEvent {
+                event: rest.parse()?,
+            }),
+            "remember" => {
+                let (entity_id, name) = rest
+                    .split_once(",")
+                    .ok_or(anyhow!("Malformed remembrance"))?;
+                Ok(DiscussionLeadAction::RememberLost {
+                    entity_id: entity_id.parse()?,
+                    name: name.to_owned(),
+                })
+            }
             _ => Err(anyhow!(
                 "Failed to parse discussion lead action, unkown tag {tag}"
             )),
@@ -153,7 +188,21 @@ impl FromStr for DiscussionLeadAction {
 }
 
 /// An opinion on an entity
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Default,
+    strum::Display,
+    strum::EnumString,
+)]
+#[strum(serialize_all = "snake_case")]
 #[qubit::ts]
 pub enum Opinion {
     Positive,
@@ -169,7 +218,11 @@ pub enum Opinion {
 pub enum DiscussionRespondAction {
     /// Given an opinion on some entity
     /// derived from relations
-    GiveOpinion { opinion: Opinion },
+    GiveOpinion {
+        #[serde(default)]
+        entity_id: EntityId,
+        opinion: Opinion,
+    },
 
     /// Give an answer to some personal question
     /// (NOTE: this includes the resolved display of the answer)
@@ -188,6 +241,25 @@ pub enum DiscussionRespondAction {
     /// Refuse to answer a question because its too personal / rude
     /// (What this looks like may vary between entities / instances)
     Balk,
+
+    /// Agree to a proposed trade, the items are exchanged
+    /// (offer/want are from the perspective of the proposer)
+    AcceptTrade { offer: EntityId, want: EntityId },
+
+    /// Turn down a proposed trade
+    DeclineTrade,
+
+    /// Turn down a proposed trade, but suggest a different one
+    /// (offer/want are from the perspective of the one countering)
+    CounterTrade { offer: EntityId, want: EntityId },
+
+    /// React to something they brought up
+    /// (either we were there too, or it's news to us)
+    ReactToEvent { event: Witnessed, saw_it: bool },
+
+    /// Comfort them about someone they lost
+    /// (grieving_too -> we lost them too)
+    Console { grieving_too: bool },
 }
 
 #[derive(
@@ -237,6 +309,7 @@ impl FromStr for PersonalTopic {
 pub enum InfoTopic {
     WaterSourceLocation,
     ShelterLocation,
+    Gossip,
 }
 
 impl FromStr for InfoTopic {
@@ -290,6 +363,18 @@ impl Entity {
             }
         }
 
+        // Can only accept a trade if we both still have the items
+        if let DiscussionAction::Respond(DiscussionRespondAction::AcceptTrade { offer, want }) =
+            action
+        {
+            let we_have_want = self.relations.inventory().any(|id| id == want);
+            let they_have_offer = interlocutor.relations.inventory().any(|id| id == offer);
+            if !we_have_want || !they_have_offer {
+                warn!("Attempted to accept trade for items no longer held");
+                return ActorActionResult::NoEffect;
+            }
+        }
+
         // Always lose interest
         // but more if we take the "lose interest" action
         let interest_loss = match action {
@@ -355,32 +440,137 @@ impl Entity {
         // Emit a log about the thing we said/did
         match action {
             DiscussionAction::Lead(discussion_lead_action) => {
-                ctx.send_log(GameLog::entity_pair(
-                    self,
-                    interlocutor,
-                    GameLogBody::EntityAsk {
-                        ask: discussion_lead_action.clone(),
-                    },
-                ));
+                ctx.send_log(
+                    GameLog::entity_pair(
+                        self,
+                        interlocutor,
+                        GameLogBody::EntityAsk {
+                            ask: discussion_lead_action.clone(),
+                        },
+                    )
+                    .with_channel(LogChannel::Local),
+                );
             }
             DiscussionAction::Respond(discussion_respond_action) => {
-                ctx.send_log(GameLog::entity_pair(
-                    self,
-                    interlocutor,
-                    GameLogBody::EntityRespond {
-                        respond: discussion_respond_action.clone(),
-                    },
-                ));
+                ctx.send_log(
+                    GameLog::entity_pair(
+                        self,
+                        interlocutor,
+                        GameLogBody::EntityRespond {
+                            respond: discussion_respond_action.clone(),
+                        },
+                    )
+                    .with_channel(LogChannel::Local),
+                );
             }
             DiscussionAction::LoseInterest => {
-                ctx.send_log(GameLog::entity_pair(
-                    self,
-                    interlocutor,
-                    GameLogBody::EntityLoseInterest,
-                ));
+                ctx.send_log(
+                    GameLog::entity_pair(self, interlocutor, GameLogBody::EntityLoseInterest)
+                        .with_channel(LogChannel::Local),
+                );
             }
         }
 
+        // Accepting a trade means actually handing the items over
+        if let DiscussionAction::Respond(DiscussionRespondAction::AcceptTrade { offer, want }) =
+            action
+        {
+            self.relations.inventory_mut().remove(want);
+            self.relations.inventory_mut().insert(offer.clone());
+            self.relations.increase_associate_bond(interlocutor.id());
+            return ActorActionResult::SideEffect(ActorActionSideEffect::ExchangeItems {
+                entity_id: interlocutor.id().clone(),
+                gained: want.clone(),
+                lost: offer.clone(),
+            });
+        }
+
+        // Having been through the same thing brings people together
+        // (and so does being there for someone)
+        if let DiscussionAction::Respond(
+            DiscussionRespondAction::ReactToEvent { saw_it: true, .. }
+            | DiscussionRespondAction::Console { .. },
+        ) = action
+        {
+            self.relations.increase_associate_bond(interlocutor.id());
+        }
+
         ActorActionResult::Ok
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::EntityId,
+        hex::AxialHex,
+        logs::GameLogBody,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_discussion_takes_turns_then_ends() {
+        let builder = TestMatch::builder().seed(1);
+        let mut lead = test_player("Lead", AxialHex::ZERO);
+        let mut other = test_player("Other", AxialHex::ZERO);
+        let lead_id = lead.entity_id.clone();
+        let other_id = other.entity_id.clone();
+        lead.attributes.focus = Some(ActorFocus::Discussion {
+            with: other_id.clone(),
+            interest: 4,
+            is_lead: true,
+        });
+        other.attributes.focus = Some(ActorFocus::Discussion {
+            with: lead_id.clone(),
+            interest: 4,
+            is_lead: false,
+        });
+
+        let mut test_match = builder.with_entity(lead).with_entity(other).build().await;
+        let said_farewell = |m: &TestMatch, id: &EntityId| {
+            m.has_log_by(id, |body| matches!(body, GameLogBody::EntityFarewell))
+        };
+        let ended = test_match
+            .tick_until(30, |m| {
+                said_farewell(m, &lead_id) || said_farewell(m, &other_id)
+            })
+            .await;
+        assert!(ended, "Discussion never ended");
+
+        // They should have taken turns
+        assert!(test_match.has_log_by(&lead_id, |body| matches!(
+            body,
+            GameLogBody::EntityAsk { .. }
+        )));
+        assert!(test_match.has_log_by(&other_id, |body| {
+            matches!(body, GameLogBody::EntityRespond { .. })
+        }));
+
+        // And whoever said farewell actually left
+        let leaver_id = if said_farewell(&test_match, &lead_id) {
+            &lead_id
+        } else {
+            &other_id
+        };
+        assert!(!matches!(
+            test_match.entity(leaver_id).unwrap().attributes.focus,
+            Some(ActorFocus::Discussion { .. })
+        ));
+
+        // Leaving the other to notice and stop talking too
+        let in_discussion = |m: &TestMatch, id: &EntityId| {
+            matches!(
+                m.entity(id).unwrap().attributes.focus,
+                Some(ActorFocus::Discussion { .. })
+            )
+        };
+        let both_left = test_match
+            .tick_until(3, |m| {
+                !in_discussion(m, &lead_id) && !in_discussion(m, &other_id)
+            })
+            .await;
+        assert!(both_left, "One participant was left talking to no one");
+        assert!(test_match.manager.check_invariants().is_empty());
+    }
+}
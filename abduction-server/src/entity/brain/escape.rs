@@ -0,0 +1,101 @@
+//! Broken escape pods, a way out of the match for anyone willing to put the work in
+//!
+//! Some matches start with an escape pod or two lying broken somewhere, each missing a couple of
+//! parts that are left lying around the world (see `generate_escape_pods`). Planners who come
+//! across a pod remember where it is, hang onto any parts they find, and have a go at fitting
+//! them. It's fiddly work, so the less organised fumble it more often than not. Whoever fits the
+//! last part climbs in and is gone, escaping the match early.
+
+use rand::Rng;
+
+use crate::{
+    entity::{
+        brain::characteristic::{Characteristic, CharacteristicStrength},
+        snapshot::EntityView,
+        Entity, EntityEscapePod, EntityId, PodPart,
+    },
+    rng::{audited_bool, RollKind},
+};
+
+impl EntityEscapePod {
+    /// Does it still need some part?
+    pub fn needs(&self, part: PodPart) -> bool {
+        self.missing.contains(&part)
+    }
+
+    /// Would one more part get it flying?
+    pub fn nearly_fixed(&self) -> bool {
+        self.missing.len() <= 1
+    }
+}
+
+impl Entity {
+    /// How likely we are to get a part fitted on any one go
+    pub fn pod_repair_chance(&self) -> f64 {
+        match self.characteristic(Characteristic::Planning) {
+            CharacteristicStrength::Low => 0.1,
+            CharacteristicStrength::Average => 0.35,
+            CharacteristicStrength::High => 0.7,
+        }
+    }
+
+    /// Whether we manage to get a part fitted this go
+    pub fn fits_pod_part(&self, rng: &mut impl Rng) -> bool {
+        audited_bool(
+            rng,
+            RollKind::PodRepair,
+            Some(&self.entity_id),
+            self.pod_repair_chance(),
+        )
+    }
+
+    /// Something we are carrying that some pod still needs (and which part it is)
+    pub fn part_for_pod(
+        &self,
+        pod: &EntityEscapePod,
+        entity_view: &EntityView,
+    ) -> Option<(EntityId, PodPart)> {
+        self.resolve_inventory(entity_view).find_map(|e| {
+            let part = e.attributes.pod_part?;
+            pod.needs(part).then(|| (e.entity_id.clone(), part))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{generate::generate_pod_part, snapshot::EntitySnapshot},
+        hex::AxialHex,
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_only_needed_parts_fit() {
+        let pod = EntityEscapePod {
+            missing: vec![PodPart::FuelCell, PodPart::Thruster],
+        };
+        let seal = generate_pod_part(PodPart::HatchSeal, AxialHex::ZERO);
+        let thruster = generate_pod_part(PodPart::Thruster, AxialHex::ZERO);
+
+        let mut player = test_player("Tinkerer", AxialHex::ZERO);
+        player
+            .relations
+            .inventory_mut()
+            .insert(seal.entity_id.clone());
+        let snapshot = EntitySnapshot::new(vec![player.clone(), seal.clone(), thruster.clone()]);
+        assert_eq!(player.part_for_pod(&pod, &snapshot.view()), None);
+
+        player
+            .relations
+            .inventory_mut()
+            .insert(thruster.entity_id.clone());
+        let snapshot = EntitySnapshot::new(vec![player.clone(), seal, thruster.clone()]);
+        assert_eq!(
+            player.part_for_pod(&pod, &snapshot.view()),
+            Some((thruster.entity_id, PodPart::Thruster))
+        );
+        assert!(!pod.nearly_fixed());
+    }
+}
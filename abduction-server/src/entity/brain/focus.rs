@@ -8,17 +8,30 @@ use crate::{
             actor_action::ActorAction,
             characteristic::Characteristic,
             discussion::{DiscussionAction, DiscussionLeadAction, InfoTopic, PersonalTopic},
+            gathering::{self, DISTRESS_LIMIT_FOR_GATHERING},
+            grief,
             motivator::{self, MotivatorKey},
+            rafting,
+            reputation::ALLY_BOND,
+            rescue::DISTRESS_LIMIT_FOR_GUARDING,
             signal::Signal,
         },
+        world::TimeOfDay,
         EntityId,
     },
     logs::AsEntityId,
+    mtch::features::FeatureFlag,
 };
 
 pub const BOND_ERROR: f32 = 0.1; // 10% for now
 pub const BOND_REQ_FOR_PERSONAL_BASE: f32 = 0.4; // TODO: move this, also check its reasonable
 
+/// Above this distress, an entity is too busy looking after themselves to travel with someone
+pub const DISTRESS_LIMIT_FOR_TRAVEL: f32 = 0.6;
+
+/// Above this distress (from anything other than hunger), an entity gives up fishing to deal with it
+pub const DISTRESS_LIMIT_FOR_FISHING: f32 = 0.6;
+
 /// Entities can focus on a certain task or objective. They can also pull other entities into a focus, affecting both of them.
 /// When a focus is active, the action-selection logic is unique.
 ///
@@ -62,6 +75,53 @@ pub enum ActorFocus {
     /// - increases boredom
     /// - blocks certain other actions
     Sheltering { shelter_entity_id: EntityId },
+
+    /// Travelling around with some other entity
+    /// the leader wanders and the other follows them
+    TravelTogether {
+        /// Id of entity travelling with
+        with: EntityId,
+
+        /// How interested in staying together we are
+        /// at 0, we part ways
+        interest: usize,
+
+        /// When true, we choose where to go
+        /// When false, we follow the other entity
+        is_leader: bool,
+    },
+
+    /// Fishing at a lake
+    /// the longer we wait, the more likely we are to catch something
+    Fishing {
+        /// How many ticks we've spent waiting for a bite
+        ticks_invested: usize,
+    },
+
+    /// Keeping watch over someone while they sleep
+    /// (see `rescue`)
+    Guarding {
+        /// Id of entity being watched over
+        ward: EntityId,
+    },
+
+    /// Sitting around a campfire at night with others, taking turns telling stories
+    /// (see `gathering`)
+    Gathering {
+        /// Id of the campfire we're sat around
+        campfire: EntityId,
+
+        /// How many stories we've told so far
+        /// (whoever has told the fewest goes next)
+        stories_told: usize,
+    },
+
+    /// Aboard a raft, out on (or about to set off across) open water
+    /// (see `rafting`)
+    Rafting {
+        /// Id of the raft we're aboard
+        raft: EntityId,
+    },
 }
 
 impl Signal for ActorFocus {
@@ -71,13 +131,141 @@ impl Signal for ActorFocus {
         actions: &mut super::signal::WeightedActorActions,
     ) {
         match self {
-            ActorFocus::Unfocused => {}
+            ActorFocus::Unfocused => {
+                // If someone we are close to is here and neither of us has anything pressing,
+                // we might head off together
+                let Some(hex) = ctx.entity.attributes.hex else {
+                    return;
+                };
+                if ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_TRAVEL {
+                    return;
+                }
+                for companion in ctx.entities.in_hex(hex).filter(|e| {
+                    e.entity_id != ctx.entity.entity_id
+                        && matches!(e.attributes.focus, Some(ActorFocus::Unfocused))
+                        && e.attributes.motivators.distress() <= DISTRESS_LIMIT_FOR_TRAVEL
+                        && ctx.entity.relations.bond(&e.entity_id) >= ALLY_BOND
+                        && e.relations.bond(ctx.entity.id()) >= ALLY_BOND
+                }) {
+                    actions.add(
+                        5,
+                        ActorAction::StartTravellingWith {
+                            entity_id: companion.entity_id.clone(),
+                        },
+                    );
+                }
+
+                // At night, we might sit around a fire with them instead
+                gathering::act_on_campfires(ctx, actions);
+
+                // Or go and remember someone we lost
+                grief::act_on_grief(ctx, actions);
+
+                // Or get out on the water
+                rafting::act_on_rafts(ctx, actions);
+            }
+
+            ActorFocus::Rafting { raft } => {
+                rafting::act_aboard(raft, ctx, actions);
+            }
 
             ActorFocus::Sleeping { .. } => {
                 actions.add(10, ActorAction::Sleep);
             }
 
-            ActorFocus::Sheltering { .. } => {
+            ActorFocus::TravelTogether { with, .. } => {
+                // Are they still travelling with us?
+                let companion = ctx.entities.by_id(with).filter(|e| {
+                    matches!(
+                        &e.attributes.focus,
+                        Some(ActorFocus::TravelTogether { with: other_with, .. }) if other_with == ctx.entity.id()
+                    )
+                });
+
+                // If they've left, or either of us has more important things to do, we go our own ways
+                let Some(companion) = companion else {
+                    actions.add(1000, ActorAction::PartWays);
+                    return;
+                };
+                if ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_TRAVEL
+                    || companion.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_TRAVEL
+                {
+                    actions.add(1000, ActorAction::PartWays);
+                    return;
+                }
+
+                actions.add(10, ActorAction::TravelTogether);
+            }
+
+            ActorFocus::Fishing { .. } => {
+                // Nothing is worth starving to death over, but we wont sit here if something else might kill us
+                let motivators = &ctx.entity.attributes.motivators;
+                let pressing = [
+                    motivators.get_motivation::<motivator::Hurt>(),
+                    motivators.get_motivation::<motivator::Thirst>(),
+                    motivators.get_motivation::<motivator::Cold>(),
+                    motivators.get_motivation::<motivator::Sickness>(),
+                ]
+                .into_iter()
+                .flatten()
+                .fold(0.0, f32::max);
+                if pressing > DISTRESS_LIMIT_FOR_FISHING {
+                    actions.add(1000, ActorAction::StopFishing);
+                    return;
+                }
+
+                actions.add(10, ActorAction::Fish);
+            }
+
+            ActorFocus::Guarding { ward } => {
+                // Once they're up (or gone), or we've got our own problems, there's no more to do
+                let still_asleep = ctx.entities.by_id(ward).is_some_and(|e| {
+                    e.attributes.hex == ctx.entity.attributes.hex
+                        && matches!(e.attributes.focus, Some(ActorFocus::Sleeping { .. }))
+                });
+                if !still_asleep
+                    || ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_GUARDING
+                {
+                    actions.add(1000, ActorAction::StopGuarding);
+                    return;
+                }
+
+                actions.add(
+                    10,
+                    ActorAction::StandGuard {
+                        entity_id: ward.clone(),
+                    },
+                );
+            }
+
+            ActorFocus::Gathering { campfire, .. } => {
+                // Once the night is over, the fire is out, or everyone else has gone, we head off
+                let Some(hex) = ctx.entity.attributes.hex else {
+                    return;
+                };
+                let fire_lit = ctx
+                    .entities
+                    .by_id(campfire)
+                    .is_some_and(|e| e.attributes.hex == Some(hex));
+                let gathered = gathering::gathered_around(campfire, hex, ctx.entities).count();
+                if !fire_lit
+                    || gathered < 2
+                    || ctx.world_state.time_of_day != TimeOfDay::Night
+                    || ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_GATHERING
+                {
+                    actions.add(1000, ActorAction::LeaveGathering);
+                    return;
+                }
+
+                // If it's our turn, we tell a story, otherwise we sit and listen
+                let our_turn = gathering::storyteller(campfire, hex, ctx.entities)
+                    .is_some_and(|e| e.entity_id == ctx.entity.entity_id);
+                if our_turn {
+                    actions.add(10, ActorAction::TellStory);
+                }
+            }
+
+            ActorFocus::Sheltering { shelter_entity_id } => {
                 // Get less cold and wet
                 actions.add(5, ActorAction::ReduceMotivator(MotivatorKey::Cold));
                 actions.add(5, ActorAction::ReduceMotivator(MotivatorKey::Saturation));
@@ -99,6 +287,29 @@ impl Signal for ActorFocus {
                 if cold == saturation && cold == 0.0 {
                     actions.add(10, ActorAction::LeaveShelter);
                 }
+
+                // Patch it up if its starting to fall apart
+                let shelter = ctx
+                    .entities
+                    .by_id(shelter_entity_id)
+                    .and_then(|e| e.attributes.shelter.as_ref());
+                let durability = shelter.map(|s| s.durability).unwrap_or(1.0);
+                if durability < 0.6 {
+                    actions.add(
+                        if durability < 0.3 { 30 } else { 10 },
+                        ActorAction::RepairShelter,
+                    );
+                }
+
+                // Nobody has claimed this place, it could be ours
+                // (more aggressive people are more territorial)
+                if shelter.is_some_and(|s| s.claimed_by.is_none()) {
+                    let aggression = ctx.entity.characteristic(Characteristic::Aggression);
+                    actions.add(
+                        if aggression.is_high() { 15 } else { 3 },
+                        ActorAction::ClaimHex,
+                    );
+                }
             }
 
             ActorFocus::Discussion { is_lead, with, .. } => {
@@ -107,6 +318,19 @@ impl Signal for ActorFocus {
                     return;
                 };
 
+                // If they've stopped talking to us (or are gone), all we can do is leave too
+                // (any discussion action notices this and ends the discussion)
+                let reciprocated = ctx.entities.by_id(with).is_some_and(|other| {
+                    matches!(
+                        &other.attributes.focus,
+                        Some(ActorFocus::Discussion { with: other_with, .. }) if other_with == ctx.entity.id()
+                    )
+                });
+                if !reciprocated {
+                    actions.add(100, DiscussionAction::LoseInterest.into());
+                    return;
+                }
+
                 // If we are the lead, we take lead actions
                 // (but dont greet, we assume thats already happaned at this point)
                 if *is_lead {
@@ -154,13 +378,57 @@ impl Signal for ActorFocus {
                         },
                     ));
 
+                    // Or just hear what people have been up to
+                    lead_actions.push((
+                        10,
+                        DiscussionLeadAction::AskForInfo {
+                            topic: InfoTopic::Gossip,
+                        },
+                    ));
+
+                    // Or make small talk about something we saw happen
+                    for event in my_memes.witnessed() {
+                        lead_actions.push((
+                            8,
+                            DiscussionLeadAction::RecallEvent {
+                                event: event.clone(),
+                            },
+                        ));
+                    }
+
+                    // and we can't help but bring up anyone we're still grieving
+                    for loss in ctx.entity.losses() {
+                        lead_actions.push((
+                            25,
+                            DiscussionLeadAction::RememberLost {
+                                entity_id: loss.entity_id.clone(),
+                                name: loss.name.clone(),
+                            },
+                        ));
+                    }
+
+                    // We might try to trade for something of theirs we need more than they do
+                    if let Some((offer, want)) = ctx
+                        .entity
+                        .best_trade_with(interlocutor, ctx.entities)
+                        .filter(|_| ctx.has_feature(FeatureFlag::Trading))
+                    {
+                        lead_actions.push((
+                            15,
+                            DiscussionLeadAction::ProposeTrade {
+                                offer: offer.entity_id.clone(),
+                                want: want.entity_id.clone(),
+                            },
+                        ));
+                    }
+
                     // During the conversation, we attempt to keep track of the others connection w/ us
                     // if we think we are close enough, we can ask personal questions
                     // but this has variance (+-rng) so we might get it wrong
                     // people also just have different tolerances for responding to personal questions
                     // they just dont want to talk about themselves...
                     let mut estimated_bond = interlocutor.relations.bond(ctx.entity.id())
-                        + rand::rng().random_range(-BOND_ERROR..=BOND_ERROR);
+                        + crate::rng::rng().random_range(-BOND_ERROR..=BOND_ERROR);
 
                     // If we are friendlier, assume they like us more
                     // (and vice versa)
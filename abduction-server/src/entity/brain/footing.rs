@@ -0,0 +1,97 @@
+//! Getting about in rough weather
+//!
+//! In wind and heavy rain, moving somewhere isn't a given (see `WeatherKind::movement_hindrance`).
+//! When the weather gets in the way, the strong can push on through anyway, the nimble keep their
+//! feet but don't get anywhere, and everyone else goes over and hurts themselves. The crew have the
+//! right gear for it and are never held up.
+
+use rand::Rng;
+
+use crate::{
+    entity::{brain::characteristic::Characteristic, world::WeatherKind, Entity},
+    has_markers,
+};
+
+/// How much of a chance strength gives of pushing on through when held up
+const PUSH_ON_SCALE: f64 = 0.5;
+
+/// How much slipping over hurts
+pub const SLIP_HURT: f32 = 2.0;
+
+/// How some attempt to move through the weather went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Footing {
+    /// Got where we were going
+    Sure,
+
+    /// Kept our feet, but didn't get anywhere
+    HeldUp,
+
+    /// Went over
+    Slipped,
+}
+
+impl Entity {
+    /// Try to make our way through some weather
+    pub fn check_footing(&self, weather: &WeatherKind, rng: &mut impl Rng) -> Footing {
+        if has_markers!(self, Crew) || !rng.random_bool(weather.movement_hindrance()) {
+            return Footing::Sure;
+        }
+
+        let strength = self
+            .characteristic(Characteristic::Strength)
+            .success_chance();
+        if rng.random_bool(strength * PUSH_ON_SCALE) {
+            return Footing::Sure;
+        }
+
+        let acrobatics = self
+            .characteristic(Characteristic::Acrobatics)
+            .success_chance();
+        if rng.random_bool(acrobatics) {
+            Footing::HeldUp
+        } else {
+            Footing::Slipped
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{
+        entity::brain::characteristic::CharacteristicStrength, hex::AxialHex,
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_rough_weather_trips_up_the_clumsy() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut clumsy = test_player("Clumsy", AxialHex::ZERO);
+        clumsy.attributes.characteristics = Some(HashMap::from([
+            (Characteristic::Strength, CharacteristicStrength::Low),
+            (Characteristic::Acrobatics, CharacteristicStrength::Low),
+        ]));
+        let mut nimble = test_player("Nimble", AxialHex::ZERO);
+        nimble.attributes.characteristics = Some(HashMap::from([
+            (Characteristic::Strength, CharacteristicStrength::High),
+            (Characteristic::Acrobatics, CharacteristicStrength::High),
+        ]));
+
+        // Nobody has any trouble on a nice day
+        assert!(
+            (0..100).all(|_| clumsy.check_footing(&WeatherKind::Lovely, &mut rng) == Footing::Sure)
+        );
+
+        let slips = |entity: &Entity, rng: &mut rand::rngs::StdRng| {
+            (0..1000)
+                .filter(|_| entity.check_footing(&WeatherKind::Hurricane, rng) == Footing::Slipped)
+                .count()
+        };
+        assert!(slips(&clumsy, &mut rng) > slips(&nimble, &mut rng));
+    }
+}
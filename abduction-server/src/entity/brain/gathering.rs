@@ -0,0 +1,202 @@
+//! Sitting around a campfire at night, telling stories
+//!
+//! Anyone carrying something to burn might light a campfire at night, and once a few players who
+//! get on are sitting around one, they can settle in for a `Gathering`. Everyone gathered there
+//! takes turns telling a story about their life before the abduction (who has told the fewest goes
+//! next), which cheers up everyone listening and brings them a little closer to whoever told it.
+//! The gathering breaks up once the night is over, the fire goes out, or there's nobody left to
+//! listen. Campfires are fires like any other, so they keep predators away and rain puts them out.
+
+use rand::seq::IndexedRandom;
+
+use crate::{
+    content::content,
+    entity::{
+        brain::{
+            actor_action::ActorAction,
+            focus::ActorFocus,
+            signal::{SignalContext, WeightedActorActions},
+        },
+        snapshot::EntityView,
+        world::TimeOfDay,
+        Entity, EntityId,
+    },
+    has_markers,
+    hex::AxialHex,
+};
+
+/// How many players (incl. us) need to be around a fire to settle in for a gathering
+pub const GATHERING_SIZE: usize = 3;
+
+/// Above this distress, there are more important things to do than sit around a fire
+pub const DISTRESS_LIMIT_FOR_GATHERING: f32 = 0.5;
+
+/// How much a story cheers up (and takes the boredom off) everyone who hears it
+pub const STORY_CHEER: f32 = 0.15;
+
+/// Is some entity a campfire? (a fire someone lit on purpose, that won't hurt anyone)
+pub fn is_campfire(entity: &Entity) -> bool {
+    has_markers!(entity, Fire) && entity.attributes.hazard.is_none()
+}
+
+/// Everyone gathered around some campfire
+pub fn gathered_around<'a>(
+    campfire_id: &'a EntityId,
+    hex: AxialHex,
+    entity_view: &'a EntityView<'a>,
+) -> impl Iterator<Item = &'a Entity> {
+    entity_view.in_hex(hex).filter(move |e| {
+        matches!(
+            &e.attributes.focus,
+            Some(ActorFocus::Gathering { campfire, .. }) if campfire == campfire_id
+        )
+    })
+}
+
+/// Whose turn it is to tell a story around some campfire
+/// (whoever has told the fewest so far)
+pub fn storyteller<'a>(
+    campfire_id: &'a EntityId,
+    hex: AxialHex,
+    entity_view: &'a EntityView<'a>,
+) -> Option<&'a Entity> {
+    gathered_around(campfire_id, hex, entity_view).min_by_key(|e| match &e.attributes.focus {
+        Some(ActorFocus::Gathering { stories_told, .. }) => (*stories_told, &e.entity_id),
+        _ => (usize::MAX, &e.entity_id),
+    })
+}
+
+impl Entity {
+    /// Something to tell everyone about our life before all this
+    /// (none if we don't have a life before all this)
+    pub fn random_story(&self, rng: &mut impl rand::Rng) -> Option<String> {
+        let background = self.attributes.background.as_ref()?;
+        let career = if background.is_retired {
+            format!("about their years as a {}", background.career)
+        } else {
+            format!("about life as a {}", background.career)
+        };
+        let stories = [
+            career,
+            format!("about growing up in {}", background.location_string()),
+            format!(
+                "about how they hope {}",
+                content().hope_text(&background.hope)
+            ),
+            format!(
+                "about their fear of {}",
+                content().fear_text(&background.fear)
+            ),
+        ];
+        stories.choose(rng).cloned()
+    }
+}
+
+/// With no focus, we might light a fire or settle in around one
+pub fn act_on_campfires(ctx: &SignalContext, actions: &mut WeightedActorActions) {
+    let Some(hex) = ctx.entity.attributes.hex else {
+        return;
+    };
+    if ctx.world_state.time_of_day != TimeOfDay::Night
+        || ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_GATHERING
+    {
+        return;
+    }
+
+    // Anyone we get on with who might sit with us
+    let company = ctx
+        .entities
+        .in_hex(hex)
+        .filter(|e| {
+            e.entity_id != ctx.entity.entity_id
+                && has_markers!(e, Player)
+                && matches!(
+                    e.attributes.focus,
+                    Some(ActorFocus::Unfocused | ActorFocus::Gathering { .. })
+                )
+                && ctx.entity.relations.bond(&e.entity_id) > 0.0
+        })
+        .count();
+
+    // No fire yet? We could get one going if we've got something to burn
+    let Some(campfire) = ctx.entities.in_hex(hex).find(|e| is_campfire(e)) else {
+        if ctx.entities.in_hex(hex).any(|e| has_markers!(e, Fire)) {
+            return;
+        }
+        if let Some(material) = ctx
+            .entity
+            .resolve_inventory(ctx.entities)
+            .find(|e| e.attributes.material.is_some())
+        {
+            actions.add(
+                if company > 0 { 8 } else { 2 },
+                ActorAction::LightCampfire {
+                    material: material.entity_id.clone(),
+                },
+            );
+        }
+        return;
+    };
+
+    if company + 1 >= GATHERING_SIZE {
+        actions.add(
+            15,
+            ActorAction::JoinGathering {
+                campfire: campfire.entity_id.clone(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        create_markers,
+        entity::{snapshot::EntitySnapshot, EntityAttributes},
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_gathered_take_turns_telling_stories() {
+        let campfire = Entity {
+            entity_id: Entity::id(),
+            name: "Campfire".into(),
+            markers: create_markers!(Fire, Inspectable),
+            attributes: EntityAttributes {
+                hex: Some(AxialHex::ZERO),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(is_campfire(&campfire));
+
+        let gathered = |name: &str, stories_told: usize| {
+            let mut player = test_player(name, AxialHex::ZERO);
+            player.attributes.focus = Some(ActorFocus::Gathering {
+                campfire: campfire.entity_id.clone(),
+                stories_told,
+            });
+            player
+        };
+        let told_two = gathered("Chatty", 2);
+        let told_one = gathered("Shy", 1);
+        let elsewhere = test_player("Elsewhere", AxialHex::ZERO);
+        let snapshot = EntitySnapshot::new(vec![
+            campfire.clone(),
+            told_two,
+            told_one.clone(),
+            elsewhere,
+        ]);
+        let view = snapshot.view();
+
+        assert_eq!(
+            gathered_around(&campfire.entity_id, AxialHex::ZERO, &view).count(),
+            2
+        );
+        assert_eq!(
+            storyteller(&campfire.entity_id, AxialHex::ZERO, &view).map(|e| &e.entity_id),
+            Some(&told_one.entity_id)
+        );
+    }
+}
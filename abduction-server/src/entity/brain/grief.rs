@@ -0,0 +1,259 @@
+//! Grieving for those we were close to, long after they're gone
+//!
+//! Mourning someone (see `ActorAction::MournEntity`) is over in a moment, but losing an ally stays
+//! with a player for a while. Whoever mourns an ally starts grieving them: for a couple of days their
+//! sadness can't fall below a floor (which fades as time goes on), they'll want to go back to their
+//! corpse (or where they died) to hold a memorial, which anyone else around who cared for them joins,
+//! and they'll bring them up when talking to others. Holding a memorial eases the grief a little.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{
+        brain::{
+            actor_action::ActorAction,
+            focus::ActorFocus,
+            motivator::{MotivatorKey, Sadness},
+            signal::{SignalContext, WeightedActorActions},
+        },
+        Entity, EntityId,
+    },
+    hex::AxialHex,
+    mtch::TickId,
+};
+
+/// How long (in ticks) someone is grieved for
+pub const GRIEF_TICKS: usize = 1200;
+
+/// How low sadness can fall right after losing someone
+/// (fading to nothing over `GRIEF_TICKS`)
+pub const GRIEF_SADNESS_FLOOR: f32 = 0.4;
+
+/// How much of the sadness floor is left once we've held a memorial
+pub const MEMORIAL_EASE: f32 = 0.5;
+
+/// How long (in ticks) after a death before we think about holding a memorial
+pub const MEMORIAL_AFTER_TICKS: usize = 200;
+
+/// Above this distress, there are more important things to do than hold a memorial
+pub const DISTRESS_LIMIT_FOR_MEMORIAL: f32 = 0.5;
+
+/// Everyone an entity is still grieving
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityGrief {
+    losses: Vec<Loss>,
+}
+
+/// Someone we lost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct Loss {
+    /// Who they were
+    pub entity_id: EntityId,
+
+    /// What they were called
+    pub name: String,
+
+    /// Where they died (if we know)
+    pub hex: Option<AxialHex>,
+
+    /// When we started grieving them
+    pub since: TickId,
+
+    /// Whether we've held a memorial for them yet
+    pub memorial_held: bool,
+}
+
+impl Loss {
+    /// How low sadness can fall, at some tick, while grieving them
+    pub fn sadness_floor(&self, tick_id: TickId) -> f32 {
+        let elapsed = tick_id.saturating_sub(self.since);
+        if elapsed >= GRIEF_TICKS {
+            return 0.0;
+        }
+        let fading = 1.0 - elapsed as f32 / GRIEF_TICKS as f32;
+        let eased = if self.memorial_held {
+            MEMORIAL_EASE
+        } else {
+            1.0
+        };
+        GRIEF_SADNESS_FLOOR * fading * eased
+    }
+}
+
+impl EntityGrief {
+    pub fn losses(&self) -> impl Iterator<Item = &Loss> {
+        self.losses.iter()
+    }
+
+    pub fn loss(&self, entity_id: &EntityId) -> Option<&Loss> {
+        self.losses.iter().find(|loss| &loss.entity_id == entity_id)
+    }
+}
+
+impl Entity {
+    /// Everyone we're still grieving
+    pub fn losses(&self) -> impl Iterator<Item = &Loss> {
+        self.attributes.grief.iter().flat_map(EntityGrief::losses)
+    }
+
+    /// Are we still grieving some entity?
+    pub fn is_grieving(&self, entity_id: &EntityId) -> bool {
+        self.losses().any(|loss| &loss.entity_id == entity_id)
+    }
+
+    /// Start grieving someone we've lost (unless we already are)
+    pub fn start_grieving(
+        &mut self,
+        entity_id: &EntityId,
+        name: String,
+        hex: Option<AxialHex>,
+        tick_id: TickId,
+    ) {
+        if self.is_grieving(entity_id) {
+            return;
+        }
+        self.attributes
+            .grief
+            .get_or_insert_default()
+            .losses
+            .push(Loss {
+                entity_id: entity_id.clone(),
+                name,
+                hex,
+                since: tick_id,
+                memorial_held: false,
+            });
+    }
+
+    /// Note that we've held (or been to) a memorial for someone
+    pub fn remember_memorial(&mut self, entity_id: &EntityId) {
+        if let Some(loss) = self
+            .attributes
+            .grief
+            .as_mut()
+            .and_then(|grief| grief.losses.iter_mut().find(|l| &l.entity_id == entity_id))
+        {
+            loss.memorial_held = true;
+        }
+    }
+
+    /// Carry on grieving, letting go of anyone lost long enough ago
+    /// and keeping our sadness from falling below the floor for the rest
+    pub fn keep_grieving(&mut self, tick_id: TickId) {
+        let Some(grief) = self.attributes.grief.as_mut() else {
+            return;
+        };
+        grief
+            .losses
+            .retain(|loss| tick_id.saturating_sub(loss.since) < GRIEF_TICKS);
+        let floor = grief
+            .losses
+            .iter()
+            .map(|loss| loss.sadness_floor(tick_id))
+            .fold(0.0, f32::max);
+        if grief.losses.is_empty() {
+            self.attributes.grief = None;
+        }
+
+        let sadness = self.attributes.motivators.get_motivation::<Sadness>();
+        if sadness.is_some_and(|sadness| sadness < floor) {
+            self.attributes
+                .motivators
+                .set_key(MotivatorKey::Sadness, floor);
+        }
+    }
+}
+
+/// Holding a memorial for anyone we're still grieving, once things have settled down a bit
+pub fn act_on_grief(ctx: &SignalContext, actions: &mut WeightedActorActions) {
+    if !matches!(ctx.focus, ActorFocus::Unfocused)
+        || ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_MEMORIAL
+    {
+        return;
+    }
+    for loss in ctx.entity.losses() {
+        if loss.memorial_held || ctx.tick_id.saturating_sub(loss.since) < MEMORIAL_AFTER_TICKS {
+            continue;
+        }
+        actions.add(
+            6,
+            ActorAction::HoldMemorial {
+                entity_id: loss.entity_id.clone(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::brain::test_signal::{EntityBuilder, SignalContextBuilder},
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_grief_keeps_sadness_up_until_it_fades() {
+        let mut player = test_player("Bereaved", AxialHex::ZERO);
+        let lost: EntityId = "lost".into();
+        player.start_grieving(&lost, "Lost".into(), Some(AxialHex::ZERO), 0);
+        let sadness = |player: &Entity| {
+            player
+                .attributes
+                .motivators
+                .get_motivation::<Sadness>()
+                .unwrap()
+        };
+
+        // Can't cheer up straight away
+        player
+            .attributes
+            .motivators
+            .set_key(MotivatorKey::Sadness, 0.0);
+        player.keep_grieving(10);
+        assert!(sadness(&player) > 0.0);
+
+        // A memorial eases it
+        let before = player.losses().next().unwrap().sadness_floor(10);
+        player.remember_memorial(&lost);
+        assert!(player.losses().next().unwrap().sadness_floor(10) < before);
+
+        // and eventually we let go
+        player
+            .attributes
+            .motivators
+            .set_key(MotivatorKey::Sadness, 0.0);
+        player.keep_grieving(GRIEF_TICKS);
+        assert_eq!(sadness(&player), 0.0);
+        assert!(!player.is_grieving(&lost));
+    }
+
+    #[test]
+    fn test_memorials_wait_for_things_to_settle() {
+        let lost: EntityId = "lost".into();
+        let mut bereaved = EntityBuilder::player("Bereaved").build();
+        bereaved.start_grieving(&lost, "Lost".into(), Some(AxialHex::ZERO), 0);
+        let holds_memorial = |tick_id: TickId, distress: f32| {
+            let mut bereaved = bereaved.clone();
+            bereaved
+                .attributes
+                .motivators
+                .set_key(MotivatorKey::Hunger, distress);
+            SignalContextBuilder::new(bereaved)
+                .tick(tick_id)
+                .with_ctx(|ctx| {
+                    let mut actions = WeightedActorActions::default();
+                    act_on_grief(ctx, &mut actions);
+                    actions
+                })
+                .max_weight_of(|action| matches!(action, ActorAction::HoldMemorial { .. }))
+                .is_some()
+        };
+
+        assert!(!holds_memorial(MEMORIAL_AFTER_TICKS - 1, 0.0));
+        assert!(holds_memorial(MEMORIAL_AFTER_TICKS, 0.0));
+        assert!(!holds_memorial(MEMORIAL_AFTER_TICKS, 0.8));
+    }
+}
@@ -0,0 +1,223 @@
+//! Little things players do to pass the time
+//!
+//! A player with nothing on their mind (unfocused, and not distressed) would otherwise just stand
+//! around, so every now and then they'll do one of these instead. They don't change anything, but
+//! which ones a player goes for depends on who they are: a woodworker carrying some wood will whittle
+//! it, a musician hums to themselves, the curious watch the stars on a clear night and so on. They're
+//! only ever raised at a low weight, so anything else a player wants to do comes first.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{
+        background::career::Career,
+        brain::{
+            actor_action::ActorAction,
+            characteristic::Characteristic,
+            focus::ActorFocus,
+            signal::{Signal, SignalContext, WeightedActorActions},
+        },
+        world::{EntityWorld, TimeOfDay, WeatherKind},
+    },
+    has_markers,
+};
+
+/// Above this distress, players have more on their mind than passing the time
+pub const DISTRESS_LIMIT_FOR_IDLING: f32 = 0.3;
+
+/// Chance that a content player does something to pass the time on a given turn
+/// (so that quiet stretches aren't wall to wall whittling)
+pub const IDLE_CHANCE: f64 = 0.25;
+
+/// Something a player might do to pass the time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum::VariantArray)]
+#[serde(rename_all = "snake_case")]
+#[qubit::ts]
+pub enum IdleBehavior {
+    /// Whittling away at some wood they're carrying
+    Whittle,
+
+    /// Humming a tune to themselves
+    Hum,
+
+    /// Looking up at the stars (only on a clear night)
+    Stargaze,
+
+    /// Stretching out their limbs
+    Stretch,
+
+    /// Drawing something in the dirt with a stick
+    Doodle,
+
+    /// Quietly watching whoever else is around
+    PeopleWatch,
+}
+
+impl IdleBehavior {
+    /// How likely some entity is to do this right now (0 -> not at all)
+    pub fn weight(&self, ctx: &SignalContext) -> usize {
+        let entity = ctx.entity;
+        let career = entity
+            .attributes
+            .background
+            .as_ref()
+            .map(|background| &background.career);
+        let is_retired = entity
+            .attributes
+            .background
+            .as_ref()
+            .is_some_and(|background| background.is_retired);
+
+        // Whether it's something they'd go for, given who they are
+        let suits_them = match self {
+            IdleBehavior::Whittle => {
+                if !entity
+                    .resolve_inventory(ctx.entities)
+                    .any(|e| e.attributes.material.is_some())
+                {
+                    return 0;
+                }
+                matches!(
+                    career,
+                    Some(Career::Woodworker | Career::Craftsman | Career::Sculptor)
+                )
+            }
+            IdleBehavior::Hum => {
+                entity.characteristic(Characteristic::Openness).is_high()
+                    || matches!(
+                        career,
+                        Some(Career::Musician | Career::Singer | Career::Composer)
+                    )
+            }
+            IdleBehavior::Stargaze => {
+                if !is_clear_night(ctx.world_state) {
+                    return 0;
+                }
+                entity.characteristic(Characteristic::Curiosity).is_high()
+                    || matches!(career, Some(Career::Astronomer))
+            }
+            IdleBehavior::Stretch => {
+                is_retired
+                    || entity.characteristic(Characteristic::Acrobatics).is_high()
+                    || matches!(
+                        career,
+                        Some(
+                            Career::YogaInstructor
+                                | Career::YogaTeacher
+                                | Career::PersonalTrainer
+                                | Career::AthleticTrainer
+                                | Career::ProfessionalAthlete
+                        )
+                    )
+            }
+            IdleBehavior::Doodle => matches!(
+                career,
+                Some(Career::Artist | Career::Painter | Career::Illustrator)
+            ),
+            IdleBehavior::PeopleWatch => {
+                let Some(hex) = entity.attributes.hex else {
+                    return 0;
+                };
+                if !ctx
+                    .entities
+                    .in_hex(hex)
+                    .any(|e| e.entity_id != entity.entity_id && has_markers!(e, Player))
+                {
+                    return 0;
+                }
+                entity.characteristic(Characteristic::Curiosity).is_high()
+                    && !entity
+                        .characteristic(Characteristic::Friendliness)
+                        .is_high()
+            }
+        };
+
+        if suits_them {
+            3
+        } else {
+            1
+        }
+    }
+}
+
+/// Is it a night where the stars can be seen?
+fn is_clear_night(world_state: &EntityWorld) -> bool {
+    world_state.time_of_day == TimeOfDay::Night
+        && !world_state.weather.is_raining()
+        && !matches!(
+            world_state.weather,
+            WeatherKind::Overcast | WeatherKind::Hurricane
+        )
+}
+
+/// Raises something to pass the time, for players with nothing else on their mind
+#[derive(Debug)]
+pub struct IdleSignal;
+
+impl Signal for IdleSignal {
+    fn act_on(&self, ctx: &SignalContext, actions: &mut WeightedActorActions) {
+        if !has_markers!(ctx.entity, Player)
+            || !matches!(ctx.focus, ActorFocus::Unfocused)
+            || ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_IDLING
+            || !crate::rng::rng().random_bool(IDLE_CHANCE)
+        {
+            return;
+        }
+
+        for behavior in <IdleBehavior as strum::VariantArray>::VARIANTS {
+            let weight = behavior.weight(ctx);
+            if weight > 0 {
+                actions.add(weight, ActorAction::Idle(*behavior));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::brain::{
+        characteristic::CharacteristicStrength,
+        test_signal::{EntityBuilder, SignalContextBuilder},
+    };
+
+    #[test]
+    fn test_stars_only_seen_on_clear_nights() {
+        let mut world = EntityWorld {
+            time_of_day: TimeOfDay::Night,
+            weather: WeatherKind::Lovely,
+            ..Default::default()
+        };
+        assert!(is_clear_night(&world));
+
+        world.weather = WeatherKind::HeavyRain;
+        assert!(!is_clear_night(&world));
+
+        world.weather = WeatherKind::Lovely;
+        world.time_of_day = TimeOfDay::Morning;
+        assert!(!is_clear_night(&world));
+    }
+
+    #[test]
+    fn test_the_curious_like_stargazing() {
+        let night = EntityWorld {
+            time_of_day: TimeOfDay::Night,
+            weather: WeatherKind::Lovely,
+            ..Default::default()
+        };
+        let stargazing = |player: EntityBuilder, world_state: EntityWorld| {
+            SignalContextBuilder::new(player.build())
+                .world_state(world_state)
+                .with_ctx(|ctx| IdleBehavior::Stargaze.weight(ctx))
+        };
+        let curious = || {
+            EntityBuilder::player("Curious")
+                .characteristic(Characteristic::Curiosity, CharacteristicStrength::High)
+        };
+
+        assert_eq!(stargazing(curious(), night.clone()), 3);
+        assert_eq!(stargazing(EntityBuilder::player("Incurious"), night), 1);
+        assert_eq!(stargazing(curious(), EntityWorld::default()), 0);
+    }
+}
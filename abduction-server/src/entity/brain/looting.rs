@@ -0,0 +1,159 @@
+//! Taking what the dead were carrying
+//!
+//! Everything a player was carrying stays with their corpse, so a death leaves a little pile of
+//! supplies behind. Anyone can go through it, though it isn't a good look, and anyone with a bit of
+//! empathy will hesitate first (and might not be able to go through with it at all).
+
+use rand::Rng;
+
+use crate::{
+    entity::{brain::characteristic::Characteristic, snapshot::EntityView, Entity},
+    rng::{audited_bool, RollKind},
+};
+
+impl Entity {
+    /// Some corpse here that is carrying something we want (that we could carry), and the thing
+    pub fn lootable_corpse_nearby<'a>(
+        &self,
+        entity_view: &'a EntityView<'a>,
+        want: impl Fn(&Entity) -> bool,
+    ) -> Option<(&'a Entity, &'a Entity)> {
+        let avail_space = self.available_inventory_load(entity_view);
+        entity_view
+            .in_hex(self.attributes.hex?)
+            .filter(|e| e.attributes.corpse.is_some() && self.can_see(e))
+            .find_map(|corpse| {
+                let item = corpse.resolve_inventory(entity_view).find(|item| {
+                    item.attributes
+                        .item
+                        .as_ref()
+                        .is_some_and(|i| i.heft <= avail_space)
+                        && want(item)
+                })?;
+                Some((corpse, item))
+            })
+    }
+
+    /// Whether we'd think twice before going through a corpse's things
+    /// (anyone who isn't short on empathy)
+    pub fn hesitates_to_loot(&self) -> bool {
+        !self.characteristic(Characteristic::Empathy).is_low()
+    }
+
+    /// Whether we can't bring ourselves to go through with looting a corpse after all
+    /// (more likely the more empathetic we are)
+    pub fn balks_at_looting(&self, rng: &mut impl Rng) -> bool {
+        if !self.hesitates_to_loot() {
+            return false;
+        }
+        let chance = self
+            .characteristic(Characteristic::Empathy)
+            .success_chance();
+        audited_bool(rng, RollKind::LootBalk, Some(&self.entity_id), chance)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        entity::{
+            brain::{characteristic::CharacteristicStrength, motivator, reputation::Deed},
+            generate::{generate_corpse, PropGenerator},
+            EntityId,
+        },
+        hex::AxialHex,
+        logs::GameLogBody,
+        mtch::{
+            features::FeatureFlag,
+            test_match::{test_player, TestMatch, TestMatchBuilder},
+        },
+    };
+
+    /// Someone starving (who won't think twice about looting) next to a corpse carrying some food
+    /// (returns the ids of the looter, the corpse and the food)
+    fn starving_looter_beside_corpse() -> (TestMatchBuilder, EntityId, EntityId, EntityId) {
+        // (nowhere else to go looking for food)
+        let builder = TestMatch::builder().seed(3).world_radius(0);
+        let mut rng = crate::rng::rng();
+        let mut food = PropGenerator::NaturalFood.generate(&mut rng);
+        food.attributes.hex = None;
+        let mut corpse = generate_corpse(&mut rng, test_player("Departed", AxialHex::ZERO));
+        corpse
+            .relations
+            .inventory_mut()
+            .insert(food.entity_id.clone());
+        let food_id = food.entity_id.clone();
+        let corpse_id = corpse.entity_id.clone();
+
+        // (someone who won't think twice about it)
+        let mut looter = test_player("Looter", AxialHex::ZERO);
+        looter.attributes.characteristics = Some(HashMap::from([(
+            Characteristic::Empathy,
+            CharacteristicStrength::Low,
+        )]));
+        looter
+            .attributes
+            .motivators
+            .set_key(motivator::MotivatorKey::Hunger, 0.8);
+        let looter_id = looter.entity_id.clone();
+
+        let builder = builder
+            .with_entity(food)
+            .with_entity(corpse)
+            .with_entity(looter);
+        (builder, looter_id, corpse_id, food_id)
+    }
+
+    #[tokio::test]
+    async fn test_starving_player_loots_corpse() {
+        let (builder, looter_id, corpse_id, food_id) = starving_looter_beside_corpse();
+        let mut test_match = builder.build().await;
+        let looted = test_match
+            .tick_until(30, |m| {
+                m.has_log_by(&looter_id, |body| {
+                    matches!(body, GameLogBody::EntityLootCorpse)
+                })
+            })
+            .await;
+
+        assert!(looted, "Looter never looted the corpse");
+        assert!(!test_match.has_log_by(&looter_id, |body| {
+            matches!(body, GameLogBody::EntityHesitateBeforeLoot)
+        }));
+        let reputation = test_match.entity(&looter_id).unwrap().attributes.reputation;
+        assert!(reputation
+            .unwrap()
+            .deeds()
+            .any(|deed| *deed == Deed::LootCorpse));
+        let corpse = test_match.entity(&corpse_id).unwrap();
+        assert!(!corpse.relations.inventory().any(|id| *id == food_id));
+    }
+
+    #[tokio::test]
+    async fn test_no_looting_without_feature() {
+        let (builder, looter_id, corpse_id, food_id) = starving_looter_beside_corpse();
+        let mut test_match = builder.without_feature(FeatureFlag::Looting).build().await;
+        let looted = test_match
+            .tick_until(30, |m| {
+                m.has_log_by(&looter_id, |body| {
+                    matches!(body, GameLogBody::EntityLootCorpse)
+                })
+            })
+            .await;
+
+        assert!(
+            !looted,
+            "Looter looted the corpse in a match without looting"
+        );
+
+        // The food never left the corpse (if there's anything left of it)
+        let looter = test_match.entity(&looter_id).unwrap();
+        assert!(!looter.relations.inventory().any(|id| *id == food_id));
+        if let Some(corpse) = test_match.entity(&corpse_id) {
+            assert!(corpse.relations.inventory().any(|id| *id == food_id));
+        }
+    }
+}
@@ -9,7 +9,15 @@ use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 
 use crate::{
-    entity::{brain::discussion::DiscussionLeadAction, EntityId},
+    entity::{
+        brain::{
+            discussion::{DiscussionLeadAction, Opinion},
+            nickname::Epithet,
+            reputation::Deed,
+        },
+        world::WeatherKind,
+        EntityId,
+    },
     hex::AxialHex,
 };
 
@@ -51,6 +59,112 @@ pub enum Meme {
     /// (not shareable)
     #[strum(to_string = "asked:{0},{1}")]
     Asked(EntityId, DiscussionLeadAction),
+
+    /// We know of a stash hidden at this location
+    /// (either our own, or we've heard about someone elses)
+    #[strum(to_string = "cache_at:{0}")]
+    CacheAt(AxialHex),
+
+    /// We know of a broken escape pod at this location
+    #[strum(to_string = "pod_at:{0}")]
+    PodAt(AxialHex),
+
+    /// Some entity has claimed the given hex (via a shelter there) as theirs
+    #[strum(to_string = "claim:{0},{1}")]
+    Claim(EntityId, AxialHex),
+
+    /// We've noticed some prop (that isn't obvious enough to see without looking)
+    /// (not shareable)
+    #[strum(to_string = "spotted:{0}")]
+    Spotted(EntityId),
+
+    /// We've been through some container and left nothing in it
+    /// (not shareable)
+    #[strum(to_string = "searched:{0}")]
+    Searched(EntityId),
+
+    // == Reputation ==
+    /// We know (first or second hand) that a given entity did something
+    #[strum(to_string = "deed:{0},{1}")]
+    Deed(EntityId, Deed),
+
+    /// We (and maybe others) call a given entity by some nickname
+    #[strum(to_string = "nickname:{0},{1}")]
+    Nickname(EntityId, Epithet),
+
+    /// We've heard what someone else thinks of a given entity
+    /// (only the last opinion we heard is kept)
+    #[strum(to_string = "opinion:{0},{1}")]
+    Opinion(EntityId, Opinion),
+
+    // == Small talk ==
+    /// We saw something happen for ourselves (and might bring it up later)
+    /// (not shareable, you had to be there)
+    #[strum(to_string = "witnessed:{0}")]
+    Witnessed(Witnessed),
+}
+
+impl Meme {
+    /// The thing this meme is about, if it means nothing once that thing is gone
+    /// (what we know about people outlives them, so that isn't included)
+    fn about_thing(&self) -> Option<&EntityId> {
+        match self {
+            Meme::EntityIsSafe(entity_id)
+            | Meme::EntityIsDangerous(entity_id)
+            | Meme::Spotted(entity_id)
+            | Meme::Searched(entity_id) => Some(entity_id),
+            _ => None,
+        }
+    }
+}
+
+/// Something that happened that an entity saw for themselves
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, strum::Display)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Witnessed {
+    /// Someone died nearby
+    #[strum(to_string = "death,{entity_id}")]
+    Death { entity_id: EntityId },
+
+    /// We were caught out in some rough weather
+    #[strum(to_string = "weather,{weather}")]
+    Weather { weather: WeatherKind },
+
+    /// Someone did something they'll be called something for
+    #[strum(to_string = "feat,{entity_id},{epithet}")]
+    Feat {
+        entity_id: EntityId,
+        epithet: Epithet,
+    },
+}
+
+impl FromStr for Witnessed {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tag, rest) = s
+            .split_once(",")
+            .ok_or(anyhow!("Malformed witnessed event"))?;
+        match tag {
+            "death" => Ok(Witnessed::Death {
+                entity_id: rest.parse()?,
+            }),
+            "weather" => Ok(Witnessed::Weather {
+                weather: rest.parse()?,
+            }),
+            "feat" => {
+                let (id, epithet) = rest
+                    .split_once(",")
+                    .ok_or(anyhow!("Malformed witnessed feat"))?;
+                Ok(Witnessed::Feat {
+                    entity_id: id.parse()?,
+                    epithet: epithet.parse()?,
+                })
+            }
+            _ => Err(anyhow!("Failed to parse witnessed event, unkown tag {tag}")),
+        }
+    }
 }
 
 impl FromStr for Meme {
@@ -63,12 +177,39 @@ impl FromStr for Meme {
             "dangerous" => Ok(Meme::EntityIsDangerous(rest.parse()?)),
             "shelter_at" => Ok(Meme::ShelterAt(rest.parse()?)),
             "water_source_at" => Ok(Meme::WaterSourceAt(rest.parse()?)),
+            "cache_at" => Ok(Meme::CacheAt(rest.parse()?)),
+            "pod_at" => Ok(Meme::PodAt(rest.parse()?)),
+            "spotted" => Ok(Meme::Spotted(rest.parse()?)),
+            "searched" => Ok(Meme::Searched(rest.parse()?)),
             "asked" => {
                 let (id, action) = rest
                     .split_once(",")
                     .ok_or(anyhow!("Malformed asked meme"))?;
                 Ok(Meme::Asked(id.parse()?, action.parse()?))
             }
+            "claim" => {
+                let (id, hex) = rest
+                    .split_once(",")
+                    .ok_or(anyhow!("Malformed claim meme"))?;
+                Ok(Meme::Claim(id.parse()?, hex.parse()?))
+            }
+            "deed" => {
+                let (id, deed) = rest.split_once(",").ok_or(anyhow!("Malformed deed meme"))?;
+                Ok(Meme::Deed(id.parse()?, deed.parse()?))
+            }
+            "nickname" => {
+                let (id, epithet) = rest
+                    .split_once(",")
+                    .ok_or(anyhow!("Malformed nickname meme"))?;
+                Ok(Meme::Nickname(id.parse()?, epithet.parse()?))
+            }
+            "opinion" => {
+                let (id, opinion) = rest
+                    .split_once(",")
+                    .ok_or(anyhow!("Malformed opinion meme"))?;
+                Ok(Meme::Opinion(id.parse()?, opinion.parse()?))
+            }
+            "witnessed" => Ok(Meme::Witnessed(rest.parse()?)),
             _ => Err(anyhow!("Failed to parse meme, unkown tag {tag}")),
         }
     }
@@ -111,6 +252,23 @@ impl MemeTable {
         self.memes.remove(meme);
     }
 
+    /// Forget anything about some particular thing that `exists` says is gone
+    /// (returns whether anything was forgotten)
+    pub fn forget_missing(&mut self, exists: impl Fn(&EntityId) -> bool) -> bool {
+        let before = self.memes.len();
+        self.memes
+            .retain(|meme| meme.about_thing().is_none_or(&exists));
+        self.memes.len() != before
+    }
+
+    pub fn has_spotted(&self, entity_id: &EntityId) -> bool {
+        self.memes.contains(&Meme::Spotted(entity_id.clone()))
+    }
+
+    pub fn has_searched(&self, entity_id: &EntityId) -> bool {
+        self.memes.contains(&Meme::Searched(entity_id.clone()))
+    }
+
     fn is_safe(&self, entity_id: &EntityId) -> bool {
         self.memes.contains(&Meme::EntityIsSafe(entity_id.clone()))
     }
@@ -155,6 +313,87 @@ impl MemeTable {
         })
     }
 
+    pub fn cache_locations(&self) -> impl Iterator<Item = AxialHex> + use<'_> {
+        self.memes.iter().filter_map(|meme| match meme {
+            Meme::CacheAt(hex) => Some(*hex),
+            _ => None,
+        })
+    }
+
+    pub fn pod_locations(&self) -> impl Iterator<Item = AxialHex> + use<'_> {
+        self.memes.iter().filter_map(|meme| match meme {
+            Meme::PodAt(hex) => Some(*hex),
+            _ => None,
+        })
+    }
+
+    /// How good/bad we think some entity is, from the deeds we know of
+    /// (0 if we dont know anything about them)
+    pub fn perceived_reputation(&self, entity_id: &EntityId) -> f32 {
+        self.memes
+            .iter()
+            .filter_map(|meme| match meme {
+                Meme::Deed(id, deed) if id == entity_id => Some(deed.alignment()),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// All the deeds we know about (of anyone)
+    pub fn known_deeds(&self) -> impl Iterator<Item = Meme> + use<'_> {
+        self.memes
+            .iter()
+            .filter(|meme| matches!(meme, Meme::Deed(..)))
+            .cloned()
+    }
+
+    /// The nicknames we know some entity by
+    pub fn nicknames_for<'a>(
+        &'a self,
+        entity_id: &'a EntityId,
+    ) -> impl Iterator<Item = Epithet> + use<'a> {
+        self.memes.iter().filter_map(move |meme| match meme {
+            Meme::Nickname(id, epithet) if id == entity_id => Some(*epithet),
+            _ => None,
+        })
+    }
+
+    /// Remember what we've heard someone thinks of some entity
+    /// (replacing whatever we'd heard before)
+    pub fn remember_opinion(&mut self, entity_id: &EntityId, opinion: Opinion) {
+        self.memes
+            .retain(|meme| !matches!(meme, Meme::Opinion(id, _) if id == entity_id));
+        self.insert(Meme::Opinion(entity_id.clone(), opinion));
+    }
+
+    /// The last opinion we heard of some entity
+    pub fn heard_opinion(&self, entity_id: &EntityId) -> Option<&Opinion> {
+        self.memes.iter().find_map(|meme| match meme {
+            Meme::Opinion(id, opinion) if id == entity_id => Some(opinion),
+            _ => None,
+        })
+    }
+
+    /// All the nicknames we know about (of anyone)
+    pub fn known_nicknames(&self) -> impl Iterator<Item = Meme> + use<'_> {
+        self.memes
+            .iter()
+            .filter(|meme| matches!(meme, Meme::Nickname(..)))
+            .cloned()
+    }
+
+    /// Everything we've seen happen for ourselves
+    pub fn witnessed(&self) -> impl Iterator<Item = &Witnessed> + use<'_> {
+        self.memes.iter().filter_map(|meme| match meme {
+            Meme::Witnessed(event) => Some(event),
+            _ => None,
+        })
+    }
+
+    pub fn has_witnessed(&self, event: &Witnessed) -> bool {
+        self.memes.contains(&Meme::Witnessed(event.clone()))
+    }
+
     pub fn asked_before(&self, target: &EntityId, action: &DiscussionLeadAction) -> bool {
         self.memes
             .contains(&Meme::Asked(target.clone(), action.clone()))
@@ -173,4 +412,96 @@ mod test {
         assert!(result.is_ok());
         assert_eq!(result.unwrap().to_string(), s);
     }
+
+    #[test]
+    fn test_parse_asked_trade_meme() {
+        let s = "asked:foobar,trade:apple,knife";
+        let result = Meme::from_str(s);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_claim_meme() {
+        let s = "claim:foobar,-2,3";
+        let result = Meme::from_str(s);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_cache_meme() {
+        let s = "cache_at:1,-1";
+        let result = Meme::from_str(s);
+        assert!(matches!(result, Ok(Meme::CacheAt(_))));
+        assert_eq!(result.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_searched_meme() {
+        let s = "searched:foobar";
+        let result = Meme::from_str(s);
+        assert!(matches!(result, Ok(Meme::Searched(_))));
+        assert_eq!(result.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_deed_meme() {
+        let s = "deed:foobar,abandon_ally";
+        let result = Meme::from_str(s);
+        assert_eq!(
+            result.unwrap(),
+            Meme::Deed("foobar".into(), Deed::AbandonAlly)
+        );
+    }
+
+    #[test]
+    fn test_parse_nickname_meme() {
+        let s = "nickname:foobar,sparky";
+        let result = Meme::from_str(s);
+        assert_eq!(
+            result.unwrap(),
+            Meme::Nickname("foobar".into(), Epithet::Sparky)
+        );
+    }
+
+    #[test]
+    fn test_parse_opinion_meme() {
+        let s = "opinion:foobar,negative";
+        let result = Meme::from_str(s);
+        assert_eq!(
+            result.unwrap(),
+            Meme::Opinion("foobar".into(), Opinion::Negative)
+        );
+        assert_eq!(
+            Meme::Opinion("foobar".into(), Opinion::Negative).to_string(),
+            s
+        );
+    }
+
+    #[test]
+    fn test_parse_witnessed_meme() {
+        let s = "witnessed:feat,foobar,sparky";
+        let result = Meme::from_str(s);
+        assert_eq!(
+            result.unwrap(),
+            Meme::Witnessed(Witnessed::Feat {
+                entity_id: "foobar".into(),
+                epithet: Epithet::Sparky
+            })
+        );
+
+        let s = "asked:foobar,recall:weather,lightning_storm";
+        let result = Meme::from_str(s);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_asked_remember_meme() {
+        let s = "asked:foobar,remember:lost,Ada Quinn";
+        let result = Meme::from_str(s);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().to_string(), s);
+    }
 }
@@ -1,34 +1,87 @@
 pub mod actor_action;
+pub mod bark;
+pub mod bonding;
 pub mod characteristic;
+pub mod clothing;
 pub mod discussion;
+pub mod escape;
 pub mod focus;
+pub mod footing;
+pub mod gathering;
+pub mod grief;
+pub mod idle;
+pub mod looting;
 pub mod meme;
 pub mod motivator;
+pub mod nickname;
+pub mod plan;
 pub mod planning;
+pub mod predator;
+pub mod rafting;
+pub mod reputation;
+pub mod rescue;
+pub mod role;
+pub mod senses;
 pub mod signal;
+pub mod stamina;
+#[cfg(test)]
+pub mod test_signal;
+pub mod throwing;
+pub mod tracking;
+pub mod trade;
 
 use itertools::Itertools;
-use rand::seq::{IndexedRandom, IteratorRandom};
-use tracing::warn;
+use rand::{
+    seq::{IndexedRandom, IteratorRandom},
+    Rng,
+};
+use tracing::{debug, warn};
 
 use crate::{
+    content::content,
     entity::{
         brain::{
             actor_action::{ActorAction, ActorActionResult, ActorActionSideEffect},
             characteristic::{Characteristic, CharacteristicStrength},
+            footing::{Footing, SLIP_HURT},
+            idle::IdleSignal,
             motivator::Sadness,
+            predator::{MAUL_HURT, MAX_PURSUIT_TICKS},
+            reputation::{Deed, ALLY_BOND},
+            rescue::{safe_hex_near, Plight, BADLY_HURT},
+            senses::ARRIVAL_NOTICE_SCALE,
             signal::{Signal, SignalContext, SignalRef, WeightedActorActions},
+            stamina::{Exertion, EXHAUSTED_STAMINA},
+            throwing::{ThrowTarget, THROW_HURT_SCALE},
         },
-        Entity, EntityFood, EntityWaterSource,
+        generate::PropGenerator,
+        kind::EntityKindError,
+        Entity, EntityAttributes, EntityCache, EntityContainer, EntityFood, EntityMarker,
+        EntityWaterSource,
     },
     event::{builder::GameEventBuilder, GameEventKind, GameEventTarget},
     has_markers,
     hex::{AxialHex, AxialHexDirection},
-    logs::{AsEntityId, GameLog, GameLogBody},
-    mtch::ActionCtx,
+    logs::{AsEntityId, GameLog, GameLogBody, LogChannel, LogTier},
+    mtch::{features::FeatureFlag, stats::PlayerStat, ActionCtx},
 };
 use focus::ActorFocus;
 
+/// Chance of catching a fish on the first tick of fishing
+const FISHING_BASE_CHANCE: f64 = 0.02;
+
+/// How much more likely a catch gets for each tick spent waiting
+const FISHING_PATIENCE_CHANCE: f64 = 0.01;
+
+/// Chance each tick of slipping and falling in the lake (less for the nimble)
+const FISHING_FALL_IN_CHANCE: f64 = 0.04;
+
+/// After this long without a bite, we give up
+const MAX_FISHING_TICKS: usize = 30;
+
+/// Barks about a motivator this high aren't just background noise
+const DESPERATE_BARK_MOTIVATION: f32 = 0.85;
+
 impl Entity {
     /// Determine the next action to be taken by an entity
     /// Only applicable for players
@@ -49,25 +102,45 @@ impl Entity {
             entity: self,
             focus: current_focus.clone(),
             world_state: ctx.world_state,
+            tick_id: ctx.tick_id,
+            features: &ctx.config.features,
         };
 
         // Collect signals
+        // (skipping any motivators that couldn't raise anything right now)
+        let motivator_signals = self.attributes.motivators.as_signals(&current_focus);
         let focus_signal = std::iter::once(SignalRef::boxed(current_focus));
-        let motivator_signals = self.attributes.motivators.as_signals();
         let planning_signals = self.get_planning_signals(&signal_ctx);
+        let idle_signal = std::iter::once(SignalRef::boxed(IdleSignal));
 
         // Merge all the signals into one iter
         let signals = itertools::chain!(
             motivator_signals,
             event_signals,
             focus_signal,
-            planning_signals
+            planning_signals,
+            idle_signal
         );
 
         // Then resolve them into actions
-        let mut actions = WeightedActorActions::default();
-        signals.for_each(|signal| signal.act_on(&signal_ctx, &mut actions));
-        actions.sample(&mut rand::rng())
+        WeightedActorActions::with_buffer(|actions| {
+            signals.for_each(|signal| signal.act_on(&signal_ctx, actions));
+
+            // If we're exhausted, we can't manage anything strenuous
+            if self.attributes.motivators.stamina() < EXHAUSTED_STAMINA {
+                actions.degrade_for_exhaustion();
+            }
+
+            // Some matches dont allow hurting anyone
+            if !ctx.config.ruleset.fights {
+                actions.reweight(|weight, action| match action {
+                    ActorAction::Throw { .. } => 0,
+                    _ => weight,
+                });
+            }
+
+            actions.sample(Some(&self.entity_id), &mut crate::rng::rng())
+        })
     }
 
     pub fn resolve_action(
@@ -75,17 +148,34 @@ impl Entity {
         action: ActorAction,
         ctx: &mut ActionCtx,
     ) -> ActorActionResult {
+        // Anything the action was about might have changed since it was chosen,
+        // in which case it just doesn't happen
+        let action_name: &'static str = (&action).into();
+        match self.try_resolve_action(action, ctx) {
+            Ok(result) => result,
+            Err(err) => {
+                warn!("{} skipped {action_name}: {err}", self.entity_id);
+                ActorActionResult::NoEffect
+            }
+        }
+    }
+
+    fn try_resolve_action(
+        &mut self,
+        action: ActorAction,
+        ctx: &mut ActionCtx,
+    ) -> Result<ActorActionResult, EntityKindError> {
         // Must have a hex to take actions
         let Some(my_hex) = self.attributes.hex else {
-            return ActorActionResult::NoEffect;
+            return Ok(ActorActionResult::NoEffect);
         };
 
         // Prep randomness
-        let mut rng = rand::rng();
+        let mut rng = crate::rng::rng();
 
         match &action {
             ActorAction::Nothing => {
-                return ActorActionResult::NoEffect;
+                return Ok(ActorActionResult::NoEffect);
             }
 
             // Just send a log
@@ -99,51 +189,70 @@ impl Entity {
                     }
                 }
 
-                return ActorActionResult::NoEffect;
+                return Ok(ActorActionResult::NoEffect);
             }
 
             ActorAction::IgnoreResult(action) => {
                 self.resolve_action(*action.clone(), ctx);
-                return ActorActionResult::NoEffect;
+                return Ok(ActorActionResult::NoEffect);
             }
 
-            ActorAction::Sequential(sub_actions) => {
-                for sub_action in sub_actions {
+            ActorAction::Plan(plan) => {
+                for (step, sub_action) in plan.steps().iter().enumerate() {
                     match self.resolve_action(sub_action.clone(), ctx) {
                         ActorActionResult::SideEffect(side_effect) => {
-                            return ActorActionResult::SideEffect(side_effect)
+                            debug!(entity_id = %self.entity_id, "Following {}", plan.at_step(step));
+                            return Ok(ActorActionResult::SideEffect(side_effect));
                         }
                         ActorActionResult::NoEffect => {
                             continue;
                         }
                         ActorActionResult::Ok => {
+                            debug!(entity_id = %self.entity_id, "Following {}", plan.at_step(step));
                             break;
                         }
                     }
                 }
 
-                return ActorActionResult::NoEffect;
+                return Ok(ActorActionResult::NoEffect);
             }
 
             ActorAction::StoreMeme(meme) => {
                 self.memes_mut().insert(meme.clone());
             }
 
+            ActorAction::ForgetMeme(meme) => {
+                self.memes_mut().remove(meme);
+            }
+
+            ActorAction::HearOpinion {
+                from,
+                about,
+                opinion,
+            } => {
+                self.assimilate_opinion(from, about, opinion);
+            }
+
             ActorAction::PickUpEntity(entity_id) => {
                 // Find that item, it must be an `item` (have an item field)
                 let Some(item_entity) = ctx.entities.by_id(entity_id) else {
                     warn!("Cannot pick up non-existent entity");
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 };
                 let Some(item) = &item_entity.attributes.item else {
                     warn!("Cannot pick up non-item");
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
+                // and we have to know its there
+                if !self.can_see(item_entity) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
                 // Do we have room?
                 let avail_space = self.available_inventory_load(ctx.entities);
                 if item.heft > avail_space {
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
                 // Log the pickup action
@@ -156,19 +265,19 @@ impl Entity {
                 // Add to our inventory
                 // and banish it from the world (so others cant pick it up too etc)
                 self.relations.inventory_mut().insert(entity_id.clone());
-                return ActorActionResult::SideEffect(ActorActionSideEffect::BanishOther(
-                    entity_id.clone(),
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::BanishOther(entity_id.clone()),
                 ));
             }
 
             ActorAction::BumpMotivator(key) => {
                 self.attributes.motivators.bump_key(*key);
-                return ActorActionResult::Ok;
+                return Ok(ActorActionResult::Ok);
             }
 
             ActorAction::ReduceMotivator(key) => {
                 self.attributes.motivators.reduce_key(*key);
-                return ActorActionResult::Ok;
+                return Ok(ActorActionResult::Ok);
             }
 
             ActorAction::WakeUp => {
@@ -182,10 +291,10 @@ impl Entity {
 
                         ctx.send_log(GameLog::entity(self, GameLogBody::EntityStopSleeping));
                     }
-                    _ => return ActorActionResult::NoEffect,
+                    _ => return Ok(ActorActionResult::NoEffect),
                 }
 
-                return ActorActionResult::Ok;
+                return Ok(ActorActionResult::Ok);
             }
 
             ActorAction::Sleep => {
@@ -225,7 +334,7 @@ impl Entity {
                     }
                 };
 
-                return ActorActionResult::Ok;
+                return Ok(ActorActionResult::Ok);
             }
 
             // Literally die
@@ -241,7 +350,7 @@ impl Entity {
                     .targets_hex_of(self)
                     .add(ctx);
 
-                return ActorActionResult::SideEffect(ActorActionSideEffect::Death);
+                return Ok(ActorActionResult::SideEffect(ActorActionSideEffect::Death));
             }
 
             ActorAction::MoveAwayFrom(log_body, markers) => {
@@ -255,7 +364,7 @@ impl Entity {
 
                 // Is there at least one? If so choose one at random
                 let Some(avoid_entity) = avoid_entities.choose(&mut rng) else {
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
                 // Emit log
@@ -266,7 +375,7 @@ impl Entity {
                     .choose(&mut rng)
                     .unwrap()
                     .clone();
-                return self.resolve_action(move_action, ctx);
+                return Ok(self.resolve_action(move_action, ctx));
             }
 
             ActorAction::GoToAdjacent(log_body, markers) => {
@@ -277,24 +386,41 @@ impl Entity {
                     .any(|e| markers.iter().any(|m| e.markers.contains(m)));
 
                 if current_hex_valid {
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
                 // If not, pull all applicable adjacent entities
+                // (but not out on open water, we can't walk out there)
                 let adj_entities = ctx
                     .entities
                     .adjacent_to_hex(my_hex)
                     .filter(|e| markers.iter().any(|m| e.markers.contains(m)))
+                    .filter(|e| {
+                        e.attributes
+                            .hex
+                            .is_some_and(|hex| !ctx.entities.resources(hex).open_water)
+                    })
                     .collect_vec();
 
                 // If no relevant adjacent hexs, we cant do anything
                 if adj_entities.is_empty() {
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
                 // But if there is, choose one at random
-                let chosen_entity = adj_entities.choose(&mut rng).unwrap();
-                let hex = chosen_entity.attributes.hex.unwrap();
+                // (steering clear of anywhere dangerous if we can)
+                let (safe_entities, hazardous_entities): (Vec<_>, Vec<_>) =
+                    adj_entities.into_iter().partition(|e| {
+                        e.attributes
+                            .hex
+                            .is_some_and(|hex| !ctx.entities.resources(hex).hazard)
+                    });
+                let chosen_entity = if safe_entities.is_empty() {
+                    hazardous_entities.choose(&mut rng).unwrap()
+                } else {
+                    safe_entities.choose(&mut rng).unwrap()
+                };
+                let hex = chosen_entity.located()?;
                 let direction = AxialHexDirection::direction_to(my_hex, hex)
                     .expect("Cannot determine direction to adj hex");
 
@@ -302,7 +428,7 @@ impl Entity {
                 ctx.send_log(GameLog::entity(self, log_body.clone()));
 
                 // Travel towards that hex
-                return self.resolve_action(ActorAction::Move(direction), ctx);
+                return Ok(self.resolve_action(ActorAction::Move(direction), ctx));
             }
 
             // This is a little tricky lets be honest
@@ -315,7 +441,7 @@ impl Entity {
                     .any(|e| markers.iter().any(|m| e.markers.contains(m)));
 
                 if current_hex_valid {
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
                 // If not, pull all applicable entities
@@ -327,7 +453,7 @@ impl Entity {
 
                 // If no relevant entities, we cant do anything
                 if target_entities.is_empty() {
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
                 // Emit log
@@ -336,52 +462,87 @@ impl Entity {
                 // Now sort the target entities by distance
                 let target_entity = target_entities
                     .iter()
-                    .min_by_key(|e| e.attributes.hex.unwrap().dist_to(my_hex))
+                    .min_by_key(|e| e.attributes.hex.map(|hex| hex.dist_to(my_hex)))
                     .unwrap();
-                let target_hex = target_entity.attributes.hex.unwrap();
+                let target_hex = target_entity.located()?;
 
-                return self.resolve_action(ActorAction::GoTowardsHex(target_hex), ctx);
+                return Ok(self.resolve_action(ActorAction::GoTowardsHex(target_hex), ctx));
             }
 
             ActorAction::GoTowardsHex(target_hex) => {
-                // Already there?
-                if *target_hex == my_hex {
-                    return ActorActionResult::NoEffect;
+                // Already there? (or as close as we can get, if it's out on open water)
+                let open_water = |hex: AxialHex| ctx.entities.resources(hex).open_water;
+                if *target_hex == my_hex
+                    || (open_water(*target_hex) && target_hex.dist_to(my_hex) == 1)
+                {
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
                 // Find our adjacent hex which is closest to the target hex
-                let adjacent_hex = my_hex
+                // (going around any open water in the way)
+                let Some(adjacent_hex) = my_hex
                     .neighbours()
                     .into_iter()
-                    .filter(|h| h.within_bounds(ctx.config.world_radius as isize))
+                    .filter(|h| {
+                        h.within_bounds(ctx.config.world_radius as isize) && !open_water(*h)
+                    })
                     .min_by_key(|h| h.dist_to(*target_hex))
-                    .unwrap();
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
 
                 // And travel towards that
                 let direction = AxialHexDirection::direction_to(my_hex, adjacent_hex).unwrap();
-                return self.resolve_action(ActorAction::Move(direction), ctx);
+                return Ok(self.resolve_action(ActorAction::Move(direction), ctx));
             }
 
             // Indicating a high motivator value
             ActorAction::Bark(motivation, motivator) => {
-                ctx.send_log(GameLog::entity(
-                    self,
-                    GameLogBody::EntityMotivatorBark {
-                        motivation: *motivation,
-                        motivator: *motivator,
-                    },
-                ));
+                // (barks are background noise, unless someone is really struggling)
+                let tier = if *motivation >= DESPERATE_BARK_MOTIVATION {
+                    LogTier::Normal
+                } else {
+                    LogTier::Ambient
+                };
+                let content = content();
+                let variant = self.choose_bark_variant(*motivator, &content, &mut rng);
+                ctx.send_log_throttled(
+                    GameLog::entity(
+                        self,
+                        GameLogBody::EntityMotivatorBark {
+                            motivation: *motivation,
+                            motivator: *motivator,
+                            line: content.bark_line(*motivator, *motivation, variant),
+                            bark_variant: variant,
+                        },
+                    )
+                    .with_tier(tier),
+                );
+
+                // This returns no effect so that the boredom is increased and to allow stacking barks + other actions in a plan
+                return Ok(ActorActionResult::NoEffect);
+            }
 
-                // This returns no effect so that the boredom is increased and to allow stacking barks + other actions w/ Sequential
-                return ActorActionResult::NoEffect;
+            // Passing the time (background noise)
+            ActorAction::Idle(behavior) => {
+                ctx.send_log_throttled(
+                    GameLog::entity(
+                        self,
+                        GameLogBody::EntityIdle {
+                            behavior: *behavior,
+                        },
+                    )
+                    .with_tier(LogTier::Ambient),
+                );
+                return Ok(ActorActionResult::NoEffect);
             }
 
             ActorAction::ConsumeFoodEntity(food_entity_id) => {
                 // Get that entity
-                let food_entity = ctx.entities.by_id(food_entity_id).unwrap();
+                let food_entity = ctx.entities.require(food_entity_id)?;
 
                 // if there is, eat it
-                let food = food_entity.attributes.food.as_ref().unwrap();
+                let food = food_entity.as_food()?;
                 self.attributes
                     .motivators
                     .reduce_by::<motivator::Hunger>(food.sustenance.min(0.1));
@@ -394,14 +555,23 @@ impl Entity {
                         food_entity,
                         GameLogBody::EntityHesitateBeforeConsume,
                     ));
+
+                    // (only corpses are morally wrong to eat for now)
+                    self.record_deed(Deed::Cannibalism, ctx);
                 }
 
                 // emit log
-                ctx.send_log(GameLog::entity_pair(
-                    self,
-                    food_entity,
-                    GameLogBody::EntityConsume,
-                ));
+                // (eating a corpse is something everyone will want to see)
+                let tier = if food.morally_wrong {
+                    LogTier::Highlight
+                } else {
+                    LogTier::Normal
+                };
+                ctx.send_log(
+                    GameLog::entity_pair(self, food_entity, GameLogBody::EntityConsume)
+                        .with_tier(tier),
+                );
+                ctx.record_stat(self, PlayerStat::FoodEaten);
 
                 // was it poisonous
                 if food.sustenance < 0.0 {
@@ -417,8 +587,8 @@ impl Entity {
                 }
 
                 // Return side effect to remove the food
-                return ActorActionResult::SideEffect(ActorActionSideEffect::RemoveOther(
-                    food_entity.entity_id.clone(),
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::RemoveOther(food_entity.entity_id.clone()),
                 ));
             }
 
@@ -427,13 +597,13 @@ impl Entity {
                     .resolve_inventory(ctx.entities)
                     .find(|e| e.attributes.food.is_some())
                 else {
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
-                return self.resolve_action(
+                return Ok(self.resolve_action(
                     ActorAction::RetrieveEntity(food_entity.entity_id.clone()),
                     ctx,
-                );
+                ));
             }
 
             ActorAction::RetrieveEntity(entity_id) => {
@@ -443,7 +613,7 @@ impl Entity {
                 // Get the item entity
                 let Some(item_entity) = ctx.entities.by_id(entity_id) else {
                     warn!("Attempted to retrieve non existent entity from inventory");
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
                 // Log that we got it out
@@ -454,261 +624,1899 @@ impl Entity {
                 ));
 
                 // Unbanish it
-                return ActorActionResult::SideEffect(ActorActionSideEffect::UnbanishOther(
-                    item_entity.entity_id.clone(),
-                    my_hex,
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::UnbanishOther(item_entity.entity_id.clone(), my_hex),
                 ));
             }
 
-            ActorAction::ConsumeNearbyFood {
-                try_dubious,
-                try_morally_wrong,
-            } => {
-                // Is there food at this location?
-                let food_entities =
-                    ctx.entities
-                        .in_hex(my_hex)
-                        .filter(|e| match e.attributes.food {
-                            // Is it food at all?
-                            None => false,
-
-                            // Is it food but dubious?
-                            Some(EntityFood {
-                                poison,
-                                morally_wrong,
-                                ..
-                            }) if poison > 0.0 => {
-                                *try_dubious && (!morally_wrong || *try_morally_wrong)
-                            }
-
-                            // Good food
-                            Some(EntityFood { .. }) => true,
-                        });
-                let Some(food_entity) = food_entities.choose(&mut rng) else {
-                    return ActorActionResult::NoEffect;
+            ActorAction::DropEntity(entity_id) => {
+                // Has to actually be on us
+                if !self.relations.inventory_mut().remove(entity_id) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                let Some(item_entity) = ctx.entities.by_id(entity_id) else {
+                    warn!("Attempted to drop non existent entity from inventory");
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
-                return self.resolve_action(
-                    ActorAction::ConsumeFoodEntity(food_entity.entity_id.clone()),
-                    ctx,
-                );
-            }
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    item_entity,
+                    GameLogBody::EntityDrop,
+                ));
 
-            // NOTE: entity may not exist at this point
-            ActorAction::MournEntity { entity_id } => {
-                // Get sad
-                self.attributes.motivators.bump::<Sadness>();
+                // Leave it here (we know where we left it)
+                self.remember_spotted(entity_id);
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::UnbanishOther(item_entity.entity_id.clone(), my_hex),
+                ));
+            }
 
-                // Find the corpse
-                let maybe_corpse_entity = ctx
-                    .entities
-                    .all()
-                    .find(|e| e.attributes.corpse == Some(entity_id.clone()));
+            ActorAction::UseItemOn { item, target } => {
+                // The item has to be on us or nearby
+                let in_inventory = self.relations.inventory().any(|id| id == item);
+                let Some(item_entity) = ctx.entities.by_id(item).filter(|e| {
+                    in_inventory || (e.attributes.hex == Some(my_hex) && self.can_see(e))
+                }) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some(healing) = &item_entity.attributes.healing else {
+                    warn!("Cannot use non-healing item");
+                    return Ok(ActorActionResult::NoEffect);
+                };
 
-                // And log
-                if let Some(corpse_entity) = maybe_corpse_entity {
+                // Treating ourselves?
+                if *target == self.entity_id {
+                    self.relations.inventory_mut().remove(item);
+                    self.attributes
+                        .motivators
+                        .reduce_by::<motivator::Hurt>(healing.hurt_reduction());
                     ctx.send_log(GameLog::entity_pair(
                         self,
-                        corpse_entity,
-                        GameLogBody::EntityMournOverCorpse,
+                        item_entity,
+                        GameLogBody::EntityHealSelf,
+                    ));
+                    return Ok(ActorActionResult::SideEffect(
+                        ActorActionSideEffect::RemoveOther(item.clone()),
                     ));
-                } else {
-                    warn!("NO CORPSE");
                 }
-            }
-
-            ActorAction::DrinkFromWaterSource { try_dubious } => {
-                // Is there food at this location?
-                let water_source_entities = ctx
-                    .entities
-                    .in_hex(my_hex)
-                    .filter(|e| self.memes_mut().assumably_safe(&e.entity_id))
-                    .filter(|e| match e.attributes.water_source {
-                        // its dubious, are we okay with that?
-                        Some(EntityWaterSource { poison }) if poison > 0.0 => *try_dubious,
 
-                        // not dubious (fallthrough)
-                        Some(EntityWaterSource { .. }) => true,
+                // Otherwise they need to be here with us
+                let Some(target_entity) =
+                    ctx.entities.in_hex(my_hex).find(|e| &e.entity_id == target)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
 
-                        // its not a water source
-                        None => false,
-                    });
+                // Its getting used up on them
+                self.relations.inventory_mut().remove(item);
 
-                // If no applicable water source, there's no effect
-                let Some(water_source_entity) = water_source_entities.choose(&mut rng) else {
-                    return ActorActionResult::NoEffect;
-                };
+                // Helping someone out makes us like them more
+                self.relations.increase_associate_bond_by(target, 0.05);
+                self.record_deed(Deed::TendWounds, ctx);
+                ctx.send_log(GameLog::entity_triple(
+                    self,
+                    target_entity,
+                    item_entity,
+                    GameLogBody::EntityHealOther,
+                ));
 
-                // if there is, drink from it
-                let water_source = water_source_entity
-                    .attributes
-                    .water_source
-                    .as_ref()
-                    .unwrap();
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::HealOther {
+                        entity_id: target.clone(),
+                        item_id: item.clone(),
+                        amount: healing.hurt_reduction(),
+                    },
+                ));
+            }
 
-                // Fully clear thirst
-                self.attributes.motivators.clear::<motivator::Thirst>();
+            ActorAction::DragEntity { entity_id } => {
+                // They need to be here with us, and stuck somewhere they shouldn't be
+                let Some(other_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id)
+                    .filter(|e| e.plight(ctx.entities) == Some(Plight::CaughtInHazard))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some(safe_hex) =
+                    safe_hex_near(my_hex, ctx.config.world_radius as isize, ctx.entities)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
 
-                // Emit log
+                // Helping someone out makes us like them more
+                self.relations.increase_associate_bond_by(entity_id, 0.05);
+                self.record_deed(Deed::Rescue, ctx);
                 ctx.send_log(GameLog::entity_pair(
                     self,
-                    water_source_entity,
-                    GameLogBody::EntityDrinkFrom,
+                    other_entity,
+                    GameLogBody::EntityDragOther,
                 ));
 
-                // Should we get sick?
-                if water_source.poison > 0.0 {
-                    self.attributes
-                        .motivators
-                        .bump_scaled::<motivator::Sickness>(2.0 * water_source.poison);
-                    ctx.send_log(GameLog::entity_pair(
-                        self,
-                        water_source_entity,
-                        GameLogBody::EntityComplainAboutTaste,
-                    ));
+                // Hauling someone about is hard work, and we end up over there with them
+                self.exert(Exertion::Heavy, ctx.entities);
+                self.attributes.hex = Some(safe_hex);
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::UnbanishOther(entity_id.clone(), safe_hex),
+                ));
+            }
 
-                    // Remember it's dangerous
-                    self.memes_mut()
-                        .remember_is_dangerous(water_source_entity.id());
+            ActorAction::ShareFood { entity_id, item } => {
+                // They need to be here with us, with room to take it
+                let Some(other_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let food_entity = ctx.entities.require(item)?;
+                food_entity.as_food()?;
+                if food_entity.as_item()?.heft > other_entity.available_inventory_load(ctx.entities)
+                {
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
-                // If the water source was safe, remember it
-                if water_source.poison == 0.0 {
-                    self.memes_mut().remember_is_safe(water_source_entity.id());
-                    self.memes_mut().insert(meme::Meme::WaterSourceAt(
-                        water_source_entity.attributes.hex.unwrap(),
-                    ));
+                // and it has to actually be on us
+                if !self.relations.inventory_mut().remove(item) {
+                    return Ok(ActorActionResult::NoEffect);
                 }
 
-                return ActorActionResult::Ok;
-            }
-
-            ActorAction::GreetEntity { entity_id } => {
-                let entity = ctx.entities.by_id(entity_id).unwrap();
-
-                // Is there an established association relation?
-                let bond = self.relations.bond(entity_id);
-
-                // Log
-                ctx.send_log(GameLog::entity_pair(
+                self.relations.increase_associate_bond_by(entity_id, 0.05);
+                ctx.send_log(GameLog::entity_triple(
                     self,
-                    entity,
-                    GameLogBody::EntityGreet {
-                        bond,
-                        response: false,
+                    other_entity,
+                    food_entity,
+                    GameLogBody::EntityShareFood,
+                ));
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::PutInOther {
+                        entity_id: entity_id.clone(),
+                        item_id: item.clone(),
                     },
                 ));
+            }
 
-                // If they are unfriendly, this goes differently
-                // NOTE: if they dont have motivators, we assume they are friendly (assuming that animals etc are friendly)
-                // TODO: probably want to have a tag for beings that inverts this assumption (e.g Predator or something)
-                let friendliness = entity.characteristic(Characteristic::Friendliness);
-                if friendliness < CharacteristicStrength::Average {
-                    // they ignore us
-                    ctx.send_log(GameLog::entity_pair(
-                        entity,
-                        &self.entity_id,
-                        GameLogBody::EntityIgnore,
-                    ));
-
-                    // And we like them less
-                    self.relations.decrease_associate_bond(&entity.entity_id);
-                } else {
-                    // Just them responding makes us like them
-                    self.relations.increase_associate_bond(&entity.entity_id);
-
-                    // And if they can respond, we start a chat with them
-                    if has_markers!(entity, CanTalk) {
-                        // And we start talking to them
-                        // Initial interest scales w/ bond but has a minimum
-                        // (for simplicity our interest starts the same as theirs in the convo)
-                        let max_interest = 20f32;
-                        let interest =
-                            ((bond * max_interest) as usize).clamp(2, max_interest as usize);
-
-                        // Log the greet response
-                        ctx.send_log(GameLog::entity_pair(
-                            entity,
-                            self.id(),
-                            GameLogBody::EntityGreet {
-                                bond,
-                                response: true,
-                            },
-                        ));
-
-                        // Set our focus
-                        self.attributes.focus = Some(ActorFocus::Discussion {
-                            with: entity_id.clone(),
-                            is_lead: true,
-                            interest,
-                        });
-
-                        // TODO: maybe there's a strat here where we force them to do a "talk" action w/ us instead
-                        return ActorActionResult::SideEffect(ActorActionSideEffect::SetFocus {
-                            entity_id: entity_id.clone(),
-                            focus: ActorFocus::Discussion {
-                                with: self.entity_id.clone(),
-                                is_lead: false,
-                                interest,
-                            },
-                        });
-                    }
+            ActorAction::CreateCache => {
+                // No point having two stashes in the same place
+                if ctx.entities.in_hex(my_hex).any(|e| {
+                    e.attributes
+                        .cache
+                        .as_ref()
+                        .is_some_and(|cache| cache.is_owner(&self.entity_id))
+                }) {
+                    return Ok(ActorActionResult::NoEffect);
                 }
-            }
 
-            ActorAction::TakeShelter => {
-                // Is there shelter at my location?
-                let Some(shelter_entity) = ctx
-                    .entities
-                    .in_hex(my_hex)
-                    .find(|e| has_markers!(e, Shelter))
-                else {
-                    return ActorActionResult::NoEffect;
+                // Whoever we're travelling with gets to use it too
+                let mut owners = vec![self.entity_id.clone()];
+                if let Some(ActorFocus::TravelTogether { with, .. }) = &self.attributes.focus {
+                    owners.push(with.clone());
+                }
+                let cache_entity = Entity {
+                    entity_id: Entity::id(),
+                    name: "Stash".into(),
+                    attributes: EntityAttributes {
+                        hex: Some(my_hex),
+                        container: Some(EntityContainer { hidden: true }),
+                        cache: Some(EntityCache { owners }),
+                        ..Default::default()
+                    },
+                    ..Default::default()
                 };
 
-                // Shelter in that thang
-                self.attributes.focus = Some(ActorFocus::Sheltering {
-                    shelter_entity_id: shelter_entity.entity_id.clone(),
-                });
-
-                // Log it
                 ctx.send_log(GameLog::entity_pair(
                     self,
-                    shelter_entity,
-                    GameLogBody::EntityTakeShelter,
+                    &cache_entity,
+                    GameLogBody::EntityCreateCache,
                 ));
 
-                // and remember it
-                self.memes_mut().insert(meme::Meme::ShelterAt(
-                    shelter_entity.attributes.hex.unwrap(),
+                // Remember where we left it
+                self.remember_spotted(&cache_entity.entity_id);
+                self.memes_mut().insert(meme::Meme::CacheAt(my_hex));
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::SpawnOther(Box::new(cache_entity)),
                 ));
-
-                return ActorActionResult::Ok;
             }
 
-            ActorAction::LeaveShelter => {
-                // Check we are in shelter
-                let Some(ActorFocus::Sheltering { shelter_entity_id }) =
-                    self.attributes.focus.clone()
-                else {
-                    warn!("Tried to leave shelter but not in shelter");
-                    return ActorActionResult::NoEffect;
+            ActorAction::DepositInCache { cache, item } => {
+                // Has to be a stash of ours, here with us
+                let Some(cache_entity) = ctx.entities.in_hex(my_hex).find(|e| {
+                    &e.entity_id == cache
+                        && e.attributes
+                            .cache
+                            .as_ref()
+                            .is_some_and(|cache| cache.is_owner(&self.entity_id))
+                }) else {
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
-                // Then leave shelter
-                self.attributes.focus = Some(ActorFocus::Unfocused);
+                // and the item has to actually be on us
+                if !self.relations.inventory_mut().remove(item) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                let Some(item_entity) = ctx.entities.by_id(item) else {
+                    warn!("Attempted to deposit non existent entity");
+                    return Ok(ActorActionResult::NoEffect);
+                };
 
-                // and log that
                 ctx.send_log(GameLog::entity_pair(
                     self,
-                    &shelter_entity_id,
-                    GameLogBody::EntityLeaveShelter,
+                    item_entity,
+                    GameLogBody::EntityDepositInCache,
                 ));
 
-                return ActorActionResult::Ok;
+                self.remember_spotted(item);
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::PutInOther {
+                        entity_id: cache_entity.entity_id.clone(),
+                        item_id: item.clone(),
+                    },
+                ));
             }
 
-            ActorAction::SeekKnownWaterSource => {
+            ActorAction::WithdrawFromCache { cache, item } => {
+                // Has to be a stash of ours, here with us
+                let Some(cache_entity) = ctx.entities.in_hex(my_hex).find(|e| {
+                    &e.entity_id == cache
+                        && e.attributes
+                            .cache
+                            .as_ref()
+                            .is_some_and(|cache| cache.is_owner(&self.entity_id))
+                }) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // with the item in it (and we need room for it)
+                let avail_space = self.available_inventory_load(ctx.entities);
+                let Some(item_entity) = cache_entity
+                    .resolve_inventory(ctx.entities)
+                    .find(|e| &e.entity_id == item)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                if item_entity
+                    .attributes
+                    .item
+                    .as_ref()
+                    .is_some_and(|i| i.heft > avail_space)
+                {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    item_entity,
+                    GameLogBody::EntityWithdrawFromCache,
+                ));
+
+                self.relations.inventory_mut().insert(item.clone());
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::TakeOutOfOther {
+                        entity_id: cache_entity.entity_id.clone(),
+                        item_id: item.clone(),
+                    },
+                ));
+            }
+
+            ActorAction::StripCorpse(entity_id) => {
+                // Find that corpse, it must be here with us
+                let Some(corpse_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id && e.attributes.corpse.is_some())
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Take the warmest thing they have on (if we can carry it)
+                let avail_space = self.available_inventory_load(ctx.entities);
+                let Some(clothing_entity) = corpse_entity
+                    .resolve_inventory(ctx.entities)
+                    .filter(|e| {
+                        e.attributes
+                            .item
+                            .as_ref()
+                            .is_some_and(|i| i.heft <= avail_space)
+                    })
+                    .filter(|e| e.attributes.clothing.is_some())
+                    .max_by(|a, b| {
+                        let warmth = |e: &Entity| e.as_clothing().map_or(0.0, |c| c.warmth);
+                        warmth(a).total_cmp(&warmth(b))
+                    })
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Not a good look
+                self.record_deed(Deed::StripCorpse, ctx);
+                ctx.send_log(GameLog::entity_triple(
+                    self,
+                    corpse_entity,
+                    clothing_entity,
+                    GameLogBody::EntityStripCorpse,
+                ));
+
+                self.relations
+                    .inventory_mut()
+                    .insert(clothing_entity.entity_id.clone());
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::TakeFromOther {
+                        entity_id: entity_id.clone(),
+                        item_id: clothing_entity.entity_id.clone(),
+                    },
+                ));
+            }
+
+            ActorAction::LootCorpse { corpse, item } => {
+                // Find that corpse, it must be here with us
+                let Some(corpse_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == corpse && e.attributes.corpse.is_some())
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // with the item on them (and we need room for it)
+                let avail_space = self.available_inventory_load(ctx.entities);
+                let Some(item_entity) = corpse_entity
+                    .resolve_inventory(ctx.entities)
+                    .find(|e| &e.entity_id == item)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                if item_entity
+                    .attributes
+                    .item
+                    .as_ref()
+                    .is_none_or(|i| i.heft > avail_space)
+                {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                // Most people would think twice about this
+                // (and some can't go through with it)
+                if self.hesitates_to_loot() {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        corpse_entity,
+                        GameLogBody::EntityHesitateBeforeLoot,
+                    ));
+                    if self.balks_at_looting(&mut crate::rng::rng()) {
+                        ctx.send_log(GameLog::entity_pair(
+                            self,
+                            corpse_entity,
+                            GameLogBody::EntityBalkAtLoot,
+                        ));
+                        return Ok(ActorActionResult::Ok);
+                    }
+                }
+
+                self.record_deed(Deed::LootCorpse, ctx);
+                ctx.send_log(GameLog::entity_triple(
+                    self,
+                    corpse_entity,
+                    item_entity,
+                    GameLogBody::EntityLootCorpse,
+                ));
+
+                // Going through their things, we might come across something of theirs
+                // (which is harder on anyone who hesitated in the first place)
+                if let Some((keepsake_entity, keepsake)) = corpse_entity
+                    .resolve_inventory(ctx.entities)
+                    .find_map(|e| Some((e, e.attributes.keepsake.as_ref()?)))
+                {
+                    if rng.random_bool(0.5) {
+                        ctx.send_log(GameLog::entity_triple(
+                            self,
+                            corpse_entity,
+                            keepsake_entity,
+                            GameLogBody::EntityFindKeepsake {
+                                description: keepsake.description.clone(),
+                            },
+                        ));
+                        if self.hesitates_to_loot() {
+                            self.attributes.motivators.bump::<Sadness>();
+                        }
+                    }
+                }
+
+                self.relations.inventory_mut().insert(item.clone());
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::TakeFromOther {
+                        entity_id: corpse.clone(),
+                        item_id: item.clone(),
+                    },
+                ));
+            }
+
+            ActorAction::SearchContainer(entity_id) => {
+                // Find that container, it must be here with us
+                let Some(container_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id && self.can_see(e))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some(container) = &container_entity.attributes.container else {
+                    warn!("Cannot search non-container");
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Did we find anything?
+                // (hidden containers are harder)
+                let mut chance = self.characteristic(Characteristic::Vision).success_chance();
+                if container.hidden {
+                    chance /= 2.0;
+                }
+                let contents = container_entity
+                    .resolve_inventory(ctx.entities)
+                    .collect_vec();
+                if contents.is_empty() || !rng.random_bool(chance) {
+                    // (no point looking through an empty one again)
+                    if contents.is_empty() {
+                        self.memes_mut()
+                            .insert(meme::Meme::Searched(container_entity.entity_id.clone()));
+                    }
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        container_entity,
+                        GameLogBody::EntitySearchFail,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                // Taking from someone elses stash is raiding it
+                if container_entity
+                    .attributes
+                    .cache
+                    .as_ref()
+                    .is_some_and(|cache| !cache.is_owner(&self.entity_id))
+                {
+                    self.record_deed(Deed::Theft, ctx);
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        container_entity,
+                        GameLogBody::EntityRaidCache,
+                    ));
+                }
+
+                // Log (and remember) each thing we found
+                for content_entity in contents {
+                    self.remember_spotted(&content_entity.entity_id);
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        content_entity,
+                        GameLogBody::EntitySearchFind,
+                    ));
+                }
+
+                // And pull it all out
+                self.memes_mut()
+                    .insert(meme::Meme::Searched(container_entity.entity_id.clone()));
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::EmptyContainer(
+                        container_entity.entity_id.clone(),
+                        my_hex,
+                    ),
+                ));
+            }
+
+            ActorAction::Throw { item, target } => {
+                // Has to be on us
+                if !self.relations.inventory().any(|id| id == item) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                let Some(item_entity) = ctx.entities.by_id(item) else {
+                    warn!("Attempted to throw non existent entity from inventory");
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Work out where its going, and whether we can get it there
+                let (target_hex, target_entity) = match target {
+                    ThrowTarget::Hex(hex) => (*hex, None),
+                    ThrowTarget::Entity(entity_id) => {
+                        let Some(target_entity) = ctx.entities.by_id(entity_id) else {
+                            return Ok(ActorActionResult::NoEffect);
+                        };
+                        let Some(hex) = target_entity.attributes.hex else {
+                            return Ok(ActorActionResult::NoEffect);
+                        };
+                        (hex, Some(target_entity))
+                    }
+                };
+                let distance = my_hex.dist_to(target_hex);
+                if distance > self.throw_range()
+                    || !target_hex.within_bounds(ctx.config.world_radius as isize)
+                {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                // Let it fly (keeping an eye on where it lands)
+                self.relations.inventory_mut().remove(item);
+                self.remember_spotted(item);
+                self.exert(Exertion::Moderate, ctx.entities);
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    item_entity,
+                    GameLogBody::EntityThrow,
+                ));
+
+                // Did it hit? (the nimble might get out of the way)
+                let hit = target_entity.filter(|target_entity| {
+                    let dodge = 0.5 * target_entity.acrobatics_chance(ctx.entities);
+                    let hit = rng.random_bool(self.throw_accuracy(distance) * (1.0 - dodge));
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        *target_entity,
+                        if hit {
+                            GameLogBody::EntityThrowHit
+                        } else {
+                            GameLogBody::EntityThrowMiss
+                        },
+                    ));
+                    hit
+                });
+
+                // Anyone there sees it come flying in
+                GameEventBuilder::new()
+                    .of_kind(GameEventKind::Projectile {
+                        entity_id: self.entity_id.clone(),
+                        hit: hit.map(|e| e.entity_id.clone()),
+                    })
+                    .targets(GameEventTarget::Hex(target_hex))
+                    .with_sense(Characteristic::Vision, 0)
+                    .add(ctx);
+
+                // Hurting people isn't a good look
+                if hit.is_some_and(|e| has_markers!(e, Player)) {
+                    self.record_deed(Deed::Assault, ctx);
+                }
+
+                // That might be enough to bring a predator down
+                let hurt = item_entity.thrown_damage() * THROW_HURT_SCALE;
+                if let Some(predator) = hit.filter(|e| e.brought_down_by(hurt)) {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        predator,
+                        GameLogBody::EntityKillPredator,
+                    ));
+                }
+
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::LandThrown {
+                        item_id: item.clone(),
+                        hex: target_hex,
+                        hit: hit.map(|e| (e.entity_id.clone(), hurt)),
+                    },
+                ));
+            }
+
+            ActorAction::FleeFrom { entity_id } => {
+                let Some(other_entity) = ctx.entities.by_id(entity_id) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some(other_hex) = other_entity.attributes.hex else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    other_entity,
+                    GameLogBody::EntityFlee,
+                ));
+
+                // Get as far away from them as we can
+                // (if they're right here, any direction will do)
+                let move_action = AxialHex::ring(my_hex, 1)
+                    .filter(|new_hex| {
+                        new_hex.dist_to(other_hex) > my_hex.dist_to(other_hex)
+                            || other_hex == my_hex
+                    })
+                    .choose(&mut rng)
+                    .and_then(|new_hex| AxialHexDirection::direction_to(my_hex, new_hex))
+                    .map_or(ActorAction::Nothing, ActorAction::Move);
+                return Ok(self.resolve_action(move_action, ctx));
+            }
+
+            ActorAction::FollowTracks { entity_id } => {
+                // Need their (freshest) tracks here
+                let Some(track) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .filter_map(|e| e.attributes.track.as_ref())
+                    .filter(|track| &track.entity_id == entity_id)
+                    .max_by_key(|track| track.fresh_for)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Can we make out which way they went?
+                let chance = self.track_reading_chance(ctx.world_state.weather.is_raining());
+                if !rng.random_bool(chance) {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        entity_id,
+                        GameLogBody::EntityLoseTracks,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    entity_id,
+                    GameLogBody::EntityFollowTracks,
+                ));
+                return Ok(self.resolve_action(ActorAction::Move(track.direction), ctx));
+            }
+
+            ActorAction::Stalk { entity_id } => {
+                let Some(quarry_hex) = ctx.entities.by_id(entity_id).and_then(|e| e.attributes.hex)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some(predator) = self.attributes.predator.as_mut() else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Someone new to go after?
+                let new_quarry = predator.quarry.as_ref() != Some(entity_id);
+                if new_quarry {
+                    predator.quarry = Some(entity_id.clone());
+                    predator.pursuit = 0;
+                }
+                predator.pursuit += 1;
+                let lost_interest = predator.pursuit >= MAX_PURSUIT_TICKS;
+                if new_quarry {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        entity_id,
+                        GameLogBody::PredatorStalk,
+                    ));
+                }
+
+                // They've kept ahead of us long enough
+                if lost_interest {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        entity_id,
+                        GameLogBody::PredatorGiveUp,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                // Follow their tracks if they left any, otherwise follow our nose
+                let has_tracks = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .filter_map(|e| e.attributes.track.as_ref())
+                    .any(|track| &track.entity_id == entity_id);
+                if has_tracks {
+                    return Ok(self.resolve_action(
+                        ActorAction::FollowTracks {
+                            entity_id: entity_id.clone(),
+                        },
+                        ctx,
+                    ));
+                }
+                return Ok(self.resolve_action(ActorAction::GoTowardsHex(quarry_hex), ctx));
+            }
+
+            ActorAction::Maul { entity_id } => {
+                // Only if the match lets the world hurt anyone
+                if !ctx.config.ruleset.hazards {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                let Some(quarry) = ctx
+                    .entities
+                    .by_id(entity_id)
+                    .filter(|e| e.attributes.hex == Some(my_hex))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                if let Some(predator) = self.attributes.predator.as_mut() {
+                    if predator.quarry.as_ref() != Some(entity_id) {
+                        predator.quarry = Some(entity_id.clone());
+                        predator.pursuit = 0;
+                    }
+                }
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    quarry,
+                    GameLogBody::PredatorMaul,
+                ));
+
+                // Anyone nearby sees it happen
+                GameEventBuilder::new()
+                    .of_kind(GameEventKind::Attack {
+                        entity_id: self.entity_id.clone(),
+                        victim: entity_id.clone(),
+                    })
+                    .targets(GameEventTarget::Hex(my_hex))
+                    .with_sense(
+                        Characteristic::Vision,
+                        ctx.world_state.weather.sight_range(1),
+                    )
+                    .add(ctx);
+
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::HurtOther {
+                        entity_id: entity_id.clone(),
+                        amount: MAUL_HURT,
+                    },
+                ));
+            }
+
+            ActorAction::LookAround => {
+                let noticed = self.notice_props(
+                    my_hex,
+                    ctx.entities,
+                    ctx.world_state.weather.visibility(),
+                    &mut rng,
+                );
+                if noticed.is_empty() {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                for prop_entity in noticed {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        prop_entity,
+                        GameLogBody::EntitySpotProp,
+                    ));
+                }
+            }
+
+            ActorAction::ConsumeNearbyFood {
+                try_dubious,
+                try_morally_wrong,
+            } => {
+                // Is there food at this location?
+                let food_entities = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .filter(|e| self.can_see(e))
+                    .filter(|e| match e.attributes.food {
+                        // Is it food at all?
+                        None => false,
+
+                        // Is it food but dubious?
+                        Some(EntityFood {
+                            poison,
+                            morally_wrong,
+                            ..
+                        }) if poison > 0.0 => {
+                            *try_dubious && (!morally_wrong || *try_morally_wrong)
+                        }
+
+                        // Good food
+                        Some(EntityFood { .. }) => true,
+                    });
+                let Some(food_entity) = food_entities.choose(&mut rng) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                return Ok(self.resolve_action(
+                    ActorAction::ConsumeFoodEntity(food_entity.entity_id.clone()),
+                    ctx,
+                ));
+            }
+
+            // NOTE: entity may not exist at this point
+            ActorAction::ConfrontEntity { entity_id } => {
+                // They have to be here
+                let Some(other_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Tell them off, and think less of them
+                self.relations.decrease_associate_bond_by(entity_id, 0.1);
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    other_entity,
+                    GameLogBody::EntityConfront,
+                ));
+            }
+
+            ActorAction::AvoidEntity { entity_id } => {
+                // They have to be here
+                let Some(other_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Emit log
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    other_entity,
+                    GameLogBody::EntityShun,
+                ));
+
+                // Then move randomly
+                let move_action = ActorAction::all_movements()
+                    .choose(&mut rng)
+                    .unwrap()
+                    .clone();
+                return Ok(self.resolve_action(move_action, ctx));
+            }
+
+            ActorAction::StealFood { entity_id } => {
+                // They have to be here and have some food on them
+                let Some(other_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some(food_entity) = other_entity
+                    .resolve_inventory(ctx.entities)
+                    .find(|e| e.attributes.food.is_some())
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Whether or not we get away with it, people saw us try
+                self.record_deed(Deed::Theft, ctx);
+
+                // Do we get away with it?
+                let caught = !rng.random_bool(self.acrobatics_chance(ctx.entities));
+                self.exert(Exertion::Heavy, ctx.entities);
+                if caught {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        other_entity,
+                        GameLogBody::EntityStealFail,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                // Take it for ourselves
+                ctx.send_log(GameLog::entity_triple(
+                    self,
+                    other_entity,
+                    food_entity,
+                    GameLogBody::EntitySteal,
+                ));
+                self.relations
+                    .inventory_mut()
+                    .insert(food_entity.entity_id.clone());
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::TakeFromOther {
+                        entity_id: entity_id.clone(),
+                        item_id: food_entity.entity_id.clone(),
+                    },
+                ));
+            }
+
+            ActorAction::StartTravellingWith { entity_id } => {
+                // They have to be here
+                let Some(companion) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Interest scales w/ how close we are
+                let max_interest = 40f32;
+                let interest = ((self.relations.bond(entity_id) * max_interest) as usize)
+                    .clamp(10, max_interest as usize);
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    companion,
+                    GameLogBody::EntityStartTravelling,
+                ));
+
+                // We lead, they follow
+                self.attributes.focus = Some(ActorFocus::TravelTogether {
+                    with: entity_id.clone(),
+                    interest,
+                    is_leader: true,
+                });
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::SetFocus {
+                        entity_id: entity_id.clone(),
+                        focus: ActorFocus::TravelTogether {
+                            with: self.entity_id.clone(),
+                            interest,
+                            is_leader: false,
+                        },
+                    },
+                ));
+            }
+
+            ActorAction::TravelTogether => {
+                let Some(ActorFocus::TravelTogether {
+                    ref with,
+                    ref mut interest,
+                    is_leader,
+                }) = self.attributes.focus
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Lose interest over time
+                *interest = interest.saturating_sub(1);
+                if *interest == 0 {
+                    return Ok(self.resolve_action(ActorAction::PartWays, ctx));
+                }
+                let with = with.clone();
+
+                // The leader wanders about
+                if is_leader {
+                    if rng.random_bool(0.3) {
+                        let move_action = ActorAction::all_movements()
+                            .choose(&mut rng)
+                            .unwrap()
+                            .clone();
+                        return Ok(self.resolve_action(move_action, ctx));
+                    }
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                // The follower goes wherever the leader went
+                let Some(leader_hex) = ctx.entities.by_id(&with).and_then(|e| e.attributes.hex)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                return Ok(self.resolve_action(ActorAction::GoTowardsHex(leader_hex), ctx));
+            }
+
+            ActorAction::PartWays => {
+                let Some(ActorFocus::TravelTogether { with, .. }) = self.attributes.focus.take()
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                self.attributes.focus = Some(ActorFocus::Unfocused);
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    &with,
+                    GameLogBody::EntityPartWays,
+                ));
+            }
+
+            ActorAction::MournEntity { entity_id } => {
+                // Get sad
+                self.attributes.motivators.bump::<Sadness>();
+
+                // Find the corpse
+                let maybe_corpse_entity = ctx
+                    .entities
+                    .all()
+                    .find(|e| e.attributes.corpse == Some(entity_id.clone()));
+
+                // And log (remembering who they were)
+                let Some(corpse_entity) = maybe_corpse_entity else {
+                    warn!("NO CORPSE");
+                    return Ok(ActorActionResult::Ok);
+                };
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    corpse_entity,
+                    GameLogBody::EntityMournOverCorpse {
+                        remembering: corpse_entity
+                            .attributes
+                            .remains
+                            .as_ref()
+                            .map(|remains| remains.summary.clone()),
+                    },
+                ));
+
+                // If we were close, it'll stay with us for a while
+                if self.relations.bond(entity_id) >= ALLY_BOND {
+                    let name = corpse_entity
+                        .attributes
+                        .remains
+                        .as_ref()
+                        .map(|remains| remains.name.clone())
+                        .unwrap_or_else(|| corpse_entity.name.clone());
+                    self.start_grieving(entity_id, name, corpse_entity.attributes.hex, ctx.tick_id);
+                }
+
+                // If we were close, we might keep something of theirs to remember them by
+                // (if they're here, and we don't have something of theirs already)
+                let already_kept = self.resolve_inventory(ctx.entities).any(|e| {
+                    e.attributes
+                        .keepsake
+                        .as_ref()
+                        .is_some_and(|k| &k.owner == entity_id)
+                });
+                if self.relations.bond(entity_id) < ALLY_BOND
+                    || already_kept
+                    || corpse_entity.attributes.hex != Some(my_hex)
+                {
+                    return Ok(ActorActionResult::Ok);
+                }
+                let avail_space = self.available_inventory_load(ctx.entities);
+                let Some((keepsake_entity, keepsake)) = corpse_entity
+                    .resolve_inventory(ctx.entities)
+                    .filter(|e| {
+                        e.attributes
+                            .item
+                            .as_ref()
+                            .is_some_and(|i| i.heft <= avail_space)
+                    })
+                    .find_map(|e| Some((e, e.attributes.keepsake.as_ref()?)))
+                else {
+                    return Ok(ActorActionResult::Ok);
+                };
+                ctx.send_log(GameLog::entity_triple(
+                    self,
+                    corpse_entity,
+                    keepsake_entity,
+                    GameLogBody::EntityKeepKeepsake {
+                        description: keepsake.description.clone(),
+                    },
+                ));
+                self.relations
+                    .inventory_mut()
+                    .insert(keepsake_entity.entity_id.clone());
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::TakeOutOfOther {
+                        entity_id: corpse_entity.entity_id.clone(),
+                        item_id: keepsake_entity.entity_id.clone(),
+                    },
+                ));
+            }
+
+            ActorAction::HoldMemorial { entity_id } => {
+                let Some(loss) = self
+                    .attributes
+                    .grief
+                    .as_ref()
+                    .and_then(|grief| grief.loss(entity_id))
+                    .filter(|loss| !loss.memorial_held)
+                    .cloned()
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Held by their corpse if it's still around, otherwise where they died
+                let corpse_entity = ctx
+                    .entities
+                    .all()
+                    .find(|e| e.attributes.corpse.as_ref() == Some(entity_id));
+                let Some(memorial_hex) = corpse_entity.and_then(|e| e.attributes.hex).or(loss.hex)
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                if memorial_hex != my_hex {
+                    return Ok(self.resolve_action(ActorAction::GoTowardsHex(memorial_hex), ctx));
+                }
+
+                let log_body = GameLogBody::EntityHoldMemorial {
+                    name: loss.name.clone(),
+                };
+                ctx.send_log(match corpse_entity {
+                    Some(corpse_entity) => GameLog::entity_pair(self, corpse_entity, log_body),
+                    None => GameLog::entity(self, log_body),
+                });
+                self.remember_memorial(entity_id);
+
+                // Anyone else here who cared for them might join in
+                GameEventBuilder::new()
+                    .of_kind(GameEventKind::Memorial {
+                        entity_id: self.entity_id.clone(),
+                        deceased: entity_id.clone(),
+                        name: loss.name,
+                    })
+                    .targets_hex_of(self)
+                    .add(ctx);
+
+                return Ok(ActorActionResult::Ok);
+            }
+
+            ActorAction::JoinMemorial {
+                host,
+                deceased,
+                name,
+            } => {
+                let Some(host_entity) = ctx
+                    .entities
+                    .by_id(host)
+                    .filter(|e| e.attributes.hex == Some(my_hex))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    host_entity,
+                    GameLogBody::EntityJoinMemorial { name: name.clone() },
+                ));
+                self.remember_memorial(deceased);
+                self.relations.increase_associate_bond(host);
+                return Ok(ActorActionResult::Ok);
+            }
+
+            ActorAction::DrinkFromWaterSource { try_dubious } => {
+                // Is there water within reach? (here, or out on the open water beside us)
+                let water_source_entities = rafting::water_within_reach(my_hex, ctx.entities)
+                    .filter(|e| self.memes_mut().assumably_safe(&e.entity_id))
+                    .filter(|e| match &e.attributes.water_source {
+                        // its run dry
+                        Some(water_source) if water_source.is_empty() => false,
+
+                        // its dubious, are we okay with that?
+                        Some(EntityWaterSource { poison, .. }) if *poison > 0.0 => *try_dubious,
+
+                        // not dubious (fallthrough)
+                        Some(EntityWaterSource { .. }) => true,
+
+                        // its not a water source
+                        None => false,
+                    });
+
+                // If no applicable water source, there's no effect
+                // (and if it's all run dry here, no point coming back until it fills up again)
+                let Some(water_source_entity) = water_source_entities.choose(&mut rng) else {
+                    let run_dry = rafting::water_within_reach(my_hex, ctx.entities).any(|e| {
+                        e.attributes
+                            .water_source
+                            .as_ref()
+                            .is_some_and(|w| w.is_empty())
+                    });
+                    if run_dry {
+                        self.memes_mut().remove(&meme::Meme::WaterSourceAt(my_hex));
+                    }
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // if there is, drink from it
+                let water_source = water_source_entity.as_water_source()?;
+
+                // Fully clear thirst
+                self.attributes.motivators.clear::<motivator::Thirst>();
+
+                // Emit log
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    water_source_entity,
+                    GameLogBody::EntityDrinkFrom,
+                ));
+
+                // Should we get sick?
+                if water_source.poison > 0.0 {
+                    self.attributes
+                        .motivators
+                        .bump_scaled::<motivator::Sickness>(2.0 * water_source.poison);
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        water_source_entity,
+                        GameLogBody::EntityComplainAboutTaste,
+                    ));
+
+                    // Remember it's dangerous
+                    self.memes_mut()
+                        .remember_is_dangerous(water_source_entity.id());
+                }
+
+                // If the water source was safe, remember it
+                if water_source.poison == 0.0 {
+                    self.memes_mut().remember_is_safe(water_source_entity.id());
+                    self.memes_mut()
+                        .insert(meme::Meme::WaterSourceAt(water_source_entity.located()?));
+                }
+
+                // Smaller sources only hold so much
+                if water_source.capacity.is_some() && ctx.config.has_feature(FeatureFlag::Drought) {
+                    return Ok(ActorActionResult::SideEffect(
+                        ActorActionSideEffect::DrinkFromOther(
+                            water_source_entity.entity_id.clone(),
+                        ),
+                    ));
+                }
+
+                return Ok(ActorActionResult::Ok);
+            }
+
+            ActorAction::GreetEntity { entity_id } => {
+                let entity = ctx.entities.require(entity_id)?;
+
+                // Is there an established association relation?
+                let bond = self.relations.bond(entity_id);
+
+                // Log
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    entity,
+                    GameLogBody::EntityGreet {
+                        bond,
+                        response: false,
+                    },
+                ));
+
+                // If they are unfriendly, this goes differently
+                // NOTE: if they dont have motivators, we assume they are friendly (assuming that animals etc are friendly)
+                //       (except predators, which are anything but)
+                let friendliness = entity.characteristic(Characteristic::Friendliness);
+                if friendliness < CharacteristicStrength::Average
+                    || entity.attributes.predator.is_some()
+                {
+                    // they ignore us
+                    ctx.send_log(GameLog::entity_pair(
+                        entity,
+                        &self.entity_id,
+                        GameLogBody::EntityIgnore,
+                    ));
+
+                    // And we like them less
+                    self.relations.decrease_associate_bond(&entity.entity_id);
+                } else {
+                    // Just them responding makes us like them
+                    self.relations.increase_associate_bond(&entity.entity_id);
+
+                    // And if they can respond, we start a chat with them
+                    if has_markers!(entity, CanTalk) {
+                        // And we start talking to them
+                        // Initial interest scales w/ bond but has a minimum
+                        // (for simplicity our interest starts the same as theirs in the convo)
+                        let max_interest = 20f32;
+                        let interest =
+                            ((bond * max_interest) as usize).clamp(2, max_interest as usize);
+
+                        // Log the greet response
+                        ctx.send_log(GameLog::entity_pair(
+                            entity,
+                            self.id(),
+                            GameLogBody::EntityGreet {
+                                bond,
+                                response: true,
+                            },
+                        ));
+
+                        ctx.record_stat(self, PlayerStat::ConversationHeld);
+                        ctx.record_stat(entity, PlayerStat::ConversationHeld);
+
+                        // Set our focus
+                        self.attributes.focus = Some(ActorFocus::Discussion {
+                            with: entity_id.clone(),
+                            is_lead: true,
+                            interest,
+                        });
+
+                        // TODO: maybe there's a strat here where we force them to do a "talk" action w/ us instead
+                        return Ok(ActorActionResult::SideEffect(
+                            ActorActionSideEffect::SetFocus {
+                                entity_id: entity_id.clone(),
+                                focus: ActorFocus::Discussion {
+                                    with: self.entity_id.clone(),
+                                    is_lead: false,
+                                    interest,
+                                },
+                            },
+                        ));
+                    }
+                }
+            }
+
+            ActorAction::TakeShelter => {
+                // Is there shelter at my location?
+                let Some(shelter_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| has_markers!(e, Shelter))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Shelter in that thang
+                self.attributes.focus = Some(ActorFocus::Sheltering {
+                    shelter_entity_id: shelter_entity.entity_id.clone(),
+                });
+
+                // Log it
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    shelter_entity,
+                    GameLogBody::EntityTakeShelter,
+                ));
+
+                // and remember it
+                self.memes_mut()
+                    .insert(meme::Meme::ShelterAt(shelter_entity.located()?));
+
+                return Ok(ActorActionResult::Ok);
+            }
+
+            ActorAction::LeaveShelter => {
+                // Check we are in shelter
+                let Some(ActorFocus::Sheltering { shelter_entity_id }) =
+                    self.attributes.focus.clone()
+                else {
+                    warn!("Tried to leave shelter but not in shelter");
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Then leave shelter
+                self.attributes.focus = Some(ActorFocus::Unfocused);
+
+                // and log that
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    &shelter_entity_id,
+                    GameLogBody::EntityLeaveShelter,
+                ));
+
+                return Ok(ActorActionResult::Ok);
+            }
+
+            ActorAction::ClaimHex => {
+                // Need a shelter here that isnt already someone elses
+                // (claims of those no longer around dont count)
+                let Some(shelter_entity) = ctx.entities.in_hex(my_hex).find(|e| {
+                    e.attributes.shelter.as_ref().is_some_and(|s| {
+                        s.claimed_by
+                            .as_ref()
+                            .is_none_or(|claimant| ctx.entities.by_id(claimant).is_none())
+                    })
+                }) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    shelter_entity,
+                    GameLogBody::EntityClaimHex,
+                ));
+
+                let claim = meme::Meme::Claim(self.entity_id.clone(), my_hex);
+                self.memes_mut().insert(claim);
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::ClaimOther(shelter_entity.entity_id.clone()),
+                ));
+            }
+
+            ActorAction::RepairShelter => {
+                // Need a shelter here and something to fix it with
+                let Some(shelter_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| e.attributes.shelter.is_some())
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some((material_id, strength)) =
+                    self.resolve_inventory(ctx.entities).find_map(|e| {
+                        Some((
+                            e.entity_id.clone(),
+                            e.attributes.material.as_ref()?.strength,
+                        ))
+                    })
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    shelter_entity,
+                    GameLogBody::EntityRepairShelter,
+                ));
+
+                // Its hard work
+                self.exert(Exertion::Heavy, ctx.entities);
+
+                // The material gets used up
+                self.relations.inventory_mut().remove(&material_id);
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::RepairOther {
+                        entity_id: shelter_entity.entity_id.clone(),
+                        material_id,
+                        amount: strength,
+                    },
+                ));
+            }
+
+            ActorAction::RepairPod { pod } => {
+                // Need the pod here and a part it's missing
+                let Some(pod_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == pod && e.attributes.escape_pod.is_some())
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some(escape_pod) = &pod_entity.attributes.escape_pod else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some((part_id, part)) = self.part_for_pod(escape_pod, ctx.entities) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Its fiddly work
+                self.exert(Exertion::Heavy, ctx.entities);
+                if !self.fits_pod_part(&mut crate::rng::rng()) {
+                    ctx.send_log(GameLog::entity_triple(
+                        self,
+                        pod_entity,
+                        &part_id,
+                        GameLogBody::EntityFumblePodRepair,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                ctx.send_log(GameLog::entity_triple(
+                    self,
+                    pod_entity,
+                    &part_id,
+                    GameLogBody::EntityFitPodPart,
+                ));
+                self.relations.inventory_mut().remove(&part_id);
+
+                // That was the last one, so we're out of here
+                if escape_pod.nearly_fixed() {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        pod_entity,
+                        GameLogBody::EntityEscapeInPod,
+                    ));
+                    self.markers.push(EntityMarker::Escaped);
+                    self.attributes.hex = None;
+                    self.attributes.focus = Some(ActorFocus::Unfocused);
+                }
+
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::FitPodPart {
+                        pod_id: pod_entity.entity_id.clone(),
+                        part_id,
+                        part,
+                    },
+                ));
+            }
+
+            ActorAction::Fish => {
+                // Can only fish at (or from beside) a lake
+                let Some(lake_entity) = rafting::lake_within_reach(my_hex, ctx.entities) else {
+                    if matches!(self.attributes.focus, Some(ActorFocus::Fishing { .. })) {
+                        self.attributes.focus = Some(ActorFocus::Unfocused);
+                    }
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // If we aren't already fishing, start now
+                let Some(ActorFocus::Fishing { ticks_invested }) = self.attributes.focus else {
+                    self.attributes.focus = Some(ActorFocus::Fishing { ticks_invested: 0 });
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        lake_entity,
+                        GameLogBody::EntityStartFishing,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                };
+
+                // Sitting around waiting is tiring in its own way
+                self.exert(Exertion::Light, ctx.entities);
+
+                // Leaning out too far, and in we go
+                let fall_in_chance =
+                    FISHING_FALL_IN_CHANCE * (1.0 - 0.5 * self.acrobatics_chance(ctx.entities));
+                if rng.random_bool(fall_in_chance) {
+                    self.attributes.focus = Some(ActorFocus::Unfocused);
+                    self.attributes
+                        .motivators
+                        .bump_scaled::<motivator::Saturation>(2.0);
+
+                    // and have to haul ourselves back out
+                    self.exert(Exertion::Heavy, ctx.entities);
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        lake_entity,
+                        GameLogBody::EntityFellInWaterSource,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                // The longer we wait, and the better our gear, the more likely a bite
+                let tool_bonus = self
+                    .resolve_inventory(ctx.entities)
+                    .filter_map(|e| Some(e.attributes.tool.as_ref()?.kind.fishing_bonus()))
+                    .fold(0.0, f64::max);
+                let catch_chance = (FISHING_BASE_CHANCE
+                    + FISHING_PATIENCE_CHANCE * ticks_invested as f64
+                    + tool_bonus)
+                    .min(1.0);
+                if rng.random_bool(catch_chance) {
+                    self.attributes.focus = Some(ActorFocus::Unfocused);
+
+                    // Keep it if we have room, otherwise it goes on the bank
+                    let mut fish_entity = PropGenerator::Fish.generate(&mut rng);
+                    if fish_entity.as_item()?.heft <= self.available_inventory_load(ctx.entities) {
+                        self.relations
+                            .inventory_mut()
+                            .insert(fish_entity.entity_id.clone());
+                    } else {
+                        fish_entity.attributes.hex = Some(my_hex);
+                        self.remember_spotted(&fish_entity.entity_id);
+                    }
+
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        &fish_entity,
+                        GameLogBody::EntityCatchFish,
+                    ));
+                    return Ok(ActorActionResult::SideEffect(
+                        ActorActionSideEffect::SpawnOther(Box::new(fish_entity)),
+                    ));
+                }
+
+                // Nothing biting today
+                if ticks_invested + 1 >= MAX_FISHING_TICKS {
+                    return Ok(self.resolve_action(ActorAction::StopFishing, ctx));
+                }
+
+                self.attributes.focus = Some(ActorFocus::Fishing {
+                    ticks_invested: ticks_invested + 1,
+                });
+                ctx.send_log(GameLog::entity(self, GameLogBody::EntityKeepFishing));
+            }
+
+            ActorAction::StopFishing => {
+                if !matches!(self.attributes.focus, Some(ActorFocus::Fishing { .. })) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                self.attributes.focus = Some(ActorFocus::Unfocused);
+                ctx.send_log(GameLog::entity(self, GameLogBody::EntityStopFishing));
+            }
+
+            ActorAction::StandGuard { entity_id } => {
+                // Only worth it while they're asleep here with us
+                let Some(ward_entity) = ctx.entities.in_hex(my_hex).find(|e| {
+                    &e.entity_id == entity_id
+                        && matches!(e.attributes.focus, Some(ActorFocus::Sleeping { .. }))
+                }) else {
+                    if matches!(self.attributes.focus, Some(ActorFocus::Guarding { .. })) {
+                        self.attributes.focus = Some(ActorFocus::Unfocused);
+                    }
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Staying alert is tiring in its own way
+                self.exert(Exertion::Light, ctx.entities);
+
+                // Already keeping watch over them?
+                if matches!(&self.attributes.focus, Some(ActorFocus::Guarding { ward }) if ward == entity_id)
+                {
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        ward_entity,
+                        GameLogBody::EntityKeepGuarding,
+                    ));
+                    return Ok(ActorActionResult::Ok);
+                }
+
+                self.attributes.focus = Some(ActorFocus::Guarding {
+                    ward: entity_id.clone(),
+                });
+                self.relations.increase_associate_bond_by(entity_id, 0.05);
+                self.record_deed(Deed::Rescue, ctx);
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    ward_entity,
+                    GameLogBody::EntityStartGuarding,
+                ));
+            }
+
+            ActorAction::StopGuarding => {
+                if !matches!(self.attributes.focus, Some(ActorFocus::Guarding { .. })) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                self.attributes.focus = Some(ActorFocus::Unfocused);
+                ctx.send_log(GameLog::entity(self, GameLogBody::EntityStopGuarding));
+            }
+
+            ActorAction::LightCampfire { material } => {
+                // Only one fire to a hex
+                if ctx.entities.in_hex(my_hex).any(|e| has_markers!(e, Fire)) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                // and we need something to burn
+                ctx.entities.require(material)?;
+                if !self.relations.inventory_mut().remove(material) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                let mut campfire = PropGenerator::Campfire.generate(&mut rng);
+                campfire.attributes.hex = Some(my_hex);
+                self.exert(Exertion::Light, ctx.entities);
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    &campfire,
+                    GameLogBody::EntityLightCampfire,
+                ));
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::LightFire {
+                        material_id: material.clone(),
+                        fire: Box::new(campfire),
+                    },
+                ));
+            }
+
+            ActorAction::JoinGathering { campfire } => {
+                let Some(campfire_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == campfire && gathering::is_campfire(e))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                self.attributes.focus = Some(ActorFocus::Gathering {
+                    campfire: campfire.clone(),
+                    stories_told: 0,
+                });
+                ctx.send_log(
+                    GameLog::entity_pair(self, campfire_entity, GameLogBody::EntityJoinGathering)
+                        .with_channel(LogChannel::Group {
+                            group_id: campfire.clone(),
+                        }),
+                );
+            }
+
+            ActorAction::TellStory => {
+                let Some(ActorFocus::Gathering { campfire, .. }) = self.attributes.focus.clone()
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let listeners = gathering::gathered_around(&campfire, my_hex, ctx.entities)
+                    .filter(|e| e.entity_id != self.entity_id)
+                    .map(|e| e.entity_id.clone())
+                    .collect_vec();
+                if listeners.is_empty() {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                let Some(story) = self.random_story(&mut rng) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                if let Some(ActorFocus::Gathering { stories_told, .. }) =
+                    self.attributes.focus.as_mut()
+                {
+                    *stories_told += 1;
+                }
+
+                // Getting it off our chest helps us too
+                let motivators = &mut self.attributes.motivators;
+                motivators.reduce_by::<motivator::Sadness>(gathering::STORY_CHEER);
+                motivators.reduce_by::<motivator::Boredom>(gathering::STORY_CHEER);
+
+                ctx.send_log(
+                    GameLog::entity_group(
+                        self,
+                        &listeners,
+                        GameLogBody::EntityTellStory { about: story },
+                    )
+                    .with_channel(LogChannel::Group { group_id: campfire }),
+                );
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::EntertainOthers {
+                        entity_ids: listeners,
+                    },
+                ));
+            }
+
+            ActorAction::LeaveGathering => {
+                let Some(ActorFocus::Gathering { campfire, .. }) = self.attributes.focus.clone()
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                self.attributes.focus = Some(ActorFocus::Unfocused);
+                ctx.send_log(
+                    GameLog::entity(self, GameLogBody::EntityLeaveGathering)
+                        .with_channel(LogChannel::Group { group_id: campfire }),
+                );
+            }
+
+            ActorAction::BuildRaft => {
+                // Only worth it beside open water, and one raft to a hex
+                if !rafting::beside_open_water(my_hex, ctx.entities)
+                    || ctx
+                        .entities
+                        .in_hex(my_hex)
+                        .any(|e| e.attributes.raft.is_some())
+                {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                // and it takes a fair bit of material
+                let Some(material_ids) = self.raft_materials(ctx.entities) else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                for material_id in &material_ids {
+                    self.relations.inventory_mut().remove(material_id);
+                }
+
+                let mut raft = PropGenerator::Raft.generate(&mut rng);
+                raft.attributes.hex = Some(my_hex);
+                if let Some(raft) = raft.attributes.raft.as_mut() {
+                    raft.owner = Some(self.entity_id.clone());
+                }
+                self.exert(Exertion::Heavy, ctx.entities);
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    &raft,
+                    GameLogBody::EntityBuildRaft,
+                ));
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::BuildRaft {
+                        material_ids,
+                        raft: Box::new(raft),
+                    },
+                ));
+            }
+
+            ActorAction::BoardRaft { raft } => {
+                if matches!(self.attributes.focus, Some(ActorFocus::Rafting { .. })) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                let Some((raft_entity, raft_details)) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == raft)
+                    .and_then(|e| Some((e, e.attributes.raft.as_ref()?)))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Is there room for us?
+                let aboard = rafting::aboard(raft_entity, ctx.entities).collect_vec();
+                if aboard.len() >= raft_details.capacity {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                // Unless whoever it belongs to is aboard to have us, we're taking it for ourselves
+                // (and if it was someone else's, that's theft)
+                let owner = raft_details.owner.as_ref().filter(|owner| {
+                    **owner != self.entity_id && ctx.entities.by_id(owner).is_some()
+                });
+                let owner_aboard =
+                    owner.is_some_and(|owner| aboard.iter().any(|e| e.entity_id == *owner));
+                self.attributes.focus = Some(ActorFocus::Rafting { raft: raft.clone() });
+                match owner.filter(|_| !owner_aboard) {
+                    Some(owner) => {
+                        self.record_deed(Deed::Theft, ctx);
+                        ctx.send_log(GameLog::entity_triple(
+                            self,
+                            raft_entity,
+                            owner,
+                            GameLogBody::EntityTakeRaft,
+                        ));
+                    }
+                    None => {
+                        ctx.send_log(GameLog::entity_pair(
+                            self,
+                            raft_entity,
+                            GameLogBody::EntityBoardRaft,
+                        ));
+                    }
+                }
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::BoardRaft {
+                        raft_id: raft.clone(),
+                        claim: !owner_aboard,
+                    },
+                ));
+            }
+
+            ActorAction::PaddleRaft(hex_direction) => {
+                let Some(ActorFocus::Rafting { raft }) = self.attributes.focus.clone() else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+                let Some((raft_entity, raft_details)) = ctx
+                    .entities
+                    .by_id(&raft)
+                    .and_then(|e| Some((e, e.attributes.raft.as_ref()?)))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // Rafts only go on the water
+                let new_hex = my_hex + (*hex_direction).into();
+                if !new_hex.within_bounds(ctx.config.world_radius as isize)
+                    || !ctx.entities.resources(new_hex).open_water
+                {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                self.exert(Exertion::Moderate, ctx.entities);
+
+                // Rough water might tip us over (more likely, the more of us there are aboard)
+                let aboard = rafting::aboard(raft_entity, ctx.entities).count().max(1);
+                if self.capsizes(&ctx.world_state.weather, raft_details, aboard, &mut rng) {
+                    self.attributes.focus = Some(ActorFocus::Unfocused);
+                    let motivators = &mut self.attributes.motivators;
+                    motivators.bump_scaled::<motivator::Saturation>(2.0);
+                    motivators.bump_scaled::<motivator::Hurt>(rafting::CAPSIZE_HURT);
+                    ctx.send_log(GameLog::entity_pair(
+                        self,
+                        raft_entity,
+                        GameLogBody::EntityCapsize,
+                    ));
+                    return Ok(ActorActionResult::SideEffect(
+                        ActorActionSideEffect::CapsizeRaft(raft),
+                    ));
+                }
+
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    raft_entity,
+                    GameLogBody::EntityPaddleRaft,
+                ));
+                self.attributes.hex = Some(new_hex);
+                ctx.record_stat(self, PlayerStat::HexTraveled);
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::MoveRaft {
+                        raft_id: raft,
+                        hex: new_hex,
+                    },
+                ));
+            }
+
+            ActorAction::DisembarkRaft => {
+                let Some(ActorFocus::Rafting { raft }) = self.attributes.focus.clone() else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // If it's gone, we're already off it
+                let Some(raft_entity) = ctx.entities.by_id(&raft) else {
+                    self.attributes.focus = Some(ActorFocus::Unfocused);
+                    return Ok(ActorActionResult::Ok);
+                };
+
+                // Out on the water, we have to paddle it ashore first
+                // (bringing everyone else aboard with us)
+                let shore = if ctx.entities.resources(my_hex).open_water {
+                    let Some(shore) = my_hex
+                        .neighbours()
+                        .into_iter()
+                        .filter(|h| {
+                            h.within_bounds(ctx.config.world_radius as isize)
+                                && !ctx.entities.resources(*h).open_water
+                        })
+                        .choose(&mut rng)
+                    else {
+                        return Ok(ActorActionResult::NoEffect);
+                    };
+                    Some(shore)
+                } else {
+                    None
+                };
+
+                self.attributes.focus = Some(ActorFocus::Unfocused);
+                if let Some(shore) = shore {
+                    self.attributes.hex = Some(shore);
+                }
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    raft_entity,
+                    GameLogBody::EntityDisembarkRaft,
+                ));
+                if let Some(shore) = shore {
+                    return Ok(ActorActionResult::SideEffect(
+                        ActorActionSideEffect::MoveRaft {
+                            raft_id: raft,
+                            hex: shore,
+                        },
+                    ));
+                }
+            }
+
+            ActorAction::SeekKnownWaterSource => {
                 // The only way we have to do this is to use shelter memes
                 let Some(water_source_loc) = self
                     .memes_mut()
@@ -716,11 +2524,11 @@ impl Entity {
                     .min_by_key(|l| l.dist_to(my_hex))
                 else {
                     // we dont know of any
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
                 // Go towards that
-                return self.resolve_action(ActorAction::GoTowardsHex(water_source_loc), ctx);
+                return Ok(self.resolve_action(ActorAction::GoTowardsHex(water_source_loc), ctx));
             }
 
             ActorAction::SeekKnownShelter => {
@@ -731,11 +2539,11 @@ impl Entity {
                     .min_by_key(|l| l.dist_to(my_hex))
                 else {
                     // we dont know of any
-                    return ActorActionResult::NoEffect;
+                    return Ok(ActorActionResult::NoEffect);
                 };
 
                 // Go towards that
-                return self.resolve_action(ActorAction::GoTowardsHex(shelter_loc), ctx);
+                return Ok(self.resolve_action(ActorAction::GoTowardsHex(shelter_loc), ctx));
             }
 
             ActorAction::WarpInEntity(entity_id) => {
@@ -757,33 +2565,149 @@ impl Entity {
                     .with_physical_senses(0)
                     .add(ctx);
 
-                return ActorActionResult::SideEffect(ActorActionSideEffect::UnbanishOther(
-                    entity_id.clone(),
-                    warp_hex,
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::UnbanishOther(entity_id.clone(), warp_hex),
+                ));
+            }
+
+            ActorAction::Teleport(hex) => {
+                // Only the crew can pull this off
+                if !has_markers!(self, Crew)
+                    || *hex == my_hex
+                    || !hex.within_bounds(ctx.config.world_radius as isize)
+                {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+
+                ctx.send_log(GameLog::entity(self, GameLogBody::EntityTeleport));
+
+                // Anyone around either end sees it happen
+                for warp_hex in [my_hex, *hex] {
+                    GameEventBuilder::new()
+                        .of_kind(GameEventKind::Warp {
+                            entity_id: self.entity_id.clone(),
+                        })
+                        .targets(GameEventTarget::HexSurrounds(warp_hex))
+                        .with_sense(
+                            Characteristic::Vision,
+                            ctx.world_state.weather.sight_range(1),
+                        )
+                        .add(ctx);
+                }
+                GameEventBuilder::new()
+                    .of_kind(GameEventKind::ArriveInHex {
+                        entity_id: self.entity_id.clone(),
+                    })
+                    .targets(GameEventTarget::Hex(*hex))
+                    .with_physical_senses(0)
+                    .add(ctx);
+
+                self.attributes.hex = Some(*hex);
+                return Ok(ActorActionResult::Ok);
+            }
+
+            ActorAction::WarpOutEntity(entity_id) => {
+                // Only the collector collects
+                let Some(collector) = &mut self.attributes.collector else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // It has to be here, and can't be alive
+                let Some(target_entity) = ctx
+                    .entities
+                    .in_hex(my_hex)
+                    .find(|e| &e.entity_id == entity_id && !has_markers!(e, Being))
+                else {
+                    return Ok(ActorActionResult::NoEffect);
+                };
+
+                // (the dead are carried away as who they were)
+                collector.collect(entity_id.clone());
+                let body = match &target_entity.attributes.remains {
+                    Some(remains) => GameLogBody::EntityCollectRemains {
+                        name: remains.name.clone(),
+                        summary: remains.summary.clone(),
+                    },
+                    None => GameLogBody::EntityWarpOut,
+                };
+                ctx.send_log(GameLog::entity_pair(self, target_entity, body));
+                GameEventBuilder::new()
+                    .of_kind(GameEventKind::Warp {
+                        entity_id: self.entity_id.clone(),
+                    })
+                    .targets(GameEventTarget::HexSurrounds(my_hex))
+                    .with_sense(
+                        Characteristic::Vision,
+                        ctx.world_state.weather.sight_range(1),
+                    )
+                    .add(ctx);
+
+                return Ok(ActorActionResult::SideEffect(
+                    ActorActionSideEffect::BanishOther(entity_id.clone()),
                 ));
             }
 
             // Moving in a given hex direction
+            ActorAction::Drift(hex_direction) => {
+                let new_hex = my_hex + (*hex_direction).into();
+                if !new_hex.within_bounds(ctx.config.world_radius as isize) {
+                    return Ok(ActorActionResult::NoEffect);
+                }
+                self.attributes.hex = Some(new_hex);
+            }
+
             ActorAction::Move(hex_direction) => {
-                let hex = self
-                    .attributes
-                    .hex
-                    .as_mut()
-                    .expect("Cannot move without hex attribute");
-                let new_hex = *hex + (*hex_direction).into();
+                // Nobody can walk out into open water
+                // (and aboard a raft, it's paddle or nothing, see `rafting`)
+                let new_hex = my_hex + (*hex_direction).into();
+                if ctx.entities.resources(new_hex).open_water
+                    || matches!(self.attributes.focus, Some(ActorFocus::Rafting { .. }))
+                {
+                    return Ok(ActorActionResult::NoEffect);
+                }
                 if new_hex.within_bounds(ctx.config.world_radius as isize) {
+                    // Can we get anywhere in this weather?
+                    match self.check_footing(&ctx.world_state.weather, &mut rng) {
+                        Footing::Sure => {}
+                        Footing::HeldUp => {
+                            ctx.send_log(GameLog::entity(
+                                self,
+                                GameLogBody::EntityHeldUpByWeather {
+                                    weather: ctx.world_state.weather.clone(),
+                                },
+                            ));
+                            self.exert(Exertion::Light, ctx.entities);
+                            return Ok(ActorActionResult::Ok);
+                        }
+                        Footing::Slipped => {
+                            ctx.send_log(GameLog::entity(self, GameLogBody::EntitySlip));
+                            self.attributes
+                                .motivators
+                                .bump_scaled::<motivator::Hurt>(SLIP_HURT);
+                            return Ok(ActorActionResult::Ok);
+                        }
+                    }
+
+                    // Are we leaving behind someone we care about who is in a bad way?
+                    let abandoning_ally = ctx.entities.in_hex(my_hex).any(|e| {
+                        self.relations.bond(&e.entity_id) >= ALLY_BOND
+                            && e.attributes
+                                .motivators
+                                .get_motivation::<motivator::Hurt>()
+                                .is_some_and(|hurt| hurt > BADLY_HURT)
+                    });
+
                     // If succesfull, get thirsty and tired
+                    // (carrying a lot makes moving much more tiring)
                     self.attributes.motivators.bump::<motivator::Thirst>();
-                    self.attributes
-                        .motivators
-                        .bump_scaled::<motivator::Tiredness>(0.3);
+                    self.exert(Exertion::Moderate, ctx.entities);
 
                     // And raise an event
                     GameEventBuilder::new()
                         .of_kind(GameEventKind::LeaveHex {
                             entity_id: self.entity_id.clone(),
                         })
-                        .targets(GameEventTarget::Hex(*hex))
+                        .targets(GameEventTarget::Hex(my_hex))
                         .with_physical_senses(0)
                         .add(ctx);
                     GameEventBuilder::new()
@@ -796,26 +2720,210 @@ impl Entity {
                         .add(ctx);
 
                     // Actually move
-                    *hex = new_hex;
+                    self.attributes.hex = Some(new_hex);
+                    ctx.record_stat(self, PlayerStat::HexTraveled);
+
+                    // We might notice something as we arrive
+                    for prop_entity in self.notice_props(
+                        new_hex,
+                        ctx.entities,
+                        ARRIVAL_NOTICE_SCALE * ctx.world_state.weather.visibility(),
+                        &mut rng,
+                    ) {
+                        ctx.send_log(GameLog::entity_pair(
+                            self,
+                            prop_entity,
+                            GameLogBody::EntitySpotProp,
+                        ));
+                    }
+
+                    // Has someone else claimed this hex?
+                    // if so, we see their markings (and anyone there might not be happy about it)
+                    let claimant = ctx
+                        .entities
+                        .in_hex(new_hex)
+                        .filter_map(|e| e.attributes.shelter.as_ref()?.claimed_by.clone())
+                        .find(|claimant| *claimant != self.entity_id);
+                    if let Some(claimant) = claimant {
+                        self.memes_mut()
+                            .insert(meme::Meme::Claim(claimant.clone(), new_hex));
+                        GameEventBuilder::new()
+                            .of_kind(GameEventKind::Trespass {
+                                entity_id: self.entity_id.clone(),
+                                claimant,
+                            })
+                            .targets(GameEventTarget::Hex(new_hex))
+                            .with_physical_senses(0)
+                            .add(ctx);
+                    }
+
+                    // (recorded after moving, so only the people we left behind hear about it)
+                    if abandoning_ally {
+                        self.record_deed(Deed::AbandonAlly, ctx);
+                    }
 
                     // and a log
                     ctx.send_log(GameLog::entity(
                         self,
                         GameLogBody::EntityMovement { by: *hex_direction },
                     ));
+
+                    // Leaving tracks behind us
+                    if self.leaves_tracks() {
+                        return Ok(ActorActionResult::SideEffect(
+                            ActorActionSideEffect::SpawnOther(Box::new(
+                                self.make_tracks(my_hex, *hex_direction),
+                            )),
+                        ));
+                    }
                 }
             }
 
             // Got a few down here which just proxy elsewhere
             ActorAction::Discussion(discussion_action) => {
-                return self.resolve_discussion_action(discussion_action, ctx)
+                return Ok(self.resolve_discussion_action(discussion_action, ctx))
             }
 
             ActorAction::Presenter(presenter_action) => {
-                return self.resolve_presenter_action(presenter_action, ctx)
+                return Ok(self.resolve_presenter_action(presenter_action, ctx))
             }
         }
 
-        ActorActionResult::Ok
+        Ok(ActorActionResult::Ok)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{
+            generate::PropGenerator, EntityAttributes, EntityCache, EntityContainer,
+            EntityLocation, EntityWaterSource,
+        },
+        hex::AxialHex,
+        location::LocationKind,
+        logs::GameLogBody,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_sleeper_wakes_up() {
+        let builder = TestMatch::builder().seed(1).world_radius(1);
+        let mut player = test_player("Sleepy", AxialHex::ZERO);
+        player.attributes.focus = Some(ActorFocus::Sleeping { remaining_turns: 3 });
+        let player_id = player.entity_id.clone();
+
+        let mut test_match = builder.with_entity(player).build().await;
+        let woke = test_match
+            .tick_until(20, |m| {
+                m.has_log_by(&player_id, |body| {
+                    matches!(body, GameLogBody::EntityStopSleeping)
+                })
+            })
+            .await;
+
+        assert!(woke, "Sleeper never woke up");
+        assert!(test_match.has_log_by(&player_id, |body| {
+            matches!(body, GameLogBody::EntityKeepSleeping)
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_fishing_catches_fresh_fish() {
+        let builder = TestMatch::builder();
+        let lake = Entity {
+            entity_id: Entity::id(),
+            name: "Test lake".into(),
+            attributes: EntityAttributes {
+                hex: Some(AxialHex::ZERO),
+                location: Some(EntityLocation {
+                    location_kind: LocationKind::Lake,
+                }),
+                water_source: Some(EntityWaterSource::quality()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let rod = PropGenerator::FishingRod.generate(&mut crate::rng::rng());
+        let mut player = test_player("Angler", AxialHex::EAST);
+        player.attributes.focus = Some(ActorFocus::Fishing { ticks_invested: 0 });
+        player
+            .relations
+            .inventory_mut()
+            .insert(rod.entity_id.clone());
+        let player_id = player.entity_id.clone();
+
+        let mut test_match = builder
+            .with_entity(lake)
+            .with_entity(rod)
+            .with_entity(player)
+            .build()
+            .await;
+        let caught = test_match
+            .tick_until(30, |m| {
+                m.has_log_by(&player_id, |body| {
+                    matches!(body, GameLogBody::EntityCatchFish)
+                })
+            })
+            .await;
+        assert!(caught, "Never caught anything");
+
+        // Its ours, and it wont last long
+        let player = test_match.entity(&player_id).unwrap();
+        let fish = player
+            .relations
+            .inventory()
+            .filter_map(|id| test_match.entity(id))
+            .find(|e| e.attributes.food.is_some())
+            .expect("Fish wasn't kept");
+        assert!(fish.attributes.food.unwrap().fresh_for.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_owner_withdraws_food_from_stash() {
+        let builder = TestMatch::builder().seed(2);
+        let mut food = PropGenerator::NaturalFood.generate(&mut crate::rng::rng());
+        food.attributes.hex = None;
+        let player = test_player("Hoarder", AxialHex::ZERO);
+        let player_id = player.entity_id.clone();
+        let mut stash = Entity {
+            entity_id: Entity::id(),
+            name: "Stash".into(),
+            attributes: EntityAttributes {
+                hex: Some(AxialHex::ZERO),
+                container: Some(EntityContainer { hidden: true }),
+                cache: Some(EntityCache {
+                    owners: vec![player_id.clone()],
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        stash
+            .relations
+            .inventory_mut()
+            .insert(food.entity_id.clone());
+        let stash_id = stash.entity_id.clone();
+        let food_id = food.entity_id.clone();
+
+        let mut test_match = builder
+            .with_entity(stash)
+            .with_entity(food)
+            .with_entity(player)
+            .build()
+            .await;
+        let withdrew = test_match
+            .tick_until(50, |m| {
+                m.has_log_by(&player_id, |body| {
+                    matches!(body, GameLogBody::EntityWithdrawFromCache)
+                })
+            })
+            .await;
+        assert!(withdrew, "Never took anything out of the stash");
+
+        // Its been moved from the stash to us (if it hasn't been eaten already)
+        let stash = test_match.entity(&stash_id).unwrap();
+        assert!(!stash.relations.inventory().any(|id| *id == food_id));
     }
 }
@@ -1,9 +1,11 @@
 #![allow(clippy::single_match)]
 
-use rand::{rng, Rng};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::rng::rng;
+
 use super::{
     actor_action::ActorAction,
     signal::{Signal, SignalContext, SignalRef},
@@ -11,25 +13,16 @@ use super::{
 use crate::{
     create_markers,
     entity::brain::{
-        discussion::DiscussionAction, focus::ActorFocus, signal::WeightedActorActions,
+        characteristic::Characteristic, discussion::DiscussionAction, focus::ActorFocus,
+        plan::Plan, rafting, signal::WeightedActorActions,
     },
+    has_markers,
     logs::GameLogBody,
+    mtch::features::FeatureFlag,
 };
 
-// Thanks GPT I guess
-macro_rules! seq {
-    ($($x:expr),*; ..$y:expr $(,)? ) => {
-        {
-            let mut v = vec![ $($x),* ];
-            v.extend_from_slice($y);
-            v
-        }
-    };
-
-    ($($x:expr),* $(,)? ) => {
-        vec![ $($x),* ]
-    };
-}
+/// The range new motivators pick their sensitivity from
+pub const SENSITIVITY_RANGE: (f32, f32) = (0.01, 0.1);
 
 /// An attribute which "motivates" behaviour for an entity
 /// primarily represented by a single 0-1 float
@@ -85,9 +78,15 @@ pub trait Motivator {
     const TABLE_KEY: MotivatorKey;
     const INIT: MotivatorInit;
 
+    /// The signal never raises anything at or below this much motivation
+    const ACTIVE_ABOVE: f32;
+
+    /// Whether the signal could raise anything for an entity with some focus
+    fn active_in(focus: &ActorFocus) -> bool;
+
     fn init() -> MotivatorData {
         let mut rng = rng();
-        let sensitivity = rng.random_range(0.01..=0.1);
+        let sensitivity = rng.random_range(SENSITIVITY_RANGE.0..=SENSITIVITY_RANGE.1);
         match Self::INIT {
             MotivatorInit::Zero => MotivatorData {
                 sensitivity,
@@ -157,19 +156,75 @@ impl MotivatorTable {
         }
     }
 
+    /// Move a motivator, specified by key, towards some target by the sensitivity
+    /// (with some scaling factor), without overshooting it
+    pub fn drift_key(&mut self, key: MotivatorKey, target: f32, scale: f32) {
+        if let Some(data) = self.0.get_mut(&key) {
+            let step = data.sensitivity * scale;
+            data.motivation = if data.motivation < target {
+                (data.motivation + step).min(target)
+            } else {
+                (data.motivation - step).max(target)
+            }
+            .clamp(0.0, 1.0);
+        }
+    }
+
+    /// Set a motivator, specified by key, to some motivation outright
+    /// (returns whether the table has that motivator at all)
+    pub fn set_key(&mut self, key: MotivatorKey, motivation: f32) -> bool {
+        let Some(data) = self.0.get_mut(&key) else {
+            return false;
+        };
+        data.motivation = motivation.clamp(0.0, 1.0);
+        true
+    }
+
     /// Decrement a motivator by the specified amount
     pub fn reduce_by<K: Motivator>(&mut self, by: f32) {
         if let Some(data) = self.0.get_mut(&K::TABLE_KEY) {
             data.motivation = (data.motivation - by).clamp(0.0, 1.0);
         }
     }
+
+    /// Every motivator in the table and its current motivation
+    pub fn motivations(&self) -> impl Iterator<Item = (&MotivatorKey, f32)> {
+        self.0.iter().map(|(key, data)| (key, data.motivation))
+    }
+
+    /// How close this entity is to dying of something
+    /// (the highest of the motivators that can kill)
+    pub fn distress(&self) -> f32 {
+        [
+            self.get_motivation::<Hurt>(),
+            self.get_motivation::<Hunger>(),
+            self.get_motivation::<Thirst>(),
+            self.get_motivation::<Cold>(),
+            self.get_motivation::<Sickness>(),
+        ]
+        .into_iter()
+        .flatten()
+        .fold(0.0, f32::max)
+    }
 }
 
 macro_rules! declare_motivators {
-    ({ $($keys:ident : $init: expr),* }) => {
+    ({ $($keys:ident : $init: expr, above $active_above:literal, in $active_in:pat),* }) => {
         /// Declare the possible motivator keys
-        #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+        #[derive(
+            Debug,
+            Clone,
+            Copy,
+            Serialize,
+            Deserialize,
+            PartialEq,
+            Eq,
+            Hash,
+            strum::VariantArray,
+            strum::EnumString,
+        )]
         #[serde(rename_all = "snake_case")]
+        #[strum(serialize_all = "snake_case")]
         #[qubit::ts]
         pub enum MotivatorKey {
             $($keys,)*
@@ -189,6 +244,13 @@ macro_rules! declare_motivators {
             impl Motivator for $keys {
                 const TABLE_KEY: MotivatorKey = MotivatorKey::$keys;
                 const INIT: MotivatorInit = $init;
+                const ACTIVE_ABOVE: f32 = $active_above;
+
+                // (some are active whatever the focus)
+                #[allow(unreachable_patterns)]
+                fn active_in(focus: &ActorFocus) -> bool {
+                    matches!(focus, $active_in)
+                }
             }
         )*
 
@@ -200,11 +262,15 @@ macro_rules! declare_motivators {
                 table
             }
 
-            pub fn as_signals(&self) -> impl Iterator<Item = SignalRef> {
+            /// The signals for each motivator that could raise anything for an entity with some focus
+            /// (the rest are skipped before they're even built)
+            pub fn as_signals(&self, focus: &ActorFocus) -> impl Iterator<Item = SignalRef> {
                 let mut signals: Vec<SignalRef> = Vec::new();
 
                 $({
-                    if let Some(behaviour_data) = self.0.get(&$keys::TABLE_KEY) {
+                    if let Some(behaviour_data) = self.0.get(&$keys::TABLE_KEY).filter(|data| {
+                        data.motivation > $keys::ACTIVE_ABOVE && $keys::active_in(focus)
+                    }) {
                         let signal = $keys(behaviour_data.clone());
                         signals.push(SignalRef::boxed(signal));
                     }
@@ -216,16 +282,18 @@ macro_rules! declare_motivators {
     }
 }
 
+// NOTE: keep the activation for each in step with its signal below,
+//       it has to cover every motivation and focus the signal acts on
 declare_motivators!({
-    Hunger: MotivatorInit::Zero,
-    Thirst: MotivatorInit::Zero,
-    Boredom: MotivatorInit::Zero,
-    Hurt: MotivatorInit::Zero,
-    Sickness: MotivatorInit::Zero,
-    Tiredness: MotivatorInit::Zero,
-    Saturation: MotivatorInit::Zero,
-    Cold: MotivatorInit::Zero,
-    Sadness: MotivatorInit::Zero
+    Hunger: MotivatorInit::Zero, above 0.3, in ActorFocus::Unfocused | ActorFocus::Discussion { .. },
+    Thirst: MotivatorInit::Zero, above 0.4, in ActorFocus::Unfocused | ActorFocus::Discussion { .. },
+    Boredom: MotivatorInit::Zero, above 0.7, in ActorFocus::Unfocused,
+    Hurt: MotivatorInit::Zero, above 0.3, in _,
+    Sickness: MotivatorInit::Zero, above 0.0, in ActorFocus::Unfocused,
+    Tiredness: MotivatorInit::Zero, above 0.6, in ActorFocus::Unfocused,
+    Saturation: MotivatorInit::Zero, above 0.0, in ActorFocus::Unfocused,
+    Cold: MotivatorInit::Zero, above 0.3, in ActorFocus::Unfocused | ActorFocus::Sleeping { .. },
+    Sadness: MotivatorInit::Zero, above 0.0, in ActorFocus::Unfocused
 });
 
 impl Signal for Hunger {
@@ -233,37 +301,99 @@ impl Signal for Hunger {
         match ctx.focus {
             ActorFocus::Unfocused => {
                 // The generic plan for finding food
-                let seek_food_plan: &[ActorAction] = &[
-                    ActorAction::GoToAdjacent(
+                // (have a proper look here first, then try somewhere else)
+                let seek_food_plan = Plan::new("seek food")
+                    .then(ActorAction::LookAround)
+                    .then(ActorAction::GoToAdjacent(
                         GameLogBody::EntityGoToAdjacentLush,
                         create_markers!(LushLocation),
-                    ),
-                    ActorAction::Bark(self.motivation(), MotivatorKey::Hunger),
-                ];
+                    ))
+                    .then(ActorAction::Bark(self.motivation(), MotivatorKey::Hunger));
 
                 // Eat food if we have it, maybe try finding some
+                // (bit more desperate, eat bad food if thats all there is)
                 if self.motivation() > 0.3 {
-                    actions.add(
-                        if self.motivation() > 0.7 { 30 } else { 10 },
-                        ActorAction::Sequential(seq![
-                            ActorAction::ConsumeNearbyFood { try_dubious: false, try_morally_wrong: false },
-                            ActorAction::RetrieveInventoryFood;
-                            ..seek_food_plan,
-                        ]),
-                    );
+                    let eat_plan = |try_dubious: bool| {
+                        Plan::new(if try_dubious { "eat anything" } else { "eat" })
+                            .then(ActorAction::ConsumeNearbyFood {
+                                try_dubious: false,
+                                try_morally_wrong: false,
+                            })
+                            .then(ActorAction::RetrieveInventoryFood)
+                            .then_if(
+                                try_dubious,
+                                ActorAction::ConsumeNearbyFood {
+                                    try_dubious: true,
+                                    try_morally_wrong: false,
+                                },
+                            )
+                            .falling_back_on(&seek_food_plan)
+                    };
+                    let weight = if self.motivation() > 0.7 { 30 } else { 10 };
+                    actions.add(weight, eat_plan(false).into());
+                    if self.motivation() > 0.6 {
+                        actions.add(weight, eat_plan(true).into());
+                    }
                 }
 
-                // Bit more desperate, eat bad food if thats all there is
-                if self.motivation() > 0.6 {
-                    actions.add(
-                                if self.motivation() > 0.7 { 30 } else { 10 },
-                                ActorAction::Sequential(seq![
-                                    ActorAction::ConsumeNearbyFood { try_dubious: false, try_morally_wrong: false },
-                                    ActorAction::RetrieveInventoryFood,
-                                    ActorAction::ConsumeNearbyFood { try_dubious: true, try_morally_wrong: false };
-                                    ..seek_food_plan,
-                                ]),
-                            );
+                // If we dont care much for others, we might just take food from someone nearby
+                if self.motivation() > 0.7
+                    && ctx.entity.characteristic(Characteristic::Empathy).is_low()
+                {
+                    for other_entity in ctx
+                        .entity
+                        .attributes
+                        .hex
+                        .into_iter()
+                        .flat_map(|hex| ctx.entities.in_hex(hex))
+                        .filter(|e| e.entity_id != ctx.entity.entity_id)
+                        // only the living (robbing the dead is looting, see below)
+                        .filter(|e| has_markers!(e, Player))
+                        .filter(|e| {
+                            e.resolve_inventory(ctx.entities)
+                                .any(|item| item.attributes.food.is_some())
+                        })
+                    {
+                        actions.add(
+                            10,
+                            ActorAction::StealFood {
+                                entity_id: other_entity.entity_id.clone(),
+                            },
+                        );
+                    }
+                }
+
+                // The dead won't be needing their food
+                if self.motivation() > 0.6 && ctx.has_feature(FeatureFlag::Looting) {
+                    if let Some((corpse, item)) = ctx
+                        .entity
+                        .lootable_corpse_nearby(ctx.entities, |e| e.attributes.food.is_some())
+                    {
+                        actions.add(
+                            8,
+                            ActorAction::LootCorpse {
+                                corpse: corpse.entity_id.clone(),
+                                item: item.entity_id.clone(),
+                            },
+                        );
+                    }
+                }
+
+                // Beside a lake we could try catching something
+                // (more worth the wait if we've got something to fish with)
+                let at_lake = ctx
+                    .entity
+                    .attributes
+                    .hex
+                    .is_some_and(|hex| rafting::lake_within_reach(hex, ctx.entities).is_some());
+                if at_lake && self.motivation() > 0.3 {
+                    let has_fishing_tool = ctx.entity.resolve_inventory(ctx.entities).any(|e| {
+                        e.attributes
+                            .tool
+                            .as_ref()
+                            .is_some_and(|t| t.kind.fishing_bonus() > 0.0)
+                    });
+                    actions.add(if has_fishing_tool { 20 } else { 5 }, ActorAction::Fish);
                 }
 
                 // if extremely hungry, we'll try absolutely desperate things
@@ -287,10 +417,10 @@ impl Signal for Hunger {
                 if self.motivation() > 0.6 {
                     actions.add(
                         if self.motivation() > 0.7 { 30 } else { 10 },
-                        ActorAction::Sequential(seq![
-                            ActorAction::Bark(self.motivation(), MotivatorKey::Hunger),
-                            ActorAction::Discussion(DiscussionAction::LoseInterest),
-                        ]),
+                        Plan::new("stop talking")
+                            .then(ActorAction::Bark(self.motivation(), MotivatorKey::Hunger))
+                            .then(ActorAction::Discussion(DiscussionAction::LoseInterest))
+                            .into(),
                     );
                 }
             }
@@ -304,27 +434,27 @@ impl Signal for Thirst {
         match ctx.focus {
             ActorFocus::Unfocused => {
                 // The generic plan for finding water
-                let seek_water_plan: &[ActorAction] = &[
-                    ActorAction::SeekKnownWaterSource,
-                    ActorAction::GoToAdjacent(
+                let seek_water_plan = Plan::new("seek water")
+                    .then(ActorAction::SeekKnownWaterSource)
+                    .then(ActorAction::GoToAdjacent(
                         GameLogBody::EntityGoToAdjacentLush,
                         create_markers!(LushLocation),
-                    ),
-                    ActorAction::GoTowards(
+                    ))
+                    .then(ActorAction::GoTowards(
                         GameLogBody::EntityGoDownhill,
                         create_markers!(LowLyingLocation),
-                    ),
-                    ActorAction::Bark(self.motivation(), MotivatorKey::Thirst),
-                ];
+                    ))
+                    .then(ActorAction::Bark(self.motivation(), MotivatorKey::Thirst));
 
                 // Little bit thirsty, start trying to get water
+                // (only go in for safe water)
                 if self.motivation() > 0.4 {
                     actions.add(
                         20,
-                        ActorAction::Sequential(seq![
-                            ActorAction::DrinkFromWaterSource { try_dubious: false }; // Only go in for safe water
-                            ..seek_water_plan,
-                        ]),
+                        Plan::new("drink")
+                            .then(ActorAction::DrinkFromWaterSource { try_dubious: false })
+                            .falling_back_on(&seek_water_plan)
+                            .into(),
                     );
                 }
 
@@ -332,11 +462,11 @@ impl Signal for Thirst {
                 if self.motivation() > 0.7 {
                     actions.add(
                         30,
-                        ActorAction::Sequential(seq![
-                            ActorAction::DrinkFromWaterSource { try_dubious: false },
-                            ActorAction::DrinkFromWaterSource { try_dubious: true };
-                            ..seek_water_plan,
-                        ]),
+                        Plan::new("drink anything")
+                            .then(ActorAction::DrinkFromWaterSource { try_dubious: false })
+                            .then(ActorAction::DrinkFromWaterSource { try_dubious: true })
+                            .falling_back_on(&seek_water_plan)
+                            .into(),
                     );
                 }
 
@@ -349,10 +479,10 @@ impl Signal for Thirst {
                 if self.motivation() > 0.6 {
                     actions.add(
                         if self.motivation() > 0.7 { 30 } else { 10 },
-                        ActorAction::Sequential(seq![
-                            ActorAction::Bark(self.motivation(), MotivatorKey::Hunger),
-                            ActorAction::Discussion(DiscussionAction::LoseInterest),
-                        ]),
+                        Plan::new("stop talking")
+                            .then(ActorAction::Bark(self.motivation(), MotivatorKey::Thirst))
+                            .then(ActorAction::Discussion(DiscussionAction::LoseInterest))
+                            .into(),
                     );
                 }
             }
@@ -392,6 +522,47 @@ impl Signal for Hurt {
     fn act_on(&self, ctx: &SignalContext, actions: &mut WeightedActorActions) {
         match ctx.focus {
             ActorFocus::Unfocused => {
+                // The dead won't be needing their medicine
+                if self.motivation() > 0.5 && ctx.has_feature(FeatureFlag::Looting) {
+                    if let Some((corpse, item)) = ctx
+                        .entity
+                        .lootable_corpse_nearby(ctx.entities, |e| e.attributes.healing.is_some())
+                    {
+                        actions.add(
+                            8,
+                            ActorAction::LootCorpse {
+                                corpse: corpse.entity_id.clone(),
+                                item: item.entity_id.clone(),
+                            },
+                        );
+                    }
+                }
+
+                // Treat our wounds if we have something to do it with
+                if self.motivation() > 0.3 {
+                    let nearby = ctx
+                        .entity
+                        .attributes
+                        .hex
+                        .into_iter()
+                        .flat_map(|hex| ctx.entities.in_hex(hex))
+                        .filter(|e| ctx.entity.can_see(e));
+                    if let Some(healing_entity) = ctx
+                        .entity
+                        .resolve_inventory(ctx.entities)
+                        .chain(nearby)
+                        .find(|e| e.attributes.healing.is_some())
+                    {
+                        actions.add(
+                            if self.motivation() > 0.7 { 30 } else { 10 },
+                            ActorAction::UseItemOn {
+                                item: healing_entity.entity_id.clone(),
+                                target: ctx.entity.entity_id.clone(),
+                            },
+                        );
+                    }
+                }
+
                 if self.motivation() > 0.5 {
                     actions.add(5, ActorAction::Bark(self.motivation(), MotivatorKey::Hurt));
                     actions.add(2, ActorAction::BumpMotivator(MotivatorKey::Sadness));
@@ -450,6 +621,19 @@ impl Signal for Tiredness {
                     );
                 }
 
+                // If we're worn out and weighed down, start ditching stuff
+                // (the heaviest thing we dont need to survive first)
+                if self.motivation() > 0.6 && ctx.entity.encumbrance(ctx.entities) > 0.5 {
+                    let heaviest = ctx
+                        .entity
+                        .resolve_inventory(ctx.entities)
+                        .filter(|e| e.attributes.food.is_none() && e.attributes.healing.is_none())
+                        .max_by_key(|e| e.attributes.item.as_ref().map(|i| i.heft));
+                    if let Some(item_entity) = heaviest {
+                        actions.add(15, ActorAction::DropEntity(item_entity.entity_id.clone()));
+                    }
+                }
+
                 if self.motivation() > 0.8 {
                     actions.add(
                         20,
@@ -497,11 +681,14 @@ impl Signal for Saturation {
                 if self.motivation() > 0.1 && ctx.world_state.weather.is_raining() {
                     actions.add(
                         10,
-                        ActorAction::Sequential(vec![
-                            ActorAction::TakeShelter,
-                            ActorAction::SeekKnownShelter,
-                            ActorAction::Bark(self.motivation(), MotivatorKey::Saturation),
-                        ]),
+                        Plan::new("get out of the rain")
+                            .then(ActorAction::TakeShelter)
+                            .then(ActorAction::SeekKnownShelter)
+                            .then(ActorAction::Bark(
+                                self.motivation(),
+                                MotivatorKey::Saturation,
+                            ))
+                            .into(),
                     );
                 }
             }
@@ -518,14 +705,31 @@ impl Signal for Cold {
                 if self.motivation() > 0.4 {
                     actions.add(
                         10,
-                        ActorAction::Sequential(vec![
-                            ActorAction::TakeShelter,
-                            ActorAction::SeekKnownShelter,
-                            ActorAction::Bark(self.motivation(), MotivatorKey::Cold),
-                        ]),
+                        Plan::new("warm up")
+                            .then(ActorAction::TakeShelter)
+                            .then(ActorAction::SeekKnownShelter)
+                            .then(ActorAction::Bark(self.motivation(), MotivatorKey::Cold))
+                            .into(),
                     );
                 }
 
+                // Put on something warmer if there's any lying around
+                if self.motivation() > 0.3 {
+                    if let Some(clothing_entity) = ctx.entity.warmer_clothing_nearby(ctx.entities) {
+                        actions.add(
+                            8,
+                            ActorAction::PickUpEntity(clothing_entity.entity_id.clone()),
+                        );
+                    }
+                }
+
+                // or take it off the dead if we're desperate
+                if self.motivation() > 0.7 {
+                    if let Some(corpse_entity) = ctx.entity.warmer_corpse_nearby(ctx.entities) {
+                        actions.add(5, ActorAction::StripCorpse(corpse_entity.entity_id.clone()));
+                    }
+                }
+
                 // The cold just makes you tired for now
                 // and maybe sick?
                 if self.motivation() > 0.6 {
@@ -545,10 +749,10 @@ impl Signal for Cold {
                 if self.motivation() > 0.7 {
                     actions.add(
                         5,
-                        ActorAction::Sequential(seq![
-                            ActorAction::Bark(self.motivation(), MotivatorKey::Cold),
-                            ActorAction::WakeUp,
-                        ]),
+                        Plan::new("wake up cold")
+                            .then(ActorAction::Bark(self.motivation(), MotivatorKey::Cold))
+                            .then(ActorAction::WakeUp)
+                            .into(),
                     );
                 }
             }
@@ -639,3 +843,227 @@ impl Signal for Sadness {
 //         actions
 //     }
 // }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{
+            brain::{
+                characteristic::CharacteristicStrength,
+                test_signal::{is_plan, EntityBuilder, SignalContextBuilder},
+            },
+            Entity, EntityClothing, EntityItem, EntityMarker,
+        },
+        hex::AxialHex,
+    };
+
+    fn motivated(key: MotivatorKey, motivation: f32) -> EntityBuilder {
+        EntityBuilder::player("Motivated").motivation(key, motivation)
+    }
+
+    fn talking() -> ActorFocus {
+        ActorFocus::Discussion {
+            with: "other".into(),
+            interest: 3,
+            is_lead: false,
+        }
+    }
+
+    /// Matches a plan with a step barking about some motivator
+    fn barks_about(key: MotivatorKey) -> impl Fn(&ActorAction) -> bool {
+        move |action| match action {
+            ActorAction::Plan(plan) => plan
+                .steps()
+                .iter()
+                .any(|step| matches!(step, ActorAction::Bark(_, k) if *k == key)),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_calm_motivators_are_skipped() {
+        let mut motivators = MotivatorTable::initialise();
+        assert_eq!(motivators.as_signals(&ActorFocus::Unfocused).count(), 0);
+
+        // Cold matters whether awake or asleep
+        motivators.set_key(MotivatorKey::Cold, 0.8);
+        assert_eq!(motivators.as_signals(&ActorFocus::Unfocused).count(), 1);
+        assert_eq!(
+            motivators
+                .as_signals(&ActorFocus::Sleeping { remaining_turns: 3 })
+                .count(),
+            1
+        );
+
+        // but hurt is always worth hearing about
+        motivators.set_key(MotivatorKey::Hurt, 0.99);
+        assert_eq!(motivators.as_signals(&ActorFocus::Unfocused).count(), 2);
+    }
+
+    #[test]
+    fn test_hunger_signal_edge_cases() {
+        let hungry = |motivation| {
+            SignalContextBuilder::new(motivated(MotivatorKey::Hunger, motivation).build())
+        };
+
+        // Right on the threshold there's nothing to do yet
+        hungry(0.3).act_on_motivators().assert_no_action(|_| true);
+
+        // Peckish players only eat what's safe
+        let actions = hungry(0.35).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(10, is_plan("eat"));
+        actions.assert_no_action(is_plan("eat anything"));
+
+        // but starving players will eat anything, and sooner
+        let actions = hungry(0.75).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(30, is_plan("eat"));
+        actions.assert_contains_action_weight_at_least(30, is_plan("eat anything"));
+
+        // Only bad enough hunger ends a conversation
+        hungry(0.55)
+            .focus(talking())
+            .act_on_motivators()
+            .assert_no_action(is_plan("stop talking"));
+        hungry(0.65)
+            .focus(talking())
+            .act_on_motivators()
+            .assert_contains_action_weight_at_least(10, barks_about(MotivatorKey::Hunger));
+
+        // and asleep we don't notice at all
+        hungry(0.95)
+            .focus(ActorFocus::Sleeping { remaining_turns: 3 })
+            .act_on_motivators()
+            .assert_no_action(|_| true);
+    }
+
+    #[test]
+    fn test_hungry_players_take_food_from_others() {
+        let food = EntityBuilder::thing("Bread")
+            .attributes(|attributes| {
+                attributes.item = Some(EntityItem::default());
+                attributes.food = Some(Default::default());
+            })
+            .build();
+        let holder = EntityBuilder::player("Holder").holding(&food).build();
+        let far_holder = EntityBuilder::player("Far Away")
+            .at(AxialHex::from((2, 0)))
+            .holding(&food)
+            .build();
+        let steals_from = |entity: &Entity| {
+            let entity_id = entity.entity_id.clone();
+            move |action: &ActorAction| matches!(action, ActorAction::StealFood { entity_id: id } if *id == entity_id)
+        };
+
+        // Only the uncaring steal, and only from those nearby
+        let callous = motivated(MotivatorKey::Hunger, 0.75)
+            .characteristic(Characteristic::Empathy, CharacteristicStrength::Low)
+            .build();
+        let actions = SignalContextBuilder::new(callous)
+            .with_entity(food.clone())
+            .with_entity(holder.clone())
+            .with_entity(far_holder.clone())
+            .act_on_motivators();
+        actions.assert_contains_action_weight_at_least(10, steals_from(&holder));
+        actions.assert_no_action(steals_from(&far_holder));
+
+        let caring = motivated(MotivatorKey::Hunger, 0.75).build();
+        SignalContextBuilder::new(caring)
+            .with_entity(food.clone())
+            .with_entity(holder.clone())
+            .act_on_motivators()
+            .assert_no_action(steals_from(&holder));
+
+        // The dead are fair game, if looting is part of the match
+        let corpse = EntityBuilder::thing("Corpse")
+            .at(AxialHex::ZERO)
+            .holding(&food)
+            .attributes(|attributes| attributes.corpse = Some(holder.entity_id.clone()))
+            .build();
+        let looting = |ctx: SignalContextBuilder| {
+            ctx.with_entity(food.clone())
+                .with_entity(corpse.clone())
+                .act_on_motivators()
+                .max_weight_of(|action| matches!(action, ActorAction::LootCorpse { .. }))
+        };
+        let hungry = || SignalContextBuilder::new(motivated(MotivatorKey::Hunger, 0.65).build());
+        assert_eq!(looting(hungry()), None);
+        assert_eq!(looting(hungry().feature(FeatureFlag::Looting)), Some(8));
+    }
+
+    #[test]
+    fn test_thirst_signal_edge_cases() {
+        let thirsty = |motivation| {
+            SignalContextBuilder::new(motivated(MotivatorKey::Thirst, motivation).build())
+        };
+
+        thirsty(0.4).act_on_motivators().assert_no_action(|_| true);
+
+        let actions = thirsty(0.5).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(20, is_plan("drink"));
+        actions.assert_no_action(is_plan("drink anything"));
+
+        let actions = thirsty(0.8).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(30, is_plan("drink anything"));
+        actions.assert_no_action(|action| matches!(action, ActorAction::BumpMotivator(_)));
+
+        // At the very end it starts to hurt
+        thirsty(0.95)
+            .act_on_motivators()
+            .assert_contains_action_weight_at_least(20, |action| {
+                matches!(action, ActorAction::BumpMotivator(MotivatorKey::Hurt))
+            });
+
+        // Leaving a conversation complains about the right thing
+        let actions = thirsty(0.65).focus(talking()).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(10, barks_about(MotivatorKey::Thirst));
+        actions.assert_no_action(barks_about(MotivatorKey::Hunger));
+    }
+
+    #[test]
+    fn test_cold_signal_edge_cases() {
+        let jumper = EntityBuilder::thing("Jumper")
+            .at(AxialHex::ZERO)
+            .marker(EntityMarker::Inspectable)
+            .attributes(|attributes| {
+                attributes.item = Some(EntityItem::default());
+                attributes.clothing = Some(EntityClothing {
+                    warmth: 0.5,
+                    waterproofing: 0.0,
+                });
+            })
+            .build();
+        let cold = |motivation| {
+            SignalContextBuilder::new(motivated(MotivatorKey::Cold, motivation).build())
+                .with_entity(jumper.clone())
+        };
+        let puts_on_jumper = |action: &ActorAction| matches!(action, ActorAction::PickUpEntity(id) if *id == jumper.entity_id);
+
+        // A little chilly -> put something on, but no need to find shelter yet
+        let actions = cold(0.35).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(8, puts_on_jumper);
+        actions.assert_no_action(is_plan("warm up"));
+
+        let actions = cold(0.45).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(10, is_plan("warm up"));
+        actions.assert_no_action(|action| matches!(action, ActorAction::BumpMotivator(_)));
+
+        // (and it only hurts when it's freezing)
+        let hurts =
+            |action: &ActorAction| matches!(action, ActorAction::BumpMotivator(MotivatorKey::Hurt));
+        cold(0.9).act_on_motivators().assert_no_action(hurts);
+        cold(0.96)
+            .act_on_motivators()
+            .assert_contains_action_weight_at_least(5, hurts);
+
+        // It only wakes you up if it's really cold
+        let asleep = ActorFocus::Sleeping { remaining_turns: 3 };
+        cold(0.65)
+            .focus(asleep.clone())
+            .act_on_motivators()
+            .assert_no_action(|_| true);
+        let actions = cold(0.75).focus(asleep).act_on_motivators();
+        actions.assert_contains_action_weight_at_least(5, is_plan("wake up cold"));
+        actions.assert_no_action(puts_on_jumper);
+    }
+}
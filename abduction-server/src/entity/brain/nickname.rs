@@ -0,0 +1,195 @@
+//! Nicknames that catch on after someone does something memorable
+//!
+//! Anyone who sees someone do something notable (get hit by lightning and live, eat a corpse) might
+//! coin a nickname for them, which then spreads through gossip like any other meme. Once most of the
+//! people who know someone call them the same thing, it sticks (e.g "Sparky Smith").
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{
+    brain::{actor_action::ActorAction, meme::Meme, reputation::Deed},
+    Entity, EntityId,
+};
+
+/// Chance of coming up with a nickname for someone when we see them do something notable
+const COIN_NICKNAME_CHANCE: f64 = 0.3;
+
+/// How many people have to know someone before a nickname can stick
+const MIN_NICKNAME_ACQUAINTANCES: usize = 2;
+
+/// What someone might come to be called, after something they did
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[qubit::ts]
+pub enum Epithet {
+    /// Got hit by lightning (and lived)
+    Sparky,
+
+    /// Ate a corpse
+    Ghoul,
+
+    /// Took something that wasn't theirs
+    Sticky,
+
+    /// Stripped (or looted) a corpse
+    Vulture,
+
+    /// Threw something at someone
+    Brick,
+
+    /// Patched someone up
+    Doc,
+
+    /// Left an ally behind
+    Turncoat,
+
+    /// Got someone out of trouble
+    Angel,
+}
+
+impl Epithet {
+    /// What someone might be called for doing some deed
+    pub fn for_deed(deed: Deed) -> Option<Self> {
+        match deed {
+            Deed::Theft => Some(Epithet::Sticky),
+            Deed::Cannibalism => Some(Epithet::Ghoul),
+            Deed::StripCorpse | Deed::LootCorpse => Some(Epithet::Vulture),
+            Deed::AbandonAlly => Some(Epithet::Turncoat),
+            Deed::TendWounds => Some(Epithet::Doc),
+            Deed::Assault => Some(Epithet::Brick),
+            Deed::Rescue => Some(Epithet::Angel),
+        }
+    }
+
+    /// How it reads in front of a name
+    pub fn title(&self) -> &'static str {
+        match self {
+            Epithet::Sparky => "Sparky",
+            Epithet::Ghoul => "Ghoul",
+            Epithet::Sticky => "Sticky",
+            Epithet::Vulture => "Vulture",
+            Epithet::Brick => "Brick",
+            Epithet::Doc => "Doc",
+            Epithet::Turncoat => "Turncoat",
+            Epithet::Angel => "Angel",
+        }
+    }
+}
+
+impl Entity {
+    /// Maybe come up with a nickname for someone we just saw do something
+    /// (unless we already call them something)
+    pub fn maybe_coin_nickname(
+        &self,
+        entity_id: &EntityId,
+        epithet: Epithet,
+    ) -> Option<ActorAction> {
+        let already_named = self
+            .attributes
+            .memes
+            .as_ref()
+            .is_some_and(|memes| memes.nicknames_for(entity_id).next().is_some());
+        if already_named || !crate::rng::rng().random_bool(COIN_NICKNAME_CHANCE) {
+            return None;
+        }
+        Some(ActorAction::StoreMeme(Meme::Nickname(
+            entity_id.clone(),
+            epithet,
+        )))
+    }
+
+    /// The nickname most of the people who know us call us by, if there is one
+    /// (only counts those that know us, i.e have some bond with us)
+    pub fn prevailing_epithet<'a>(
+        &self,
+        entities: impl Iterator<Item = &'a Entity>,
+    ) -> Option<Epithet> {
+        let mut acquaintances = 0;
+        let mut counts: Vec<(Epithet, usize)> = Vec::new();
+        for entity in entities.filter(|e| knows(e, &self.entity_id)) {
+            acquaintances += 1;
+            let Some(memes) = &entity.attributes.memes else {
+                continue;
+            };
+            for epithet in memes.nicknames_for(&self.entity_id) {
+                match counts.iter_mut().find(|(e, _)| *e == epithet) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((epithet, 1)),
+                }
+            }
+        }
+
+        if acquaintances < MIN_NICKNAME_ACQUAINTANCES {
+            return None;
+        }
+        counts
+            .into_iter()
+            .find(|(_, count)| *count * 2 > acquaintances)
+            .map(|(epithet, _)| epithet)
+    }
+
+    /// Our name with some epithet in front of it
+    /// (e.g "Sparky Smith", or "Sparky John" if we have no family name)
+    pub fn nicknamed(&self, epithet: Epithet) -> String {
+        let name = self.attributes.family_name.as_deref().unwrap_or(&self.name);
+        format!("{} {name}", epithet.title())
+    }
+}
+
+fn knows(entity: &Entity, entity_id: &EntityId) -> bool {
+    entity.entity_id != *entity_id && entity.relations.associates().any(|(id, _)| id == entity_id)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::brain::meme::MemeTable;
+
+    fn acquaintance(of: &Entity, nickname: Option<Epithet>) -> Entity {
+        let mut entity = Entity {
+            entity_id: Entity::id(),
+            ..Default::default()
+        };
+        entity.relations.increase_associate_bond(&of.entity_id);
+        let mut memes = MemeTable::default();
+        if let Some(epithet) = nickname {
+            memes.insert(Meme::Nickname(of.entity_id.clone(), epithet));
+        }
+        entity.attributes.memes = Some(memes);
+        entity
+    }
+
+    #[test]
+    fn test_nickname_needs_majority() {
+        let mut smith = Entity {
+            entity_id: Entity::id(),
+            name: "John Smith".into(),
+            ..Default::default()
+        };
+        smith.attributes.family_name = Some("Smith".into());
+
+        let mut others = vec![
+            acquaintance(&smith, Some(Epithet::Sparky)),
+            acquaintance(&smith, None),
+        ];
+        assert_eq!(smith.prevailing_epithet(others.iter()), None);
+
+        others.push(acquaintance(&smith, Some(Epithet::Sparky)));
+        let epithet = smith.prevailing_epithet(others.iter());
+        assert_eq!(epithet, Some(Epithet::Sparky));
+        assert_eq!(smith.nicknamed(epithet.unwrap()), "Sparky Smith");
+    }
+}
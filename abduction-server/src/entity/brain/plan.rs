@@ -0,0 +1,154 @@
+//! Plans: a few actions to try in order until one works
+//!
+//! Signals often want "do this, or if that doesn't work this, or failing all that at least moan
+//! about it". A `Plan` spells that out a step at a time (some steps only if some condition holds),
+//! and can fall back on the steps of some shared plan (e.g the usual way of looking for food). It's
+//! raised as an `ActorAction::Plan`, which tries each step until one does something, and prints
+//! as its name and steps so it's easy to see which step of what someone got up to.
+
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::entity::brain::actor_action::ActorAction;
+
+/// Some steps to try in order, until one of them works
+#[derive(Debug, Clone)]
+pub struct Plan {
+    /// What the plan is for (e.g "find water")
+    name: &'static str,
+
+    /// What to try, in order
+    steps: Vec<ActorAction>,
+}
+
+impl Plan {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Try this next
+    pub fn then(mut self, action: ActorAction) -> Self {
+        self.steps.push(action);
+        self
+    }
+
+    /// Try this next, but only if some condition holds
+    pub fn then_if(self, condition: bool, action: ActorAction) -> Self {
+        if condition {
+            self.then(action)
+        } else {
+            self
+        }
+    }
+
+    /// Try this next, if there is anything to try
+    pub fn then_maybe(self, action: Option<ActorAction>) -> Self {
+        match action {
+            Some(action) => self.then(action),
+            None => self,
+        }
+    }
+
+    /// Do this next, carrying on with the plan whether it works or not
+    /// (e.g saying something before doing something)
+    pub fn also(self, action: ActorAction) -> Self {
+        self.then(ActorAction::ignore(action))
+    }
+
+    /// If nothing so far works, try the steps of some other plan
+    pub fn falling_back_on(mut self, fallback: &Plan) -> Self {
+        self.steps.extend_from_slice(&fallback.steps);
+        self
+    }
+
+    pub fn steps(&self) -> &[ActorAction] {
+        &self.steps
+    }
+
+    /// Is there nothing to try?
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The plan as followed up to some step (for debugging)
+    pub fn at_step(&self, step: usize) -> PlanProgress<'_> {
+        PlanProgress { plan: self, step }
+    }
+}
+
+impl From<Plan> for ActorAction {
+    fn from(plan: Plan) -> Self {
+        ActorAction::Plan(plan)
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({})",
+            self.name,
+            self.steps.iter().map(step_name).join(" -> ")
+        )
+    }
+}
+
+/// How far through some plan an entity got
+pub struct PlanProgress<'a> {
+    plan: &'a Plan,
+    step: usize,
+}
+
+impl fmt::Display for PlanProgress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}, step {} of {} ({})",
+            self.plan.name,
+            self.step + 1,
+            self.plan.steps.len(),
+            self.plan.steps.get(self.step).map_or("?", step_name)
+        )
+    }
+}
+
+/// What a step is called in a printed plan
+fn step_name(action: &ActorAction) -> &'static str {
+    match action {
+        ActorAction::IgnoreResult(action) => step_name(action),
+        action => action.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::brain::motivator::MotivatorKey;
+
+    #[test]
+    fn test_plan_steps_in_order() {
+        let complain = Plan::new("complain").then(ActorAction::Bark(0.5, MotivatorKey::Thirst));
+        let plan = Plan::new("find water")
+            .then(ActorAction::DrinkFromWaterSource { try_dubious: false })
+            .then_if(
+                false,
+                ActorAction::DrinkFromWaterSource { try_dubious: true },
+            )
+            .then(ActorAction::SeekKnownWaterSource)
+            .falling_back_on(&complain);
+
+        assert_eq!(plan.steps().len(), 3);
+        assert_eq!(
+            plan.to_string(),
+            "find water (DrinkFromWaterSource -> SeekKnownWaterSource -> Bark)"
+        );
+        assert_eq!(
+            plan.at_step(1).to_string(),
+            "find water, step 2 of 3 (SeekKnownWaterSource)"
+        );
+    }
+}
@@ -3,17 +3,40 @@ use itertools::Itertools;
 use crate::entity::{
     brain::{
         actor_action::ActorAction,
-        characteristic::Characteristic,
+        characteristic::{Characteristic, CharacteristicStrength},
+        meme::Meme,
+        motivator,
+        plan::Plan,
+        rafting::{self, RAFT_MATERIALS},
         signal::{Signal, SignalContext, SignalRef, WeightedActorActions},
     },
-    Entity,
+    Entity, EntityId,
 };
 
 /// Some future need that can be planned for
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum PlanningSignal {
     /// Do we have access to food in inventory?
     FoodAccess,
+
+    /// Is there something around worth scavenging through?
+    Scavenge,
+
+    /// Do we have something to treat wounds with?
+    FirstAidAccess,
+
+    /// Do we have something to patch up a shelter (or build a raft) with?
+    MaterialAccess,
+
+    /// Should we be keeping things in a stash?
+    Stash,
+
+    /// Is there someone nearby whose wounds we could treat?
+    /// (holds the healing item we'd use)
+    TendToOthers(EntityId),
+
+    /// Is there a broken escape pod we could get away in?
+    Escape,
     // Do we have access to water in inventory?
     // (NOT REALLY A THING YET)
     // WaterAccess,
@@ -33,18 +56,261 @@ impl Signal for PlanningSignal {
         };
 
         match self {
+            PlanningSignal::Scavenge => {
+                // Curious people are more likely to rummage around
+                let weight = match ctx.entity.characteristic(Characteristic::Curiosity) {
+                    CharacteristicStrength::Low => 1,
+                    CharacteristicStrength::Average => 2,
+                    CharacteristicStrength::High => 5,
+                };
+
+                // Have a look around for anything we've missed
+                actions.add(weight, ActorAction::LookAround);
+
+                // Search any containers here that we can see
+                // (we dont know if they have anything, but they might, unless we've already emptied them)
+                // and raid the stashes of others, if we're sharp eyed enough to find anything
+                let sharp_eyed = !ctx.entity.characteristic(Characteristic::Vision).is_low();
+                let searched = |e: &Entity| {
+                    ctx.entity
+                        .attributes
+                        .memes
+                        .as_ref()
+                        .is_some_and(|memes| memes.has_searched(&e.entity_id))
+                };
+                for container_entity in ctx
+                    .entities
+                    .in_hex(hex)
+                    .filter(|e| e.attributes.container.is_some() && ctx.entity.can_see(e))
+                    .filter(|e| !searched(e))
+                    .filter(|e| match &e.attributes.cache {
+                        Some(cache) => sharp_eyed && !cache.is_owner(&ctx.entity.entity_id),
+                        None => true,
+                    })
+                {
+                    actions.add(
+                        weight,
+                        ActorAction::SearchContainer(container_entity.entity_id.clone()),
+                    );
+                }
+            }
+
+            PlanningSignal::FirstAidAccess => {
+                // Pick up anything that could help with wounds later
+                if let Some(healing_entity) = ctx.entities.in_hex(hex).find(|e| {
+                    e.attributes.healing.is_some()
+                        && e.attributes.item.is_some()
+                        && ctx.entity.can_see(e)
+                }) {
+                    actions.add(
+                        2,
+                        ActorAction::PickUpEntity(healing_entity.entity_id.clone()),
+                    );
+                }
+            }
+
+            PlanningSignal::MaterialAccess => {
+                // Pick up something we could fix a shelter (or build a raft) with
+                if let Some(material_entity) = ctx.entities.in_hex(hex).find(|e| {
+                    e.attributes.material.is_some()
+                        && e.attributes.item.is_some()
+                        && ctx.entity.can_see(e)
+                }) {
+                    actions.add(
+                        1,
+                        ActorAction::PickUpEntity(material_entity.entity_id.clone()),
+                    );
+                }
+            }
+
+            PlanningSignal::TendToOthers(item) => {
+                // Caring people are much more likely to help
+                let weight = match ctx.entity.characteristic(Characteristic::Empathy) {
+                    CharacteristicStrength::Low => 1,
+                    CharacteristicStrength::Average => 5,
+                    CharacteristicStrength::High => 15,
+                };
+
+                // Anyone here in a bad way that we dont dislike?
+                for other_entity in ctx.entities.in_hex(hex).filter(|e| {
+                    e.entity_id != ctx.entity.entity_id
+                        && e.attributes
+                            .motivators
+                            .get_motivation::<motivator::Hurt>()
+                            .is_some_and(|hurt| hurt > 0.4)
+                        && ctx.entity.relations.bond(&e.entity_id) >= 0.0
+                }) {
+                    actions.add(
+                        weight,
+                        ActorAction::UseItemOn {
+                            item: item.clone(),
+                            target: other_entity.entity_id.clone(),
+                        },
+                    );
+                }
+            }
+
+            PlanningSignal::Stash => {
+                let inventory = ctx.entity.resolve_inventory(ctx.entities).collect_vec();
+                let inv_has_food = inventory.iter().any(|e| e.attributes.food.is_some());
+                let inv_full = ctx.entity.available_inventory_load(ctx.entities) == 0;
+                let our_cache = ctx.entities.in_hex(hex).find(|e| {
+                    e.attributes
+                        .cache
+                        .as_ref()
+                        .is_some_and(|cache| cache.is_owner(&ctx.entity.entity_id))
+                });
+
+                let Some(cache_entity) = our_cache else {
+                    let known_caches = ctx
+                        .entity
+                        .attributes
+                        .memes
+                        .as_ref()
+                        .map(|memes| memes.cache_locations().collect_vec())
+                        .unwrap_or_default();
+
+                    // If we're out of food, go and see what's in a stash we know of
+                    // (if its not ours, we might be able to raid it)
+                    // otherwise if we cant carry any more, hide some of it away
+                    let raid_cache = known_caches
+                        .iter()
+                        .find(|h| **h != hex)
+                        .filter(|_| !inv_has_food)
+                        .map(|cache_hex| ActorAction::GoTowardsHex(*cache_hex));
+                    let plan = Plan::new("stash supplies").then_maybe(raid_cache).then_if(
+                        inv_full && known_caches.is_empty(),
+                        ActorAction::CreateCache,
+                    );
+                    if !plan.is_empty() {
+                        actions.add(1, plan.into());
+                    }
+                    return;
+                };
+
+                // Leave anything we dont need right away in the stash
+                if inv_full {
+                    if let Some(item_entity) = inventory
+                        .iter()
+                        .find(|e| e.attributes.food.is_none() && e.attributes.healing.is_none())
+                    {
+                        actions.add(
+                            2,
+                            ActorAction::DepositInCache {
+                                cache: cache_entity.entity_id.clone(),
+                                item: item_entity.entity_id.clone(),
+                            },
+                        );
+                    }
+                }
+
+                // And take out some food if we need it
+                if !inv_has_food {
+                    if let Some(food_entity) = cache_entity
+                        .resolve_inventory(ctx.entities)
+                        .find(|e| e.attributes.food.is_some())
+                    {
+                        actions.add(
+                            3,
+                            ActorAction::WithdrawFromCache {
+                                cache: cache_entity.entity_id.clone(),
+                                item: food_entity.entity_id.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+
+            PlanningSignal::Escape => {
+                let known_pods = ctx
+                    .entity
+                    .attributes
+                    .memes
+                    .as_ref()
+                    .map(|memes| memes.pod_locations().collect_vec())
+                    .unwrap_or_default();
+
+                match ctx
+                    .entities
+                    .in_hex(hex)
+                    .find(|e| e.attributes.escape_pod.is_some() && ctx.entity.can_see(e))
+                {
+                    Some(pod_entity) => {
+                        // Remember where it is
+                        if !known_pods.contains(&hex) {
+                            actions.add(5, ActorAction::StoreMeme(Meme::PodAt(hex)));
+                        }
+
+                        // and have a go at fixing it, if we have what it needs
+                        let escape_pod = pod_entity.attributes.escape_pod.as_ref().unwrap();
+                        if ctx.entity.part_for_pod(escape_pod, ctx.entities).is_some() {
+                            actions.add(
+                                10,
+                                ActorAction::RepairPod {
+                                    pod: pod_entity.entity_id.clone(),
+                                },
+                            );
+                        }
+                    }
+
+                    // Someone must have got away in it
+                    None if known_pods.contains(&hex) => {
+                        actions.add(5, ActorAction::ForgetMeme(Meme::PodAt(hex)));
+                    }
+                    None => {}
+                }
+
+                // Hang onto any parts we come across
+                if let Some(part_entity) = ctx.entities.in_hex(hex).find(|e| {
+                    e.attributes.pod_part.is_some()
+                        && e.attributes.item.is_some()
+                        && ctx.entity.can_see(e)
+                }) {
+                    actions.add(2, ActorAction::PickUpEntity(part_entity.entity_id.clone()));
+                }
+
+                // and take them to a pod we know of
+                let carrying_part = ctx
+                    .entity
+                    .resolve_inventory(ctx.entities)
+                    .any(|e| e.attributes.pod_part.is_some());
+                if let Some(pod_hex) = known_pods
+                    .iter()
+                    .filter(|h| **h != hex)
+                    .min_by_key(|h| hex.dist_to(**h))
+                    .filter(|_| carrying_part)
+                {
+                    actions.add(3, ActorAction::GoTowardsHex(*pod_hex));
+                }
+            }
+
             // PlanningSignal::WaterAccess => todo!(),
             // PlanningSignal::Shelter => todo!(),
             PlanningSignal::FoodAccess => {
+                // Nothing to eat here? Head whichever way has the most
+                // (keeping clear of anywhere dangerous)
+                if ctx.entities.resources(hex).food == 0 {
+                    if let Some((food_hex, _)) = ctx
+                        .entities
+                        .adjacent_resources(hex)
+                        .filter(|(_, resources)| resources.food > 0 && !resources.hazard)
+                        .max_by_key(|(_, resources)| resources.food)
+                    {
+                        actions.add(1, ActorAction::GoTowardsHex(food_hex));
+                    }
+                }
+
                 // Attempt to pick up food at our location
                 // Is there food we could pick up?
                 for food_entity in ctx
                     .entities
                     .in_hex(hex)
-                    .filter(|e| e.attributes.food.is_some())
+                    .filter(|e| e.attributes.food.is_some() && ctx.entity.can_see(e))
                 {
                     // If the food is morally wrong and we care about that, dont pick it up lol
-                    let food = food_entity.attributes.food.as_ref().unwrap();
+                    let Ok(food) = food_entity.as_food() else {
+                        continue;
+                    };
                     if food.morally_wrong
                         && !ctx.entity.characteristic(Characteristic::Empathy).is_low()
                     {
@@ -63,6 +329,14 @@ impl Entity {
     pub fn get_planning_signals(&self, ctx: &SignalContext) -> impl Iterator<Item = SignalRef> {
         let mut plan_signals = Vec::new();
 
+        // If the entity is not good at planning, they dont get these signals
+        // (and they all need a location to make sense anyway)
+        if ctx.entity.characteristic(Characteristic::Planning).is_low()
+            || self.attributes.hex.is_none()
+        {
+            return plan_signals.into_iter().map(SignalRef::boxed);
+        }
+
         // Get all the items in our inventory, thats a large part of it
         let inventory = self.resolve_inventory(ctx.entities).collect_vec();
 
@@ -74,15 +348,48 @@ impl Entity {
             plan_signals.push(PlanningSignal::FoodAccess);
         }
 
-        // Do we have water in inventory - no such thing yet
-        // let inv_has_food = inventory.iter().any(|e| e.attributes.water_source);
+        // Do we have something to treat wounds with?
+        // if so we might be able to help others, otherwise we should get something
+        match inventory.iter().find(|e| e.attributes.healing.is_some()) {
+            Some(healing_entity) => plan_signals.push(PlanningSignal::TendToOthers(
+                healing_entity.entity_id.clone(),
+            )),
+            None => plan_signals.push(PlanningSignal::FirstAidAccess),
+        }
 
-        // If the entity is not good at planning, they dont get these signals
-        // (doing this a lazy way here)
-        if ctx.entity.characteristic(Characteristic::Planning).is_low() {
-            plan_signals.clear();
+        // If we know of a shelter, we might need to repair it at some point
+        // (and by the water, we could build a raft with enough)
+        let knows_of_shelter = self
+            .attributes
+            .memes
+            .as_ref()
+            .is_some_and(|memes| memes.shelter_locations().next().is_some());
+        let beside_water = self
+            .attributes
+            .hex
+            .is_some_and(|hex| rafting::beside_open_water(hex, ctx.entities));
+        let inv_material = inventory
+            .iter()
+            .filter(|e| e.attributes.material.is_some())
+            .count();
+        if (knows_of_shelter && inv_material == 0)
+            || (beside_water && inv_material < RAFT_MATERIALS)
+        {
+            plan_signals.push(PlanningSignal::MaterialAccess);
         }
 
+        // Always keep an eye out for stuff to scavenge
+        plan_signals.push(PlanningSignal::Scavenge);
+
+        // and think about what to keep stashed away
+        plan_signals.push(PlanningSignal::Stash);
+
+        // and keep an eye out for a way out
+        plan_signals.push(PlanningSignal::Escape);
+
+        // Do we have water in inventory - no such thing yet
+        // let inv_has_food = inventory.iter().any(|e| e.attributes.water_source);
+
         // Return all the signals
         plan_signals.into_iter().map(SignalRef::boxed)
     }
@@ -0,0 +1,229 @@
+//! Wild beasts that stalk the players at night
+//!
+//! A predator lies low through the day, but once night falls it picks someone out to hunt (whoever
+//! is alone with it, or whoever's tracks or scent it comes across) and goes after them for a while
+//! before losing interest. It won't go near a fire or anyone who isn't alone, so sticking together
+//! is the best defence. Enough hits will bring one down, which leaves plenty of meat behind.
+
+use std::cmp::Reverse;
+
+use itertools::Itertools;
+use rand::{seq::IndexedRandom, Rng};
+
+use crate::{
+    create_markers,
+    entity::{
+        brain::{actor_action::ActorAction, focus::ActorFocus},
+        snapshot::EntityView,
+        world::TimeOfDay,
+        Entity, EntityAttributes, EntityFood, EntityItem, EntityPredator,
+    },
+    has_markers,
+    hex::AxialHex,
+    mtch::ActionCtx,
+};
+
+/// How many ticks a predator will go after someone for before losing interest
+pub const MAX_PURSUIT_TICKS: usize = 12;
+
+/// How many hexes away a predator can pick up someone's scent from
+const SCENT_RANGE: isize = 3;
+
+/// How many players it takes to scare a predator off
+const GROUP_SIZE: usize = 2;
+
+/// Chance each tick of a predator prowling somewhere else when it isn't hunting
+const PROWL_CHANCE: f64 = 0.1;
+
+/// How much being mauled hurts
+pub const MAUL_HURT: f32 = 4.0;
+
+/// How much hurt it takes to bring a predator down
+pub const PREDATOR_TOUGHNESS: f32 = 12.0;
+
+/// How many hunks of meat a predator leaves behind
+const MEAT_PORTIONS: usize = 3;
+
+/// How many ticks predator meat lasts before it spoils
+const MEAT_FRESH_TICKS: usize = 200;
+
+impl EntityPredator {
+    /// Is it after someone (and hasn't lost interest in them yet)?
+    pub fn is_hunting(&self) -> bool {
+        self.quarry.is_some() && self.pursuit < MAX_PURSUIT_TICKS
+    }
+}
+
+impl Entity {
+    /// Determine the next action for a predator
+    pub fn get_next_action_as_predator(&self, ctx: &ActionCtx) -> ActorAction {
+        let Some(my_hex) = self.attributes.hex else {
+            return ActorAction::Nothing;
+        };
+
+        // Nothing is worth sticking around near a fire, or when outnumbered
+        if let Some(threat) = predator_threat(my_hex, ctx.entities) {
+            return ActorAction::FleeFrom {
+                entity_id: threat.entity_id.clone(),
+            };
+        }
+
+        // It only hunts at night (and only if it has someone to hunt)
+        let quarry = (ctx.world_state.time_of_day == TimeOfDay::Night)
+            .then(|| self.pick_quarry(my_hex, ctx.entities))
+            .flatten();
+        let Some(quarry) = quarry else {
+            let mut rng = crate::rng::rng();
+            if !rng.random_bool(PROWL_CHANCE) {
+                return ActorAction::Nothing;
+            }
+            return ActorAction::all_movements()
+                .choose(&mut rng)
+                .cloned()
+                .unwrap_or(ActorAction::Nothing);
+        };
+
+        // Pounce if they're right here, otherwise go after them
+        let entity_id = quarry.entity_id.clone();
+        if quarry.attributes.hex == Some(my_hex) {
+            ActorAction::Maul { entity_id }
+        } else {
+            ActorAction::Stalk { entity_id }
+        }
+    }
+
+    /// Who we'd go after
+    /// (anyone alone with us, otherwise whoever we're already after if we haven't lost interest,
+    /// otherwise whoever left tracks here, otherwise whoever's closest within scent range)
+    fn pick_quarry<'a>(
+        &self,
+        hex: AxialHex,
+        entity_view: &'a EntityView<'a>,
+    ) -> Option<&'a Entity> {
+        let predator = self.attributes.predator.as_ref()?;
+        let tired_of = predator
+            .quarry
+            .as_ref()
+            .filter(|_| predator.pursuit >= MAX_PURSUIT_TICKS);
+        let huntable =
+            |e: &&Entity| Some(&e.entity_id) != tired_of && is_vulnerable(e, entity_view);
+
+        entity_view
+            .in_hex(hex)
+            .find(|e| is_vulnerable(e, entity_view))
+            .or_else(|| {
+                let quarry_id = predator.quarry.as_ref()?;
+                entity_view.by_id(quarry_id).filter(huntable)
+            })
+            .or_else(|| {
+                entity_view
+                    .in_hex(hex)
+                    .filter_map(|e| e.attributes.track.as_ref())
+                    .sorted_by_key(|track| Reverse(track.fresh_for))
+                    .find_map(|track| entity_view.by_id(&track.entity_id).filter(huntable))
+            })
+            .or_else(|| {
+                entity_view
+                    .all()
+                    .filter(huntable)
+                    .filter_map(|e| Some((e, hex.dist_to(e.attributes.hex?))))
+                    .filter(|(_, dist)| *dist <= SCENT_RANGE)
+                    .min_by_key(|(_, dist)| *dist)
+                    .map(|(e, _)| e)
+            })
+    }
+
+    /// Would this much more hurt bring us down, if we're a predator?
+    pub fn brought_down_by(&self, hurt: f32) -> bool {
+        self.attributes
+            .predator
+            .as_ref()
+            .is_some_and(|predator| predator.wounds + hurt >= PREDATOR_TOUGHNESS)
+    }
+
+    /// The meat left behind when a predator is brought down
+    pub fn predator_carcass(&self) -> Vec<Entity> {
+        (0..MEAT_PORTIONS)
+            .map(|_| Entity {
+                entity_id: Entity::id(),
+                name: format!("{} meat", self.name),
+                markers: create_markers!(Inspectable),
+                attributes: EntityAttributes {
+                    hex: self.attributes.hex,
+                    item: Some(EntityItem::default()),
+                    food: Some(EntityFood {
+                        sustenance: 1.0,
+                        poison: 0.0,
+                        morally_wrong: false,
+                        fresh_for: Some(MEAT_FRESH_TICKS),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+/// Whatever would scare a predator off from some hex (a fire, or a group of players)
+fn predator_threat<'a>(hex: AxialHex, entity_view: &'a EntityView<'a>) -> Option<&'a Entity> {
+    if let Some(fire) = entity_view.in_hex(hex).find(|e| has_markers!(e, Fire)) {
+        return Some(fire);
+    }
+    let mut players = entity_view.in_hex(hex).filter(|e| is_exposed(e));
+    let first = players.next()?;
+    (players.count() + 1 >= GROUP_SIZE).then_some(first)
+}
+
+/// Is some player out in the open? (not tucked away in a shelter)
+fn is_exposed(entity: &Entity) -> bool {
+    has_markers!(entity, Player)
+        && !matches!(entity.attributes.focus, Some(ActorFocus::Sheltering { .. }))
+}
+
+/// Is some player out in the open, on their own, and away from any fire?
+fn is_vulnerable(entity: &Entity, entity_view: &EntityView) -> bool {
+    is_exposed(entity)
+        && entity
+            .attributes
+            .hex
+            .is_some_and(|hex| predator_threat(hex, entity_view).is_none())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{entity::snapshot::EntitySnapshot, mtch::test_match::test_player};
+
+    fn predator_at(hex: AxialHex) -> Entity {
+        Entity {
+            entity_id: Entity::id(),
+            name: "Grey wolf".into(),
+            markers: create_markers!(Being, Animal),
+            attributes: EntityAttributes {
+                hex: Some(hex),
+                predator: Some(Default::default()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_predator_avoids_groups() {
+        let hex = AxialHex::ZERO;
+        let predator = predator_at(hex);
+        let loner = test_player("Loner", AxialHex::WEST + AxialHex::WEST);
+        let snapshot = EntitySnapshot::new(vec![
+            predator.clone(),
+            loner.clone(),
+            test_player("Pair A", AxialHex::EAST),
+            test_player("Pair B", AxialHex::EAST),
+        ]);
+        let view = snapshot.view();
+
+        // The pair are closer, but there's two of them
+        let quarry = predator.pick_quarry(hex, &view).unwrap();
+        assert_eq!(quarry.entity_id, loner.entity_id);
+    }
+}
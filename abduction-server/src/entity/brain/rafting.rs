@@ -0,0 +1,462 @@
+//! Getting about on the water
+//!
+//! Lakes are open water, nobody can walk out into one (though anyone who ends up in the water can
+//! wade back out), so drinking and fishing are done from the shore. Anyone carrying enough building
+//! material can lash together a raft beside the water, then climb aboard and paddle it out across
+//! the lake, bringing everyone else aboard along with them. A raft only has room for a couple, and
+//! the more there are aboard the more likely rough weather is to tip it over, throwing everyone in
+//! and losing the raft. Rafts stay wherever they were left, so anyone can take one, but taking
+//! someone else's (when they aren't aboard to invite us) is theft, and they won't be happy about it.
+
+use itertools::Itertools;
+use strum::VariantArray;
+
+use crate::{
+    entity::{
+        brain::{
+            actor_action::ActorAction,
+            characteristic::Characteristic,
+            focus::ActorFocus,
+            signal::{SignalContext, WeightedActorActions},
+        },
+        snapshot::EntityView,
+        world::WeatherKind,
+        Entity, EntityId, EntityRaft,
+    },
+    hex::{AxialHex, AxialHexDirection},
+    rng::{audited_bool, RollKind},
+};
+
+/// How much building material it takes to lash together a raft
+pub const RAFT_MATERIALS: usize = 3;
+
+/// How many a raft has room for
+pub const RAFT_CAPACITY: usize = 2;
+
+/// Above this distress, there are more important things to do than mess about on the water
+pub const DISTRESS_LIMIT_FOR_RAFTING: f32 = 0.5;
+
+/// How much going over (and the scramble back out) hurts
+pub const CAPSIZE_HURT: f32 = 2.0;
+
+/// Is some hex beside open water? (somewhere to drink, fish and launch a raft from)
+pub fn beside_open_water(hex: AxialHex, entity_view: &EntityView) -> bool {
+    hex.neighbours()
+        .into_iter()
+        .any(|h| entity_view.resources(h).open_water)
+}
+
+/// Every water source within reach of some hex
+/// (anything in it, or out on the open water beside it)
+pub fn water_within_reach<'a>(
+    hex: AxialHex,
+    entity_view: &'a EntityView<'a>,
+) -> impl Iterator<Item = &'a Entity> {
+    let open_water = hex
+        .neighbours()
+        .into_iter()
+        .filter(|h| entity_view.resources(*h).open_water);
+    std::iter::once(hex)
+        .chain(open_water)
+        .flat_map(|h| entity_view.in_hex(h))
+        .filter(|e| e.attributes.water_source.is_some())
+}
+
+/// The lake at (or beside) some hex, if there is one to fish in
+pub fn lake_within_reach<'a>(hex: AxialHex, entity_view: &'a EntityView<'a>) -> Option<&'a Entity> {
+    water_within_reach(hex, entity_view).find(|e| {
+        e.attributes
+            .hex
+            .is_some_and(|h| entity_view.resources(h).open_water)
+    })
+}
+
+/// Is some entity aboard some raft?
+pub fn is_aboard(entity: &Entity, raft_id: &EntityId) -> bool {
+    matches!(
+        &entity.attributes.focus,
+        Some(ActorFocus::Rafting { raft }) if raft == raft_id
+    )
+}
+
+/// Everyone aboard some raft
+pub fn aboard<'a>(
+    raft: &'a Entity,
+    entity_view: &'a EntityView<'a>,
+) -> impl Iterator<Item = &'a Entity> {
+    raft.attributes
+        .hex
+        .into_iter()
+        .flat_map(|hex| entity_view.in_hex(hex))
+        .filter(|e| is_aboard(e, &raft.entity_id))
+}
+
+impl Entity {
+    /// Enough of the building material we are carrying to lash together a raft
+    /// (none if we haven't got enough)
+    pub fn raft_materials(&self, entity_view: &EntityView) -> Option<Vec<EntityId>> {
+        let materials = self
+            .resolve_inventory(entity_view)
+            .filter(|e| e.attributes.material.is_some())
+            .map(|e| e.entity_id.clone())
+            .take(RAFT_MATERIALS)
+            .collect_vec();
+        (materials.len() == RAFT_MATERIALS).then_some(materials)
+    }
+
+    /// Whether some raft we are paddling goes over this time
+    /// (rough weather is what does it, and the fuller it is the more likely it goes)
+    pub fn capsizes(
+        &self,
+        weather: &WeatherKind,
+        raft: &EntityRaft,
+        aboard: usize,
+        rng: &mut impl rand::Rng,
+    ) -> bool {
+        let load = aboard as f64 / raft.capacity.max(1) as f64;
+        audited_bool(
+            rng,
+            RollKind::Capsize,
+            Some(&self.entity_id),
+            (weather.capsize_chance() * load).min(1.0),
+        )
+    }
+}
+
+/// With no focus, we might build a raft by the water or climb aboard one
+pub fn act_on_rafts(ctx: &SignalContext, actions: &mut WeightedActorActions) {
+    let Some(hex) = ctx.entity.attributes.hex else {
+        return;
+    };
+    if ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_RAFTING {
+        return;
+    }
+
+    // No raft here yet? We could build one if we're by the water with enough to build it from
+    let rafts = ctx
+        .entities
+        .in_hex(hex)
+        .filter_map(|e| Some((e, e.attributes.raft.as_ref()?)))
+        .collect_vec();
+    if rafts.is_empty() {
+        if beside_open_water(hex, ctx.entities) && ctx.entity.raft_materials(ctx.entities).is_some()
+        {
+            actions.add(3, ActorAction::BuildRaft);
+        }
+        return;
+    }
+
+    // Any raft with room could take us out on the water
+    // (though only the heartless would take someone else's without them)
+    for (raft_entity, raft) in rafts {
+        let aboard = aboard(raft_entity, ctx.entities).collect_vec();
+        if aboard.len() >= raft.capacity {
+            continue;
+        }
+        let weight = match &raft.owner {
+            Some(owner) if *owner != ctx.entity.entity_id => {
+                if aboard.iter().any(|e| e.entity_id == *owner) {
+                    if ctx.entity.relations.bond(owner) > 0.0 {
+                        3
+                    } else {
+                        0
+                    }
+                } else if ctx.entity.characteristic(Characteristic::Empathy).is_low() {
+                    2
+                } else {
+                    0
+                }
+            }
+            _ => 3,
+        };
+        if weight > 0 {
+            actions.add(
+                weight,
+                ActorAction::BoardRaft {
+                    raft: raft_entity.entity_id.clone(),
+                },
+            );
+        }
+    }
+}
+
+/// Aboard a raft, we paddle about on the water until we've had enough
+pub fn act_aboard(raft: &EntityId, ctx: &SignalContext, actions: &mut WeightedActorActions) {
+    let Some(hex) = ctx.entity.attributes.hex else {
+        return;
+    };
+
+    // Once it's gone, or something needs dealing with ashore, we get off
+    if ctx.entities.by_id(raft).is_none()
+        || ctx.entity.attributes.motivators.distress() > DISTRESS_LIMIT_FOR_RAFTING
+    {
+        actions.add(1000, ActorAction::DisembarkRaft);
+        return;
+    }
+
+    for direction in AxialHexDirection::VARIANTS {
+        if ctx.entities.resources(hex + (*direction).into()).open_water {
+            actions.add(5, ActorAction::PaddleRaft(*direction));
+        }
+    }
+    let on_water = ctx.entities.resources(hex).open_water;
+    actions.add(if on_water { 2 } else { 5 }, ActorAction::DisembarkRaft);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{
+            brain::{
+                characteristic::CharacteristicStrength,
+                test_signal::{EntityBuilder, SignalContextBuilder},
+            },
+            generate::PropGenerator,
+            EntityAttributes, EntityLocation, EntityWaterSource,
+        },
+        location::LocationKind,
+        logs::GameLogBody,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    fn lake(hex: AxialHex) -> Entity {
+        Entity {
+            entity_id: Entity::id(),
+            name: "Test lake".into(),
+            attributes: EntityAttributes {
+                hex: Some(hex),
+                location: Some(EntityLocation {
+                    location_kind: LocationKind::Lake,
+                }),
+                water_source: Some(EntityWaterSource::quality()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn raft(hex: AxialHex, owner: Option<&Entity>) -> Entity {
+        let mut raft = PropGenerator::Raft.generate(&mut crate::rng::rng());
+        raft.attributes.hex = Some(hex);
+        raft.attributes.raft.as_mut().unwrap().owner = owner.map(|e| e.entity_id.clone());
+        raft
+    }
+
+    fn is_boarding(action: &ActorAction) -> bool {
+        matches!(action, ActorAction::BoardRaft { .. })
+    }
+
+    #[test]
+    fn test_rafts_are_built_beside_the_water() {
+        let materials = (0..RAFT_MATERIALS)
+            .map(|_| PropGenerator::BuildingMaterial.generate(&mut crate::rng::rng()))
+            .collect_vec();
+        let builder = materials
+            .iter()
+            .fold(EntityBuilder::player("Builder"), |builder, material| {
+                builder.holding(material)
+            })
+            .build();
+        let act = |player: Entity, lake_hex: AxialHex| {
+            let ctx = materials
+                .iter()
+                .cloned()
+                .fold(SignalContextBuilder::new(player), |ctx, material| {
+                    ctx.with_entity(material)
+                })
+                .with_entity(lake(lake_hex));
+            ctx.with_ctx(|ctx| {
+                let mut actions = WeightedActorActions::default();
+                act_on_rafts(ctx, &mut actions);
+                actions
+            })
+        };
+
+        act(builder.clone(), AxialHex::EAST)
+            .assert_contains_action_weight_at_least(3, |a| matches!(a, ActorAction::BuildRaft));
+
+        // Not out of sight of the water, or without enough to build it from
+        act(builder, AxialHex::from((2, 0)))
+            .assert_no_action(|a| matches!(a, ActorAction::BuildRaft));
+        act(
+            EntityBuilder::player("Empty handed").build(),
+            AxialHex::EAST,
+        )
+        .assert_no_action(|a| matches!(a, ActorAction::BuildRaft));
+    }
+
+    #[test]
+    fn test_full_rafts_cant_be_boarded() {
+        let raft = raft(AxialHex::ZERO, None);
+        let passenger = |name: &str| {
+            EntityBuilder::player(name)
+                .focus(ActorFocus::Rafting {
+                    raft: raft.entity_id.clone(),
+                })
+                .build()
+        };
+        let boarding = |passengers: Vec<Entity>| {
+            passengers
+                .into_iter()
+                .fold(
+                    SignalContextBuilder::new(EntityBuilder::player("Latecomer").build())
+                        .with_entity(raft.clone()),
+                    |ctx, passenger| ctx.with_entity(passenger),
+                )
+                .with_ctx(|ctx| {
+                    let mut actions = WeightedActorActions::default();
+                    act_on_rafts(ctx, &mut actions);
+                    actions
+                })
+        };
+
+        boarding(vec![passenger("First")]).assert_contains_action_weight_at_least(3, is_boarding);
+        boarding(vec![passenger("First"), passenger("Second")]).assert_no_action(is_boarding);
+    }
+
+    #[test]
+    fn test_only_the_heartless_take_rafts() {
+        let owner = test_player("Owner", AxialHex::from((2, 0)));
+        let raft = raft(AxialHex::ZERO, Some(&owner));
+        let boarding = |empathy: CharacteristicStrength| {
+            let player = EntityBuilder::player("Passerby")
+                .characteristic(Characteristic::Empathy, empathy)
+                .build();
+            SignalContextBuilder::new(player)
+                .with_entity(owner.clone())
+                .with_entity(raft.clone())
+                .with_ctx(|ctx| {
+                    let mut actions = WeightedActorActions::default();
+                    act_on_rafts(ctx, &mut actions);
+                    actions
+                })
+        };
+
+        boarding(CharacteristicStrength::Low)
+            .assert_contains_action_weight_at_least(2, is_boarding);
+        boarding(CharacteristicStrength::Average).assert_no_action(is_boarding);
+    }
+
+    #[test]
+    fn test_rough_weather_capsizes_rafts() {
+        let paddler = test_player("Paddler", AxialHex::ZERO);
+        let raft = EntityRaft {
+            capacity: RAFT_CAPACITY,
+            owner: None,
+        };
+        let capsizes = |weather: WeatherKind| {
+            let mut rng = crate::rng::rng();
+            (0..100)
+                .filter(|_| paddler.capsizes(&weather, &raft, RAFT_CAPACITY, &mut rng))
+                .count()
+        };
+
+        assert_eq!(capsizes(WeatherKind::Lovely), 0);
+        assert!(capsizes(WeatherKind::Hurricane) > 0);
+    }
+
+    #[tokio::test]
+    async fn test_nobody_walks_into_the_lake() {
+        let builder = TestMatch::builder();
+        let player = test_player("Walker", AxialHex::EAST);
+        let player_id = player.entity_id.clone();
+        let mut test_match = builder
+            .with_entity(lake(AxialHex::ZERO))
+            .with_entity(player)
+            .build()
+            .await;
+
+        for _ in 0..50 {
+            test_match.tick().await;
+            let player = test_match.entity(&player_id).unwrap();
+            assert_ne!(
+                player.attributes.hex,
+                Some(AxialHex::ZERO),
+                "Walked into the lake"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rafting_out_onto_the_lake() {
+        let builder = TestMatch::builder();
+        let materials = (0..RAFT_MATERIALS)
+            .map(|_| PropGenerator::BuildingMaterial.generate(&mut crate::rng::rng()))
+            .collect_vec();
+        let mut player = test_player("Rafter", AxialHex::EAST);
+        for material in &materials {
+            player
+                .relations
+                .inventory_mut()
+                .insert(material.entity_id.clone());
+        }
+        let player_id = player.entity_id.clone();
+        let mut test_match = materials
+            .into_iter()
+            .fold(builder, |builder, material| builder.with_entity(material))
+            .with_entity(lake(AxialHex::ZERO))
+            .with_entity(player)
+            .build()
+            .await;
+
+        let paddled = test_match
+            .tick_until(200, |m| {
+                m.has_log_by(&player_id, |body| {
+                    matches!(body, GameLogBody::EntityPaddleRaft)
+                })
+            })
+            .await;
+        assert!(paddled, "Never paddled out onto the lake");
+        assert!(test_match.has_log_by(&player_id, |body| {
+            matches!(body, GameLogBody::EntityBuildRaft)
+        }));
+
+        // The raft went with us
+        let player = test_match.entity(&player_id).unwrap();
+        let Some(ActorFocus::Rafting { raft }) = &player.attributes.focus else {
+            panic!("Not aboard the raft");
+        };
+        let raft = test_match.entity(raft).unwrap();
+        assert_eq!(raft.attributes.hex, player.attributes.hex);
+        assert_eq!(raft.attributes.raft.unwrap().owner, Some(player_id));
+    }
+
+    #[tokio::test]
+    async fn test_taking_a_raft_is_theft() {
+        let builder = TestMatch::builder();
+        let owner = test_player("Owner", AxialHex::from((-2, 0)));
+        let thief = EntityBuilder::player("Thief")
+            .at(AxialHex::EAST)
+            .characteristic(Characteristic::Empathy, CharacteristicStrength::Low)
+            .build();
+        let raft = raft(AxialHex::EAST, Some(&owner));
+        let (owner_id, thief_id, raft_id) = (
+            owner.entity_id.clone(),
+            thief.entity_id.clone(),
+            raft.entity_id.clone(),
+        );
+        let mut test_match = builder
+            .with_entity(lake(AxialHex::ZERO))
+            .with_entity(owner)
+            .with_entity(thief)
+            .with_entity(raft)
+            .build()
+            .await;
+
+        let taken = test_match
+            .tick_until(100, |m| {
+                m.has_log_by(&thief_id, |body| {
+                    matches!(body, GameLogBody::EntityTakeRaft)
+                })
+            })
+            .await;
+        assert!(taken, "Never took the raft");
+
+        // Its theirs now, and the owner knows who to blame
+        let raft = test_match.entity(&raft_id).unwrap();
+        assert_eq!(raft.attributes.raft.unwrap().owner, Some(thief_id.clone()));
+        let owner = test_match.entity(&owner_id).unwrap();
+        assert!(owner.relations.bond(&thief_id) < 0.0);
+    }
+}
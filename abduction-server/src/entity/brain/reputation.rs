@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::Entity,
+    event::{builder::GameEventBuilder, GameEventKind, GameEventTarget},
+    mtch::ActionCtx,
+};
+
+/// How much we have to like someone before leaving them behind counts as abandoning them
+pub const ALLY_BOND: f32 = 0.3;
+
+/// Something an entity did that others would judge them for
+/// (good or bad)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[qubit::ts]
+pub enum Deed {
+    /// Took something that belonged to someone else
+    Theft,
+
+    /// Ate (part of) a corpse
+    Cannibalism,
+
+    /// Took the clothes off a corpse
+    StripCorpse,
+
+    /// Went through a corpse's things
+    LootCorpse,
+
+    /// Walked away from an ally who was in a bad way
+    AbandonAlly,
+
+    /// Treated the wounds of someone else
+    TendWounds,
+
+    /// Threw something at someone and hit them
+    Assault,
+
+    /// Got someone out of trouble (see `rescue`)
+    Rescue,
+}
+
+impl Deed {
+    /// How good/bad is this deed?
+    /// (negative is bad)
+    pub fn alignment(&self) -> f32 {
+        match self {
+            Deed::Theft => -1.0,
+            Deed::Cannibalism => -2.0,
+            Deed::StripCorpse => -1.0,
+            Deed::LootCorpse => -0.5,
+            Deed::AbandonAlly => -1.5,
+            Deed::TendWounds => 1.0,
+            Deed::Assault => -1.5,
+            Deed::Rescue => 1.5,
+        }
+    }
+}
+
+/// The record of every deed an entity has done
+/// (regardless of whether anyone saw it)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct ReputationLedger {
+    deeds: Vec<Deed>,
+}
+
+impl ReputationLedger {
+    pub fn deeds(&self) -> impl Iterator<Item = &Deed> {
+        self.deeds.iter()
+    }
+
+    /// Overall alignment of this entity
+    /// (0 is neutral, negative is villainous, positive is heroic)
+    pub fn score(&self) -> f32 {
+        self.deeds.iter().map(Deed::alignment).sum()
+    }
+}
+
+impl Entity {
+    /// Add a deed to our ledger and let anyone nearby see it happen
+    pub fn record_deed(&mut self, deed: Deed, ctx: &mut ActionCtx) {
+        self.attributes
+            .reputation
+            .get_or_insert_default()
+            .deeds
+            .push(deed);
+
+        if let Some(hex) = self.attributes.hex {
+            GameEventBuilder::new()
+                .of_kind(GameEventKind::Deed {
+                    entity_id: self.entity_id.clone(),
+                    deed,
+                })
+                .targets(GameEventTarget::Hex(hex))
+                .with_physical_senses(0)
+                .add(ctx);
+        }
+    }
+}
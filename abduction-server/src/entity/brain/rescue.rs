@@ -0,0 +1,204 @@
+//! Coming to the aid of others in trouble
+//!
+//! Anyone in a bad way (badly hurt, caught in a hazard, or asleep with something dangerous
+//! about) raises a `Distress` event each tick for anyone nearby to notice. Whether someone answers
+//! it comes down to how much they like them and how much they care about people in general. The
+//! hurt get patched up or handed some food, anyone stuck in a hazard gets dragged out of it, and
+//! the sleeping get someone to stand guard over them, which is enough to keep a predator off (see
+//! `predator`). There's no carrying water around yet, so that can't be shared.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{
+        brain::{
+            characteristic::Characteristic, focus::ActorFocus, motivator, reputation::ALLY_BOND,
+        },
+        snapshot::EntityView,
+        Entity, EntityId,
+    },
+    event::{builder::GameEventBuilder, GameEventKind, GameEventTarget},
+    hex::AxialHex,
+    mtch::ActionCtx,
+};
+
+/// Above this hurt, someone is in a bad way
+pub const BADLY_HURT: f32 = 0.6;
+
+/// Above this hunger, someone in a bad way could do with something to eat
+pub const HUNGRY_ENOUGH_TO_SHARE: f32 = 0.5;
+
+/// How many hexes away a predator has to be to put a sleeper in danger
+const DANGER_RANGE: isize = 3;
+
+/// Above this distress, a guard has to go and look after themselves instead
+pub const DISTRESS_LIMIT_FOR_GUARDING: f32 = 0.6;
+
+/// Some kind of trouble an entity can be in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Plight {
+    /// Stuck in a hex with something dangerous in it
+    CaughtInHazard,
+
+    /// Asleep with a predator about, and nobody keeping watch
+    AsleepInDanger,
+
+    /// Hurt enough to be in real trouble
+    BadlyHurt,
+}
+
+impl Entity {
+    /// What trouble we're in, if any
+    /// (if in more than one kind, the most pressing)
+    pub fn plight(&self, entity_view: &EntityView) -> Option<Plight> {
+        let hex = self.attributes.hex?;
+
+        if entity_view
+            .in_hex(hex)
+            .any(|e| e.attributes.hazard.is_some())
+        {
+            return Some(Plight::CaughtInHazard);
+        }
+
+        let asleep = matches!(self.attributes.focus, Some(ActorFocus::Sleeping { .. }));
+        if asleep && !is_watched_over(self, hex, entity_view) && predator_near(hex, entity_view) {
+            return Some(Plight::AsleepInDanger);
+        }
+
+        self.attributes
+            .motivators
+            .get_motivation::<motivator::Hurt>()
+            .is_some_and(|hurt| hurt > BADLY_HURT)
+            .then_some(Plight::BadlyHurt)
+    }
+
+    /// How keen we are to help someone in trouble (0 if not at all)
+    /// (allies always are, and the caring will help anyone they don't dislike)
+    pub fn rescue_weight(&self, entity_id: &EntityId) -> usize {
+        let bond = self.relations.bond(entity_id);
+        if bond < 0.0 {
+            return 0;
+        }
+
+        let ally = bond >= ALLY_BOND;
+        match (ally, self.characteristic(Characteristic::Empathy).is_high()) {
+            (true, true) => 60,
+            (true, false) => 30,
+            (false, true) => 20,
+            (false, false) => 0,
+        }
+    }
+
+    /// Let anyone nearby know we're in trouble, if we are
+    pub fn call_for_help(&self, ctx: &mut ActionCtx) {
+        let (Some(hex), Some(plight)) = (self.attributes.hex, self.plight(ctx.entities)) else {
+            return;
+        };
+
+        GameEventBuilder::new()
+            .of_kind(GameEventKind::Distress {
+                entity_id: self.entity_id.clone(),
+                plight,
+            })
+            .targets(GameEventTarget::HexSurrounds(hex))
+            .with_physical_senses(1)
+            .add(ctx);
+    }
+}
+
+/// Somewhere next to some hex to drag someone to, that has nothing dangerous in it
+/// (and isn't out on open water)
+pub fn safe_hex_near(
+    hex: AxialHex,
+    world_radius: isize,
+    entity_view: &EntityView,
+) -> Option<AxialHex> {
+    hex.neighbours().into_iter().find(|h| {
+        h.within_bounds(world_radius)
+            && !entity_view.resources(*h).open_water
+            && !entity_view
+                .in_hex(*h)
+                .any(|e| e.attributes.hazard.is_some())
+    })
+}
+
+/// Is someone already keeping watch over some sleeper?
+fn is_watched_over(sleeper: &Entity, hex: AxialHex, entity_view: &EntityView) -> bool {
+    entity_view.in_hex(hex).any(|e| {
+        matches!(
+            &e.attributes.focus,
+            Some(ActorFocus::Guarding { ward }) if *ward == sleeper.entity_id
+        )
+    })
+}
+
+/// Is there a predator within reach of some hex?
+fn predator_near(hex: AxialHex, entity_view: &EntityView) -> bool {
+    entity_view.all().any(|e| {
+        e.attributes.predator.is_some()
+            && e.attributes
+                .hex
+                .is_some_and(|h| h.dist_to(hex) <= DANGER_RANGE)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        create_markers,
+        entity::{snapshot::EntitySnapshot, EntityAttributes, EntityHazard},
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_plight_of_sleepers_and_the_stuck() {
+        let wolf = Entity {
+            entity_id: Entity::id(),
+            name: "Grey wolf".into(),
+            markers: create_markers!(Being, Animal),
+            attributes: EntityAttributes {
+                hex: Some(AxialHex::from((2, 0))),
+                predator: Some(Default::default()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut sleeper = test_player("Sleeper", AxialHex::ZERO);
+        sleeper.attributes.focus = Some(ActorFocus::Sleeping {
+            remaining_turns: 10,
+        });
+        let snapshot = EntitySnapshot::new(vec![wolf.clone(), sleeper.clone()]);
+        assert_eq!(
+            sleeper.plight(&snapshot.view()),
+            Some(Plight::AsleepInDanger)
+        );
+
+        // Not once someone is keeping watch
+        let mut guard = test_player("Guard", AxialHex::ZERO);
+        guard.attributes.focus = Some(ActorFocus::Guarding {
+            ward: sleeper.entity_id.clone(),
+        });
+        let snapshot = EntitySnapshot::new(vec![wolf, sleeper.clone(), guard]);
+        assert_eq!(sleeper.plight(&snapshot.view()), None);
+
+        // Someone stuck in a hazard needs getting out before anything else
+        let stuck = test_player("Stuck", AxialHex::WEST);
+        let hazard = Entity {
+            entity_id: Entity::id(),
+            name: "Bramble".into(),
+            attributes: EntityAttributes {
+                hex: Some(AxialHex::WEST),
+                hazard: Some(EntityHazard { damage: 1 }),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let snapshot = EntitySnapshot::new(vec![stuck.clone(), hazard]);
+        let view = snapshot.view();
+        assert_eq!(stuck.plight(&view), Some(Plight::CaughtInHazard));
+        let safe = safe_hex_near(AxialHex::WEST, 10, &view).unwrap();
+        assert!(safe.is_adjacent(AxialHex::WEST));
+    }
+}
@@ -0,0 +1,226 @@
+//! What part an entity plays in the match, and so how it decides what to do
+//!
+//! Each role has a `Brain` that picks an action for entities in that role each tick. The tick
+//! loop just asks every entity with a role for its next action, so new kinds of actor only need a
+//! role and a brain here.
+
+use rand::{seq::IndexedRandom, Rng};
+use strum::VariantArray;
+
+use crate::{
+    content::content,
+    entity::{
+        brain::{actor_action::ActorAction, signal::SignalRef},
+        Entity,
+    },
+    has_markers,
+    hex::AxialHexDirection,
+    logs::GameLogBody,
+    mtch::ActionCtx,
+};
+
+/// Chance each tick of an animal wandering off somewhere else
+const ANIMAL_WANDER_CHANCE: f64 = 0.3;
+
+/// Chance each tick of a spirit drifting somewhere else
+const SPIRIT_DRIFT_CHANCE: f64 = 0.15;
+
+/// Chance each tick of a spirit thinking back on what they hoped for
+const SPIRIT_WISTFUL_CHANCE: f64 = 0.005;
+
+/// Decides what an entity does next
+pub trait Brain: Sync {
+    fn decide<'a>(
+        &self,
+        entity: &'a Entity,
+        ctx: &ActionCtx,
+        event_signals: &mut dyn Iterator<Item = SignalRef<'a>>,
+    ) -> ActorAction;
+}
+
+/// The part an entity plays in the match
+/// (roles act in this order each tick, so the crew get in before the players)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EntityRole {
+    Presenter,
+    Collector,
+    Player,
+    Predator,
+    Animal,
+    Spirit,
+}
+
+impl EntityRole {
+    /// Which role some entity has, if it acts at all
+    pub fn of(entity: &Entity) -> Option<Self> {
+        if entity.attributes.presenter.is_some() {
+            Some(EntityRole::Presenter)
+        } else if entity.attributes.collector.is_some() {
+            Some(EntityRole::Collector)
+        } else if has_markers!(entity, Player) {
+            Some(EntityRole::Player)
+        } else if entity.attributes.predator.is_some() {
+            Some(EntityRole::Predator)
+        } else if has_markers!(entity, Animal) {
+            Some(EntityRole::Animal)
+        } else if entity.attributes.spirit.is_some() {
+            Some(EntityRole::Spirit)
+        } else {
+            None
+        }
+    }
+
+    /// The brain that decides for entities with this role
+    pub fn brain(&self) -> &'static dyn Brain {
+        match self {
+            EntityRole::Presenter => &PresenterBrain,
+            EntityRole::Collector => &CollectorBrain,
+            EntityRole::Player => &PlayerBrain,
+            EntityRole::Predator => &PredatorBrain,
+            EntityRole::Animal => &AnimalBrain,
+            EntityRole::Spirit => &SpiritBrain,
+        }
+    }
+}
+
+struct PlayerBrain;
+
+impl Brain for PlayerBrain {
+    fn decide<'a>(
+        &self,
+        entity: &'a Entity,
+        ctx: &ActionCtx,
+        event_signals: &mut dyn Iterator<Item = SignalRef<'a>>,
+    ) -> ActorAction {
+        entity.get_next_action(ctx, event_signals)
+    }
+}
+
+struct PresenterBrain;
+
+impl Brain for PresenterBrain {
+    fn decide<'a>(
+        &self,
+        entity: &'a Entity,
+        ctx: &ActionCtx,
+        event_signals: &mut dyn Iterator<Item = SignalRef<'a>>,
+    ) -> ActorAction {
+        entity.get_next_action_as_presenter(ctx, event_signals)
+    }
+}
+
+struct CollectorBrain;
+
+impl Brain for CollectorBrain {
+    fn decide<'a>(
+        &self,
+        entity: &'a Entity,
+        ctx: &ActionCtx,
+        event_signals: &mut dyn Iterator<Item = SignalRef<'a>>,
+    ) -> ActorAction {
+        entity.get_next_action_as_collector(ctx, event_signals)
+    }
+}
+
+/// Predators lie low by day and hunt by night (see `predator`)
+struct PredatorBrain;
+
+impl Brain for PredatorBrain {
+    fn decide<'a>(
+        &self,
+        entity: &'a Entity,
+        ctx: &ActionCtx,
+        _event_signals: &mut dyn Iterator<Item = SignalRef<'a>>,
+    ) -> ActorAction {
+        entity.get_next_action_as_predator(ctx)
+    }
+}
+
+/// Animals just wander about for now
+/// (TODO: grazing, fleeing, being hunted etc)
+struct AnimalBrain;
+
+impl Brain for AnimalBrain {
+    fn decide<'a>(
+        &self,
+        _entity: &'a Entity,
+        _ctx: &ActionCtx,
+        _event_signals: &mut dyn Iterator<Item = SignalRef<'a>>,
+    ) -> ActorAction {
+        let mut rng = crate::rng::rng();
+        if !rng.random_bool(ANIMAL_WANDER_CHANCE) {
+            return ActorAction::Nothing;
+        }
+        ActorAction::all_movements()
+            .choose(&mut rng)
+            .cloned()
+            .unwrap_or(ActorAction::Nothing)
+    }
+}
+
+/// Spirits drift about aimlessly, every now and then thinking of what they'd hoped for
+struct SpiritBrain;
+
+impl Brain for SpiritBrain {
+    fn decide<'a>(
+        &self,
+        entity: &'a Entity,
+        _ctx: &ActionCtx,
+        _event_signals: &mut dyn Iterator<Item = SignalRef<'a>>,
+    ) -> ActorAction {
+        let mut rng = crate::rng::rng();
+        if let Some(hope) = entity
+            .attributes
+            .spirit
+            .as_ref()
+            .and_then(|s| s.hope.as_ref())
+        {
+            if rng.random_bool(SPIRIT_WISTFUL_CHANCE) {
+                return ActorAction::Log {
+                    other: None,
+                    body: GameLogBody::SpiritWistful {
+                        hope: content().hope_text(hope),
+                    },
+                };
+            }
+        }
+        if !rng.random_bool(SPIRIT_DRIFT_CHANCE) {
+            return ActorAction::Nothing;
+        }
+        AxialHexDirection::VARIANTS
+            .choose(&mut rng)
+            .map(|direction| ActorAction::Drift(*direction))
+            .unwrap_or(ActorAction::Nothing)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        hex::AxialHex,
+        mtch::{
+            crew::{generate_collector, generate_presenter},
+            test_match::test_player,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_crew_act_before_players() {
+        let player = test_player("Player", AxialHex::ZERO);
+        let presenter = generate_presenter();
+        let collector = generate_collector();
+
+        let mut roles = [&player, &collector, &presenter].map(|e| EntityRole::of(e).unwrap());
+        roles.sort();
+        assert_eq!(
+            roles,
+            [
+                EntityRole::Presenter,
+                EntityRole::Collector,
+                EntityRole::Player
+            ]
+        );
+    }
+}
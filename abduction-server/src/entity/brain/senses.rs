@@ -0,0 +1,121 @@
+//! Entities have to notice props before they can do anything with them
+//!
+//! Anything that isn't `Inspectable` (items, caches etc) starts out unnoticed. Entities spot them by
+//! looking around, or sometimes just by arriving somewhere, with the odds depending on their vision
+//! and how conspicuous the prop is. Once spotted, they remember it (as a meme) wherever it ends up.
+
+use rand::Rng;
+
+use crate::{
+    entity::{
+        brain::{characteristic::Characteristic, meme::Meme},
+        snapshot::EntityView,
+        Entity, EntityId,
+    },
+    has_markers,
+    hex::AxialHex,
+};
+
+/// How much less likely we are to notice something just by walking in
+/// (compared to deliberately looking around)
+pub const ARRIVAL_NOTICE_SCALE: f64 = 0.5;
+
+impl Entity {
+    /// How easy this is to notice (0 -> nigh invisible, 1 -> can't miss it)
+    /// None if it doesn't need to be noticed at all (beings, landmarks etc)
+    pub fn conspicuousness(&self) -> Option<f64> {
+        if has_markers!(self, Inspectable) {
+            return None;
+        }
+        if let Some(container) = &self.attributes.container {
+            return Some(if container.hidden { 0.15 } else { 0.6 });
+        }
+
+        // Bigger things are easier to spot
+        let item = self.attributes.item.as_ref()?;
+        Some((0.3 + 0.15 * item.heft as f64).min(0.9))
+    }
+
+    /// Are we aware of some other entity?
+    pub fn can_see(&self, other: &Entity) -> bool {
+        other.conspicuousness().is_none()
+            || self.relations.inventory().any(|id| *id == other.entity_id)
+            || other
+                .attributes
+                .cache
+                .as_ref()
+                .is_some_and(|cache| cache.is_owner(&self.entity_id))
+            || self
+                .attributes
+                .memes
+                .as_ref()
+                .is_some_and(|memes| memes.has_spotted(&other.entity_id))
+    }
+
+    /// Remember that we know about some prop (i.e because we put it there)
+    pub fn remember_spotted(&mut self, entity_id: &EntityId) {
+        self.memes_mut().insert(Meme::Spotted(entity_id.clone()));
+    }
+
+    /// Try to notice the props in some hex we haven't spotted yet
+    /// Returns whatever we noticed
+    pub fn notice_props<'a>(
+        &mut self,
+        hex: AxialHex,
+        entity_view: &'a EntityView<'a>,
+        scale: f64,
+        rng: &mut impl Rng,
+    ) -> Vec<&'a Entity> {
+        let vision = self.characteristic(Characteristic::Vision).success_chance();
+        let noticed: Vec<_> = entity_view
+            .in_hex(hex)
+            .filter(|e| !self.can_see(e))
+            .filter(|e| {
+                let conspicuousness = e.conspicuousness().unwrap_or(1.0);
+                rng.random_bool((vision * conspicuousness * scale).clamp(0.0, 1.0))
+            })
+            .collect();
+        for entity in &noticed {
+            self.remember_spotted(&entity.entity_id);
+        }
+        noticed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        entity::{snapshot::EntitySnapshot, EntityAttributes, EntityItem},
+        mtch::test_match::test_player,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_props_must_be_noticed() {
+        let mut player = test_player("Looker", AxialHex::ZERO);
+        let pebble = Entity {
+            entity_id: Entity::id(),
+            name: "Pebble".into(),
+            attributes: EntityAttributes {
+                hex: Some(AxialHex::ZERO),
+                item: Some(EntityItem::default()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(!player.can_see(&pebble));
+
+        let snapshot = EntitySnapshot::new(vec![pebble.clone()]);
+        let view = snapshot.view();
+        let mut rng = crate::rng::rng();
+        let noticed = player.notice_props(AxialHex::ZERO, &view, 100.0, &mut rng);
+        assert_eq!(noticed.len(), 1);
+        assert!(player.can_see(&pebble));
+
+        // and we dont notice it again
+        assert!(player
+            .notice_props(AxialHex::ZERO, &view, 100.0, &mut rng)
+            .is_empty());
+    }
+}
@@ -1,10 +1,14 @@
-use rand::distr::{weighted::WeightedIndex, Distribution};
-
-use crate::entity::{
-    brain::{actor_action::ActorAction, focus::ActorFocus},
-    snapshot::EntityView,
-    world::EntityWorld,
-    Entity,
+use std::{cell::RefCell, collections::HashSet};
+
+use crate::{
+    entity::{
+        brain::{actor_action::ActorAction, focus::ActorFocus},
+        snapshot::EntityView,
+        world::EntityWorld,
+        Entity, EntityId,
+    },
+    mtch::{features::FeatureFlag, TickId},
+    rng::{audited_index, RollKind},
 };
 
 /// Information available when resolving a signal into actions
@@ -22,6 +26,19 @@ pub struct SignalContext<'a> {
 
     /// The current world state
     pub world_state: &'a EntityWorld,
+
+    /// The current tick
+    pub tick_id: TickId,
+
+    /// The experimental systems this match is played with
+    pub features: &'a HashSet<FeatureFlag>,
+}
+
+impl SignalContext<'_> {
+    /// Is some experimental system part of this match?
+    pub fn has_feature(&self, feature: FeatureFlag) -> bool {
+        self.features.contains(&feature)
+    }
 }
 
 /// Something that a player acts on -> can raise weighted actions
@@ -59,37 +76,102 @@ impl Signal for SignalRef<'_> {
 /// Actions and their weights as returned by a signal implementor
 #[derive(Debug, Clone, Default)]
 pub struct WeightedActorActions {
-    actions: Option<Vec<(usize, ActorAction)>>,
+    actions: Vec<(usize, ActorAction)>,
+}
+
+thread_local! {
+    /// Reused for every decision made on this thread (see `WeightedActorActions::with_buffer`)
+    static ACTIONS_BUFFER: RefCell<WeightedActorActions> =
+        const { RefCell::new(WeightedActorActions { actions: Vec::new() }) };
 }
 
 impl WeightedActorActions {
-    pub fn sample(mut self, rng: &mut impl rand::Rng) -> ActorAction {
+    /// Collect actions into this thread's buffer, rather than allocating a new one each decision
+    /// NOTE: not reentrant, nothing acting on a signal can use the buffer too
+    pub fn with_buffer<R>(f: impl FnOnce(&mut WeightedActorActions) -> R) -> R {
+        ACTIONS_BUFFER.with_borrow_mut(|actions| {
+            actions.actions.clear();
+            f(actions)
+        })
+    }
+
+    /// Pick one of the actions by weight, leaving none behind
+    /// (the entity is only used to audit the roll, see `crate::rng::audited_index`)
+    pub fn sample(
+        &mut self,
+        entity_id: Option<&EntityId>,
+        rng: &mut impl rand::Rng,
+    ) -> ActorAction {
         // Add no-op if no actions
-        if self.actions.is_none() {
+        if self.actions.is_empty() {
             self.add(1, ActorAction::Nothing);
         }
 
-        // Build the distribution
-        let (weights, actions): (Vec<_>, Vec<_>) = self.actions.unwrap().into_iter().unzip();
-        let dist = WeightedIndex::new(&weights).unwrap();
-
         // Sample the distribution
-        actions[dist.sample(rng)].clone()
+        let weights: Vec<_> = self.actions.iter().map(|(weight, _)| *weight).collect();
+        let index = audited_index(rng, RollKind::ActionSample, entity_id, &weights);
+        let (_, action) = self.actions.swap_remove(index);
+        self.actions.clear();
+        action
     }
 
     /// NOTE: I occasionally just use this for debugging
     #[allow(unused)]
     pub fn len(&self) -> usize {
-        self.actions.as_ref().map(|x| x.len()).unwrap_or_default()
+        self.actions.len()
     }
 }
 
 impl WeightedActorActions {
     pub fn add(&mut self, weight: usize, action: ActorAction) {
-        self.actions.get_or_insert_default().push((weight, action));
+        self.actions.push((weight, action));
     }
 
     pub fn extend(&mut self, actions: impl Iterator<Item = (usize, ActorAction)>) {
-        self.actions.get_or_insert_default().extend(actions);
+        self.actions.extend(actions);
+    }
+
+    /// Change the weight of each action, actions reweighted to 0 are dropped
+    pub fn reweight(&mut self, f: impl Fn(usize, &ActorAction) -> usize) {
+        for (weight, action) in self.actions.iter_mut() {
+            *weight = f(*weight, action);
+        }
+        self.actions.retain(|(weight, _)| *weight > 0);
+    }
+}
+
+/// Assertions for checking what a signal raised (see `brain::test_signal`)
+#[cfg(test)]
+impl WeightedActorActions {
+    /// The highest weight any action matching some predicate was raised with
+    pub fn max_weight_of(&self, predicate: impl Fn(&ActorAction) -> bool) -> Option<usize> {
+        self.actions
+            .iter()
+            .filter(|(_, action)| predicate(action))
+            .map(|(weight, _)| *weight)
+            .max()
+    }
+
+    #[track_caller]
+    pub fn assert_contains_action_weight_at_least(
+        &self,
+        min_weight: usize,
+        predicate: impl Fn(&ActorAction) -> bool,
+    ) {
+        let weight = self.max_weight_of(predicate);
+        assert!(
+            weight.is_some_and(|weight| weight >= min_weight),
+            "expected an action with weight at least {min_weight}, got {weight:?} in {:#?}",
+            self.actions
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_no_action(&self, predicate: impl Fn(&ActorAction) -> bool) {
+        assert!(
+            self.max_weight_of(predicate).is_none(),
+            "expected no such action in {:#?}",
+            self.actions
+        );
     }
 }
@@ -0,0 +1,158 @@
+//! Strenuous actions wear entities out
+//!
+//! Stamina isn't tracked on its own, it's derived from how tired and hungry an entity is.
+//! Actions cost stamina (by making the entity more tired) based on how much effort they take,
+//! and an exhausted entity can't manage anything strenuous at all.
+
+use crate::entity::{
+    brain::{
+        actor_action::ActorAction,
+        motivator::{self, MotivatorTable},
+        signal::WeightedActorActions,
+    },
+    snapshot::EntityView,
+    Entity,
+};
+
+/// Below this stamina, an entity is too exhausted for anything strenuous
+pub const EXHAUSTED_STAMINA: f32 = 0.2;
+
+/// How much effort an action takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Exertion {
+    /// Talking, eating, sleeping etc
+    None,
+
+    /// Searching, fishing etc
+    Light,
+
+    /// Getting around
+    Moderate,
+
+    /// Building, wrestling something off someone, hauling yourself out of a lake
+    Heavy,
+}
+
+impl Exertion {
+    /// How much more tired this makes us (before encumbrance)
+    fn tiredness_cost(&self) -> f32 {
+        match self {
+            Exertion::None => 0.0,
+            Exertion::Light => 0.1,
+            Exertion::Moderate => 0.3,
+            Exertion::Heavy => 0.5,
+        }
+    }
+
+    /// How much hungrier this makes us
+    fn hunger_cost(&self) -> f32 {
+        match self {
+            Exertion::Heavy => 0.2,
+            _ => 0.0,
+        }
+    }
+}
+
+impl ActorAction {
+    /// How much effort this action would take (at most)
+    pub fn exertion(&self) -> Exertion {
+        use ActorAction::*;
+        match self {
+            IgnoreResult(action) => action.exertion(),
+            Plan(plan) => plan
+                .steps()
+                .iter()
+                .map(|a| a.exertion())
+                .max()
+                .unwrap_or(Exertion::None),
+
+            Move(_)
+            | GoTowardsHex(_)
+            | GoTowards(..)
+            | GoToAdjacent(..)
+            | MoveAwayFrom(..)
+            | AvoidEntity { .. }
+            | TravelTogether
+            | SeekKnownShelter
+            | SeekKnownWaterSource
+            | FleeFrom { .. }
+            | FollowTracks { .. }
+            | PaddleRaft(_)
+            | Throw { .. } => Exertion::Moderate,
+
+            RepairShelter | RepairPod { .. } | StealFood { .. } | BuildRaft => Exertion::Heavy,
+
+            SearchContainer(_) | Fish | LightCampfire { .. } => Exertion::Light,
+
+            _ => Exertion::None,
+        }
+    }
+}
+
+impl MotivatorTable {
+    /// How much energy this entity has left for strenuous things
+    /// (0 -> exhausted, 1 -> fresh)
+    pub fn stamina(&self) -> f32 {
+        let tiredness = self
+            .get_motivation::<motivator::Tiredness>()
+            .unwrap_or_default();
+        let hunger = self
+            .get_motivation::<motivator::Hunger>()
+            .unwrap_or_default();
+        (1.0 - tiredness) * (1.0 - 0.5 * hunger)
+    }
+}
+
+impl Entity {
+    /// Pay the stamina cost of some effort
+    /// (carrying a lot makes everything harder)
+    pub fn exert(&mut self, exertion: Exertion, entity_view: &EntityView) {
+        let encumbrance = self.encumbrance(entity_view);
+        let motivators = &mut self.attributes.motivators;
+        motivators
+            .bump_scaled::<motivator::Tiredness>(exertion.tiredness_cost() * (1.0 + encumbrance));
+        motivators.bump_scaled::<motivator::Hunger>(exertion.hunger_cost());
+    }
+}
+
+impl WeightedActorActions {
+    /// Cut down to what an exhausted entity can still manage
+    /// nothing heavy, and getting around is a struggle
+    pub fn degrade_for_exhaustion(&mut self) {
+        self.reweight(|weight, action| match action.exertion() {
+            Exertion::Heavy => 0,
+            Exertion::Moderate => (weight / 2).max(1),
+            _ => weight,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::entity::EntityId;
+
+    use super::*;
+
+    #[test]
+    fn test_exhausted_cant_do_heavy_work() {
+        let mut motivators = MotivatorTable::initialise();
+        assert!(motivators.stamina() > EXHAUSTED_STAMINA);
+        motivators.bump_scaled::<motivator::Tiredness>(1000.0);
+        assert!(motivators.stamina() < EXHAUSTED_STAMINA);
+
+        let mut actions = WeightedActorActions::default();
+        actions.add(
+            100,
+            ActorAction::StealFood {
+                entity_id: EntityId::default(),
+            },
+        );
+        actions.add(1, ActorAction::Sleep);
+        actions.degrade_for_exhaustion();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            actions.sample(None, &mut crate::rng::rng()),
+            ActorAction::Sleep
+        ));
+    }
+}
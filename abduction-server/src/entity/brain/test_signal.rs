@@ -0,0 +1,261 @@
+//! Harness for testing signals on their own
+//!
+//! Acting on a signal needs a whole `SignalContext` (a view of every entity, the world state and so
+//! on), which is a lot to put together by hand just to check what one signal raises. An
+//! `EntityBuilder` makes the entities involved, a `SignalContextBuilder` holds everything the context
+//! borrows and acts on signals with it, and `WeightedActorActions` has a few assertions (see
+//! `assert_contains_action_weight_at_least`) for checking what came out.
+
+use std::collections::HashSet;
+
+use crate::{
+    entity::{
+        brain::{
+            actor_action::ActorAction,
+            characteristic::{Characteristic, CharacteristicStrength},
+            focus::ActorFocus,
+            motivator::MotivatorKey,
+            signal::{Signal, SignalContext, WeightedActorActions},
+        },
+        snapshot::EntitySnapshot,
+        world::EntityWorld,
+        Entity, EntityAttributes, EntityMarker,
+    },
+    hex::AxialHex,
+    mtch::{features::FeatureFlag, test_match::test_player, TickId},
+};
+
+/// Builds an entity for a test a bit at a time
+#[derive(Debug)]
+pub struct EntityBuilder {
+    entity: Entity,
+}
+
+impl EntityBuilder {
+    /// A bare-bones player (see `test_player`), calm and at the origin to start with
+    pub fn player(name: &str) -> Self {
+        Self {
+            entity: test_player(name, AxialHex::ZERO),
+        }
+    }
+
+    /// Something that isn't a player, with nothing to it yet
+    pub fn thing(name: &str) -> Self {
+        Self {
+            entity: Entity {
+                entity_id: Entity::id(),
+                name: name.into(),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn at(mut self, hex: AxialHex) -> Self {
+        self.entity.attributes.hex = Some(hex);
+        self
+    }
+
+    pub fn marker(mut self, marker: EntityMarker) -> Self {
+        if !self.entity.markers.contains(&marker) {
+            self.entity.markers.push(marker);
+        }
+        self
+    }
+
+    /// Set some motivator outright
+    /// (only players have motivators to set)
+    pub fn motivation(mut self, key: MotivatorKey, motivation: f32) -> Self {
+        assert!(
+            self.entity.attributes.motivators.set_key(key, motivation),
+            "{} has no {key:?} motivator",
+            self.entity.name
+        );
+        self
+    }
+
+    pub fn characteristic(
+        mut self,
+        characteristic: Characteristic,
+        strength: CharacteristicStrength,
+    ) -> Self {
+        self.entity
+            .attributes
+            .characteristics
+            .get_or_insert_default()
+            .insert(characteristic, strength);
+        self
+    }
+
+    pub fn focus(mut self, focus: ActorFocus) -> Self {
+        self.entity.attributes.focus = Some(focus);
+        self
+    }
+
+    /// Hold some other entity in our inventory
+    /// (it still needs adding to the context to be seen)
+    pub fn holding(mut self, item: &Entity) -> Self {
+        self.entity
+            .relations
+            .inventory_mut()
+            .insert(item.entity_id.clone());
+        self
+    }
+
+    /// Set up anything else about the entity
+    pub fn attributes(mut self, f: impl FnOnce(&mut EntityAttributes)) -> Self {
+        f(&mut self.entity.attributes);
+        self
+    }
+
+    pub fn build(self) -> Entity {
+        self.entity
+    }
+}
+
+/// Everything a `SignalContext` needs, for acting on signals in tests
+/// (by default the entity is unfocused, on the first tick of a match with no experimental systems)
+#[derive(Debug)]
+pub struct SignalContextBuilder {
+    entity: Entity,
+    others: Vec<Entity>,
+    focus: Option<ActorFocus>,
+    world_state: EntityWorld,
+    tick_id: TickId,
+    features: HashSet<FeatureFlag>,
+}
+
+impl SignalContextBuilder {
+    /// Act on signals as some entity
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            others: Vec::new(),
+            focus: None,
+            world_state: EntityWorld::default(),
+            tick_id: 0,
+            features: HashSet::new(),
+        }
+    }
+
+    /// Put some other entity in the world
+    pub fn with_entity(mut self, entity: Entity) -> Self {
+        self.others.push(entity);
+        self
+    }
+
+    /// Act with some focus
+    /// (otherwise uses the entity's own focus)
+    pub fn focus(mut self, focus: ActorFocus) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    pub fn world_state(mut self, world_state: EntityWorld) -> Self {
+        self.world_state = world_state;
+        self
+    }
+
+    pub fn tick(mut self, tick_id: TickId) -> Self {
+        self.tick_id = tick_id;
+        self
+    }
+
+    pub fn feature(mut self, feature: FeatureFlag) -> Self {
+        self.features.insert(feature);
+        self
+    }
+
+    /// Do something with the context
+    pub fn with_ctx<R>(&self, f: impl FnOnce(&SignalContext) -> R) -> R {
+        let snapshot = EntitySnapshot::new(
+            std::iter::once(self.entity.clone()).chain(self.others.iter().cloned()),
+        );
+        let view = snapshot.view();
+        let ctx = SignalContext {
+            entities: &view,
+            entity: &self.entity,
+            focus: self
+                .focus
+                .clone()
+                .or_else(|| self.entity.attributes.focus.clone())
+                .unwrap_or(ActorFocus::Unfocused),
+            world_state: &self.world_state,
+            tick_id: self.tick_id,
+            features: &self.features,
+        };
+        f(&ctx)
+    }
+
+    /// Every action some signal raises
+    pub fn act_on(&self, signal: &impl Signal) -> WeightedActorActions {
+        self.with_ctx(|ctx| {
+            let mut actions = WeightedActorActions::default();
+            signal.act_on(ctx, &mut actions);
+            actions
+        })
+    }
+
+    /// Every action the entity's motivators raise
+    /// (as they would be when deciding what to do, so only those that are active)
+    pub fn act_on_motivators(&self) -> WeightedActorActions {
+        self.with_ctx(|ctx| {
+            let mut actions = WeightedActorActions::default();
+            for signal in ctx.entity.attributes.motivators.as_signals(&ctx.focus) {
+                signal.act_on(ctx, &mut actions);
+            }
+            actions
+        })
+    }
+}
+
+/// Matches a plan with some name (e.g "seek food")
+pub fn is_plan(name: &str) -> impl Fn(&ActorAction) -> bool + '_ {
+    move |action| match action {
+        ActorAction::Plan(plan) => plan
+            .to_string()
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.starts_with(" (")),
+        _ => false,
+    }
+}
+
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_harness_sees_what_we_put_in_the_world() {
+        let food = EntityBuilder::thing("Apple")
+            .at(AxialHex::ZERO)
+            .attributes(|attributes| attributes.food = Some(Default::default()))
+            .build();
+        let player = EntityBuilder::player("Hungry")
+            .holding(&food)
+            .motivation(MotivatorKey::Hunger, 0.5)
+            .build();
+
+        let ctx = SignalContextBuilder::new(player.clone()).with_entity(food.clone());
+        ctx.with_ctx(|ctx| {
+            assert_eq!(ctx.focus, ActorFocus::Unfocused);
+            assert_eq!(ctx.entity.entity_id, player.entity_id);
+            assert_eq!(ctx.entities.in_hex(AxialHex::ZERO).count(), 2);
+            assert!(ctx
+                .entity
+                .resolve_inventory(ctx.entities)
+                .any(|e| e.entity_id == food.entity_id));
+        });
+
+        let actions = ctx.act_on_motivators();
+        actions.assert_contains_action_weight_at_least(10, is_plan("eat"));
+        actions.assert_no_action(is_plan("eat anything"));
+
+        // Entities act with their own focus, unless told otherwise
+        let asleep = ActorFocus::Sleeping { remaining_turns: 3 };
+        let sleeper = EntityBuilder::player("Sleeper")
+            .focus(asleep.clone())
+            .build();
+        let ctx = SignalContextBuilder::new(sleeper);
+        assert_eq!(ctx.with_ctx(|ctx| ctx.focus.clone()), asleep);
+        let ctx = ctx.focus(ActorFocus::Unfocused);
+        assert_eq!(ctx.with_ctx(|ctx| ctx.focus.clone()), ActorFocus::Unfocused);
+    }
+}
@@ -0,0 +1,79 @@
+//! Throwing things at things
+//!
+//! How far something can be thrown depends on strength, and how likely it is to land where it was
+//! meant to depends on aim (and how far away the target is). Whatever gets thrown always ends up
+//! in the hex it was thrown at, hitting someone on the way or not.
+
+use crate::{
+    entity::{
+        brain::characteristic::{Characteristic, CharacteristicStrength},
+        snapshot::EntityView,
+        Entity, EntityId,
+    },
+    hex::AxialHex,
+};
+
+/// How much worse our aim gets for each hex away the target is
+const AIM_FALLOFF_PER_HEX: f64 = 0.15;
+
+/// How much a hit hurts (per unit of damage, see `Entity::thrown_damage`)
+pub const THROW_HURT_SCALE: f32 = 4.0;
+
+/// What something is being thrown at
+#[derive(Debug, Clone)]
+#[allow(unused)]
+pub enum ThrowTarget {
+    Hex(AxialHex),
+    Entity(EntityId),
+}
+
+impl Entity {
+    /// How many hexes away we can throw something
+    pub fn throw_range(&self) -> isize {
+        match self.characteristic(Characteristic::Strength) {
+            CharacteristicStrength::Low => 1,
+            CharacteristicStrength::Average => 2,
+            CharacteristicStrength::High => 3,
+        }
+    }
+
+    /// Chance of hitting something some distance away
+    /// (before they get a chance to dodge)
+    pub fn throw_accuracy(&self, distance: isize) -> f64 {
+        let aim = self.characteristic(Characteristic::Aim).success_chance();
+        (aim - AIM_FALLOFF_PER_HEX * distance as f64).max(0.05)
+    }
+
+    /// How much this hurts when it hits someone
+    /// (heavier things hurt more, some tools are made for it)
+    pub fn thrown_damage(&self) -> f32 {
+        let heft = self.attributes.item.as_ref().map_or(0, |item| item.heft);
+        let bonus = self
+            .attributes
+            .tool
+            .as_ref()
+            .map_or(0.0, |tool| tool.kind.throw_bonus());
+        heft as f32 + bonus
+    }
+
+    /// The best thing we are carrying to throw at someone
+    pub fn best_throwable<'a>(&'a self, entity_view: &'a EntityView<'a>) -> Option<&'a Entity> {
+        self.resolve_inventory(entity_view)
+            .filter(|e| e.attributes.food.is_none() && e.attributes.healing.is_none())
+            .max_by(|a, b| a.thrown_damage().total_cmp(&b.thrown_damage()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mtch::test_match::test_player;
+
+    use super::*;
+
+    #[test]
+    fn test_aim_falls_off_with_distance() {
+        let thrower = test_player("Thrower", AxialHex::ZERO);
+        assert!(thrower.throw_accuracy(0) > thrower.throw_accuracy(thrower.throw_range()));
+        assert!(thrower.throw_accuracy(100) > 0.0);
+    }
+}
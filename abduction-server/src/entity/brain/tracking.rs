@@ -0,0 +1,53 @@
+//! Beings leave tracks behind them as they move around
+//!
+//! Tracks are short-lived entities left in the hex someone moved out of, pointing the way they went.
+//! Anyone sharp-eyed enough can follow them to go after someone, though they fade after a while
+//! (and much faster in the rain).
+
+use crate::{
+    entity::{brain::characteristic::Characteristic, Entity, EntityAttributes, EntityTrack},
+    has_markers,
+    hex::{AxialHex, AxialHexDirection},
+};
+
+/// How many ticks tracks last for
+const TRACK_FRESH_TICKS: usize = 10;
+
+/// Chance each tick of rain washing some tracks away
+pub const TRACK_RAIN_WASH_CHANCE: f64 = 0.2;
+
+impl Entity {
+    /// Do we leave tracks when we move?
+    /// (the crew know how to cover theirs)
+    pub fn leaves_tracks(&self) -> bool {
+        has_markers!(self, Being) && !has_markers!(self, Crew)
+    }
+
+    /// The tracks we leave behind in some hex, when moving off in some direction
+    pub fn make_tracks(&self, hex: AxialHex, direction: AxialHexDirection) -> Entity {
+        Entity {
+            entity_id: Entity::id(),
+            name: format!("{}'s tracks", self.name),
+            attributes: EntityAttributes {
+                hex: Some(hex),
+                track: Some(EntityTrack {
+                    entity_id: self.entity_id.clone(),
+                    direction,
+                    fresh_for: TRACK_FRESH_TICKS,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Chance of making out which way some tracks go
+    pub fn track_reading_chance(&self, raining: bool) -> f64 {
+        let vision = self.characteristic(Characteristic::Vision).success_chance();
+        if raining {
+            vision / 2.0
+        } else {
+            vision
+        }
+    }
+}
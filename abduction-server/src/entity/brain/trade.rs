@@ -0,0 +1,55 @@
+use itertools::Itertools;
+
+use crate::entity::{brain::motivator, snapshot::EntityView, Entity, EntityId};
+
+/// How much a trade has to be worth to us before we'll propose it
+const MIN_TRADE_GAIN: f32 = 0.2;
+
+/// How much our bond with someone makes us willing to take a worse deal
+const BOND_TRADE_GENEROSITY: f32 = 0.5;
+
+impl Entity {
+    /// How much we'd value having some item, based on what we need right now
+    pub fn value_of_item(&self, item: &Entity) -> f32 {
+        let motivators = &self.attributes.motivators;
+
+        let mut value = 0.5;
+        if item.attributes.food.is_some() {
+            let hunger = motivators.get_motivation::<motivator::Hunger>();
+            value += 0.5 + hunger.unwrap_or_default() * 3.0;
+        }
+        if let Some(healing) = &item.attributes.healing {
+            let hurt = motivators.get_motivation::<motivator::Hurt>();
+            value += healing.quality + hurt.unwrap_or_default() * 3.0;
+        }
+        if item.attributes.tool.is_some() {
+            value += 1.0;
+        }
+        value
+    }
+
+    /// Would we give up `want` (which we have) for `offer` (which they have)?
+    /// the more we like them, the worse a deal we're willing to take
+    pub fn would_accept_trade(&self, offer: &Entity, want: &Entity, from: &EntityId) -> bool {
+        let gain = self.value_of_item(offer) - self.value_of_item(want);
+        gain + self.relations.bond(from) * BOND_TRADE_GENEROSITY >= 0.0
+    }
+
+    /// Find the trade with some other entity that would most benefit us
+    /// returns (what we'd offer, what we want)
+    pub fn best_trade_with<'a>(
+        &'a self,
+        other: &'a Entity,
+        entity_view: &'a EntityView<'a>,
+    ) -> Option<(&'a Entity, &'a Entity)> {
+        self.resolve_inventory(entity_view)
+            .cartesian_product(other.resolve_inventory(entity_view).collect_vec())
+            .map(|(offer, want)| {
+                let gain = self.value_of_item(want) - self.value_of_item(offer);
+                (offer, want, gain)
+            })
+            .filter(|(_, _, gain)| *gain > MIN_TRADE_GAIN)
+            .max_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+            .map(|(offer, want, _)| (offer, want))
+    }
+}
@@ -4,14 +4,18 @@ use rand::{
 };
 use strum::VariantArray;
 
-use crate::entity::{
-    background::{career::Career, fear::Fear, hope::Hope, EntityBackground},
-    generate::random_city_country_pair,
+use crate::{
+    content::content,
+    entity::{
+        background::{career::Career, fear::Fear, hope::Hope, EntityBackground},
+        generate::random_city_country_pair,
+    },
 };
 
 impl EntityBackground {
     pub fn random_for_age(rng: &mut impl rand::Rng, age: usize) -> Self {
         let (city, country) = random_city_country_pair().unwrap();
+        let content = content();
         Self {
             country_name: country,
             city_name: city,
@@ -24,12 +28,8 @@ impl EntityBackground {
             is_retired: rng.random_bool(is_retired_response(age)),
 
             // sample from distributions
-            eye_colour: sample_from_weighted_pairs(rng, EYE_COLOR_WEIGHTS)
-                .unwrap()
-                .to_string(),
-            hair_colour: sample_from_weighted_pairs(rng, HAIR_COLOR_WEIGHTS)
-                .unwrap()
-                .to_string(),
+            eye_colour: sample_from_weighted_pairs(rng, &content.eye_colour_weights()).unwrap(),
+            hair_colour: sample_from_weighted_pairs(rng, &content.hair_colour_weights()).unwrap(),
         }
     }
 }
@@ -47,6 +47,7 @@ fn is_retired_response(age: usize) -> f64 {
     numer / denom
 }
 
+/// NOTE: only used when the content file doesn't specify eye colours
 pub const EYE_COLOR_WEIGHTS: &[(usize, &str)] = &[
     (40, "brown"),
     (30, "blue"),
@@ -57,6 +58,7 @@ pub const EYE_COLOR_WEIGHTS: &[(usize, &str)] = &[
     (1, "red"),
 ];
 
+/// NOTE: only used when the content file doesn't specify hair colours
 pub const HAIR_COLOR_WEIGHTS: &[(usize, &str)] = &[
     (30, "black"),
     (12, "dark brown"),
@@ -1,13 +1,35 @@
-use crate::entity::{Entity, EntityAttributes, EntityFood, EntityItem, EntityMarker};
+use rand::seq::IndexedRandom;
+
+use crate::{
+    content::content,
+    entity::{
+        Entity, EntityAttributes, EntityFood, EntityItem, EntityKeepsake, EntityMarker,
+        EntityRemains,
+    },
+};
+
+/// Little things someone might have had in their pocket when they were taken
+const TRINKETS: &[&str] = &[
+    "photograph",
+    "keyring",
+    "lucky coin",
+    "locket",
+    "bus ticket",
+    "friendship bracelet",
+];
 
 pub fn generate_corpse(rng: &mut impl rand::Rng, player: Entity) -> Entity {
-    // TODO
     Entity {
         entity_id: Entity::id(),
         markers: vec![EntityMarker::Inspectable],
         name: format!("Corpse of {}", &player.name),
         attributes: EntityAttributes {
             hex: player.attributes.hex,
+            remains: Some(EntityRemains {
+                summary: summarise(&player),
+                name: player.name,
+            }),
+            display_color_hue: player.attributes.display_color_hue,
             corpse: Some(player.entity_id),
             item: Some(EntityItem { heft: 4 }),
             food: Some(EntityFood {
@@ -19,3 +41,62 @@ pub fn generate_corpse(rng: &mut impl rand::Rng, player: Entity) -> Entity {
         ..Default::default()
     }
 }
+
+/// The things a player had on them that say who they were
+/// (a letter about what they were hoping for, and some trinket from home)
+/// these go with their corpse, along with everything else they were carrying
+pub fn generate_personal_effects(rng: &mut impl rand::Rng, player: &Entity) -> Vec<Entity> {
+    let Some(background) = &player.attributes.background else {
+        return Vec::new();
+    };
+    let first_name = player
+        .attributes
+        .first_name
+        .clone()
+        .unwrap_or_else(|| player.name.clone());
+    let keepsake = |name: String, description: String| Entity {
+        entity_id: Entity::id(),
+        name,
+        attributes: EntityAttributes {
+            item: Some(EntityItem::default()),
+            keepsake: Some(EntityKeepsake {
+                owner: player.entity_id.clone(),
+                description,
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let trinket = TRINKETS.choose(rng).unwrap();
+    vec![
+        keepsake(
+            format!("Letter from {first_name}"),
+            format!(
+                "a letter home, about how they hope {}",
+                content().hope_text(&background.hope)
+            ),
+        ),
+        keepsake(
+            format!("{first_name}'s {trinket}"),
+            format!("a {trinket} from {}", background.city_name),
+        ),
+    ]
+}
+
+/// A few words on who someone was (i.e "a retired nurse from Lyon, France")
+fn summarise(player: &Entity) -> String {
+    let Some(background) = &player.attributes.background else {
+        return "someone nobody knew much about".to_owned();
+    };
+    let retired = if background.is_retired {
+        "retired "
+    } else {
+        ""
+    };
+    format!(
+        "a {retired}{} from {}",
+        background.career,
+        background.location_string()
+    )
+}
@@ -1,8 +1,14 @@
 pub mod background;
 pub mod corpse;
 pub mod player;
+pub mod pod;
 pub mod prop;
+pub mod spirit;
+pub mod world;
 
 pub use corpse::*;
 pub use player::*;
+pub use pod::*;
 pub use prop::*;
+pub use spirit::*;
+pub use world::*;
@@ -1,15 +1,17 @@
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use rand::prelude::*;
 use std::collections::HashMap;
 #[cfg(not(test))]
 use std::env;
 use std::fs::{self, File};
 use std::io::{BufReader, Read, Seek};
-use std::sync::LazyLock;
+use std::sync::{LazyLock, Once};
 use std::{io::SeekFrom, os::unix::fs::MetadataExt, path::PathBuf};
 use strum::IntoEnumIterator;
+use tracing::warn;
 
 use crate::create_markers;
+use crate::entity::appearance::EntityAppearance;
 use crate::entity::background::EntityBackground;
 use crate::entity::brain::characteristic::{Characteristic, CharacteristicStrength};
 use crate::entity::brain::meme::MemeTable;
@@ -18,17 +20,102 @@ use crate::entity::{Entity, EntityAttributes};
 use crate::hex::AxialHex;
 
 #[cfg(test)]
-static PLAYER_DATA_DIR: LazyLock<PathBuf> =
-    LazyLock::new(|| "../gather-player-data/output/".into());
+static PLAYER_DATA_DIR: LazyLock<Option<PathBuf>> =
+    LazyLock::new(|| Some("../gather-player-data/output/".into()));
 
 #[cfg(not(test))]
-static PLAYER_DATA_DIR: LazyLock<PathBuf> =
-    LazyLock::new(|| env::var("PLAYER_DATA_PATH").unwrap().into());
+static PLAYER_DATA_DIR: LazyLock<Option<PathBuf>> =
+    LazyLock::new(|| env::var("PLAYER_DATA_PATH").ok().map(PathBuf::from));
+
+/// So we only warn about falling back to the embedded data once
+static FALLBACK_WARNING: Once = Once::new();
+
+/// The files of player data (see `gather-player-data`)
+///
+/// A small version of each is compiled in, which is used if the real ones can't be read
+/// so a misconfigured deployment still makes (less varied) players rather than crashing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter)]
+pub enum PlayerDataFile {
+    YoungNames,
+    MatureNames,
+    OldNames,
+    FamilyNames,
+    Cities,
+}
+
+impl PlayerDataFile {
+    fn file_name(&self) -> &'static str {
+        match self {
+            PlayerDataFile::YoungNames => "young.txt",
+            PlayerDataFile::MatureNames => "mature.txt",
+            PlayerDataFile::OldNames => "old.txt",
+            PlayerDataFile::FamilyNames => "family_names.txt",
+            PlayerDataFile::Cities => "cities.txt",
+        }
+    }
+
+    fn embedded(&self) -> &'static str {
+        match self {
+            PlayerDataFile::YoungNames => include_str!("fallback_player_data/young.txt"),
+            PlayerDataFile::MatureNames => include_str!("fallback_player_data/mature.txt"),
+            PlayerDataFile::OldNames => include_str!("fallback_player_data/old.txt"),
+            PlayerDataFile::FamilyNames => include_str!("fallback_player_data/family_names.txt"),
+            PlayerDataFile::Cities => include_str!("fallback_player_data/cities.txt"),
+        }
+    }
+
+    /// Where this file should be, if the data dir is configured at all
+    pub fn path(&self) -> Option<PathBuf> {
+        PLAYER_DATA_DIR
+            .as_ref()
+            .map(|dir| dir.join(self.file_name()))
+    }
+
+    /// Get a random line from this file, falling back to the embedded data if it can't be read
+    pub fn random_line(&self) -> anyhow::Result<String> {
+        let from_file = self
+            .path()
+            .ok_or(anyhow!("`PLAYER_DATA_PATH` is not set"))
+            .and_then(|path| random_line_from_text_file(&path));
+        match from_file {
+            Ok(line) if !line.is_empty() => Ok(line),
+            result => {
+                FALLBACK_WARNING.call_once(|| {
+                    let reason = result
+                        .err()
+                        .map_or("file is empty".to_owned(), |err| format!("{err:?}"));
+                    warn!("Falling back to embedded player data: {reason}");
+                });
+                self.embedded_random_line()
+            }
+        }
+    }
 
-static FAMILY_NAMES_PATH: LazyLock<PathBuf> =
-    LazyLock::new(|| PLAYER_DATA_DIR.join("family_names.txt"));
+    fn embedded_random_line(&self) -> anyhow::Result<String> {
+        let mut rng = crate::rng::rng();
+        self.embedded()
+            .lines()
+            .filter(|line| !line.is_empty())
+            .choose(&mut rng)
+            .map(str::to_owned)
+            .ok_or(anyhow!("No embedded data for {self:?}"))
+    }
+}
 
-static CITIES_PATH: LazyLock<PathBuf> = LazyLock::new(|| PLAYER_DATA_DIR.join("cities.txt"));
+/// Check that every player data file is where it should be and isn't empty
+/// (if not, players will be generated from the embedded data instead)
+pub fn validate_player_data() -> anyhow::Result<()> {
+    for data_file in PlayerDataFile::iter() {
+        let path = data_file
+            .path()
+            .ok_or(anyhow!("`PLAYER_DATA_PATH` is not set"))?;
+        let metadata = fs::metadata(&path).context(format!("Reading player data {path:?}"))?;
+        if metadata.size() == 0 {
+            bail!("Player data {path:?} is empty");
+        }
+    }
+    Ok(())
+}
 
 // Player gen constants
 const PLAYER_AGE_RANGE: std::ops::Range<usize> = 18..100;
@@ -37,14 +124,14 @@ const PLAYER_AGE_RANGE: std::ops::Range<usize> = 18..100;
 /// (Returns but does not save to DB)
 pub fn generate_player() -> anyhow::Result<Entity> {
     // Generate an age / class
-    let mut rng = rand::rng();
+    let mut rng = crate::rng::rng();
     let age = rng.random_range(PLAYER_AGE_RANGE);
     let age_class = AgeClass::from(age);
 
     // Generate an age appropriate name
     // TODO: could add other things like infix letters "* P. * " or suffix titles "Jr" "Sr" etc
     let first_name = age_class.get_random_first_name()?;
-    let family_name = random_line_from_text_file(&FAMILY_NAMES_PATH)?;
+    let family_name = PlayerDataFile::FamilyNames.random_line()?;
     let player_name = format!("{first_name} {family_name}");
 
     // FUTURE: {
@@ -85,6 +172,12 @@ pub fn generate_player() -> anyhow::Result<Entity> {
     // Generate a background
     attributes.background = Some(EntityBackground::random_for_age(&mut rng, age));
 
+    // And a look to go with it
+    attributes.appearance = Some(EntityAppearance::random(
+        &mut rng,
+        attributes.background.as_ref(),
+    ));
+
     // I want to feed this man some memes!
     // (For players we just default initialise this so that its always readable)
     attributes.memes = Some(MemeTable::default());
@@ -130,9 +223,20 @@ pub fn generate_player() -> anyhow::Result<Entity> {
     Ok(player_entity)
 }
 
+/// Give a player a new (age appropriate) name
+pub fn regenerate_player_name(player: &mut Entity) -> anyhow::Result<()> {
+    let age = player.attributes.age.context("Player has no age")?;
+    let first_name = AgeClass::from(age).get_random_first_name()?;
+    let family_name = PlayerDataFile::FamilyNames.random_line()?;
+    player.name = format!("{first_name} {family_name}");
+    player.attributes.first_name = Some(first_name);
+    player.attributes.family_name = Some(family_name);
+    Ok(())
+}
+
 /// get a random (city, country) pair from the player data
 pub fn random_city_country_pair() -> anyhow::Result<(String, String)> {
-    let line = random_line_from_text_file(&CITIES_PATH)?;
+    let line = PlayerDataFile::Cities.random_line()?;
     let (city, country) = line
         .split_once(":")
         .ok_or(anyhow!("Malformed city/country line '{line}'"))?;
@@ -145,7 +249,7 @@ pub fn random_city_country_pair() -> anyhow::Result<(String, String)> {
 ///       at odd times, not during game running
 pub fn random_line_from_text_file(path: &PathBuf) -> anyhow::Result<String> {
     // Need a source of randomness
-    let mut rng = rand::rng();
+    let mut rng = crate::rng::rng();
 
     // Figure out how large the file is
     // then get a random byte offset
@@ -184,18 +288,17 @@ enum AgeClass {
 }
 
 impl AgeClass {
-    pub fn get_names_path(&self) -> PathBuf {
+    pub fn names_data_file(&self) -> PlayerDataFile {
         match self {
-            AgeClass::Young => PLAYER_DATA_DIR.join("young.txt"),
-            AgeClass::Mature => PLAYER_DATA_DIR.join("mature.txt"),
-            AgeClass::Old => PLAYER_DATA_DIR.join("old.txt"),
+            AgeClass::Young => PlayerDataFile::YoungNames,
+            AgeClass::Mature => PlayerDataFile::MatureNames,
+            AgeClass::Old => PlayerDataFile::OldNames,
         }
     }
 
     /// Get a random first name that is reasonable for this age range
     pub fn get_random_first_name(&self) -> anyhow::Result<String> {
-        let path = self.get_names_path();
-        random_line_from_text_file(&path)
+        self.names_data_file().random_line()
     }
 }
 
@@ -220,8 +323,18 @@ mod test {
 
     #[test]
     fn test_random_line() {
-        let line = random_line_from_text_file(&FAMILY_NAMES_PATH);
+        let line = random_line_from_text_file(&PlayerDataFile::FamilyNames.path().unwrap());
         assert!(line.is_ok());
         assert!(!line.unwrap().is_empty());
     }
+
+    #[test]
+    fn test_embedded_player_data() {
+        for data_file in PlayerDataFile::iter() {
+            assert!(!data_file.embedded_random_line().unwrap().is_empty());
+        }
+        for line in PlayerDataFile::Cities.embedded().lines() {
+            assert!(line.split_once(':').is_some());
+        }
+    }
 }
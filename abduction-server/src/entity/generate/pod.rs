@@ -0,0 +1,70 @@
+use rand::seq::{IndexedRandom, SliceRandom};
+use strum::VariantArray;
+
+use crate::{
+    create_markers,
+    entity::{Entity, EntityAttributes, EntityEscapePod, EntityItem, PodPart},
+    hex::AxialHex,
+};
+
+/// How many escape pods are scattered about a world
+const MIN_ESCAPE_PODS: usize = 1;
+const MAX_ESCAPE_PODS: usize = 2;
+
+/// How many parts each escape pod is missing
+const PARTS_MISSING: usize = 2;
+
+/// Some broken escape pods scattered around a world, along with the parts they are missing
+/// (each part is left lying somewhere else, so fixing a pod means going looking)
+pub fn generate_escape_pods(world_radius: isize, rng: &mut impl rand::Rng) -> Vec<Entity> {
+    let hexes = AxialHex::all_in_bounds(world_radius);
+    let pod_count = rng.random_range(MIN_ESCAPE_PODS..=MAX_ESCAPE_PODS);
+
+    let mut entities = Vec::new();
+    for _ in 0..pod_count {
+        let mut missing = PodPart::VARIANTS.to_vec();
+        missing.shuffle(rng);
+        missing.truncate(PARTS_MISSING);
+
+        let Some(pod_hex) = hexes.choose(rng) else {
+            break;
+        };
+        for part in &missing {
+            let part_hex = hexes.choose(rng).copied().unwrap_or(*pod_hex);
+            entities.push(generate_pod_part(*part, part_hex));
+        }
+        entities.push(Entity {
+            entity_id: Entity::id(),
+            name: String::from("Broken escape pod"),
+            markers: create_markers!(Inspectable),
+            attributes: EntityAttributes {
+                hex: Some(*pod_hex),
+                escape_pod: Some(EntityEscapePod { missing }),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+
+    entities
+}
+
+/// A part for an escape pod, left lying at some hex
+pub fn generate_pod_part(part: PodPart, hex: AxialHex) -> Entity {
+    Entity {
+        entity_id: Entity::id(),
+        name: String::from(match part {
+            PodPart::FuelCell => "Fuel cell",
+            PodPart::Thruster => "Thruster",
+            PodPart::NavComputer => "Navigation computer",
+            PodPart::HatchSeal => "Hatch seal",
+        }),
+        attributes: EntityAttributes {
+            hex: Some(hex),
+            item: Some(EntityItem::default()),
+            pod_part: Some(part),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
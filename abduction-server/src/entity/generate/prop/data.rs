@@ -172,3 +172,79 @@ pub const NATURAL_SHELTER: &[&str] = &[
     "large tree",
     "dirt embankment",
 ];
+
+pub const ABANDONED_CRATE: &[&str] = &[
+    "abandoned crate",
+    "battered crate",
+    "rotting crate",
+    "dented supply box",
+    "rusted footlocker",
+];
+
+pub const CRATE_QUALIFIER: &[&str] = &["wooden", "metal", "splintered", "water-stained"];
+
+pub const HOLLOW_LOG: &[&str] = &["hollow log", "rotting log", "fallen trunk", "hollow stump"];
+
+pub const KNIFE: &[&str] = &[
+    "pocket knife",
+    "hunting knife",
+    "kitchen knife",
+    "rusty blade",
+    "switchblade",
+];
+
+pub const FISHING_ROD: &[&str] = &[
+    "fishing rod",
+    "bamboo fishing pole",
+    "old fishing rod",
+    "makeshift rod and line",
+];
+
+pub const SPEAR: &[&str] = &[
+    "spear",
+    "sharpened stick",
+    "fishing spear",
+    "bone-tipped spear",
+];
+
+pub const HEALING_HERB: &[&str] = &[
+    "yarrow",
+    "comfrey",
+    "plantain leaves",
+    "calendula",
+    "arnica",
+    "wild aloe",
+    "feverfew",
+];
+
+pub const BUILDING_MATERIAL: &[&str] = &[
+    "bundle of branches",
+    "pile of sticks",
+    "armful of reeds",
+    "stack of bark",
+    "coil of vines",
+];
+
+pub const WARM_CLOTHING: &[&str] = &[
+    "woolly jumper",
+    "fleece",
+    "knitted scarf",
+    "thick coat",
+    "moth-eaten blanket",
+    "beanie",
+];
+
+pub const WATERPROOF_CLOTHING: &[&str] = &[
+    "raincoat",
+    "plastic poncho",
+    "oilskin jacket",
+    "bin bag cape",
+];
+
+pub const PREDATOR: &[&str] = &[
+    "wild boar",
+    "grey wolf",
+    "mountain lion",
+    "black bear",
+    "hungry hyena",
+];
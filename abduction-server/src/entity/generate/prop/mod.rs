@@ -7,12 +7,18 @@ use rand::seq::IndexedRandom;
 
 use crate::{
     create_markers,
-    entity::{Entity, EntityAttributes, EntityFood, EntityItem, EntityWaterSource},
+    entity::{
+        appearance::EntityAppearance, brain::rafting::RAFT_CAPACITY, Entity, EntityAttributes,
+        EntityClothing, EntityContainer, EntityFood, EntityHealing, EntityItem, EntityMaterial,
+        EntityPredator, EntityRaft, EntityShelter, EntityTool, EntityWaterSource, ToolKind,
+    },
+    hex::AxialHex,
 };
 
 /// These are different generators that can create types of props
 /// locations can be associated with prop generators to seed the world in this locations
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, strum::EnumString)]
+#[strum(serialize_all = "snake_case")]
 pub enum PropGenerator {
     /// Food that you might find in nature,
     NaturalFood,
@@ -20,13 +26,13 @@ pub enum PropGenerator {
     /// A lake
     Lake,
 
-    /// Fish that can be found in a large water source
+    /// A freshly caught fish, only comes from fishing at a lake
     Fish,
 
-    /// A naturally occuring infinite source of water, guaranteed to be high quality
+    /// A naturally occuring (small) source of water, guaranteed to be high quality
     QualityNaturalWaterSource,
 
-    /// A naturally occuring infinite source of water, potentially causing sickness
+    /// A naturally occuring (small) source of water, potentially causing sickness
     DubiousNaturalWaterSource,
 
     /// A naturally occuring place to shelter
@@ -34,9 +40,57 @@ pub enum PropGenerator {
 
     /// Food found in nature that might be poisonous
     PossiblyPoisonousFood,
-    // TODO: fish, wildlife etc (they are different because must be "caught" to become food)
+
+    /// A crate left lying around, may have some items in it
+    AbandonedCrate,
+
+    /// A cache of items hidden in a log, harder to search than a crate
+    HollowLog,
+
+    /// A medkit, only found in containers
+    Medkit,
+
+    /// Some plants that can be used to treat wounds, but not as well as a medkit
+    HealingHerb,
+
+    /// Sticks, reeds etc that can be used to repair a shelter
+    BuildingMaterial,
+
+    /// A knife, only found in containers
+    Knife,
+
+    /// A fishing rod, sometimes left lying by a lake
+    FishingRod,
+
+    /// A spear, only found in containers
+    Spear,
+
+    /// Something warm to wear, only found in containers
+    WarmClothing,
+
+    /// Something to keep the rain off, only found in containers
+    WaterproofClothing,
+
+    /// A crate of supplies dropped in by the production (when the viewers vote for it)
+    SupplyDrop,
+
+    /// A wild animal that hunts the players at night (when the viewers vote for it)
+    Predator,
+
+    /// A fire someone lit to sit around, only made by players (see `brain::gathering`)
+    Campfire,
+
+    /// A raft lashed together from building material, only made by players (see `brain::rafting`)
+    Raft,
+    // TODO: wildlife etc (they are different because must be "caught" to become food)
 }
 
+/// How many ticks a freshly caught fish lasts before it spoils
+const FISH_FRESH_TICKS: usize = 120;
+
+/// How many drinks a natural water source holds when full
+const NATURAL_WATER_SOURCE_DRINKS: std::ops::Range<f32> = 10.0..30.0;
+
 pub fn capitalize(s: &str) -> String {
     format!("{}{}", &s[0..1].to_uppercase(), &s[1..])
 }
@@ -93,10 +147,93 @@ impl PropGenerator {
                 )
             }
             PropGenerator::NaturalShelter => String::from(*choice!(rng, NATURAL_SHELTER)),
+            PropGenerator::AbandonedCrate => {
+                format!(
+                    "{} {}",
+                    choice!(rng, CRATE_QUALIFIER),
+                    choice!(rng, ABANDONED_CRATE)
+                )
+            }
+            PropGenerator::HollowLog => String::from(*choice!(rng, HOLLOW_LOG)),
+            PropGenerator::Medkit => String::from("medkit"),
+            PropGenerator::HealingHerb => String::from(*choice!(rng, HEALING_HERB)),
+            PropGenerator::BuildingMaterial => String::from(*choice!(rng, BUILDING_MATERIAL)),
+            PropGenerator::Knife => String::from(*choice!(rng, KNIFE)),
+            PropGenerator::FishingRod => String::from(*choice!(rng, FISHING_ROD)),
+            PropGenerator::Spear => String::from(*choice!(rng, SPEAR)),
+            PropGenerator::WarmClothing => String::from(*choice!(rng, WARM_CLOTHING)),
+            PropGenerator::WaterproofClothing => String::from(*choice!(rng, WATERPROOF_CLOTHING)),
+            PropGenerator::SupplyDrop => String::from("supply drop"),
+            PropGenerator::Predator => String::from(*choice!(rng, PREDATOR)),
+            PropGenerator::Campfire => String::from("campfire"),
+            PropGenerator::Raft => String::from("raft"),
+        }
+    }
+
+    /// Generate this prop at a location, along with anything it contains
+    /// (contained entities are banished and held in the props inventory)
+    pub fn generate_at(&self, location: AxialHex, rng: &mut impl rand::Rng) -> Vec<Entity> {
+        let mut entity = self.generate(rng);
+        entity.attributes.hex = Some(location);
+
+        let contents = self.generate_contents(rng);
+        for content in &contents {
+            entity
+                .relations
+                .inventory_mut()
+                .insert(content.entity_id.clone());
+        }
+
+        std::iter::once(entity).chain(contents).collect()
+    }
+
+    /// Generate the items held by this prop (if its a container)
+    pub fn generate_contents(&self, rng: &mut impl rand::Rng) -> Vec<Entity> {
+        match self {
+            PropGenerator::AbandonedCrate | PropGenerator::HollowLog => {
+                let count = rng.random_range(0..=2);
+                (0..count)
+                    .map(|_| {
+                        // Mostly food but occasionally something really valuable
+                        let generator = if rng.random_bool(0.1) {
+                            PropGenerator::Medkit
+                        } else if rng.random_bool(0.05) {
+                            PropGenerator::Knife
+                        } else if rng.random_bool(0.05) {
+                            PropGenerator::Spear
+                        } else if rng.random_bool(0.08) {
+                            PropGenerator::WarmClothing
+                        } else if rng.random_bool(0.05) {
+                            PropGenerator::WaterproofClothing
+                        } else {
+                            PropGenerator::NaturalFood
+                        };
+                        generator.generate(rng)
+                    })
+                    .collect()
+            }
+            PropGenerator::SupplyDrop => [
+                PropGenerator::Medkit,
+                PropGenerator::NaturalFood,
+                PropGenerator::NaturalFood,
+                PropGenerator::WarmClothing,
+            ]
+            .iter()
+            .map(|generator| generator.generate(rng))
+            .collect(),
+            _ => vec![],
         }
     }
 
     pub fn generate(&self, rng: &mut impl rand::Rng) -> Entity {
+        let mut entity = self.generate_kind(rng);
+
+        // Props of the same kind don't all look exactly alike
+        entity.attributes.appearance = Some(EntityAppearance::random(rng, None));
+        entity
+    }
+
+    fn generate_kind(&self, rng: &mut impl rand::Rng) -> Entity {
         match self {
             PropGenerator::NaturalFood | PropGenerator::PossiblyPoisonousFood => Entity {
                 entity_id: Entity::id(),
@@ -118,15 +255,18 @@ impl PropGenerator {
                     entity_id: Entity::id(),
                     name: capitalize(&self.name(rng)),
                     attributes: EntityAttributes {
-                        water_source: Some(match self {
-                            PropGenerator::QualityNaturalWaterSource => {
-                                EntityWaterSource::quality()
-                            }
-                            PropGenerator::DubiousNaturalWaterSource => {
-                                EntityWaterSource::dubious(rng)
+                        water_source: Some(
+                            match self {
+                                PropGenerator::QualityNaturalWaterSource => {
+                                    EntityWaterSource::quality()
+                                }
+                                PropGenerator::DubiousNaturalWaterSource => {
+                                    EntityWaterSource::dubious(rng)
+                                }
+                                _ => unreachable!(),
                             }
-                            _ => unreachable!(),
-                        }),
+                            .limited(rng.random_range(NATURAL_WATER_SOURCE_DRINKS)),
+                        ),
                         ..Default::default()
                     },
                     ..Default::default()
@@ -137,12 +277,10 @@ impl PropGenerator {
                 entity_id: Entity::id(),
                 name: capitalize(&self.name(rng)),
                 attributes: EntityAttributes {
-                    // TODO: in future it may be required to catch fish instead
                     item: Some(EntityItem::default()),
-                    food: Some(EntityFood::healthy(rng)),
+                    food: Some(EntityFood::fresh(rng, FISH_FRESH_TICKS)),
                     ..Default::default()
                 },
-                markers: create_markers!(Being), // fish are alive
                 ..Default::default()
             },
 
@@ -161,6 +299,149 @@ impl PropGenerator {
                 name: capitalize(&self.name(rng)),
                 markers: create_markers!(Shelter),
                 attributes: EntityAttributes {
+                    shelter: Some(EntityShelter {
+                        durability: rng.random_range(0.6..=1.0),
+                        claimed_by: None,
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::BuildingMaterial => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                attributes: EntityAttributes {
+                    item: Some(EntityItem::default()),
+                    material: Some(EntityMaterial {
+                        strength: rng.random_range(0.2..0.4),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::SupplyDrop => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                markers: create_markers!(Inspectable),
+                attributes: EntityAttributes {
+                    container: Some(EntityContainer { hidden: false }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::Predator => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                markers: create_markers!(Inspectable, Being, Animal),
+                attributes: EntityAttributes {
+                    predator: Some(EntityPredator::default()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            // (unlike a wildfire, it's no hazard)
+            PropGenerator::Campfire => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                markers: create_markers!(Fire, Inspectable),
+                ..Default::default()
+            },
+
+            PropGenerator::Raft => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                markers: create_markers!(Inspectable),
+                attributes: EntityAttributes {
+                    raft: Some(EntityRaft {
+                        capacity: RAFT_CAPACITY,
+                        owner: None,
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::AbandonedCrate | PropGenerator::HollowLog => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                // Crates are out in the open, but hollow logs just look like logs
+                markers: match self {
+                    PropGenerator::AbandonedCrate => create_markers!(Inspectable),
+                    _ => vec![],
+                },
+                attributes: EntityAttributes {
+                    container: Some(EntityContainer {
+                        hidden: matches!(self, PropGenerator::HollowLog),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::Medkit => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                attributes: EntityAttributes {
+                    item: Some(EntityItem::default()),
+                    healing: Some(EntityHealing {
+                        quality: rng.random_range(0.8..1.0),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::HealingHerb => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                attributes: EntityAttributes {
+                    item: Some(EntityItem::default()),
+                    healing: Some(EntityHealing {
+                        quality: rng.random_range(0.1..0.4),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::Knife | PropGenerator::FishingRod | PropGenerator::Spear => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                attributes: EntityAttributes {
+                    item: Some(EntityItem::default()),
+                    tool: Some(EntityTool {
+                        kind: match self {
+                            PropGenerator::Knife => ToolKind::Knife,
+                            PropGenerator::FishingRod => ToolKind::FishingRod,
+                            PropGenerator::Spear => ToolKind::Spear,
+                            _ => unreachable!(),
+                        },
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+
+            PropGenerator::WarmClothing | PropGenerator::WaterproofClothing => Entity {
+                entity_id: Entity::id(),
+                name: capitalize(&self.name(rng)),
+                attributes: EntityAttributes {
+                    item: Some(EntityItem::default()),
+                    clothing: Some(match self {
+                        PropGenerator::WarmClothing => EntityClothing {
+                            warmth: rng.random_range(0.3..0.7),
+                            waterproofing: rng.random_range(0.0..0.2),
+                        },
+                        PropGenerator::WaterproofClothing => EntityClothing {
+                            warmth: rng.random_range(0.0..0.2),
+                            waterproofing: rng.random_range(0.5..0.9),
+                        },
+                        _ => unreachable!(),
+                    }),
                     ..Default::default()
                 },
                 ..Default::default()
@@ -0,0 +1,22 @@
+use crate::entity::{Entity, EntityAttributes, EntitySpirit};
+
+/// The spirit of a player who just died, left drifting where they fell
+pub fn generate_spirit(player: &Entity) -> Entity {
+    Entity {
+        entity_id: Entity::id(),
+        name: format!("Spirit of {}", &player.name),
+        attributes: EntityAttributes {
+            hex: player.attributes.hex,
+            spirit: Some(EntitySpirit {
+                player: player.entity_id.clone(),
+                hope: player
+                    .attributes
+                    .background
+                    .as_ref()
+                    .map(|bg| bg.hope.clone()),
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
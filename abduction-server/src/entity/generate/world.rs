@@ -0,0 +1,14 @@
+use crate::entity::{world::EntityWorld, Entity, EntityAttributes};
+
+/// The entity holding the state of the world, as it is at the start of a match
+pub fn generate_world() -> Entity {
+    Entity {
+        entity_id: Entity::id(),
+        name: "World".into(),
+        attributes: EntityAttributes {
+            world: Some(EntityWorld::default()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
@@ -0,0 +1,119 @@
+//! Getting at what makes an entity some kind of thing
+//!
+//! Actions are chosen against the world as it was and resolved against the world as it is, so the
+//! entity an action was about might not be what it was any more (eaten, picked up, gone). Rather
+//! than unwrapping the attribute they need, actions ask for it with one of these accessors and the
+//! action is skipped (see `Entity::resolve_action`) if the entity isn't that kind of thing.
+
+use std::fmt;
+
+use crate::{
+    entity::{
+        background::EntityBackground, brain::meme::MemeTable, snapshot::EntityView, Entity,
+        EntityClothing, EntityFood, EntityId, EntityItem, EntityWaterSource,
+    },
+    hex::AxialHex,
+};
+
+/// Some entity isn't (or is no longer) what it was expected to be
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityKindError {
+    /// There's no entity with that id
+    Missing { entity_id: EntityId },
+
+    /// The entity exists, but isn't that kind of thing
+    NotA {
+        entity_id: EntityId,
+        kind: &'static str,
+    },
+}
+
+impl fmt::Display for EntityKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntityKindError::Missing { entity_id } => write!(f, "there is no entity {entity_id}"),
+            EntityKindError::NotA { entity_id, kind } => write!(f, "{entity_id} is not {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for EntityKindError {}
+
+impl Entity {
+    fn expect_kind<'a, T>(
+        &self,
+        attribute: Option<&'a T>,
+        kind: &'static str,
+    ) -> Result<&'a T, EntityKindError> {
+        attribute.ok_or_else(|| EntityKindError::NotA {
+            entity_id: self.entity_id.clone(),
+            kind,
+        })
+    }
+
+    pub fn as_food(&self) -> Result<&EntityFood, EntityKindError> {
+        self.expect_kind(self.attributes.food.as_ref(), "food")
+    }
+
+    pub fn as_water_source(&self) -> Result<&EntityWaterSource, EntityKindError> {
+        self.expect_kind(self.attributes.water_source.as_ref(), "a water source")
+    }
+
+    pub fn as_item(&self) -> Result<&EntityItem, EntityKindError> {
+        self.expect_kind(self.attributes.item.as_ref(), "an item")
+    }
+
+    pub fn as_clothing(&self) -> Result<&EntityClothing, EntityKindError> {
+        self.expect_kind(self.attributes.clothing.as_ref(), "clothing")
+    }
+
+    pub fn as_background(&self) -> Result<&EntityBackground, EntityKindError> {
+        self.expect_kind(
+            self.attributes.background.as_ref(),
+            "someone with a background",
+        )
+    }
+
+    pub fn as_memes(&self) -> Result<&MemeTable, EntityKindError> {
+        self.expect_kind(self.attributes.memes.as_ref(), "someone with memories")
+    }
+
+    /// Where the entity is
+    pub fn located(&self) -> Result<AxialHex, EntityKindError> {
+        self.expect_kind(self.attributes.hex.as_ref(), "anywhere")
+            .copied()
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// Some entity that has to exist
+    pub fn require(&'a self, entity_id: &EntityId) -> Result<&'a Entity, EntityKindError> {
+        self.by_id(entity_id)
+            .ok_or_else(|| EntityKindError::Missing {
+                entity_id: entity_id.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::entity::{generate::generate_pod_part, snapshot::EntitySnapshot, PodPart};
+
+    #[test]
+    fn test_wrong_kind_is_an_error() {
+        let part = generate_pod_part(PodPart::Thruster, AxialHex::ZERO);
+        assert!(part.as_item().is_ok());
+        assert_eq!(
+            part.as_food().map(|_| ()),
+            Err(EntityKindError::NotA {
+                entity_id: part.entity_id.clone(),
+                kind: "food",
+            })
+        );
+
+        let snapshot = EntitySnapshot::new(vec![part.clone()]);
+        assert!(snapshot.view().require(&part.entity_id).is_ok());
+        assert!(snapshot.view().require(&"gone".into()).is_err());
+    }
+}
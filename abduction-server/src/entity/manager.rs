@@ -1,18 +1,20 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{hash_map::Entry, HashMap, VecDeque},
     ops::Deref,
+    sync::Arc,
 };
 
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use sqlx::{query_file_as, types::Json};
+use sqlx::{query_file_as, types::Json, SqliteExecutor};
 use tokio::sync::broadcast;
-use tracing::{debug, info};
+use tracing::{debug, info, warn, Span};
 
 use super::{Entity, EntityId};
 use crate::{
-    entity::EntityPayload,
-    mtch::{MatchId, TickEvent},
+    entity::{store::MutationStore, EntityPayload},
+    mtch::{MatchId, TickEvent, TickId},
     Db,
 };
 
@@ -37,6 +39,16 @@ pub enum EntityManagerMutation {
     RemoveEntity { entity_id: EntityId },
 }
 
+impl EntityManagerMutation {
+    /// The entity being mutated
+    pub fn entity_id(&self) -> &EntityId {
+        match self {
+            EntityManagerMutation::SetEntity { entity } => &entity.entity_id,
+            EntityManagerMutation::RemoveEntity { entity_id } => entity_id,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "text")]
 pub enum EntityMutationType {
@@ -55,11 +67,16 @@ pub struct EntityMutation {
     match_id: String,
     mutation_type: EntityMutationType,
     payload: Option<EntityPayload>,
+
+    /// The tick of the match this mutation happened during
+    #[serde(default)]
+    tick_id: TickId,
 }
 
 impl EntityMutation {
     pub fn from_entity_manager_mutation(
         match_id: &MatchId,
+        tick_id: TickId,
         mutation: EntityManagerMutation,
     ) -> Self {
         match mutation {
@@ -68,34 +85,187 @@ impl EntityMutation {
                 match_id: match_id.clone(),
                 mutation_type: EntityMutationType::Set,
                 payload: Some(entity.into()),
+                tick_id,
             },
             EntityManagerMutation::RemoveEntity { entity_id } => Self {
                 entity_id,
                 match_id: match_id.clone(),
                 mutation_type: EntityMutationType::Delete,
                 payload: None,
+                tick_id,
             },
         }
     }
+
+    /// The change as clients would have been sent it
+    /// (none for a set without a payload, which shouldn't happen, or one that can't be read)
+    pub fn into_entity_manager_mutation(self) -> Option<EntityManagerMutation> {
+        match (self.mutation_type, self.payload) {
+            (EntityMutationType::Set, Some(payload)) => {
+                match payload.convert_to_entity(self.entity_id) {
+                    Ok(entity) => Some(EntityManagerMutation::SetEntity { entity }),
+                    Err(err) => {
+                        warn!("Skipping unreadable entity mutation: {err:#}");
+                        None
+                    }
+                }
+            }
+            (EntityMutationType::Set, None) => None,
+            (EntityMutationType::Delete, _) => Some(EntityManagerMutation::RemoveEntity {
+                entity_id: self.entity_id,
+            }),
+        }
+    }
+
+    fn from_row(match_id: &MatchId, row: MutationRow) -> Self {
+        Self {
+            entity_id: row.entity_id,
+            match_id: match_id.clone(),
+            mutation_type: row.mutation_type,
+            payload: row.payload.and_then(|payload| payload.0),
+            tick_id: row.tick_id as TickId,
+        }
+    }
+
+    /// Load every mutation made in a match, oldest first
+    pub async fn load_from_match(match_id: &MatchId, db: &Db) -> anyhow::Result<Vec<Self>> {
+        let rows = query_file_as!(MutationRow, "queries/get_match_mutations.sql", match_id)
+            .fetch_all(db)
+            .await
+            .context("Failed to load match mutations")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self::from_row(match_id, row))
+            .collect())
+    }
+
+    /// Load the mutations made in a match after tick `after` up to and including tick `up_to`, oldest first
+    pub async fn load_between(
+        match_id: &MatchId,
+        after: Option<TickId>,
+        up_to: TickId,
+        db: &Db,
+    ) -> anyhow::Result<Vec<Self>> {
+        let after = after.map(|tick_id| tick_id as i64).unwrap_or(-1);
+        let up_to = up_to as i64;
+        let rows = query_file_as!(
+            MutationRow,
+            "queries/get_match_mutations_between.sql",
+            match_id,
+            after,
+            up_to
+        )
+        .fetch_all(db)
+        .await
+        .context("Failed to load match mutations")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self::from_row(match_id, row))
+            .collect())
+    }
+
+    /// Apply this mutation to a set of entities
+    pub fn apply_to(&self, entities: &mut HashMap<EntityId, EntityPayload>) {
+        match (&self.mutation_type, &self.payload) {
+            (EntityMutationType::Set, Some(payload)) => {
+                entities.insert(self.entity_id.clone(), payload.clone());
+            }
+            (EntityMutationType::Set, None) => {
+                warn!("Set mutation for {} has no payload", self.entity_id);
+            }
+            (EntityMutationType::Delete, _) => {
+                entities.remove(&self.entity_id);
+            }
+        }
+    }
+
+    /// Persist this mutation to the DB
+    pub async fn save(&self, db: impl SqliteExecutor<'_>) -> anyhow::Result<()> {
+        let payload = Json(&self.payload);
+        let tick_id = self.tick_id as i64;
+        sqlx::query_file!(
+            "queries/add_match_mutation.sql",
+            self.entity_id,
+            self.match_id,
+            self.mutation_type,
+            payload,
+            tick_id,
+        )
+        .execute(db)
+        .await
+        .context("Failed to persist entity mutation to DB")?;
+
+        Ok(())
+    }
 }
 
+/// The kinds of entity there should only ever be one of in a match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Singleton {
+    /// Holds the state of the whole world (weather etc)
+    World,
+
+    /// The show's presenter
+    Presenter,
+
+    /// The presenter's co-host, who collects the dead
+    Collector,
+}
+
+impl Singleton {
+    /// Which singleton some entity is, if any
+    pub fn of(entity: &Entity) -> Option<Self> {
+        let attributes = &entity.attributes;
+        if attributes.world.is_some() {
+            Some(Singleton::World)
+        } else if attributes.presenter.is_some() {
+            Some(Singleton::Presenter)
+        } else if attributes.collector.is_some() {
+            Some(Singleton::Collector)
+        } else {
+            None
+        }
+    }
+}
+
+/// How many of the most recent mutations to keep for each entity (in debug builds)
+/// so that when something looks wrong we can see how it got that way
+const MUTATION_TRAIL_LENGTH: usize = 5;
+
 /// Loads entities and manages updating them FOR A GIVEN MATCH
 ///
 /// # UPDATING ENTITIES
 /// In particular, when an entity is mutated somehow,
-/// it is immediately updated in this structs state and we store
-/// a mutation to be sent to clients/the db at the end of the tick
+/// it is immediately updated in this structs state.
+/// At the end of the tick, each entity that isn't the same `Arc` it was last flushed as
+/// is sent to clients/the db as it is then, along with any that were removed.
+///
+/// Entities are kept behind an `Arc` so snapshots can share them rather than cloning everything
+///
+/// # SINGLETONS
+/// There is only ever one of each `Singleton` (the world etc), trying to add a second is an error.
+/// If old data has duplicates, the oldest is kept and the rest are removed when loading.
+#[derive(Clone)]
 pub struct EntityManager {
     /// The match id
     match_id: MatchId,
 
     /// Map from entity id to entity
     /// (Note that entity object also has an id)
-    entities: HashMap<EntityId, Entity>,
+    entities: HashMap<EntityId, Arc<Entity>>,
+
+    /// Each entity as it was when it was last flushed
+    /// (anything not still the same `Arc` has been changed since)
+    flushed: HashMap<EntityId, Arc<Entity>>,
 
-    /// Waiting mutations for flush
-    /// (its a queue so we can do optimisations like removing a set for an entity that was also deleted)
-    pending_mutations: VecDeque<EntityManagerMutation>,
+    /// The last few flushed mutations for each entity
+    /// (only kept in debug builds)
+    mutation_trails: HashMap<EntityId, VecDeque<EntityManagerMutation>>,
+
+    /// Which entity is each singleton
+    singletons: HashMap<Singleton, EntityId>,
 }
 
 impl EntityManager {
@@ -103,20 +273,45 @@ impl EntityManager {
         Self {
             match_id: match_id.clone(),
             entities: HashMap::default(),
-            pending_mutations: Default::default(),
+            flushed: Default::default(),
+            mutation_trails: Default::default(),
+            singletons: Default::default(),
         }
     }
 
     pub fn get_entity(&self, entity_id: &EntityId) -> Option<Entity> {
-        self.entities.get(entity_id).cloned()
+        self.entities
+            .get(entity_id)
+            .map(|entity| entity.as_ref().clone())
     }
 
     pub fn get_all_entities(&self) -> impl Iterator<Item = &Entity> {
-        self.entities.values()
+        self.entities.values().map(Arc::as_ref)
+    }
+
+    /// Get the one entity of some singleton kind, if there is one
+    pub fn singleton(&self, singleton: Singleton) -> Option<&Entity> {
+        let entity_id = self.singletons.get(&singleton)?;
+        self.entities.get(entity_id).map(Arc::as_ref)
+    }
+
+    /// The entity holding the state of the world
+    pub fn world(&self) -> Option<&Entity> {
+        self.singleton(Singleton::World)
+    }
+
+    /// The show's presenter
+    pub fn presenter(&self) -> Option<&Entity> {
+        self.singleton(Singleton::Presenter)
+    }
+
+    /// Get every entity without cloning them
+    /// (they're shared until they're next changed, which is cheap to snapshot)
+    pub fn share_all_entities(&self) -> impl Iterator<Item = Arc<Entity>> + '_ {
+        self.entities.values().cloned()
     }
 
     /// Static method which gets entities but does not save them against a manager
-    #[allow(unused)]
     pub async fn load_entities_from_match(
         match_id: &MatchId,
         db: &Db,
@@ -130,8 +325,14 @@ impl EntityManager {
         .await
         .unwrap()
         .into_iter()
-        .map(|AggregatedEntities { entity_id, entity }| {
-            entity.unwrap().deref().clone().convert_to_entity(entity_id)
+        .filter_map(|AggregatedEntities { entity_id, entity }| {
+            entity
+                .unwrap()
+                .deref()
+                .clone()
+                .convert_to_entity(entity_id)
+                .inspect_err(|err| warn!("Skipping unreadable entity: {err:#}"))
+                .ok()
         })
     }
 
@@ -148,25 +349,85 @@ impl EntityManager {
         .unwrap()
         .into_iter()
         .for_each(|AggregatedEntities { entity_id, entity }| {
-            loaded += 1;
-            self.entities.insert(
-                entity_id.clone(),
-                entity.unwrap().deref().clone().convert_to_entity(entity_id),
-            );
+            match entity
+                .unwrap()
+                .deref()
+                .clone()
+                .convert_to_entity(entity_id.clone())
+            {
+                Ok(entity) => {
+                    loaded += 1;
+                    self.entities.insert(entity_id, Arc::new(entity));
+                }
+                Err(err) => warn!("Skipping unreadable entity: {err:#}"),
+            }
         });
 
         info!("Loaded {} entities", loaded);
+        self.flushed = self.entities.clone();
+        self.repair_singletons();
+    }
+
+    /// Register the singletons, removing any duplicates
+    /// (keeping the oldest, as ids are time ordered)
+    fn repair_singletons(&mut self) {
+        self.singletons.clear();
+        let mut duplicates = Vec::new();
+        for entity in self
+            .entities
+            .values()
+            .sorted_by(|a, b| a.entity_id.cmp(&b.entity_id))
+        {
+            let Some(singleton) = Singleton::of(entity) else {
+                continue;
+            };
+            match self.singletons.entry(singleton) {
+                Entry::Occupied(_) => duplicates.push((singleton, entity.entity_id.clone())),
+                Entry::Vacant(entry) => {
+                    entry.insert(entity.entity_id.clone());
+                }
+            }
+        }
+
+        for (singleton, entity_id) in duplicates {
+            warn!("Removing duplicate {singleton:?} entity {entity_id}");
+            self.entities.remove(&entity_id);
+        }
+    }
+
+    /// Make sure an entity isn't a second of some singleton, and register it if it is one
+    fn register_singleton(
+        &mut self,
+        entity_id: &EntityId,
+        singleton: Option<Singleton>,
+    ) -> anyhow::Result<()> {
+        if let Some(singleton) = singleton {
+            if let Some(existing_id) = self.singletons.get(&singleton) {
+                if existing_id != entity_id {
+                    bail!(
+                        "Can't add {entity_id} as there is already a {singleton:?} ({existing_id})"
+                    );
+                }
+            }
+        }
+
+        // (it might have stopped being one)
+        self.singletons
+            .retain(|kind, id| id != entity_id || Some(*kind) == singleton);
+        if let Some(singleton) = singleton {
+            self.singletons.insert(singleton, entity_id.clone());
+        }
+        Ok(())
     }
 
     /// Update or create a new entity
     pub fn upsert_entity(&mut self, entity: Entity) -> anyhow::Result<()> {
+        // There can only be one of some things
+        self.register_singleton(&entity.entity_id, Singleton::of(&entity))?;
+
         // Upsert that an entity
         self.entities
-            .insert(entity.entity_id.clone(), entity.clone());
-
-        // Store a mutation for later
-        self.pending_mutations
-            .push_back(EntityManagerMutation::SetEntity { entity });
+            .insert(entity.entity_id.clone(), Arc::new(entity));
 
         Ok(())
     }
@@ -183,44 +444,62 @@ impl EntityManager {
             .ok_or(anyhow!("No such entity"))?;
 
         // Update it
-        let mut entity_updated = entity.clone();
-        mutate(&mut entity_updated);
-        self.upsert_entity(entity_updated)?;
+        // (only cloning it if it's still shared, with a snapshot or as it was last flushed)
+        let entity = Arc::make_mut(entity);
+        mutate(entity);
+        let singleton = Singleton::of(entity);
 
-        Ok(())
+        // NOTE: checked after the fact, so a change that makes it a duplicate singleton is still kept
+        self.register_singleton(entity_id, singleton)
     }
 
     #[allow(unused)]
     pub fn remove_entity(&mut self, entity_id: &EntityId) -> anyhow::Result<()> {
         // Remove that an entity
         self.entities.remove(entity_id);
-
-        // Store a mutation for later
-        self.pending_mutations
-            .push_back(EntityManagerMutation::RemoveEntity {
-                entity_id: entity_id.clone(),
-            });
+        self.singletons.retain(|_, id| id != entity_id);
 
         Ok(())
     }
 
+    /// The last few flushed mutations to an entity, oldest first
+    /// NOTE: always empty in release builds
+    pub fn mutation_trail(
+        &self,
+        entity_id: &EntityId,
+    ) -> impl Iterator<Item = &EntityManagerMutation> {
+        self.mutation_trails
+            .get(entity_id)
+            .into_iter()
+            .flat_map(|trail| trail.iter())
+    }
+
     pub async fn flush_changes(
         &mut self,
         tick_tx: &broadcast::Sender<TickEvent>,
-        db: &Db,
-    ) -> anyhow::Result<()> {
-        // If there are no changes, we dont need to do anything
-        if self.pending_mutations.is_empty() {
-            return Ok(());
-        }
-
-        // Otherwise, drain them all
-        let pending_mutations: Vec<_> = self.pending_mutations.drain(0..).collect();
+        tick_id: TickId,
+        store: &impl MutationStore,
+    ) -> anyhow::Result<usize> {
+        let pending_mutations = self.take_pending_mutations();
         let mutation_count = pending_mutations.len();
+        Span::current().record("mutations", mutation_count);
+        if pending_mutations.is_empty() {
+            return Ok(0);
+        }
 
-        // TODO: de-dupe mutations affecting the same entity
-        //   - If the last op was a `D` -> dont send the initial sets, its just deleted
-        //   - If multiple sets for an entity, only keep the last one
+        // Keep track of how entities got to where they are
+        if cfg!(debug_assertions) {
+            for mutation in &pending_mutations {
+                let trail = self
+                    .mutation_trails
+                    .entry(mutation.entity_id().clone())
+                    .or_default();
+                trail.push_back(mutation.clone());
+                if trail.len() > MUTATION_TRAIL_LENGTH {
+                    trail.pop_front();
+                }
+            }
+        }
 
         // Send changes to clients
         // TODO: we could do JSON diffs here perhaps...
@@ -229,25 +508,82 @@ impl EntityManager {
         })?;
 
         // Add changes to DB
-        for mutation in pending_mutations {
-            let mutation = EntityMutation::from_entity_manager_mutation(&self.match_id, mutation);
-            let payload = Json(mutation.payload);
-
-            sqlx::query_file!(
-                "queries/add_match_mutation.sql",
-                mutation.entity_id,
-                mutation.match_id,
-                mutation.mutation_type,
-                payload,
-            )
-            .execute(db)
-            .await
-            .context("Failed to persist entity mutation to DB")?;
-        }
+        self.save_mutations(pending_mutations, tick_id, store)
+            .await?;
 
         debug!("Flushed {mutation_count} pending mutation(s)");
+        Ok(mutation_count)
+    }
+
+    /// Save changes to entities to the DB without sending them to clients
+    /// (i.e for a match that isn't being played yet)
+    pub async fn save_changes(
+        &mut self,
+        tick_id: TickId,
+        store: &impl MutationStore,
+    ) -> anyhow::Result<()> {
+        let pending_mutations = self.take_pending_mutations();
+        self.save_mutations(pending_mutations, tick_id, store).await
+    }
+
+    async fn save_mutations(
+        &self,
+        mutations: Vec<EntityManagerMutation>,
+        tick_id: TickId,
+        store: &impl MutationStore,
+    ) -> anyhow::Result<()> {
+        for mutation in mutations {
+            let mutation =
+                EntityMutation::from_entity_manager_mutation(&self.match_id, tick_id, mutation);
+            store.save_mutation(&mutation).await?;
+        }
         Ok(())
     }
+
+    /// Forget which entities changed since the last flush, without flushing them
+    /// (the changes are still made, they're just never sent or saved)
+    pub fn discard_changes(&mut self) {
+        self.flushed = self.entities.clone();
+    }
+
+    /// Work out which entities were changed (or removed) since the last flush
+    /// (in id order, which is the order they were made in)
+    fn take_pending_mutations(&mut self) -> Vec<EntityManagerMutation> {
+        let mut changed = self
+            .entities
+            .iter()
+            .filter(|(entity_id, entity)| {
+                self.flushed
+                    .get(*entity_id)
+                    .is_none_or(|flushed| !Arc::ptr_eq(flushed, entity))
+            })
+            .map(|(entity_id, entity)| (entity_id.clone(), Some(entity.clone())))
+            .collect_vec();
+        changed.extend(
+            self.flushed
+                .keys()
+                .filter(|entity_id| !self.entities.contains_key(*entity_id))
+                .map(|entity_id| (entity_id.clone(), None)),
+        );
+        changed.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        changed
+            .into_iter()
+            .map(|(entity_id, entity)| match entity {
+                Some(entity) => {
+                    let mutation = EntityManagerMutation::SetEntity {
+                        entity: entity.as_ref().clone(),
+                    };
+                    self.flushed.insert(entity_id, entity);
+                    mutation
+                }
+                None => {
+                    self.flushed.remove(&entity_id);
+                    EntityManagerMutation::RemoveEntity { entity_id }
+                }
+            })
+            .collect()
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -255,3 +591,113 @@ struct AggregatedEntities {
     entity_id: EntityId,
     entity: Option<Json<EntityPayload>>,
 }
+
+#[derive(sqlx::FromRow)]
+struct MutationRow {
+    entity_id: EntityId,
+    mutation_type: EntityMutationType,
+    payload: Option<Json<Option<EntityPayload>>>,
+    tick_id: i64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::generate::generate_world,
+        hex::AxialHex,
+        mtch::{
+            test_match::{test_player, TestMatch},
+            MatchManager, TickEvent,
+        },
+    };
+
+    #[tokio::test]
+    async fn test_untouched_entities_arent_flushed() {
+        let builder = TestMatch::builder();
+        let player = test_player("Untouched", AxialHex::ZERO);
+        let player_id = player.entity_id.clone();
+        let mut test_match = builder.with_entity(player).build().await;
+        let ctx = test_match.ctx().clone();
+        let mut tick_rx = ctx.tick_tx.subscribe();
+        let entities = &mut test_match.manager.entities;
+
+        // Snapshots share entities without changing them
+        let _snapshot = entities.share_all_entities().collect_vec();
+        entities
+            .flush_changes(&ctx.tick_tx, 1, &ctx.db)
+            .await
+            .unwrap();
+        assert!(tick_rx.try_recv().is_err());
+
+        entities
+            .mutate(&player_id, |player| player.name = "Changed".into())
+            .unwrap();
+        entities
+            .flush_changes(&ctx.tick_tx, 2, &ctx.db)
+            .await
+            .unwrap();
+        assert!(matches!(
+            tick_rx.try_recv(),
+            Ok(TickEvent::EntityChanges { changes }) if changes.len() == 1
+        ));
+
+        entities.remove_entity(&player_id).unwrap();
+        entities
+            .flush_changes(&ctx.tick_tx, 3, &ctx.db)
+            .await
+            .unwrap();
+        assert!(matches!(
+            tick_rx.try_recv(),
+            Ok(TickEvent::EntityChanges { changes }) if matches!(
+                changes.as_slice(),
+                [EntityManagerMutation::RemoveEntity { entity_id }] if *entity_id == player_id
+            )
+        ));
+        entities
+            .flush_changes(&ctx.tick_tx, 4, &ctx.db)
+            .await
+            .unwrap();
+        assert!(tick_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_only_one_world() {
+        let mut test_match = TestMatch::builder().build().await;
+        let world_id = test_match
+            .manager
+            .entities
+            .world()
+            .unwrap()
+            .entity_id
+            .clone();
+        assert!(test_match
+            .manager
+            .entities
+            .upsert_entity(generate_world())
+            .is_err());
+
+        // (but if there are two from old data, the newer one goes)
+        let config = test_match.manager.config.clone();
+        EntityMutation::from_entity_manager_mutation(
+            &config.match_id,
+            0,
+            EntityManagerMutation::SetEntity {
+                entity: generate_world(),
+            },
+        )
+        .save(&test_match.ctx().db)
+        .await
+        .unwrap();
+        let reloaded = MatchManager::load_match(config, &test_match.ctx().db).await;
+        assert_eq!(reloaded.entities.world().unwrap().entity_id, world_id);
+        assert_eq!(
+            reloaded
+                .entities
+                .get_all_entities()
+                .filter(|e| e.attributes.world.is_some())
+                .count(),
+            1
+        );
+    }
+}
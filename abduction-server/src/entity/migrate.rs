@@ -0,0 +1,217 @@
+//! Keeping old entity payloads readable as entities change shape
+//!
+//! Entities are saved as JSON (on each mutation, and in snapshots), so when a field of
+//! `EntityAttributes` changes shape, payloads saved before then can no longer be read. To keep old
+//! matches loading, payloads are saved with the version of the shape they were saved in, and
+//! whenever one is read it's first brought up to date by the migrations in `MIGRATIONS`.
+//!
+//! When changing the shape of an entity (in a way serde defaults can't paper over), add a migration
+//! to the end of `MIGRATIONS` that rewrites a payload from the previous shape to the new one. It
+//! works on the raw JSON, as the old shape no longer exists to deserialize into. Stored payloads can
+//! then be rewritten ahead of time with `abduction-server migrate-entities`, so they don't have to be
+//! migrated every time they're read.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use serde_json::{json, Map, Value};
+use sqlx::{query_file, query_file_as, types::Json};
+use tracing::info;
+
+use crate::{
+    entity::{EntityId, EntityPayload},
+    Db,
+};
+
+/// A migration of a payload's fields from one version to the next
+type Migration = fn(&mut Map<String, Value>) -> anyhow::Result<()>;
+
+/// Every migration, in order (the first migrates version 0 to version 1 etc)
+const MIGRATIONS: &[Migration] = &[sleeping_is_a_focus];
+
+/// The version of the payload shape entities are saved in now
+pub const ENTITY_PAYLOAD_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// How many mutations are rewritten at a time by `migrate_entities`
+const MIGRATE_BATCH_SIZE: i64 = 1000;
+
+impl EntityPayload {
+    /// Bring this payload up to the current version
+    /// (returns whether anything needed doing)
+    pub fn migrate(&mut self) -> anyhow::Result<bool> {
+        let from = self.version as usize;
+        let pending = MIGRATIONS.get(from..).ok_or(anyhow!(
+            "Entity payload is version {from}, newer than this server knows about"
+        ))?;
+        for (version, migration) in pending.iter().enumerate() {
+            migration(&mut self.fields).with_context(|| {
+                format!("Migrating entity payload from version {}", from + version)
+            })?;
+        }
+        self.version = ENTITY_PAYLOAD_VERSION;
+        Ok(!pending.is_empty())
+    }
+}
+
+/// 0 -> 1: sleeping used to be an `asleep` attribute, rather than a focus
+fn sleeping_is_a_focus(fields: &mut Map<String, Value>) -> anyhow::Result<()> {
+    let Some(attributes) = fields.get_mut("attributes").and_then(Value::as_object_mut) else {
+        return Ok(());
+    };
+    let remaining_turns = match attributes.remove("asleep") {
+        Some(Value::Number(turns)) => turns.as_u64().unwrap_or(1),
+        Some(Value::Bool(true)) => 1,
+        _ => return Ok(()),
+    };
+    if attributes.get("focus").is_none_or(Value::is_null) {
+        attributes.insert(
+            "focus".into(),
+            json!({ "kind": "sleeping", "remaining_turns": remaining_turns }),
+        );
+    }
+    Ok(())
+}
+
+/// Rewrite every stored payload that isn't the current version
+/// (run with `abduction-server migrate-entities`, while the server isn't running)
+pub async fn migrate_entities(db: &Db) -> anyhow::Result<()> {
+    let current = ENTITY_PAYLOAD_VERSION as i64;
+
+    // Every mutation, a batch at a time
+    let mut after = 0;
+    let mut mutations = 0;
+    loop {
+        let rows = query_file!(
+            "queries/get_outdated_entity_mutations.sql",
+            after,
+            current,
+            MIGRATE_BATCH_SIZE
+        )
+        .fetch_all(db)
+        .await
+        .context("Failed to load outdated entity mutations")?;
+        let Some(last) = rows.last() else {
+            break;
+        };
+        after = last.mutation_id;
+
+        for row in rows {
+            let Some(Json(mut payload)) = row.payload else {
+                continue;
+            };
+            payload
+                .migrate()
+                .with_context(|| format!("Migrating mutation {}", row.mutation_id))?;
+            let payload = Json(payload);
+            query_file!(
+                "queries/set_entity_mutation_payload.sql",
+                payload,
+                row.mutation_id
+            )
+            .execute(db)
+            .await
+            .context("Failed to save migrated entity mutation")?;
+            mutations += 1;
+        }
+    }
+    info!("Migrated {mutations} entity mutations");
+
+    // and every snapshot with anything outdated in it
+    let snapshots = query_file_as!(SnapshotKey, "queries/get_entity_snapshot_ticks.sql")
+        .fetch_all(db)
+        .await
+        .context("Failed to load entity snapshots")?;
+    let mut migrated_snapshots = 0;
+    for SnapshotKey { match_id, tick_id } in snapshots {
+        let Some(snapshot) =
+            query_file!("queries/get_latest_entity_snapshot.sql", match_id, tick_id)
+                .fetch_optional(db)
+                .await
+                .context("Failed to load entity snapshot")?
+        else {
+            continue;
+        };
+        let mut entities = snapshot.entities.0;
+        let mut changed = false;
+        for (entity_id, payload) in entities.iter_mut() {
+            changed |= payload
+                .migrate()
+                .with_context(|| format!("Migrating {entity_id} in snapshot at tick {tick_id}"))?;
+        }
+        if !changed {
+            continue;
+        }
+
+        let entities = Json(entities);
+        query_file!(
+            "queries/add_entity_snapshot.sql",
+            match_id,
+            tick_id,
+            entities
+        )
+        .execute(db)
+        .await
+        .context("Failed to save migrated entity snapshot")?;
+        migrated_snapshots += 1;
+    }
+    info!("Migrated {migrated_snapshots} entity snapshots");
+
+    println!(
+        "Migrated {mutations} entity mutations and {migrated_snapshots} snapshots to version {current}"
+    );
+    Ok(())
+}
+
+#[derive(sqlx::FromRow)]
+struct SnapshotKey {
+    match_id: String,
+    tick_id: i64,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{brain::focus::ActorFocus, Entity},
+        hex::AxialHex,
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_old_payloads_are_migrated_when_read() {
+        // Saved before payloads were versioned (and while sleeping was an attribute)
+        let mut payload: EntityPayload = serde_json::from_value(json!({
+            "name": "Sleepy",
+            "markers": ["player"],
+            "attributes": { "motivators": {}, "asleep": 4 },
+            "relations": {},
+        }))
+        .unwrap();
+        assert_eq!(payload.version, 0);
+
+        let entity = payload.clone().convert_to_entity("sleepy".into()).unwrap();
+        assert_eq!(
+            entity.attributes.focus,
+            Some(ActorFocus::Sleeping { remaining_turns: 4 })
+        );
+        assert!(payload.migrate().unwrap());
+        assert!(!payload.migrate().unwrap());
+    }
+
+    #[test]
+    fn test_new_payloads_round_trip() {
+        let player = test_player("Current", AxialHex::ZERO);
+        let payload = EntityPayload::from(player.clone());
+        assert_eq!(payload.version, ENTITY_PAYLOAD_VERSION);
+
+        let saved = serde_json::to_string(&payload).unwrap();
+        let read: EntityPayload = serde_json::from_str(&saved).unwrap();
+        let entity: Entity = read.convert_to_entity(player.entity_id.clone()).unwrap();
+        assert_eq!(entity.name, player.name);
+
+        // (newer than we know how to read)
+        let mut future = payload;
+        future.version = ENTITY_PAYLOAD_VERSION + 1;
+        assert!(future.migrate().is_err());
+    }
+}
@@ -1,14 +1,19 @@
+pub mod appearance;
 pub mod background;
 pub mod brain;
 pub mod generate;
+pub mod kind;
 pub mod manager;
+pub mod migrate;
 pub mod snapshot;
+pub mod store;
 pub mod world;
 
 use std::collections::{HashMap, HashSet};
 
 pub use manager::*;
 
+use anyhow::Context;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
@@ -16,19 +21,27 @@ use uuid::Uuid;
 
 use crate::{
     entity::{
-        background::EntityBackground,
+        appearance::EntityAppearance,
+        background::{hope::Hope, EntityBackground},
         brain::{
             characteristic::{Characteristic, CharacteristicStrength},
             focus::ActorFocus,
+            grief::EntityGrief,
             meme::MemeTable,
             motivator::MotivatorTable,
+            reputation::ReputationLedger,
         },
         snapshot::EntityView,
         world::EntityWorld,
     },
-    hex::AxialHex,
+    hex::{AxialHex, AxialHexDirection},
     location::LocationKind,
-    mtch::crew::{EntityCollector, EntityPresenter},
+    mtch::{
+        crew::{EntityCollector, EntityPresenter},
+        season::EntitySeasonHistory,
+        TickId,
+    },
+    rng,
 };
 
 /// These are sort of tags that can be associated with an entity
@@ -70,7 +83,10 @@ pub enum EntityMarker {
     /// Something alive
     Being,
 
-    /// Whether the player escaped on the ship
+    /// A being that is an animal (wildlife etc)
+    Animal,
+
+    /// Whether the player escaped (on the ship, or in an escape pod)
     /// Maybe remove this later
     Escaped,
 
@@ -80,6 +96,13 @@ pub enum EntityMarker {
 
     /// This entity represents somewhere an entity can shelter
     Shelter,
+
+    /// This entity is floodwater covering a low-lying hex
+    Floodwater,
+
+    /// This entity broke something while being resolved
+    /// and has been taken out of play until someone looks into it
+    Quarantined,
 }
 
 pub type EntityId = String; // TODO: use a uuid
@@ -98,21 +121,42 @@ pub struct EntityAttributes {
     /// The entity family name
     pub family_name: Option<String>,
 
+    /// What everyone has taken to calling this entity (e.g "Sparky Smith")
+    /// (set once most of the people who know them share a nickname for them)
+    pub nickname: Option<String>,
+
     /// How old the entity is in years
     pub age: Option<usize>,
 
     /// Which hex the entity is located in if applicable
     pub hex: Option<AxialHex>,
 
+    /// The tick this entity was first seen lying on the ground, if it only lasts so long there
+    /// (see `mtch::despawn`)
+    pub lying_since: Option<TickId>,
+
     /// If set, this entity is a corpse of some previous entity
     pub corpse: Option<EntityId>,
 
+    /// Who a corpse was when they were alive
+    pub remains: Option<EntityRemains>,
+
+    /// If set, this entity is the spirit of some previous entity, drifting about after they died
+    /// (spirits can't be seen or interacted with, they're only shown on a layer of their own)
+    pub spirit: Option<EntitySpirit>,
+
     /// If set, this item is entity as a pickupable item
     pub item: Option<EntityItem>,
 
+    /// If set, this item belonged to someone who has died and means something because of it
+    pub keepsake: Option<EntityKeepsake>,
+
     /// If set, this entity is a hazard which can deal damage when interacted with
     pub hazard: Option<EntityHazard>,
 
+    /// If set, this entity is a wild beast that hunts players (see `brain::predator`)
+    pub predator: Option<EntityPredator>,
+
     /// If set, this entity represents a location with the given location kind
     pub location: Option<EntityLocation>,
 
@@ -122,6 +166,43 @@ pub struct EntityAttributes {
     /// If set, this entity is an infinite water source
     pub water_source: Option<EntityWaterSource>,
 
+    /// If set, this entity holds other entities (in its inventory) which are hidden until searched
+    pub container: Option<EntityContainer>,
+
+    /// If set, this entity is a stash that some entities keep their things in
+    /// (NOTE: should also be a hidden container)
+    pub cache: Option<EntityCache>,
+
+    /// If set, this entity is a tool which can be used for something
+    pub tool: Option<EntityTool>,
+
+    /// If set, this entity can be used to treat wounds
+    pub healing: Option<EntityHealing>,
+
+    /// If set, this entity can be worn (by carrying it) to keep the weather off
+    pub clothing: Option<EntityClothing>,
+
+    /// If set, this entity is a shelter that can wear down over time
+    /// (NOTE: should also have the `Shelter` marker)
+    pub shelter: Option<EntityShelter>,
+
+    /// If set, this entity can be used to build/repair things
+    pub material: Option<EntityMaterial>,
+
+    /// If set, this entity is a raft that can carry a few players across open water
+    /// (see `brain::rafting`)
+    pub raft: Option<EntityRaft>,
+
+    /// If set, this entity is the tracks some other entity left behind when moving
+    pub track: Option<EntityTrack>,
+
+    /// If set, this entity is a broken escape pod that can be fixed up to escape in
+    /// (see `brain::escape`)
+    pub escape_pod: Option<EntityEscapePod>,
+
+    /// If set, this item is a part that some escape pod might be missing
+    pub pod_part: Option<PodPart>,
+
     /// The current details of the world
     pub world: Option<EntityWorld>,
 
@@ -136,6 +217,9 @@ pub struct EntityAttributes {
     /// (e.g for player dots)
     pub display_color_hue: Option<f32>,
 
+    /// How the site should draw this entity (see `appearance`)
+    pub appearance: Option<EntityAppearance>,
+
     /// Optionally, a background
     pub background: Option<EntityBackground>,
 
@@ -143,11 +227,20 @@ pub struct EntityAttributes {
     #[serde(flatten)]
     pub memes: Option<MemeTable>,
 
+    /// Optionally, a record of the good/bad things this entity has done
+    pub reputation: Option<ReputationLedger>,
+
+    /// If present, everyone this entity is still grieving (see `brain::grief`)
+    pub grief: Option<EntityGrief>,
+
     /// If present, this entity is the presenter
     pub presenter: Option<EntityPresenter>,
 
     /// If present, this entity is the collector
     pub collector: Option<EntityCollector>,
+
+    /// If present, the seasons this entity played before this one (see `mtch::season`)
+    pub season_history: Option<EntitySeasonHistory>,
 }
 
 #[skip_serializing_none]
@@ -204,13 +297,21 @@ impl EntityRelations {
     /// NOTE: increases by 1% w/ no current cap
     /// TODO: we prob want the ability to limit the influence of this
     pub fn increase_associate_bond(&mut self, entity_id: &EntityId) {
+        self.increase_associate_bond_by(entity_id, 0.01);
+    }
+
+    /// Create a new associate relation if it doesnt exist, otherwise strengthen it by some amount
+    pub fn increase_associate_bond_by(&mut self, entity_id: &EntityId, amount: f32) {
         let associates = self.associates.get_or_insert(Default::default());
         match associates.entry(entity_id.clone()) {
             std::collections::hash_map::Entry::Occupied(mut occupied_entry) => {
-                occupied_entry.get_mut().bond += 0.01;
+                occupied_entry.get_mut().bond += amount;
             }
             std::collections::hash_map::Entry::Vacant(vacant_entry) => {
-                vacant_entry.insert(EntityAssociate { bond: 0.01 });
+                vacant_entry.insert(EntityAssociate {
+                    bond: amount,
+                    last_together: None,
+                });
             }
         }
     }
@@ -218,15 +319,12 @@ impl EntityRelations {
     /// Create a new associate relation if it doesnt exist, otherwise lower it
     /// NOTE: decreases by 1% w/ no current cap
     pub fn decrease_associate_bond(&mut self, entity_id: &EntityId) {
-        let associates = self.associates.get_or_insert(Default::default());
-        match associates.entry(entity_id.clone()) {
-            std::collections::hash_map::Entry::Occupied(mut occupied_entry) => {
-                occupied_entry.get_mut().bond -= 0.01;
-            }
-            std::collections::hash_map::Entry::Vacant(vacant_entry) => {
-                vacant_entry.insert(EntityAssociate { bond: -0.01 });
-            }
-        }
+        self.decrease_associate_bond_by(entity_id, 0.01);
+    }
+
+    /// Create a new associate relation if it doesnt exist, otherwise lower it by some amount
+    pub fn decrease_associate_bond_by(&mut self, entity_id: &EntityId, amount: f32) {
+        self.increase_associate_bond_by(entity_id, -amount);
     }
 }
 
@@ -239,6 +337,23 @@ impl EntityRelations {
 #[qubit::ts]
 pub struct EntityAssociate {
     bond: f32,
+
+    /// The last tick we were seen together (see `brain::bonding`)
+    last_together: Option<TickId>,
+}
+
+/// Footprints, scent etc left behind by some entity as they move on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct EntityTrack {
+    /// Who left these tracks
+    pub entity_id: EntityId,
+
+    /// Which way they went
+    pub direction: AxialHexDirection,
+
+    /// How many more ticks until these fade away completely
+    pub fresh_for: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -268,6 +383,194 @@ impl Default for EntityItem {
         Self { heft: 1 }
     }
 }
+/// Something holding items that must be searched to find them
+/// (the items are held in the container's inventory)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityContainer {
+    /// Hidden containers (e.g a cache in a hollow log) are harder to search
+    pub hidden: bool,
+}
+
+/// A stash some entities (e.g an alliance) have hidden away to share their things
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityCache {
+    /// Who can deposit in and withdraw from this stash (anyone else taking from it is raiding it)
+    pub owners: Vec<EntityId>,
+}
+
+impl EntityCache {
+    pub fn is_owner(&self, entity_id: &EntityId) -> bool {
+        self.owners.contains(entity_id)
+    }
+}
+
+/// What's left of a player after they die
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct EntitySpirit {
+    /// Who this was
+    pub player: EntityId,
+
+    /// What they were hoping for (which they still think about)
+    pub hope: Option<Hope>,
+}
+
+/// Who a corpse was (see `generate_corpse`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct EntityRemains {
+    /// Their name when they died
+    pub name: String,
+
+    /// A few words on who they were (i.e "a retired nurse from Lyon, France")
+    pub summary: String,
+}
+
+/// One of the personal effects someone was carrying when they died
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct EntityKeepsake {
+    /// Who it belonged to
+    pub owner: EntityId,
+
+    /// What it says about them (i.e "a letter home, about how they hope to see the ocean")
+    pub description: String,
+}
+
+/// A wild beast that hunts players
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityPredator {
+    /// Who it's going after, if anyone
+    pub quarry: Option<EntityId>,
+
+    /// How many ticks it has been going after them for
+    pub pursuit: usize,
+
+    /// How badly it has been hurt (it goes down once this reaches `PREDATOR_TOUGHNESS`)
+    pub wounds: f32,
+}
+
+/// A broken down escape pod
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityEscapePod {
+    /// The parts it needs fitting before it will fly
+    pub missing: Vec<PodPart>,
+}
+
+/// A part that an escape pod can be missing
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, strum::VariantArray)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum PodPart {
+    FuelCell,
+    Thruster,
+    NavComputer,
+    HatchSeal,
+}
+
+/// An item that can be used to do something
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct EntityTool {
+    pub kind: ToolKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum ToolKind {
+    Knife,
+    FishingRod,
+    Spear,
+}
+
+impl ToolKind {
+    /// How much more likely this tool makes catching a fish
+    pub fn fishing_bonus(&self) -> f64 {
+        match self {
+            ToolKind::FishingRod => 0.25,
+            ToolKind::Spear => 0.15,
+            ToolKind::Knife => 0.0,
+        }
+    }
+
+    /// How much more it hurts to be hit by this when thrown
+    pub fn throw_bonus(&self) -> f32 {
+        match self {
+            ToolKind::Spear => 2.0,
+            ToolKind::Knife => 1.0,
+            ToolKind::FishingRod => 0.0,
+        }
+    }
+}
+
+/// How much a shelter is holding together
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityShelter {
+    /// At 0 the shelter collapses
+    /// (0-1)
+    pub durability: f32,
+
+    /// If set, someone has claimed this shelter (and so the hex its in) as theirs
+    pub claimed_by: Option<EntityId>,
+}
+
+/// Something that can be used up to build/repair things
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityMaterial {
+    /// How much durability this restores when used for a repair
+    /// (0-1)
+    pub strength: f32,
+}
+
+/// A raft someone lashed together, that stays where it was left for anyone to take
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityRaft {
+    /// How many can be aboard at once
+    pub capacity: usize,
+
+    /// If set, whoever built it (or last took it for themselves)
+    pub owner: Option<EntityId>,
+}
+
+/// Something that can be used (up) to reduce hurt
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityHealing {
+    /// How good is this at healing?
+    /// (0-1)
+    pub quality: f32,
+}
+
+/// Something that can be worn to keep the weather off
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct EntityClothing {
+    /// How much less likely the wearer is to get cold
+    /// (0-1)
+    pub warmth: f32,
+
+    /// How much less likely the wearer is to get soaked by rain
+    /// (0-1)
+    pub waterproofing: f32,
+}
+
+impl EntityHealing {
+    /// How much hurt is reduced by a full quality healing item
+    const MAX_HURT_REDUCTION: f32 = 0.5;
+
+    pub fn hurt_reduction(&self) -> f32 {
+        self.quality * Self::MAX_HURT_REDUCTION
+    }
+}
+
 /// Consumable food
 /// TODO: restructure this to just have seperate sustenance and poison fields
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -283,6 +586,9 @@ pub struct EntityFood {
     /// Is it "wrong" to eat this?
     /// i.e a corpse etc
     pub morally_wrong: bool,
+
+    /// If set, this food is fresh and will spoil after this many more ticks
+    pub fresh_for: Option<usize>,
 }
 
 impl EntityFood {
@@ -291,6 +597,17 @@ impl EntityFood {
             sustenance: rng.random_range(0.0..1.0),
             poison: 0.0,
             morally_wrong: false,
+            fresh_for: None,
+        }
+    }
+
+    /// Freshly caught, filling and safe but it wont stay that way for long
+    pub fn fresh(rng: &mut impl Rng, fresh_for: usize) -> Self {
+        Self {
+            sustenance: rng.random_range(0.4..1.0),
+            poison: 0.0,
+            morally_wrong: false,
+            fresh_for: Some(fresh_for),
         }
     }
 
@@ -303,30 +620,78 @@ impl EntityFood {
                 0.0
             },
             morally_wrong: false,
+            fresh_for: None,
         }
     }
+
+    /// Let this food go off, its less filling and now makes you sick
+    pub fn spoil(&mut self) {
+        self.sustenance /= 2.0;
+        self.poison = self.poison.max(0.6);
+        self.fresh_for = None;
+    }
 }
 
-/// An infinite water source
-/// All water is just as good at quenching thirst and all water sources are infinite
-/// so we just care about whether its tainted by disease/poison etc
+/// A water source
+/// All water is just as good at quenching thirst, so we care about whether its tainted by
+/// disease/poison etc and, for smaller sources that can run dry, how much is left (see `drought`)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[qubit::ts]
 pub struct EntityWaterSource {
     /// Poison between 0 and 1 -> 1 is worst poison
     pub poison: f32,
+
+    /// How much water is left, for a source that can run dry
+    /// (none if it never does, i.e a lake)
+    #[serde(default)]
+    pub capacity: Option<WaterCapacity>,
+}
+
+/// How much water some limited water source holds (in drinks)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct WaterCapacity {
+    pub remaining: f32,
+    pub max: f32,
+
+    /// How many ticks it has been empty without any rain
+    #[serde(default)]
+    pub dry_for: usize,
 }
 
 impl EntityWaterSource {
     pub fn quality() -> Self {
-        Self { poison: 0.0 }
+        Self {
+            poison: 0.0,
+            capacity: None,
+        }
     }
 
     pub fn dubious(rng: &mut impl Rng) -> Self {
         Self {
             poison: rng.random_range(0.0..1.0),
+            capacity: None,
         }
     }
+
+    /// Only holds so much water (starting full)
+    pub fn limited(self, max: f32) -> Self {
+        Self {
+            capacity: Some(WaterCapacity {
+                remaining: max,
+                max,
+                dry_for: 0,
+            }),
+            ..self
+        }
+    }
+
+    /// Is there any water left to drink?
+    pub fn is_empty(&self) -> bool {
+        self.capacity
+            .as_ref()
+            .is_some_and(|capacity| capacity.remaining < 1.0)
+    }
 }
 
 /// A full entity including an id
@@ -350,25 +715,45 @@ pub struct Entity {
     pub relations: EntityRelations,
 }
 
-/// An entity as stored in a payload on an entity_mutation row
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+/// An entity as stored in a payload on an entity_mutation row (or in a snapshot)
+/// kept in whatever shape it was saved in, along with which version of the shape that was,
+/// so it can be migrated to the current shape when it's read (see `migrate`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EntityPayload {
+    /// Which version of the payload shape this was saved with
+    /// (payloads from before they were versioned are version 0)
+    #[serde(default)]
+    pub version: u32,
+
+    /// The entity itself (i.e `EntityFields` once migrated)
+    #[serde(flatten)]
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// The current shape of an entity in a payload
+#[derive(Serialize, Deserialize)]
+struct EntityFields {
     /// A required name
-    pub name: String,
+    name: String,
 
     /// A set of unique "markers"
-    pub markers: Vec<EntityMarker>,
+    markers: Vec<EntityMarker>,
 
     /// Grab bag of attributes
-    pub attributes: EntityAttributes,
+    attributes: EntityAttributes,
 
     /// Relations with other entities
-    pub relations: EntityRelations,
+    relations: EntityRelations,
 }
 
 impl Entity {
+    /// A new entity id (these are time ordered, so the oldest entity sorts first)
+    /// NOTE: when the game rng is seeded they come from it instead, as entities act in order of id
     pub fn id() -> EntityId {
-        Uuid::now_v7().hyphenated().to_string()
+        rng::seeded_id()
+            .unwrap_or_else(Uuid::now_v7)
+            .hyphenated()
+            .to_string()
     }
 
     /// Get (or insert if not present) a mut reference to this entities meme table
@@ -404,36 +789,68 @@ impl Entity {
         // TODO: and from having a bag etc
     }
 
+    /// How many inventory "slots" are currently taken up
+    pub fn inventory_load(&self, entity_view: &EntityView) -> usize {
+        self.resolve_inventory(entity_view)
+            .filter_map(|e| e.attributes.item.as_ref().map(|i| i.heft))
+            .sum::<usize>()
+    }
+
     /// Inventory items take up "slots", of which we have an amount derived from our characteristics
     pub fn available_inventory_load(&self, entity_view: &EntityView) -> usize {
-        let current_slots = self
-            .resolve_inventory(entity_view)
-            .filter_map(|e| e.attributes.item.as_ref().map(|i| i.heft))
-            .sum::<usize>();
-        let max_slots = self.max_inventory_load();
-        max_slots - current_slots
+        self.max_inventory_load()
+            .saturating_sub(self.inventory_load(entity_view))
+    }
+
+    /// How weighed down we are by what we're carrying
+    /// (0 -> carrying nothing, 1 -> full)
+    pub fn encumbrance(&self, entity_view: &EntityView) -> f32 {
+        (self.inventory_load(entity_view) as f32 / self.max_inventory_load() as f32).min(1.0)
+    }
+
+    /// Chance of pulling off something nimble (dodging, slipping away etc)
+    /// carrying a lot makes this harder
+    pub fn acrobatics_chance(&self, entity_view: &EntityView) -> f64 {
+        let base = self
+            .characteristic(Characteristic::Acrobatics)
+            .success_chance();
+        base * (1.0 - 0.5 * self.encumbrance(entity_view) as f64)
     }
 }
 
 impl EntityPayload {
-    pub fn convert_to_entity(self, entity_id: EntityId) -> Entity {
-        Entity {
+    /// Migrate this payload to the current shape, and read the entity out of it
+    pub fn convert_to_entity(mut self, entity_id: EntityId) -> anyhow::Result<Entity> {
+        self.migrate()?;
+        let fields: EntityFields =
+            serde_json::from_value(serde_json::Value::Object(self.fields))
+                .with_context(|| format!("Failed to read payload of entity {entity_id}"))?;
+        Ok(Entity {
             entity_id,
-            attributes: self.attributes,
-            markers: self.markers,
-            name: self.name,
-            relations: self.relations,
-        }
+            attributes: fields.attributes,
+            markers: fields.markers,
+            name: fields.name,
+            relations: fields.relations,
+        })
     }
 }
 
 impl From<Entity> for EntityPayload {
     fn from(value: Entity) -> Self {
-        Self {
+        let fields = EntityFields {
             attributes: value.attributes,
             markers: value.markers,
             name: value.name,
             relations: value.relations,
+        };
+        let serde_json::Value::Object(fields) =
+            serde_json::to_value(fields).expect("Entities should always serialize")
+        else {
+            unreachable!("Entities serialize as objects");
+        };
+        Self {
+            version: migrate::ENTITY_PAYLOAD_VERSION,
+            fields,
         }
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
     entity::{Entity, EntityId},
@@ -9,8 +9,58 @@ use crate::{
 /// with caches for quickly accessing entities in certain hexs or by id
 #[derive(Debug, Clone, Default)]
 pub struct EntityView<'a> {
+    all: Vec<&'a Entity>,
     by_hex: HashMap<AxialHex, Vec<&'a Entity>>,
     by_id: HashMap<EntityId, &'a Entity>,
+    resources: HashMap<AxialHex, HexResources>,
+}
+
+/// What can be found in some hex, at a glance
+/// (so deciding where to head doesn't mean looking through everything there every time)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HexResources {
+    /// How many things there are to eat (not counting anything it'd be wrong to eat)
+    pub food: usize,
+
+    /// Is there water to drink (that hasn't run dry)?
+    pub water: bool,
+
+    /// Is there somewhere to shelter?
+    pub shelter: bool,
+
+    /// Is there anything that would hurt us?
+    pub hazard: bool,
+
+    /// Is it open water, that can't be walked into? (see `brain::rafting`)
+    pub open_water: bool,
+}
+
+impl HexResources {
+    fn add(&mut self, entity: &Entity) {
+        if entity
+            .attributes
+            .food
+            .as_ref()
+            .is_some_and(|food| !food.morally_wrong)
+        {
+            self.food += 1;
+        }
+        if entity
+            .attributes
+            .water_source
+            .as_ref()
+            .is_some_and(|water_source| !water_source.is_empty())
+        {
+            self.water = true;
+        }
+        self.shelter |= entity.attributes.shelter.is_some();
+        self.hazard |= entity.attributes.hazard.is_some();
+        self.open_water |= entity
+            .attributes
+            .location
+            .as_ref()
+            .is_some_and(|location| location.location_kind.is_open_water());
+    }
 }
 
 impl<'a> EntityView<'a> {
@@ -19,7 +69,7 @@ impl<'a> EntityView<'a> {
     }
 
     pub fn all(&'a self) -> impl Iterator<Item = &'a Entity> {
-        self.by_id.values().copied()
+        self.all.iter().copied()
     }
 
     pub fn in_hex(&'a self, hex: AxialHex) -> impl Iterator<Item = &'a Entity> {
@@ -29,6 +79,21 @@ impl<'a> EntityView<'a> {
             .flat_map(|ents| ents.iter().copied())
     }
 
+    /// What can be found in some hex
+    pub fn resources(&self, hex: AxialHex) -> HexResources {
+        self.resources.get(&hex).copied().unwrap_or_default()
+    }
+
+    /// What can be found in each of the hexes adjacent to some hex
+    pub fn adjacent_resources(
+        &self,
+        hex: AxialHex,
+    ) -> impl Iterator<Item = (AxialHex, HexResources)> + '_ {
+        hex.neighbours()
+            .into_iter()
+            .map(|hex| (hex, self.resources(hex)))
+    }
+
     /// Get all the entities that are adjacent to some hex (but not in that hex itself)
     pub fn adjacent_to_hex(&'a self, hex: AxialHex) -> impl Iterator<Item = &'a Entity> {
         hex.neighbours()
@@ -37,14 +102,28 @@ impl<'a> EntityView<'a> {
     }
 }
 
+/// A frozen copy of some entities
+/// (shared with the entity manager until they change, so these are cheap to make)
 #[derive(Debug, Clone)]
 pub struct EntitySnapshot {
-    entities: Vec<Entity>,
+    entities: Vec<Arc<Entity>>,
 }
 
 impl EntitySnapshot {
-    pub fn new(entities: Vec<Entity>) -> Self {
-        Self { entities }
+    pub fn new(entities: impl IntoIterator<Item = impl Into<Arc<Entity>>>) -> Self {
+        Self {
+            entities: entities.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Every entity in the snapshot (in snapshot order)
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter().map(Arc::as_ref)
+    }
+
+    /// Every entity in the snapshot (in snapshot order), still shared
+    pub fn as_slice(&self) -> &[Arc<Entity>] {
+        &self.entities
     }
 
     pub fn view(&self) -> EntityView {
@@ -52,16 +131,84 @@ impl EntitySnapshot {
         let mut view = EntityView::default();
 
         // Add all entities
-        for entity in &self.entities {
+        for entity in self.entities.iter().map(Arc::as_ref) {
+            // add in snapshot order
+            view.all.push(entity);
+
             // add by id
             view.by_id.insert(entity.entity_id.clone(), entity);
 
             // add by hex
             if let Some(hex) = entity.attributes.hex {
                 view.by_hex.entry(hex).or_default().push(entity);
+                view.resources.entry(hex).or_default().add(entity);
             }
         }
 
         view
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::{EntityAttributes, EntityFood, EntityHazard, EntityLocation},
+        location::LocationKind,
+    };
+
+    fn thing(hex: AxialHex, attributes: EntityAttributes) -> Entity {
+        Entity {
+            entity_id: Entity::id(),
+            name: "Thing".into(),
+            attributes: EntityAttributes {
+                hex: Some(hex),
+                ..attributes
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resources_are_summarised_by_hex() {
+        let food = |morally_wrong| EntityAttributes {
+            food: Some(EntityFood {
+                sustenance: 0.5,
+                poison: 0.0,
+                morally_wrong,
+                fresh_for: None,
+            }),
+            ..Default::default()
+        };
+        let snapshot = EntitySnapshot::new(vec![
+            thing(AxialHex::ZERO, food(false)),
+            thing(AxialHex::ZERO, food(false)),
+            thing(AxialHex::ZERO, food(true)),
+            thing(
+                AxialHex::EAST,
+                EntityAttributes {
+                    hazard: Some(EntityHazard { damage: 1 }),
+                    ..Default::default()
+                },
+            ),
+            thing(
+                AxialHex::from((2, 0)),
+                EntityAttributes {
+                    location: Some(EntityLocation {
+                        location_kind: LocationKind::Lake,
+                    }),
+                    ..Default::default()
+                },
+            ),
+        ]);
+        let view = snapshot.view();
+
+        assert_eq!(view.resources(AxialHex::ZERO).food, 2);
+        assert!(!view.resources(AxialHex::ZERO).hazard);
+        assert!(view.resources(AxialHex::EAST).hazard);
+        assert!(view.resources(AxialHex::from((2, 0))).open_water);
+        assert!(!view.resources(AxialHex::ZERO).open_water);
+        assert_eq!(view.resources(AxialHex::WEST), HexResources::default());
+        assert_eq!(view.adjacent_resources(AxialHex::ZERO).count(), 6);
+    }
+}
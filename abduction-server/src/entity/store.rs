@@ -0,0 +1,28 @@
+//! Where the entity manager saves its changes
+//!
+//! The simulation itself (entities, their brains, events and the tick) shouldn't need to know about
+//! the db, so that it can one day be pulled out into its own crate and run headless (for balance
+//! tools, property tests and the like). This is the first seam for that: the entity manager saves
+//! the changes it makes through a `MutationStore` rather than straight to the db, and the server's
+//! db is just one kind of store. Loading, logs and match config still go straight to the db for now
+//! (see "Simulation Crate" in the README for what else is in the way).
+
+use std::future::Future;
+
+use super::EntityMutation;
+use crate::Db;
+
+/// Somewhere changes to entities can be saved
+pub trait MutationStore {
+    /// Save a single change to an entity
+    fn save_mutation(
+        &self,
+        mutation: &EntityMutation,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+impl MutationStore for Db {
+    async fn save_mutation(&self, mutation: &EntityMutation) -> anyhow::Result<()> {
+        mutation.save(self).await
+    }
+}
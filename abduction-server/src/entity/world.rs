@@ -6,8 +6,25 @@ use rand::{
 };
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
+use tracing::warn;
 
-use crate::logs::{GameLog, GameLogBody};
+use crate::{
+    logs::{GameLog, GameLogBody},
+    mtch::{
+        audience::AudienceMood,
+        calendar::{MatchTime, PartOfDay, START_HOUR},
+        TickId,
+    },
+};
+
+/// Chance each tick of heavy rain raising the water level (scaled down for lighter rain)
+const WATER_RISE_CHANCE: f64 = 0.05;
+
+/// Chance each tick of the water level dropping when it isn't raining
+const WATER_RECEDE_CHANCE: f64 = 0.03;
+
+/// How much the water level rises or drops by at a time
+const WATER_LEVEL_STEP: f32 = 0.05;
 
 /// Describes current state of the world
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,34 +34,92 @@ pub struct EntityWorld {
     pub time_of_day: TimeOfDay,
     pub weather: WeatherKind,
     pub day: usize,
+
+    /// Which hour of the day it is (0-23, see `mtch::calendar`)
+    #[serde(default)]
+    pub hour: usize,
+
+    /// Roughly what part of the day it is (for showing viewers)
+    #[serde(default)]
+    pub part_of_day: PartOfDay,
+
+    /// How engaged the audience is (0-1), see `AudienceTracker`
+    #[serde(default)]
+    pub audience_engagement: f32,
+
+    /// How high the water is (0-1), raised by rain (see `mtch::flood`)
+    #[serde(default)]
+    pub water_level: f32,
 }
 
 impl Default for EntityWorld {
     fn default() -> Self {
         Self {
             day: 1,
+            hour: START_HOUR,
+            part_of_day: PartOfDay::default(),
             time_of_day: TimeOfDay::default(),
             weather: WeatherKind::default(),
+            audience_engagement: 0.5,
+            water_level: 0.0,
         }
     }
 }
 
 impl EntityWorld {
-    pub fn update(&mut self, log_tx: &broadcast::Sender<GameLog>, rng: &mut impl Rng) {
-        // Update TOD
-        self.time_of_day = self.time_of_day.next();
-        log_tx
-            .send(GameLog::global(GameLogBody::TimeOfDayChange {
-                time_of_day: self.time_of_day.clone(),
-            }))
-            .unwrap();
+    pub fn audience_mood(&self) -> AudienceMood {
+        AudienceMood::from_engagement(self.audience_engagement)
+    }
+
+    /// Raise the water level a bit when it's raining, otherwise let it drop
+    /// (returns whether it changed)
+    pub fn update_water_level(&mut self, rng: &mut impl Rng) -> bool {
+        let rain = self.weather.rain_proc_chance_scale() as f64;
+        let step = if rain > 0.0 {
+            if !rng.random_bool(rain * WATER_RISE_CHANCE) {
+                return false;
+            }
+            WATER_LEVEL_STEP
+        } else {
+            if self.water_level <= 0.0 || !rng.random_bool(WATER_RECEDE_CHANCE) {
+                return false;
+            }
+            -WATER_LEVEL_STEP
+        };
 
-        // Go to next day
-        if self.time_of_day == TimeOfDay::Morning {
-            self.day += 1;
+        // (rounded so the steps dont drift)
+        let water_level = ((self.water_level + step) * 100.0).round() / 100.0;
+        self.water_level = water_level.clamp(0.0, 1.0);
+        true
+    }
+
+    /// Move the clock on to the time at some tick
+    /// (returns whether it changed)
+    pub fn advance_clock(&mut self, tick_id: TickId, log_tx: &broadcast::Sender<GameLog>) -> bool {
+        let time = MatchTime::at_tick(tick_id);
+        if time.day == self.day && time.hour == self.hour {
+            return false;
+        }
+        self.day = time.day;
+        self.hour = time.hour;
+        self.part_of_day = time.part_of_day();
+
+        let time_of_day = time.time_of_day();
+        if time_of_day != self.time_of_day {
+            self.time_of_day = time_of_day;
+            let log = GameLog::global(GameLogBody::TimeOfDayChange {
+                time_of_day: self.time_of_day.clone(),
+                day: self.day,
+                part_of_day: self.part_of_day,
+            });
+            if let Err(err) = log_tx.send(log) {
+                warn!("Failed to send time of day change: {err}");
+            }
         }
+        true
+    }
 
-        // Update weather
+    pub fn update_weather(&mut self, log_tx: &broadcast::Sender<GameLog>, rng: &mut impl Rng) {
         if let Some(next_weather) = self.weather.next_weather(rng) {
             // logs
             self.weather = next_weather;
@@ -57,9 +132,12 @@ impl EntityWorld {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[derive(
+    Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq, strum::Display, strum::EnumString,
+)]
 #[qubit::ts]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum TimeOfDay {
     #[default]
     Morning,
@@ -88,9 +166,21 @@ impl TimeOfDay {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    strum::Display,
+    strum::EnumString,
+)]
 #[qubit::ts]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum WeatherKind {
     /// Nice gently sun and clouds - no additional effects
     #[default]
@@ -125,6 +215,14 @@ impl WeatherKind {
         self.rain_proc_chance_scale() > 0.0
     }
 
+    /// Is this the kind of weather people will talk about afterwards?
+    pub fn is_rough(&self) -> bool {
+        matches!(
+            self,
+            WeatherKind::Hurricane | WeatherKind::HeavyRain | WeatherKind::LightningStorm
+        )
+    }
+
     pub fn rain_proc_chance_scale(&self) -> f32 {
         match self {
             WeatherKind::Lovely => 0.0,
@@ -151,6 +249,57 @@ impl WeatherKind {
         }
     }
 
+    /// Chance of struggling to get anywhere when moving about in this weather
+    /// (see `brain::footing`)
+    pub fn movement_hindrance(&self) -> f64 {
+        match self {
+            WeatherKind::Lovely => 0.0,
+            WeatherKind::Sunny => 0.0,
+            WeatherKind::Overcast => 0.0,
+            WeatherKind::LightWind => 0.0,
+            WeatherKind::Hurricane => 0.3,
+            WeatherKind::LightRain => 0.05,
+            WeatherKind::HeavyRain => 0.15,
+            WeatherKind::LightningStorm => 0.25,
+        }
+    }
+
+    /// Chance of a (fully laden) raft going over on the water in this weather
+    /// (see `brain::rafting`)
+    pub fn capsize_chance(&self) -> f64 {
+        match self {
+            WeatherKind::Lovely => 0.0,
+            WeatherKind::Sunny => 0.0,
+            WeatherKind::Overcast => 0.0,
+            WeatherKind::LightWind => 0.02,
+            WeatherKind::Hurricane => 0.4,
+            WeatherKind::LightRain => 0.02,
+            WeatherKind::HeavyRain => 0.1,
+            WeatherKind::LightningStorm => 0.3,
+        }
+    }
+
+    /// How well anyone can make things out in this weather
+    /// (scales the odds of noticing things, and how far away things can be seen)
+    pub fn visibility(&self) -> f64 {
+        match self {
+            WeatherKind::Lovely => 1.0,
+            WeatherKind::Sunny => 1.0,
+            WeatherKind::Overcast => 0.9,
+            WeatherKind::LightWind => 1.0,
+            WeatherKind::Hurricane => 0.6,
+            WeatherKind::LightRain => 0.8,
+            WeatherKind::HeavyRain => 0.45,
+            WeatherKind::LightningStorm => 0.4,
+        }
+    }
+
+    /// How many hexes away something can be seen in this weather,
+    /// given how far away it could be seen on a clear day
+    pub fn sight_range(&self, clear_range: usize) -> usize {
+        (clear_range as f64 * self.visibility()).round() as usize
+    }
+
     pub fn transitions(&self) -> Vec<(Self, usize)> {
         use WeatherKind::*;
         match self {
@@ -185,3 +334,27 @@ impl WeatherKind {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rain_raises_water_level() {
+        let mut rng = crate::rng::rng();
+        let mut world = EntityWorld {
+            weather: WeatherKind::HeavyRain,
+            ..Default::default()
+        };
+        for _ in 0..10_000 {
+            world.update_water_level(&mut rng);
+        }
+        assert_eq!(world.water_level, 1.0);
+
+        world.weather = WeatherKind::Sunny;
+        for _ in 0..10_000 {
+            world.update_water_level(&mut rng);
+        }
+        assert_eq!(world.water_level, 0.0);
+    }
+}
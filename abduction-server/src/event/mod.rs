@@ -3,6 +3,10 @@ pub mod signal;
 
 use std::collections::{HashMap, HashSet};
 
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, SqliteExecutor};
 use tracing::debug;
 
 use crate::{
@@ -10,16 +14,21 @@ use crate::{
         brain::{
             characteristic::{Characteristic, CharacteristicStrength},
             discussion::{DiscussionLeadAction, DiscussionRespondAction},
+            nickname::Epithet,
+            reputation::Deed,
+            rescue::Plight,
             signal::SignalRef,
         },
         Entity, EntityId,
     },
     hex::AxialHex,
     logs::AsEntityId,
+    mtch::{MatchId, TickId},
+    Db,
 };
 
 /// An event happening in the game
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameEvent {
     /// What is this event
     kind: GameEventKind,
@@ -31,8 +40,25 @@ pub struct GameEvent {
     notice_conditions: Option<Vec<NoticeCondition>>,
 }
 
+impl GameEvent {
+    pub fn kind(&self) -> &GameEventKind {
+        &self.kind
+    }
+
+    /// Where did this event *happen*
+    /// (only hex targets imply a location)
+    pub fn location(&self) -> Option<AxialHex> {
+        match self.target {
+            GameEventTarget::Hex(axial_hex) => Some(axial_hex),
+            GameEventTarget::HexSurrounds(axial_hex) => Some(axial_hex),
+            _ => None,
+        }
+    }
+}
+
 /// Some condition for noticing an event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
 pub enum NoticeCondition {
     /// Relies on some characteristic to notice this, at a given max dist
     /// e.g visual acuity, hearing etc
@@ -68,7 +94,8 @@ impl NoticeCondition {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
 pub enum GameEventKind {
     /// Some entity arrives in a new hex
     ArriveInHex { entity_id: EntityId },
@@ -95,10 +122,78 @@ pub enum GameEventKind {
         entity_id: EntityId,
         action: DiscussionRespondAction,
     },
+
+    /// Some entity did something others would judge them for
+    Deed { entity_id: EntityId, deed: Deed },
+
+    /// Some entity did something memorable enough that others might start calling them something
+    Feat {
+        entity_id: EntityId,
+        epithet: Epithet,
+    },
+
+    /// Some entity entered a hex claimed by someone else
+    Trespass {
+        entity_id: EntityId,
+        claimant: EntityId,
+    },
+
+    /// Some entity threw something into a hex (maybe hitting someone)
+    /// NOTE: event targets the hex it landed in
+    Projectile {
+        entity_id: EntityId,
+        hit: Option<EntityId>,
+    },
+
+    /// Some predator set upon someone
+    /// NOTE: event targets the hex it happened in (and those around it)
+    Attack {
+        entity_id: EntityId,
+        victim: EntityId,
+    },
+
+    /// Some crew member did something otherworldly (teleported, warped something out etc)
+    /// NOTE: event targets the hex it happened in (and those around it)
+    Warp { entity_id: EntityId },
+
+    /// Some entity is in trouble and could do with some help
+    /// (whether or not they know it, e.g if they're asleep)
+    /// NOTE: event targets the hex they're in (and those around it)
+    Distress { entity_id: EntityId, plight: Plight },
+
+    /// Some entity is holding a memorial for someone who died
+    /// NOTE: event targets the hex it's held in
+    Memorial {
+        entity_id: EntityId,
+        deceased: EntityId,
+        name: String,
+    },
+}
+
+impl GameEventKind {
+    /// The entity this event is about
+    pub fn entity_id(&self) -> &EntityId {
+        match self {
+            GameEventKind::ArriveInHex { entity_id }
+            | GameEventKind::LeaveHex { entity_id }
+            | GameEventKind::Death { entity_id }
+            | GameEventKind::LeadDiscussion { entity_id, .. }
+            | GameEventKind::RespondDiscussion { entity_id, .. }
+            | GameEventKind::Deed { entity_id, .. }
+            | GameEventKind::Feat { entity_id, .. }
+            | GameEventKind::Trespass { entity_id, .. }
+            | GameEventKind::Projectile { entity_id, .. }
+            | GameEventKind::Attack { entity_id, .. }
+            | GameEventKind::Warp { entity_id }
+            | GameEventKind::Distress { entity_id, .. }
+            | GameEventKind::Memorial { entity_id, .. } => entity_id,
+        }
+    }
 }
 
 #[allow(unused)]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GameEventTarget {
     /// A specific entity
     Entity(EntityId),
@@ -116,6 +211,66 @@ pub enum GameEventTarget {
     Global,
 }
 
+/// An event as saved against the match it was raised in
+/// (kept so matches can be archived with their events, see `mtch::archive`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGameEvent {
+    /// The tick it was raised during
+    pub tick_id: TickId,
+
+    /// The event itself
+    /// NOTE: left as raw json as it is only ever passed along as is
+    pub payload: Value,
+}
+
+impl SavedGameEvent {
+    /// Persist the events raised during some tick
+    pub async fn save_all(
+        events: &[GameEvent],
+        match_id: &MatchId,
+        tick_id: TickId,
+        db: &Db,
+    ) -> anyhow::Result<()> {
+        for event in events {
+            let payload = serde_json::to_value(event).context("Serialising game event")?;
+            Self { tick_id, payload }.save(match_id, db).await?;
+        }
+        Ok(())
+    }
+
+    /// Persist this event against a match
+    pub async fn save(
+        &self,
+        match_id: &MatchId,
+        db: impl SqliteExecutor<'_>,
+    ) -> anyhow::Result<()> {
+        let tick_id = self.tick_id as i64;
+        let payload = Json(&self.payload);
+        sqlx::query_file!("queries/add_match_event.sql", match_id, tick_id, payload)
+            .execute(db)
+            .await
+            .context("Failed to persist game event to DB")?;
+
+        Ok(())
+    }
+
+    /// Load every event saved for a match, oldest first
+    pub async fn load_from_match(match_id: &MatchId, db: &Db) -> anyhow::Result<Vec<Self>> {
+        let rows = sqlx::query_file!("queries/get_match_events.sql", match_id)
+            .fetch_all(db)
+            .await
+            .context("Failed to load match events")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Self {
+                tick_id: row.tick_id as TickId,
+                payload: row.payload.0,
+            })
+            .collect())
+    }
+}
+
 /// A store for the events raised during a tick
 /// raised events are processed in the next tick
 ///
@@ -167,11 +322,7 @@ impl<'a> EventsView<'a> {
                 GameEventTarget::HexSurrounds(axial_hex) => {
                     // NOTE: this can store events in out-of-bound hexs but we just ignore that
                     // they'll never get recalled and then theyll be deleted
-                    view.events_by_hex
-                        .entry(*axial_hex)
-                        .or_default()
-                        .push(event);
-                    for hex in axial_hex.neighbours() {
+                    for hex in AxialHex::spiral(*axial_hex, 1) {
                         view.events_by_hex.entry(hex).or_default().push(event);
                     }
                 }
@@ -224,4 +375,10 @@ impl EventStore {
     pub fn view(&self) -> EventsView<'_> {
         EventsView::new(&self.events)
     }
+
+    /// Every event raised during the last tick
+    #[cfg(test)]
+    pub fn all(&self) -> &[GameEvent] {
+        &self.events
+    }
 }
@@ -1,32 +1,39 @@
+use itertools::Itertools;
 use rand::seq::IteratorRandom;
 use tracing::{info, warn};
 
 use super::GameEventKind;
 use crate::{
-    entity::brain::{
-        actor_action::ActorAction,
-        characteristic::Characteristic,
-        discussion::{
-            DiscussionAction, DiscussionLeadAction, DiscussionRespondAction, InfoTopic, Opinion,
-            PersonalTopic,
+    content::content,
+    entity::{
+        brain::{
+            actor_action::ActorAction,
+            characteristic::Characteristic,
+            discussion::{
+                DiscussionAction, DiscussionLeadAction, DiscussionRespondAction, InfoTopic,
+                Opinion, PersonalTopic,
+            },
+            focus::{ActorFocus, BOND_REQ_FOR_PERSONAL_BASE},
+            meme::{Meme, Witnessed},
+            motivator::{self, MotivatorKey},
+            nickname::Epithet,
+            plan::Plan,
+            reputation::ALLY_BOND,
+            rescue::{Plight, HUNGRY_ENOUGH_TO_SHARE},
+            signal::{Signal, SignalContext, WeightedActorActions},
+            throwing::ThrowTarget,
         },
-        focus::{ActorFocus, BOND_REQ_FOR_PERSONAL_BASE},
-        meme::Meme,
-        motivator::MotivatorKey,
-        signal::{Signal, SignalContext, WeightedActorActions},
+        Entity,
     },
-    event::{GameEvent, GameEventTarget},
+    event::GameEvent,
+    has_markers,
     logs::GameLogBody,
 };
 
 impl Signal for GameEvent {
     fn act_on(&self, ctx: &SignalContext, actions: &mut WeightedActorActions) {
         // Where did this event *happen*
-        let location = match self.target {
-            GameEventTarget::Hex(axial_hex) => Some(axial_hex),
-            GameEventTarget::HexSurrounds(axial_hex) => Some(axial_hex),
-            _ => None,
-        };
+        let location = self.location();
 
         // Did the entity notice this event?
         let did_notice = match &self.notice_conditions {
@@ -50,10 +57,27 @@ impl Signal for GameEvent {
 
         // Then handle the specific type
         match &self.kind {
-            GameEventKind::LeaveHex {
-                entity_id: _entity_id,
-            } => {
-                // TODO
+            GameEventKind::LeaveHex { entity_id } => {
+                // Ignore this if its us, or if we are busy
+                if *entity_id == ctx.entity.entity_id || ctx.focus != ActorFocus::Unfocused {
+                    return;
+                }
+
+                // Keep up with our allies, or go after someone we have a bone to pick with
+                let ally = ctx.entity.relations.bond(entity_id) >= ALLY_BOND;
+                let pursue = ctx.entity.relations.dislike(entity_id)
+                    && ctx
+                        .entity
+                        .characteristic(Characteristic::Aggression)
+                        .is_high();
+                if ally || pursue {
+                    actions.add(
+                        10,
+                        ActorAction::FollowTracks {
+                            entity_id: entity_id.clone(),
+                        },
+                    );
+                }
             }
             GameEventKind::ArriveInHex { entity_id } => {
                 // Ignore this if its us
@@ -67,9 +91,33 @@ impl Signal for GameEvent {
                     return;
                 }
 
+                // Have we heard bad things about them?
+                let Ok(memes) = ctx.entity.as_memes() else {
+                    return;
+                };
+                let bad_reputation = memes.perceived_reputation(entity_id) < 0.0;
+
+                // If we care about that sort of thing, we either keep away from them or tell them off
+                // (depending on how confrontational we are)
+                if bad_reputation && ctx.entity.characteristic(Characteristic::Empathy).is_high() {
+                    let aggression = ctx.entity.characteristic(Characteristic::Aggression);
+                    actions.add(
+                        if aggression.is_low() { 30 } else { 10 },
+                        ActorAction::AvoidEntity {
+                            entity_id: entity_id.clone(),
+                        },
+                    );
+                    actions.add(
+                        if aggression.is_high() { 30 } else { 10 },
+                        ActorAction::ConfrontEntity {
+                            entity_id: entity_id.clone(),
+                        },
+                    );
+                }
+
                 // If we are friendly, we might choose to great the entity arriving in the hex
                 let friendliness = ctx.entity.characteristic(Characteristic::Friendliness);
-                let dislike = ctx.entity.relations.dislike(entity_id);
+                let dislike = ctx.entity.relations.dislike(entity_id) || bad_reputation;
 
                 // If we're a friendly person and we dont dislike this person who showed up, consider greeting them
                 // NOTE: if we aren't friendly we may still great them, just less likely
@@ -83,42 +131,377 @@ impl Signal for GameEvent {
                 }
             }
 
+            GameEventKind::Deed { entity_id, deed } => {
+                // Ignore this if its us
+                if *entity_id == ctx.entity.entity_id {
+                    return;
+                }
+
+                // We saw that, and we'll remember it
+                // (maybe even call them something for it)
+                let witness = Plan::new("witness deed")
+                    .then(ActorAction::Log {
+                        other: Some(entity_id.clone()),
+                        body: GameLogBody::EntityWitnessDeed { deed: *deed },
+                    })
+                    .also(ActorAction::StoreMeme(Meme::Deed(entity_id.clone(), *deed)))
+                    .then_maybe(
+                        Epithet::for_deed(*deed)
+                            .and_then(|epithet| ctx.entity.maybe_coin_nickname(entity_id, epithet)),
+                    );
+                actions.add(40, witness.into());
+            }
+
+            GameEventKind::Feat { entity_id, epithet } => {
+                // Ignore this if its us
+                if *entity_id == ctx.entity.entity_id {
+                    return;
+                }
+
+                // Did we see what they just did?
+                // (we'll be talking about it either way)
+                let remember = ActorAction::StoreMeme(Meme::Witnessed(Witnessed::Feat {
+                    entity_id: entity_id.clone(),
+                    epithet: *epithet,
+                }));
+                let witness = Plan::new("witness feat")
+                    .also(remember)
+                    .then_maybe(ctx.entity.maybe_coin_nickname(entity_id, *epithet));
+                actions.add(40, witness.into());
+            }
+
+            GameEventKind::Trespass {
+                entity_id,
+                claimant,
+            } => {
+                // Ignore this if its us, or if we are busy
+                if *entity_id == ctx.entity.entity_id || ctx.focus != ActorFocus::Unfocused {
+                    return;
+                }
+
+                // We only care if its ours (or one of our allies)
+                let is_ours = *claimant == ctx.entity.entity_id
+                    || ctx.entity.relations.bond(claimant) >= ALLY_BOND;
+                if !is_ours || ctx.entity.relations.like(entity_id) {
+                    return;
+                }
+
+                // How we deal with them depends on who we are
+                if ctx
+                    .entity
+                    .characteristic(Characteristic::Aggression)
+                    .is_high()
+                {
+                    actions.add(
+                        40,
+                        ActorAction::ConfrontEntity {
+                            entity_id: entity_id.clone(),
+                        },
+                    );
+
+                    // or give them something to think about on their way out
+                    if let Some(throwable) = ctx.entity.best_throwable(ctx.entities) {
+                        actions.add(
+                            10,
+                            ActorAction::Throw {
+                                item: throwable.entity_id.clone(),
+                                target: ThrowTarget::Entity(entity_id.clone()),
+                            },
+                        );
+                    }
+                } else if ctx.entity.characteristic(Characteristic::Resolve).is_low() {
+                    actions.add(
+                        20,
+                        ActorAction::AvoidEntity {
+                            entity_id: entity_id.clone(),
+                        },
+                    );
+                } else {
+                    actions.add(
+                        20,
+                        ActorAction::Log {
+                            other: Some(entity_id.clone()),
+                            body: GameLogBody::EntityWarnTrespasser,
+                        },
+                    );
+                }
+            }
+
+            GameEventKind::Projectile { entity_id, hit } => {
+                // Ignore our own throws
+                if *entity_id == ctx.entity.entity_id {
+                    return;
+                }
+
+                // Being hit gets our attention no matter what we are doing,
+                // otherwise we only notice if we aren't busy
+                let was_hit = hit.as_ref() == Some(&ctx.entity.entity_id);
+                if !was_hit && ctx.focus != ActorFocus::Unfocused {
+                    return;
+                }
+
+                // The aggressive throw something back if they can
+                if was_hit
+                    && ctx
+                        .entity
+                        .characteristic(Characteristic::Aggression)
+                        .is_high()
+                {
+                    if let Some(throwable) = ctx.entity.best_throwable(ctx.entities) {
+                        actions.add(
+                            50,
+                            ActorAction::Throw {
+                                item: throwable.entity_id.clone(),
+                                target: ThrowTarget::Entity(entity_id.clone()),
+                            },
+                        );
+                        return;
+                    }
+                }
+
+                // Otherwise get out of there
+                // (unless we've got the nerve to stay put)
+                if was_hit || ctx.entity.characteristic(Characteristic::Resolve).is_low() {
+                    actions.add(
+                        if was_hit { 50 } else { 20 },
+                        ActorAction::FleeFrom {
+                            entity_id: entity_id.clone(),
+                        },
+                    );
+                }
+            }
+
+            GameEventKind::Attack { entity_id, victim } => {
+                // Being set upon gets our attention no matter what we are doing,
+                // otherwise we only notice if we aren't busy
+                let attacked = *victim == ctx.entity.entity_id;
+                if !attacked && ctx.focus != ActorFocus::Unfocused {
+                    return;
+                }
+
+                // Anyone with the nerve for it fights it off with whatever they've got
+                if !ctx.entity.characteristic(Characteristic::Resolve).is_low() {
+                    if let Some(throwable) = ctx.entity.best_throwable(ctx.entities) {
+                        actions.add(
+                            if attacked { 50 } else { 40 },
+                            ActorAction::Throw {
+                                item: throwable.entity_id.clone(),
+                                target: ThrowTarget::Entity(entity_id.clone()),
+                            },
+                        );
+                        return;
+                    }
+                }
+
+                // Otherwise get away from it
+                actions.add(
+                    if attacked { 60 } else { 30 },
+                    ActorAction::FleeFrom {
+                        entity_id: entity_id.clone(),
+                    },
+                );
+            }
+
+            GameEventKind::Warp { entity_id } => {
+                // The crew are used to it, and the busy don't notice
+                if has_markers!(ctx.entity, Crew) || ctx.focus != ActorFocus::Unfocused {
+                    return;
+                }
+
+                // Some are terrified of whatever these beings are
+                if ctx.entity.characteristic(Characteristic::Resolve).is_low() {
+                    actions.add(
+                        30,
+                        Plan::new("flee the crew")
+                            .then(ActorAction::Log {
+                                other: Some(entity_id.clone()),
+                                body: GameLogBody::EntityFrightenedByCrew,
+                            })
+                            .then(ActorAction::FleeFrom {
+                                entity_id: entity_id.clone(),
+                            })
+                            .into(),
+                    );
+                    return;
+                }
+
+                // but mostly it's quite the spectacle
+                actions.add(
+                    15,
+                    Plan::new("marvel at the crew")
+                        .then(ActorAction::Log {
+                            other: Some(entity_id.clone()),
+                            body: GameLogBody::EntityAwedByCrew,
+                        })
+                        .then(ActorAction::ReduceMotivator(MotivatorKey::Boredom))
+                        .into(),
+                );
+            }
+
+            GameEventKind::Distress { entity_id, plight } => {
+                // We can't help ourselves, and won't drop anything but wandering about to help others
+                if *entity_id == ctx.entity.entity_id
+                    || !matches!(
+                        ctx.focus,
+                        ActorFocus::Unfocused | ActorFocus::TravelTogether { .. }
+                    )
+                {
+                    return;
+                }
+
+                // (and only if we care enough about them)
+                let weight = ctx.entity.rescue_weight(entity_id);
+                let (Some(hex), Some(other_entity)) =
+                    (ctx.entity.attributes.hex, ctx.entities.by_id(entity_id))
+                else {
+                    return;
+                };
+                let Some(other_hex) = other_entity.attributes.hex.filter(|_| weight > 0) else {
+                    return;
+                };
+
+                // Get over there first
+                if other_hex != hex {
+                    actions.add(weight, ActorAction::GoTowardsHex(other_hex));
+                    return;
+                }
+
+                match plight {
+                    Plight::CaughtInHazard => {
+                        actions.add(
+                            weight,
+                            ActorAction::DragEntity {
+                                entity_id: entity_id.clone(),
+                            },
+                        );
+                    }
+                    Plight::AsleepInDanger => {
+                        actions.add(
+                            weight,
+                            ActorAction::StandGuard {
+                                entity_id: entity_id.clone(),
+                            },
+                        );
+                    }
+                    Plight::BadlyHurt => {
+                        // Patch them up if we can
+                        let inventory = ctx.entity.resolve_inventory(ctx.entities).collect_vec();
+                        if let Some(healing_entity) =
+                            inventory.iter().find(|e| e.attributes.healing.is_some())
+                        {
+                            actions.add(
+                                weight,
+                                ActorAction::UseItemOn {
+                                    item: healing_entity.entity_id.clone(),
+                                    target: entity_id.clone(),
+                                },
+                            );
+                        }
+
+                        // and give them something to eat if they need it more than we do
+                        let hunger = |e: &Entity| {
+                            e.attributes
+                                .motivators
+                                .get_motivation::<motivator::Hunger>()
+                                .unwrap_or_default()
+                        };
+                        let their_hunger = hunger(other_entity);
+                        if their_hunger > HUNGRY_ENOUGH_TO_SHARE
+                            && their_hunger > hunger(ctx.entity)
+                        {
+                            if let Some(food_entity) =
+                                inventory.iter().find(|e| e.attributes.food.is_some())
+                            {
+                                actions.add(
+                                    weight,
+                                    ActorAction::ShareFood {
+                                        entity_id: entity_id.clone(),
+                                        item: food_entity.entity_id.clone(),
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            GameEventKind::Memorial {
+                entity_id,
+                deceased,
+                name,
+            } => {
+                // Anyone with nothing better to do who cared for them joins in
+                if *entity_id == ctx.entity.entity_id
+                    || ctx.focus != ActorFocus::Unfocused
+                    || !(ctx.entity.is_grieving(deceased) || ctx.entity.relations.like(deceased))
+                {
+                    return;
+                }
+                actions.add(
+                    100,
+                    ActorAction::JoinMemorial {
+                        host: entity_id.clone(),
+                        deceased: deceased.clone(),
+                        name: name.clone(),
+                    },
+                );
+            }
+
             GameEventKind::Death { entity_id } => {
+                // However we take it, we won't forget it
+                let remember = ActorAction::StoreMeme(Meme::Witnessed(Witnessed::Death {
+                    entity_id: entity_id.clone(),
+                }));
+                let mut affected = false;
+
                 // Have a mini funeral?
                 let empathy = ctx.entity.characteristic(Characteristic::Empathy);
                 if empathy.is_high() || (ctx.entity.relations.like(entity_id) && !empathy.is_low())
                 {
+                    affected = true;
                     actions.add(
                         40, // too low?
-                        ActorAction::MournEntity {
-                            entity_id: entity_id.clone(),
-                        },
+                        Plan::new("mourn")
+                            .also(remember.clone())
+                            .then(ActorAction::MournEntity {
+                                entity_id: entity_id.clone(),
+                            })
+                            .into(),
                     );
                 }
 
                 // Or just be really upset about it?
                 // (but in a non personal way)
                 if ctx.entity.characteristic(Characteristic::Resolve).is_low() {
+                    affected = true;
                     actions.add(
                         40, // too low?
-                        ActorAction::Sequential(vec![
-                            ActorAction::Log {
+                        Plan::new("grieve")
+                            .also(remember.clone())
+                            .then(ActorAction::Log {
                                 other: None,
                                 body: GameLogBody::EntityUpsetByDeath,
-                            },
-                            ActorAction::Bark(1.0, MotivatorKey::Sadness),
-                            ActorAction::BumpMotivator(MotivatorKey::Sadness),
-                        ]),
+                            })
+                            .then(ActorAction::Bark(1.0, MotivatorKey::Sadness))
+                            .then(ActorAction::BumpMotivator(MotivatorKey::Sadness))
+                            .into(),
                     );
                 }
+
+                // Otherwise just take it in
+                if !affected {
+                    actions.add(40, remember);
+                }
             }
 
             GameEventKind::LeadDiscussion {
                 entity_id: interlocutor_id,
                 action,
             } => {
-                let mut rng = rand::rng();
-                let memes = ctx.entity.attributes.memes.as_ref().unwrap();
+                let mut rng = crate::rng::rng();
+                let Ok(memes) = ctx.entity.as_memes() else {
+                    return;
+                };
 
                 info!("Seeing lead discussion event {self:?}");
 
@@ -143,6 +526,7 @@ impl Signal for GameEvent {
                         actions.add(
                             50,
                             DiscussionAction::Respond(DiscussionRespondAction::GiveOpinion {
+                                entity_id: subject_id.clone(),
                                 opinion,
                             })
                             .into(),
@@ -162,6 +546,17 @@ impl Signal for GameEvent {
                                 .shelter_locations()
                                 .choose(&mut rng)
                                 .map(Meme::ShelterAt),
+                            // (dont gossip about the person we are talking to)
+                            InfoTopic::Gossip => memes
+                                .known_deeds()
+                                .chain(memes.known_nicknames())
+                                .filter(|meme| match meme {
+                                    Meme::Deed(id, _) | Meme::Nickname(id, _) => {
+                                        id != interlocutor_id
+                                    }
+                                    _ => true,
+                                })
+                                .choose(&mut rng),
                         };
 
                         // Then respond w/ that
@@ -175,6 +570,73 @@ impl Signal for GameEvent {
                         );
                     }
 
+                    DiscussionLeadAction::ProposeTrade { offer, want } => {
+                        // Need to know what we're trading
+                        let (Some(offer_entity), Some(want_entity)) =
+                            (ctx.entities.by_id(offer), ctx.entities.by_id(want))
+                        else {
+                            actions.add(
+                                50,
+                                DiscussionAction::Respond(DiscussionRespondAction::DeclineTrade)
+                                    .into(),
+                            );
+                            return;
+                        };
+
+                        // Is it a good deal? (or do we like them enough to not care?)
+                        let respond_action = if ctx.entity.would_accept_trade(
+                            offer_entity,
+                            want_entity,
+                            interlocutor_id,
+                        ) {
+                            DiscussionRespondAction::AcceptTrade {
+                                offer: offer.clone(),
+                                want: want.clone(),
+                            }
+                        } else {
+                            // If not, is there something else of ours we'd give up for what they offered?
+                            let counter = ctx
+                                .entity
+                                .resolve_inventory(ctx.entities)
+                                .filter(|e| &e.entity_id != want)
+                                .find(|e| {
+                                    ctx.entity
+                                        .would_accept_trade(offer_entity, e, interlocutor_id)
+                                });
+                            match counter {
+                                Some(counter_entity) => DiscussionRespondAction::CounterTrade {
+                                    offer: counter_entity.entity_id.clone(),
+                                    want: offer.clone(),
+                                },
+                                None => DiscussionRespondAction::DeclineTrade,
+                            }
+                        };
+
+                        actions.add(50, DiscussionAction::Respond(respond_action).into());
+                    }
+
+                    DiscussionLeadAction::RecallEvent { event } => {
+                        // Were we there too?
+                        actions.add(
+                            50,
+                            DiscussionAction::Respond(DiscussionRespondAction::ReactToEvent {
+                                event: event.clone(),
+                                saw_it: memes.has_witnessed(event),
+                            })
+                            .into(),
+                        );
+                    }
+
+                    DiscussionLeadAction::RememberLost { entity_id, .. } => {
+                        actions.add(
+                            50,
+                            DiscussionAction::Respond(DiscussionRespondAction::Console {
+                                grieving_too: ctx.entity.is_grieving(entity_id),
+                            })
+                            .into(),
+                        );
+                    }
+
                     DiscussionLeadAction::AskPersonal {
                         topic: personal_topic,
                     } => {
@@ -196,11 +658,10 @@ impl Signal for GameEvent {
                                 50,
                                 DiscussionAction::Respond(DiscussionRespondAction::Balk).into(),
                             );
-                        } else {
-                            let bg = ctx.entity.attributes.background.as_ref().unwrap();
+                        } else if let Ok(bg) = ctx.entity.as_background() {
                             let answer = match personal_topic {
-                                PersonalTopic::Fear => bg.fear.to_string(),
-                                PersonalTopic::Hope => bg.hope.to_string(),
+                                PersonalTopic::Fear => content().fear_text(&bg.fear),
+                                PersonalTopic::Hope => content().hope_text(&bg.hope),
                             };
 
                             actions.add(
@@ -227,25 +688,130 @@ impl Signal for GameEvent {
                             // TODO: maybe some people wouldn't be friendly?
                             actions.add(
                                 10000,
-                                ActorAction::Sequential(vec![
-                                    ActorAction::Log {
+                                Plan::new("thank them")
+                                    .then(ActorAction::Log {
                                         other: Some(entity_id.clone()),
                                         body: GameLogBody::EntityThank,
-                                    },
-                                    ActorAction::StoreMeme(meme.clone()),
-                                ]),
+                                    })
+                                    .then(ActorAction::StoreMeme(meme.clone()))
+                                    .into(),
                             );
                         }
                     }
 
-                    // TODO: FUTURE: update our opinion
-                    DiscussionRespondAction::GiveOpinion { opinion } => todo!(),
+                    // Take what they think on board
+                    // (how much it sways us depends on how much we trust them, see `assimilate_opinion`)
+                    DiscussionRespondAction::GiveOpinion {
+                        entity_id: subject_id,
+                        opinion,
+                    } => {
+                        actions.add(
+                            10000,
+                            ActorAction::HearOpinion {
+                                from: entity_id.clone(),
+                                about: subject_id.clone(),
+                                opinion: opinion.clone(),
+                            },
+                        );
+                    }
+
+                    // If they countered with something we'd be happy with, propose that instead
+                    DiscussionRespondAction::CounterTrade { offer, want } => {
+                        let (Some(offer_entity), Some(want_entity)) =
+                            (ctx.entities.by_id(offer), ctx.entities.by_id(want))
+                        else {
+                            return;
+                        };
+
+                        if ctx
+                            .entity
+                            .would_accept_trade(offer_entity, want_entity, entity_id)
+                        {
+                            actions.add(
+                                10000,
+                                DiscussionAction::Lead(DiscussionLeadAction::ProposeTrade {
+                                    offer: want.clone(),
+                                    want: offer.clone(),
+                                })
+                                .into(),
+                            );
+                        }
+                    }
 
                     // Not much for us to do in these cases tbh
                     DiscussionRespondAction::Balk
-                    | DiscussionRespondAction::GivePersonal { .. } => {}
+                    | DiscussionRespondAction::GivePersonal { .. }
+                    | DiscussionRespondAction::AcceptTrade { .. }
+                    | DiscussionRespondAction::DeclineTrade
+                    | DiscussionRespondAction::ReactToEvent { .. }
+                    | DiscussionRespondAction::Console { .. } => {}
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::brain::test_signal::SignalContextBuilder,
+        event::{builder::GameEventBuilder, GameEventTarget},
+        hex::AxialHex,
+        mtch::test_match::test_player,
+    };
+
+    #[test]
+    fn test_hearing_an_opinion_is_taken_on_board() {
+        let listener = test_player("Listener", AxialHex::ZERO);
+        let speaker = test_player("Speaker", AxialHex::ZERO);
+        let subject = test_player("Subject", AxialHex::ZERO);
+
+        let event = GameEventBuilder::new()
+            .targets(GameEventTarget::Entity(listener.entity_id.clone()))
+            .of_kind(GameEventKind::RespondDiscussion {
+                entity_id: speaker.entity_id.clone(),
+                action: DiscussionRespondAction::GiveOpinion {
+                    entity_id: subject.entity_id.clone(),
+                    opinion: Opinion::Negative,
+                },
+            })
+            .build();
+        let mut actions = SignalContextBuilder::new(listener)
+            .with_entity(speaker.clone())
+            .with_entity(subject.clone())
+            .act_on(&event);
+
+        let action = actions.sample(None, &mut crate::rng::rng());
+        assert!(matches!(
+            action,
+            ActorAction::HearOpinion { from, about, opinion: Opinion::Negative }
+                if from == speaker.entity_id && about == subject.entity_id
+        ));
+    }
+
+    #[test]
+    fn test_witnessing_doesnt_drown_out_everything_else() {
+        let witness = test_player("Witness", AxialHex::ZERO);
+        let subject = test_player("Subject", AxialHex::ZERO);
+        let ctx = SignalContextBuilder::new(witness.clone()).with_entity(subject.clone());
+        let witnessed = |kind: GameEventKind| {
+            let event = GameEventBuilder::new()
+                .targets(GameEventTarget::Entity(witness.entity_id.clone()))
+                .of_kind(kind)
+                .build();
+            ctx.act_on(&event).max_weight_of(|_| true)
+        };
+
+        // Taking it in is just one reaction among others
+        let feat = witnessed(GameEventKind::Feat {
+            entity_id: subject.entity_id.clone(),
+            epithet: Epithet::Sparky,
+        });
+        assert_eq!(feat, Some(40));
+        let death = witnessed(GameEventKind::Death {
+            entity_id: subject.entity_id.clone(),
+        });
+        assert_eq!(death, Some(40));
+    }
+}
@@ -0,0 +1,209 @@
+//! An Atom feed of the big moments in recent matches (starts, deaths and winners)
+//!
+//! This is rendered from what's been persisted, so fans can follow along in a feed reader (and
+//! other sites can embed it) without needing a client of their own. Like every public feed, it
+//! leaves out anything that has been moderated.
+
+use anyhow::Context;
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use itertools::Itertools;
+use sqlx::query_file;
+use tracing::warn;
+
+use crate::{
+    has_markers,
+    mtch::{
+        history::{latest_tick, state_at_tick},
+        moderation::Moderation,
+        MatchId,
+    },
+    Db, ServerCtx,
+};
+
+/// How many of the most recent matches are in the feed
+const FEED_MATCH_COUNT: i64 = 5;
+
+/// Identifies the feed (and prefixes the id of every entry)
+const FEED_ID: &str = "tag:abduction.giraugh.xyz,2025:feed";
+
+/// Something that happened, as an entry in the feed
+#[derive(Debug, Clone, PartialEq)]
+struct FeedEntry {
+    id: String,
+    title: String,
+    updated: DateTime<Utc>,
+    content: String,
+}
+
+/// Serve the feed
+pub async fn match_feed(ctx: ServerCtx) -> Response {
+    let moderation = ctx.moderation.read().await.clone();
+    match feed_entries(&ctx.db, &moderation).await {
+        Ok(entries) => (
+            [(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")],
+            render_atom(&entries),
+        )
+            .into_response(),
+        Err(err) => {
+            warn!("Failed to build match feed: {err:?}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+/// Every entry in the feed, newest first
+async fn feed_entries(db: &Db, moderation: &Moderation) -> anyhow::Result<Vec<FeedEntry>> {
+    let matches = query_file!("queries/get_recent_match_configs.sql", FEED_MATCH_COUNT)
+        .fetch_all(db)
+        .await
+        .context("Getting recent matches")?;
+
+    let mut entries = Vec::new();
+    for row in matches {
+        let match_id = row.match_id;
+        entries.push(FeedEntry {
+            id: format!("{FEED_ID}/{match_id}/start"),
+            title: "A new match has begun".to_owned(),
+            updated: row.created_at.and_utc(),
+            content: format!("Match {match_id} is underway, who will make it out?"),
+        });
+        entries.extend(death_entries(&match_id, db, moderation).await?);
+        if row.complete {
+            entries.extend(winner_entry(&match_id, db, moderation).await?);
+        }
+    }
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.updated));
+    Ok(entries)
+}
+
+async fn death_entries(
+    match_id: &MatchId,
+    db: &Db,
+    moderation: &Moderation,
+) -> anyhow::Result<Vec<FeedEntry>> {
+    let deaths = query_file!("queries/get_match_deaths.sql", match_id)
+        .fetch_all(db)
+        .await
+        .context("Getting match deaths")?;
+
+    let entries = deaths
+        .into_iter()
+        .filter_map(|row| {
+            let name = moderation.filter_name(&row.entity_id?, row.name?)?;
+            Some(FeedEntry {
+                id: format!("{FEED_ID}/{match_id}/death/{}", row.log_id),
+                title: format!("{name} has died"),
+                updated: row.timestamp.and_utc(),
+                content: format!("{name} didn't make it."),
+            })
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Whoever was left standing at the end of a match
+async fn winner_entry(
+    match_id: &MatchId,
+    db: &Db,
+    moderation: &Moderation,
+) -> anyhow::Result<Option<FeedEntry>> {
+    let tick_id = latest_tick(match_id, db).await?;
+    let entities = state_at_tick(match_id, tick_id, db).await?;
+    let winners = entities
+        .into_iter()
+        .filter(|e| has_markers!(e, Player))
+        .filter_map(|e| moderation.filter_name(&e.entity_id, e.name))
+        .collect_vec();
+
+    let ended_at: Option<NaiveDateTime> =
+        query_file!("queries/get_match_last_log_time.sql", match_id)
+            .fetch_one(db)
+            .await
+            .context("Getting end of match")?
+            .timestamp;
+    let Some(ended_at) = ended_at else {
+        return Ok(None);
+    };
+
+    let content = match winners.as_slice() {
+        [] => "Nobody made it out.".to_owned(),
+        [winner] => format!("{winner} is the last one standing!"),
+        winners => format!("{} made it to the end.", winners.join(", ")),
+    };
+    Ok(Some(FeedEntry {
+        id: format!("{FEED_ID}/{match_id}/end"),
+        title: "The match is over".to_owned(),
+        updated: ended_at.and_utc(),
+        content,
+    }))
+}
+
+/// Render some entries (newest first) as an Atom feed
+fn render_atom(entries: &[FeedEntry]) -> String {
+    let updated = entries
+        .first()
+        .map(|entry| entry.updated)
+        .unwrap_or_default();
+
+    let mut feed = String::new();
+    feed.push_str(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+    feed.push('\n');
+    feed.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+    feed.push('\n');
+    feed.push_str(&format!("  <id>{FEED_ID}</id>\n"));
+    feed.push_str("  <title>Abduction</title>\n");
+    feed.push_str(&format!("  <updated>{}</updated>\n", updated.to_rfc3339()));
+    feed.push_str("  <author><name>The Presenter</name></author>\n");
+    for entry in entries {
+        feed.push_str("  <entry>\n");
+        feed.push_str(&format!("    <id>{}</id>\n", escape_xml(&entry.id)));
+        feed.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        feed.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            entry.updated.to_rfc3339()
+        ));
+        feed.push_str(&format!(
+            "    <content type=\"text\">{}</content>\n",
+            escape_xml(&entry.content)
+        ));
+        feed.push_str("  </entry>\n");
+    }
+    feed.push_str("</feed>\n");
+    feed
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_atom_escapes_entries() {
+        let entry = FeedEntry {
+            id: format!("{FEED_ID}/match/death/1"),
+            title: "<Bobby> & \"friends\" has died".to_owned(),
+            updated: DateTime::from_timestamp(1_760_000_000, 0).unwrap(),
+            content: "They didn't make it.".to_owned(),
+        };
+        let feed = render_atom(&[entry]);
+        assert!(feed.starts_with("<?xml"));
+        assert!(feed.contains("<title>&lt;Bobby&gt; &amp; &quot;friends&quot; has died</title>"));
+        assert!(feed.contains("<updated>2025-10-09T08:53:20+00:00</updated>"));
+        assert_eq!(feed.matches("<entry>").count(), 1);
+    }
+}
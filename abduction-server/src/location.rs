@@ -22,6 +22,10 @@ pub struct LocPropGenerators {
     /// Each entity may be generated 0 or more times
     pub optional: Vec<PropGenerator>,
 
+    /// Each entity is generated at most once, with the given chance
+    /// (for things that should be sparse across the world)
+    pub rare: Vec<(PropGenerator, f64)>,
+
     /// When set, the maximum number of props for this tile
     /// (can be used to make a tile much more populated or much less)
     pub max_count: Option<usize>,
@@ -42,16 +46,34 @@ impl LocPropGenerators {
         self
     }
 
+    pub fn with_rare(mut self, generator: PropGenerator, chance: f64) -> Self {
+        self.rare.push((generator, chance));
+        self
+    }
+
     pub fn with_gen_count(mut self, count: usize) -> Self {
         self.max_count = Some(count);
         self
     }
 
-    pub fn generate_optional_at(&self, location: AxialHex, mut rng: &mut impl rand::Rng) -> Entity {
+    pub fn generate_optional_at(
+        &self,
+        location: AxialHex,
+        mut rng: &mut impl rand::Rng,
+    ) -> Vec<Entity> {
         let generator = self.optional.choose(&mut rng).unwrap();
-        let mut entity = generator.generate(&mut rng);
-        entity.attributes.hex = Some(location);
-        entity
+        generator.generate_at(location, rng)
+    }
+
+    /// Roll for each of the rare generators
+    pub fn generate_rare_at(&self, location: AxialHex, rng: &mut impl rand::Rng) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        for (generator, chance) in &self.rare {
+            if rng.random_bool(*chance) {
+                entities.extend(generator.generate_at(location, rng));
+            }
+        }
+        entities
     }
 }
 
@@ -77,6 +99,13 @@ pub enum LocationKind {
     SmallHut,
 }
 
+impl LocationKind {
+    /// Is this water that can't be walked into, only crossed on a raft? (see `brain::rafting`)
+    pub fn is_open_water(&self) -> bool {
+        matches!(self, LocationKind::Lake)
+    }
+}
+
 // Generation controls
 impl LocationKind {
     pub fn markers(&self) -> Vec<EntityMarker> {
@@ -128,35 +157,41 @@ impl LocationKind {
     pub fn prop_generators(&self) -> LocPropGenerators {
         use PropGenerator::*;
         match self {
-            // Plains are pretty barren
-            LocationKind::Plain => LocPropGenerators::default(),
+            // Plains are pretty barren, but stuff gets left lying around
+            LocationKind::Plain => LocPropGenerators::default().with_rare(AbandonedCrate, 0.05),
 
             // Hills have food but not water
             LocationKind::Hill => LocPropGenerators::default()
                 .with_optional(NaturalFood)
                 .with_optional(NaturalShelter)
+                .with_optional(BuildingMaterial)
+                .with_rare(AbandonedCrate, 0.05)
                 .with_gen_count(2),
 
-            // Forests are lush with lots of food and water
+            // Forests are lush with lots of food, water, medicinal plants and building material
             LocationKind::Forest => LocPropGenerators::default()
                 .with_optional(PossiblyPoisonousFood)
+                .with_optional(HealingHerb)
+                .with_optional(BuildingMaterial)
                 .with_optional(NaturalFood)
                 .with_optional(QualityNaturalWaterSource)
                 .with_optional(DubiousNaturalWaterSource)
+                .with_rare(HollowLog, 0.1)
                 .with_gen_count(8),
 
-            // Lakes always generate a lake water source and also food in the form of fish
+            // Lakes always generate a lake water source, fish have to be caught
             LocationKind::Lake => LocPropGenerators::default()
                 .with_required(Lake)
-                .with_optional(Fish),
+                .with_rare(HealingHerb, 0.3)
+                .with_rare(FishingRod, 0.15),
 
             // Mountiains are pretty barren but can have a mountain lake
             LocationKind::Mountain => LocPropGenerators::default()
                 .with_optional(QualityNaturalWaterSource)
                 .with_optional(NaturalShelter),
 
-            // Small Hut is a WIP
-            LocationKind::SmallHut => LocPropGenerators::none(),
+            // Small Hut is a WIP, but someone left their stuff there
+            LocationKind::SmallHut => LocPropGenerators::none().with_rare(AbandonedCrate, 0.8),
         }
     }
 }
@@ -173,12 +208,12 @@ impl Biome {
 pub fn generate_locations_for_world(world_radius: isize, biome: Biome) -> Vec<Entity> {
     // Generate an environment entity in each hex
     // For each, choose a random biome, weighted towards existing adjacent biomes if applicable
-    let mut rng = rand::rng();
+    let mut rng = crate::rng::rng();
     let mut locs_by_hex = HashMap::<AxialHex, LocationKind>::new();
     let biome_locs = biome.all_locations();
     let mut loc_entities = Vec::new();
 
-    let mut hexs = AxialHex::all_in_bounds(world_radius);
+    let mut hexs = AxialHex::spiral(AxialHex::ZERO, world_radius).collect_vec();
     hexs.shuffle(&mut rng);
     hexs.iter().for_each(|hex| {
         // Initialise weights to count of adjacent
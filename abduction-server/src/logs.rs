@@ -1,23 +1,92 @@
-use serde::Serialize;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{types::Json, SqliteExecutor};
+
+use itertools::Itertools;
 
 use crate::{
     entity::{
         brain::{
+            bark::BarkVariant,
             discussion::{DiscussionLeadAction, DiscussionRespondAction},
+            idle::IdleBehavior,
             motivator::MotivatorKey,
+            reputation::Deed,
         },
         world::{TimeOfDay, WeatherKind},
         Entity, EntityId,
     },
     hex::{AxialHex, AxialHexDirection},
+    mtch::{calendar::PartOfDay, intervention::Intervention, MatchId},
+    Db,
 };
 
-#[derive(Debug, Clone, Serialize)]
+/// How much a log matters, so clients can leave out the minor stuff
+/// (e.g a highlights-only mode for busy matches)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    strum::EnumIter,
+    strum::Display,
+)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum LogTier {
+    /// Background flavour, there's a lot of it
+    Ambient,
+
+    /// Something happened
+    #[default]
+    Normal,
+
+    /// Something worth tuning in for
+    Highlight,
+}
+
+/// Who a log is for, so clients following some part of the match can leave out the rest
+/// (e.g everything said around a campfire, without it drowning out the rest of the feed)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[qubit::ts]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LogChannel {
+    /// Anyone watching
+    #[default]
+    Global,
+
+    /// Anyone watching the hex it happened in (i.e conversations between a couple of players)
+    Local,
+
+    /// Anyone following some group (i.e everyone gathered around a campfire, by its id)
+    Group { group_id: EntityId },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[qubit::ts]
 pub struct GameLog {
     /// Optionally, somewhere this event happened
     pub hex: Option<AxialHex>,
 
+    /// How much this log matters
+    /// (logs saved before tiers existed are all normal)
+    #[serde(default)]
+    pub tier: LogTier,
+
+    /// Who it's for
+    /// (logs saved before channels existed are all global)
+    #[serde(default)]
+    pub channel: LogChannel,
+
     /// The entities involved
     /// Typically:
     ///   0 -> entity did an action
@@ -34,6 +103,8 @@ impl GameLog {
         Self {
             hex: None,
             involved_entities: vec![],
+            tier: body.tier(),
+            channel: LogChannel::Global,
             body,
         }
     }
@@ -42,6 +113,28 @@ impl GameLog {
         Self {
             hex: entity.attributes.hex,
             involved_entities: vec![entity.entity_id.clone()],
+            tier: body.tier(),
+            channel: LogChannel::Global,
+            body,
+        }
+    }
+
+    /// NOTE: uses hex from entity a
+    pub fn entity_triple(
+        entity_a: &Entity,
+        entity_b_id: impl AsEntityId,
+        entity_c_id: impl AsEntityId,
+        body: GameLogBody,
+    ) -> Self {
+        Self {
+            hex: entity_a.attributes.hex,
+            involved_entities: vec![
+                entity_a.entity_id.clone(),
+                entity_b_id.id().clone(),
+                entity_c_id.id().clone(),
+            ],
+            tier: body.tier(),
+            channel: LogChannel::Global,
             body,
         }
     }
@@ -51,9 +144,99 @@ impl GameLog {
         Self {
             hex: entity_a.attributes.hex,
             involved_entities: vec![entity_a.entity_id.clone(), entity_b_id.id().clone()],
+            tier: body.tier(),
+            channel: LogChannel::Global,
             body,
         }
     }
+
+    /// NOTE: uses hex from the entity
+    pub fn entity_group(
+        entity: &Entity,
+        others: impl IntoIterator<Item = impl AsEntityId>,
+        body: GameLogBody,
+    ) -> Self {
+        Self {
+            hex: entity.attributes.hex,
+            involved_entities: std::iter::once(entity.entity_id.clone())
+                .chain(others.into_iter().map(|other| other.id().clone()))
+                .collect(),
+            tier: body.tier(),
+            channel: LogChannel::Global,
+            body,
+        }
+    }
+
+    /// Override the usual tier for this kind of log
+    /// (for when it matters more or less than usual where it was sent)
+    pub fn with_tier(mut self, tier: LogTier) -> Self {
+        self.tier = tier;
+        self
+    }
+
+    pub fn with_channel(mut self, channel: LogChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Whether this log is in one of some channels (none -> every channel)
+    pub fn in_channels(&self, channels: Option<&[LogChannel]>) -> bool {
+        channels.is_none_or(|channels| channels.contains(&self.channel))
+    }
+
+    /// Whether this log matters enough to be shown when only showing logs of some tier and up
+    pub fn at_least(&self, min_tier: Option<LogTier>) -> bool {
+        min_tier.is_none_or(|min_tier| self.tier >= min_tier)
+    }
+
+    /// Persist this log against the match it happened in
+    pub async fn save(&self, match_id: &MatchId, db: &Db) -> anyhow::Result<()> {
+        let payload = serde_json::to_value(self).context("Serialising game log")?;
+        Self::save_raw(&payload, match_id, db).await
+    }
+
+    /// Load every log saved for a match, oldest first
+    /// NOTE: logs are left as raw json as they are only ever passed along as is
+    pub async fn load_from_match(match_id: &MatchId, db: &Db) -> anyhow::Result<Vec<Value>> {
+        let rows = sqlx::query_file!("queries/get_match_logs.sql", match_id)
+            .fetch_all(db)
+            .await
+            .context("Failed to load match logs")?;
+
+        Ok(rows.into_iter().map(|row| row.payload.0).collect())
+    }
+
+    /// Load the logs saved for a match of some tier and up, oldest first
+    pub async fn load_tiered_from_match(
+        match_id: &MatchId,
+        min_tier: Option<LogTier>,
+        db: &Db,
+    ) -> anyhow::Result<Vec<Self>> {
+        let logs = Self::load_from_match(match_id, db)
+            .await?
+            .into_iter()
+            .map(serde_json::from_value::<Self>)
+            .filter_ok(|log| log.at_least(min_tier))
+            .collect::<Result<Vec<_>, _>>()
+            .context("Parsing saved game logs")?;
+
+        Ok(logs)
+    }
+
+    /// Persist a raw (json) log against a match
+    pub async fn save_raw(
+        payload: &Value,
+        match_id: &MatchId,
+        db: impl SqliteExecutor<'_>,
+    ) -> anyhow::Result<()> {
+        let payload = Json(payload);
+        sqlx::query_file!("queries/add_match_log.sql", match_id, payload)
+            .execute(db)
+            .await
+            .context("Failed to persist game log to DB")?;
+
+        Ok(())
+    }
 }
 
 pub trait AsEntityId {
@@ -90,7 +273,16 @@ impl<T: AsRef<Entity>> AsEntityId for T {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    test,
+    derive(strum::EnumDiscriminants),
+    strum_discriminants(
+        name(GameLogKind),
+        derive(strum::VariantArray, strum::IntoStaticStr),
+        strum(serialize_all = "snake_case")
+    )
+)]
 #[qubit::ts]
 #[serde(tag = "kind", rename_all = "snake_case")]
 #[allow(unused)]
@@ -99,7 +291,16 @@ pub enum GameLogBody {
     EntityMovement { by: AxialHexDirection },
 
     /// The time of day changed
-    TimeOfDayChange { time_of_day: TimeOfDay },
+    /// (with the day and part of the day it now is, see `mtch::calendar`)
+    TimeOfDayChange {
+        time_of_day: TimeOfDay,
+
+        #[serde(default)]
+        day: usize,
+
+        #[serde(default)]
+        part_of_day: PartOfDay,
+    },
 
     /// The weather changed
     WeatherChange { weather: WeatherKind },
@@ -107,6 +308,10 @@ pub enum GameLogBody {
     /// An entity death
     EntityDeath,
 
+    /// The spirit of a dead player thinks back on what they hoped for
+    /// (expanded from their `Hope`)
+    SpiritWistful { hope: String },
+
     /// Primary entity greets a secondary entity
     /// Includes the bond between them (0 -> unknown before this, 0.5 -> have talked a few times, 1 -> friendly etc)
     EntityGreet { bond: f32, response: bool },
@@ -140,8 +345,42 @@ pub enum GameLogBody {
     /// Primary entity retrieves the secondary entity from their inventory
     EntityRetrieve,
 
+    /// Primary entity searches the secondary entity (a container) but doesn't find anything
+    /// (either because they missed it, or because its empty)
+    EntitySearchFail,
+
+    /// Primary entity searches a container and finds the secondary entity in it
+    EntitySearchFind,
+
+    /// Primary entity hides away a stash (the secondary entity) at their location
+    EntityCreateCache,
+
+    /// Primary entity puts the secondary entity in their stash
+    EntityDepositInCache,
+
+    /// Primary entity takes the secondary entity out of their stash
+    EntityWithdrawFromCache,
+
+    /// Primary entity raids the secondary entity (someone elses stash)
+    EntityRaidCache,
+
+    /// Primary entity notices the secondary entity (a prop) at their location
+    EntitySpotProp,
+
     /// Primary entity mourns the death of a corpse secondary entity,
-    EntityMournOverCorpse,
+    /// (remembering who they were, if anyone knew)
+    EntityMournOverCorpse { remembering: Option<String> },
+
+    /// Primary entity keeps the tertiary entity (a keepsake) from the secondary entity (a corpse)
+    /// to remember them by
+    EntityKeepKeepsake { description: String },
+
+    /// Primary entity holds a memorial for someone they lost (named, as they're gone)
+    /// (the secondary entity is their corpse, if it's still around)
+    EntityHoldMemorial { name: String },
+
+    /// Primary entity joins the secondary entity in remembering someone they lost
+    EntityJoinMemorial { name: String },
 
     /// Primary entity is saddened by death (does not point at the corpse entity)
     EntityUpsetByDeath,
@@ -149,6 +388,9 @@ pub enum GameLogBody {
     /// Lightning strikes the ground and creates a fire
     LightningStrike,
 
+    /// The primary entity (a fire) burned itself out
+    FireBurnOut,
+
     /// An entity letting it be known it has a high motivator e.g:
     ///  high boredom -> "John Smith lets out a big yawn"
     ///  high pain -> "John Smith winces in pain"
@@ -156,11 +398,28 @@ pub enum GameLogBody {
     EntityMotivatorBark {
         motivation: f32,
         motivator: MotivatorKey,
+
+        /// What they said/did, from the content file
+        /// (`{name}` stands in for their name)
+        line: Option<String>,
+
+        /// How the bark was flavoured by who they are (see `brain::bark`)
+        #[serde(default)]
+        bark_variant: Option<BarkVariant>,
     },
 
+    /// Primary entity does something to pass the time
+    EntityIdle { behavior: IdleBehavior },
+
     /// Primary entity was hit by lightning
     EntityHitByLightning,
 
+    /// The primary entity tried to go somewhere but couldn't make any headway in the weather
+    EntityHeldUpByWeather { weather: WeatherKind },
+
+    /// The primary entity lost their footing in the weather and went over
+    EntitySlip,
+
     /// Entity is warming up a bit in the sun
     EntityWarmBecauseOfTime,
 
@@ -201,18 +460,57 @@ pub enum GameLogBody {
     /// The primary entity consumed the secondary entity
     EntityConsume,
 
+    /// The primary entity starts fishing in the secondary entity (a lake)
+    EntityStartFishing,
+
+    /// The primary entity keeps waiting for a bite
+    EntityKeepFishing,
+
+    /// The primary entity caught the secondary entity (a fish)
+    EntityCatchFish,
+
+    /// The primary entity gives up on fishing without catching anything
+    EntityStopFishing,
+
     /// The primary entity took shelter in/at the secondary entity
     EntityTakeShelter,
 
     /// The primary entity left the shelter of the secondary entity
     EntityLeaveShelter,
 
+    /// The primary entity claims the secondary entity (a shelter) and its hex as theirs
+    EntityClaimHex,
+
+    /// The primary entity warns the secondary entity that they are trespassing
+    EntityWarnTrespasser,
+
+    /// The primary entity patched up the secondary entity (a shelter)
+    EntityRepairShelter,
+
+    /// The primary entity (a shelter) was worn down by weather or fire until it fell apart
+    ShelterCollapse,
+
+    /// The primary entity was hurt when the secondary entity (a shelter) collapsed on them
+    EntityHurtByCollapse,
+
     /// The primary entity warps in the secondary entity to some game hex
     EntityWarpIn,
 
     /// The primary entity warps out the secondary entity from some game hex
     EntityWarpOut,
 
+    /// The primary entity warps out the secondary entity (a corpse), who was `name`
+    EntityCollectRemains { name: String, summary: String },
+
+    /// The primary entity vanishes and reappears in some other hex
+    EntityTeleport,
+
+    /// The primary entity is in awe of something the secondary entity (crew) just did
+    EntityAwedByCrew,
+
+    /// The primary entity is frightened by something the secondary entity (crew) just did
+    EntityFrightenedByCrew,
+
     /// The primary entity says an exact quote
     /// used by crew
     EntitySayExact { quote: String },
@@ -220,6 +518,481 @@ pub enum GameLogBody {
     /// Primary entity thanks the secondary entity
     EntityThank,
 
+    /// Primary entity saw the secondary entity do something (good or bad)
+    EntityWitnessDeed { deed: Deed },
+
+    /// Primary entity is now known by a nickname
+    /// (enough of the people who know them call them it)
+    EntityNicknamed { nickname: String },
+
+    /// Primary entity tells off the secondary entity for their past deeds
+    EntityConfront,
+
+    /// Primary entity keeps away from the secondary entity because of their past deeds
+    EntityShun,
+
+    /// Primary entity throws the secondary entity
+    EntityThrow,
+
+    /// Primary entity hits the secondary entity with something they threw
+    EntityThrowHit,
+
+    /// Primary entity throws something at the secondary entity but misses
+    EntityThrowMiss,
+
+    /// Primary entity runs away from the secondary entity
+    EntityFlee,
+
+    /// Primary entity follows the tracks the secondary entity left
+    EntityFollowTracks,
+
+    /// Primary entity tries to follow the tracks the secondary entity left, but loses them
+    EntityLoseTracks,
+
+    /// Primary entity steals the tertiary entity from the secondary entity
+    EntitySteal,
+
+    /// Primary entity tries to steal from the secondary entity but gets caught
+    EntityStealFail,
+
+    /// Primary entity strips the tertiary entity (clothing) off the secondary entity (a corpse)
+    EntityStripCorpse,
+
+    /// Primary entity thinks twice about going through the secondary entity's (a corpse) things
+    EntityHesitateBeforeLoot,
+
+    /// Primary entity can't bring themselves to go through the secondary entity's (a corpse) things
+    EntityBalkAtLoot,
+
+    /// Primary entity takes the tertiary entity from what the secondary entity (a corpse) was carrying
+    EntityLootCorpse,
+
+    /// Primary entity comes across the tertiary entity (a keepsake) in the secondary entity's (a corpse) things
+    EntityFindKeepsake { description: String },
+
+    /// Primary entity sets off travelling together with the secondary entity
+    EntityStartTravelling,
+
+    /// Primary entity stops travelling with the secondary entity
+    EntityPartWays,
+
+    /// Primary entity drops the secondary entity (from their inventory) where they are
+    EntityDrop,
+
+    /// Primary entity treats their own wounds using the secondary entity
+    EntityHealSelf,
+
+    /// Primary entity treats the wounds of the secondary entity using the tertiary entity
+    EntityHealOther,
+
+    /// Primary entity drags the secondary entity out of harm's way
+    EntityDragOther,
+
+    /// Primary entity gives the secondary entity the tertiary entity (some food) to eat
+    EntityShareFood,
+
+    /// Primary entity starts keeping watch over the secondary entity while they sleep
+    EntityStartGuarding,
+
+    /// Primary entity keeps watching over the secondary entity
+    EntityKeepGuarding,
+
+    /// Primary entity stops keeping watch
+    EntityStopGuarding,
+
+    /// Primary entity gets the secondary entity (a campfire) going
+    EntityLightCampfire,
+
+    /// Primary entity settles in around the secondary entity (a campfire) with the others there
+    EntityJoinGathering,
+
+    /// Primary entity tells everyone else involved a story about their life before the abduction
+    /// (i.e "about growing up in Paris, France")
+    EntityTellStory { about: String },
+
+    /// Primary entity gets up from around the campfire
+    EntityLeaveGathering,
+
+    /// Primary entity lashes together the secondary entity (a raft) by the water
+    EntityBuildRaft,
+
+    /// Primary entity climbs aboard the secondary entity (a raft)
+    EntityBoardRaft,
+
+    /// Primary entity makes off with the secondary entity (a raft) that belonged to the tertiary entity
+    EntityTakeRaft,
+
+    /// Primary entity paddles the secondary entity (a raft) on across the water
+    EntityPaddleRaft,
+
+    /// Primary entity climbs off the secondary entity (a raft)
+    EntityDisembarkRaft,
+
+    /// The secondary entity (a raft) goes over in rough water, throwing the primary entity in
+    EntityCapsize,
+
     /// Entity A (a hazard) hurts entity B
     HazardHurt,
+
+    /// Primary entity (a predator) starts stalking the secondary entity
+    PredatorStalk,
+
+    /// Primary entity (a predator) loses interest in the secondary entity
+    PredatorGiveUp,
+
+    /// Primary entity (a predator) mauls the secondary entity
+    PredatorMaul,
+
+    /// Primary entity brings down the secondary entity (a predator)
+    EntityKillPredator,
+
+    /// Meteors rain down from the sky
+    MeteorShower,
+
+    /// Primary entity was hit by the secondary entity (a meteorite)
+    EntityHitByMeteor,
+
+    /// Lights dance across the night sky
+    Aurora,
+
+    /// A herd of animals stampedes across the world
+    AnimalStampede,
+
+    /// Primary entity was trampled by stampeding animals
+    EntityTrampled,
+
+    /// Low-lying parts of the world flood
+    Flood,
+
+    /// The floodwater recedes
+    FloodRecede,
+
+    /// Primary entity (an item on the ground) was washed away by floodwater
+    EntityWashedAway,
+
+    /// Primary entity gets out of the floodwater to higher ground
+    EntityEscapeFlood,
+
+    /// The primary entity (a water source) dried up in the long dry spell
+    WaterSourceDryUp,
+
+    /// Primary entity fits the tertiary entity (a part) into the secondary entity (an escape pod)
+    EntityFitPodPart,
+
+    /// Primary entity can't get the tertiary entity (a part) to fit the secondary entity (an escape pod)
+    EntityFumblePodRepair,
+
+    /// Primary entity gets the secondary entity (an escape pod) working and flies off in it
+    EntityEscapeInPod,
+
+    /// An operator reached into the match (to the primary entity, if any)
+    /// (see `mtch::intervention`)
+    DivineIntervention { intervention: Intervention },
+
+    /// Primary entity keeps doing the same thing
+    /// (stands in for `times` logs like `log` that were too close together, see `mtch::throttle`)
+    EntityRepeat { log: Box<GameLogBody>, times: usize },
+}
+
+impl GameLogBody {
+    /// How much this kind of log usually matters
+    /// (see `GameLog::with_tier` for when it doesn't)
+    pub fn tier(&self) -> LogTier {
+        use GameLogBody::*;
+        match self {
+            EntityMovement { .. }
+            | SpiritWistful { .. }
+            | EntityLoseInterest
+            | EntityIgnore
+            | EntityTrackBeing
+            | EntityAvoid
+            | EntityPickUp
+            | EntityRetrieve
+            | EntitySearchFail
+            | EntitySpotProp
+            | EntityMotivatorBark { .. }
+            | EntityIdle { .. }
+            | EntityWarmBecauseOfTime
+            | EntityColdBecauseOfTime
+            | EntitySaturatedBecauseOfRain
+            | EntityGoDownhill
+            | EntityGoToAdjacentLush
+            | EntityDrinkFrom
+            | EntityKeepSleeping
+            | EntityKeepFishing
+            | EntityKeepGuarding
+            | EntityStopGuarding
+            | EntityLeaveGathering
+            | EntityPaddleRaft
+            | EntityShun
+            | EntityFollowTracks
+            | EntityLoseTracks
+            | PredatorGiveUp
+            | EntityDrop
+            | FireBurnOut
+            | WaterSourceDryUp
+            | EntityHeldUpByWeather { .. } => LogTier::Ambient,
+
+            EntityDeath
+            | LightningStrike
+            | EntityHitByLightning
+            | EntityClaimHex
+            | ShelterCollapse
+            | EntityHurtByCollapse
+            | EntityWarpIn
+            | EntityWarpOut
+            | EntityCollectRemains { .. }
+            | EntityThrowHit
+            | EntitySteal
+            | EntityStealFail
+            | EntityStripCorpse
+            | EntityLootCorpse
+            | EntityRaidCache
+            | MeteorShower
+            | EntityHitByMeteor
+            | Aurora
+            | AnimalStampede
+            | EntityTrampled
+            | Flood
+            | PredatorMaul
+            | EntityKillPredator
+            | EntityDragOther
+            | EntityTellStory { .. }
+            | EntityTakeRaft
+            | EntityCapsize
+            | EntityEscapeInPod
+            | DivineIntervention { .. } => LogTier::Highlight,
+
+            EntityRepeat { log, .. } => log.tier(),
+
+            _ => LogTier::Normal,
+        }
+    }
+}
+
+#[cfg(test)]
+impl GameLogBody {
+    /// A log body of some kind with made up details
+    /// (so every kind can be checked, see `test_every_log_kind_round_trips`)
+    pub fn arbitrary(kind: GameLogKind) -> Self {
+        use crate::entity::brain::discussion::InfoTopic;
+        use GameLogBody::*;
+        match kind {
+            GameLogKind::EntityMovement => EntityMovement {
+                by: AxialHexDirection::East,
+            },
+            GameLogKind::TimeOfDayChange => TimeOfDayChange {
+                time_of_day: TimeOfDay::Night,
+                day: 3,
+                part_of_day: PartOfDay::Dusk,
+            },
+            GameLogKind::WeatherChange => WeatherChange {
+                weather: WeatherKind::HeavyRain,
+            },
+            GameLogKind::EntityDeath => EntityDeath,
+            GameLogKind::SpiritWistful => SpiritWistful {
+                hope: "to see the sea".into(),
+            },
+            GameLogKind::EntityGreet => EntityGreet {
+                bond: 0.5,
+                response: false,
+            },
+            GameLogKind::EntityFarewell => EntityFarewell,
+            GameLogKind::EntityAsk => EntityAsk {
+                ask: DiscussionLeadAction::AskForInfo {
+                    topic: InfoTopic::ShelterLocation,
+                },
+            },
+            GameLogKind::EntityRespond => EntityRespond {
+                respond: DiscussionRespondAction::Balk,
+            },
+            GameLogKind::EntityLoseInterest => EntityLoseInterest,
+            GameLogKind::EntityIgnore => EntityIgnore,
+            GameLogKind::EntityTrackBeing => EntityTrackBeing,
+            GameLogKind::EntityAvoid => EntityAvoid,
+            GameLogKind::EntityPickUp => EntityPickUp,
+            GameLogKind::EntityRetrieve => EntityRetrieve,
+            GameLogKind::EntitySearchFail => EntitySearchFail,
+            GameLogKind::EntitySearchFind => EntitySearchFind,
+            GameLogKind::EntityCreateCache => EntityCreateCache,
+            GameLogKind::EntityDepositInCache => EntityDepositInCache,
+            GameLogKind::EntityWithdrawFromCache => EntityWithdrawFromCache,
+            GameLogKind::EntityRaidCache => EntityRaidCache,
+            GameLogKind::EntitySpotProp => EntitySpotProp,
+            GameLogKind::EntityMournOverCorpse => EntityMournOverCorpse {
+                remembering: Some("a retired nurse from Lyon, France".into()),
+            },
+            GameLogKind::EntityKeepKeepsake => EntityKeepKeepsake {
+                description: "a locket from Lyon".into(),
+            },
+            GameLogKind::EntityHoldMemorial => EntityHoldMemorial {
+                name: "Ada Quinn".into(),
+            },
+            GameLogKind::EntityJoinMemorial => EntityJoinMemorial {
+                name: "Ada Quinn".into(),
+            },
+            GameLogKind::EntityUpsetByDeath => EntityUpsetByDeath,
+            GameLogKind::LightningStrike => LightningStrike,
+            GameLogKind::FireBurnOut => FireBurnOut,
+            GameLogKind::EntityMotivatorBark => EntityMotivatorBark {
+                motivation: 0.9,
+                motivator: MotivatorKey::Hunger,
+                line: Some("{name}'s stomach growls".into()),
+                bark_variant: Some(BarkVariant::Joking),
+            },
+            GameLogKind::EntityIdle => EntityIdle {
+                behavior: IdleBehavior::Whittle,
+            },
+            GameLogKind::EntityHitByLightning => EntityHitByLightning,
+            GameLogKind::EntityHeldUpByWeather => EntityHeldUpByWeather {
+                weather: WeatherKind::Hurricane,
+            },
+            GameLogKind::EntitySlip => EntitySlip,
+            GameLogKind::EntityWarmBecauseOfTime => EntityWarmBecauseOfTime,
+            GameLogKind::EntityColdBecauseOfTime => EntityColdBecauseOfTime,
+            GameLogKind::EntitySaturatedBecauseOfRain => EntitySaturatedBecauseOfRain,
+            GameLogKind::EntityGoDownhill => EntityGoDownhill,
+            GameLogKind::EntityGoToAdjacentLush => EntityGoToAdjacentLush,
+            GameLogKind::EntityFellInWaterSource => EntityFellInWaterSource,
+            GameLogKind::EntityComplainAboutTaste => EntityComplainAboutTaste,
+            GameLogKind::EntityDrinkFrom => EntityDrinkFrom,
+            GameLogKind::EntityStartSleeping => EntityStartSleeping,
+            GameLogKind::EntityKeepSleeping => EntityKeepSleeping,
+            GameLogKind::EntityStopSleeping => EntityStopSleeping,
+            GameLogKind::EntityHesitateBeforeConsume => EntityHesitateBeforeConsume,
+            GameLogKind::EntityConsume => EntityConsume,
+            GameLogKind::EntityStartFishing => EntityStartFishing,
+            GameLogKind::EntityKeepFishing => EntityKeepFishing,
+            GameLogKind::EntityCatchFish => EntityCatchFish,
+            GameLogKind::EntityStopFishing => EntityStopFishing,
+            GameLogKind::EntityTakeShelter => EntityTakeShelter,
+            GameLogKind::EntityLeaveShelter => EntityLeaveShelter,
+            GameLogKind::EntityClaimHex => EntityClaimHex,
+            GameLogKind::EntityWarnTrespasser => EntityWarnTrespasser,
+            GameLogKind::EntityRepairShelter => EntityRepairShelter,
+            GameLogKind::ShelterCollapse => ShelterCollapse,
+            GameLogKind::EntityHurtByCollapse => EntityHurtByCollapse,
+            GameLogKind::EntityWarpIn => EntityWarpIn,
+            GameLogKind::EntityWarpOut => EntityWarpOut,
+            GameLogKind::EntityCollectRemains => EntityCollectRemains {
+                name: "Jane Doe".into(),
+                summary: "a retired nurse from Lyon, France".into(),
+            },
+            GameLogKind::EntityTeleport => EntityTeleport,
+            GameLogKind::EntityAwedByCrew => EntityAwedByCrew,
+            GameLogKind::EntityFrightenedByCrew => EntityFrightenedByCrew,
+            GameLogKind::EntitySayExact => EntitySayExact {
+                quote: "Welcome back!".into(),
+            },
+            GameLogKind::EntityThank => EntityThank,
+            GameLogKind::EntityWitnessDeed => EntityWitnessDeed { deed: Deed::Theft },
+            GameLogKind::EntityNicknamed => EntityNicknamed {
+                nickname: "Sparky Smith".into(),
+            },
+            GameLogKind::EntityConfront => EntityConfront,
+            GameLogKind::EntityShun => EntityShun,
+            GameLogKind::EntityThrow => EntityThrow,
+            GameLogKind::EntityThrowHit => EntityThrowHit,
+            GameLogKind::EntityThrowMiss => EntityThrowMiss,
+            GameLogKind::EntityFlee => EntityFlee,
+            GameLogKind::EntityFollowTracks => EntityFollowTracks,
+            GameLogKind::EntityLoseTracks => EntityLoseTracks,
+            GameLogKind::EntitySteal => EntitySteal,
+            GameLogKind::EntityStealFail => EntityStealFail,
+            GameLogKind::EntityStripCorpse => EntityStripCorpse,
+            GameLogKind::EntityHesitateBeforeLoot => EntityHesitateBeforeLoot,
+            GameLogKind::EntityBalkAtLoot => EntityBalkAtLoot,
+            GameLogKind::EntityLootCorpse => EntityLootCorpse,
+            GameLogKind::EntityFindKeepsake => EntityFindKeepsake {
+                description: "a letter home, about how they hope to see the sea".into(),
+            },
+            GameLogKind::EntityStartTravelling => EntityStartTravelling,
+            GameLogKind::EntityPartWays => EntityPartWays,
+            GameLogKind::EntityDrop => EntityDrop,
+            GameLogKind::EntityHealSelf => EntityHealSelf,
+            GameLogKind::EntityHealOther => EntityHealOther,
+            GameLogKind::EntityDragOther => EntityDragOther,
+            GameLogKind::EntityShareFood => EntityShareFood,
+            GameLogKind::EntityStartGuarding => EntityStartGuarding,
+            GameLogKind::EntityKeepGuarding => EntityKeepGuarding,
+            GameLogKind::EntityStopGuarding => EntityStopGuarding,
+            GameLogKind::EntityLightCampfire => EntityLightCampfire,
+            GameLogKind::EntityJoinGathering => EntityJoinGathering,
+            GameLogKind::EntityTellStory => EntityTellStory {
+                about: "about growing up in Paris, France".into(),
+            },
+            GameLogKind::EntityLeaveGathering => EntityLeaveGathering,
+            GameLogKind::EntityBuildRaft => EntityBuildRaft,
+            GameLogKind::EntityBoardRaft => EntityBoardRaft,
+            GameLogKind::EntityTakeRaft => EntityTakeRaft,
+            GameLogKind::EntityPaddleRaft => EntityPaddleRaft,
+            GameLogKind::EntityDisembarkRaft => EntityDisembarkRaft,
+            GameLogKind::EntityCapsize => EntityCapsize,
+            GameLogKind::HazardHurt => HazardHurt,
+            GameLogKind::PredatorStalk => PredatorStalk,
+            GameLogKind::PredatorGiveUp => PredatorGiveUp,
+            GameLogKind::PredatorMaul => PredatorMaul,
+            GameLogKind::EntityKillPredator => EntityKillPredator,
+            GameLogKind::MeteorShower => MeteorShower,
+            GameLogKind::EntityHitByMeteor => EntityHitByMeteor,
+            GameLogKind::Aurora => Aurora,
+            GameLogKind::AnimalStampede => AnimalStampede,
+            GameLogKind::EntityTrampled => EntityTrampled,
+            GameLogKind::Flood => Flood,
+            GameLogKind::FloodRecede => FloodRecede,
+            GameLogKind::EntityWashedAway => EntityWashedAway,
+            GameLogKind::EntityEscapeFlood => EntityEscapeFlood,
+            GameLogKind::WaterSourceDryUp => WaterSourceDryUp,
+            GameLogKind::EntityFitPodPart => EntityFitPodPart,
+            GameLogKind::EntityFumblePodRepair => EntityFumblePodRepair,
+            GameLogKind::EntityEscapeInPod => EntityEscapeInPod,
+            GameLogKind::DivineIntervention => DivineIntervention {
+                intervention: Intervention::SetWeather {
+                    weather: WeatherKind::LightningStorm,
+                },
+            },
+            GameLogKind::EntityRepeat => EntityRepeat {
+                log: Box::new(EntityColdBecauseOfTime),
+                times: 3,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use strum::VariantArray;
+
+    use super::*;
+
+    #[test]
+    fn test_every_log_kind_round_trips() {
+        for kind in GameLogKind::VARIANTS {
+            let log = GameLog::global(GameLogBody::arbitrary(*kind));
+            let json = serde_json::to_value(&log).unwrap();
+            assert_eq!(
+                json["kind"],
+                <&str>::from(kind),
+                "{kind:?} has the wrong tag"
+            );
+
+            let parsed: GameLog = serde_json::from_value(json).unwrap();
+            assert_eq!(GameLogKind::from(&parsed.body), *kind);
+            assert_eq!(parsed.tier, log.tier);
+        }
+    }
+
+    /// NOTE: logs are only rendered by the site, so check it has a message for every kind
+    #[test]
+    fn test_site_renders_every_log_kind() {
+        let renderer = std::fs::read_to_string("../abduction-site/src/lib/logs.ts").expect(
+            "Failed to read the site's log renderer (is abduction-site next to the server?)",
+        );
+        let missing = GameLogKind::VARIANTS
+            .iter()
+            .map(<&str>::from)
+            .filter(|kind| !renderer.contains(&format!("log.kind === '{kind}'")))
+            .collect_vec();
+        assert!(missing.is_empty(), "No message for {missing:?}");
+    }
 }
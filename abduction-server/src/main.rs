@@ -1,13 +1,22 @@
+mod admin;
+mod balance;
+mod behavior;
 mod command;
+mod content;
 mod entity;
 mod event;
+mod feed;
 mod hex;
 mod location;
 mod logs;
 mod mtch;
+mod protocol;
+mod retention;
+mod rng;
 
 use axum::routing::get;
-use futures::{Stream, StreamExt};
+use chrono::Utc;
+use futures::{FutureExt, Stream, StreamExt};
 use qubit::{handler, TypeScript};
 use sqlx::{sqlite::SqliteConnectOptions, Pool, Sqlite, SqlitePool};
 use std::sync::atomic;
@@ -16,18 +25,48 @@ use tokio::fs;
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::error::RecvError;
 use tokio::time::{sleep, Duration};
-use tokio::{net::TcpListener, sync::Mutex};
+use tokio::{net::TcpListener, sync::RwLock};
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::TaskTracker;
 use tracing::{debug, info, level_filters::LevelFilter, warn};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
-use crate::command::process_stdin_commands;
-use crate::entity::Entity;
-use crate::logs::GameLog;
-use crate::mtch::{MatchConfig, MatchManager, TickEvent};
+use crate::admin::{Admin, AdminAction};
+use crate::command::{process_stdin_commands, serve_console, Command, CommandSource};
+use crate::entity::{
+    background::BackgroundManifest, migrate::migrate_entities, Entity, EntityId, EntityManager,
+    EntityMarker,
+};
+use crate::logs::{GameLog, LogChannel, LogTier};
+use crate::mtch::actor::{EntitiesPage, MatchHandle, ENTITIES_PAGE_SIZE};
+use crate::mtch::archive::MatchArchive;
+use crate::mtch::drama::FocusSuggestion;
+use crate::mtch::features::{alternate_experiments, FeatureFlag};
+use crate::mtch::focus_filter::FocusFilter;
+use crate::mtch::forecast::{self, Forecast};
+use crate::mtch::history::{changes_since, state_at_tick};
+use crate::mtch::moderation::{Moderation, ModerationTag};
+use crate::mtch::naming::NameVote;
+use crate::mtch::poll::{Poll, PollOption};
+use crate::mtch::prepare::{prepare_match, PrepareMatchArgs};
+use crate::mtch::preview::WorldPreview;
+use crate::mtch::requests::{PendingOn, RequestId};
+use crate::mtch::ruleset::MatchVariant;
+use crate::mtch::search::{self, EntitySearchResult};
+use crate::mtch::season::EntitySeasonHistory;
+use crate::mtch::season_config::{SeasonConfig, SeasonId, SeasonStanding};
+use crate::mtch::standings::{player_standings, PlayerStanding};
+use crate::mtch::stats::PlayerMatchStats;
+use crate::mtch::world_history::WorldTransition;
+use crate::mtch::{MatchConfig, MatchId, MatchManager, TickEvent, TickId};
+use crate::protocol::ProtocolInfo;
+use crate::retention::LogRetention;
+use crate::rng::AuditedRoll;
+use crate::{balance::BalanceManifest, behavior::reload_behavior, content::reload_content};
 
-const TICK_DELAY: Duration = Duration::from_millis(500);
+/// How many game logs can be in flight at once
+/// (a busy tick can send a lot of them, and they're all saved at the end of it)
+const LOG_CHANNEL_CAPACITY: usize = 512;
 
 #[cfg(feature = "dev")]
 const MATCH_COOLDOWN_DURATION: Duration = Duration::from_secs(1);
@@ -52,52 +91,741 @@ struct ServerCtx {
     db: Db,
 
     /// When a match is running,
-    /// the match manager for that match
-    match_manager: Arc<Mutex<Option<MatchManager>>>,
+    /// a way to get at it (see `MatchActor`)
+    current_match: MatchHandle,
 
     /// Flags that commands can set to change behaviour in ticks
     flags: Arc<CtxFlags>,
+
+    /// What admins have hidden from public feeds
+    moderation: Arc<RwLock<Moderation>>,
 }
 
 #[derive(Debug, Default)]
 struct CtxFlags {
     pub force_end_match: atomic::AtomicBool,
+
+    /// Write out how long each phase of every tick takes (see `TickTimings`)
+    pub profile_ticks: atomic::AtomicBool,
 }
 
 /// Get the current state of all entities
 #[handler(query)]
 async fn get_entity_states(ctx: ServerCtx) -> Option<Vec<Entity>> {
-    ctx.match_manager
-        .lock()
-        .await
-        .as_ref()
-        .map(MatchManager::all_entity_states)
+    let snapshot = ctx.current_match.snapshot()?;
+    Some(
+        ctx.moderation
+            .read()
+            .await
+            .filter_entities(snapshot.entities().cloned()),
+    )
+}
+
+/// Get the current state of some of the entities, starting after `cursor` (or from the start)
+/// (for loading a large world a page at a time, rather than with `get_entity_states`)
+/// Returns null if no current match
+#[handler(query)]
+async fn get_entities_page(ctx: ServerCtx, cursor: Option<EntityId>) -> Option<EntitiesPage> {
+    let snapshot = ctx.current_match.snapshot()?;
+    let (entities, next_cursor) = snapshot.entities_page(cursor.as_ref(), ENTITIES_PAGE_SIZE);
+    Some(EntitiesPage {
+        entities: ctx
+            .moderation
+            .read()
+            .await
+            .filter_entities(entities.iter().map(Arc::as_ref).cloned()),
+        next_cursor,
+        tick_id: snapshot.tick_id,
+    })
+}
+
+/// Find the entities (optionally only those with some markers) whose names or careers best match
+/// some query, best first
+/// Returns null if no current match
+#[handler(query)]
+async fn search_entities(
+    ctx: ServerCtx,
+    query: String,
+    markers: Option<Vec<EntityMarker>>,
+) -> Option<Vec<EntitySearchResult>> {
+    let snapshot = ctx.current_match.snapshot()?;
+    let moderation = ctx.moderation.read().await;
+    let results = search::search_entities(
+        snapshot
+            .entities()
+            .filter(|entity| !moderation.is_hidden(&entity.entity_id)),
+        &query,
+        &markers.unwrap_or_default(),
+    );
+
+    // (leaving out anything only found by a name that can't be shown)
+    Some(
+        results
+            .into_iter()
+            .filter(|result| {
+                moderation.filter_name(&result.entity_id, result.name.clone())
+                    == Some(result.name.clone())
+            })
+            .collect(),
+    )
 }
 
 /// Get the config for the current match
 /// Returns null if no current match
 #[handler(query)]
 async fn get_match_config(ctx: ServerCtx) -> Option<MatchConfig> {
-    ctx.match_manager
-        .lock()
+    ctx.current_match
+        .snapshot()
+        .map(|snapshot| snapshot.config.clone())
+}
+
+/// Get a small picture of the map of the current match (for showing it without loading any entities)
+/// Returns null if no current match
+#[handler(query)]
+async fn get_world_preview(ctx: ServerCtx) -> Option<WorldPreview> {
+    ctx.current_match
+        .snapshot()
+        .map(|snapshot| snapshot.world_preview.as_ref().clone())
+}
+
+/// Get the config for the next match, if it has been prepared but hasn't started yet
+/// Returns null if there isn't one
+#[handler(query)]
+async fn get_upcoming_match(ctx: ServerCtx) -> Option<MatchConfig> {
+    match MatchConfig::get_upcoming(&ctx.db).await {
+        Ok(config) => config,
+        Err(err) => {
+            warn!("Failed to get upcoming match: {err:?}");
+            None
+        }
+    }
+}
+
+/// Get the players (incl. their backgrounds) lined up for a match that hasn't started yet
+/// Returns null if the match has already started (or couldn't be found)
+#[handler(query)]
+async fn get_match_roster(ctx: ServerCtx, match_id: MatchId) -> Option<Vec<Entity>> {
+    let config = match MatchConfig::get(&ctx.db, match_id.clone()).await {
+        Ok(config) => config,
+        Err(err) => {
+            warn!("Failed to get roster for match {match_id}: {err:?}");
+            return None;
+        }
+    };
+    if config.started {
+        return None;
+    }
+
+    let players = EntityManager::load_entities_from_match(&match_id, &ctx.db)
         .await
-        .as_ref()
-        .map(|mm| mm.config.clone())
+        .filter(|e| has_markers!(e, Player));
+    Some(ctx.moderation.read().await.filter_entities(players))
+}
+
+/// Get the seasons some contestant played before this one
+/// Returns null if they aren't in the current or upcoming match (or haven't played before)
+#[handler(query)]
+async fn get_entity_history(ctx: ServerCtx, entity_id: EntityId) -> Option<EntitySeasonHistory> {
+    let moderation = ctx.moderation.read().await;
+    if moderation.is_hidden(&entity_id) {
+        return None;
+    }
+
+    let current = ctx
+        .current_match
+        .snapshot()
+        .and_then(|snapshot| snapshot.entity(&entity_id).cloned());
+    let entity = match current {
+        Some(entity) => entity,
+        None => {
+            let upcoming = match MatchConfig::get_upcoming(&ctx.db).await {
+                Ok(upcoming) => upcoming?,
+                Err(err) => {
+                    warn!("Failed to get upcoming match: {err:?}");
+                    return None;
+                }
+            };
+            EntityManager::load_entities_from_match(&upcoming.match_id, &ctx.db)
+                .await
+                .find(|e| e.entity_id == entity_id)?
+        }
+    };
+
+    // (leaving out anyone hidden they used to know)
+    let mut history = entity.attributes.season_history?;
+    for season in &mut history.seasons {
+        season
+            .bonds
+            .retain(|bond| !moderation.is_hidden(&bond.entity_id));
+    }
+    Some(history)
+}
+
+/// Get the config for the current season (the one the latest match is part of)
+/// Returns null if there hasn't been one
+#[handler(query)]
+async fn get_current_season(ctx: ServerCtx) -> Option<SeasonConfig> {
+    match SeasonConfig::get_latest(&ctx.db).await {
+        Ok(season) => season,
+        Err(err) => {
+            warn!("Failed to get current season: {err:?}");
+            None
+        }
+    }
+}
+
+/// Get how everyone has done over the finished matches of a season (best first)
+/// Returns null if the season couldn't be found
+#[handler(query)]
+async fn get_season_standings(ctx: ServerCtx, season_id: SeasonId) -> Option<Vec<SeasonStanding>> {
+    let standings = match SeasonConfig::get(&ctx.db, season_id.clone()).await {
+        Ok(Some(season)) => season.standings(&ctx.db).await,
+        Ok(None) => return None,
+        Err(err) => Err(err),
+    };
+    match standings {
+        Ok(standings) => {
+            let moderation = ctx.moderation.read().await;
+            Some(
+                standings
+                    .into_iter()
+                    .filter_map(|standing| {
+                        let name = moderation.filter_name(&standing.entity_id, standing.name)?;
+                        Some(SeasonStanding { name, ..standing })
+                    })
+                    .collect(),
+            )
+        }
+        Err(err) => {
+            warn!("Failed to get standings for season {season_id}: {err:?}");
+            None
+        }
+    }
+}
+
+/// Get the entities most worth watching right now (most dramatic first)
+/// Returns null if no current match
+#[handler(query)]
+async fn get_focus_suggestions(ctx: ServerCtx) -> Option<Vec<FocusSuggestion>> {
+    let mut suggestions = ctx.current_match.snapshot()?.focus_suggestions.clone();
+    let moderation = ctx.moderation.read().await;
+    suggestions.retain(|suggestion| !moderation.is_hidden(&suggestion.entity_id));
+    Some(suggestions)
+}
+
+/// Get the standings (incl. reputation) of every player still in the match
+/// Returns null if no current match
+#[handler(query)]
+async fn get_standings(ctx: ServerCtx) -> Option<Vec<PlayerStanding>> {
+    let snapshot = ctx.current_match.snapshot()?;
+    let entities = ctx
+        .moderation
+        .read()
+        .await
+        .filter_entities(snapshot.entities().cloned());
+    Some(player_standings(entities.iter()))
+}
+
+/// Get how the weather and time of day changed over a match, in order
+/// Returns null if it couldn't be loaded
+#[handler(query)]
+async fn get_weather_history(ctx: ServerCtx, match_id: MatchId) -> Option<Vec<WorldTransition>> {
+    match WorldTransition::load_for_match(&match_id, &ctx.db).await {
+        Ok(history) => Some(history),
+        Err(err) => {
+            warn!("Failed to get weather history for match {match_id}: {err:?}");
+            None
+        }
+    }
+}
+
+/// Get what some player got up to in a match
+/// Returns null if they haven't done anything (or it couldn't be loaded)
+#[handler(query)]
+async fn get_player_stats(
+    ctx: ServerCtx,
+    match_id: MatchId,
+    entity_id: EntityId,
+) -> Option<PlayerMatchStats> {
+    if ctx.moderation.read().await.is_hidden(&entity_id) {
+        return None;
+    }
+    match PlayerMatchStats::load(&match_id, &entity_id, &ctx.db).await {
+        Ok(stats) => stats,
+        Err(err) => {
+            warn!("Failed to get stats for {entity_id} in match {match_id}: {err:?}");
+            None
+        }
+    }
+}
+
+/// Get the state of every entity in a match as it was at the end of some tick
+/// (so clients can scrub back through a match)
+/// Returns null if it couldn't be reconstructed
+#[handler(query)]
+async fn get_state_at_tick(
+    ctx: ServerCtx,
+    match_id: MatchId,
+    tick_id: TickId,
+) -> Option<Vec<Entity>> {
+    match state_at_tick(&match_id, tick_id, &ctx.db).await {
+        Ok(entities) => Some(ctx.moderation.read().await.filter_entities(entities)),
+        Err(err) => {
+            warn!("Failed to get state of match {match_id} at tick {tick_id}: {err:?}");
+            None
+        }
+    }
+}
+
+/// Get the logs from a match, oldest first
+/// (only those of `min_tier` and up, if given)
+/// Returns null if they couldn't be loaded
+#[handler(query)]
+async fn get_match_logs(
+    ctx: ServerCtx,
+    match_id: MatchId,
+    min_tier: Option<LogTier>,
+) -> Option<Vec<GameLog>> {
+    match GameLog::load_tiered_from_match(&match_id, min_tier, &ctx.db).await {
+        Ok(logs) => {
+            let moderation = ctx.moderation.read().await;
+            Some(
+                logs.into_iter()
+                    .filter_map(|log| moderation.filter_log(log))
+                    .collect(),
+            )
+        }
+        Err(err) => {
+            warn!("Failed to get logs for match {match_id}: {err:?}");
+            None
+        }
+    }
+}
+
+/// Export a match (config, entity mutations, logs and events) to a compressed archive on disk
+/// Returns the path of the archive, or null if the key isn't an admin's or it couldn't be exported
+#[handler(mutation)]
+async fn export_match(ctx: ServerCtx, admin_key: String, match_id: MatchId) -> Option<String> {
+    let Some(admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected export with an unknown admin key");
+        return None;
+    };
+    match MatchArchive::export(match_id.clone(), &ctx.db).await {
+        Ok(path) => {
+            admin
+                .record(&ctx, AdminAction::ExportMatch { match_id })
+                .await;
+            Some(path.display().to_string())
+        }
+        Err(err) => {
+            warn!("Failed to export match {match_id}: {err:?}");
+            None
+        }
+    }
+}
+
+/// Get the open votes on naming landmarks and props
+/// Returns null if no current match
+#[handler(query)]
+async fn get_name_votes(ctx: ServerCtx) -> Option<Vec<NameVote>> {
+    let votes = ctx.current_match.with(|mm| mm.naming.open_votes()).await?;
+    let moderation = ctx.moderation.read().await;
+    Some(
+        votes
+            .into_iter()
+            .filter_map(|vote| moderation.filter_name_vote(vote))
+            .collect(),
+    )
+}
+
+/// Suggest a name for a landmark or prop, opening a vote on it if there isn't one
+/// Returns an id for the suggestion if it was accepted
+/// (a `RequestApplied` event is sent with it once the vote has closed, see `requests`)
+#[handler(mutation)]
+async fn suggest_name(ctx: ServerCtx, entity_id: EntityId, name: String) -> Option<RequestId> {
+    ctx.current_match
+        .with(move |mm| {
+            let entity = mm.entities.get_entity(&entity_id)?;
+            match mm.naming.suggest(&entity, &name) {
+                Ok(()) => {
+                    mm.audience.record_interaction();
+                    Some(mm.requests.add(PendingOn::NameVote(entity_id)))
+                }
+                Err(err) => {
+                    info!("Rejected name suggestion for {entity_id}: {err}");
+                    None
+                }
+            }
+        })
+        .await
+        .flatten()
+}
+
+/// Vote for a name that has been suggested for a landmark or prop
+/// Returns an id for the vote if it was counted
+/// (a `RequestApplied` event is sent with it once the vote has closed, see `requests`)
+#[handler(mutation)]
+async fn vote_name(ctx: ServerCtx, entity_id: EntityId, name: String) -> Option<RequestId> {
+    ctx.current_match
+        .with(move |mm| match mm.naming.vote(&entity_id, &name) {
+            Ok(()) => {
+                mm.audience.record_interaction();
+                Some(mm.requests.add(PendingOn::NameVote(entity_id)))
+            }
+            Err(err) => {
+                info!("Rejected name vote for {entity_id}: {err}");
+                None
+            }
+        })
+        .await
+        .flatten()
+}
+
+/// Get the open viewer poll
+/// Returns null if there isn't one (or no current match)
+#[handler(query)]
+async fn get_poll(ctx: ServerCtx) -> Option<Poll> {
+    ctx.current_match
+        .with(|mm| mm.poll.current())
+        .await
+        .flatten()
+}
+
+/// Open a viewer poll on what should happen next
+/// Returns whether the poll was started (only admins can start one)
+#[handler(mutation)]
+async fn start_poll(ctx: ServerCtx, admin_key: String, options: Vec<PollOption>) -> bool {
+    let Some(admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected poll with an unknown admin key");
+        return false;
+    };
+    let started = ctx
+        .current_match
+        .with({
+            let options = options.clone();
+            move |mm| match mm.poll.start(options) {
+                Ok(()) => true,
+                Err(err) => {
+                    info!("Rejected poll: {err}");
+                    false
+                }
+            }
+        })
+        .await
+        .unwrap_or(false);
+    if started {
+        admin.record(&ctx, AdminAction::StartPoll { options }).await;
+    }
+    started
+}
+
+/// Vote for an option in the open viewer poll
+/// Returns an id for the vote if it was counted
+/// (a `RequestApplied` event is sent with it once the poll has closed, see `requests`)
+#[handler(mutation)]
+async fn vote_poll(ctx: ServerCtx, option: PollOption) -> Option<RequestId> {
+    ctx.current_match
+        .with(move |mm| match mm.poll.vote(option) {
+            Ok(()) => {
+                mm.audience.record_interaction();
+                Some(mm.requests.add(PendingOn::Poll))
+            }
+            Err(err) => {
+                info!("Rejected poll vote: {err}");
+                None
+            }
+        })
+        .await
+        .flatten()
+}
+
+/// Reload flavour content (barks etc) and behaviour config from disk
+/// Returns whether it was reloaded, if not (or the key isn't an admin's) the previous content is kept
+#[handler(mutation)]
+async fn admin_reload_content(ctx: ServerCtx, admin_key: String) -> bool {
+    let Some(admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected content reload with an unknown admin key");
+        return false;
+    };
+    match reload_content().and_then(|_| reload_behavior()) {
+        Ok(()) => {
+            admin.record(&ctx, AdminAction::ReloadContent).await;
+            true
+        }
+        Err(err) => {
+            warn!("Failed to reload content: {err:?}");
+            false
+        }
+    }
+}
+
+/// Check the player data files are all there
+/// Returns whether they are, if not players are generated from a small embedded set of data
+#[handler(query)]
+async fn validate_player_data(_ctx: ServerCtx) -> bool {
+    match entity::generate::validate_player_data() {
+        Ok(()) => true,
+        Err(err) => {
+            warn!("Invalid player data: {err:?}");
+            false
+        }
+    }
+}
+
+/// Get everything that decides how the simulation is balanced (see `BalanceManifest`)
+/// (i.e for the "how it works" page, so it's always up to date with the live tuning)
+#[handler(query)]
+async fn get_balance_manifest(_ctx: ServerCtx) -> BalanceManifest {
+    BalanceManifest::current()
+}
+
+/// Get which version of the wire protocol the server speaks, and the oldest it still serves
+/// (see `protocol`, i.e so a client can tell whether it needs updating before subscribing)
+#[handler(query)]
+async fn get_protocol(_ctx: ServerCtx) -> ProtocolInfo {
+    ProtocolInfo::current()
+}
+
+/// Get the display text for every career, fear and hope id (see `BackgroundManifest`)
+#[handler(query)]
+async fn get_background_manifest(_ctx: ServerCtx) -> BackgroundManifest {
+    BackgroundManifest::current()
+}
+
+/// Check a match for some number of players can be sized with the current sizing config
+/// Returns what's wrong with it, if anything (a match that can't be sized gets a default radius)
+#[handler(query)]
+async fn admin_validate_match_sizing(_ctx: ServerCtx, player_count: usize) -> Vec<String> {
+    behavior::behavior().sizing.problems(player_count)
+}
+
+/// Significant random rolls made during some tick (only recorded when started with `--audit-rolls`)
+/// Returns none if rolls aren't being audited, and no rolls if the tick is too old
+#[handler(query)]
+async fn debug_tick_rolls(_ctx: ServerCtx, tick_id: TickId) -> Option<Vec<AuditedRoll>> {
+    rng::tick_rolls(tick_id)
+}
+
+/// Tag an entity in the current match for moderation
+/// Returns whether it was tagged (only admins can tag entities)
+#[handler(mutation)]
+async fn admin_tag_entity(
+    ctx: ServerCtx,
+    admin_key: String,
+    entity_id: EntityId,
+    tag: ModerationTag,
+) -> bool {
+    let Some(admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected tag with an unknown admin key");
+        return false;
+    };
+    let Some(snapshot) = ctx.current_match.snapshot() else {
+        return false;
+    };
+    let Some(entity) = snapshot.entity(&entity_id) else {
+        return false;
+    };
+    ctx.moderation.write().await.tag_entity(entity, tag);
+    info!("Tagged {entity_id} as {tag:?}");
+    admin
+        .record(&ctx, AdminAction::TagEntity { entity_id, tag })
+        .await;
+    true
+}
+
+/// Remove the moderation tag from an entity
+/// Returns whether it was tagged (only admins can untag entities)
+#[handler(mutation)]
+async fn admin_untag_entity(ctx: ServerCtx, admin_key: String, entity_id: EntityId) -> bool {
+    let Some(admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected untag with an unknown admin key");
+        return false;
+    };
+    let untagged = ctx.moderation.write().await.untag_entity(&entity_id);
+    match untagged {
+        Ok(()) => {
+            admin
+                .record(&ctx, AdminAction::UntagEntity { entity_id })
+                .await;
+            true
+        }
+        Err(err) => {
+            info!("Failed to untag: {err}");
+            false
+        }
+    }
+}
+
+/// Hide any logs (or names etc) mentioning some phrase
+/// Returns whether it was blocked (only admins can block phrases)
+#[handler(mutation)]
+async fn admin_block_phrase(ctx: ServerCtx, admin_key: String, phrase: String) -> bool {
+    let Some(admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected phrase block with an unknown admin key");
+        return false;
+    };
+    let blocked = ctx.moderation.write().await.block_phrase(&phrase);
+    match blocked {
+        Ok(()) => {
+            admin
+                .record(&ctx, AdminAction::BlockPhrase { phrase })
+                .await;
+            true
+        }
+        Err(err) => {
+            info!("Failed to block phrase: {err}");
+            false
+        }
+    }
+}
+
+/// Play the current match forward some number of ticks, without any of it being kept, to see
+/// how it's likely to go (i.e to decide whether to step in, see `mtch::forecast`)
+/// Returns none if there is no current match (or the key isn't an admin's)
+#[handler(query)]
+async fn admin_forecast(ctx: ServerCtx, admin_key: String, ticks: usize) -> Option<Forecast> {
+    let Some(_admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected forecast with an unknown admin key");
+        return None;
+    };
+    let copy = ctx.current_match.with(|mm| mm.dry_run_copy()).await?;
+    Some(forecast::forecast(copy, ticks, &ctx).await)
+}
+
+/// Run an operator command (the same as the console accepts, e.g `kill <entity>`)
+/// Returns what happened, or null if the key isn't an admin's or the command was invalid or failed
+/// (importing matches isn't allowed from here, see `Command::handle`)
+#[handler(mutation)]
+async fn admin_run_command(ctx: ServerCtx, admin_key: String, command: String) -> Option<String> {
+    let Some(admin) = Admin::authenticate(&admin_key) else {
+        warn!("Rejected command with an unknown admin key");
+        return None;
+    };
+    let command = match Command::parse(&command) {
+        Ok(command) => command,
+        Err(err) => {
+            info!("Rejected command: {err}");
+            return None;
+        }
+    };
+    match command.handle(ctx, CommandSource::Rpc(admin)).await {
+        Ok(reply) => Some(reply),
+        Err(err) => {
+            warn!("Command failed: {err:?}");
+            None
+        }
+    }
 }
 
 /// Get a stream of all tick events
+/// (with only the entity changes in `focus`, if given)
+///
+/// Clients should say which `protocol_version` they speak, the stream starts with a `Heartbeat`
+/// saying which the server speaks (or just `ProtocolUnsupported` if the client is too old)
 #[handler(subscription)]
-async fn events_stream(ctx: ServerCtx) -> impl Stream<Item = TickEvent> {
+async fn events_stream(
+    ctx: ServerCtx,
+    focus: Option<FocusFilter>,
+    protocol_version: Option<u32>,
+) -> impl Stream<Item = TickEvent> {
     let stream = tokio_stream::wrappers::BroadcastStream::new(ctx.tick_tx.subscribe());
-    stream.filter_map(|e| async { e.ok() })
+    let focus = focus.unwrap_or_default();
+    protocol::greet(
+        protocol_version,
+        stream.filter_map(move |e| {
+            let moderation = ctx.moderation.clone();
+            let event = e.ok().and_then(|event| focus.filter_tick_event(event));
+            async move { moderation.read().await.filter_tick_event(event?) }
+        }),
+    )
+}
+
+/// Get a stream of tick events that first catches up on what changed since the client last saw
+/// (i.e after reconnecting, so it doesn't need to get every entity again)
+///  - if `match_id` is the current match, replays what changed after `since_tick`
+///  - otherwise, replays every entity in the current match
+///
+/// Once it's replayed everything it sends `CaughtUp`, then carries on like `events_stream`
+/// (if it couldn't replay anything it never sends `CaughtUp`, and the client should start over)
+///
+/// Like `events_stream`, it starts with a `Heartbeat` (see `protocol`)
+#[handler(subscription)]
+async fn resume_stream(
+    ctx: ServerCtx,
+    match_id: MatchId,
+    since_tick: TickId,
+    focus: Option<FocusFilter>,
+    protocol_version: Option<u32>,
+) -> impl Stream<Item = TickEvent> {
+    if !protocol::is_supported(protocol_version) {
+        return protocol::greet(protocol_version, futures::stream::empty());
+    }
+
+    // Start listening on the match actor, so it can't tick between replaying and going live
+    // (every change up to its current tick is already in the db)
+    let replaying = ctx.current_match.run({
+        let ctx = ctx.clone();
+        move |mm| {
+            async move {
+                let live = tokio_stream::wrappers::BroadcastStream::new(ctx.tick_tx.subscribe());
+                let mut replay = Vec::new();
+                let current_match_id = &mm.config.match_id;
+                let after = (*current_match_id == match_id).then_some(since_tick);
+                match changes_since(current_match_id, after, mm.tick_id, &ctx.db).await {
+                    Ok(changes) => {
+                        replay.push(TickEvent::EntityChanges { changes });
+                        replay.push(TickEvent::CaughtUp {
+                            tick_id: mm.tick_id,
+                        });
+                    }
+                    Err(err) => warn!("Failed to replay changes since tick {since_tick}: {err:?}"),
+                }
+                (live, replay)
+            }
+            .boxed()
+        }
+    });
+    // (with no match running there's nothing to replay)
+    let (live, replay) = match replaying.await {
+        Some(replaying) => replaying,
+        None => (
+            tokio_stream::wrappers::BroadcastStream::new(ctx.tick_tx.subscribe()),
+            Vec::new(),
+        ),
+    };
+
+    let focus = focus.unwrap_or_default();
+    protocol::greet(
+        protocol_version,
+        futures::stream::iter(replay.into_iter().map(Ok))
+            .chain(live)
+            .filter_map(move |e| {
+                let moderation = ctx.moderation.clone();
+                let event = e.ok().and_then(|event| focus.filter_tick_event(event));
+                async move { moderation.read().await.filter_tick_event(event?) }
+            }),
+    )
 }
 
 /// Get a stream of game logs
-/// TODO: these should prob be saved to the DB too
+/// (only those of `min_tier` and up, in `focus` and in one of `channels`, if given)
 #[handler(subscription)]
-async fn game_log_stream(ctx: ServerCtx) -> impl Stream<Item = GameLog> {
+async fn game_log_stream(
+    ctx: ServerCtx,
+    min_tier: Option<LogTier>,
+    focus: Option<FocusFilter>,
+    channels: Option<Vec<LogChannel>>,
+) -> impl Stream<Item = GameLog> {
     let stream = tokio_stream::wrappers::BroadcastStream::new(ctx.log_tx.subscribe());
-    stream.filter_map(|e| async { e.ok() })
+    let focus = focus.unwrap_or_default();
+    stream.filter_map(move |e| {
+        let moderation = ctx.moderation.clone();
+        let log = e
+            .ok()
+            .filter(|log| log.at_least(min_tier) && log.in_channels(channels.as_deref()))
+            .and_then(|log| focus.filter_log(log));
+        async move { moderation.read().await.filter_log(log?) }
+    })
 }
 
 #[tokio::main]
@@ -115,9 +843,43 @@ async fn main() {
     // Create a qubit router
     let router = qubit::Router::new()
         .handler(get_entity_states)
+        .handler(get_entities_page)
+        .handler(search_entities)
         .handler(get_match_config)
+        .handler(get_world_preview)
+        .handler(get_upcoming_match)
+        .handler(get_match_roster)
+        .handler(get_entity_history)
+        .handler(get_current_season)
+        .handler(get_season_standings)
+        .handler(get_focus_suggestions)
+        .handler(get_standings)
+        .handler(get_state_at_tick)
+        .handler(get_player_stats)
+        .handler(get_weather_history)
+        .handler(get_match_logs)
+        .handler(export_match)
+        .handler(get_name_votes)
+        .handler(suggest_name)
+        .handler(vote_name)
+        .handler(get_poll)
+        .handler(start_poll)
+        .handler(vote_poll)
+        .handler(admin_reload_content)
+        .handler(validate_player_data)
+        .handler(admin_validate_match_sizing)
+        .handler(get_balance_manifest)
+        .handler(get_protocol)
+        .handler(get_background_manifest)
+        .handler(debug_tick_rolls)
+        .handler(admin_tag_entity)
+        .handler(admin_untag_entity)
+        .handler(admin_block_phrase)
+        .handler(admin_run_command)
+        .handler(admin_forecast)
         .handler(game_log_stream)
-        .handler(events_stream);
+        .handler(events_stream)
+        .handler(resume_stream);
 
     // Generate ts types
     if fs::try_exists("../abduction-site").await.unwrap() {
@@ -130,6 +892,11 @@ async fn main() {
         warn!("Skipping writing ts bindings");
     }
 
+    // Make sure we'll be able to generate players
+    if let Err(err) = entity::generate::validate_player_data() {
+        warn!("Invalid player data, players will be generated from embedded data: {err:?}");
+    }
+
     // Setup db connection
     let db_conn_string = env::var("DATABASE_URL")
         .expect("`DATABASE_URL` environment variable must contain a connection string");
@@ -147,21 +914,47 @@ async fn main() {
     info!("Running db migrations");
     sqlx::migrate!().run(&db).await.unwrap();
 
+    // Prepare a match ahead of time instead of running the server (see `mtch::prepare`)
+    if env::args().nth(1).as_deref() == Some("prepare-match") {
+        let args = PrepareMatchArgs::parse(env::args().skip(2)).expect("Invalid arguments");
+        prepare_match(args, &db)
+            .await
+            .expect("Failed to prepare match");
+        return;
+    }
+
+    // Or bring every saved entity up to the current payload version (see `entity::migrate`)
+    if env::args().nth(1).as_deref() == Some("migrate-entities") {
+        migrate_entities(&db)
+            .await
+            .expect("Failed to migrate entities");
+        return;
+    }
+
     // Create channel for tick events
     let (tick_tx, mut tick_rx) = broadcast::channel::<TickEvent>(20);
 
     // Create channel for game logs
-    let (log_tx, mut log_rx) = broadcast::channel::<GameLog>(20);
+    let (log_tx, mut log_rx) = broadcast::channel::<GameLog>(LOG_CHANNEL_CAPACITY);
 
-    // Create a spot that could later be a match manager (youll see)
-    let match_manager = Arc::default();
     let ctx_flags = CtxFlags::default();
+    if env::args().any(|arg| arg == "--audit-rolls") {
+        info!("Auditing rolls");
+        rng::enable_roll_audit();
+    }
+    if env::args().any(|arg| arg == "--profile-ticks") {
+        info!("Profiling ticks");
+        ctx_flags
+            .profile_ticks
+            .store(true, atomic::Ordering::Relaxed);
+    }
     let server_ctx = ServerCtx {
         tick_tx: tick_tx.clone(),
         log_tx: log_tx.clone(),
         db: db.clone(),
         flags: Arc::new(ctx_flags),
-        match_manager,
+        moderation: Arc::default(),
+        current_match: MatchHandle::default(),
     };
 
     // Create service and handle
@@ -170,6 +963,13 @@ async fn main() {
     // Nest into an Axum router
     let axum_router = axum::Router::<()>::new()
         .route("/up", get(|| async { "Healthy" }))
+        .route(
+            "/feed.atom",
+            get({
+                let ctx = server_ctx.clone();
+                move || feed::match_feed(ctx.clone())
+            }),
+        )
         .nest_service("/rpc", qubit_service);
 
     // Setup a task tracker
@@ -275,6 +1075,21 @@ async fn main() {
         }
     });
 
+    // Prune old logs every so often (see `retention`)
+    tracker.spawn({
+        let token = token.clone();
+        let db = server_ctx.db.clone();
+        let retention = LogRetention::from_env();
+        info!("Keeping logs for {retention:?}");
+
+        async move {
+            tokio::select! {
+                () = retention.prune_periodically(&db) => {},
+                () = token.cancelled() => {},
+            }
+        }
+    });
+
     // Listen for commands on stdin and handle them
     tracker.spawn({
         let token = token.clone();
@@ -291,6 +1106,26 @@ async fn main() {
         }
     });
 
+    // Take operator commands over a unix socket too, if asked to
+    if let Ok(socket_path) = env::var("CONSOLE_SOCKET") {
+        tracker.spawn({
+            let token = token.clone();
+            let qubit_ctx = server_ctx.clone();
+            let handle_console = async move {
+                if let Err(err) = serve_console(qubit_ctx, socket_path).await {
+                    warn!("Operator console stopped: {err:?}");
+                }
+            };
+
+            async move {
+                tokio::select! {
+                    () = handle_console => {},
+                    () = token.cancelled() => {},
+                }
+            }
+        });
+    }
+
     // Wait for shutdown signal...
     tokio::signal::ctrl_c().await.unwrap();
     info!("Shutting down...");
@@ -305,115 +1140,119 @@ async fn run_match_now(ctx: ServerCtx) -> anyhow::Result<()> {
     // Is there an incomplete one to keep running?
     let match_manager = match MatchConfig::get_incomplete(&ctx.db).await? {
         // If so then just load it now
-        Some(match_config) => {
+        Some(match_config) if match_config.started => {
             info!("Loading in-progress match ({})", match_config.match_id);
             MatchManager::load_match(match_config, &ctx.db).await
         }
 
-        // Otherwise, consult the shedule and possibly wait till later
-        // or, create a new match, save it to the db and initialise entities for it
-        None => {
-            // Are we supposed to be running yet?
-            // TODO:
-            // info!("Checking match schedule");
-            // TODO
-
-            // TODO: actually check schedule but for now just wait for a delay
-            sleep(MATCH_COOLDOWN_DURATION).await;
+        // If it hasn't started yet, its players are already prepared so just wait to start it
+        // (until it's scheduled to start, if it was prepared ahead of time, see `mtch::prepare`)
+        Some(match_config) => {
+            info!(
+                "Waiting to start upcoming match ({})",
+                match_config.match_id
+            );
+            match match_config.scheduled_start(&ctx.db).await? {
+                Some(starts_at) => {
+                    info!("Match is scheduled to start at {starts_at}");
+                    sleep((starts_at - Utc::now()).to_std().unwrap_or_default()).await;
+                }
+                None => sleep(MATCH_COOLDOWN_DURATION).await,
+            }
+            start_match(match_config, &ctx).await?
+        }
 
+        // Otherwise, create a new match and prepare its players,
+        // then consult the shedule and possibly wait till later to start it
+        None => {
             // Okay cool, create a new match
             info!("Creating a new match");
-            let dev_match = MatchConfig::isolated(10, 5);
+            // (the variant can be picked with `MATCH_VARIANT`, i.e `MATCH_VARIANT=blitz`)
+            let variant = match env::var("MATCH_VARIANT") {
+                Ok(variant) => MatchVariant::from_str(&variant).unwrap_or_else(|_| {
+                    warn!("Unknown match variant {variant}, playing a standard match");
+                    MatchVariant::Standard
+                }),
+                Err(_) => MatchVariant::Standard,
+            };
+            // (and any experiments are switched on if the last match didn't have them, see `FeatureFlag`)
+            let last_match = MatchConfig::get_latest(&ctx.db)
+                .await
+                .expect("Failed to get last match config");
+            let features = alternate_experiments(
+                last_match.as_ref().map(|config| &*config.features),
+                &FeatureFlag::experiments(),
+            );
+            // (it's the next match of the season, so whoever made it out of the last one might
+            //  come back for it, see `SeasonConfig`)
+            let season = SeasonConfig::for_next_match(&ctx.db)
+                .await
+                .expect("Failed to get season for new match");
+            let dev_match = season
+                .next_match(&ctx.db)
+                .await
+                .expect("Failed to get next match of season")
+                .with_variant(variant)
+                .with_features(features);
             dev_match
                 .save(&ctx.db)
                 .await
                 .expect("Failed to save new match config");
+            season
+                .add_match(&dev_match.match_id, &ctx.db)
+                .await
+                .expect("Failed to add match to season");
 
-            // Create match manager
-            // and prepare it to run
-            let mut match_manager = MatchManager::load_match(dev_match, &ctx.db).await;
-            match_manager
-                .initialise_new_match(&ctx.db)
+            // Generate its players now, so everyone can see who's playing while they wait
+            MatchManager::load_match(dev_match.clone(), &ctx.db)
+                .await
+                .prepare_new_match(&ctx.db)
                 .await
-                .expect("Failed to initialise match");
+                .expect("Failed to prepare match");
+
+            // Are we supposed to be running yet?
+            // TODO:
+            // info!("Checking match schedule");
+            // TODO
 
-            // Fire off a "new match started" event
-            ctx.tick_tx.send(TickEvent::StartOfMatch)?;
+            // TODO: actually check schedule but for now just wait for a delay
+            sleep(MATCH_COOLDOWN_DURATION).await;
 
-            match_manager
+            start_match(dev_match, &ctx).await?
         }
     };
 
-    // Update the shared match manager to this new match manager w/ the loaded match
-    {
-        let mut shared_match_manager = ctx.match_manager.lock().await;
-        *shared_match_manager = Some(match_manager);
-    }
-
-    // Start the tick loop
-    tick_loop(ctx).await
+    // Make it the current match and play it (see `MatchActor`)
+    ctx.current_match.start(match_manager).run(&ctx).await
 }
 
-async fn tick_loop(ctx: ServerCtx) -> anyhow::Result<()> {
-    // Start the tick loop
-    info!("Starting main tick loop");
-    let mut tick_count = 0;
-    loop {
-        ctx.tick_tx
-            .send(TickEvent::StartOfTick {
-                tick_id: tick_count,
-            })
-            .expect("Cannot send start of tick event");
-
-        // Generate updates for this tick
-        ctx.match_manager
-            .lock()
+/// Start a match that has been prepared
+async fn start_match(match_config: MatchConfig, ctx: &ServerCtx) -> anyhow::Result<MatchManager> {
+    // Create match manager (which loads the prepared players)
+    // and prepare it to run (unless its world was already generated, see `mtch::prepare`)
+    let mut match_manager = MatchManager::load_match(match_config, &ctx.db).await;
+    if match_manager.entities.world().is_none() {
+        match_manager
+            .initialise_new_match(&ctx.db)
             .await
-            .as_mut()
-            .expect("Tick loop is running but match manager isnt present...")
-            .perform_match_tick(&ctx)
-            .await;
-
-        // Tell em we finished the tick
-        ctx.tick_tx
-            .send(TickEvent::EndOfTick {
-                tick_id: tick_count,
-            })
-            .expect("Cannot send end of tick event");
-
-        // Did the match just finish?
-        {
-            let mut maybe_mm = ctx.match_manager.lock().await;
-            let mm = maybe_mm
-                .as_mut()
-                .expect("Tick loop is running but match_manager isn't present...");
-            if mm.match_over() || ctx.flags.force_end_match.load(atomic::Ordering::Relaxed) {
-                info!("Match completed");
-
-                // Ensure flag is unset now
-                ctx.flags
-                    .force_end_match
-                    .store(false, atomic::Ordering::Relaxed);
-
-                // Update the config to set `complete=true`
-                mm.config.complete = true;
-                mm.config.save(&ctx.db).await?;
-
-                // Send an event
-                ctx.tick_tx.send(TickEvent::EndOfMatch)?;
-
-                // Remove the shared manager
-                *maybe_mm = None;
-
-                // Break the loop
-                break;
-            }
-        }
-
-        // Wait for next tick...
-        tick_count += 1;
-        tokio::time::sleep(TICK_DELAY).await;
+            .expect("Failed to initialise match");
     }
 
-    Ok(())
+    // Note the balance it's being played with, so replays can check they match
+    info!(
+        "Match {} is being played with balance {}",
+        match_manager.config.match_id,
+        BalanceManifest::current().hash()
+    );
+
+    // It's no longer upcoming
+    match_manager.config.started = true;
+    match_manager.config.save(&ctx.db).await?;
+
+    // Fire off a "new match started" event
+    ctx.tick_tx.send(TickEvent::StartOfMatch {
+        protocol_version: protocol::PROTOCOL_VERSION,
+    })?;
+
+    Ok(match_manager)
 }
@@ -0,0 +1,390 @@
+//! The running match, as an actor
+//!
+//! While a match is running, its `MatchManager` is owned by a single task (the `MatchActor`) which
+//! plays its ticks. Nothing else ever holds the manager. Anything that needs to change or look at
+//! it (votes, polls, admin commands...) sends a job over a channel with `MatchHandle::run`, and the
+//! actor runs it between ticks and sends back the answer. Queries that only need the state of the
+//! match read the `MatchSnapshot` the actor publishes at the end of every tick instead, so viewer
+//! traffic never has to wait on a tick (or on anyone else).
+
+use std::sync::Arc;
+
+use futures::{future::BoxFuture, FutureExt};
+use itertools::Itertools;
+use serde::Serialize;
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::info;
+
+use crate::{
+    entity::{snapshot::EntitySnapshot, Entity, EntityId},
+    ServerCtx,
+};
+
+use super::{
+    drama::{FocusSuggestion, FOCUS_SUGGESTION_COUNT},
+    pace::TickPace,
+    preview::WorldPreview,
+    MatchConfig, MatchManager, TickEvent, TickId,
+};
+
+/// How many jobs can be waiting on the actor before senders have to wait
+const JOB_QUEUE_CAPACITY: usize = 64;
+
+/// How many entities are sent in each page of the initial sync (see `MatchSnapshot::entities_page`)
+pub const ENTITIES_PAGE_SIZE: usize = 200;
+
+/// Something to do with the match, run by the actor between ticks
+type MatchJob = Box<dyn for<'a> FnOnce(&'a mut MatchManager) -> BoxFuture<'a, ()> + Send>;
+
+/// The current match as of the end of some tick
+/// (never changes once published, so queries can't see a tick half done)
+#[derive(Debug)]
+pub struct MatchSnapshot {
+    pub config: MatchConfig,
+    pub tick_id: TickId,
+
+    /// Every entity (in order of id)
+    /// (shared with the manager, so publishing doesn't mean copying the whole world)
+    entities: EntitySnapshot,
+    pub focus_suggestions: Vec<FocusSuggestion>,
+    pub world_preview: Arc<WorldPreview>,
+}
+
+impl MatchSnapshot {
+    fn of(mm: &MatchManager) -> Self {
+        let entities = EntitySnapshot::new(
+            mm.entities
+                .share_all_entities()
+                .sorted_by(|a, b| a.entity_id.cmp(&b.entity_id)),
+        );
+        Self {
+            config: mm.config.clone(),
+            tick_id: mm.tick_id,
+            entities,
+            focus_suggestions: mm.drama.suggestions(FOCUS_SUGGESTION_COUNT),
+            world_preview: mm.world_preview.clone(),
+        }
+    }
+
+    /// Every entity (in order of id)
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.iter()
+    }
+
+    pub fn entity(&self, entity_id: &EntityId) -> Option<&Entity> {
+        let entities = self.entities.as_slice();
+        entities
+            .binary_search_by(|e| e.entity_id.cmp(entity_id))
+            .ok()
+            .map(|i| entities[i].as_ref())
+    }
+
+    /// Up to some number of entities, starting after some cursor (or from the start),
+    /// along with the cursor to get the rest from (if there are any)
+    /// NOTE: the cursor is the id of the last entity in the page, so it still works in later
+    ///       snapshots (entities added since then have later ids, see `Entity::id`)
+    pub fn entities_page(
+        &self,
+        cursor: Option<&EntityId>,
+        limit: usize,
+    ) -> (&[Arc<Entity>], Option<EntityId>) {
+        let entities = self.entities.as_slice();
+        let start = cursor.map_or(0, |cursor| {
+            entities.partition_point(|e| e.entity_id <= *cursor)
+        });
+        let end = (start + limit).min(entities.len());
+        let page = &entities[start..end];
+        let next_cursor = page
+            .last()
+            .filter(|_| end < entities.len())
+            .map(|e| e.entity_id.clone());
+        (page, next_cursor)
+    }
+}
+
+/// Some of the entities in the current match, for syncing a large world a bit at a time
+/// (see `get_entities_page`)
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct EntitiesPage {
+    pub entities: Vec<Entity>,
+
+    /// Where the next page starts (none if this is the last one)
+    pub next_cursor: Option<EntityId>,
+
+    /// The tick this page is as of
+    /// (pages can be from different ticks, so resume from the earliest once they're all in)
+    pub tick_id: TickId,
+}
+
+/// A way to get at the current match (if there is one)
+#[derive(Debug, Clone, Default)]
+pub struct MatchHandle {
+    inner: Arc<MatchHandleInner>,
+}
+
+#[derive(Debug, Default)]
+struct MatchHandleInner {
+    /// Where to send jobs for the actor of the current match
+    jobs: watch::Sender<Option<mpsc::Sender<MatchJob>>>,
+
+    /// The latest snapshot of the current match
+    snapshot: watch::Sender<Option<Arc<MatchSnapshot>>>,
+}
+
+impl MatchHandle {
+    /// The latest snapshot of the current match (if there is one)
+    pub fn snapshot(&self) -> Option<Arc<MatchSnapshot>> {
+        self.inner.snapshot.borrow().clone()
+    }
+
+    /// Publish a new snapshot of the current match
+    /// (the actor does this after every tick, jobs only need to if they change what's in it)
+    pub fn publish(&self, mm: &MatchManager) {
+        self.inner
+            .snapshot
+            .send_replace(Some(Arc::new(MatchSnapshot::of(mm))));
+    }
+
+    /// Have the actor do something with the current match, and wait for the result
+    /// Returns None if there is no current match (or it ended first)
+    pub async fn run<T, F>(&self, f: F) -> Option<T>
+    where
+        T: Send + 'static,
+        F: for<'a> FnOnce(&'a mut MatchManager) -> BoxFuture<'a, T> + Send + 'static,
+    {
+        let jobs = self.inner.jobs.borrow().clone()?;
+        let (reply_tx, reply_rx) = oneshot::channel();
+        let job: MatchJob = Box::new(move |mm| {
+            async move {
+                let _ = reply_tx.send(f(mm).await);
+            }
+            .boxed()
+        });
+        jobs.send(job).await.ok()?;
+        reply_rx.await.ok()
+    }
+
+    /// Like `run`, for something that doesn't need to wait on anything
+    pub async fn with<T, F>(&self, f: F) -> Option<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut MatchManager) -> T + Send + 'static,
+    {
+        self.run(move |mm| futures::future::ready(f(mm)).boxed())
+            .await
+    }
+
+    /// Make some match the current match, returning the actor to play it
+    pub fn start(&self, mm: MatchManager) -> MatchActor {
+        let (jobs_tx, jobs_rx) = mpsc::channel(JOB_QUEUE_CAPACITY);
+        self.publish(&mm);
+        self.inner.jobs.send_replace(Some(jobs_tx));
+        MatchActor {
+            manager: mm,
+            jobs: jobs_rx,
+            handle: self.clone(),
+        }
+    }
+
+    /// There's no current match any more
+    /// (anything still waiting on the actor gets None)
+    fn stop(&self) {
+        self.inner.jobs.send_replace(None);
+        self.inner.snapshot.send_replace(None);
+    }
+}
+
+/// Owns and plays the current match (see `MatchHandle::start`)
+pub struct MatchActor {
+    manager: MatchManager,
+    jobs: mpsc::Receiver<MatchJob>,
+    handle: MatchHandle,
+}
+
+impl MatchActor {
+    /// Play the match until it's over, running jobs between ticks
+    pub async fn run(mut self, ctx: &ServerCtx) -> anyhow::Result<()> {
+        info!("Starting main tick loop");
+        let mut pace = TickPace::from_env();
+        loop {
+            // Generate updates for this tick
+            let mm = &mut self.manager;
+            ctx.tick_tx
+                .send(TickEvent::StartOfTick {
+                    tick_id: mm.tick_id + 1,
+                })
+                .expect("Cannot send start of tick event");
+
+            let summary = mm.perform_match_tick(ctx).await;
+
+            // Make sure nothing got into a weird state
+            if cfg!(debug_assertions) {
+                mm.report_invariant_violations();
+            }
+
+            // Let queries see how it ended up
+            self.handle.publish(mm);
+
+            // Tell em we finished the tick
+            // (how fast we're going, which depends on whether anyone is watching, and how much work it was)
+            pace.update(ctx.tick_tx.receiver_count());
+            ctx.tick_tx
+                .send(TickEvent::EndOfTick {
+                    tick_id: mm.tick_id,
+                    speed: pace.speed(),
+                    summary,
+                })
+                .expect("Cannot send end of tick event");
+
+            // Did the match just finish?
+            let force_end = ctx
+                .flags
+                .force_end_match
+                .swap(false, std::sync::atomic::Ordering::Relaxed);
+            if mm.match_over() || force_end {
+                info!("Match completed");
+
+                // Update the config to set `complete=true`
+                mm.config.complete = true;
+                mm.config.save(&ctx.db).await?;
+
+                // Send an event
+                ctx.tick_tx.send(TickEvent::EndOfMatch)?;
+                break;
+            }
+
+            // Wait for next tick, doing whatever is asked of us in the meantime
+            let wait = pace.wait(&ctx.tick_tx);
+            tokio::pin!(wait);
+            loop {
+                tokio::select! {
+                    () = &mut wait => break,
+                    Some(job) = self.jobs.recv() => job(&mut self.manager).await,
+                }
+            }
+        }
+
+        self.handle.stop();
+        Ok(())
+    }
+
+    /// Run the next job sent to the actor
+    #[cfg(test)]
+    async fn run_next_job(&mut self) {
+        if let Some(job) = self.jobs.recv().await {
+            job(&mut self.manager).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        hex::AxialHex,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_entities_come_a_page_at_a_time() {
+        let mut builder = TestMatch::builder();
+        for i in 0..5 {
+            builder = builder.with_entity(test_player(&format!("Player {i}"), AxialHex::ZERO));
+        }
+        let test_match = builder.build().await;
+        let snapshot = MatchSnapshot::of(&test_match.manager);
+
+        // Every entity turns up exactly once, in order
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next_cursor) = snapshot.entities_page(cursor.as_ref(), 2);
+            assert!(page.len() <= 2);
+            seen.extend(page.iter().map(|e| e.entity_id.clone()));
+            match next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => break,
+            }
+        }
+        let all = snapshot.entities().map(|e| e.entity_id.clone());
+        assert_eq!(seen, all.collect::<Vec<_>>());
+        assert!(seen.is_sorted());
+    }
+
+    #[tokio::test]
+    async fn test_jobs_run_on_the_actor() {
+        let builder = TestMatch::builder();
+        let player = test_player("Watched", AxialHex::ZERO);
+        let player_id = player.entity_id.clone();
+        let test_match = builder.with_entity(player).build().await;
+
+        let handle = MatchHandle::default();
+        assert!(handle.with(|mm| mm.tick_id).await.is_none());
+        assert!(handle.snapshot().is_none());
+
+        let mut actor = handle.start(test_match.manager);
+        assert!(handle.snapshot().unwrap().entity(&player_id).is_some());
+
+        let asking = tokio::spawn({
+            let handle = handle.clone();
+            async move {
+                handle
+                    .with(|mm| {
+                        mm.tick_id += 1;
+                        mm.tick_id
+                    })
+                    .await
+            }
+        });
+        actor.run_next_job().await;
+        assert_eq!(asking.await.unwrap(), Some(1));
+
+        // (snapshots only change when published)
+        assert_eq!(handle.snapshot().unwrap().tick_id, 0);
+
+        handle.stop();
+        assert!(handle.with(|mm| mm.tick_id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_dont_see_later_changes() {
+        let builder = TestMatch::builder();
+        let player = test_player("Watched", AxialHex::ZERO);
+        let player_id = player.entity_id.clone();
+        let test_match = builder.with_entity(player).build().await;
+
+        let handle = MatchHandle::default();
+        let mut actor = handle.start(test_match.manager);
+        let before = handle.snapshot().unwrap();
+
+        // Change them (as a tick would, part way through)
+        let changing = tokio::spawn({
+            let handle = handle.clone();
+            let player_id = player_id.clone();
+            async move {
+                handle
+                    .with(move |mm| {
+                        mm.entities
+                            .mutate(&player_id, |e| e.name = "Changed".into())
+                            .unwrap()
+                    })
+                    .await
+            }
+        });
+        actor.run_next_job().await;
+        changing.await.unwrap();
+        assert_eq!(before.entity(&player_id).unwrap().name, "Watched");
+        assert_eq!(
+            handle.snapshot().unwrap().entity(&player_id).unwrap().name,
+            "Watched"
+        );
+
+        // Until the next snapshot is published
+        handle.publish(&actor.manager);
+        assert_eq!(
+            handle.snapshot().unwrap().entity(&player_id).unwrap().name,
+            "Changed"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Export matches to (and import them from) a portable archive on disk,
+//! so interesting matches can be shared, taken off the live DB and replayed elsewhere
+//!
+//! Archives are gzipped json, holding the match config and everything saved during the match
+//! (entity mutations, logs and the game events raised each tick).
+
+use std::{
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::fs;
+use tracing::info;
+
+use crate::{entity::EntityMutation, event::SavedGameEvent, logs::GameLog, Db};
+
+use super::{MatchConfig, MatchId};
+
+/// Where exported archives are written to
+const ARCHIVE_DIR: &str = "data/archives";
+
+/// Bump this when the archive format changes in a way older servers can't read
+/// (1 -> plain json without events)
+const ARCHIVE_FORMAT_VERSION: u32 = 2;
+
+/// What every gzip file starts with
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Everything needed to recreate a match
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchArchive {
+    pub format_version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub config: MatchConfig,
+
+    /// Every entity mutation in the match, oldest first
+    pub mutations: Vec<EntityMutation>,
+
+    /// Every game log in the match, oldest first
+    pub logs: Vec<Value>,
+
+    /// Every game event raised in the match, oldest first
+    #[serde(default)]
+    pub events: Vec<SavedGameEvent>,
+}
+
+impl MatchArchive {
+    /// Gather up everything about a match from the db
+    pub async fn from_db(match_id: MatchId, db: &Db) -> anyhow::Result<Self> {
+        let config = MatchConfig::get(db, match_id.clone()).await?;
+        let mutations = EntityMutation::load_from_match(&match_id, db).await?;
+        let logs = GameLog::load_from_match(&match_id, db).await?;
+        let events = SavedGameEvent::load_from_match(&match_id, db).await?;
+
+        Ok(Self {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            exported_at: Utc::now(),
+            config,
+            mutations,
+            logs,
+            events,
+        })
+    }
+
+    /// Export a match to an archive on disk, returning the path of the archive
+    pub async fn export(match_id: MatchId, db: &Db) -> anyhow::Result<PathBuf> {
+        let archive = Self::from_db(match_id, db).await?;
+        let summary = format!(
+            "{} mutations, {} logs, {} events",
+            archive.mutations.len(),
+            archive.logs.len(),
+            archive.events.len()
+        );
+
+        fs::create_dir_all(ARCHIVE_DIR)
+            .await
+            .context("Creating archive directory")?;
+        let path = Path::new(ARCHIVE_DIR).join(format!("{}.json.gz", archive.config.match_id));
+        let contents = tokio::task::spawn_blocking(move || archive.compress()).await??;
+        fs::write(&path, contents)
+            .await
+            .context("Writing match archive")?;
+
+        info!("Exported match ({summary}) to {}", path.display());
+        Ok(path)
+    }
+
+    /// Import a match from an archive on disk into the db
+    /// NOTE: the match is imported as complete so it wont be picked up and resumed as the live match
+    pub async fn import(path: impl AsRef<Path>, db: &Db) -> anyhow::Result<MatchId> {
+        let contents = fs::read(path).await.context("Reading match archive")?;
+        let archive = tokio::task::spawn_blocking(move || Self::decompress(&contents)).await??;
+        archive.restore(db).await
+    }
+
+    /// Put everything in the archive into the db
+    /// (all or nothing, if any of it can't be saved none of it is)
+    pub async fn restore(mut self, db: &Db) -> anyhow::Result<MatchId> {
+        if self.format_version > ARCHIVE_FORMAT_VERSION {
+            bail!(
+                "Unsupported archive format version {} (expected at most {})",
+                self.format_version,
+                ARCHIVE_FORMAT_VERSION
+            );
+        }
+
+        let match_id = self.config.match_id.clone();
+        if MatchConfig::get(db, match_id.clone()).await.is_ok() {
+            bail!("Match {match_id} already exists");
+        }
+
+        self.config.complete = true;
+        let mut tx = db.begin().await.context("Starting match import")?;
+        self.config.save(&mut *tx).await?;
+        for mutation in &self.mutations {
+            mutation.save(&mut *tx).await?;
+        }
+        for log in &self.logs {
+            GameLog::save_raw(log, &match_id, &mut *tx).await?;
+        }
+        for event in &self.events {
+            event.save(&match_id, &mut *tx).await?;
+        }
+        tx.commit().await.context("Finishing match import")?;
+
+        info!(
+            "Imported match {match_id} ({} mutations, {} logs, {} events)",
+            self.mutations.len(),
+            self.logs.len(),
+            self.events.len(),
+        );
+        Ok(match_id)
+    }
+
+    /// Write the archive out as gzipped json
+    pub fn compress(&self) -> anyhow::Result<Vec<u8>> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        serde_json::to_writer(&mut encoder, self).context("Serialising match archive")?;
+        encoder.finish().context("Compressing match archive")
+    }
+
+    /// Read an archive back in
+    /// (older archives are plain json, so those are read as is)
+    pub fn decompress(contents: &[u8]) -> anyhow::Result<Self> {
+        let mut json = Vec::new();
+        if contents.starts_with(&GZIP_MAGIC) {
+            GzDecoder::new(contents)
+                .read_to_end(&mut json)
+                .context("Decompressing match archive")?;
+        } else {
+            json.write_all(contents)?;
+        }
+        serde_json::from_slice(&json).context("Parsing match archive")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        hex::AxialHex,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_archive_has_whole_match() {
+        let mut test_match = TestMatch::builder()
+            .with_entity(test_player("Archived", AxialHex::ZERO))
+            .build()
+            .await;
+        for _ in 0..5 {
+            test_match.tick().await;
+        }
+
+        let match_id = test_match.manager.config.match_id.clone();
+        let archive = MatchArchive::from_db(match_id.clone(), &test_match.ctx().db)
+            .await
+            .unwrap();
+        assert_eq!(archive.logs.len(), test_match.logs.len());
+        assert_eq!(archive.events.len(), test_match.events.len());
+        assert!(archive.mutations.len() >= test_match.manager.entities.get_all_entities().count());
+
+        // It survives being compressed and imported somewhere else
+        let archive = MatchArchive::decompress(&archive.compress().unwrap()).unwrap();
+        let elsewhere = TestMatch::builder().build().await;
+        assert_eq!(
+            archive.restore(&elsewhere.ctx().db).await.unwrap(),
+            match_id.clone()
+        );
+        let imported = MatchArchive::from_db(match_id, &elsewhere.ctx().db)
+            .await
+            .unwrap();
+        assert!(imported.config.complete);
+        assert_eq!(imported.logs.len(), test_match.logs.len());
+        assert_eq!(imported.events.len(), test_match.events.len());
+    }
+}
@@ -0,0 +1,111 @@
+//! How engaged the folks at home are
+//!
+//! Engagement is driven by how many people are watching (subscribed to the tick stream) and how much
+//! they are getting involved (naming votes for now, predictions/gifts later). It moves slowly, so a
+//! single burst of activity doesn't swing it wildly. The presenter reacts to it, and it's kept on
+//! the world entity so anything else in the simulation can take it into account.
+
+use serde::{Deserialize, Serialize};
+
+/// How much of the previous engagement carries into the next tick
+const ENGAGEMENT_SMOOTHING: f32 = 0.95;
+
+/// How many viewers it takes for them to count for half of the viewer engagement
+const HALF_ENGAGED_VIEWERS: f32 = 10.0;
+
+/// How many interactions in a single tick count as the audience being fully involved
+const FULLY_INVOLVED_INTERACTIONS: f32 = 5.0;
+
+/// How much interactions count towards engagement (the rest being viewers)
+const INTERACTION_WEIGHT: f32 = 0.3;
+
+/// Broadly how the audience is feeling
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum AudienceMood {
+    Bored,
+    #[default]
+    Content,
+    Excited,
+}
+
+impl AudienceMood {
+    pub fn from_engagement(engagement: f32) -> Self {
+        if engagement < 0.3 {
+            AudienceMood::Bored
+        } else if engagement > 0.7 {
+            AudienceMood::Excited
+        } else {
+            AudienceMood::Content
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AudienceTracker {
+    engagement: f32,
+
+    /// Interactions since the last update
+    interactions: usize,
+}
+
+impl Default for AudienceTracker {
+    fn default() -> Self {
+        Self {
+            engagement: 0.5,
+            interactions: 0,
+        }
+    }
+}
+
+impl AudienceTracker {
+    /// Someone in the audience did something (voted, suggested a name etc)
+    pub fn record_interaction(&mut self) {
+        self.interactions += 1;
+    }
+
+    /// Update engagement given how many people are currently watching
+    /// Returns the new engagement (0 -> nobody cares, 1 -> glued to their screens)
+    pub fn update(&mut self, viewers: usize) -> f32 {
+        let viewers = viewers as f32;
+        let viewer_engagement = viewers / (viewers + HALF_ENGAGED_VIEWERS);
+        let interaction_engagement =
+            (self.interactions as f32 / FULLY_INVOLVED_INTERACTIONS).min(1.0);
+        let target = viewer_engagement * (1.0 - INTERACTION_WEIGHT)
+            + interaction_engagement * INTERACTION_WEIGHT;
+
+        self.interactions = 0;
+        self.engagement =
+            self.engagement * ENGAGEMENT_SMOOTHING + target * (1.0 - ENGAGEMENT_SMOOTHING);
+        self.engagement
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_engagement_follows_audience() {
+        let mut audience = AudienceTracker::default();
+        for _ in 0..200 {
+            audience.update(0);
+        }
+        assert_eq!(
+            AudienceMood::from_engagement(audience.engagement),
+            AudienceMood::Bored
+        );
+
+        for _ in 0..200 {
+            for _ in 0..5 {
+                audience.record_interaction();
+            }
+            audience.update(100);
+        }
+        assert_eq!(
+            AudienceMood::from_engagement(audience.engagement),
+            AudienceMood::Excited
+        );
+    }
+}
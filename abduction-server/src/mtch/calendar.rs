@@ -0,0 +1,110 @@
+//! Keeping track of the in-game time
+//!
+//! Every match starts at dawn on day 1, each `TICKS_PER_HOUR` ticks the clock moves on an hour, and
+//! a new day starts every dawn.
+//! The time (and so the time of day) is worked out from the tick, rather than drifting randomly, so
+//! that viewers (and anything that happens daily, like recaps from the presenter) can count on it.
+//! The world keeps the current time (see `EntityWorld::advance_clock`) so clients can show it.
+
+use serde::{Deserialize, Serialize};
+
+use super::TickId;
+use crate::entity::world::TimeOfDay;
+
+/// How many ticks make up an hour in the match
+pub const TICKS_PER_HOUR: TickId = 25;
+
+/// How many hours make up a day
+pub const HOURS_PER_DAY: usize = 24;
+
+/// What hour the match (and every day) starts at (dawn)
+pub const START_HOUR: usize = 5;
+
+/// A time in the match
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchTime {
+    /// Which day it is (starting at 1)
+    pub day: usize,
+
+    /// Which hour of the day it is (0-23)
+    pub hour: usize,
+}
+
+impl MatchTime {
+    /// What time it is at some tick
+    pub fn at_tick(tick_id: TickId) -> Self {
+        let hours = tick_id / TICKS_PER_HOUR;
+        Self {
+            day: 1 + hours / HOURS_PER_DAY,
+            hour: (START_HOUR + hours) % HOURS_PER_DAY,
+        }
+    }
+
+    /// Is this tick the first of a new day (after the first)?
+    pub fn is_start_of_day(tick_id: TickId) -> bool {
+        tick_id > 0 && Self::at_tick(tick_id).day != Self::at_tick(tick_id - 1).day
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.hour {
+            5..13 => TimeOfDay::Morning,
+            13..21 => TimeOfDay::Afternoon,
+            _ => TimeOfDay::Night,
+        }
+    }
+
+    pub fn part_of_day(&self) -> PartOfDay {
+        match self.hour {
+            5..7 => PartOfDay::Dawn,
+            7..12 => PartOfDay::Morning,
+            12..14 => PartOfDay::Midday,
+            14..19 => PartOfDay::Afternoon,
+            19..21 => PartOfDay::Dusk,
+            _ => PartOfDay::Night,
+        }
+    }
+}
+
+/// Roughly what part of the day it is, for telling viewers the time (i.e "Day 3, dawn")
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, strum::Display)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PartOfDay {
+    #[default]
+    Dawn,
+    Morning,
+    Midday,
+    Afternoon,
+    Dusk,
+    Night,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_calendar_follows_the_ticks() {
+        let start = MatchTime::at_tick(0);
+        assert_eq!(start, MatchTime { day: 1, hour: 5 });
+        assert_eq!(start.part_of_day(), PartOfDay::Dawn);
+        assert_eq!(start.time_of_day(), TimeOfDay::Morning);
+
+        // A day later, it's dawn again
+        let ticks_per_day = TICKS_PER_HOUR * HOURS_PER_DAY;
+        assert_eq!(
+            MatchTime::at_tick(ticks_per_day),
+            MatchTime { day: 2, hour: 5 }
+        );
+
+        assert!(MatchTime::is_start_of_day(ticks_per_day));
+        assert!(!MatchTime::is_start_of_day(ticks_per_day + 1));
+        assert!(!MatchTime::is_start_of_day(0));
+
+        // (and the day doesn't change at midnight)
+        let midnight = (HOURS_PER_DAY - START_HOUR) * TICKS_PER_HOUR;
+        assert_eq!(MatchTime::at_tick(midnight), MatchTime { day: 1, hour: 0 });
+        assert_eq!(MatchTime::at_tick(midnight).time_of_day(), TimeOfDay::Night);
+    }
+}
@@ -1,12 +1,23 @@
+use std::collections::HashSet;
+
 use anyhow::Context;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::prelude::FromRow;
-use tracing::info;
+use sqlx::{prelude::FromRow, types::Json, SqliteExecutor};
+use tracing::{info, warn};
 use uuid::Uuid;
 
-use crate::Db;
+use crate::{behavior::behavior, Db};
+
+use super::{
+    features::FeatureFlag,
+    ruleset::{MatchVariant, Ruleset},
+    segments::ShowScript,
+    MatchId,
+};
 
-use super::MatchId;
+/// The world radius for a match that can't be sized to its players
+const DEFAULT_WORLD_RADIUS: usize = 5;
 
 /// The configuration for a given match
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -33,6 +44,24 @@ pub struct MatchConfig {
     /// How far the world extends in every direction as a number of hexs
     /// TODO: I really want this to be unsigned...
     pub world_radius: i32,
+
+    /// The segments the presenter runs during the match
+    #[ts(as = "ShowScript")]
+    pub show_script: Json<ShowScript>,
+
+    /// The rules the match is played by
+    #[ts(as = "Ruleset")]
+    pub ruleset: Json<Ruleset>,
+
+    /// Has the match started?
+    /// (until then, it's upcoming and only has its players)
+    pub started: bool,
+
+    /// The experimental systems the match is played with
+    /// (archives from before these existed had all of them)
+    #[ts(as = "HashSet<FeatureFlag>")]
+    #[serde(default = "all_features")]
+    pub features: Json<HashSet<FeatureFlag>>,
 }
 
 impl MatchConfig {
@@ -43,15 +72,54 @@ impl MatchConfig {
             preceding_match_id: preceding_player_id,
             world_radius: world_radius as i32,
             complete: false,
+            show_script: Json(ShowScript::standard(player_count)),
+            ruleset: Json(Ruleset::default()),
+            started: false,
+            features: Json(FeatureFlag::all()),
         }
     }
 
+    /// Play the match as some variant
+    pub fn with_variant(mut self, variant: MatchVariant) -> Self {
+        self.ruleset = Json(Ruleset::of(variant));
+        self
+    }
+
+    /// Play the match with only some of the experimental systems
+    pub fn with_features(mut self, features: HashSet<FeatureFlag>) -> Self {
+        self.features = Json(features);
+        self
+    }
+
+    /// Follow on from some match, bringing back whoever made it out of it (see `season`)
+    pub fn with_preceding_match(mut self, preceding_match_id: Option<MatchId>) -> Self {
+        self.preceding_match_id = preceding_match_id;
+        self
+    }
+
+    /// Is some experimental system part of this match?
+    pub fn has_feature(&self, feature: FeatureFlag) -> bool {
+        self.features.contains(&feature)
+    }
+
+    /// A new match with its world sized to fit its players (see `MatchSizing`)
+    /// (if it can't be sized, e.g the sizing config is no good, it gets the default radius)
+    pub fn sized_for(player_count: usize) -> Self {
+        let world_radius = behavior()
+            .sizing
+            .world_radius(player_count)
+            .unwrap_or_else(|err| {
+                warn!("Using default world radius: {err:?}");
+                DEFAULT_WORLD_RADIUS
+            });
+        Self::new(player_count, world_radius, None)
+    }
+
     pub fn isolated(player_count: usize, world_extents: usize) -> Self {
         Self::new(player_count, world_extents, None)
     }
 
     /// Get one match config from the db
-    #[allow(unused)]
     pub async fn get(db: &Db, match_id: MatchId) -> anyhow::Result<Self> {
         sqlx::query_file_as!(Self, "queries/get_match_config.sql", match_id)
             .fetch_one(db)
@@ -66,7 +134,23 @@ impl MatchConfig {
             .context("getting unfinished match config")
     }
 
-    pub async fn save(&self, db: &Db) -> anyhow::Result<()> {
+    /// Get the config of the next match, if it has been prepared but hasn't started yet
+    pub async fn get_upcoming(db: &Db) -> anyhow::Result<Option<Self>> {
+        sqlx::query_file_as!(Self, "queries/get_upcoming_match_config.sql")
+            .fetch_optional(db)
+            .await
+            .context("getting upcoming match config")
+    }
+
+    /// Get the config of the most recently created match, whatever state it's in
+    pub async fn get_latest(db: &Db) -> anyhow::Result<Option<Self>> {
+        sqlx::query_file_as!(Self, "queries/get_latest_match_config.sql")
+            .fetch_optional(db)
+            .await
+            .context("getting latest match config")
+    }
+
+    pub async fn save(&self, db: impl SqliteExecutor<'_>) -> anyhow::Result<()> {
         info!("Saving match configuration {} to db", &self.match_id);
         sqlx::query_file_as!(
             Self,
@@ -76,10 +160,37 @@ impl MatchConfig {
             self.preceding_match_id,
             self.world_radius,
             self.complete,
+            self.show_script,
+            self.ruleset,
+            self.started,
+            self.features,
         )
         .execute(db)
         .await
         .map(|_| ())
         .context("Saving match config")
     }
+
+    /// When this match is supposed to start, if it's been scheduled (see `prepare`)
+    pub async fn scheduled_start(&self, db: &Db) -> anyhow::Result<Option<DateTime<Utc>>> {
+        let row = sqlx::query_file!("queries/get_match_start.sql", self.match_id)
+            .fetch_optional(db)
+            .await
+            .context("getting match start")?;
+        Ok(row.map(|row| row.starts_at.and_utc()))
+    }
+
+    /// Schedule this (saved) match to start at some time
+    pub async fn schedule_start(&self, starts_at: DateTime<Utc>, db: &Db) -> anyhow::Result<()> {
+        let starts_at: NaiveDateTime = starts_at.naive_utc();
+        sqlx::query_file!("queries/set_match_start.sql", self.match_id, starts_at)
+            .execute(db)
+            .await
+            .map(|_| ())
+            .context("Scheduling match start")
+    }
+}
+
+fn all_features() -> Json<HashSet<FeatureFlag>> {
+    Json(FeatureFlag::all())
 }
@@ -10,6 +10,10 @@
 
 use std::collections::HashMap;
 
+use rand::{
+    seq::{IndexedRandom, IteratorRandom},
+    Rng,
+};
 use serde::{Deserialize, Serialize};
 use tracing::warn;
 
@@ -19,6 +23,8 @@ use crate::{
         brain::{
             actor_action::{ActorAction, ActorActionResult},
             characteristic::{Characteristic, CharacteristicStrength},
+            focus::ActorFocus,
+            plan::Plan,
             signal::SignalRef,
         },
         Entity, EntityAttributes, EntityId,
@@ -26,7 +32,7 @@ use crate::{
     has_markers,
     hex::AxialHex,
     logs::{GameLog, GameLogBody},
-    mtch::ActionCtx,
+    mtch::{audience::AudienceMood, poll::PollOption, ActionCtx},
 };
 
 pub fn generate_presenter() -> Entity {
@@ -96,18 +102,60 @@ pub fn generate_collector() -> Entity {
 #[qubit::ts]
 pub struct EntityPresenter {
     wait: usize,
+
+    /// How the audience was feeling when we last said something about it
+    #[serde(default)]
+    audience_mood: AudienceMood,
+
+    /// The campfire we last dropped in on a gathering around (see `brain::gathering`)
+    #[serde(default)]
+    gathering_seen: Option<EntityId>,
 }
 
 impl Default for EntityPresenter {
     fn default() -> Self {
-        Self { wait: 10 }
+        Self {
+            wait: 10,
+            audience_mood: AudienceMood::default(),
+            gathering_seen: None,
+        }
+    }
+}
+
+/// How long the presenter waits between introductions
+/// (if the audience is losing interest, keep things moving)
+fn introduction_wait(audience_mood: AudienceMood) -> usize {
+    match audience_mood {
+        AudienceMood::Bored => 5,
+        _ => 10,
     }
 }
 
+/// Chance each tick (when not busy) of the presenter popping over to check in on someone
+const PRESENTER_CHECK_IN_CHANCE: f64 = 0.02;
+
+/// Chance each tick (when not busy) of the presenter dropping in on a gathering around a campfire
+const PRESENTER_GATHERING_CHANCE: f64 = 0.1;
+
+/// What the presenter might say when dropping in on a gathering
+const GATHERING_QUOTES: &[&str] = &[
+    "Now this is what it's all about folks, a little warmth in the dark",
+    "Pull up a log, it's story time! Don't mind me, I'm just passing through",
+    "Who knew a bunch of abductees could be so heartwarming? Not me, that's for sure",
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[qubit::ts]
 pub struct EntityCollector {
-    // TODO
+    /// Everything warped out so far (so it can be added to future games)
+    #[serde(default)]
+    collection: Vec<EntityId>,
+}
+
+impl EntityCollector {
+    pub fn collect(&mut self, entity_id: EntityId) {
+        self.collection.push(entity_id);
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -115,6 +163,29 @@ pub enum PresenterAction {
     Wait,
     StartWaiting(usize),
     IntroducePlayer(EntityId),
+
+    /// Let everyone know some entity has been renamed by the viewers
+    AnnounceName {
+        entity_id: EntityId,
+        old_name: String,
+        name: String,
+    },
+
+    /// Say something about how the folks at home are feeling
+    ReactToAudience(AudienceMood),
+
+    /// Run a segment of the show (see `ShowScript`)
+    PresentSegment {
+        quote: String,
+    },
+
+    /// Let everyone know what the viewers voted for
+    AnnouncePollResult(PollOption),
+
+    /// Say something about the gathering around some campfire
+    CommentOnGathering {
+        campfire_id: EntityId,
+    },
 }
 
 impl From<PresenterAction> for ActorAction {
@@ -140,20 +211,62 @@ impl Entity {
             return ActorAction::Presenter(PresenterAction::Wait);
         }
 
+        let audience_mood = ctx.world_state.audience_mood();
+
         // For now, each action just warp in one player
         // is there a player needing unbanished?
-        if let Some(to_warp_entity) = ctx
-            .entities
-            .all()
-            .find(|e| e.attributes.hex.is_none() && has_markers!(e, Player))
-        {
-            return ActorAction::Sequential(vec![
-                ActorAction::ignore(
-                    PresenterAction::IntroducePlayer(to_warp_entity.entity_id.clone()).into(),
-                ),
-                PresenterAction::StartWaiting(10).into(),
-                ActorAction::WarpInEntity(to_warp_entity.entity_id.clone()),
-            ]);
+        if let Some(to_warp_entity) = ctx.entities.all().find(|e| {
+            e.attributes.hex.is_none() && has_markers!(e, Player) && !has_markers!(e, Escaped)
+        }) {
+            return Plan::new("introduce player")
+                .also(PresenterAction::IntroducePlayer(to_warp_entity.entity_id.clone()).into())
+                .then(PresenterAction::StartWaiting(introduction_wait(audience_mood)).into())
+                .then(ActorAction::WarpInEntity(to_warp_entity.entity_id.clone()))
+                .into();
+        }
+
+        // Has the audience warmed up (or cooled off)?
+        if audience_mood != presenter.audience_mood {
+            return Plan::new("react to audience")
+                .also(PresenterAction::ReactToAudience(audience_mood).into())
+                .then(PresenterAction::StartWaiting(10).into())
+                .into();
+        }
+
+        // Drop in on any gathering we haven't seen yet
+        let mut rng = crate::rng::rng();
+        let gathering = ctx.entities.all().find_map(|e| match &e.attributes.focus {
+            Some(ActorFocus::Gathering { campfire, .. })
+                if presenter.gathering_seen.as_ref() != Some(campfire) =>
+            {
+                Some((campfire.clone(), e.attributes.hex?))
+            }
+            _ => None,
+        });
+        if let Some((campfire_id, hex)) = gathering {
+            if rng.random_bool(PRESENTER_GATHERING_CHANCE) {
+                return Plan::new("drop in on gathering")
+                    .then(ActorAction::Teleport(hex))
+                    .then(PresenterAction::CommentOnGathering { campfire_id }.into())
+                    .then(PresenterAction::StartWaiting(20).into())
+                    .into();
+            }
+        }
+
+        // Every so often, pop over to see how someone is getting on
+        if rng.random_bool(PRESENTER_CHECK_IN_CHANCE) {
+            if let Some(player_hex) = ctx
+                .entities
+                .all()
+                .filter(|e| has_markers!(e, Player))
+                .filter_map(|e| e.attributes.hex)
+                .choose(&mut rng)
+            {
+                return Plan::new("check in")
+                    .then(ActorAction::Teleport(player_hex))
+                    .then(PresenterAction::StartWaiting(20).into())
+                    .into();
+            }
         }
 
         ActorAction::Nothing
@@ -174,9 +287,17 @@ impl Entity {
                 ActorActionResult::NoEffect // this can be chained to start waiting afterwards
             }
             PresenterAction::IntroducePlayer(entity_id) => {
-                let player_entity = ctx.entities.by_id(entity_id).unwrap();
-                let name = player_entity.attributes.first_name.as_ref().unwrap();
-                let bg = player_entity.attributes.background.as_ref().unwrap();
+                let Ok(player_entity) = ctx.entities.require(entity_id) else {
+                    return ActorActionResult::NoEffect;
+                };
+                let Ok(bg) = player_entity.as_background() else {
+                    return ActorActionResult::NoEffect;
+                };
+                let name = player_entity
+                    .attributes
+                    .first_name
+                    .as_ref()
+                    .unwrap_or(&player_entity.name);
                 let retired = if bg.is_retired { "retired " } else { "" };
                 let career = bg.career.to_string();
                 let location = bg.location_string();
@@ -190,6 +311,89 @@ impl Entity {
                     },
                 ));
 
+                ActorActionResult::Ok
+            }
+            PresenterAction::AnnounceName {
+                entity_id,
+                old_name,
+                name,
+            } => {
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    entity_id,
+                    GameLogBody::EntitySayExact {
+                        quote: format!(
+                            "By popular demand, the {} shall henceforth be known as {name}!",
+                            old_name.to_lowercase()
+                        ),
+                    },
+                ));
+
+                ActorActionResult::Ok
+            }
+            PresenterAction::PresentSegment { quote } => {
+                ctx.send_log(GameLog::entity(
+                    self,
+                    GameLogBody::EntitySayExact {
+                        quote: quote.clone(),
+                    },
+                ));
+
+                ActorActionResult::Ok
+            }
+            PresenterAction::AnnouncePollResult(option) => {
+                let quote = match option {
+                    PollOption::Storm => {
+                        "The people have spoken! I hope you all packed an umbrella, there's a storm rolling in"
+                    }
+                    PollOption::SupplyDrop => {
+                        "The people have spoken! Keep your eyes peeled, we've dropped in some supplies"
+                    }
+                    PollOption::ReleasePredator => {
+                        "The people have spoken! Something hungry has just been let loose out there"
+                    }
+                };
+                ctx.send_log(GameLog::entity(
+                    self,
+                    GameLogBody::EntitySayExact {
+                        quote: quote.to_owned(),
+                    },
+                ));
+
+                ActorActionResult::Ok
+            }
+            PresenterAction::CommentOnGathering { campfire_id } => {
+                self.attributes.presenter.as_mut().unwrap().gathering_seen =
+                    Some(campfire_id.clone());
+                let quote = GATHERING_QUOTES.choose(&mut crate::rng::rng()).unwrap();
+                ctx.send_log(GameLog::entity_pair(
+                    self,
+                    campfire_id,
+                    GameLogBody::EntitySayExact {
+                        quote: (*quote).to_owned(),
+                    },
+                ));
+
+                ActorActionResult::Ok
+            }
+            PresenterAction::ReactToAudience(audience_mood) => {
+                self.attributes.presenter.as_mut().unwrap().audience_mood = *audience_mood;
+                let quote = match audience_mood {
+                    AudienceMood::Excited => "The folks at home are loving this!",
+                    AudienceMood::Content => {
+                        "Stay tuned folks, there's plenty more where that came from"
+                    }
+                    AudienceMood::Bored => {
+                        "Don't touch that dial! Things are about to heat up, I promise"
+                    }
+                };
+                ctx.send_log(GameLog::entity(
+                    self,
+                    GameLogBody::EntitySayExact {
+                        quote: quote.to_owned(),
+                    },
+                ));
+
                 ActorActionResult::Ok
             }
         }
@@ -206,7 +410,17 @@ impl Entity {
             return ActorAction::Nothing;
         };
 
-        // Find the nearest player corpse if present
+        // Is there a corpse right here? Collect it
+        let my_hex = self.attributes.hex.unwrap();
+        if let Some(corpse_entity) = ctx
+            .entities
+            .in_hex(my_hex)
+            .find(|e| e.attributes.corpse.is_some())
+        {
+            return ActorAction::WarpOutEntity(corpse_entity.entity_id.clone());
+        }
+
+        // Otherwise find the nearest player corpse if present
         if let Some(corpse_entity) = ctx
             .entities
             .all()
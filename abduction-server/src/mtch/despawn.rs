@@ -0,0 +1,175 @@
+//! Clearing away props nobody wants
+//!
+//! Over a long match props pile up (food nobody ate, fires, things dropped and forgotten about) and
+//! every one of them is sent to everyone watching. Some kinds of prop only last so long lying on the
+//! ground before they're cleared away. How long something has been lying there is counted from its
+//! `lying_since`, which is set the first tick it's seen on the ground and cleared if it's picked up.
+//! Memes about things that are gone (cleared away, eaten, burned down...) are forgotten too, so those
+//! don't pile up either.
+
+use itertools::Itertools;
+
+use crate::{
+    entity::{snapshot::EntityView, Entity},
+    has_markers,
+    logs::{GameLog, GameLogBody},
+    ServerCtx,
+};
+
+use super::{MatchManager, TickId};
+
+/// How many ticks a fire burns for before going out on its own
+const FIRE_BURN_TICKS: usize = 150;
+
+/// How many ticks food lasts on the ground before it rots away to nothing (or something eats it)
+const GROUND_FOOD_TICKS: usize = 1200;
+
+/// How many ticks an item lasts on the ground before it's buried, lost or taken by the crew
+const LOOSE_ITEM_TICKS: usize = 2400;
+
+/// How long some entity lasts lying on the ground (if it doesn't last forever)
+fn lifetime(entity: &Entity) -> Option<usize> {
+    if has_markers!(entity, Fire) {
+        return Some(FIRE_BURN_TICKS);
+    }
+
+    // The dead are left where they fell, and pod parts are too important to lose
+    if entity.attributes.corpse.is_some() || entity.attributes.pod_part.is_some() {
+        return None;
+    }
+
+    if entity.attributes.food.is_some() {
+        Some(GROUND_FOOD_TICKS)
+    } else if entity.attributes.item.is_some() {
+        Some(LOOSE_ITEM_TICKS)
+    } else {
+        None
+    }
+}
+
+/// Whether some entity that only lasts so long on the ground has been there long enough
+fn expired(entity: &Entity, tick_id: TickId) -> bool {
+    match (lifetime(entity), entity.attributes.lying_since) {
+        (Some(lifetime), Some(lying_since)) => tick_id.saturating_sub(lying_since) >= lifetime,
+        _ => false,
+    }
+}
+
+impl MatchManager {
+    /// Clear away anything that's been lying around for too long,
+    /// then have everyone forget about anything that's no longer around
+    pub fn despawn_expired_props(&mut self, entities_view: &EntityView, ctx: &ServerCtx) {
+        let tick_id = self.tick_id;
+        for entity in entities_view.all().filter(|e| lifetime(e).is_some()) {
+            // It may be gone by now (e.g washed away)
+            if self.entities.get_entity(&entity.entity_id).is_none() {
+                continue;
+            }
+
+            if expired(entity, tick_id) {
+                if has_markers!(entity, Fire) {
+                    ctx.log_tx
+                        .send(GameLog::entity(entity, GameLogBody::FireBurnOut))
+                        .unwrap();
+                }
+                self.entities.remove_entity(&entity.entity_id).unwrap();
+                continue;
+            }
+
+            // Start counting once it's on the ground, and stop if it's picked up
+            let lying_since = match (entity.attributes.hex, entity.attributes.lying_since) {
+                (Some(_), None) => Some(tick_id),
+                (None, Some(_)) => None,
+                _ => continue,
+            };
+            self.entities
+                .mutate(&entity.entity_id, |e| {
+                    e.attributes.lying_since = lying_since
+                })
+                .unwrap();
+        }
+
+        // Forget about anything that's gone
+        let forgetful = self
+            .entities
+            .get_all_entities()
+            .filter_map(|entity| {
+                let mut memes = entity.attributes.memes.clone()?;
+                memes
+                    .forget_missing(|entity_id| self.entities.get_entity(entity_id).is_some())
+                    .then(|| (entity.entity_id.clone(), memes))
+            })
+            .collect_vec();
+        for (entity_id, memes) in forgetful {
+            self.entities
+                .mutate(&entity_id, |e| e.attributes.memes = Some(memes))
+                .unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        create_markers,
+        entity::{brain::meme::Meme, generate::PropGenerator, EntityAttributes},
+        hex::AxialHex,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_props_are_cleared_away_once_expired() {
+        let builder = TestMatch::builder();
+        let mut rng = crate::rng::rng();
+        let mut food = PropGenerator::NaturalFood.generate(&mut rng);
+        food.attributes.hex = Some(AxialHex::from((2, 0)));
+        let food_id = food.entity_id.clone();
+
+        // (a fire that has been burning for ages)
+        let fire = Entity {
+            entity_id: Entity::id(),
+            name: "Fire".into(),
+            markers: create_markers!(Fire, Inspectable),
+            attributes: EntityAttributes {
+                hex: Some(AxialHex::from((-2, 0))),
+                lying_since: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let fire_id = fire.entity_id.clone();
+
+        // (someone who remembers something long gone)
+        let mut player = test_player("Forgetful", AxialHex::ZERO);
+        player
+            .attributes
+            .memes
+            .get_or_insert_default()
+            .insert(Meme::Spotted("long-gone".into()));
+        let player_id = player.entity_id.clone();
+
+        let mut test_match = builder
+            .with_entity(food)
+            .with_entity(fire)
+            .with_entity(player)
+            .build()
+            .await;
+        test_match.manager.tick_id = FIRE_BURN_TICKS;
+        test_match.tick().await;
+
+        assert!(test_match.entity(&fire_id).is_none());
+        assert!(test_match.has_log_by(&fire_id, |body| matches!(body, GameLogBody::FireBurnOut)));
+        assert_eq!(
+            test_match.entity(&food_id).unwrap().attributes.lying_since,
+            Some(FIRE_BURN_TICKS + 1)
+        );
+        assert!(!test_match
+            .entity(&player_id)
+            .unwrap()
+            .attributes
+            .memes
+            .unwrap()
+            .has_spotted(&"long-gone".into()));
+    }
+}
@@ -0,0 +1,126 @@
+//! Drama scoring, so a broadcast-style client can cut to the most interesting contestant
+//! without having to recompute any heuristics itself
+//!
+//! Each tick every player gets a score from what just happened to them (events they were part of or saw),
+//! how close they are to dying of something, and what they are focused on. Scores decay over time
+//! so that a contestant stays interesting for a little while after something happens to them.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    entity::{brain::focus::ActorFocus, Entity, EntityId},
+    event::{GameEvent, GameEventKind},
+    has_markers,
+};
+
+/// How much of the previous score carries into the next tick
+const DRAMA_DECAY: f32 = 0.8;
+
+/// How many suggestions we return to clients
+pub const FOCUS_SUGGESTION_COUNT: usize = 5;
+
+/// An entity that might be worth watching
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct FocusSuggestion {
+    pub entity_id: EntityId,
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DramaTracker {
+    scores: HashMap<EntityId, f32>,
+}
+
+impl DramaTracker {
+    /// Update scores given the state of the world after a tick and the events raised during it
+    pub fn update<'a>(&mut self, entities: impl Iterator<Item = &'a Entity>, events: &[GameEvent]) {
+        let players: Vec<_> = entities
+            .filter(|e| has_markers!(e, Player) && e.attributes.hex.is_some())
+            .collect();
+
+        // Everything decays, and anyone no longer around is forgotten
+        let mut next_scores: HashMap<EntityId, f32> = players
+            .iter()
+            .map(|p| {
+                let previous = self.scores.get(&p.entity_id).copied().unwrap_or_default();
+                (
+                    p.entity_id.clone(),
+                    previous * DRAMA_DECAY + Self::entity_score(p),
+                )
+            })
+            .collect();
+
+        // The subject of an event gets the most drama,
+        // but anyone in the hex it happened in gets some too
+        for event in events {
+            let weight = Self::event_weight(event.kind());
+            if let Some(score) = next_scores.get_mut(event.kind().entity_id()) {
+                *score += weight;
+            }
+
+            if let Some(location) = event.location() {
+                for witness in players
+                    .iter()
+                    .filter(|p| p.attributes.hex == Some(location))
+                    .filter(|p| &p.entity_id != event.kind().entity_id())
+                {
+                    *next_scores.get_mut(&witness.entity_id).unwrap() += weight * 0.5;
+                }
+            }
+        }
+
+        self.scores = next_scores;
+    }
+
+    /// The most dramatic entities, most dramatic first
+    pub fn suggestions(&self, count: usize) -> Vec<FocusSuggestion> {
+        let mut suggestions: Vec<_> = self
+            .scores
+            .iter()
+            .map(|(entity_id, score)| FocusSuggestion {
+                entity_id: entity_id.clone(),
+                score: *score,
+            })
+            .collect();
+        suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+        suggestions.truncate(count);
+        suggestions
+    }
+
+    /// Drama from the state of the entity itself
+    fn entity_score(entity: &Entity) -> f32 {
+        // Being close to death from anything is dramatic
+        // (squared so that its mostly the really bad cases that count)
+        let distress = entity.attributes.motivators.distress();
+
+        // Talking is more interesting than sleeping
+        let focus = match entity.attributes.focus {
+            Some(ActorFocus::Discussion { .. }) => 1.0,
+            Some(ActorFocus::TravelTogether { .. }) => 0.5,
+            Some(ActorFocus::Sleeping { .. }) => -0.5,
+            _ => 0.0,
+        };
+
+        (distress.powi(2) * 3.0 + focus).max(0.0)
+    }
+
+    fn event_weight(kind: &GameEventKind) -> f32 {
+        match kind {
+            GameEventKind::Death { .. } => 5.0,
+            GameEventKind::Deed { .. } => 2.0,
+            GameEventKind::Feat { .. } => 2.0,
+            GameEventKind::Trespass { .. } => 1.0,
+            GameEventKind::Projectile { hit: Some(_), .. } => 3.0,
+            GameEventKind::Projectile { hit: None, .. } => 1.0,
+            GameEventKind::Attack { .. } => 3.0,
+            GameEventKind::Warp { .. } => 0.5,
+            GameEventKind::Distress { .. } => 1.0,
+            GameEventKind::Memorial { .. } => 1.0,
+            GameEventKind::LeadDiscussion { .. } | GameEventKind::RespondDiscussion { .. } => 1.0,
+            GameEventKind::ArriveInHex { .. } | GameEventKind::LeaveHex { .. } => 0.2,
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Smaller water sources running low, and drying up in a long dry spell
+//!
+//! Lakes never run dry, but smaller water sources only hold so much (see `WaterCapacity`). Every
+//! drink takes some and rain tops them back up, but when it isn't raining they slowly evaporate.
+//! Once one has sat empty through a long enough dry spell it dries up for good: it's removed and
+//! everyone who knew of water there forgets about it. Over a long match this keeps players moving
+//! on to wherever the water still is.
+
+use itertools::Itertools;
+
+use crate::{
+    entity::{brain::meme::Meme, snapshot::EntityView, world::EntityWorld, WaterCapacity},
+    hex::AxialHex,
+    logs::{GameLog, GameLogBody},
+    ServerCtx,
+};
+
+use super::{MatchManager, TickId};
+
+/// How often (in ticks) water sources fill up or evaporate
+/// (not every tick, so they aren't all sent to clients every tick)
+pub const WATER_SOURCE_INTERVAL: TickId = 10;
+
+/// How many drinks heavy rain puts back into a water source each tick
+const RAIN_REFILL: f32 = 0.5;
+
+/// How many drinks evaporate from a water source each tick it isn't raining
+const EVAPORATION: f32 = 0.01;
+
+/// How many ticks a water source can sit empty without rain before it dries up for good
+const DRY_UP_TICKS: usize = 300;
+
+impl WaterCapacity {
+    /// Fill up or evaporate a little over some ticks of weather
+    /// (`rain` as in `rain_proc_chance_scale`, returns whether it has now dried up for good)
+    fn weather(&mut self, rain: f32, ticks: usize) -> bool {
+        if rain > 0.0 {
+            self.remaining = (self.remaining + rain * RAIN_REFILL * ticks as f32).min(self.max);
+            self.dry_for = 0;
+            return false;
+        }
+
+        self.remaining = (self.remaining - EVAPORATION * ticks as f32).max(0.0);
+        if self.remaining > 0.0 {
+            return false;
+        }
+        self.dry_for += ticks;
+        self.dry_for >= DRY_UP_TICKS
+    }
+}
+
+impl MatchManager {
+    /// Fill up or evaporate every water source that can run dry,
+    /// removing any that have been empty for too long
+    /// (called every `WATER_SOURCE_INTERVAL` ticks)
+    pub fn resolve_water_sources(
+        &mut self,
+        entities_view: &EntityView,
+        world_state: &EntityWorld,
+        ctx: &ServerCtx,
+    ) {
+        let rain = world_state.weather.rain_proc_chance_scale();
+        let limited = entities_view
+            .all()
+            .filter(|e| {
+                e.attributes
+                    .water_source
+                    .as_ref()
+                    .is_some_and(|w| w.capacity.is_some())
+            })
+            .collect_vec();
+        for water_source_entity in limited {
+            // It may be gone by now
+            let Some(mut entity) = self.entities.get_entity(&water_source_entity.entity_id) else {
+                continue;
+            };
+            let Some(capacity) = entity
+                .attributes
+                .water_source
+                .as_mut()
+                .and_then(|w| w.capacity.as_mut())
+            else {
+                continue;
+            };
+
+            let before = capacity.clone();
+            if !capacity.weather(rain, WATER_SOURCE_INTERVAL) {
+                if *capacity != before {
+                    self.entities.upsert_entity(entity).unwrap();
+                }
+                continue;
+            }
+
+            ctx.log_tx
+                .send(GameLog::entity(&entity, GameLogBody::WaterSourceDryUp))
+                .unwrap();
+            self.entities.remove_entity(&entity.entity_id).unwrap();
+            if let Some(hex) = entity.attributes.hex {
+                self.forget_water_source_at(hex);
+            }
+        }
+    }
+
+    /// Have everyone forget about water at some hex, unless there's still some there
+    fn forget_water_source_at(&mut self, hex: AxialHex) {
+        let still_water = self
+            .entities
+            .get_all_entities()
+            .any(|e| e.attributes.hex == Some(hex) && e.attributes.water_source.is_some());
+        if still_water {
+            return;
+        }
+
+        let knowing = self
+            .entities
+            .get_all_entities()
+            .filter(|e| {
+                e.attributes
+                    .memes
+                    .as_ref()
+                    .is_some_and(|memes| memes.water_source_locations().contains(&hex))
+            })
+            .map(|e| e.entity_id.clone())
+            .collect_vec();
+        for entity_id in knowing {
+            self.entities
+                .mutate(&entity_id, |e| {
+                    if let Some(memes) = e.attributes.memes.as_mut() {
+                        memes.remove(&Meme::WaterSourceAt(hex));
+                    }
+                })
+                .unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_water_sources_only_dry_up_after_a_long_dry_spell() {
+        let mut capacity = WaterCapacity {
+            remaining: 1.0,
+            max: 10.0,
+            dry_for: 0,
+        };
+
+        // Rain tops it back up (but never past full)
+        capacity.weather(1.0, 1);
+        assert_eq!(capacity.remaining, 1.5);
+        capacity.weather(1.0, 100);
+        assert_eq!(capacity.remaining, 10.0);
+
+        // It takes a long while without rain to dry up
+        let mut dry_ticks = WATER_SOURCE_INTERVAL;
+        while !capacity.weather(0.0, WATER_SOURCE_INTERVAL) {
+            dry_ticks += WATER_SOURCE_INTERVAL;
+        }
+        assert_eq!(capacity.remaining, 0.0);
+        assert!(dry_ticks > DRY_UP_TICKS);
+
+        // but rain in the meantime saves it
+        capacity.dry_for = DRY_UP_TICKS - 1;
+        assert!(!capacity.weather(0.3, 1));
+        assert_eq!(capacity.dry_for, 0);
+    }
+}
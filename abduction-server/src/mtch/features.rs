@@ -0,0 +1,109 @@
+//! Switching experimental systems on and off per match
+//!
+//! Each match has a set of `FeatureFlag`s (stored with its config). The brain checks them when it's
+//! deciding which signals raise which actions, and the tick checks them before running the world
+//! effects they cover, so a system can sit out some matches without being pulled out of the code.
+//!
+//! Any flags listed in `MATCH_EXPERIMENTS` (i.e `MATCH_EXPERIMENTS=looting,trading`) are switched
+//! on and off in alternating matches, so they can be compared against matches without them.
+
+use std::{collections::HashSet, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+use tracing::warn;
+
+/// Some system that a match can do without
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::EnumString,
+    strum::VariantArray,
+)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum FeatureFlag {
+    /// Players can go through the things a corpse was carrying
+    Looting,
+
+    /// Players can propose trades during a conversation
+    Trading,
+
+    /// Lots of rain can flood low lying hexes
+    Flooding,
+
+    /// Something out of the ordinary happens every so often (see `behavior.wildcards`)
+    Wildcards,
+
+    /// A broken escape pod or two can be fixed up for someone to escape early
+    EscapePods,
+
+    /// Smaller water sources run low as they're drunk from, and can dry up in a long dry spell
+    Drought,
+}
+
+impl FeatureFlag {
+    /// Every feature, as played in a match that hasn't opted out of any
+    pub fn all() -> HashSet<Self> {
+        Self::VARIANTS.iter().copied().collect()
+    }
+
+    /// The features being tried out in alternating matches (see `MATCH_EXPERIMENTS`)
+    pub fn experiments() -> HashSet<Self> {
+        let Ok(experiments) = std::env::var("MATCH_EXPERIMENTS") else {
+            return HashSet::new();
+        };
+        experiments
+            .split(',')
+            .map(str::trim)
+            .filter(|flag| !flag.is_empty())
+            .filter_map(|flag| {
+                Self::from_str(flag)
+                    .inspect_err(|_| warn!("Unknown feature flag {flag} in MATCH_EXPERIMENTS"))
+                    .ok()
+            })
+            .collect()
+    }
+}
+
+/// The features for the next match, given the ones the last match was played with
+/// (each experiment is on for the next match if it was off for the last one, and vice versa)
+pub fn alternate_experiments(
+    previous: Option<&HashSet<FeatureFlag>>,
+    experiments: &HashSet<FeatureFlag>,
+) -> HashSet<FeatureFlag> {
+    FeatureFlag::all()
+        .into_iter()
+        .filter(|flag| {
+            !experiments.contains(flag) || previous.is_some_and(|features| !features.contains(flag))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_experiments_alternate() {
+        let experiments = HashSet::from([FeatureFlag::Looting]);
+
+        // Experiments start off switched off
+        let first = alternate_experiments(None, &experiments);
+        assert!(!first.contains(&FeatureFlag::Looting));
+        assert!(first.contains(&FeatureFlag::Trading));
+
+        let second = alternate_experiments(Some(&first), &experiments);
+        assert_eq!(second, FeatureFlag::all());
+
+        let third = alternate_experiments(Some(&second), &experiments);
+        assert_eq!(third, first);
+    }
+}
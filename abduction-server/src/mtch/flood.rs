@@ -0,0 +1,131 @@
+//! Low-lying parts of the world flooding after a lot of rain
+//!
+//! The world has a water level which rain slowly raises (see `EntityWorld::update_water_level`).
+//! Once it's high enough every low-lying hex floods: the floodwater is a (dubious) water source
+//! but also a hazard, anything left on the ground is washed away and anyone there has to get out
+//! to higher ground. Once the rain stops and the water drops back down, the floodwater recedes.
+
+use std::collections::HashSet;
+
+use rand::seq::IteratorRandom;
+
+use crate::{
+    create_markers,
+    entity::{
+        brain::focus::ActorFocus, snapshot::EntityView, world::EntityWorld, Entity,
+        EntityAttributes, EntityHazard, EntityWaterSource,
+    },
+    has_markers,
+    hex::AxialHex,
+    logs::{GameLog, GameLogBody},
+    ServerCtx,
+};
+
+use super::MatchManager;
+
+/// Water level at which low-lying hexes flood
+const FLOOD_WATER_LEVEL: f32 = 0.7;
+
+/// Water level at which the floodwater recedes again
+const RECEDE_WATER_LEVEL: f32 = 0.3;
+
+/// How poisonous it is to drink floodwater
+const FLOODWATER_POISON: f32 = 0.4;
+
+impl MatchManager {
+    /// Flood low-lying hexes if the water is high enough, or dry them out once it drops again
+    pub fn resolve_flooding(
+        &mut self,
+        entities_view: &EntityView,
+        world_state: &EntityWorld,
+        ctx: &ServerCtx,
+    ) {
+        let flooded = entities_view.all().any(|e| has_markers!(e, Floodwater));
+        if !flooded && world_state.water_level >= FLOOD_WATER_LEVEL {
+            self.flood(entities_view, ctx);
+        } else if flooded && world_state.water_level <= RECEDE_WATER_LEVEL {
+            self.recede_flood(entities_view, ctx);
+        }
+    }
+
+    fn flood(&mut self, entities_view: &EntityView, ctx: &ServerCtx) {
+        let mut rng = crate::rng::rng();
+        let radius = self.config.world_radius as isize;
+        let low_lying: HashSet<AxialHex> = entities_view
+            .all()
+            .filter(|e| e.attributes.location.is_some() && has_markers!(e, LowLyingLocation))
+            .filter_map(|e| e.attributes.hex)
+            .collect();
+        if low_lying.is_empty() {
+            return;
+        }
+
+        ctx.log_tx
+            .send(GameLog::global(GameLogBody::Flood))
+            .unwrap();
+
+        for hex in &low_lying {
+            for entity in entities_view.in_hex(*hex) {
+                let Some(mut entity) = self.entities.get_entity(&entity.entity_id) else {
+                    continue;
+                };
+
+                // Anything lying around is washed away
+                if entity.attributes.item.is_some() {
+                    ctx.log_tx
+                        .send(GameLog::entity(&entity, GameLogBody::EntityWashedAway))
+                        .unwrap();
+                    self.entities.remove_entity(&entity.entity_id).unwrap();
+                    continue;
+                }
+
+                // Anyone here has to get to higher ground
+                if !has_markers!(entity, Being) || has_markers!(entity, Crew) {
+                    continue;
+                }
+                let Some(dry_hex) = hex
+                    .neighbours()
+                    .into_iter()
+                    .filter(|h| h.within_bounds(radius) && !low_lying.contains(h))
+                    .choose(&mut rng)
+                else {
+                    continue;
+                };
+                entity.attributes.hex = Some(dry_hex);
+                if entity.attributes.focus.is_some() {
+                    entity.attributes.focus = Some(ActorFocus::Unfocused);
+                }
+                ctx.log_tx
+                    .send(GameLog::entity(&entity, GameLogBody::EntityEscapeFlood))
+                    .unwrap();
+                self.entities.upsert_entity(entity).unwrap();
+            }
+
+            let floodwater = Entity {
+                entity_id: Entity::id(),
+                name: "Floodwater".into(),
+                markers: create_markers!(Floodwater, Inspectable),
+                attributes: EntityAttributes {
+                    hex: Some(*hex),
+                    water_source: Some(EntityWaterSource {
+                        poison: FLOODWATER_POISON,
+                        capacity: None,
+                    }),
+                    hazard: Some(EntityHazard { damage: 1 }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            self.entities.upsert_entity(floodwater).unwrap();
+        }
+    }
+
+    fn recede_flood(&mut self, entities_view: &EntityView, ctx: &ServerCtx) {
+        ctx.log_tx
+            .send(GameLog::global(GameLogBody::FloodRecede))
+            .unwrap();
+        for floodwater in entities_view.all().filter(|e| has_markers!(e, Floodwater)) {
+            self.entities.remove_entity(&floodwater.entity_id).unwrap();
+        }
+    }
+}
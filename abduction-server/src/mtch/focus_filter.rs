@@ -0,0 +1,138 @@
+//! Only sending clients the part of the match they're watching
+//!
+//! A client following one player (or one corner of the world) on a phone doesn't need every
+//! entity change and log in the match. Subscriptions can pass a `FocusFilter` and anything outside
+//! it is dropped on the server, before it's sent. Every part of the filter has to match for
+//! something to be sent, and anything a part can't be checked against (e.g a log with no hex, or an
+//! entity being removed) is let through, so clients never miss global goings on like the weather.
+//!
+//! NOTE: entities that move out of focus aren't removed, clients keep whatever they were last sent
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{manager::EntityManagerMutation, Entity, EntityId, EntityMarker},
+    hex::AxialHex,
+    logs::GameLog,
+};
+
+use super::TickEvent;
+
+/// What a client wants to hear about
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct FocusFilter {
+    /// Only what happens within some distance of a hex
+    pub near: Option<FocusArea>,
+
+    /// Only what involves one of these entities
+    pub entity_ids: Option<Vec<EntityId>>,
+
+    /// Only entities with one of these markers
+    /// (logs only know who was involved, so this only applies to entity changes)
+    pub markers: Option<Vec<EntityMarker>>,
+}
+
+/// Some hexes around a hex
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct FocusArea {
+    pub hex: AxialHex,
+    pub radius: isize,
+}
+
+impl FocusFilter {
+    fn allows_hex(&self, hex: Option<AxialHex>) -> bool {
+        match (&self.near, hex) {
+            (Some(area), Some(hex)) => area.hex.dist_to(hex) <= area.radius,
+            _ => true,
+        }
+    }
+
+    fn allows_entity_id(&self, entity_id: &EntityId) -> bool {
+        self.entity_ids
+            .as_ref()
+            .is_none_or(|entity_ids| entity_ids.contains(entity_id))
+    }
+
+    fn allows_entity(&self, entity: &Entity) -> bool {
+        self.allows_hex(entity.attributes.hex)
+            && self.allows_entity_id(&entity.entity_id)
+            && self
+                .markers
+                .as_ref()
+                .is_none_or(|markers| markers.iter().any(|m| entity.markers.contains(m)))
+    }
+
+    /// A log, if it's in focus
+    pub fn filter_log(&self, log: GameLog) -> Option<GameLog> {
+        let involves_focused = log.involved_entities.is_empty()
+            || log
+                .involved_entities
+                .iter()
+                .any(|entity_id| self.allows_entity_id(entity_id));
+        (self.allows_hex(log.hex) && involves_focused).then_some(log)
+    }
+
+    /// A tick event with only the changes in focus
+    /// (or nothing, if none of them are)
+    pub fn filter_tick_event(&self, event: TickEvent) -> Option<TickEvent> {
+        match event {
+            TickEvent::EntityChanges { mut changes } => {
+                changes.retain(|change| match change {
+                    EntityManagerMutation::SetEntity { entity } => self.allows_entity(entity),
+                    EntityManagerMutation::RemoveEntity { .. } => true,
+                });
+                (!changes.is_empty()).then_some(TickEvent::EntityChanges { changes })
+            }
+            event => Some(event),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{logs::GameLogBody, mtch::test_match::test_player};
+
+    #[test]
+    fn test_focus_filter_drops_what_is_out_of_focus() {
+        let near = test_player("Near", AxialHex::ZERO);
+        let far = test_player("Far", AxialHex::from((5, 0)));
+        let filter = FocusFilter {
+            near: Some(FocusArea {
+                hex: AxialHex::ZERO,
+                radius: 2,
+            }),
+            ..Default::default()
+        };
+
+        let say = || GameLogBody::EntitySayExact {
+            quote: "Hello?".to_owned(),
+        };
+        assert!(filter.filter_log(GameLog::entity(&near, say())).is_some());
+        assert!(filter.filter_log(GameLog::entity(&far, say())).is_none());
+        assert!(filter.filter_log(GameLog::global(say())).is_some());
+
+        let changes = [&near, &far]
+            .map(|entity| EntityManagerMutation::SetEntity {
+                entity: entity.clone(),
+            })
+            .to_vec();
+        let Some(TickEvent::EntityChanges { changes }) =
+            filter.filter_tick_event(TickEvent::EntityChanges { changes })
+        else {
+            panic!("Expected the near change to be kept");
+        };
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].entity_id(), &near.entity_id);
+
+        // Following someone in particular
+        let filter = FocusFilter {
+            entity_ids: Some(vec![far.entity_id.clone()]),
+            ..Default::default()
+        };
+        assert!(filter.filter_log(GameLog::entity(&near, say())).is_none());
+        assert!(filter.filter_log(GameLog::entity(&far, say())).is_some());
+    }
+}
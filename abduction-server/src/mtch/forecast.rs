@@ -0,0 +1,191 @@
+//! Playing the current match forward to see where it's headed
+//!
+//! Before stepping in (see `intervention`), whoever is running the show can ask how the next however
+//! many ticks are likely to go. The match is copied into a throwaway `MatchManager` with `dry_run`
+//! set, which plays ticks like any other but never flushes them, and is given a `ServerCtx` of its
+//! own so nothing it logs or sends reaches anyone watching. Its rolls aren't audited either (see
+//! `rng::unaudited`). It's only one way things could go, so it's a hint rather than a prophecy.
+
+use std::sync::Arc;
+
+use itertools::Itertools;
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::{
+    entity::{brain::motivator, EntityId},
+    has_markers,
+    logs::GameLog,
+    ServerCtx, LOG_CHANNEL_CAPACITY,
+};
+
+use super::{MatchManager, TickEvent, TickId};
+
+/// The most ticks a forecast will play forward
+pub const MAX_FORECAST_TICKS: usize = 500;
+
+/// How hungry someone has to be by the end of a forecast to be at risk of starving
+const STARVING_HUNGER: f32 = 0.8;
+
+/// How a forecast played out
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct Forecast {
+    /// How many ticks were played forward
+    /// (fewer than asked for if the match would be over by then)
+    pub ticks: usize,
+
+    /// Anyone who didn't make it, in the order they died
+    pub deaths: Vec<ProjectedDeath>,
+
+    /// Anyone still in play by the end who is close to starving
+    pub starving: Vec<EntityId>,
+
+    /// How many players are still in play by the end
+    pub players_remaining: usize,
+}
+
+/// Someone who died in a forecast
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct ProjectedDeath {
+    pub entity_id: EntityId,
+    pub name: String,
+    pub tick_id: TickId,
+}
+
+impl MatchManager {
+    /// A throwaway copy of the match, to play forward without any of it being kept
+    pub fn dry_run_copy(&self) -> Self {
+        let mut entities = self.entities.clone();
+        entities.discard_changes();
+        Self {
+            config: self.config.clone(),
+            entities,
+            events: Default::default(),
+            drama: Default::default(),
+            naming: Default::default(),
+            audience: Default::default(),
+            segments: Default::default(),
+            poll: Default::default(),
+            requests: Default::default(),
+            scheduler: Default::default(),
+            log_throttle: Default::default(),
+            world_preview: self.world_preview.clone(),
+            world_history: self.world_history.clone(),
+            tick_id: self.tick_id,
+            dry_run: true,
+        }
+    }
+
+    /// Players (by id and name) still in play
+    fn players_in_play(&self) -> Vec<(EntityId, String)> {
+        self.entities
+            .get_all_entities()
+            .filter(|e| has_markers!(e, Player) && !has_markers!(e, Escaped))
+            .map(|e| (e.entity_id.clone(), e.name.clone()))
+            .collect()
+    }
+}
+
+/// Play a dry run copy of a match (see `MatchManager::dry_run_copy`) forward some number of ticks,
+/// or until it's over, and see how it turns out
+pub async fn forecast(mut mm: MatchManager, ticks: usize, ctx: &ServerCtx) -> Forecast {
+    assert!(
+        mm.dry_run,
+        "Can only forecast with a dry run copy of a match"
+    );
+
+    // (the tick receiver is held so sending doesn't fail for lack of one, and moderation is left
+    //  out so the forecast doesn't take any renames meant for the real thing)
+    let (tick_tx, _tick_rx) = broadcast::channel::<TickEvent>(20);
+    let (log_tx, _log_rx) = broadcast::channel::<GameLog>(LOG_CHANNEL_CAPACITY);
+    let sandbox = ServerCtx {
+        tick_tx,
+        log_tx,
+        db: ctx.db.clone(),
+        current_match: Default::default(),
+        flags: Arc::default(),
+        moderation: Arc::default(),
+    };
+
+    let start_tick = mm.tick_id;
+    let mut deaths = Vec::new();
+    crate::rng::unaudited(async {
+        for _ in 0..ticks.min(MAX_FORECAST_TICKS) {
+            let players = mm.players_in_play();
+            mm.perform_match_tick(&sandbox).await;
+
+            // Players only ever leave play by escaping (which they're still counted for) or dying
+            for (entity_id, name) in players {
+                if mm.entities.get_entity(&entity_id).is_none() {
+                    deaths.push(ProjectedDeath {
+                        entity_id,
+                        name,
+                        tick_id: mm.tick_id,
+                    });
+                }
+            }
+
+            if mm.match_over() {
+                break;
+            }
+        }
+    })
+    .await;
+
+    let remaining = mm.players_in_play();
+    let starving = remaining
+        .iter()
+        .filter(|(entity_id, _)| {
+            mm.entities.get_entity(entity_id).is_some_and(|e| {
+                e.attributes
+                    .motivators
+                    .get_motivation::<motivator::Hunger>()
+                    .is_some_and(|hunger| hunger >= STARVING_HUNGER)
+            })
+        })
+        .map(|(entity_id, _)| entity_id.clone())
+        .collect_vec();
+
+    Forecast {
+        ticks: mm.tick_id - start_tick,
+        deaths,
+        starving,
+        players_remaining: remaining.len(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        hex::AxialHex,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_forecast_leaves_the_match_alone() {
+        let builder = TestMatch::builder();
+        let mut doomed = test_player("Doomed", AxialHex::ZERO);
+        doomed
+            .attributes
+            .motivators
+            .bump_scaled::<motivator::Hurt>(1000.0);
+        let doomed_id = doomed.entity_id.clone();
+
+        let test_match = builder
+            .with_entity(doomed)
+            .with_entity(test_player("Fine", AxialHex::ZERO))
+            .build()
+            .await;
+        let forecast = forecast(test_match.manager.dry_run_copy(), 10, test_match.ctx()).await;
+
+        assert!(forecast.deaths.iter().any(|d| d.entity_id == doomed_id));
+        assert_eq!(forecast.players_remaining, 1);
+
+        // Though it's still alive and well in the real thing
+        assert_eq!(test_match.manager.tick_id, 0);
+        assert!(test_match.entity(&doomed_id).is_some());
+    }
+}
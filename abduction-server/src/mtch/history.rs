@@ -0,0 +1,202 @@
+//! Reconstructing the state of a match at any tick in its history
+//!
+//! Every mutation records the tick it happened during, so replaying them up to some tick gives every entity
+//! as it was at the end of that tick. So that scrubbing back through a long match doesn't mean replaying
+//! all of it every time, a snapshot of every entity is saved every `SNAPSHOT_INTERVAL` ticks and
+//! replaying starts from the nearest one.
+//!
+//! The same mutations let a client that dropped out part way through a match catch up on just what
+//! it missed (see `changes_since`), rather than fetching every entity again.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Context;
+use itertools::Itertools;
+use sqlx::{query_file, types::Json};
+
+use crate::{
+    entity::{Entity, EntityId, EntityManagerMutation, EntityMutation, EntityPayload},
+    Db,
+};
+
+use super::{MatchId, MatchManager, TickId};
+
+/// How many ticks between saved snapshots
+const SNAPSHOT_INTERVAL: TickId = 100;
+
+/// The last tick of a match that changed anything (0 if nothing has happened yet)
+pub async fn latest_tick(match_id: &MatchId, db: &Db) -> anyhow::Result<TickId> {
+    let row = query_file!("queries/get_latest_match_tick.sql", match_id)
+        .fetch_one(db)
+        .await
+        .context("Failed to get latest tick")?;
+    Ok(row.tick_id.unwrap_or_default() as TickId)
+}
+
+/// Every entity in a match as it was at the end of some tick
+pub async fn state_at_tick(
+    match_id: &MatchId,
+    tick_id: TickId,
+    db: &Db,
+) -> anyhow::Result<Vec<Entity>> {
+    // Start from the nearest snapshot if there is one
+    let up_to = tick_id as i64;
+    let snapshot = query_file!("queries/get_latest_entity_snapshot.sql", match_id, up_to)
+        .fetch_optional(db)
+        .await
+        .context("Failed to load entity snapshot")?;
+    let (snapshot_tick, mut entities) = match snapshot {
+        Some(row) => (Some(row.tick_id as TickId), row.entities.0),
+        None => (None, HashMap::new()),
+    };
+
+    // Then replay everything that happened since
+    for mutation in EntityMutation::load_between(match_id, snapshot_tick, tick_id, db).await? {
+        mutation.apply_to(&mut entities);
+    }
+
+    let mut entities = entities
+        .into_iter()
+        .map(|(entity_id, payload)| payload.convert_to_entity(entity_id))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    entities.sort_by(|a, b| a.entity_id.cmp(&b.entity_id));
+    Ok(entities)
+}
+
+/// What changed in a match after tick `after` up to and including tick `up_to`, as clients are sent it
+/// (only the last change to each entity, as that's all a client catching up needs)
+pub async fn changes_since(
+    match_id: &MatchId,
+    after: Option<TickId>,
+    up_to: TickId,
+    db: &Db,
+) -> anyhow::Result<Vec<EntityManagerMutation>> {
+    let mutations = EntityMutation::load_between(match_id, after, up_to, db).await?;
+
+    // Work backwards keeping the first change we see for each entity
+    let mut seen = HashSet::new();
+    let mut changes = mutations
+        .into_iter()
+        .rev()
+        .filter_map(EntityMutation::into_entity_manager_mutation)
+        .filter(|change| seen.insert(change.entity_id().clone()))
+        .collect_vec();
+    changes.reverse();
+    Ok(changes)
+}
+
+impl MatchManager {
+    /// Save a snapshot of every entity, if this tick is due one
+    pub async fn maybe_save_snapshot(&self, db: &Db) -> anyhow::Result<()> {
+        if !self.tick_id.is_multiple_of(SNAPSHOT_INTERVAL) {
+            return Ok(());
+        }
+
+        let entities: HashMap<EntityId, EntityPayload> = self
+            .entities
+            .get_all_entities()
+            .map(|entity| (entity.entity_id.clone(), entity.clone().into()))
+            .collect();
+        let entities = Json(entities);
+        let tick_id = self.tick_id as i64;
+        query_file!(
+            "queries/add_entity_snapshot.sql",
+            self.config.match_id,
+            tick_id,
+            entities
+        )
+        .execute(db)
+        .await
+        .context("Failed to save entity snapshot")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::{
+        entity::EntityManagerMutation,
+        hex::AxialHex,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_state_at_tick_matches_live() {
+        let builder = TestMatch::builder();
+        let player = test_player("Scrubbed", AxialHex::ZERO);
+        let player_id = player.entity_id.clone();
+        let mut test_match = builder.with_entity(player).build().await;
+
+        // Remember how they were at the end of each tick
+        let mut history = Vec::new();
+        for _ in 0..5 {
+            test_match.tick().await;
+            let player = test_match.entity(&player_id).unwrap();
+            history.push((test_match.manager.tick_id, player.attributes.motivators));
+        }
+
+        let match_id = test_match.manager.config.match_id.clone();
+        for (tick_id, motivators) in history {
+            let entities = state_at_tick(&match_id, tick_id, &test_match.ctx().db)
+                .await
+                .unwrap();
+            let player = entities.iter().find(|e| e.entity_id == player_id).unwrap();
+            assert_eq!(
+                player
+                    .attributes
+                    .motivators
+                    .motivations()
+                    .collect::<HashMap<_, _>>(),
+                motivators.motivations().collect::<HashMap<_, _>>()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_changes_since_catches_up() {
+        let builder = TestMatch::builder();
+        let player = test_player("Reconnecting", AxialHex::ZERO);
+        let player_id = player.entity_id.clone();
+        let mut test_match = builder.with_entity(player).build().await;
+        for _ in 0..5 {
+            test_match.tick().await;
+        }
+
+        let match_id = test_match.manager.config.match_id.clone();
+        let changes = changes_since(&match_id, Some(2), 5, &test_match.ctx().db)
+            .await
+            .unwrap();
+        assert!(changes.iter().map(|change| change.entity_id()).all_unique());
+
+        // Only their latest state is replayed
+        let player = changes
+            .iter()
+            .find_map(|change| match change {
+                EntityManagerMutation::SetEntity { entity } if entity.entity_id == player_id => {
+                    Some(entity)
+                }
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            player
+                .attributes
+                .motivators
+                .motivations()
+                .collect::<HashMap<_, _>>(),
+            test_match
+                .entity(&player_id)
+                .unwrap()
+                .attributes
+                .motivators
+                .motivations()
+                .collect::<HashMap<_, _>>()
+        );
+    }
+}
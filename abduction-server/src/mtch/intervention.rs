@@ -0,0 +1,145 @@
+//! Operators (or the viewers, via polls) reaching into a running match
+//!
+//! These are applied between ticks, so the changes go out to clients with the next tick's entity
+//! changes like anything else. They deliberately go through the usual systems where they can
+//! (e.g killing someone just hurts them enough that they die on their own turn), so the logs,
+//! corpses, stats etc all still happen.
+//!
+//! Anything an operator does is put on the record (see `MatchManager::record_intervention`), both
+//! in the append only `admin_audit` table (see `admin::audit`) and in front of viewers as a divine
+//! intervention, so the show stays above board.
+
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    admin,
+    command::{Command, CommandSource},
+    entity::{
+        brain::motivator::MotivatorKey, generate::PropGenerator, world::WeatherKind, Entity,
+        EntityId,
+    },
+    hex::AxialHex,
+    logs::{GameLog, GameLogBody},
+    mtch::MatchManager,
+    ServerCtx,
+};
+
+/// What an operator did to a match (as shown to viewers)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[qubit::ts]
+pub enum Intervention {
+    /// Put down a new prop (the primary entity)
+    SpawnProp,
+
+    /// Set one of the primary entity's motivators outright
+    SetMotivator {
+        motivator: MotivatorKey,
+        motivation: f32,
+    },
+
+    /// Struck down the primary entity
+    Kill,
+
+    /// Changed the weather
+    SetWeather { weather: WeatherKind },
+}
+
+impl Intervention {
+    /// The log letting viewers know about it (involving whatever it was done to, if anything)
+    pub fn log(self, entity: Option<&Entity>) -> GameLog {
+        let body = GameLogBody::DivineIntervention { intervention: self };
+        match entity {
+            Some(entity) => GameLog::entity(entity, body),
+            None => GameLog::global(body),
+        }
+    }
+}
+
+impl MatchManager {
+    /// Find an entity by its id, or failing that by its name (ignoring case)
+    pub fn find_entity(&self, id_or_name: &str) -> Option<Entity> {
+        self.entities
+            .get_entity(&id_or_name.to_owned())
+            .or_else(|| {
+                self.entities
+                    .get_all_entities()
+                    .find(|e| e.name.eq_ignore_ascii_case(id_or_name))
+                    .cloned()
+            })
+    }
+
+    /// Change the weather right now, rather than waiting for it to turn
+    pub fn set_weather(&mut self, weather: WeatherKind, ctx: &ServerCtx) -> anyhow::Result<()> {
+        let mut world_entity = self
+            .entities
+            .world()
+            .cloned()
+            .ok_or(anyhow!("No world entity"))?;
+        let world = world_entity.attributes.world.as_mut().unwrap();
+        world.weather = weather;
+        ctx.log_tx
+            .send(GameLog::global(GameLogBody::WeatherChange {
+                weather: world.weather.clone(),
+            }))
+            .unwrap();
+        self.entities.upsert_entity(world_entity)
+    }
+
+    /// Put a new prop (and anything it holds) down somewhere in the world
+    /// (returns the id of the prop itself)
+    pub fn spawn_prop(&mut self, prop: PropGenerator, hex: AxialHex) -> anyhow::Result<EntityId> {
+        if hex.dist_to_origin() > self.config.world_radius as isize {
+            bail!("{hex} is outside the world");
+        }
+        let mut rng = crate::rng::rng();
+        let entities = prop.generate_at(hex, &mut rng);
+        let entity_id = entities[0].entity_id.clone();
+        for entity in entities {
+            self.entities.upsert_entity(entity)?;
+        }
+        Ok(entity_id)
+    }
+
+    /// Set one of an entity's motivators outright
+    pub fn set_motivation(
+        &mut self,
+        entity_id: &EntityId,
+        key: MotivatorKey,
+        motivation: f32,
+    ) -> anyhow::Result<()> {
+        let mut entity = self
+            .entities
+            .get_entity(entity_id)
+            .ok_or(anyhow!("No entity {entity_id}"))?;
+        if !entity.attributes.motivators.set_key(key, motivation) {
+            bail!("{} has no {key:?} motivator", entity.name);
+        }
+        self.entities.upsert_entity(entity)
+    }
+
+    /// Hurt an entity so badly they die the next time they act
+    pub fn kill_entity(&mut self, entity_id: &EntityId) -> anyhow::Result<()> {
+        self.set_motivation(entity_id, MotivatorKey::Hurt, 1.0)
+    }
+
+    /// Put an operator's intervention on the record, and let viewers know about it
+    /// (interventions happen between ticks, so the log is saved here rather than with a tick's)
+    pub async fn record_intervention(
+        &self,
+        source: &CommandSource,
+        command: &Command,
+        log: GameLog,
+        ctx: &ServerCtx,
+    ) -> anyhow::Result<()> {
+        let during = Some((&self.config.match_id, self.tick_id));
+        admin::audit_during(&ctx.db, &source.actor(), during, &format!("{command:?}"))
+            .await
+            .context("Failed to record intervention")?;
+
+        log.save(&self.config.match_id, &ctx.db).await?;
+        ctx.log_tx.send(log)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+//! Sanity checks on the state of a match
+//!
+//! These should always hold after a tick, if they don't then some action or side effect
+//! left the world in a state it shouldn't be in. They are only checked in debug builds.
+
+use std::{collections::HashMap, fmt};
+
+use tracing::warn;
+
+use crate::{
+    entity::{
+        brain::{focus::ActorFocus, motivator::MotivatorKey},
+        Entity, EntityId,
+    },
+    has_markers,
+    hex::AxialHex,
+};
+
+use super::MatchManager;
+
+/// Something about the state of a match that should never be the case
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvariantViolation {
+    /// An entity is holding an item that doesn't exist
+    MissingInventoryItem {
+        entity_id: EntityId,
+        item_id: EntityId,
+    },
+
+    /// An item is held by someone but still placed in the world
+    HeldItemInHex {
+        entity_id: EntityId,
+        item_id: EntityId,
+        hex: AxialHex,
+    },
+
+    /// A player has wandered off the edge of the world
+    PlayerOutOfBounds { entity_id: EntityId, hex: AxialHex },
+
+    /// An entity is in a discussion with someone that isn't in a discussion with them
+    OneSidedDiscussion { entity_id: EntityId, with: EntityId },
+
+    /// A motivation has gone outside of [0, 1]
+    MotivatorOutOfRange {
+        entity_id: EntityId,
+        motivator: MotivatorKey,
+        motivation: f32,
+    },
+}
+
+impl InvariantViolation {
+    /// The entities whose mutations are worth looking at to explain this
+    pub fn involved_entities(&self) -> Vec<&EntityId> {
+        match self {
+            InvariantViolation::MissingInventoryItem { entity_id, item_id }
+            | InvariantViolation::HeldItemInHex {
+                entity_id, item_id, ..
+            } => vec![entity_id, item_id],
+            InvariantViolation::OneSidedDiscussion { entity_id, with } => vec![entity_id, with],
+            InvariantViolation::PlayerOutOfBounds { entity_id, .. }
+            | InvariantViolation::MotivatorOutOfRange { entity_id, .. } => vec![entity_id],
+        }
+    }
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantViolation::MissingInventoryItem { entity_id, item_id } => {
+                write!(f, "{entity_id} holds non-existent item {item_id}")
+            }
+            InvariantViolation::HeldItemInHex {
+                entity_id,
+                item_id,
+                hex,
+            } => write!(f, "{item_id} is held by {entity_id} but is also at {hex}"),
+            InvariantViolation::PlayerOutOfBounds { entity_id, hex } => {
+                write!(f, "player {entity_id} is out of bounds at {hex}")
+            }
+            InvariantViolation::OneSidedDiscussion { entity_id, with } => {
+                write!(
+                    f,
+                    "{entity_id} is in a discussion with {with} but not vice versa"
+                )
+            }
+            InvariantViolation::MotivatorOutOfRange {
+                entity_id,
+                motivator,
+                motivation,
+            } => write!(
+                f,
+                "{entity_id} has {motivator:?} motivation of {motivation}"
+            ),
+        }
+    }
+}
+
+/// Check every invariant against a set of entities
+pub fn check_invariants<'a>(
+    entities: impl Iterator<Item = &'a Entity>,
+    world_radius: isize,
+) -> Vec<InvariantViolation> {
+    let by_id: HashMap<&EntityId, &Entity> = entities.map(|e| (&e.entity_id, e)).collect();
+    let mut violations = Vec::new();
+
+    for entity in by_id.values() {
+        let entity_id = &entity.entity_id;
+
+        // Held items must exist, and must not also be out in the world
+        for item_id in entity.relations.inventory() {
+            match by_id.get(item_id) {
+                None => violations.push(InvariantViolation::MissingInventoryItem {
+                    entity_id: entity_id.clone(),
+                    item_id: item_id.clone(),
+                }),
+                Some(item) => {
+                    if let Some(hex) = item.attributes.hex {
+                        violations.push(InvariantViolation::HeldItemInHex {
+                            entity_id: entity_id.clone(),
+                            item_id: item_id.clone(),
+                            hex,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Players stay within the world
+        if let Some(hex) = entity.attributes.hex {
+            if has_markers!(entity, Player) && !hex.within_bounds(world_radius) {
+                violations.push(InvariantViolation::PlayerOutOfBounds {
+                    entity_id: entity_id.clone(),
+                    hex,
+                });
+            }
+        }
+
+        // Discussions take two
+        if let Some(ActorFocus::Discussion { with, .. }) = &entity.attributes.focus {
+            let reciprocated = by_id.get(with).is_some_and(|other| {
+                matches!(
+                    &other.attributes.focus,
+                    Some(ActorFocus::Discussion { with: other_with, .. }) if other_with == entity_id
+                )
+            });
+            if !reciprocated {
+                violations.push(InvariantViolation::OneSidedDiscussion {
+                    entity_id: entity_id.clone(),
+                    with: with.clone(),
+                });
+            }
+        }
+
+        // Motivations are always 0-1
+        for (key, motivation) in entity.attributes.motivators.motivations() {
+            if !(0.0..=1.0).contains(&motivation) {
+                violations.push(InvariantViolation::MotivatorOutOfRange {
+                    entity_id: entity_id.clone(),
+                    motivator: *key,
+                    motivation,
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+impl MatchManager {
+    /// Check the invariants for the current state of the match
+    pub fn check_invariants(&self) -> Vec<InvariantViolation> {
+        check_invariants(
+            self.entities.get_all_entities(),
+            self.config.world_radius as isize,
+        )
+    }
+
+    /// Check the invariants, and log any violations along with the recent mutations
+    /// to the entities involved
+    pub fn report_invariant_violations(&self) {
+        for violation in self.check_invariants() {
+            warn!("Invariant violated: {violation}");
+            for entity_id in violation.involved_entities() {
+                for mutation in self.entities.mutation_trail(entity_id) {
+                    warn!("  {entity_id}: {mutation:?}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mtch::test_match::test_player;
+
+    use super::*;
+
+    #[test]
+    fn test_detects_violations() {
+        let mut wanderer = test_player("Wanderer", AxialHex::ZERO);
+        wanderer.attributes.hex = Some(AxialHex::from((5, 0)));
+        wanderer
+            .relations
+            .inventory_mut()
+            .insert("nothing".to_owned());
+        let wanderer_id = wanderer.entity_id.clone();
+
+        let violations = check_invariants([wanderer].iter(), 2);
+        assert!(
+            violations.contains(&InvariantViolation::MissingInventoryItem {
+                entity_id: wanderer_id.clone(),
+                item_id: "nothing".to_owned(),
+            })
+        );
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, InvariantViolation::PlayerOutOfBounds { .. })));
+    }
+}
@@ -12,28 +12,90 @@
 /// - This match will then have lots of players generated for it
 /// - The match will then be scheduled but not run until the Monday.
 /// - Add queries and UI such that players can see the next upcoming match.
+/// - (a seeded match and those that follow on from it make up a season, see `season_config`)
+pub mod actor;
+pub mod archive;
+pub mod audience;
+pub mod calendar;
 pub mod config;
 pub mod crew;
+pub mod despawn;
+pub mod drama;
+pub mod drought;
+pub mod features;
+pub mod flood;
+pub mod focus_filter;
+pub mod forecast;
+pub mod history;
+pub mod intervention;
+pub mod invariants;
+pub mod moderation;
+pub mod naming;
+pub mod pace;
+pub mod poll;
+pub mod prepare;
+pub mod preview;
+pub mod profile;
+pub mod requests;
+pub mod ruleset;
+pub mod schedule;
+pub mod search;
+pub mod season;
+pub mod season_config;
+pub mod segments;
+pub mod sizing;
+pub mod standings;
+pub mod stats;
+pub mod telemetry;
+#[cfg(test)]
+pub mod test_match;
+pub mod throttle;
 pub mod tick;
+pub mod wildcard;
+pub mod world_history;
+
+use std::sync::Arc;
 
 use anyhow::Context;
 pub use config::*;
 
+use itertools::Itertools;
 use rand::Rng;
 use serde::Serialize;
 use tokio::sync::broadcast::Sender;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
+    behavior::behavior,
     entity::{
-        generate::generate_player, snapshot::EntityView, world::EntityWorld, Entity,
-        EntityAttributes, EntityManager, EntityManagerMutation,
+        brain::focus::ActorFocus,
+        generate::{generate_escape_pods, generate_player, generate_world},
+        snapshot::EntityView,
+        world::EntityWorld,
+        Entity, EntityId, EntityManager, EntityManagerMutation,
     },
     event::{EventStore, EventsView, GameEvent},
     has_markers,
     location::{generate_locations_for_world, Biome},
     logs::GameLog,
-    mtch::crew::{generate_collector, generate_presenter},
+    mtch::{
+        audience::AudienceTracker,
+        crew::{generate_collector, generate_presenter},
+        drama::DramaTracker,
+        features::FeatureFlag,
+        naming::NameVotes,
+        poll::ViewerPoll,
+        preview::WorldPreview,
+        profile::TickSummary,
+        requests::{PendingRequests, RequestId},
+        schedule::ActorScheduler,
+        season_config::SeasonConfig,
+        segments::{SegmentKind, SegmentScheduler},
+        stats::{PlayerStat, PlayerStatsBuffer},
+        telemetry::MatchTelemetry,
+        throttle::LogThrottle,
+        world_history::WorldHistory,
+    },
     Db, ServerCtx,
 };
 
@@ -41,9 +103,8 @@ use crate::{
 /// (generated as a UUID but its just TEXT, can be anything...)
 pub type MatchId = String;
 
-/// An id identifying a specific tick
-/// NOTE: Not scoped to a match but global for the server
-/// NOTE: Tick ids are not unique and may overflow, just helps with debugging and testing
+/// An id identifying a specific tick of a match
+/// (counts up from 1, 0 being before the match has started)
 pub type TickId = usize;
 
 /// The context that actions are resolved in
@@ -54,9 +115,12 @@ pub struct ActionCtx<'a> {
     pub events: &'a EventsView<'a>,
     pub config: &'a MatchConfig,
     pub world_state: &'a EntityWorld,
+    pub tick_id: TickId,
 
     log_tx: &'a Sender<GameLog>,
+    log_throttle: &'a mut LogThrottle,
     events_buffer: &'a mut Vec<GameEvent>,
+    stats_buffer: &'a mut PlayerStatsBuffer,
 }
 
 impl ActionCtx<'_> {
@@ -69,15 +133,48 @@ impl ActionCtx<'_> {
         }
     }
 
+    /// Send a log that tends to come up over and over (e.g barks)
+    /// held back if the same entity sent one just like it recently, see `throttle::LogThrottle`
+    pub fn send_log_throttled(&mut self, log: GameLog) {
+        if let Some(log) = self.log_throttle.throttle(log, self.tick_id) {
+            self.send_log(log);
+        }
+    }
+
     pub fn add_event(&mut self, event: GameEvent) {
         self.events_buffer.push(event);
     }
+
+    /// Count something a player did towards their match stats
+    pub fn record_stat(&mut self, entity: &Entity, stat: PlayerStat) {
+        self.stats_buffer.record(entity, stat);
+    }
 }
 
 pub struct MatchManager {
     pub config: MatchConfig,
     pub entities: EntityManager,
     pub events: EventStore,
+    pub drama: DramaTracker,
+    pub naming: NameVotes,
+    pub audience: AudienceTracker,
+    pub segments: SegmentScheduler,
+    pub poll: ViewerPoll,
+    pub requests: PendingRequests,
+    pub scheduler: ActorScheduler,
+    pub log_throttle: LogThrottle,
+
+    /// What the map looks like (see `preview`)
+    pub world_preview: Arc<WorldPreview>,
+
+    /// How the weather and time of day have changed (see `world_history`)
+    pub world_history: WorldHistory,
+
+    /// The last tick that was performed
+    pub tick_id: TickId,
+
+    /// If set, ticks are never flushed to the db or sent to clients (see `forecast`)
+    pub dry_run: bool,
 }
 
 impl MatchManager {
@@ -86,33 +183,74 @@ impl MatchManager {
         let mut match_entities = EntityManager::new(&match_config.match_id);
         match_entities.load_entities(db).await;
 
+        // If the world has already been laid out, so has the preview
+        let world_preview = Arc::new(WorldPreview::of(
+            match_entities.get_all_entities(),
+            match_config.world_radius as usize,
+        ));
+
+        // Carry on from how the world last was (see `world_history`)
+        let world_history = WorldHistory::load(&match_config.match_id, db)
+            .await
+            .unwrap_or_else(|err| {
+                warn!("Failed to load world history: {err:?}");
+                Default::default()
+            });
+
+        // If we are resuming, carry on counting from where we left off
+        let tick_id = history::latest_tick(&match_config.match_id, db)
+            .await
+            .unwrap();
+
         Self {
             config: match_config,
             entities: match_entities,
             events: Default::default(),
+            drama: Default::default(),
+            naming: Default::default(),
+            audience: Default::default(),
+            segments: Default::default(),
+            poll: Default::default(),
+            requests: Default::default(),
+            scheduler: Default::default(),
+            log_throttle: Default::default(),
+            world_preview,
+            world_history,
+            tick_id,
+            dry_run: false,
         }
     }
 
-    /// Load in a match configuration, generating any resources needed for the game
+    /// Generate the players for a new match
     ///
-    /// This should only be done once per match, realistically - so prob do it when
-    /// the config is created
-    pub async fn initialise_new_match(&mut self, _db: &Db) -> anyhow::Result<()> {
-        // Now we initialise it...
-        info!("Initialising match {}", &self.config.match_id);
-
-        // TODO: Add all the unescaped players from the last game
-        // In practice, this just means cloning the entity into the new match
-        let existing_players = 0;
-        // if let Some(preceding_match_id) = &self.match_config.preceding_match_id {
-        //     EntityManager::load_entities_from_match(preceding_match_id, db)
-        //         .await
-        //         .filter(|e| has_markers!(e, Player) && !has_markers!(e, Escaped))
-        //         .for_each(|entity| {
-        //             existing_players += 1;
-        //             unimplemented!(); // TODO: actually copy across the entity
-        //         })
-        // }
+    /// This is done when the match is created (well before it starts) so that everyone can see who
+    /// will be playing. They are saved straight away, but not sent to clients as nothing is being played yet
+    pub async fn prepare_new_match(&mut self, db: &Db) -> anyhow::Result<()> {
+        info!("Preparing players for match {}", &self.config.match_id);
+
+        // Bring back everyone who made it out of the last match (see `season`)
+        // (NOTE: the dead are only left behind as corpses, so they stay dead either way)
+        if let Some(preceding_match_id) = &self.config.preceding_match_id {
+            let preceding = EntityManager::load_entities_from_match(preceding_match_id, db)
+                .await
+                .collect_vec();
+            let rules = SeasonConfig::of_match(&self.config.match_id, db)
+                .await?
+                .map(|season| season.carry_over.0)
+                .unwrap_or_default();
+            let returning = season::returning_players(preceding_match_id, &preceding, &rules);
+            for player in returning
+                .into_iter()
+                .take(self.config.player_count as usize)
+            {
+                self.entities.upsert_entity(player)?;
+            }
+        }
+        let existing_players = self
+            .entities
+            .get_all_entities()
+            .filter(|e| has_markers!(e, Player))
+            .count() as i32;
 
         // If we dont have enough players for the match configuration,
         // then generate and add more
@@ -127,8 +265,24 @@ impl MatchManager {
             self.entities.upsert_entity(player_entity)?;
         }
 
+        self.entities.save_changes(self.tick_id, db).await
+    }
+
+    /// Generate the world for a match that is about to start (its players are already prepared)
+    ///
+    /// This should only be done once per match, when it starts
+    pub async fn initialise_new_match(&mut self, _db: &Db) -> anyhow::Result<()> {
+        // Now we initialise it...
+        info!("Initialising match {}", &self.config.match_id);
+
+        // Spread props thicker or thinner depending on how many players there are to share them
+        let prop_density = behavior().sizing.prop_density(
+            self.config.player_count as usize,
+            self.config.world_radius as usize,
+        );
+
         // Generate a location entity in each hex
-        let mut rng = rand::rng();
+        let mut rng = crate::rng::rng();
         for entity in generate_locations_for_world(self.config.world_radius as isize, Biome::Green)
         {
             // Create the location
@@ -139,72 +293,113 @@ impl MatchManager {
             let location_kind = entity.attributes.location.as_ref().unwrap().location_kind;
             let prop_generators = location_kind.prop_generators();
             let max_gen = prop_generators.max_count.unwrap_or(5);
-            let prop_count = rng.random_range(0..=max_gen);
+            let prop_count = (rng.random_range(0..=max_gen) as f32 * prop_density).round() as usize;
 
             // Generate required entities for location type
             for required_generator in &prop_generators.required {
-                let mut entity = required_generator.generate(&mut rng);
-                // Set its location and insert it
-                entity.attributes.hex = Some(*hex);
-                self.entities.upsert_entity(entity)?;
+                for entity in required_generator.generate_at(*hex, &mut rng) {
+                    self.entities.upsert_entity(entity)?;
+                }
             }
 
             // Generate a few from the optional generators
             if !prop_generators.optional.is_empty() {
                 for _ in 0..prop_count {
-                    let entity = prop_generators.generate_optional_at(*hex, &mut rng);
-                    self.entities.upsert_entity(entity)?;
+                    for entity in prop_generators.generate_optional_at(*hex, &mut rng) {
+                        self.entities.upsert_entity(entity)?;
+                    }
                 }
             }
+
+            // And maybe something rare
+            for entity in prop_generators.generate_rare_at(*hex, &mut rng) {
+                self.entities.upsert_entity(entity)?;
+            }
+        }
+
+        // Leave a broken escape pod or two lying around for the keen to fix up
+        if self.config.has_feature(FeatureFlag::EscapePods) {
+            for entity in generate_escape_pods(self.config.world_radius as isize, &mut rng) {
+                self.entities.upsert_entity(entity)?;
+            }
         }
 
         // Establish the current state of the world
-        self.entities.upsert_entity(Entity {
-            entity_id: Entity::id(),
-            name: "World".into(),
-            attributes: EntityAttributes {
-                world: Some(EntityWorld::default()),
-                ..Default::default()
-            },
-            ..Default::default()
-        })?;
+        self.entities.upsert_entity(generate_world())?;
 
         // Add the presenter and co-host
         self.entities.upsert_entity(generate_presenter())?;
         self.entities.upsert_entity(generate_collector())?;
 
-        Ok(())
-    }
+        // Now that the map is laid out, it can be previewed
+        self.world_preview = Arc::new(WorldPreview::of(
+            self.entities.get_all_entities(),
+            self.config.world_radius as usize,
+        ));
 
-    pub fn all_entity_states(&self) -> Vec<Entity> {
-        self.entities.get_all_entities().cloned().collect()
+        Ok(())
     }
 
-    /// is the match over? True if there is 0-1 players left
+    /// is the match over? True if there is 0-1 players left (not counting anyone who escaped)
+    /// (or the ruleset says its over for some other reason)
     pub fn match_over(&self) -> bool {
-        let player_count = self
+        let ruleset = &self.config.ruleset;
+        if ruleset
+            .max_ticks
+            .is_some_and(|max_ticks| self.tick_id >= max_ticks)
+        {
+            return true;
+        }
+
+        let players = self
             .entities
             .get_all_entities()
-            .filter(|e| has_markers!(e, Player))
-            .count();
-        player_count <= 1
+            .filter(|e| has_markers!(e, Player) && !has_markers!(e, Escaped))
+            .collect_vec();
+        if ruleset.last_awake_wins {
+            let awake_count = players
+                .iter()
+                .filter(|e| !matches!(e.attributes.focus, Some(ActorFocus::Sleeping { .. })))
+                .count();
+            if awake_count <= 1 {
+                return true;
+            }
+        }
+        players.len() <= 1
     }
 
-    fn maybe_next_world_state(&mut self, entity_view: &EntityView, ctx: &ServerCtx) -> EntityWorld {
-        let mut rng = rand::rng();
-        let mut world_entity = entity_view
-            .all()
-            .find(|e| e.attributes.world.is_some())
-            .expect("Expected world entity to exist")
-            .clone();
+    fn maybe_next_world_state(&mut self, ctx: &ServerCtx) -> EntityWorld {
+        let mut rng = crate::rng::rng();
+        let (mut world_entity, mut changed) = match self.entities.world() {
+            Some(world_entity) => (world_entity.clone(), false),
+            None => {
+                warn!("No world entity, starting the world over");
+                (generate_world(), true)
+            }
+        };
 
+        let world = world_entity.attributes.world.as_mut().unwrap();
+        if world.advance_clock(self.tick_id, &ctx.log_tx) {
+            changed = true;
+        }
         if rng.random_bool(0.005) {
-            world_entity
-                .attributes
-                .world
-                .as_mut()
-                .unwrap()
-                .update(&ctx.log_tx, &mut rng);
+            world.update_weather(&ctx.log_tx, &mut rng);
+            changed = true;
+        }
+        if world.update_water_level(&mut rng) {
+            changed = true;
+        }
+
+        // Keep track of the audience
+        // (rounded, so this isn't a change every tick)
+        let engagement = self.audience.update(ctx.tick_tx.receiver_count());
+        let engagement = (engagement * 20.0).round() / 20.0;
+        if engagement != world.audience_engagement {
+            world.audience_engagement = engagement;
+            changed = true;
+        }
+
+        if changed {
             self.entities.upsert_entity(world_entity.clone()).unwrap();
         }
 
@@ -220,6 +415,8 @@ impl MatchManager {
 ///  - StartOfTick
 ///  - (Processing happens on server)
 ///  - EntityChanges
+///  - RequestApplied (for each request settled this tick)
+///  - Telemetry (every so often)
 ///  - EndOfTick
 #[derive(Debug, Clone, Serialize)]
 #[qubit::ts]
@@ -229,15 +426,51 @@ pub enum TickEvent {
     StartOfTick { tick_id: TickId },
 
     /// A new tick has ended
-    EndOfTick { tick_id: TickId },
+    /// (with how fast the match is being played, see `TickPace::speed`, and how much work it was)
+    EndOfTick {
+        tick_id: TickId,
+        speed: f32,
+        summary: TickSummary,
+    },
 
-    /// A new match just started
+    /// A new match just started, with which version of the protocol the server speaks
     /// (note: does not fire if resumed, only when completely new)
-    StartOfMatch,
+    StartOfMatch { protocol_version: u32 },
 
     /// The match ended
     EndOfMatch,
 
     /// Set of changes to entities during the last tick
     EntityChanges { changes: Vec<EntityManagerMutation> },
+
+    /// The presenter is running a segment of the show
+    Announcement { segment: SegmentKind, quote: String },
+
+    /// How the match is going overall (see `MatchTelemetry`)
+    Telemetry { telemetry: MatchTelemetry },
+
+    /// An entity broke something while acting and was taken out of play
+    EntityQuarantined { entity_id: EntityId, reason: String },
+
+    /// A request from a viewer (i.e a vote) has taken effect, and its changes have been sent
+    /// (see `requests`)
+    RequestApplied {
+        request_id: RequestId,
+        tick_id: TickId,
+    },
+
+    /// A resumed stream has replayed everything up to the end of this tick
+    /// (see `resume_stream`, everything after this is live)
+    CaughtUp { tick_id: TickId },
+
+    /// The first event on every stream, with which version of the protocol the server speaks
+    /// (see `protocol`)
+    Heartbeat { protocol_version: u32 },
+
+    /// The client is too old for this server and won't be sent anything else
+    /// (see `protocol`)
+    ProtocolUnsupported {
+        protocol_version: u32,
+        min_supported_version: u32,
+    },
 }
@@ -0,0 +1,237 @@
+//! Keeping things we'd rather not show out of public feeds
+//!
+//! Admins can tag entities as hidden (left out of everything public) or redacted (their name is
+//! blocked and they get a new one on the next tick, e.g for an offensive generated name). Logs are
+//! moderated by what they contain, so any log involving a hidden entity or mentioning a blocked
+//! phrase (incl. the names of redacted entities) is dropped.
+//!
+//! NOTE: moderation only lives in memory, so it has to be redone if the server restarts
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{generate::regenerate_player_name, manager::EntityManagerMutation, Entity, EntityId},
+    has_markers,
+    logs::GameLog,
+};
+
+use super::{naming::NameVote, TickEvent};
+
+/// What entities with a blocked name are shown as until they're renamed
+const REDACTED_NAME: &str = "[redacted]";
+
+/// How an admin has tagged some entity
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationTag {
+    /// Leave it out of public feeds entirely
+    Hidden,
+
+    /// Block its name and give it a new one
+    Redacted,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Moderation {
+    tags: HashMap<EntityId, ModerationTag>,
+
+    /// Redacted entities that still need a new name
+    pending_renames: HashSet<EntityId>,
+
+    /// Anything mentioning these is hidden (always lowercase)
+    blocked_phrases: Vec<String>,
+}
+
+impl Moderation {
+    /// Tag some entity, replacing any tag it already had
+    pub fn tag_entity(&mut self, entity: &Entity, tag: ModerationTag) {
+        if tag == ModerationTag::Redacted {
+            self.block_phrase(&entity.name).ok();
+            self.pending_renames.insert(entity.entity_id.clone());
+        }
+        self.tags.insert(entity.entity_id.clone(), tag);
+    }
+
+    /// Remove the tag from some entity
+    /// (a blocked name stays blocked)
+    pub fn untag_entity(&mut self, entity_id: &EntityId) -> anyhow::Result<()> {
+        if self.tags.remove(entity_id).is_none() {
+            bail!("{entity_id} isn't tagged");
+        }
+        self.pending_renames.remove(entity_id);
+        Ok(())
+    }
+
+    /// Hide anything mentioning some phrase
+    pub fn block_phrase(&mut self, phrase: &str) -> anyhow::Result<()> {
+        let phrase = phrase.trim().to_lowercase();
+        if phrase.is_empty() {
+            bail!("Can't block an empty phrase");
+        }
+        if !self.blocked_phrases.contains(&phrase) {
+            self.blocked_phrases.push(phrase);
+        }
+        Ok(())
+    }
+
+    /// Redacted entities that need a new name, which are then considered renamed
+    pub fn take_pending_renames(&mut self) -> Vec<EntityId> {
+        self.pending_renames.drain().collect()
+    }
+
+    pub fn is_hidden(&self, entity_id: &EntityId) -> bool {
+        self.tags.get(entity_id) == Some(&ModerationTag::Hidden)
+    }
+
+    fn is_blocked(&self, text: &str) -> bool {
+        let text = text.to_lowercase();
+        self.blocked_phrases
+            .iter()
+            .any(|phrase| text.contains(phrase))
+    }
+
+    /// The name of some entity as it can be shown publicly (if at all)
+    pub fn filter_name(&self, entity_id: &EntityId, name: String) -> Option<String> {
+        if self.is_hidden(entity_id) {
+            return None;
+        }
+        if self.is_blocked(&name) {
+            return Some(REDACTED_NAME.to_owned());
+        }
+        Some(name)
+    }
+
+    /// An entity as it can be shown publicly (if at all)
+    pub fn filter_entity(&self, mut entity: Entity) -> Option<Entity> {
+        entity.name = self.filter_name(&entity.entity_id, entity.name)?;
+        entity.attributes.nickname = entity
+            .attributes
+            .nickname
+            .filter(|nickname| !self.is_blocked(nickname));
+        Some(entity)
+    }
+
+    /// Every entity that can be shown publicly
+    pub fn filter_entities(&self, entities: impl IntoIterator<Item = Entity>) -> Vec<Entity> {
+        entities
+            .into_iter()
+            .filter_map(|entity| self.filter_entity(entity))
+            .collect()
+    }
+
+    /// A log, if it can be shown publicly
+    pub fn filter_log(&self, log: GameLog) -> Option<GameLog> {
+        if log.involved_entities.iter().any(|id| self.is_hidden(id)) {
+            return None;
+        }
+        let body = serde_json::to_string(&log.body).unwrap_or_default();
+        if self.is_blocked(&body) {
+            return None;
+        }
+        Some(log)
+    }
+
+    /// A tick event as it can be shown publicly (if at all)
+    pub fn filter_tick_event(&self, event: TickEvent) -> Option<TickEvent> {
+        match event {
+            TickEvent::EntityChanges { changes } => Some(TickEvent::EntityChanges {
+                changes: changes
+                    .into_iter()
+                    .filter_map(|change| match change {
+                        EntityManagerMutation::SetEntity { entity } => self
+                            .filter_entity(entity)
+                            .map(|entity| EntityManagerMutation::SetEntity { entity }),
+                        EntityManagerMutation::RemoveEntity { entity_id } => (!self
+                            .is_hidden(&entity_id))
+                        .then_some(EntityManagerMutation::RemoveEntity { entity_id }),
+                    })
+                    .collect(),
+            }),
+            TickEvent::Announcement { ref quote, .. } if self.is_blocked(quote) => None,
+            TickEvent::EntityQuarantined { ref entity_id, .. } if self.is_hidden(entity_id) => None,
+            event => Some(event),
+        }
+    }
+
+    /// A name vote as it can be shown publicly (if at all)
+    pub fn filter_name_vote(&self, mut vote: NameVote) -> Option<NameVote> {
+        if self.is_hidden(&vote.entity_id) {
+            return None;
+        }
+        if self.is_blocked(&vote.current_name) {
+            vote.current_name = REDACTED_NAME.to_owned();
+        }
+        vote.suggestions
+            .retain(|suggestion| !self.is_blocked(&suggestion.name));
+        Some(vote)
+    }
+}
+
+/// Come up with a new name for some entity
+/// (players get a new generated name, anything else is just called what it is)
+pub fn regenerate_name(entity: &mut Entity) -> anyhow::Result<()> {
+    if has_markers!(entity, Player) {
+        return regenerate_player_name(entity);
+    }
+
+    let attributes = &entity.attributes;
+    let name = if let Some(location) = &attributes.location {
+        format!("{:?}", location.location_kind)
+    } else if attributes.shelter.is_some() {
+        "Shelter".to_owned()
+    } else if attributes.water_source.is_some() {
+        "Water source".to_owned()
+    } else if attributes.container.is_some() {
+        "Container".to_owned()
+    } else {
+        "Something".to_owned()
+    };
+    entity.name = name;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{hex::AxialHex, logs::GameLogBody, mtch::test_match::test_player};
+
+    use super::*;
+
+    #[test]
+    fn test_moderation_filters_public_feeds() {
+        let mut moderation = Moderation::default();
+        let hidden = test_player("Hidden", AxialHex::ZERO);
+        let redacted = test_player("Rude Name", AxialHex::ZERO);
+        let other = test_player("Other", AxialHex::ZERO);
+        moderation.tag_entity(&hidden, ModerationTag::Hidden);
+        moderation.tag_entity(&redacted, ModerationTag::Redacted);
+
+        let entities =
+            moderation.filter_entities([hidden.clone(), redacted.clone(), other.clone()]);
+        assert_eq!(entities.len(), 2);
+        assert_eq!(entities[0].name, REDACTED_NAME);
+        assert_eq!(entities[1].name, "Other");
+
+        let say = |quote: &str| GameLogBody::EntitySayExact {
+            quote: quote.to_owned(),
+        };
+        assert!(moderation
+            .filter_log(GameLog::entity(&hidden, say("Hi")))
+            .is_none());
+        assert!(moderation
+            .filter_log(GameLog::entity(&other, say("Hi rude name")))
+            .is_none());
+        assert!(moderation
+            .filter_log(GameLog::entity(&other, say("Hi")))
+            .is_some());
+
+        assert_eq!(
+            moderation.take_pending_renames(),
+            vec![redacted.entity_id.clone()]
+        );
+        assert!(moderation.take_pending_renames().is_empty());
+    }
+}
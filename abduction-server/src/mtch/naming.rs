@@ -0,0 +1,217 @@
+//! Viewers get to name landmarks and notable props
+//!
+//! Anyone can suggest a name for something, which opens a vote on it for a while (if there isn't one already).
+//! Everyone can then vote on the suggestions and once the vote closes the winning name is applied and
+//! the presenter announces it. This only ever changes names, so it can't affect how the match plays out.
+//!
+//! NOTE: there is no way to tell viewers apart, so nothing stops someone voting many times
+
+use std::collections::HashMap;
+
+use anyhow::bail;
+use serde::Serialize;
+
+use crate::{
+    entity::{Entity, EntityId},
+    has_markers,
+};
+
+/// How many ticks a vote stays open after the first suggestion
+const NAME_VOTE_TICKS: usize = 60;
+
+/// Most suggestions a single vote can have
+const MAX_SUGGESTIONS_PER_VOTE: usize = 5;
+
+/// Longest name that can be suggested (in characters)
+const MAX_NAME_LENGTH: usize = 32;
+
+/// An open vote on what to call some entity
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct NameVote {
+    pub entity_id: EntityId,
+
+    /// What the entity is called right now
+    pub current_name: String,
+
+    /// Suggested names, in the order they were suggested
+    pub suggestions: Vec<NameSuggestion>,
+
+    /// How many more ticks until the vote closes
+    pub remaining_ticks: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct NameSuggestion {
+    pub name: String,
+    pub votes: usize,
+}
+
+/// A vote that has closed, and the name that won it
+#[derive(Debug, Clone)]
+pub struct NameVoteResult {
+    pub entity_id: EntityId,
+    pub old_name: String,
+    pub name: String,
+}
+
+impl NameVote {
+    /// The suggestion with the most votes (earlier suggestions win ties)
+    fn winner(&self) -> Option<&NameSuggestion> {
+        self.suggestions
+            .iter()
+            .rev()
+            .max_by_key(|suggestion| suggestion.votes)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct NameVotes {
+    votes: HashMap<EntityId, NameVote>,
+}
+
+/// Can viewers name this entity?
+/// Only landmarks and notable props, never players or the crew
+pub fn is_nameable(entity: &Entity) -> bool {
+    let attributes = &entity.attributes;
+    !has_markers!(entity, Player)
+        && !has_markers!(entity, Crew)
+        && (attributes.location.is_some()
+            || attributes.shelter.is_some()
+            || attributes.water_source.is_some()
+            || attributes.container.is_some())
+}
+
+/// Tidy up a suggested name, making sure its something we're happy to show
+fn clean_name(name: &str) -> anyhow::Result<String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    if name.is_empty() {
+        bail!("Name is empty");
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        bail!("Name is longer than {MAX_NAME_LENGTH} characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '\'' | '-'))
+    {
+        bail!("Name has characters other than letters, numbers, spaces, apostrophes and hyphens");
+    }
+    Ok(name)
+}
+
+impl NameVotes {
+    /// All the votes that are currently open
+    pub fn open_votes(&self) -> Vec<NameVote> {
+        self.votes.values().cloned().collect()
+    }
+
+    /// Suggest a name for some entity, opening a vote on it if there isn't one already
+    /// (suggesting a name also counts as voting for it)
+    pub fn suggest(&mut self, entity: &Entity, name: &str) -> anyhow::Result<()> {
+        if !is_nameable(entity) {
+            bail!("{} can't be named", entity.entity_id);
+        }
+        let name = clean_name(name)?;
+
+        let vote = self
+            .votes
+            .entry(entity.entity_id.clone())
+            .or_insert_with(|| NameVote {
+                entity_id: entity.entity_id.clone(),
+                current_name: entity.name.clone(),
+                suggestions: Vec::new(),
+                remaining_ticks: NAME_VOTE_TICKS,
+            });
+
+        // Someone beat us to it, thats just a vote then
+        if let Some(suggestion) = vote
+            .suggestions
+            .iter_mut()
+            .find(|s| s.name.eq_ignore_ascii_case(&name))
+        {
+            suggestion.votes += 1;
+            return Ok(());
+        }
+
+        if vote.suggestions.len() >= MAX_SUGGESTIONS_PER_VOTE {
+            bail!("{} already has enough suggestions", entity.entity_id);
+        }
+        vote.suggestions.push(NameSuggestion { name, votes: 1 });
+        Ok(())
+    }
+
+    /// Vote for a name that has already been suggested for some entity
+    pub fn vote(&mut self, entity_id: &EntityId, name: &str) -> anyhow::Result<()> {
+        let Some(vote) = self.votes.get_mut(entity_id) else {
+            bail!("There is no open vote on {entity_id}");
+        };
+        let Some(suggestion) = vote
+            .suggestions
+            .iter_mut()
+            .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+        else {
+            bail!("{name:?} hasn't been suggested for {entity_id}");
+        };
+        suggestion.votes += 1;
+        Ok(())
+    }
+
+    /// Count down the open votes, returning the results of any that closed
+    pub fn tick(&mut self) -> Vec<NameVoteResult> {
+        let mut results = Vec::new();
+        self.votes.retain(|_, vote| {
+            vote.remaining_ticks = vote.remaining_ticks.saturating_sub(1);
+            if vote.remaining_ticks > 0 {
+                return true;
+            }
+
+            if let Some(winner) = vote.winner() {
+                results.push(NameVoteResult {
+                    entity_id: vote.entity_id.clone(),
+                    old_name: vote.current_name.clone(),
+                    name: winner.name.clone(),
+                });
+            }
+            false
+        });
+        results
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::entity::{EntityAttributes, EntityShelter};
+
+    use super::*;
+
+    #[test]
+    fn test_most_votes_wins() {
+        let shelter = Entity {
+            entity_id: Entity::id(),
+            name: "Cave".into(),
+            attributes: EntityAttributes {
+                shelter: Some(EntityShelter::default()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut votes = NameVotes::default();
+        votes.suggest(&shelter, "The  Pit").unwrap();
+        votes.suggest(&shelter, "Cosy Corner").unwrap();
+        votes.vote(&shelter.entity_id, "cosy corner").unwrap();
+        assert!(votes.suggest(&shelter, "<script>").is_err());
+        assert!(votes.vote(&shelter.entity_id, "Nowhere").is_err());
+
+        for _ in 1..NAME_VOTE_TICKS {
+            assert!(votes.tick().is_empty());
+        }
+        let results = votes.tick();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].old_name, "Cave");
+        assert_eq!(results[0].name, "Cosy Corner");
+        assert!(votes.open_votes().is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+//! How quickly a match is played
+//!
+//! Ticks are normally `TICK_DELAY` apart, but there's no point playing a match at full speed
+//! overnight when nobody is watching. Once nobody has been subscribed to tick events for a little
+//! while, ticks stretch out to the idle delay (`IDLE_TICK_DELAY_MS`, 5s if not set), and go back to
+//! full speed as soon as someone tunes back in. How fast things are going is sent out with every
+//! `EndOfTick` so clients can show it.
+
+use std::time::Duration;
+
+use tokio::{sync::broadcast, time::Instant};
+use tracing::{info, warn};
+
+use super::TickEvent;
+
+/// How long between ticks when someone is watching
+pub const TICK_DELAY: Duration = Duration::from_millis(500);
+
+/// How long between ticks when nobody is watching, unless `IDLE_TICK_DELAY_MS` says otherwise
+const DEFAULT_IDLE_TICK_DELAY: Duration = Duration::from_secs(5);
+
+/// How many ticks nobody has to be watching for before we slow down
+/// (so a refresh doesn't slow the match down)
+const IDLE_AFTER_TICKS: usize = 20;
+
+/// How many of the tick event listeners are the server's own (i.e tracing them), not viewers
+const INTERNAL_LISTENERS: usize = 1;
+
+#[derive(Debug, Clone)]
+pub struct TickPace {
+    /// How long between ticks when nobody is watching
+    idle_delay: Duration,
+
+    /// How many ticks in a row nobody has been watching for
+    unwatched_ticks: usize,
+}
+
+impl Default for TickPace {
+    fn default() -> Self {
+        Self {
+            idle_delay: DEFAULT_IDLE_TICK_DELAY,
+            unwatched_ticks: 0,
+        }
+    }
+}
+
+impl TickPace {
+    /// The pace with the idle delay from `IDLE_TICK_DELAY_MS` (if set)
+    pub fn from_env() -> Self {
+        let Ok(idle_delay) = std::env::var("IDLE_TICK_DELAY_MS") else {
+            return Self::default();
+        };
+        match idle_delay.parse() {
+            // (it's never quicker than when someone is watching)
+            Ok(ms) => Self {
+                idle_delay: Duration::from_millis(ms).max(TICK_DELAY),
+                ..Default::default()
+            },
+            Err(_) => {
+                warn!("Invalid IDLE_TICK_DELAY_MS {idle_delay}, using the default");
+                Self::default()
+            }
+        }
+    }
+
+    /// Update the pace given how many are listening to tick events after a tick
+    pub fn update(&mut self, listeners: usize) {
+        let was_idle = self.is_idle();
+        if viewers(listeners) == 0 {
+            self.unwatched_ticks += 1;
+        } else {
+            self.unwatched_ticks = 0;
+        }
+
+        if self.is_idle() != was_idle {
+            info!("Playing at {}x speed", self.speed());
+        }
+    }
+
+    /// Has nobody been watching for long enough to slow down?
+    pub fn is_idle(&self) -> bool {
+        self.unwatched_ticks >= IDLE_AFTER_TICKS
+    }
+
+    /// How long until the next tick
+    pub fn delay(&self) -> Duration {
+        if self.is_idle() {
+            self.idle_delay
+        } else {
+            TICK_DELAY
+        }
+    }
+
+    /// How fast the match is being played compared to when someone is watching
+    /// (1 -> full speed, 0.1 -> a tenth of the speed)
+    pub fn speed(&self) -> f32 {
+        TICK_DELAY.as_secs_f32() / self.delay().as_secs_f32()
+    }
+
+    /// Wait until the next tick
+    /// (cut short if someone starts watching while we're idle)
+    pub async fn wait(&self, tick_tx: &broadcast::Sender<TickEvent>) {
+        let until = Instant::now() + self.delay();
+        while Instant::now() < until {
+            tokio::time::sleep(TICK_DELAY.min(until.saturating_duration_since(Instant::now())))
+                .await;
+            if viewers(tick_tx.receiver_count()) > 0 {
+                break;
+            }
+        }
+    }
+}
+
+fn viewers(listeners: usize) -> usize {
+    listeners.saturating_sub(INTERNAL_LISTENERS)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slows_down_when_unwatched() {
+        let mut pace = TickPace::default();
+        pace.update(INTERNAL_LISTENERS + 1);
+        assert_eq!(pace.delay(), TICK_DELAY);
+
+        for _ in 0..IDLE_AFTER_TICKS {
+            pace.update(INTERNAL_LISTENERS);
+        }
+        assert_eq!(pace.delay(), DEFAULT_IDLE_TICK_DELAY);
+        assert_eq!(pace.speed(), 0.1);
+
+        // Back to full speed as soon as someone is watching
+        pace.update(INTERNAL_LISTENERS + 1);
+        assert_eq!(pace.speed(), 1.0);
+    }
+}
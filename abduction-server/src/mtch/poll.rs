@@ -0,0 +1,145 @@
+//! Viewers get to vote on shaking things up
+//!
+//! A poll offers a few interventions (a storm, a supply drop etc). It stays open for a while, then
+//! whichever option has the most votes happens and the presenter announces it. There is only ever
+//! one poll open at a time.
+//!
+//! NOTE: like name votes, there is no way to tell viewers apart, so nothing stops someone voting many times
+
+use anyhow::bail;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+/// How many ticks a poll stays open for
+const POLL_TICKS: usize = 60;
+
+/// Something the viewers can vote to make happen
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum PollOption {
+    /// Whip up a lightning storm
+    Storm,
+
+    /// Drop a crate of supplies somewhere
+    SupplyDrop,
+
+    /// Let a predator loose
+    ReleasePredator,
+}
+
+/// The open poll
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct Poll {
+    /// Each option and how many votes it has, in the order they were given
+    pub options: Vec<PollTally>,
+
+    /// How many more ticks until the poll closes
+    pub remaining_ticks: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct PollTally {
+    pub option: PollOption,
+    pub votes: usize,
+}
+
+impl Poll {
+    /// The option with the most votes (earlier options win ties)
+    fn winner(&self) -> Option<PollOption> {
+        self.options
+            .iter()
+            .rev()
+            .max_by_key(|tally| tally.votes)
+            .map(|tally| tally.option)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ViewerPoll {
+    current: Option<Poll>,
+}
+
+impl ViewerPoll {
+    /// The poll that is currently open (if there is one)
+    pub fn current(&self) -> Option<Poll> {
+        self.current.clone()
+    }
+
+    /// Open a poll on some options
+    pub fn start(&mut self, options: Vec<PollOption>) -> anyhow::Result<()> {
+        if self.current.is_some() {
+            bail!("There is already a poll open");
+        }
+        if options.len() < 2 {
+            bail!("A poll needs at least two options");
+        }
+        if !options.iter().all_unique() {
+            bail!("A poll can't have the same option twice");
+        }
+
+        self.current = Some(Poll {
+            options: options
+                .into_iter()
+                .map(|option| PollTally { option, votes: 0 })
+                .collect(),
+            remaining_ticks: POLL_TICKS,
+        });
+        Ok(())
+    }
+
+    /// Vote for one of the options in the open poll
+    pub fn vote(&mut self, option: PollOption) -> anyhow::Result<()> {
+        let Some(poll) = &mut self.current else {
+            bail!("There is no poll open");
+        };
+        let Some(tally) = poll.options.iter_mut().find(|t| t.option == option) else {
+            bail!("{option:?} isn't an option in this poll");
+        };
+        tally.votes += 1;
+        Ok(())
+    }
+
+    /// Count down the open poll, returning the winning option if it closed
+    /// (if nobody voted, nothing wins)
+    pub fn tick(&mut self) -> Option<PollOption> {
+        let poll = self.current.as_mut()?;
+        poll.remaining_ticks = poll.remaining_ticks.saturating_sub(1);
+        if poll.remaining_ticks > 0 {
+            return None;
+        }
+
+        let poll = self.current.take()?;
+        if poll.options.iter().all(|tally| tally.votes == 0) {
+            return None;
+        }
+        poll.winner()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_poll_closes_with_winner() {
+        let mut poll = ViewerPoll::default();
+        assert!(poll.start(vec![PollOption::Storm]).is_err());
+        poll.start(vec![PollOption::Storm, PollOption::SupplyDrop])
+            .unwrap();
+        assert!(poll
+            .start(vec![PollOption::Storm, PollOption::SupplyDrop])
+            .is_err());
+
+        poll.vote(PollOption::SupplyDrop).unwrap();
+        assert!(poll.vote(PollOption::ReleasePredator).is_err());
+
+        for _ in 1..POLL_TICKS {
+            assert!(poll.tick().is_none());
+        }
+        assert_eq!(poll.tick(), Some(PollOption::SupplyDrop));
+        assert!(poll.current().is_none());
+    }
+}
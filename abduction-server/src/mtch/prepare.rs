@@ -0,0 +1,177 @@
+//! Preparing a match ahead of time, from the command line
+//!
+//! i.e `abduction-server prepare-match --players 100 --radius 14 --start "2025-01-06T09:00Z"`
+//!
+//! This is the weekend seeding from the plan up in `mtch`: a new season is started with a big
+//! match, and both its players and its world are generated and saved straight away (rather than
+//! the world being generated when it starts). It's then left for the server to pick up as the
+//! upcoming match, which it waits to start until its scheduled start (if it was given one).
+//! The server shouldn't be running while a match is being prepared.
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use itertools::Itertools;
+use tracing::info;
+
+use crate::{has_markers, Db};
+
+use super::{
+    features::{alternate_experiments, FeatureFlag},
+    season_config::SeasonConfig,
+    MatchConfig, MatchManager,
+};
+
+/// How a match should be prepared
+#[derive(Debug, PartialEq)]
+pub struct PrepareMatchArgs {
+    pub players: usize,
+
+    /// How far the world extends (if not given, it's sized to fit the players)
+    pub radius: Option<usize>,
+
+    /// When the match should start (if not given, it starts as soon as the server is up)
+    pub start: Option<DateTime<Utc>>,
+}
+
+impl PrepareMatchArgs {
+    /// Parse the arguments that came after `prepare-match`
+    pub fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut players = None;
+        let mut radius = None;
+        let mut start = None;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let value = args.next().ok_or(anyhow!("Expected a value after {arg}"))?;
+            match arg.as_str() {
+                "--players" => players = Some(value.parse().context("Invalid player count")?),
+                "--radius" => radius = Some(value.parse().context("Invalid world radius")?),
+                "--start" => start = Some(parse_start(&value)?),
+                _ => bail!("Unknown argument {arg}"),
+            }
+        }
+
+        Ok(Self {
+            players: players.ok_or(anyhow!("Expected `--players <count>`"))?,
+            radius,
+            start,
+        })
+    }
+}
+
+/// Parse a start time, with or without seconds (i.e `2025-01-06T09:00Z`)
+fn parse_start(value: &str) -> anyhow::Result<DateTime<Utc>> {
+    if let Ok(start) = DateTime::from_str(value) {
+        return Ok(start);
+    }
+    NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y-%m-%dT%H:%M")
+        .map(|start| start.and_utc())
+        .with_context(|| format!("Invalid start time {value}"))
+}
+
+/// Create a match (and a season for it) and generate everything for it,
+/// printing who is going to be playing
+pub async fn prepare_match(args: PrepareMatchArgs, db: &Db) -> anyhow::Result<MatchConfig> {
+    if let Some(existing) = MatchConfig::get_incomplete(db).await? {
+        bail!(
+            "Match {} hasn't finished yet, so there's no need to prepare another",
+            existing.match_id
+        );
+    }
+
+    // It's a new season, with a new match to start it off
+    // (any experiments are switched on if the last match didn't have them, see `FeatureFlag`)
+    let last_match = MatchConfig::get_latest(db).await?;
+    let features = alternate_experiments(
+        last_match.as_ref().map(|config| &*config.features),
+        &FeatureFlag::experiments(),
+    );
+    let config = match args.radius {
+        Some(radius) => MatchConfig::isolated(args.players, radius),
+        None => MatchConfig::sized_for(args.players),
+    }
+    .with_features(features);
+    config.save(db).await?;
+    let season = SeasonConfig::seed(args.players, db).await?;
+    season.add_match(&config.match_id, db).await?;
+    if let Some(start) = args.start {
+        config.schedule_start(start, db).await?;
+    }
+
+    // Generate its players, then its world
+    let mut manager = MatchManager::load_match(config.clone(), db).await;
+    manager
+        .prepare_new_match(db)
+        .await
+        .context("Preparing players")?;
+    manager
+        .initialise_new_match(db)
+        .await
+        .context("Generating world")?;
+    manager.entities.save_changes(manager.tick_id, db).await?;
+    info!("Prepared match {}", config.match_id);
+
+    // Show who's playing
+    let roster = manager
+        .entities
+        .get_all_entities()
+        .filter(|e| has_markers!(e, Player))
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect_vec();
+    println!(
+        "Match {} (season {}) with {} players in a world of radius {}",
+        config.match_id,
+        season.number,
+        roster.len(),
+        config.world_radius
+    );
+    match args.start {
+        Some(start) => println!("Starting at {start}"),
+        None => println!("Starting as soon as the server is up"),
+    }
+    for player in roster {
+        match &player.attributes.background {
+            Some(background) => println!(
+                " - {}, {} from {}",
+                player.name,
+                background.career,
+                background.location_string()
+            ),
+            None => println!(" - {}", player.name),
+        }
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn test_prepare_match_args() {
+        let parsed =
+            PrepareMatchArgs::parse(args("--players 100 --radius 14 --start 2025-01-06T09:00Z"))
+                .unwrap();
+        assert_eq!(parsed.players, 100);
+        assert_eq!(parsed.radius, Some(14));
+        assert_eq!(
+            parsed.start.map(|start| start.to_rfc3339()),
+            Some("2025-01-06T09:00:00+00:00".to_owned())
+        );
+
+        let parsed = PrepareMatchArgs::parse(args("--players 20")).unwrap();
+        assert_eq!(parsed.radius, None);
+        assert_eq!(parsed.start, None);
+
+        assert!(PrepareMatchArgs::parse(args("--radius 14")).is_err());
+        assert!(PrepareMatchArgs::parse(args("--players")).is_err());
+        assert!(PrepareMatchArgs::parse(args("--players 20 --colour red")).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! A small picture of the whole map, for showing a match (e.g on a card) before loading any of it
+//!
+//! The map is only ever laid out when the match is initialised (see `initialise_new_match`), so the
+//! preview is worked out then (or when a match is loaded) and kept on the `MatchManager`, rather than
+//! being put together from the location entities on every request.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{entity::Entity, hex::AxialHex, location::LocationKind};
+
+/// What's where on the map, roughly
+#[derive(Debug, Clone, Default, Serialize)]
+#[qubit::ts]
+pub struct WorldPreview {
+    pub world_radius: usize,
+
+    /// Each row of hexes (top to bottom, i.e by r), as runs of the same kind of location
+    /// (left to right, i.e by q)
+    /// NOTE: the first hex in a row is at q = max(-radius, -r - radius)
+    pub rows: Vec<Vec<PreviewRun>>,
+}
+
+/// Some hexes in a row that all look the same
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[qubit::ts]
+pub struct PreviewRun {
+    /// (none -> there's nothing there)
+    pub location_kind: Option<LocationKind>,
+
+    /// What hue to show them in (see `display_color_hue`)
+    pub hue: Option<f32>,
+
+    pub length: usize,
+}
+
+impl WorldPreview {
+    /// Work out what the map looks like from its locations
+    pub fn of<'a>(entities: impl Iterator<Item = &'a Entity>, world_radius: usize) -> Self {
+        let locations: HashMap<_, _> = entities
+            .filter_map(|entity| {
+                let location = entity.attributes.location.as_ref()?;
+                Some((
+                    entity.attributes.hex?,
+                    (location.location_kind, entity.attributes.display_color_hue),
+                ))
+            })
+            .collect();
+
+        let radius = world_radius as isize;
+        let rows = (-radius..=radius)
+            .map(|r| {
+                let mut runs: Vec<PreviewRun> = Vec::new();
+                for q in (-radius).max(-r - radius)..=radius.min(-r + radius) {
+                    let (location_kind, hue) = locations
+                        .get(&AxialHex::from((q, r)))
+                        .map_or((None, None), |&(kind, hue)| (Some(kind), hue));
+                    match runs.last_mut() {
+                        Some(run) if run.location_kind == location_kind => run.length += 1,
+                        _ => runs.push(PreviewRun {
+                            location_kind,
+                            hue,
+                            length: 1,
+                        }),
+                    }
+                }
+                runs
+            })
+            .collect();
+
+        Self { world_radius, rows }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::location::{generate_locations_for_world, Biome};
+
+    #[test]
+    fn test_preview_covers_the_whole_map() {
+        let locations = generate_locations_for_world(2, Biome::Green);
+        let preview = WorldPreview::of(locations.iter(), 2);
+
+        // Every hex is in there, once
+        assert_eq!(preview.rows.len(), 5);
+        let row_lengths: Vec<usize> = preview
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|run| run.length).sum())
+            .collect();
+        assert_eq!(row_lengths, vec![3, 4, 5, 4, 3]);
+        assert!(preview
+            .rows
+            .iter()
+            .flatten()
+            .all(|run| run.location_kind.is_some() && run.hue.is_some()));
+
+        // And a missing location leaves a gap
+        let centre = locations
+            .iter()
+            .find(|e| e.attributes.hex == Some(AxialHex::ZERO))
+            .unwrap();
+        let preview = WorldPreview::of(
+            locations.iter().filter(|e| e.entity_id != centre.entity_id),
+            2,
+        );
+        assert!(preview.rows[2]
+            .iter()
+            .any(|run| run.location_kind.is_none()));
+    }
+}
@@ -0,0 +1,321 @@
+//! Timing how long each phase of a tick takes
+//!
+//! Each phase of a tick also has a tracing span (with some counters as fields), which is what
+//! you want for a flamegraph. When the server is started with `--profile-ticks` the timings are
+//! also appended to a csv per match, which is easier for spotting when a tick got slower.
+//!
+//! Every tick also ends with a `TickSummary` (sent along with `TickEvent::EndOfTick`), which is a
+//! rough count of how much work the tick was, for dashboards to keep an eye on.
+
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use itertools::Itertools;
+use serde::Serialize;
+use strum::IntoEnumIterator;
+use tokio::{fs, io::AsyncWriteExt};
+
+use super::{MatchId, TickId};
+use crate::entity::brain::actor_action::ActorAction;
+
+/// Where the timing csvs are written to
+const PROFILE_DIR: &str = "data/profiles";
+
+/// The phases of a tick, in the order they happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum TickPhase {
+    /// Closing name votes and polls, moderation renames
+    Votes,
+
+    /// Next world state and global effects
+    World,
+
+    /// Segments and announcements from the presenter
+    Presenter,
+
+    /// Everyone with a role acting
+    Actors,
+
+    /// Saving changes, snapshots, logs and stats
+    Flush,
+
+    /// Updating who is worth watching
+    Drama,
+}
+
+/// How long each phase of a single tick took
+#[derive(Debug)]
+pub struct TickTimings {
+    tick_id: TickId,
+    phase_start: Instant,
+    phases: Vec<(TickPhase, Duration)>,
+}
+
+impl TickTimings {
+    pub fn start(tick_id: TickId) -> Self {
+        Self {
+            tick_id,
+            phase_start: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    /// Record how long some phase took (since the end of the last one)
+    pub fn end_phase(&mut self, phase: TickPhase) {
+        let now = Instant::now();
+        self.phases.push((phase, now - self.phase_start));
+        self.phase_start = now;
+    }
+
+    fn csv_header() -> String {
+        let phases = TickPhase::iter().map(|phase| {
+            let name: &'static str = phase.into();
+            format!("{name}_us")
+        });
+        ["tick_id".to_owned()].into_iter().chain(phases).join(",")
+    }
+
+    /// Timings as a csv row (in microseconds, blank for any phase that didn't happen)
+    fn csv_row(&self) -> String {
+        let phases = TickPhase::iter().map(|phase| {
+            self.phases
+                .iter()
+                .find(|(p, _)| *p == phase)
+                .map(|(_, duration)| duration.as_micros().to_string())
+                .unwrap_or_default()
+        });
+        [self.tick_id.to_string()]
+            .into_iter()
+            .chain(phases)
+            .join(",")
+    }
+
+    /// Append these timings to the csv for some match
+    pub async fn save(&self, match_id: &MatchId) -> anyhow::Result<PathBuf> {
+        fs::create_dir_all(PROFILE_DIR)
+            .await
+            .context("Creating profile dir")?;
+
+        let path = Path::new(PROFILE_DIR).join(format!("{match_id}.csv"));
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await
+            .context("Opening profile csv")?;
+
+        let mut contents = String::new();
+        if file.metadata().await?.len() == 0 {
+            contents.push_str(&Self::csv_header());
+            contents.push('\n');
+        }
+        contents.push_str(&self.csv_row());
+        contents.push('\n');
+        file.write_all(contents.as_bytes())
+            .await
+            .context("Writing profile csv")?;
+
+        Ok(path)
+    }
+}
+
+/// Roughly what some action was about (for counting them up in the `TickSummary`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, strum::EnumIter)]
+#[serde(rename_all = "snake_case")]
+#[qubit::ts]
+pub enum ActionCategory {
+    /// Getting from one place to another
+    Movement,
+
+    /// Eating, drinking, sleeping, shelter and the like
+    Survival,
+
+    /// Picking up, using and stashing things
+    Items,
+
+    /// Spending time with others
+    Social,
+
+    /// Picking fights, or running from them
+    Conflict,
+
+    /// Running the show
+    Presenter,
+
+    /// Not doing much of anything
+    Idle,
+
+    Other,
+}
+
+impl ActorAction {
+    /// What this action is about
+    /// (a plan counts as whatever it tries first)
+    pub fn category(&self) -> ActionCategory {
+        use ActorAction::*;
+        match self {
+            IgnoreResult(action) => action.category(),
+            Plan(plan) => plan
+                .steps()
+                .first()
+                .map(|a| a.category())
+                .unwrap_or(ActionCategory::Idle),
+
+            Move(_)
+            | Drift(_)
+            | GoTowardsHex(_)
+            | GoTowards(..)
+            | GoToAdjacent(..)
+            | MoveAwayFrom(..)
+            | TravelTogether
+            | SeekKnownShelter
+            | SeekKnownWaterSource
+            | FollowTracks { .. }
+            | BoardRaft { .. }
+            | PaddleRaft(_)
+            | DisembarkRaft => ActionCategory::Movement,
+
+            ConsumeFoodEntity(_)
+            | ConsumeNearbyFood { .. }
+            | RetrieveInventoryFood
+            | Sleep
+            | WakeUp
+            | DrinkFromWaterSource { .. }
+            | TakeShelter
+            | LeaveShelter
+            | RepairShelter
+            | ClaimHex
+            | RepairPod { .. }
+            | Fish
+            | StopFishing
+            | LightCampfire { .. }
+            | BuildRaft => ActionCategory::Survival,
+
+            PickUpEntity(_)
+            | RetrieveEntity(_)
+            | DropEntity(_)
+            | UseItemOn { .. }
+            | DragEntity { .. }
+            | SearchContainer(_)
+            | CreateCache
+            | DepositInCache { .. }
+            | WithdrawFromCache { .. }
+            | StripCorpse(_)
+            | LootCorpse { .. }
+            | Throw { .. } => ActionCategory::Items,
+
+            GreetEntity { .. }
+            | ShareFood { .. }
+            | StartTravellingWith { .. }
+            | PartWays
+            | MournEntity { .. }
+            | HoldMemorial { .. }
+            | JoinMemorial { .. }
+            | Discussion(_)
+            | HearOpinion { .. }
+            | StandGuard { .. }
+            | StopGuarding
+            | JoinGathering { .. }
+            | TellStory
+            | LeaveGathering => ActionCategory::Social,
+
+            ConfrontEntity { .. }
+            | AvoidEntity { .. }
+            | StealFood { .. }
+            | FleeFrom { .. }
+            | Stalk { .. }
+            | Maul { .. } => ActionCategory::Conflict,
+
+            Presenter(_) | WarpInEntity(_) | Teleport(_) | WarpOutEntity(_) => {
+                ActionCategory::Presenter
+            }
+
+            Nothing | LookAround | Bark(..) | Idle(_) => ActionCategory::Idle,
+
+            _ => ActionCategory::Other,
+        }
+    }
+}
+
+/// How many actions of some category were taken in a tick
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct ActionCount {
+    pub category: ActionCategory,
+    pub count: usize,
+}
+
+/// How much work a tick was
+#[derive(Debug, Clone, Default, Serialize)]
+#[qubit::ts]
+pub struct TickSummary {
+    /// How many entities got a turn to act
+    pub entities_processed: usize,
+
+    /// How many actions were decided on, by category
+    /// (only categories with any actions)
+    pub actions: Vec<ActionCount>,
+
+    /// How many changes to entities were saved and sent out
+    pub mutations_flushed: usize,
+
+    /// How many game events were raised for next tick
+    pub events_raised: usize,
+
+    /// How long the whole tick took to process
+    pub duration_ms: f32,
+}
+
+impl TickSummary {
+    /// Count one more action of some category
+    pub fn record_action(&mut self, category: ActionCategory) {
+        match self.actions.iter_mut().find(|a| a.category == category) {
+            Some(action_count) => action_count.count += 1,
+            None => self.actions.push(ActionCount { category, count: 1 }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_timings_row_matches_header() {
+        let mut timings = TickTimings::start(3);
+        timings.end_phase(TickPhase::Votes);
+        timings.end_phase(TickPhase::Actors);
+
+        let header = TickTimings::csv_header();
+        let row = timings.csv_row();
+        assert!(header.starts_with("tick_id,votes_us,world_us"));
+        assert_eq!(header.split(',').count(), row.split(',').count());
+        assert!(row.starts_with("3,"));
+        assert_eq!(row.split(',').nth(2), Some(""));
+    }
+
+    #[test]
+    fn test_summary_counts_actions_by_category() {
+        let mut summary = TickSummary::default();
+        summary.record_action(ActorAction::Sleep.category());
+        summary.record_action(ActorAction::LookAround.category());
+        summary.record_action(
+            ActorAction::IgnoreResult(Box::new(ActorAction::RetrieveInventoryFood)).category(),
+        );
+
+        assert_eq!(summary.actions.iter().map(|a| a.count).sum::<usize>(), 3);
+        assert_eq!(summary.actions.len(), 2);
+        assert_eq!(
+            summary
+                .actions
+                .iter()
+                .find(|a| a.category == ActionCategory::Survival)
+                .map(|a| a.count),
+            Some(2)
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Letting viewers know when what they asked for took effect
+//!
+//! Most of what viewers can do to a match (suggesting names, voting in the poll etc) doesn't do
+//! anything straight away, it counts towards something that's settled on some later tick (when the
+//! vote closes). So each request that's accepted is given a `RequestId`, which is held onto until
+//! whatever it went towards is settled. Once the changes from that tick have gone out, a
+//! `TickEvent::RequestApplied` goes out for it, so clients can show that it happened.
+
+use std::collections::HashMap;
+
+use crate::entity::{Entity, EntityId};
+
+/// Identifies a request from a viewer (see `PendingRequests`)
+pub type RequestId = String;
+
+/// Something requests can be waiting on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PendingOn {
+    /// The open viewer poll closing
+    Poll,
+
+    /// The vote on naming some entity closing
+    NameVote(EntityId),
+}
+
+/// Every request still waiting on something to be settled
+#[derive(Debug, Clone, Default)]
+pub struct PendingRequests {
+    pending: HashMap<PendingOn, Vec<RequestId>>,
+}
+
+impl PendingRequests {
+    /// Hold onto a new request until what it's waiting on is settled (returns its id)
+    pub fn add(&mut self, on: PendingOn) -> RequestId {
+        let request_id = Entity::id();
+        self.pending.entry(on).or_default().push(request_id.clone());
+        request_id
+    }
+
+    /// Take every request that was waiting on something that has now been settled
+    pub fn settle(&mut self, on: &PendingOn) -> Vec<RequestId> {
+        self.pending.remove(on).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_requests_are_settled_once() {
+        let mut requests = PendingRequests::default();
+        let vote = requests.add(PendingOn::Poll);
+        let suggestion = requests.add(PendingOn::NameVote("cave".into()));
+        assert_ne!(vote, suggestion);
+
+        assert_eq!(requests.settle(&PendingOn::Poll), vec![vote]);
+        assert!(requests.settle(&PendingOn::Poll).is_empty());
+        assert_eq!(
+            requests.settle(&PendingOn::NameVote("cave".into())),
+            vec![suggestion]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Variants of the usual match, with different rules
+//!
+//! A match is played by a `Ruleset` (stored with its config), which bundles up the tweaks for
+//! each variant. World effects, the tick loop and the brain check it wherever they'd otherwise
+//! do something the variant doesn't allow.
+
+use serde::{Deserialize, Serialize};
+
+use super::TickId;
+
+/// The different kinds of match
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, strum::EnumString)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MatchVariant {
+    /// The usual
+    #[default]
+    Standard,
+
+    /// Everyone gets hungry (etc) much quicker, and the match is over sooner
+    Blitz,
+
+    /// Nothing can hurt anyone, the last one still awake wins
+    Peaceful,
+
+    /// Anyone who dies stays dead, even in later matches
+    Hardcore,
+}
+
+/// The rules a match is played by
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+#[serde(default)]
+pub struct Ruleset {
+    pub variant: MatchVariant,
+
+    /// How much quicker motivators drift on their own (see `BehaviorConfig::homeostasis`)
+    pub motivator_rate: f32,
+
+    /// Can the world hurt players? (hazards, lightning, meteors etc)
+    pub hazards: bool,
+
+    /// Can players hurt each other? (throwing things at people)
+    pub fights: bool,
+
+    /// The match is over once only one player is still awake (and they win)
+    pub last_awake_wins: bool,
+
+    /// The match is over after this many ticks, however many players are left
+    pub max_ticks: Option<TickId>,
+
+    /// Players who die can't be brought back in later matches
+    /// NOTE: only survivors and escapees are carried into the next match so far (see `season`),
+    ///       so the dead stay dead either way
+    pub permadeath: bool,
+
+    /// Players who die leave a spirit behind to drift about the world
+    pub spirits: bool,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::of(MatchVariant::Standard)
+    }
+}
+
+impl Ruleset {
+    /// The rules for some variant
+    pub fn of(variant: MatchVariant) -> Self {
+        let standard = Self {
+            variant,
+            motivator_rate: 1.0,
+            hazards: true,
+            fights: true,
+            last_awake_wins: false,
+            max_ticks: None,
+            permadeath: false,
+            spirits: true,
+        };
+        match variant {
+            MatchVariant::Standard => standard,
+            MatchVariant::Blitz => Self {
+                motivator_rate: 3.0,
+                max_ticks: Some(2000),
+                ..standard
+            },
+            MatchVariant::Peaceful => Self {
+                hazards: false,
+                fights: false,
+                last_awake_wins: true,
+                ..standard
+            },
+            MatchVariant::Hardcore => Self {
+                permadeath: true,
+                ..standard
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::brain::focus::ActorFocus,
+        hex::AxialHex,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[test]
+    fn test_missing_rules_are_standard() {
+        let ruleset: Ruleset = serde_json::from_str("{}").unwrap();
+        assert_eq!(ruleset.variant, MatchVariant::Standard);
+        assert!(ruleset.hazards && ruleset.fights);
+        assert_eq!(ruleset.max_ticks, None);
+    }
+
+    #[tokio::test]
+    async fn test_peaceful_match_won_by_last_awake() {
+        let builder = TestMatch::builder();
+        let awake = test_player("Awake", AxialHex::ZERO);
+        let mut asleep = test_player("Asleep", AxialHex::ZERO);
+        asleep.attributes.focus = Some(ActorFocus::Sleeping {
+            remaining_turns: 50,
+        });
+
+        let peaceful = builder
+            .variant(MatchVariant::Peaceful)
+            .with_entity(awake.clone())
+            .with_entity(asleep.clone())
+            .build()
+            .await;
+        assert!(peaceful.manager.match_over());
+
+        let standard = TestMatch::builder()
+            .with_entity(awake)
+            .with_entity(asleep)
+            .build()
+            .await;
+        assert!(!standard.manager.match_over());
+    }
+}
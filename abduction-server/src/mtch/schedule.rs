@@ -0,0 +1,134 @@
+//! Deciding who gets to act each tick
+//!
+//! Working out what every entity does each tick gets heavy with hundreds of players, and most of
+//! them are just pottering about. So entities with nothing pressing (no high motivators, no events
+//! for them, not in the middle of something) only decide what to do every few ticks, spread out so
+//! roughly the same number decide each tick. Anyone with something going on still acts every tick.
+//!
+//! How many ticks the idle have to wait adapts to how long acting has been taking, so a small
+//! match never waits at all.
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    time::Duration,
+};
+
+use crate::entity::{brain::focus::ActorFocus, Entity, EntityPredator};
+
+use super::TickId;
+
+/// How long we'd like everyone acting to take each tick
+/// (ticks are 500ms apart, and this is the bulk of the work)
+const ACTOR_BUDGET: Duration = Duration::from_millis(200);
+
+/// The longest the idle will wait between decisions
+const MAX_DECISION_INTERVAL: usize = 8;
+
+/// Distress above which something is pressing enough to act on right away
+const URGENT_DISTRESS: f32 = 0.5;
+
+#[derive(Debug, Clone)]
+pub struct ActorScheduler {
+    /// Entities with nothing pressing decide once every this many ticks
+    decision_interval: usize,
+}
+
+impl Default for ActorScheduler {
+    fn default() -> Self {
+        Self {
+            decision_interval: 1,
+        }
+    }
+}
+
+impl ActorScheduler {
+    pub fn decision_interval(&self) -> usize {
+        self.decision_interval
+    }
+
+    /// Whether some entity should decide what to do this tick
+    /// (`has_events` is whether there are any events for them to react to)
+    pub fn should_decide(&self, entity: &Entity, has_events: bool, tick_id: TickId) -> bool {
+        if self.decision_interval <= 1 || has_events || Self::is_urgent(entity) {
+            return true;
+        }
+
+        // Everyone else gets a turn every few ticks, spread out by id
+        let mut hasher = DefaultHasher::new();
+        entity.entity_id.hash(&mut hasher);
+        let shard = hasher.finish() as usize % self.decision_interval;
+        shard == tick_id % self.decision_interval
+    }
+
+    /// Does this entity have something going on they should act on every tick?
+    fn is_urgent(entity: &Entity) -> bool {
+        // The crew are always busy
+        if entity.attributes.presenter.is_some() || entity.attributes.collector.is_some() {
+            return true;
+        }
+
+        // Predators don't let up once they're after someone
+        if entity
+            .attributes
+            .predator
+            .as_ref()
+            .is_some_and(EntityPredator::is_hunting)
+        {
+            return true;
+        }
+
+        // Focuses are worked through a tick at a time (sleeping, talking etc)
+        if !matches!(entity.attributes.focus, None | Some(ActorFocus::Unfocused)) {
+            return true;
+        }
+
+        entity.attributes.motivators.distress() >= URGENT_DISTRESS
+    }
+
+    /// Adjust how often the idle decide, given how long everyone acting took this tick
+    pub fn adapt(&mut self, actors_took: Duration) {
+        if actors_took > ACTOR_BUDGET {
+            self.decision_interval = (self.decision_interval + 1).min(MAX_DECISION_INTERVAL);
+        } else if actors_took < ACTOR_BUDGET / 2 {
+            self.decision_interval = self.decision_interval.saturating_sub(1).max(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{entity::brain::motivator, hex::AxialHex, mtch::test_match::test_player};
+
+    use super::*;
+
+    #[test]
+    fn test_idle_decide_less_often_when_slow() {
+        let mut scheduler = ActorScheduler::default();
+        let idle = test_player("Idle", AxialHex::ZERO);
+        let mut hurt = test_player("Hurt", AxialHex::ZERO);
+        hurt.attributes
+            .motivators
+            .bump_scaled::<motivator::Hurt>(100.0);
+
+        // Everyone decides when there's time for it
+        assert!((0..10).all(|tick_id| scheduler.should_decide(&idle, false, tick_id)));
+
+        // but when it's slow the idle only get every few ticks
+        for _ in 0..3 {
+            scheduler.adapt(ACTOR_BUDGET * 2);
+        }
+        assert_eq!(scheduler.decision_interval(), 4);
+        let turns = (0..40)
+            .filter(|tick_id| scheduler.should_decide(&idle, false, *tick_id))
+            .count();
+        assert_eq!(turns, 10);
+        assert!((0..40).all(|tick_id| scheduler.should_decide(&idle, true, tick_id)));
+        assert!((0..40).all(|tick_id| scheduler.should_decide(&hurt, false, tick_id)));
+
+        // and it speeds back up once there's time again
+        for _ in 0..10 {
+            scheduler.adapt(Duration::ZERO);
+        }
+        assert_eq!(scheduler.decision_interval(), 1);
+    }
+}
@@ -0,0 +1,250 @@
+//! Finding entities by (roughly) what they're called, so viewers can look up "that guy named Henrik"
+//!
+//! Every entity is scored against the query on each of its names (and career, for players), and the
+//! best of those is how well it matches. Matching ignores case and is forgiving: the whole thing,
+//! the start of a word and anywhere in the text all count (in that order), as does being a letter
+//! or two off from a word, and failing all that, having the letters of the query in order.
+
+use serde::Serialize;
+
+use crate::entity::{Entity, EntityId, EntityMarker};
+
+/// The most results a search returns
+pub const SEARCH_RESULT_LIMIT: usize = 20;
+
+/// How much a match on a career counts, compared to a match on a name
+/// (so that searching "baker" finds the Bakers before the bakers)
+const CAREER_WEIGHT: f32 = 0.7;
+
+/// What about an entity matched a search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[qubit::ts]
+pub enum SearchField {
+    Name,
+    FirstName,
+    FamilyName,
+    Career,
+}
+
+/// An entity that matched a search
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct EntitySearchResult {
+    pub entity_id: EntityId,
+    pub name: String,
+
+    /// What matched best
+    pub matched: SearchField,
+
+    /// How well it matched (0-1, higher is better)
+    pub score: f32,
+}
+
+/// Find the entities best matching some query, best first
+/// (only those with every one of `markers`)
+pub fn search_entities<'a>(
+    entities: impl Iterator<Item = &'a Entity>,
+    query: &str,
+    markers: &[EntityMarker],
+) -> Vec<EntitySearchResult> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results: Vec<_> = entities
+        .filter(|entity| markers.iter().all(|marker| entity.markers.contains(marker)))
+        .filter_map(|entity| {
+            let (matched, score) = searchable_fields(entity)
+                .filter_map(|(field, text)| {
+                    let weight = match field {
+                        SearchField::Career => CAREER_WEIGHT,
+                        _ => 1.0,
+                    };
+                    Some((field, weight * match_score(&query, &text)?))
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+            Some(EntitySearchResult {
+                entity_id: entity.entity_id.clone(),
+                name: entity.name.clone(),
+                matched,
+                score,
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    results.truncate(SEARCH_RESULT_LIMIT);
+    results
+}
+
+/// Everything about an entity a search can match
+fn searchable_fields(entity: &Entity) -> impl Iterator<Item = (SearchField, String)> + '_ {
+    let attributes = &entity.attributes;
+    [
+        Some((SearchField::Name, entity.name.clone())),
+        attributes
+            .first_name
+            .clone()
+            .map(|name| (SearchField::FirstName, name)),
+        attributes
+            .family_name
+            .clone()
+            .map(|name| (SearchField::FamilyName, name)),
+        attributes
+            .background
+            .as_ref()
+            .map(|background| (SearchField::Career, background.career.to_string())),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// How well some (lowercase) query matches some text, if at all
+fn match_score(query: &str, text: &str) -> Option<f32> {
+    let text = text.to_lowercase();
+    if text == query {
+        return Some(1.0);
+    }
+    if text.starts_with(query) {
+        return Some(0.9);
+    }
+
+    let words = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty());
+    if words.clone().any(|word| word.starts_with(query)) {
+        return Some(0.8);
+    }
+    if text.contains(query) {
+        return Some(0.6);
+    }
+
+    // A letter or two off (allowing more for longer queries)
+    let allowed_typos = match query.chars().count() {
+        0..4 => 0,
+        4..8 => 1,
+        _ => 2,
+    };
+    if let Some(typos) = words
+        .map(|word| edit_distance(query, word))
+        .filter(|typos| *typos <= allowed_typos)
+        .min()
+    {
+        return Some(0.5 - 0.1 * typos as f32);
+    }
+
+    // Or at least the letters are all there
+    let mut chars = text.chars();
+    query
+        .chars()
+        .all(|c| chars.any(|t| t == c))
+        .then(|| 0.1 + 0.2 * query.chars().count() as f32 / text.chars().count() as f32)
+}
+
+/// How many letters need adding, removing or changing to turn one word into another
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<_> = b.chars().collect();
+    let mut row: Vec<_> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (above + 1)
+                .min(row[j] + 1)
+                .min(diagonal + usize::from(ca != *cb));
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use strum::VariantArray;
+
+    use super::*;
+    use crate::{
+        entity::background::{career::Career, fear::Fear, hope::Hope, EntityBackground},
+        hex::AxialHex,
+        mtch::test_match::test_player,
+    };
+
+    fn player(first_name: &str, family_name: &str, career: Career) -> Entity {
+        let mut player = test_player(&format!("{first_name} {family_name}"), AxialHex::ZERO);
+        player.attributes.first_name = Some(first_name.into());
+        player.attributes.family_name = Some(family_name.into());
+        player.attributes.background = Some(EntityBackground {
+            country_name: "Norway".into(),
+            city_name: "Bergen".into(),
+            career,
+            is_retired: false,
+            eye_colour: "blue".into(),
+            hair_colour: "blond".into(),
+            fear: Fear::VARIANTS[0].clone(),
+            hope: Hope::VARIANTS[0].clone(),
+        });
+        player
+    }
+
+    #[test]
+    fn test_search_ranks_closest_matches_first() {
+        let henrik = player("Henrik", "Olsen", Career::SousChef);
+        let henrietta = player("Henrietta", "Baker", Career::Actor);
+        let baker = player("Ola", "Nordmann", Career::Baker);
+        let tree = Entity {
+            entity_id: Entity::id(),
+            name: "Henrik's Tree".into(),
+            ..Default::default()
+        };
+        let entities = [
+            henrik.clone(),
+            henrietta.clone(),
+            baker.clone(),
+            tree.clone(),
+        ];
+        let ids = |query: &str, markers: &[EntityMarker]| {
+            search_entities(entities.iter(), query, markers)
+                .into_iter()
+                .map(|result| result.entity_id)
+                .collect::<Vec<_>>()
+        };
+
+        // Whole names beat the start of a name, which beats the start of another word
+        let results = search_entities(entities.iter(), "HENRIK", &[]);
+        assert_eq!(results[0].entity_id, henrik.entity_id);
+        assert_eq!(results[0].matched, SearchField::FirstName);
+        assert_eq!(results[1].entity_id, tree.entity_id);
+        assert_eq!(
+            ids("henri", &[]),
+            vec![
+                henrietta.entity_id.clone(),
+                henrik.entity_id.clone(),
+                tree.entity_id.clone()
+            ]
+        );
+
+        // Only players, if asked
+        assert!(!ids("henrik", &[EntityMarker::Player]).contains(&tree.entity_id));
+
+        // Names count for more than careers
+        assert_eq!(
+            ids("baker", &[]),
+            vec![henrietta.entity_id, baker.entity_id]
+        );
+
+        // Forgiving typos
+        assert_eq!(
+            ids("henrick", &[EntityMarker::Player]),
+            vec![henrik.entity_id]
+        );
+        assert!(ids("", &[]).is_empty());
+        assert!(ids("zzz", &[]).is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+//! Contestants coming back for another season
+//!
+//! When a match follows on from another (see `MatchConfig::preceding_match_id`), whoever made it
+//! out of the last one (still standing at the end, or escaped early) is brought back for it rather
+//! than someone new being generated. They come back empty handed and knowing nothing of the new
+//! world, but they're still who they were and still feel the same about everyone. Each match they
+//! play goes down in their `season_history`, so the site can show a returning contestant's past.
+//!
+//! Who comes back depends on the carry-over rules of the season the match is part of
+//! (see `season_config`, a season being a run of matches that follow on from each other).
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{brain::motivator::MotivatorTable, Entity, EntityId, EntityMarker},
+    has_markers,
+};
+
+use super::{season_config::CarryOverRules, MatchId};
+
+/// How strongly someone has to feel about someone else (either way) for it to go down in history
+const MEMORABLE_BOND: f32 = 0.5;
+
+/// The seasons some contestant has played before this one
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct EntitySeasonHistory {
+    /// Oldest first
+    pub seasons: Vec<PastSeason>,
+}
+
+/// Some match a contestant played in and made it out of
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct PastSeason {
+    pub match_id: MatchId,
+    pub outcome: SeasonOutcome,
+
+    /// Whoever they felt strongest about by the end of it
+    pub bonds: Vec<PastBond>,
+}
+
+/// How some season ended for a contestant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[qubit::ts]
+pub enum SeasonOutcome {
+    /// Last one standing
+    Won,
+
+    /// Still standing at the end, along with others
+    Survived,
+
+    /// Got out early in an escape pod
+    Escaped,
+}
+
+/// Someone a contestant felt strongly about (as of the end of some season)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct PastBond {
+    pub entity_id: EntityId,
+
+    /// What they were called back then
+    pub name: String,
+
+    pub bond: f32,
+}
+
+/// How each player who made it out of some match (given its entities at the end) did
+pub fn outcomes(entities: &[Entity]) -> Vec<(&Entity, SeasonOutcome)> {
+    let players = entities
+        .iter()
+        .filter(|e| has_markers!(e, Player))
+        .collect_vec();
+    let still_standing = players.iter().filter(|e| !has_markers!(e, Escaped)).count();
+
+    players
+        .into_iter()
+        .map(|player| {
+            let outcome = if has_markers!(player, Escaped) {
+                SeasonOutcome::Escaped
+            } else if still_standing == 1 {
+                SeasonOutcome::Won
+            } else {
+                SeasonOutcome::Survived
+            };
+            (player, outcome)
+        })
+        .collect()
+}
+
+/// The players from the end of some match who come back for the next one (if the rules let
+/// them), with the match added to their history and ready to be warped in again
+pub fn returning_players(
+    match_id: &MatchId,
+    entities: &[Entity],
+    rules: &CarryOverRules,
+) -> Vec<Entity> {
+    outcomes(entities)
+        .into_iter()
+        .filter(|(_, outcome)| rules.brings_back(*outcome))
+        .map(|(player, outcome)| {
+            let bonds = player
+                .relations
+                .associates()
+                .map(|(entity_id, _)| (entity_id, player.relations.bond(entity_id)))
+                .filter(|(_, bond)| bond.abs() >= MEMORABLE_BOND)
+                .filter_map(|(entity_id, bond)| {
+                    let other = entities.iter().find(|e| &e.entity_id == entity_id)?;
+                    Some(PastBond {
+                        entity_id: entity_id.clone(),
+                        name: other.name.clone(),
+                        bond,
+                    })
+                })
+                .collect();
+
+            let mut player = player.clone();
+            player
+                .attributes
+                .season_history
+                .get_or_insert_default()
+                .seasons
+                .push(PastSeason {
+                    match_id: match_id.clone(),
+                    outcome,
+                    bonds,
+                });
+            player.start_new_season();
+            player
+        })
+        .collect()
+}
+
+impl Entity {
+    /// Leave behind everything from the last match, keeping who we are and who we know
+    fn start_new_season(&mut self) {
+        self.markers.retain(|m| *m != EntityMarker::Escaped);
+
+        // Banished until warped in again
+        self.attributes.hex = None;
+        self.attributes.focus = None;
+        self.attributes.motivators = MotivatorTable::initialise();
+        self.attributes.memes = Some(Default::default());
+        self.attributes.reputation = None;
+        self.relations.inventory_mut().clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{entity::generate::generate_corpse, hex::AxialHex, mtch::test_match::test_player};
+
+    #[test]
+    fn test_survivors_return_with_history() {
+        let mut survivor = test_player("Survivor", AxialHex::ZERO);
+        let mut escapee = test_player("Escapee", AxialHex::ZERO);
+        let fallen = test_player("Fallen", AxialHex::ZERO);
+        survivor
+            .relations
+            .increase_associate_bond_by(&escapee.entity_id, 0.8);
+        survivor
+            .relations
+            .increase_associate_bond_by(&fallen.entity_id, 0.1);
+        escapee.markers.push(EntityMarker::Escaped);
+        escapee.attributes.hex = None;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let match_id = MatchId::from("last-season");
+        let entities = vec![survivor, escapee, generate_corpse(&mut rng, fallen)];
+        let returning = returning_players(&match_id, &entities, &CarryOverRules::default());
+        assert_eq!(returning.len(), 2);
+
+        let survivor = &returning[0];
+        let history = survivor.attributes.season_history.as_ref().unwrap();
+        assert_eq!(history.seasons.len(), 1);
+        assert_eq!(history.seasons[0].match_id, match_id);
+        assert_eq!(history.seasons[0].outcome, SeasonOutcome::Won);
+        assert_eq!(history.seasons[0].bonds.len(), 1);
+        assert_eq!(history.seasons[0].bonds[0].name, "Escapee");
+        assert_eq!(survivor.attributes.hex, None);
+
+        let escapee = &returning[1];
+        let history = escapee.attributes.season_history.as_ref().unwrap();
+        assert_eq!(history.seasons[0].outcome, SeasonOutcome::Escaped);
+        assert!(!has_markers!(escapee, Escaped));
+
+        // Unless escaping means you're out for good
+        let rules = CarryOverRules {
+            escapees_return: false,
+            ..Default::default()
+        };
+        assert_eq!(returning_players(&match_id, &entities, &rules).len(), 1);
+    }
+}
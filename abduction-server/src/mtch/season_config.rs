@@ -0,0 +1,304 @@
+//! Seasons, runs of matches played by (mostly) the same contestants
+//!
+//! A season starts with a match that follows on from nothing and has plenty of players (the
+//! weekend seeding in the plan up in `mtch`). Every match after that follows on from the last one,
+//! bringing back whoever the season's carry-over rules allow (see `season::returning_players`) and
+//! topping up with new players, until the season has played all its matches and the next one is
+//! seeded. The standings add up how everyone did over the season's finished matches.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sqlx::{prelude::FromRow, types::Json};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::{
+    entity::{Entity, EntityId, EntityManager},
+    Db,
+};
+
+use super::{
+    config::MatchConfig,
+    season::{outcomes, SeasonOutcome},
+    MatchId,
+};
+
+pub type SeasonId = String;
+
+/// How many matches make up a season, unless it says otherwise
+const DEFAULT_MATCH_COUNT: usize = 5;
+
+/// How many players are generated for the first match of a season
+const DEFAULT_SEED_PLAYER_COUNT: usize = 20;
+
+/// How many players the rest of the matches of a season are for
+/// (if not enough come back, the rest are new)
+const FOLLOW_ON_PLAYER_COUNT: usize = 10;
+
+/// The configuration for a season
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[qubit::ts]
+pub struct SeasonConfig {
+    /// Unique v7 uuid for this season
+    pub season_id: SeasonId,
+
+    /// Which season this is (counting up from 1)
+    pub number: i32,
+
+    /// How many matches are played before the season is over
+    pub match_count: i32,
+
+    /// How many players are generated for the first match of the season
+    pub seed_player_count: i32,
+
+    /// Who comes back for the next match of the season
+    #[ts(as = "CarryOverRules")]
+    pub carry_over: Json<CarryOverRules>,
+}
+
+/// Who comes back from one match of a season for the next
+/// (the dead never do)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct CarryOverRules {
+    /// Whether the last one standing comes back (or retires a champion)
+    pub winner_returns: bool,
+
+    /// Whether those still standing alongside others come back
+    pub survivors_return: bool,
+
+    /// Whether those who escaped early come back
+    pub escapees_return: bool,
+}
+
+impl Default for CarryOverRules {
+    fn default() -> Self {
+        Self {
+            winner_returns: true,
+            survivors_return: true,
+            escapees_return: true,
+        }
+    }
+}
+
+impl CarryOverRules {
+    /// Does someone who had some outcome come back?
+    pub fn brings_back(&self, outcome: SeasonOutcome) -> bool {
+        match outcome {
+            SeasonOutcome::Won => self.winner_returns,
+            SeasonOutcome::Survived => self.survivors_return,
+            SeasonOutcome::Escaped => self.escapees_return,
+        }
+    }
+}
+
+/// How some contestant has done over a season
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct SeasonStanding {
+    pub entity_id: EntityId,
+    pub name: String,
+
+    /// How many of the season's matches they made it out of
+    pub matches: usize,
+    pub wins: usize,
+    pub escapes: usize,
+}
+
+/// A match in a season
+#[derive(Debug, Clone)]
+pub struct SeasonMatch {
+    pub match_id: MatchId,
+    pub complete: bool,
+}
+
+impl SeasonConfig {
+    fn new(number: i32) -> Self {
+        Self {
+            season_id: Uuid::now_v7().hyphenated().to_string(),
+            number,
+            match_count: DEFAULT_MATCH_COUNT as i32,
+            seed_player_count: DEFAULT_SEED_PLAYER_COUNT as i32,
+            carry_over: Json(CarryOverRules::default()),
+        }
+    }
+
+    /// Get one season config from the db
+    pub async fn get(db: &Db, season_id: SeasonId) -> anyhow::Result<Option<Self>> {
+        sqlx::query_file_as!(Self, "queries/get_season_config.sql", season_id)
+            .fetch_optional(db)
+            .await
+            .context("getting season config")
+    }
+
+    /// Get the config of the most recent season, whatever state it's in
+    pub async fn get_latest(db: &Db) -> anyhow::Result<Option<Self>> {
+        sqlx::query_file_as!(Self, "queries/get_latest_season_config.sql")
+            .fetch_optional(db)
+            .await
+            .context("getting latest season config")
+    }
+
+    /// Get the config of the season some match is part of (if it's part of one)
+    pub async fn of_match(match_id: &MatchId, db: &Db) -> anyhow::Result<Option<Self>> {
+        sqlx::query_file_as!(Self, "queries/get_match_season_config.sql", match_id)
+            .fetch_optional(db)
+            .await
+            .context("getting season config of match")
+    }
+
+    /// The season the next match should be part of
+    /// (the latest, unless it's played all its matches, in which case a new one is seeded)
+    pub async fn for_next_match(db: &Db) -> anyhow::Result<Self> {
+        let latest = Self::get_latest(db).await?;
+        if let Some(season) = &latest {
+            if season.matches(db).await?.len() < season.match_count as usize {
+                return Ok(season.clone());
+            }
+        }
+
+        let season = Self::new(latest.map_or(1, |season| season.number + 1));
+        info!("Seeding season {}", season.number);
+        season.save(db).await?;
+        Ok(season)
+    }
+
+    /// Start a new season (whether or not the last one is over), seeded with some number of players
+    pub async fn seed(seed_player_count: usize, db: &Db) -> anyhow::Result<Self> {
+        let latest = Self::get_latest(db).await?;
+        let season = Self {
+            seed_player_count: seed_player_count as i32,
+            ..Self::new(latest.map_or(1, |season| season.number + 1))
+        };
+        info!("Seeding season {}", season.number);
+        season.save(db).await?;
+        Ok(season)
+    }
+
+    /// The config for the next match of the season
+    /// (the first is seeded from scratch, the rest follow on from the last)
+    pub async fn next_match(&self, db: &Db) -> anyhow::Result<MatchConfig> {
+        let config = match self.matches(db).await?.last() {
+            None => MatchConfig::sized_for(self.seed_player_count as usize),
+            Some(last) => MatchConfig::sized_for(FOLLOW_ON_PLAYER_COUNT)
+                .with_preceding_match(Some(last.match_id.clone())),
+        };
+        Ok(config)
+    }
+
+    /// The matches in the season so far (oldest first)
+    pub async fn matches(&self, db: &Db) -> anyhow::Result<Vec<SeasonMatch>> {
+        let matches = sqlx::query_file!("queries/get_season_matches.sql", self.season_id)
+            .fetch_all(db)
+            .await
+            .context("getting season matches")?
+            .into_iter()
+            .map(|row| SeasonMatch {
+                match_id: row.match_id,
+                complete: row.complete,
+            })
+            .collect();
+        Ok(matches)
+    }
+
+    /// Make some (saved) match part of the season
+    pub async fn add_match(&self, match_id: &MatchId, db: &Db) -> anyhow::Result<()> {
+        sqlx::query_file!("queries/add_season_match.sql", self.season_id, match_id)
+            .execute(db)
+            .await
+            .context("adding match to season")?;
+        Ok(())
+    }
+
+    /// How everyone has done over the season's finished matches (best first)
+    pub async fn standings(&self, db: &Db) -> anyhow::Result<Vec<SeasonStanding>> {
+        let mut finished = Vec::new();
+        for season_match in self.matches(db).await? {
+            if season_match.complete {
+                finished.push(
+                    EntityManager::load_entities_from_match(&season_match.match_id, db)
+                        .await
+                        .collect(),
+                );
+            }
+        }
+        Ok(tally_standings(finished))
+    }
+
+    pub async fn save(&self, db: &Db) -> anyhow::Result<()> {
+        info!("Saving season configuration {} to db", &self.season_id);
+        sqlx::query_file!(
+            "queries/set_season_config.sql",
+            self.season_id,
+            self.number,
+            self.match_count,
+            self.seed_player_count,
+            self.carry_over,
+        )
+        .execute(db)
+        .await
+        .map(|_| ())
+        .context("Saving season config")
+    }
+}
+
+/// Add up how everyone did, given the entities at the end of each match
+fn tally_standings(matches: Vec<Vec<Entity>>) -> Vec<SeasonStanding> {
+    let mut standings: HashMap<EntityId, SeasonStanding> = HashMap::new();
+    for entities in &matches {
+        for (player, outcome) in outcomes(entities) {
+            let standing = standings
+                .entry(player.entity_id.clone())
+                .or_insert_with(|| SeasonStanding {
+                    entity_id: player.entity_id.clone(),
+                    name: String::new(),
+                    matches: 0,
+                    wins: 0,
+                    escapes: 0,
+                });
+            // (they might have been renamed since)
+            standing.name = player.name.clone();
+            standing.matches += 1;
+            match outcome {
+                SeasonOutcome::Won => standing.wins += 1,
+                SeasonOutcome::Escaped => standing.escapes += 1,
+                SeasonOutcome::Survived => {}
+            }
+        }
+    }
+
+    let mut standings: Vec<_> = standings.into_values().collect();
+    standings.sort_by(|a, b| {
+        (b.wins, b.escapes, b.matches)
+            .cmp(&(a.wins, a.escapes, a.matches))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    standings
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{entity::EntityMarker, hex::AxialHex, mtch::test_match::test_player};
+
+    #[test]
+    fn test_standings_add_up_over_matches() {
+        let veteran = test_player("Veteran", AxialHex::ZERO);
+        let mut runner = test_player("Runner", AxialHex::ZERO);
+        runner.markers.push(EntityMarker::Escaped);
+
+        // Veteran wins the first (Runner escaped), then both make it through the second
+        let standings = tally_standings(vec![
+            vec![veteran.clone(), runner.clone()],
+            vec![veteran.clone(), test_player("Rookie", AxialHex::ZERO)],
+        ]);
+
+        assert_eq!(standings.len(), 3);
+        assert_eq!(standings[0].name, "Veteran");
+        assert_eq!((standings[0].matches, standings[0].wins), (2, 1));
+        assert_eq!(standings[1].name, "Runner");
+        assert_eq!(standings[1].escapes, 1);
+    }
+}
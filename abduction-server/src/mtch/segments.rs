@@ -0,0 +1,201 @@
+//! Scripted segments of the show
+//!
+//! Each match has a show script (part of its config) listing segments for the presenter to run at
+//! certain points, like the opening monologue, recapping each day or announcing the final two. The scheduler works out
+//! when each one is due, then the presenter says their lines, which also go out to clients as an
+//! announcement so they can be shown front and centre.
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{entity::snapshot::EntityView, has_markers};
+
+use super::{calendar::MatchTime, MatchConfig, TickId};
+
+/// A segment of the show
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentKind {
+    /// Welcome everyone to the show
+    OpeningMonologue,
+
+    /// Catch everyone up on how things are going
+    MidpointRecap,
+
+    /// Catch everyone up as a new day dawns
+    DayRecap,
+
+    /// Build up to the last two contestants facing off
+    FinalShowdown,
+}
+
+/// When a segment should happen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SegmentTrigger {
+    /// Once this many ticks of the match have passed
+    AfterTicks { ticks: TickId },
+
+    /// Once there are only this many players left
+    PlayersRemaining { count: usize },
+
+    /// At the start of every day (after the first, see `mtch::calendar`)
+    EveryDay,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct ShowSegment {
+    pub segment: SegmentKind,
+    pub trigger: SegmentTrigger,
+}
+
+/// Every segment planned for a match
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[qubit::ts]
+pub struct ShowScript(Vec<ShowSegment>);
+
+impl ShowScript {
+    /// The usual running order
+    pub fn standard(player_count: usize) -> Self {
+        Self(vec![
+            ShowSegment {
+                segment: SegmentKind::OpeningMonologue,
+                trigger: SegmentTrigger::AfterTicks { ticks: 0 },
+            },
+            ShowSegment {
+                segment: SegmentKind::DayRecap,
+                trigger: SegmentTrigger::EveryDay,
+            },
+            ShowSegment {
+                segment: SegmentKind::MidpointRecap,
+                trigger: SegmentTrigger::PlayersRemaining {
+                    count: player_count / 2,
+                },
+            },
+            ShowSegment {
+                segment: SegmentKind::FinalShowdown,
+                trigger: SegmentTrigger::PlayersRemaining { count: 2 },
+            },
+        ])
+    }
+}
+
+/// Keeps track of which segments of the show script are due
+#[derive(Debug, Default)]
+pub struct SegmentScheduler {
+    /// How many players were left last tick
+    /// (not known until the first tick after loading the match)
+    players_remaining: Option<usize>,
+}
+
+impl SegmentScheduler {
+    /// Which segments are due on this tick
+    pub fn due(
+        &mut self,
+        script: &ShowScript,
+        tick_id: TickId,
+        players_remaining: usize,
+    ) -> Vec<SegmentKind> {
+        let previous_remaining = self.players_remaining.replace(players_remaining);
+        script
+            .0
+            .iter()
+            .filter(|segment| match segment.trigger {
+                SegmentTrigger::AfterTicks { ticks } => tick_id == ticks + 1,
+                SegmentTrigger::PlayersRemaining { count } => {
+                    players_remaining <= count
+                        && previous_remaining.is_some_and(|previous| previous > count)
+                }
+                SegmentTrigger::EveryDay => MatchTime::is_start_of_day(tick_id),
+            })
+            .map(|segment| segment.segment)
+            .collect()
+    }
+}
+
+impl SegmentKind {
+    /// What the presenter says for this segment
+    pub fn script(
+        &self,
+        entity_view: &EntityView,
+        config: &MatchConfig,
+        tick_id: TickId,
+    ) -> String {
+        let players = entity_view
+            .all()
+            .filter(|e| has_markers!(e, Player))
+            .collect_vec();
+
+        match self {
+            SegmentKind::OpeningMonologue => format!(
+                "Welcome, welcome, one and all, to the greatest show in the galaxy! {} contestants, one world, and only one of them is getting out of here. Let's meet them!",
+                config.player_count
+            ),
+            SegmentKind::MidpointRecap => format!(
+                "We're halfway there folks! Only {} of our {} contestants are still standing. Who will be next?",
+                players.len(),
+                config.player_count
+            ),
+            SegmentKind::DayRecap => format!(
+                "Rise and shine, it's day {} here on the show! {} of our {} contestants have made it through the night. Who'll still be here tomorrow?",
+                MatchTime::at_tick(tick_id).day,
+                players.len(),
+                config.player_count
+            ),
+            SegmentKind::FinalShowdown => match players.as_slice() {
+                [first, second] => format!(
+                    "And then there were two! It's {} versus {}, and only one of them walks away from this!",
+                    first.name, second.name
+                ),
+                _ => "And then there were two! Only one of them walks away from this!".to_owned(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_segments_happen_once() {
+        let script = ShowScript::standard(10);
+        let mut scheduler = SegmentScheduler::default();
+
+        assert_eq!(
+            scheduler.due(&script, 1, 10),
+            vec![SegmentKind::OpeningMonologue]
+        );
+        assert!(scheduler.due(&script, 2, 10).is_empty());
+        assert_eq!(
+            scheduler.due(&script, 3, 5),
+            vec![SegmentKind::MidpointRecap]
+        );
+        assert!(scheduler.due(&script, 4, 4).is_empty());
+        assert_eq!(
+            scheduler.due(&script, 5, 2),
+            vec![SegmentKind::FinalShowdown]
+        );
+    }
+
+    #[test]
+    fn test_day_recaps_happen_each_dawn() {
+        use crate::mtch::calendar::{HOURS_PER_DAY, TICKS_PER_HOUR};
+
+        let script = ShowScript::standard(10);
+        let mut scheduler = SegmentScheduler::default();
+        let ticks_per_day = TICKS_PER_HOUR * HOURS_PER_DAY;
+        for day in 1..=3 {
+            assert_eq!(
+                scheduler.due(&script, ticks_per_day * day, 10),
+                vec![SegmentKind::DayRecap]
+            );
+            assert!(scheduler
+                .due(&script, ticks_per_day * day + 1, 10)
+                .is_empty());
+        }
+    }
+}
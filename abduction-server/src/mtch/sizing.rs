@@ -0,0 +1,136 @@
+//! How big a match's world is, given how many are playing
+//!
+//! Too many players in a small world and they're all over each other (and the food), too few in a
+//! big one and they might never meet. So the world radius comes from the player count, aiming for
+//! some number of hexes per player, and props are spread thicker or thinner to make up for the radius
+//! being rounded (or hitting its limits). The ratios can be tuned without a rebuild (see
+//! `BehaviorConfig::sizing`).
+
+use serde::{Deserialize, Serialize};
+
+/// How to size a match's world to its players
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[qubit::ts]
+#[serde(default)]
+pub struct MatchSizing {
+    /// How many hexes of world we'd like for each player
+    pub hexes_per_player: f32,
+
+    /// The smallest and largest the world radius can be
+    pub min_radius: usize,
+    pub max_radius: usize,
+
+    /// The limits on how much thicker (or thinner) props can be spread than usual
+    pub min_prop_density: f32,
+    pub max_prop_density: f32,
+}
+
+impl Default for MatchSizing {
+    fn default() -> Self {
+        Self {
+            hexes_per_player: 9.0,
+            min_radius: 2,
+            max_radius: 20,
+            min_prop_density: 0.5,
+            max_prop_density: 2.0,
+        }
+    }
+}
+
+impl MatchSizing {
+    /// Anything wrong with sizing a match for some number of players
+    /// (empty if it's all fine)
+    pub fn problems(&self, player_count: usize) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !self.hexes_per_player.is_finite() || self.hexes_per_player <= 0.0 {
+            problems.push(format!(
+                "Hexes per player must be positive, not {}",
+                self.hexes_per_player
+            ));
+        }
+        if self.min_radius > self.max_radius {
+            problems.push(format!(
+                "Min radius {} is bigger than max radius {}",
+                self.min_radius, self.max_radius
+            ));
+        }
+        if !(self.min_prop_density > 0.0 && self.min_prop_density <= self.max_prop_density) {
+            problems.push(format!(
+                "Prop density limits {}-{} must be positive and in order",
+                self.min_prop_density, self.max_prop_density
+            ));
+        }
+        if player_count == 0 {
+            problems.push("A match needs at least one player".to_owned());
+        }
+        if hex_count(self.max_radius) < player_count {
+            problems.push(format!(
+                "{player_count} players won't fit in a world of radius {}",
+                self.max_radius
+            ));
+        }
+        problems
+    }
+
+    /// The world radius for some number of players
+    pub fn world_radius(&self, player_count: usize) -> anyhow::Result<usize> {
+        let problems = self.problems(player_count);
+        if !problems.is_empty() {
+            anyhow::bail!("Can't size match: {}", problems.join(", "));
+        }
+
+        let wanted_hexes = (player_count as f32 * self.hexes_per_player).ceil() as usize;
+        let radius = (0..)
+            .find(|radius| hex_count(*radius) >= wanted_hexes)
+            .unwrap();
+        Ok(radius.clamp(self.min_radius, self.max_radius))
+    }
+
+    /// How much thicker (or thinner) than usual to spread props in a world of some radius
+    /// (so there's about as much to go around per player whatever the radius ended up being)
+    pub fn prop_density(&self, player_count: usize, world_radius: usize) -> f32 {
+        let wanted_hexes = player_count as f32 * self.hexes_per_player;
+        let density = wanted_hexes / hex_count(world_radius) as f32;
+
+        // (not `clamp`, a bad config shouldn't take the server down)
+        density
+            .min(self.max_prop_density)
+            .max(self.min_prop_density)
+    }
+}
+
+/// How many hexes there are in a world of some radius
+fn hex_count(radius: usize) -> usize {
+    3 * radius * (radius + 1) + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_world_grows_with_players() {
+        let sizing = MatchSizing::default();
+        assert_eq!(sizing.world_radius(10).unwrap(), 5);
+        assert_eq!(sizing.world_radius(1).unwrap(), sizing.min_radius);
+        assert_eq!(sizing.world_radius(1_000).unwrap(), sizing.max_radius);
+
+        // Crowded worlds get more to go around
+        let crowded = sizing.prop_density(1_000, sizing.max_radius);
+        assert_eq!(crowded, sizing.max_prop_density);
+        assert!((sizing.prop_density(10, 5) - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_bad_sizing_is_reported() {
+        let sizing = MatchSizing {
+            hexes_per_player: 0.0,
+            min_radius: 10,
+            max_radius: 1,
+            min_prop_density: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(sizing.problems(5).len(), 3);
+        assert!(sizing.world_radius(5).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+//! Where each remaining player stands, so viewers can follow the heroes and villains of a match
+
+use serde::Serialize;
+
+use crate::{
+    entity::{brain::reputation::Deed, Entity, EntityId},
+    has_markers,
+};
+
+/// A summary of a player for viewers
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct PlayerStanding {
+    pub entity_id: EntityId,
+    pub name: String,
+
+    /// Overall alignment from everything they've done
+    /// (negative is villainous, positive is heroic)
+    pub reputation: f32,
+
+    /// Everything they've done that counts towards their reputation
+    pub deeds: Vec<Deed>,
+
+    /// How many other players know about at least one of their deeds
+    pub notoriety: usize,
+}
+
+/// Get the standings of every player still in the match, most heroic first
+pub fn player_standings<'a>(entities: impl Iterator<Item = &'a Entity>) -> Vec<PlayerStanding> {
+    let players: Vec<_> = entities.filter(|e| has_markers!(e, Player)).collect();
+
+    let mut standings: Vec<_> = players
+        .iter()
+        .map(|player| {
+            let ledger = player.attributes.reputation.clone().unwrap_or_default();
+            PlayerStanding {
+                entity_id: player.entity_id.clone(),
+                name: player.name.clone(),
+                reputation: ledger.score(),
+                deeds: ledger.deeds().copied().collect(),
+                notoriety: players
+                    .iter()
+                    .filter_map(|other| other.attributes.memes.as_ref())
+                    .filter(|memes| memes.perceived_reputation(&player.entity_id) != 0.0)
+                    .count(),
+            }
+        })
+        .collect();
+
+    standings.sort_by(|a, b| b.reputation.total_cmp(&a.reputation));
+    standings
+}
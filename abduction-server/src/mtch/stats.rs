@@ -0,0 +1,151 @@
+//! Counters of what each player got up to during a match, for post-match profile pages
+//!
+//! Resolution code records stats on the `ActionCtx` as things happen, and they are added onto
+//! the totals in the DB at the end of each tick.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use serde::Serialize;
+use sqlx::{query_file, query_file_as};
+
+use crate::{
+    entity::{Entity, EntityId},
+    has_markers, Db,
+};
+
+use super::MatchId;
+
+/// Above this distress, a player is at death's door
+pub const NEAR_DEATH_DISTRESS: f32 = 0.9;
+
+/// Something worth counting
+#[derive(Debug, Clone, Copy)]
+pub enum PlayerStat {
+    HexTraveled,
+    FoodEaten,
+    ConversationHeld,
+    NearDeath,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[qubit::ts]
+pub struct PlayerMatchStats {
+    pub hexes_traveled: i64,
+    pub food_eaten: i64,
+    pub conversations_held: i64,
+    pub near_death_events: i64,
+
+    /// (nothing can be crafted yet)
+    pub items_crafted: i64,
+}
+
+impl PlayerMatchStats {
+    fn record(&mut self, stat: PlayerStat) {
+        let counter = match stat {
+            PlayerStat::HexTraveled => &mut self.hexes_traveled,
+            PlayerStat::FoodEaten => &mut self.food_eaten,
+            PlayerStat::ConversationHeld => &mut self.conversations_held,
+            PlayerStat::NearDeath => &mut self.near_death_events,
+        };
+        *counter += 1;
+    }
+
+    /// Get the stats for a player in some match (if they've done anything)
+    pub async fn load(
+        match_id: &MatchId,
+        entity_id: &EntityId,
+        db: &Db,
+    ) -> anyhow::Result<Option<Self>> {
+        query_file_as!(
+            Self,
+            "queries/get_player_match_stats.sql",
+            match_id,
+            entity_id
+        )
+        .fetch_optional(db)
+        .await
+        .context("Failed to load player stats")
+    }
+}
+
+/// Stats recorded during a tick, that haven't been saved yet
+#[derive(Debug, Default)]
+pub struct PlayerStatsBuffer {
+    stats: HashMap<EntityId, PlayerMatchStats>,
+}
+
+impl PlayerStatsBuffer {
+    /// Count something a player did (anyone else is ignored)
+    pub fn record(&mut self, entity: &Entity, stat: PlayerStat) {
+        if has_markers!(entity, Player) {
+            self.stats
+                .entry(entity.entity_id.clone())
+                .or_default()
+                .record(stat);
+        }
+    }
+
+    /// Add everything recorded onto the totals in the DB
+    pub async fn flush(self, match_id: &MatchId, db: &Db) -> anyhow::Result<()> {
+        for (entity_id, stats) in self.stats {
+            query_file!(
+                "queries/add_player_match_stats.sql",
+                match_id,
+                entity_id,
+                stats.hexes_traveled,
+                stats.food_eaten,
+                stats.conversations_held,
+                stats.near_death_events,
+                stats.items_crafted
+            )
+            .execute(db)
+            .await
+            .context("Failed to save player stats")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::brain::motivator,
+        hex::AxialHex,
+        logs::GameLogBody,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_stats_count_movement() {
+        let builder = TestMatch::builder();
+        let mut wanderer = test_player("Wanderer", AxialHex::ZERO);
+        wanderer
+            .attributes
+            .motivators
+            .bump_scaled::<motivator::Boredom>(1000.0);
+        let wanderer_id = wanderer.entity_id.clone();
+        let mut test_match = builder.with_entity(wanderer).build().await;
+
+        let moved = test_match
+            .tick_until(30, |m| {
+                m.has_log_by(&wanderer_id, |body| {
+                    matches!(body, GameLogBody::EntityMovement { .. })
+                })
+            })
+            .await;
+        assert!(moved, "Never moved");
+
+        let match_id = test_match.manager.config.match_id.clone();
+        let stats = PlayerMatchStats::load(&match_id, &wanderer_id, &test_match.ctx().db)
+            .await
+            .unwrap()
+            .unwrap();
+        let moves = test_match
+            .logs_by(&wanderer_id)
+            .filter(|log| matches!(log.body, GameLogBody::EntityMovement { .. }))
+            .count();
+        assert_eq!(stats.hexes_traveled, moves as i64);
+    }
+}
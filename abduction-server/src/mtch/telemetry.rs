@@ -0,0 +1,155 @@
+//! A rough picture of how the match is going, for dashboards
+//!
+//! Every `TELEMETRY_INTERVAL` ticks a `TickEvent::Telemetry` goes out with how each motivator is spread
+//! across the players still in it, how many of each kind of entity there are, and what the world is
+//! doing. It's all aggregate, so a dashboard can chart the health of a match without following every
+//! entity change.
+
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    entity::{
+        brain::motivator::MotivatorKey,
+        world::{TimeOfDay, WeatherKind},
+        Entity, EntityMarker,
+    },
+    has_markers,
+};
+
+use super::{MatchManager, TickId};
+
+/// How many ticks between telemetry being sent out
+const TELEMETRY_INTERVAL: TickId = 10;
+
+/// How the match is going as of some tick
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct MatchTelemetry {
+    pub tick_id: TickId,
+
+    /// How each motivator is spread across the players still in the match
+    /// (empty if there are none)
+    pub motivators: Vec<MotivatorSpread>,
+
+    /// How many entities have each marker
+    pub markers: Vec<MarkerCount>,
+
+    pub weather: Option<WeatherKind>,
+    pub time_of_day: Option<TimeOfDay>,
+    pub day: Option<usize>,
+}
+
+/// The lowest, average and highest motivation of some motivator across the players
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct MotivatorSpread {
+    pub motivator: MotivatorKey,
+    pub min: f32,
+    pub mean: f32,
+    pub max: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct MarkerCount {
+    pub marker: EntityMarker,
+    pub count: usize,
+}
+
+impl MatchTelemetry {
+    /// Sum up some set of entities
+    pub fn gather<'a>(tick_id: TickId, entities: impl Iterator<Item = &'a Entity>) -> Self {
+        let entities = entities.collect_vec();
+
+        let mut motivations: HashMap<MotivatorKey, Vec<f32>> = HashMap::new();
+        for player in entities.iter().filter(|e| has_markers!(e, Player)) {
+            for (key, motivation) in player.attributes.motivators.motivations() {
+                motivations.entry(*key).or_default().push(motivation);
+            }
+        }
+        let motivators = motivations
+            .into_iter()
+            .map(|(motivator, values)| MotivatorSpread {
+                motivator,
+                min: values.iter().copied().fold(f32::INFINITY, f32::min),
+                mean: values.iter().sum::<f32>() / values.len() as f32,
+                max: values.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+            })
+            .sorted_by_key(|spread| format!("{:?}", spread.motivator))
+            .collect();
+
+        let markers = entities
+            .iter()
+            .flat_map(|e| e.markers.iter())
+            .counts()
+            .into_iter()
+            .map(|(marker, count)| MarkerCount {
+                marker: marker.clone(),
+                count,
+            })
+            .sorted_by_key(|marker_count| format!("{:?}", marker_count.marker))
+            .collect();
+
+        let world = entities.iter().find_map(|e| e.attributes.world.as_ref());
+        Self {
+            tick_id,
+            motivators,
+            markers,
+            weather: world.map(|world| world.weather.clone()),
+            time_of_day: world.map(|world| world.time_of_day.clone()),
+            day: world.map(|world| world.day),
+        }
+    }
+}
+
+impl MatchManager {
+    /// How the match is going, if this tick is due some telemetry
+    pub fn maybe_gather_telemetry(&self) -> Option<MatchTelemetry> {
+        if !self.tick_id.is_multiple_of(TELEMETRY_INTERVAL) {
+            return None;
+        }
+        Some(MatchTelemetry::gather(
+            self.tick_id,
+            self.entities.get_all_entities(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{create_markers, entity::brain::motivator::MotivatorTable};
+
+    use super::*;
+
+    #[test]
+    fn test_telemetry_spreads_motivators() {
+        let players = [0.2, 0.6].map(|hunger| {
+            let mut motivators = MotivatorTable::initialise();
+            motivators.set_key(MotivatorKey::Hunger, hunger);
+            let mut player = Entity {
+                markers: create_markers!(Player, Human),
+                ..Default::default()
+            };
+            player.attributes.motivators = motivators;
+            player
+        });
+
+        let telemetry = MatchTelemetry::gather(10, players.iter());
+        let hunger = telemetry
+            .motivators
+            .iter()
+            .find(|spread| spread.motivator == MotivatorKey::Hunger)
+            .unwrap();
+        assert_eq!((hunger.min, hunger.max), (0.2, 0.6));
+        assert!((hunger.mean - 0.4).abs() < 0.001);
+        assert!(telemetry
+            .markers
+            .iter()
+            .any(|marker_count| marker_count.marker == EntityMarker::Player
+                && marker_count.count == 2));
+        assert!(telemetry.weather.is_none());
+    }
+}
@@ -0,0 +1,228 @@
+//! Harness for running scripted matches in tests
+//!
+//! A `TestMatch` is a real `MatchManager` backed by an in-memory db, with a seeded rng
+//! and only the entities a test places by hand (plus a world and its locations).
+//! Tests advance it tick by tick and then assert on the logs, events and entity states
+//!
+//! NOTE: the rng is seeded as soon as the builder is made, so make it before any of the entities
+//! to place (so they get the same ids, and everything else they roll, every time)
+
+use std::{collections::HashSet, sync::Arc};
+
+use sqlx::sqlite::SqlitePoolOptions;
+use tokio::sync::broadcast;
+
+use crate::{
+    create_markers,
+    entity::{
+        brain::{meme::MemeTable, motivator::MotivatorTable},
+        generate::generate_world,
+        Entity, EntityAttributes, EntityId,
+    },
+    event::GameEvent,
+    has_markers,
+    hex::AxialHex,
+    location::{generate_locations_for_world, Biome},
+    logs::{GameLog, GameLogBody},
+    mtch::{features::FeatureFlag, ruleset::MatchVariant, MatchConfig, MatchManager, TickEvent},
+    ServerCtx, LOG_CHANNEL_CAPACITY,
+};
+
+pub struct TestMatchBuilder {
+    world_radius: usize,
+    variant: MatchVariant,
+    features: HashSet<FeatureFlag>,
+    entities: Vec<Entity>,
+}
+
+impl TestMatchBuilder {
+    /// Seed for the game rng
+    /// (reseeds it straight away, so set it before making any entities to place)
+    pub fn seed(self, seed: u64) -> Self {
+        crate::rng::seed(seed);
+        self
+    }
+
+    pub fn world_radius(mut self, world_radius: usize) -> Self {
+        self.world_radius = world_radius;
+        self
+    }
+
+    pub fn variant(mut self, variant: MatchVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Play the match without some experimental system
+    pub fn without_feature(mut self, feature: FeatureFlag) -> Self {
+        self.features.remove(&feature);
+        self
+    }
+
+    /// Place an entity in the match before it starts
+    pub fn with_entity(mut self, entity: Entity) -> Self {
+        self.entities.push(entity);
+        self
+    }
+
+    pub async fn build(self) -> TestMatch {
+        // NOTE: every connection to an in-memory db gets its own db, so there can only be one
+        let db = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("Failed to open in-memory db");
+        sqlx::migrate!()
+            .run(&db)
+            .await
+            .expect("Failed to run migrations");
+
+        let (tick_tx, tick_rx) = broadcast::channel::<TickEvent>(20);
+        let (log_tx, log_rx) = broadcast::channel::<GameLog>(LOG_CHANNEL_CAPACITY);
+        let ctx = ServerCtx {
+            tick_tx,
+            log_tx,
+            db,
+            current_match: Default::default(),
+            flags: Arc::default(),
+            moderation: Arc::default(),
+        };
+
+        let player_count = self
+            .entities
+            .iter()
+            .filter(|e| has_markers!(e, Player))
+            .count();
+        let config = MatchConfig::isolated(player_count, self.world_radius)
+            .with_variant(self.variant)
+            .with_features(self.features);
+        config
+            .save(&ctx.db)
+            .await
+            .expect("Failed to save match config");
+
+        // Just the world and its locations, everything else is up to the test
+        let mut manager = MatchManager::load_match(config, &ctx.db).await;
+        let world = generate_world();
+        let locations = generate_locations_for_world(self.world_radius as isize, Biome::Green);
+        for entity in std::iter::once(world).chain(locations).chain(self.entities) {
+            manager.entities.upsert_entity(entity).unwrap();
+        }
+        manager
+            .entities
+            .flush_changes(&ctx.tick_tx, manager.tick_id, &ctx.db)
+            .await
+            .expect("Failed to flush initial entities");
+
+        TestMatch {
+            manager,
+            ctx,
+            log_rx,
+            _tick_rx: tick_rx,
+            logs: Vec::new(),
+            events: Vec::new(),
+            tick_count: 0,
+        }
+    }
+}
+
+pub struct TestMatch {
+    pub manager: MatchManager,
+    ctx: ServerCtx,
+    log_rx: broadcast::Receiver<GameLog>,
+
+    /// Held so sending tick events doesn't fail for lack of a receiver
+    _tick_rx: broadcast::Receiver<TickEvent>,
+
+    /// Every log sent so far, oldest first
+    pub logs: Vec<GameLog>,
+
+    /// Every event raised so far, oldest first
+    pub events: Vec<GameEvent>,
+
+    /// How many ticks have been run
+    pub tick_count: usize,
+}
+
+impl TestMatch {
+    /// Start setting up a match (seeding the game rng, see `TestMatchBuilder::seed`)
+    pub fn builder() -> TestMatchBuilder {
+        crate::rng::seed(0);
+        TestMatchBuilder {
+            world_radius: 2,
+            variant: MatchVariant::Standard,
+            features: FeatureFlag::all(),
+            entities: Vec::new(),
+        }
+    }
+
+    /// Run a single tick
+    pub async fn tick(&mut self) {
+        self.manager.perform_match_tick(&self.ctx).await;
+        self.manager.report_invariant_violations();
+        self.tick_count += 1;
+
+        while let Ok(log) = self.log_rx.try_recv() {
+            self.logs.push(log);
+        }
+        self.events
+            .extend(self.manager.events.all().iter().cloned());
+    }
+
+    /// Run ticks until some condition holds, giving up after `max_ticks`
+    /// returns whether the condition was met
+    pub async fn tick_until(
+        &mut self,
+        max_ticks: usize,
+        condition: impl Fn(&TestMatch) -> bool,
+    ) -> bool {
+        for _ in 0..max_ticks {
+            self.tick().await;
+            if condition(self) {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn ctx(&self) -> &ServerCtx {
+        &self.ctx
+    }
+
+    pub fn entity(&self, entity_id: &EntityId) -> Option<Entity> {
+        self.manager.entities.get_entity(entity_id)
+    }
+
+    /// All the logs where some entity was the one acting
+    pub fn logs_by<'a>(&'a self, entity_id: &'a EntityId) -> impl Iterator<Item = &'a GameLog> {
+        self.logs
+            .iter()
+            .filter(move |log| log.involved_entities.first() == Some(entity_id))
+    }
+
+    /// Has some entity acted in a way that sent a log matching `predicate`?
+    pub fn has_log_by(
+        &self,
+        entity_id: &EntityId,
+        predicate: impl Fn(&GameLogBody) -> bool,
+    ) -> bool {
+        self.logs_by(entity_id).any(|log| predicate(&log.body))
+    }
+}
+
+/// A bare-bones player to place in a test match
+/// (generated players need the name data on disk)
+pub fn test_player(name: &str, hex: AxialHex) -> Entity {
+    Entity {
+        entity_id: Entity::id(),
+        name: name.into(),
+        markers: create_markers!(Player, Inspectable, Being, Human, CanTalk),
+        attributes: EntityAttributes {
+            hex: Some(hex),
+            motivators: MotivatorTable::initialise(),
+            memes: Some(MemeTable::default()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
@@ -0,0 +1,137 @@
+//! Keeping repetitive logs out of the feed
+//!
+//! Some logs come up over and over (someone barking about the cold every tick they're cold), which
+//! buries everything else in the feed. Logs sent through the throttle (see
+//! `ActionCtx::send_log_throttled`) are held back if the same entity sent the same kind of log only
+//! a few ticks ago. Once the cooldown is up, the next one goes out as an `EntityRepeat` summarising
+//! how many times it happened in the meantime.
+
+use std::{collections::HashMap, mem::Discriminant};
+
+use crate::{
+    entity::EntityId,
+    logs::{GameLog, GameLogBody},
+};
+
+use super::TickId;
+
+/// How many ticks an entity has to wait before the same kind of log goes out again
+const LOG_COOLDOWN_TICKS: usize = 10;
+
+#[derive(Debug, Clone, Default)]
+pub struct LogThrottle {
+    /// The last time each entity sent each kind of log
+    sent: HashMap<(EntityId, Discriminant<GameLogBody>), SentLog>,
+}
+
+#[derive(Debug, Clone)]
+struct SentLog {
+    /// The tick it last went out
+    tick_id: TickId,
+
+    /// How many have been held back since
+    held_back: usize,
+}
+
+impl LogThrottle {
+    /// What (if anything) should go out for some log sent on some tick
+    /// (logs not involving any entity are never held back)
+    pub fn throttle(&mut self, log: GameLog, tick_id: TickId) -> Option<GameLog> {
+        let Some(entity_id) = log.involved_entities.first() else {
+            return Some(log);
+        };
+
+        let key = (entity_id.clone(), std::mem::discriminant(&log.body));
+        let Some(sent) = self.sent.get_mut(&key) else {
+            self.sent.insert(
+                key,
+                SentLog {
+                    tick_id,
+                    held_back: 0,
+                },
+            );
+            return Some(log);
+        };
+
+        // Too soon, hold it back
+        if tick_id < sent.tick_id + LOG_COOLDOWN_TICKS {
+            sent.held_back += 1;
+            return None;
+        }
+
+        // Otherwise let it through, as a summary if we've been holding some back
+        let held_back = std::mem::take(&mut sent.held_back);
+        sent.tick_id = tick_id;
+        if held_back == 0 {
+            return Some(log);
+        }
+        Some(GameLog {
+            body: GameLogBody::EntityRepeat {
+                log: Box::new(log.body),
+                times: held_back + 1,
+            },
+            ..log
+        })
+    }
+
+    /// Forget about logs that have cooled down and have nothing held back
+    /// (so the dead don't hang around forever)
+    pub fn forget_stale(&mut self, tick_id: TickId) {
+        self.sent
+            .retain(|_, sent| sent.held_back > 0 || tick_id < sent.tick_id + LOG_COOLDOWN_TICKS);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        entity::brain::motivator::MotivatorKey,
+        logs::{LogChannel, LogTier},
+    };
+
+    fn bark(entity_id: &EntityId) -> GameLog {
+        GameLog {
+            hex: None,
+            tier: LogTier::Ambient,
+            channel: LogChannel::Global,
+            involved_entities: vec![entity_id.clone()],
+            body: GameLogBody::EntityMotivatorBark {
+                motivation: 0.5,
+                motivator: MotivatorKey::Cold,
+                line: Some("Brr".into()),
+                bark_variant: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_repeats_are_summarised() {
+        let mut throttle = LogThrottle::default();
+        let entity_id: EntityId = "shivering".into();
+
+        assert!(throttle.throttle(bark(&entity_id), 0).is_some());
+        for tick_id in 1..5 {
+            assert!(throttle.throttle(bark(&entity_id), tick_id).is_none());
+        }
+
+        // Someone else can still complain
+        assert!(throttle.throttle(bark(&"other".into()), 3).is_some());
+
+        let summary = throttle.throttle(bark(&entity_id), LOG_COOLDOWN_TICKS);
+        assert!(matches!(
+            summary.map(|log| log.body),
+            Some(GameLogBody::EntityRepeat { times: 5, .. })
+        ));
+
+        // Back to normal after that
+        throttle.forget_stale(LOG_COOLDOWN_TICKS * 3);
+        assert!(matches!(
+            throttle.throttle(bark(&entity_id), LOG_COOLDOWN_TICKS * 3),
+            Some(GameLog {
+                body: GameLogBody::EntityMotivatorBark { .. },
+                ..
+            })
+        ));
+    }
+}
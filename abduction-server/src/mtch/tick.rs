@@ -1,159 +1,594 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::atomic,
+    time::Instant,
+};
+
 use itertools::Itertools;
 use rand::Rng;
-use tracing::{info, warn};
+use tokio::sync::broadcast::{self, error::TryRecvError};
+use tracing::{debug_span, error, field, info, info_span, warn, Instrument, Span};
 
 use crate::{
+    behavior::behavior,
     create_markers,
     entity::{
         brain::{
             actor_action::{ActorAction, ActorActionResult, ActorActionSideEffect},
+            bonding::BOND_DRIFT_INTERVAL,
+            characteristic::Characteristic,
             focus::ActorFocus,
+            gathering::STORY_CHEER,
+            meme::{Meme, Witnessed},
             motivator,
+            nickname::Epithet,
+            rafting::{is_aboard, CAPSIZE_HURT},
+            role::EntityRole,
+            tracking::TRACK_RAIN_WASH_CHANCE,
         },
-        generate::generate_corpse,
+        generate::{generate_corpse, generate_personal_effects, generate_spirit, PropGenerator},
         snapshot::{EntitySnapshot, EntityView},
         world::{EntityWorld, TimeOfDay, WeatherKind},
-        Entity, EntityAttributes, EntityHazard, EntityManager,
+        Entity, EntityAttributes, EntityHazard, EntityId, EntityManager, EntityMarker,
     },
+    event::{builder::GameEventBuilder, GameEventKind, GameEventTarget, SavedGameEvent},
     has_markers,
     hex::AxialHex,
     logs::{GameLog, GameLogBody},
-    mtch::{ActionCtx, MatchManager},
-    ServerCtx,
+    mtch::{
+        crew::PresenterAction,
+        drought::WATER_SOURCE_INTERVAL,
+        features::FeatureFlag,
+        moderation::regenerate_name,
+        naming::NameVoteResult,
+        poll::PollOption,
+        profile::{TickPhase, TickSummary, TickTimings},
+        requests::PendingOn,
+        stats::{PlayerStat, PlayerStatsBuffer, NEAR_DEATH_DISTRESS},
+        ActionCtx, MatchConfig, MatchManager, TickEvent,
+    },
+    rng::{audited_bool, RollKind},
+    Db, ServerCtx,
 };
 
 impl MatchManager {
     /// Perform one game tick
     /// When a match is on, this is called every second or so to update the state of the world
-    pub async fn perform_match_tick(&mut self, ctx: &ServerCtx) {
+    /// (returns a summary of how much work the tick was)
+    pub async fn perform_match_tick(&mut self, ctx: &ServerCtx) -> TickSummary {
+        self.tick_id += 1;
+        crate::rng::set_audit_tick(self.tick_id);
+        let tick_start = Instant::now();
+        let mut timings = TickTimings::start(self.tick_id);
+        let mut summary = TickSummary::default();
+
+        // Listen for the logs sent this tick so we can save them once its done
+        let mut log_rx = ctx.log_tx.subscribe();
+
+        let (renamed, poll_result) = {
+            let _span = info_span!("votes").entered();
+
+            // Close any name votes that are done, and rename the winners
+            // (before the snapshot, so nothing acting this tick undoes it)
+            let renamed = self.apply_name_votes();
+
+            // Same goes for the viewer poll
+            let poll_result = self.apply_poll_result(ctx);
+
+            (renamed, poll_result)
+        };
+
+        // Anything viewers asked for that has now been settled
+        let applied_requests = renamed
+            .iter()
+            .map(|result| PendingOn::NameVote(result.entity_id.clone()))
+            .chain(poll_result.is_some().then_some(PendingOn::Poll))
+            .flat_map(|on| self.requests.settle(&on))
+            .collect_vec();
+
+        // And renaming anything with a redacted name
+        self.apply_moderation_renames(ctx)
+            .instrument(info_span!("moderation"))
+            .await;
+        timings.end_phase(TickPhase::Votes);
+
+        let world_span = info_span!("world").entered();
+
         // Get all entities
         // this is our copy for performing this tick
         // NOTE: that entities wont be updated in here, so every entity kind of sees a frozen copy of the world
         //       until the next tick
         // let all_entities = self.entities.get_all_entities().cloned().collect_vec();
-        let entity_snapshot =
-            EntitySnapshot::new(self.entities.get_all_entities().cloned().collect_vec());
+        // NOTE: sorted so that entities act in the same order every tick (ids are time ordered)
+        let entity_snapshot = EntitySnapshot::new(
+            self.entities
+                .share_all_entities()
+                .sorted_by(|a, b| a.entity_id.cmp(&b.entity_id)),
+        );
         let entities_view = entity_snapshot.view();
 
         // Perform world updates
         // i.e next time/weather
-        let current_world_state = self.maybe_next_world_state(&entities_view, ctx);
+        let current_world_state = self.maybe_next_world_state(ctx);
+        self.world_history
+            .observe(&current_world_state, self.tick_id);
 
         // Do global effects
         // (i.e that dont target specific players at random, just stuff everywhere)
         self.resolve_global_world_effects(&entities_view, &current_world_state, ctx);
 
+        // Flooding (or drying out) after a lot of rain
+        if self.config.has_feature(FeatureFlag::Flooding) {
+            self.resolve_flooding(&entities_view, &current_world_state, ctx);
+        }
+
+        // Smaller water sources filling up with rain (or drying up without it)
+        if self.config.has_feature(FeatureFlag::Drought)
+            && self.tick_id.is_multiple_of(WATER_SOURCE_INTERVAL)
+        {
+            self.resolve_water_sources(&entities_view, &current_world_state, ctx);
+        }
+
+        // And maybe something really out of the ordinary
+        if self.config.has_feature(FeatureFlag::Wildcards) {
+            self.resolve_wildcard_events(&entities_view, &current_world_state, ctx);
+        }
+
+        // Clear away anything that has been lying around too long
+        self.despawn_expired_props(&entities_view, ctx);
+
+        // Bonds drift with how much time people have been spending together
+        if self.tick_id.is_multiple_of(BOND_DRIFT_INTERVAL) {
+            self.resolve_bond_drift(&entities_view);
+        }
+
+        world_span.exit();
+        timings.end_phase(TickPhase::World);
+
         // Prepare a view for the events this tick
         // and a buffer of pending events
         let events = self.events.view();
         let mut events_buffer = Vec::new();
+        let mut stats_buffer = PlayerStatsBuffer::default();
+
+        // (taken for the tick so the match can still be borrowed while acting)
+        let mut log_throttle = std::mem::take(&mut self.log_throttle);
+        log_throttle.forget_stale(self.tick_id);
 
         // Build the context which we pass to each resolution method
         let mut action_ctx = ActionCtx {
             entities: &entities_view,
             events: &events,
             log_tx: &ctx.log_tx,
+            log_throttle: &mut log_throttle,
             config: &self.config,
             world_state: &current_world_state,
+            tick_id: self.tick_id,
             events_buffer: &mut events_buffer,
+            stats_buffer: &mut stats_buffer,
         };
 
-        // Before any players act, the presenter/collector get to act
-        if let Some(presenter_entity) = entities_view
-            .all()
-            .find(|e| e.attributes.presenter.is_some())
         {
-            let mut rng = rand::rng();
-            let events = action_ctx
-                .events
-                .get_event_signals_for_entity(presenter_entity);
-            let action = presenter_entity.get_next_action_as_presenter(&action_ctx, events);
-            Self::resolve_actor_action(
-                &mut action_ctx,
-                &mut self.entities,
-                &mut rng,
-                presenter_entity.clone(),
-                action,
+            let span = info_span!(
+                "presenter",
+                segments = field::Empty,
+                announcements = field::Empty
+            )
+            .entered();
+
+            // Is it time for any segments of the show?
+            let players_remaining = entities_view
+                .all()
+                .filter(|e| has_markers!(e, Player) && !has_markers!(e, Escaped))
+                .count();
+            let segments =
+                self.segments
+                    .due(&self.config.show_script, self.tick_id, players_remaining);
+            span.record("segments", segments.len());
+            span.record(
+                "announcements",
+                renamed.len() + usize::from(poll_result.is_some()),
             );
-        } else {
-            warn!("No presenter.. uhh is present");
-        };
 
-        if let Some(collector_entity) = entities_view
-            .all()
-            .find(|e| e.attributes.collector.is_some())
+            // Before anyone acts, the presenter runs any segments and announces any new names
+            if let Some(presenter_entity) = self.entities.presenter().cloned() {
+                let mut rng = crate::rng::rng();
+                for segment in segments {
+                    let quote = segment.script(&entities_view, &self.config, self.tick_id);
+                    if let Err(err) = ctx.tick_tx.send(TickEvent::Announcement {
+                        segment,
+                        quote: quote.clone(),
+                    }) {
+                        warn!("Failed to send announcement: {err}");
+                    }
+                    Self::resolve_actor_action(
+                        &mut action_ctx,
+                        &mut self.entities,
+                        &mut rng,
+                        presenter_entity.clone(),
+                        PresenterAction::PresentSegment { quote }.into(),
+                    );
+                }
+
+                if let Some(option) = poll_result {
+                    Self::resolve_actor_action(
+                        &mut action_ctx,
+                        &mut self.entities,
+                        &mut rng,
+                        presenter_entity.clone(),
+                        PresenterAction::AnnouncePollResult(option).into(),
+                    );
+                }
+
+                for result in renamed {
+                    Self::resolve_actor_action(
+                        &mut action_ctx,
+                        &mut self.entities,
+                        &mut rng,
+                        presenter_entity.clone(),
+                        PresenterAction::AnnounceName {
+                            entity_id: result.entity_id,
+                            old_name: result.old_name,
+                            name: result.name,
+                        }
+                        .into(),
+                    );
+                }
+            } else {
+                warn!("No presenter.. uhh is present");
+            };
+        }
+        timings.end_phase(TickPhase::Presenter);
+
         {
-            let mut rng = rand::rng();
-            let events = action_ctx
-                .events
-                .get_event_signals_for_entity(collector_entity);
-            let action = collector_entity.get_next_action_as_collector(&action_ctx, events);
-            Self::resolve_actor_action(
-                &mut action_ctx,
-                &mut self.entities,
-                &mut rng,
-                collector_entity.clone(),
-                action,
-            );
-        } else {
-            warn!("No collector is present");
-        };
+            // Then everyone with a role gets to act (the crew first)
+            // (except anyone quarantined for breaking something, or anyone who got away)
+            let actors = entities_view
+                .all()
+                .filter(|e| !has_markers!(e, Quarantined) && !has_markers!(e, Escaped))
+                .filter_map(|e| Some((EntityRole::of(e)?, e)))
+                .sorted_by_key(|(role, _)| *role)
+                .collect_vec();
+            let _span = info_span!(
+                "actors",
+                actors = actors.len(),
+                decision_interval = self.scheduler.decision_interval()
+            )
+            .entered();
+            summary.entities_processed = actors.len();
+            let actors_start = Instant::now();
+            for (role, actor) in actors {
+                let _actor_span =
+                    debug_span!("actor", ?role, entity_id = %actor.entity_id).entered();
+                let mut rng = crate::rng::rng();
 
-        let players = entities_view.all().filter(|e| has_markers!(e, Player));
-        for player in players {
-            let mut rng = rand::rng();
+                // World acting on this player
+                if role == EntityRole::Player {
+                    let Some(mut player) = self.entities.get_entity(&actor.entity_id) else {
+                        warn!("NO GOOD!");
+                        continue;
+                    };
 
-            // World acting on this player
-            {
-                // let mut player = player.clone();
-                let Some(mut player) = self.entities.get_entity(&player.entity_id) else {
-                    warn!("NO GOOD!");
-                    continue;
-                };
+                    // (if this breaks, they're quarantined rather than taking the tick down with them)
+                    // (and if that leaves them in trouble, anyone nearby might come and help)
+                    let resolved = catch_panic(|| {
+                        self.resolve_world_effect_on_player(&mut player, &mut action_ctx);
+                        player.call_for_help(&mut action_ctx);
+                    });
+                    if let Err(reason) = resolved {
+                        Self::quarantine_entity(&mut self.entities, &actor.entity_id, reason, ctx);
+                        continue;
+                    }
+                    self.entities.upsert_entity(player).unwrap();
+                }
 
-                self.resolve_world_effect_on_player(&mut player, &mut action_ctx);
-                self.entities.upsert_entity(player).unwrap();
-            }
+                // Their action this tick
+                {
+                    // Get a new copy to preserve changes from earlier in the tick
+                    // Skipping this step if they were removed
+                    let Some(entity) = self.entities.get_entity(&actor.entity_id) else {
+                        warn!("NO GOOD!");
+                        continue;
+                    };
 
-            // Player actions in this hex
-            {
-                // Get a new copy to preserve changes from previous loop
-                // Skipping this step if they were removed
-                let Some(player) = self.entities.get_entity(&player.entity_id) else {
-                    warn!("NO GOOD!");
-                    continue;
-                };
+                    // If they have no hex yet, they actually cannot do anything
+                    // i.e they are banished
+                    if entity.attributes.hex.is_none() {
+                        continue;
+                    }
+
+                    // Do they get to decide what to do this tick?
+                    // (if nothing is pressing, they might have to wait)
+                    let has_events = action_ctx
+                        .events
+                        .get_event_signals_for_entity(&entity)
+                        .next()
+                        .is_some();
+                    if self
+                        .scheduler
+                        .should_decide(&entity, has_events, self.tick_id)
+                    {
+                        let resolved = catch_panic(|| {
+                            // What are they going to do?
+                            let action = {
+                                let mut events =
+                                    action_ctx.events.get_event_signals_for_entity(&entity);
+                                role.brain().decide(&entity, &action_ctx, &mut events)
+                            };
+                            let category = action.category();
 
-                // If the player has no hex yet, they actually cannot do anything
-                // i.e they are banished
-                if player.attributes.hex.is_none() {
+                            // Go update it
+                            Self::resolve_actor_action(
+                                &mut action_ctx,
+                                &mut self.entities,
+                                &mut rng,
+                                entity,
+                                action,
+                            );
+                            category
+                        });
+                        match resolved {
+                            Ok(category) => summary.record_action(category),
+                            Err(reason) => {
+                                Self::quarantine_entity(
+                                    &mut self.entities,
+                                    &actor.entity_id,
+                                    reason,
+                                    ctx,
+                                );
+                                continue;
+                            }
+                        }
+                    } else {
+                        Self::resolve_waiting_actor(
+                            &mut self.entities,
+                            entity,
+                            self.config.ruleset.motivator_rate,
+                        );
+                    }
+                }
+
+                // Did this tick bring them to death's door?
+                if role != EntityRole::Player {
                     continue;
                 }
+                if let Some(updated_player) = self.entities.get_entity(&actor.entity_id) {
+                    let distress = |e: &Entity| e.attributes.motivators.distress();
+                    if distress(actor) < NEAR_DEATH_DISTRESS
+                        && distress(&updated_player) >= NEAR_DEATH_DISTRESS
+                    {
+                        action_ctx.record_stat(&updated_player, PlayerStat::NearDeath);
+                    }
+                }
+            }
+            self.scheduler.adapt(actors_start.elapsed());
+        }
+        timings.end_phase(TickPhase::Actors);
+        self.log_throttle = log_throttle;
 
-                // What are they going to do?
-                let events = action_ctx.events.get_event_signals_for_entity(&player);
-                let action = player.get_next_action(&action_ctx, events);
+        // Any nicknames that have caught on stick
+        self.apply_nicknames(ctx);
 
-                // Go update it
-                Self::resolve_actor_action(
-                    &mut action_ctx,
-                    &mut self.entities,
-                    &mut rng,
-                    player,
-                    action,
-                );
+        // Nothing that happens in a dry run is kept (see `forecast`)
+        if self.dry_run {
+            self.entities.discard_changes();
+        } else {
+            async {
+                // Flush changes to entities to the DB and to clients
+                summary.mutations_flushed = self
+                    .entities
+                    .flush_changes(&ctx.tick_tx, self.tick_id, &ctx.db)
+                    .await
+                    .unwrap();
+
+                // Now that their changes are out, let viewers know their requests went through
+                for request_id in applied_requests {
+                    if let Err(err) = ctx.tick_tx.send(TickEvent::RequestApplied {
+                        request_id,
+                        tick_id: self.tick_id,
+                    }) {
+                        warn!("Failed to send applied request: {err}");
+                    }
+                }
+
+                // Every so often, save everything so the match history is quick to scrub through
+                if let Err(err) = self.maybe_save_snapshot(&ctx.db).await {
+                    warn!("Failed to save snapshot: {err:?}");
+                }
+
+                // And the logs too (so the match can be archived later)
+                let log_count = self.persist_logs(&mut log_rx, &ctx.db).await;
+                Span::current().record("logs", log_count);
+
+                // As well as the events raised
+                if let Err(err) = SavedGameEvent::save_all(
+                    &events_buffer,
+                    &self.config.match_id,
+                    self.tick_id,
+                    &ctx.db,
+                )
+                .await
+                {
+                    warn!("{err:?}");
+                }
+
+                // Add onto everyone's stats
+                if let Err(err) = stats_buffer.flush(&self.config.match_id, &ctx.db).await {
+                    warn!("{err:?}");
+                }
+
+                // And keep a record of the weather
+                if let Err(err) = self
+                    .world_history
+                    .flush(&self.config.match_id, &ctx.db)
+                    .await
+                {
+                    warn!("{err:?}");
+                }
+            }
+            .instrument(info_span!(
+                "flush",
+                mutations = field::Empty,
+                logs = field::Empty
+            ))
+            .await;
+        }
+        timings.end_phase(TickPhase::Flush);
+
+        // Figure out who is most worth watching now
+        info_span!("drama").in_scope(|| {
+            self.drama
+                .update(self.entities.get_all_entities(), &events_buffer);
+        });
+        timings.end_phase(TickPhase::Drama);
+
+        // Let any dashboards know how things are going
+        if let Some(telemetry) = self.maybe_gather_telemetry() {
+            if let Err(err) = ctx.tick_tx.send(TickEvent::Telemetry { telemetry }) {
+                warn!("Failed to send telemetry: {err}");
+            }
+        }
+
+        // Write out how long that all took, if we're profiling
+        if ctx.flags.profile_ticks.load(atomic::Ordering::Relaxed) {
+            if let Err(err) = timings.save(&self.config.match_id).await {
+                warn!("Failed to save tick timings: {err:?}");
             }
         }
 
-        // Flush changes to entities to the DB and to clients
-        self.entities
-            .flush_changes(&ctx.tick_tx, &ctx.db)
-            .await
-            .unwrap();
+        summary.events_raised = events_buffer.len();
+        summary.duration_ms = tick_start.elapsed().as_secs_f32() * 1000.0;
 
         // And empty out the event buffer
         // (by swapping it in)
         self.events.end_tick(events_buffer);
+
+        summary
+    }
+
+    /// Count down name votes, applying the winning names of any that closed
+    fn apply_name_votes(&mut self) -> Vec<NameVoteResult> {
+        let results = self.naming.tick();
+        for result in &results {
+            // It may be gone by now
+            let Some(mut entity) = self.entities.get_entity(&result.entity_id) else {
+                continue;
+            };
+            entity.name = result.name.clone();
+            self.entities.upsert_entity(entity).unwrap();
+        }
+        results
+    }
+
+    /// Give any player most of their acquaintances call by the same nickname that nickname
+    fn apply_nicknames(&mut self, ctx: &ServerCtx) {
+        let entities = self.entities.get_all_entities().cloned().collect_vec();
+        for player in entities.iter().filter(|e| has_markers!(e, Player)) {
+            let Some(epithet) = player.prevailing_epithet(entities.iter()) else {
+                continue;
+            };
+            let nickname = player.nicknamed(epithet);
+            if player.attributes.nickname.as_ref() == Some(&nickname) {
+                continue;
+            }
+
+            let mut player = player.clone();
+            ctx.log_tx
+                .send(GameLog::entity(
+                    &player,
+                    GameLogBody::EntityNicknamed {
+                        nickname: nickname.clone(),
+                    },
+                ))
+                .unwrap();
+            player.attributes.nickname = Some(nickname);
+            self.entities.upsert_entity(player).unwrap();
+        }
+    }
+
+    /// Take an entity that broke something while being resolved out of play
+    /// (it stays in the match, but doesn't act again until someone looks into it)
+    fn quarantine_entity(
+        entities: &mut EntityManager,
+        entity_id: &EntityId,
+        reason: String,
+        ctx: &ServerCtx,
+    ) {
+        error!("Quarantining {entity_id} after it panicked: {reason}");
+        if let Some(mut entity) = entities.get_entity(entity_id) {
+            if !has_markers!(entity, Quarantined) {
+                entity.markers.push(EntityMarker::Quarantined);
+            }
+            entities.upsert_entity(entity).unwrap();
+        }
+        if let Err(err) = ctx.tick_tx.send(TickEvent::EntityQuarantined {
+            entity_id: entity_id.clone(),
+            reason,
+        }) {
+            warn!("Failed to send quarantine event: {err}");
+        }
+    }
+
+    /// Give anything whose name was redacted a new one
+    async fn apply_moderation_renames(&mut self, ctx: &ServerCtx) {
+        let renames = ctx.moderation.write().await.take_pending_renames();
+        for entity_id in renames {
+            // It may be gone by now
+            let Some(mut entity) = self.entities.get_entity(&entity_id) else {
+                continue;
+            };
+            if let Err(err) = regenerate_name(&mut entity) {
+                warn!("Failed to rename {entity_id}: {err:?}");
+                continue;
+            }
+            self.entities.upsert_entity(entity).unwrap();
+        }
+    }
+
+    /// Count down the viewer poll, making the winning option happen if it closed
+    fn apply_poll_result(&mut self, ctx: &ServerCtx) -> Option<PollOption> {
+        let option = self.poll.tick()?;
+        let mut rng = crate::rng::rng();
+        let hex = AxialHex::random_in_bounds(&mut rng, self.config.world_radius as isize);
+        match option {
+            PollOption::Storm => {
+                if let Err(err) = self.set_weather(WeatherKind::LightningStorm, ctx) {
+                    warn!("Failed to whip up a storm: {err:?}");
+                }
+            }
+            PollOption::SupplyDrop => {
+                for entity in PropGenerator::SupplyDrop.generate_at(hex, &mut rng) {
+                    self.entities.upsert_entity(entity).unwrap();
+                }
+            }
+            PollOption::ReleasePredator => {
+                for entity in PropGenerator::Predator.generate_at(hex, &mut rng) {
+                    self.entities.upsert_entity(entity).unwrap();
+                }
+            }
+        }
+        Some(option)
+    }
+
+    /// Save every log that has been sent since we started listening
+    /// (returns how many were received)
+    async fn persist_logs(&self, log_rx: &mut broadcast::Receiver<GameLog>, db: &Db) -> usize {
+        let mut count = 0;
+        loop {
+            match log_rx.try_recv() {
+                Ok(log) => {
+                    count += 1;
+                    if let Err(err) = log.save(&self.config.match_id, db).await {
+                        warn!("{err:?}");
+                    }
+                }
+                Err(TryRecvError::Lagged(missed)) => {
+                    warn!("Missed persisting {missed} game log(s)");
+                }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        count
     }
 
     // Do global effects
@@ -165,11 +600,12 @@ impl MatchManager {
         current_world_state: &EntityWorld,
         ctx: &ServerCtx,
     ) {
-        let mut rng = rand::rng();
+        let mut rng = crate::rng::rng();
 
         // Lightning starting fires
-        if matches!(current_world_state.weather, WeatherKind::LightningStorm)
-            && rng.random_bool(0.05)
+        if self.config.ruleset.hazards
+            && matches!(current_world_state.weather, WeatherKind::LightningStorm)
+            && audited_bool(&mut rng, RollKind::LightningFire, None, 0.05)
         {
             let fire_entity = Entity {
                 entity_id: Entity::id(),
@@ -196,6 +632,92 @@ impl MatchManager {
         // Fire spreading
         // TODO
 
+        // Storms and fire wearing down shelters
+        let storm_damage_chance = match current_world_state.weather {
+            WeatherKind::LightningStorm | WeatherKind::Hurricane => 0.1,
+            WeatherKind::HeavyRain => 0.05,
+            _ => 0.0,
+        };
+        for shelter_entity in entities_view
+            .all()
+            .filter(|e| e.attributes.shelter.is_some() && e.attributes.hex.is_some())
+        {
+            let on_fire = entities_view
+                .in_hex(shelter_entity.attributes.hex.unwrap())
+                .any(|e| has_markers!(e, Fire));
+            let mut damage = 0.0;
+            if rng.random_bool(storm_damage_chance) {
+                damage += rng.random_range(0.0..0.05);
+            }
+            if on_fire {
+                damage += 0.05;
+            }
+            if damage == 0.0 {
+                continue;
+            }
+
+            let mut shelter_entity = shelter_entity.clone();
+            let shelter = shelter_entity.attributes.shelter.as_mut().unwrap();
+            shelter.durability -= damage;
+
+            // Still standing?
+            if shelter.durability > 0.0 {
+                self.entities.upsert_entity(shelter_entity).unwrap();
+                continue;
+            }
+
+            // Otherwise it falls apart, on top of anyone inside
+            ctx.log_tx
+                .send(GameLog::entity(
+                    &shelter_entity,
+                    GameLogBody::ShelterCollapse,
+                ))
+                .unwrap();
+            for occupant in entities_view.all().filter(|e| {
+                matches!(
+                    &e.attributes.focus,
+                    Some(ActorFocus::Sheltering { shelter_entity_id }) if *shelter_entity_id == shelter_entity.entity_id
+                )
+            }) {
+                let mut occupant = occupant.clone();
+                occupant.attributes.focus = Some(ActorFocus::Unfocused);
+                occupant
+                    .attributes
+                    .motivators
+                    .bump_scaled::<motivator::Hurt>(5.0);
+                ctx.log_tx
+                    .send(GameLog::entity_pair(
+                        &occupant,
+                        &shelter_entity,
+                        GameLogBody::EntityHurtByCollapse,
+                    ))
+                    .unwrap();
+                self.entities.upsert_entity(occupant).unwrap();
+            }
+            self.entities
+                .remove_entity(&shelter_entity.entity_id)
+                .unwrap();
+        }
+
+        // Fresh food going off
+        for food_entity in entities_view.all().filter(|e| {
+            e.attributes
+                .food
+                .as_ref()
+                .is_some_and(|f| f.fresh_for.is_some())
+        }) {
+            let mut food_entity = food_entity.clone();
+            let food = food_entity.attributes.food.as_mut().unwrap();
+            match food.fresh_for.as_mut().unwrap() {
+                0 => {
+                    food.spoil();
+                    food_entity.name = format!("Rotten {}", food_entity.name.to_lowercase());
+                }
+                fresh_for => *fresh_for -= 1,
+            }
+            self.entities.upsert_entity(food_entity).unwrap();
+        }
+
         // Rain putting out fires
         if current_world_state.weather.is_raining() {
             for entity in entities_view.all() {
@@ -206,18 +728,52 @@ impl MatchManager {
                 }
             }
         }
+
+        // Tracks fading away
+        // (rain washes them away much quicker)
+        let raining = current_world_state.weather.is_raining();
+        for track_entity in entities_view.all().filter(|e| e.attributes.track.is_some()) {
+            let mut track_entity = track_entity.clone();
+            let track = track_entity.attributes.track.as_mut().unwrap();
+            if track.fresh_for == 0 || (raining && rng.random_bool(TRACK_RAIN_WASH_CHANCE)) {
+                self.entities
+                    .remove_entity(&track_entity.entity_id)
+                    .unwrap();
+                continue;
+            }
+            track.fresh_for -= 1;
+            self.entities.upsert_entity(track_entity).unwrap();
+        }
     }
 
     fn resolve_world_effect_on_player(&self, player: &mut Entity, ctx: &mut ActionCtx) {
-        let mut rng = rand::rng();
+        let mut rng = crate::rng::rng();
 
         // Are they sheltering?
         // if so, some of the world stops acting on them
         let unfocused = matches!(player.attributes.focus, None | Some(ActorFocus::Unfocused));
         let sheltering = matches!(player.attributes.focus, Some(ActorFocus::Sheltering { .. }));
 
+        // Are they travelling with someone?
+        // there's safety in numbers, they keep an eye out and keep each other warm
+        let travelling = matches!(
+            player.attributes.focus,
+            Some(ActorFocus::TravelTogether { .. })
+        );
+
         // Is there a `hazard` entity at their hex?
-        if player.attributes.hex.is_some() && rng.random_bool(0.7) && unfocused {
+        // (unless nothing can hurt anyone this match)
+        let hazard_chance = if travelling { 0.35 } else { 0.7 };
+        if self.config.ruleset.hazards
+            && player.attributes.hex.is_some()
+            && audited_bool(
+                &mut rng,
+                RollKind::Hazard,
+                Some(&player.entity_id),
+                hazard_chance,
+            )
+            && (unfocused || travelling)
+        {
             for entity in self
                 .entities
                 .get_all_entities()
@@ -261,29 +817,22 @@ impl MatchManager {
             }
         }
 
-        // Maybe they are just hungry/thirsty?
-        if rng.random_bool(0.02) {
-            // TODO: slowly tune this
-            if rng.random_bool(0.5) {
-                player.attributes.motivators.bump::<motivator::Hunger>();
-            } else {
-                player.attributes.motivators.bump::<motivator::Thirst>();
-            }
-        }
-
         // Is it cold?
         let cold_chance_scale_from_time = ctx
             .world_state
             .time_of_day
             .current_temp_as_cold_proc_chance_scale();
         let cold_chance_scale_from_wind = ctx.world_state.weather.wind_proc_chance_scale();
-        let cold_chance = cold_chance_scale_from_time * cold_chance_scale_from_wind * 0.2;
+        let cold_chance = cold_chance_scale_from_time
+            * cold_chance_scale_from_wind
+            * (1.0 - player.warmth(ctx.entities))
+            * if travelling { 0.1 } else { 0.2 };
         if !sheltering && rng.random_bool(cold_chance as f64) {
             // TODO: prob need a way to find shelter or warm up huh
             player.attributes.motivators.bump::<motivator::Cold>();
 
             // Emit log
-            ctx.send_log(GameLog::entity(
+            ctx.send_log_throttled(GameLog::entity(
                 player,
                 GameLogBody::EntityColdBecauseOfTime,
             ));
@@ -304,7 +853,7 @@ impl MatchManager {
                     .motivators
                     .reduce_by::<motivator::Cold>(0.3);
 
-                ctx.send_log(GameLog::entity(
+                ctx.send_log_throttled(GameLog::entity(
                     player,
                     GameLogBody::EntityWarmBecauseOfTime,
                 ));
@@ -312,22 +861,31 @@ impl MatchManager {
         }
 
         // Is it raining?
-        let rain_chance_scale = ctx.world_state.weather.rain_proc_chance_scale();
+        let rain_chance_scale = ctx.world_state.weather.rain_proc_chance_scale()
+            * (1.0 - player.waterproofing(ctx.entities));
         if !sheltering && rng.random_bool((rain_chance_scale as f64) * 0.1) {
             // TODO: prob need a way to find shelter or warm up huh
             player.attributes.motivators.bump::<motivator::Saturation>();
 
             // Emit log
-            ctx.send_log(GameLog::entity(
+            ctx.send_log_throttled(GameLog::entity(
                 player,
                 GameLogBody::EntitySaturatedBecauseOfRain,
             ));
         }
 
         // Lightning strike?
-        if !sheltering && matches!(ctx.world_state.weather, WeatherKind::LightningStorm) {
+        if !sheltering
+            && self.config.ruleset.hazards
+            && matches!(ctx.world_state.weather, WeatherKind::LightningStorm)
+        {
             // Quite rare to be direct hit
-            if rng.random_bool(0.0005) {
+            if audited_bool(
+                &mut rng,
+                RollKind::LightningStrike,
+                Some(&player.entity_id),
+                0.0005,
+            ) {
                 // Very damaging
                 player
                     .attributes
@@ -335,20 +893,40 @@ impl MatchManager {
                     .bump_scaled::<motivator::Hurt>(20.0);
 
                 // Emit log
-                ctx.send_log(GameLog::entity(player, GameLogBody::EntityHitByLightning))
+                ctx.send_log(GameLog::entity(player, GameLogBody::EntityHitByLightning));
+
+                // Anyone who sees that will be talking about it
+                if let Some(hex) = player.attributes.hex {
+                    GameEventBuilder::new()
+                        .of_kind(GameEventKind::Feat {
+                            entity_id: player.entity_id.clone(),
+                            epithet: Epithet::Sparky,
+                        })
+                        .targets(GameEventTarget::HexSurrounds(hex))
+                        .with_sense(Characteristic::Vision, 1)
+                        .add(ctx);
+                }
             }
         }
 
-        // Or tired?
-        // (more at night)
-        if rng.random_bool(0.005)
-            || (ctx.world_state.time_of_day == TimeOfDay::Night && rng.random_bool(0.01))
-        {
+        // Anyone caught out in a storm will be talking about it for a while
+        if !sheltering && ctx.world_state.weather.is_rough() {
+            player
+                .memes_mut()
+                .insert(Meme::Witnessed(Witnessed::Weather {
+                    weather: ctx.world_state.weather.clone(),
+                }));
+        }
+
+        // Extra tired at night?
+        // (they get tired anyway, see `BehaviorConfig::homeostasis`)
+        if ctx.world_state.time_of_day == TimeOfDay::Night && rng.random_bool(0.01) {
             player.attributes.motivators.bump::<motivator::Tiredness>();
         }
     }
 
     fn resolve_action_side_effect(
+        config: &MatchConfig,
         entities: &mut EntityManager,
         rng: &mut impl rand::Rng,
         entity: Entity,
@@ -360,9 +938,18 @@ impl MatchManager {
                 entities.remove_entity(&entity.entity_id).unwrap();
 
                 // Add a corpse
-                entities
-                    .upsert_entity(generate_corpse(rng, entity))
-                    .unwrap();
+                // (everything they were carrying stays with them, clothes, personal effects and all)
+                let mut carried_ids = entity.relations.inventory().cloned().collect_vec();
+                for effect in generate_personal_effects(rng, &entity) {
+                    carried_ids.push(effect.entity_id.clone());
+                    entities.upsert_entity(effect).unwrap();
+                }
+                if config.ruleset.spirits {
+                    entities.upsert_entity(generate_spirit(&entity)).unwrap();
+                }
+                let mut corpse = generate_corpse(rng, entity);
+                corpse.relations.inventory_mut().extend(carried_ids);
+                entities.upsert_entity(corpse).unwrap();
             }
             Some(ActorActionSideEffect::RemoveOther(entity_id)) => {
                 entities.remove_entity(&entity_id).unwrap();
@@ -392,12 +979,334 @@ impl MatchManager {
                 entities.upsert_entity(other_entity).unwrap();
                 entities.upsert_entity(entity).unwrap();
             }
+            Some(ActorActionSideEffect::TakeFromOther { entity_id, item_id }) => {
+                // They wont be happy about it
+                let mut other_entity = entities.get_entity(&entity_id).unwrap();
+                other_entity.relations.inventory_mut().remove(&item_id);
+                other_entity
+                    .relations
+                    .decrease_associate_bond_by(&entity.entity_id, 0.2);
+                entities.upsert_entity(other_entity).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::PutInOther { entity_id, item_id }) => {
+                let mut other_entity = entities.get_entity(&entity_id).unwrap();
+                other_entity.relations.inventory_mut().insert(item_id);
+                entities.upsert_entity(other_entity).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::TakeOutOfOther { entity_id, item_id }) => {
+                let mut other_entity = entities.get_entity(&entity_id).unwrap();
+                other_entity.relations.inventory_mut().remove(&item_id);
+                entities.upsert_entity(other_entity).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::ExchangeItems {
+                entity_id,
+                gained,
+                lost,
+            }) => {
+                // A fair trade, they'll like us a little more
+                let mut other_entity = entities.get_entity(&entity_id).unwrap();
+                other_entity.relations.inventory_mut().remove(&lost);
+                other_entity.relations.inventory_mut().insert(gained);
+                other_entity
+                    .relations
+                    .increase_associate_bond(&entity.entity_id);
+                entities.upsert_entity(other_entity).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::ClaimOther(entity_id)) => {
+                let mut other_entity = entities.get_entity(&entity_id).unwrap();
+                if let Some(shelter) = other_entity.attributes.shelter.as_mut() {
+                    shelter.claimed_by = Some(entity.entity_id.clone());
+                }
+                entities.upsert_entity(other_entity).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::DrinkFromOther(entity_id)) => {
+                // (it may have dried up or been drunk dry already this tick)
+                if let Some(mut other_entity) = entities.get_entity(&entity_id) {
+                    if let Some(capacity) = other_entity
+                        .attributes
+                        .water_source
+                        .as_mut()
+                        .and_then(|w| w.capacity.as_mut())
+                    {
+                        capacity.remaining = (capacity.remaining - 1.0).max(0.0);
+                    }
+                    entities.upsert_entity(other_entity).unwrap();
+                }
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::RepairOther {
+                entity_id,
+                material_id,
+                amount,
+            }) => {
+                let mut other_entity = entities.get_entity(&entity_id).unwrap();
+                if let Some(shelter) = other_entity.attributes.shelter.as_mut() {
+                    shelter.durability = (shelter.durability + amount).min(1.0);
+                }
+                entities.upsert_entity(other_entity).unwrap();
+
+                // The material is used up
+                entities.remove_entity(&material_id).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::LandThrown { item_id, hex, hit }) => {
+                // Whoever it hit is going to feel that
+                if let Some((hit_id, hurt)) = hit {
+                    if let Some(mut hit_entity) = entities.get_entity(&hit_id) {
+                        hit_entity
+                            .attributes
+                            .motivators
+                            .bump_scaled::<motivator::Hurt>(hurt);
+                        hit_entity
+                            .relations
+                            .decrease_associate_bond_by(&entity.entity_id, 0.2);
+
+                        // Predators shrug most of it off, but go down eventually
+                        // (leaving plenty of meat behind)
+                        if hit_entity.brought_down_by(hurt) {
+                            entities.remove_entity(&hit_id).unwrap();
+                            for meat in hit_entity.predator_carcass() {
+                                entities.upsert_entity(meat).unwrap();
+                            }
+                        } else {
+                            if let Some(predator) = hit_entity.attributes.predator.as_mut() {
+                                predator.wounds += hurt;
+                            }
+                            entities.upsert_entity(hit_entity).unwrap();
+                        }
+                    }
+                }
+
+                // And it lands where it was thrown
+                if let Some(mut item_entity) = entities.get_entity(&item_id) {
+                    item_entity.attributes.hex = Some(hex);
+                    entities.upsert_entity(item_entity).unwrap();
+                }
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::HurtOther { entity_id, amount }) => {
+                if let Some(mut other_entity) = entities.get_entity(&entity_id) {
+                    other_entity
+                        .attributes
+                        .motivators
+                        .bump_scaled::<motivator::Hurt>(amount);
+                    entities.upsert_entity(other_entity).unwrap();
+                }
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::HealOther {
+                entity_id,
+                item_id,
+                amount,
+            }) => {
+                // Heal them, they'll appreciate that
+                let mut other_entity = entities.get_entity(&entity_id).unwrap();
+                other_entity
+                    .attributes
+                    .motivators
+                    .reduce_by::<motivator::Hurt>(amount);
+                other_entity
+                    .relations
+                    .increase_associate_bond_by(&entity.entity_id, 0.1);
+                entities.upsert_entity(other_entity).unwrap();
+
+                // The item is used up
+                entities.remove_entity(&item_id).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::FitPodPart {
+                pod_id,
+                part_id,
+                part,
+            }) => {
+                // The part is fitted, so its no longer its own thing
+                entities.remove_entity(&part_id).unwrap();
+
+                // And the pod is one step closer (or gone, if someone just flew off in it)
+                if let Some(mut pod_entity) = entities.get_entity(&pod_id) {
+                    if let Some(escape_pod) = pod_entity.attributes.escape_pod.as_mut() {
+                        escape_pod.missing.retain(|p| *p != part);
+                    }
+                    if pod_entity
+                        .attributes
+                        .escape_pod
+                        .as_ref()
+                        .is_some_and(|pod| pod.missing.is_empty())
+                    {
+                        entities.remove_entity(&pod_id).unwrap();
+                    } else {
+                        entities.upsert_entity(pod_entity).unwrap();
+                    }
+                }
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::SpawnOther(other_entity)) => {
+                entities.upsert_entity(*other_entity).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::LightFire { material_id, fire }) => {
+                // The material goes up in smoke
+                entities.remove_entity(&material_id).unwrap();
+                entities.upsert_entity(*fire).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::EntertainOthers { entity_ids }) => {
+                // A good story cheers everyone up, and they'll think a bit better of whoever told it
+                for entity_id in entity_ids {
+                    let Some(mut other_entity) = entities.get_entity(&entity_id) else {
+                        continue;
+                    };
+                    let motivators = &mut other_entity.attributes.motivators;
+                    motivators.reduce_by::<motivator::Sadness>(STORY_CHEER);
+                    motivators.reduce_by::<motivator::Boredom>(STORY_CHEER);
+                    other_entity
+                        .relations
+                        .increase_associate_bond_by(&entity.entity_id, 0.05);
+                    entities.upsert_entity(other_entity).unwrap();
+                }
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::BuildRaft { material_ids, raft }) => {
+                // The material is used up
+                for material_id in material_ids {
+                    entities.remove_entity(&material_id).unwrap();
+                }
+                entities.upsert_entity(*raft).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::BoardRaft { raft_id, claim }) => {
+                // Someone else might have taken the last place (or set off in it) first
+                let mut entity = entity;
+                let aboard = entities
+                    .get_all_entities()
+                    .filter(|e| e.entity_id != entity.entity_id && is_aboard(e, &raft_id))
+                    .count();
+                let raft_entity = entities.get_entity(&raft_id).filter(|e| {
+                    e.attributes.hex == entity.attributes.hex
+                        && e.attributes
+                            .raft
+                            .as_ref()
+                            .is_some_and(|raft| aboard < raft.capacity)
+                });
+                match raft_entity {
+                    None => {
+                        entity.attributes.focus = Some(ActorFocus::Unfocused);
+                    }
+
+                    // If we're taking it, it's ours now (and whoever had it won't be happy about that)
+                    Some(mut raft_entity) if claim => {
+                        let raft = raft_entity.attributes.raft.as_mut().unwrap();
+                        if let Some(mut owner) = raft
+                            .owner
+                            .as_ref()
+                            .filter(|owner| **owner != entity.entity_id)
+                            .and_then(|owner| entities.get_entity(owner))
+                        {
+                            owner
+                                .relations
+                                .decrease_associate_bond_by(&entity.entity_id, 0.2);
+                            entities.upsert_entity(owner).unwrap();
+                        }
+                        raft.owner = Some(entity.entity_id.clone());
+                        entities.upsert_entity(raft_entity).unwrap();
+                    }
+                    Some(_) => {}
+                }
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::MoveRaft { raft_id, hex }) => {
+                // Everyone else aboard comes along too
+                let aboard = entities
+                    .get_all_entities()
+                    .filter(|e| e.entity_id != entity.entity_id && is_aboard(e, &raft_id))
+                    .map(|e| e.entity_id.clone())
+                    .collect_vec();
+                for other_id in aboard.iter().chain(std::iter::once(&raft_id)) {
+                    if let Some(mut other_entity) = entities.get_entity(other_id) {
+                        other_entity.attributes.hex = Some(hex);
+                        entities.upsert_entity(other_entity).unwrap();
+                    }
+                }
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::CapsizeRaft(raft_id)) => {
+                // Everyone else aboard goes in too, and the raft is lost
+                let aboard = entities
+                    .get_all_entities()
+                    .filter(|e| e.entity_id != entity.entity_id && is_aboard(e, &raft_id))
+                    .map(|e| e.entity_id.clone())
+                    .collect_vec();
+                for other_id in aboard {
+                    if let Some(mut other_entity) = entities.get_entity(&other_id) {
+                        other_entity.attributes.focus = Some(ActorFocus::Unfocused);
+                        let motivators = &mut other_entity.attributes.motivators;
+                        motivators.bump_scaled::<motivator::Saturation>(2.0);
+                        motivators.bump_scaled::<motivator::Hurt>(CAPSIZE_HURT);
+                        entities.upsert_entity(other_entity).unwrap();
+                    }
+                }
+                entities.remove_entity(&raft_id).unwrap();
+                entities.upsert_entity(entity).unwrap();
+            }
+            Some(ActorActionSideEffect::EmptyContainer(entity_id, hex)) => {
+                // Take everything out of the container
+                let mut container_entity = entities.get_entity(&entity_id).unwrap();
+                let contents: Vec<_> = container_entity.relations.inventory_mut().drain().collect();
+                entities.upsert_entity(container_entity).unwrap();
+
+                // And place it all in the world
+                for content_id in contents {
+                    if let Some(mut content_entity) = entities.get_entity(&content_id) {
+                        content_entity.attributes.hex = Some(hex);
+                        entities.upsert_entity(content_entity).unwrap();
+                    }
+                }
+
+                entities.upsert_entity(entity).unwrap();
+            }
             None => {
                 entities.upsert_entity(entity).unwrap();
             }
         }
     }
 
+    /// Let everyone's bonds drift (see `brain::bonding`)
+    fn resolve_bond_drift(&mut self, entities_view: &EntityView) {
+        for entity in entities_view.all() {
+            if entity.relations.associates().next().is_none() {
+                continue;
+            }
+
+            // It may be gone by now (e.g washed away)
+            let mut drifted = entity.clone();
+            if self.entities.get_entity(&entity.entity_id).is_none()
+                || !drifted.drift_bonds(entities_view, self.tick_id)
+            {
+                continue;
+            }
+            self.entities
+                .mutate(&entity.entity_id, |e| e.relations = drifted.relations)
+                .unwrap();
+        }
+    }
+
+    /// An actor waiting for their turn to decide carries on as they were
+    /// (a player's motivators still drift, but they dont count as idle)
+    fn resolve_waiting_actor(entities: &mut EntityManager, mut entity: Entity, rate_scale: f32) {
+        if has_markers!(entity, Player) {
+            entity
+                .attributes
+                .motivators
+                .apply_homeostasis(&behavior(), rate_scale, false);
+            entities.upsert_entity(entity).unwrap();
+        }
+    }
+
     fn resolve_actor_action(
         ctx: &mut ActionCtx,
         entities: &mut EntityManager,
@@ -407,19 +1316,81 @@ impl MatchManager {
     ) {
         let result = entity.resolve_action(action, ctx);
 
-        // Players get bored when they dont do anything
+        // A player's motivators drift on their own (i.e getting bored when they dont do anything)
         if has_markers!(entity, Player) {
-            if matches!(result, ActorActionResult::NoEffect) {
-                entity
-                    .attributes
-                    .motivators
-                    .bump_scaled::<motivator::Boredom>(2.0);
-            } else {
+            let idle = matches!(result, ActorActionResult::NoEffect);
+            entity.attributes.motivators.apply_homeostasis(
+                &behavior(),
+                ctx.config.ruleset.motivator_rate,
+                idle,
+            );
+            if !idle {
                 entity.attributes.motivators.clear::<motivator::Boredom>();
             }
+
+            // and those still grieving can't shake it off just yet
+            entity.keep_grieving(ctx.tick_id);
         }
 
         let side_effect = result.side_effect();
-        Self::resolve_action_side_effect(entities, rng, entity, side_effect);
+        Self::resolve_action_side_effect(ctx.config, entities, rng, entity, side_effect);
+    }
+}
+
+/// Run something that might panic, catching it so it can be dealt with
+/// (returns what it panicked with if it did)
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_owned())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        entity::brain::motivator,
+        event::GameEventKind,
+        hex::AxialHex,
+        logs::GameLogBody,
+        mtch::test_match::{test_player, TestMatch},
+    };
+
+    #[tokio::test]
+    async fn test_death_leaves_corpse_and_spirit() {
+        let builder = TestMatch::builder();
+        let mut player = test_player("Doomed", AxialHex::ZERO);
+        player
+            .attributes
+            .motivators
+            .bump_scaled::<motivator::Hurt>(1000.0);
+        let player_id = player.entity_id.clone();
+
+        let mut test_match = builder.with_entity(player).build().await;
+        let died = test_match
+            .tick_until(10, |m| m.entity(&player_id).is_none())
+            .await;
+
+        assert!(died, "Player never died");
+        assert!(test_match.has_log_by(&player_id, |body| matches!(body, GameLogBody::EntityDeath)));
+        assert!(test_match.events.iter().any(|event| matches!(
+            event.kind(),
+            GameEventKind::Death { entity_id } if *entity_id == player_id
+        )));
+        assert!(test_match.manager.entities.get_all_entities().any(|e| e
+            .attributes
+            .corpse
+            .as_ref()
+            == Some(&player_id)));
+
+        // and their spirit lingers
+        assert!(test_match.manager.entities.get_all_entities().any(|e| e
+            .attributes
+            .spirit
+            .as_ref()
+            .is_some_and(|s| s.player == player_id)));
     }
 }
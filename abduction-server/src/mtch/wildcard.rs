@@ -0,0 +1,211 @@
+//! Rare "wildcard" events that shake up long matches
+//!
+//! Each biome has a table of events and how likely they are each tick (see `BehaviorConfig`), so
+//! they can be tuned without a rebuild. Every tick we roll for each event in the match's biome and
+//! apply whatever comes up to the world directly, without going through anyone's brain.
+
+use rand::{seq::IndexedRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    behavior::behavior,
+    create_markers,
+    entity::{
+        brain::motivator,
+        snapshot::EntityView,
+        world::{EntityWorld, TimeOfDay},
+        Entity, EntityAttributes, EntityHazard,
+    },
+    has_markers,
+    hex::AxialHex,
+    location::Biome,
+    logs::{GameLog, GameLogBody},
+    ServerCtx,
+};
+
+use super::MatchManager;
+
+/// How many hexes a meteor shower hits
+const METEOR_IMPACTS: std::ops::RangeInclusive<usize> = 2..=4;
+
+/// How hurt someone gets when a meteor lands on them
+const METEOR_HURT: f32 = 4.0;
+
+/// How hurt someone gets when they're trampled
+const STAMPEDE_HURT: f32 = 3.0;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum WildcardEvent {
+    /// Meteors land in a few hexes, starting fires and hurting anyone there
+    MeteorShower,
+
+    /// Lights in the night sky calm everyone down (only happens at night)
+    Aurora,
+
+    /// A herd of animals charges through a line of hexes, trampling anyone in the way
+    Stampede,
+}
+
+/// How likely some event is to happen each tick
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[qubit::ts]
+pub struct WildcardChance {
+    pub event: WildcardEvent,
+    pub chance: f64,
+}
+
+/// The events that could happen in each biome, unless configured otherwise
+pub fn default_wildcard_tables() -> Vec<(Biome, Vec<WildcardChance>)> {
+    use WildcardEvent::*;
+    let chance = |event, chance| WildcardChance { event, chance };
+    vec![(
+        Biome::Green,
+        vec![
+            chance(MeteorShower, 0.0005),
+            chance(Aurora, 0.002),
+            chance(Stampede, 0.001),
+        ],
+    )]
+}
+
+impl MatchManager {
+    /// Roll for (and apply) any wildcard events this tick
+    pub fn resolve_wildcard_events(
+        &mut self,
+        entities_view: &EntityView,
+        world_state: &EntityWorld,
+        ctx: &ServerCtx,
+    ) {
+        let mut rng = crate::rng::rng();
+
+        // NOTE: every match is in the green biome for now
+        let behavior = behavior();
+        let Some(table) = behavior.wildcards.get(&Biome::Green) else {
+            return;
+        };
+
+        for WildcardChance { event, chance } in table {
+            if !rng.random_bool(chance.clamp(0.0, 1.0)) {
+                continue;
+            }
+            let hazards = self.config.ruleset.hazards;
+            match event {
+                WildcardEvent::MeteorShower if hazards => self.meteor_shower(entities_view, ctx),
+                WildcardEvent::MeteorShower => {}
+                WildcardEvent::Aurora if world_state.time_of_day == TimeOfDay::Night => {
+                    self.aurora(entities_view, ctx)
+                }
+                WildcardEvent::Aurora => {}
+                WildcardEvent::Stampede if hazards => self.stampede(entities_view, ctx),
+                WildcardEvent::Stampede => {}
+            }
+        }
+    }
+
+    fn meteor_shower(&mut self, entities_view: &EntityView, ctx: &ServerCtx) {
+        let mut rng = crate::rng::rng();
+        ctx.log_tx
+            .send(GameLog::global(GameLogBody::MeteorShower))
+            .unwrap();
+
+        let radius = self.config.world_radius as isize;
+        for _ in 0..rng.random_range(METEOR_IMPACTS) {
+            let hex = AxialHex::random_in_bounds(&mut rng, radius);
+            let meteorite = Entity {
+                entity_id: Entity::id(),
+                name: "Smouldering meteorite".into(),
+                markers: create_markers!(Fire, Inspectable),
+                attributes: EntityAttributes {
+                    hex: Some(hex),
+                    hazard: Some(EntityHazard { damage: 1 }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            self.hurt_players_in_hex(entities_view, hex, METEOR_HURT, ctx, |player| {
+                GameLog::entity_pair(player, &meteorite, GameLogBody::EntityHitByMeteor)
+            });
+            self.entities.upsert_entity(meteorite).unwrap();
+        }
+    }
+
+    fn aurora(&mut self, entities_view: &EntityView, ctx: &ServerCtx) {
+        ctx.log_tx
+            .send(GameLog::global(GameLogBody::Aurora))
+            .unwrap();
+
+        for player in entities_view.all().filter(|e| has_markers!(e, Player)) {
+            let Some(mut player) = self.entities.get_entity(&player.entity_id) else {
+                continue;
+            };
+            player.attributes.motivators.clear::<motivator::Sadness>();
+            player.attributes.motivators.clear::<motivator::Boredom>();
+            self.entities.upsert_entity(player).unwrap();
+        }
+    }
+
+    fn stampede(&mut self, entities_view: &EntityView, ctx: &ServerCtx) {
+        let mut rng = crate::rng::rng();
+        let radius = self.config.world_radius as isize;
+
+        // Charge from somewhere in some direction until they reach the edge of the world
+        let start = AxialHex::random_in_bounds(&mut rng, radius);
+        let direction = *AxialHex::ZERO.neighbours().choose(&mut rng).unwrap();
+        ctx.log_tx
+            .send(GameLog {
+                hex: Some(start),
+                ..GameLog::global(GameLogBody::AnimalStampede)
+            })
+            .unwrap();
+
+        let mut hex = start;
+        while hex.within_bounds(radius) {
+            self.hurt_players_in_hex(entities_view, hex, STAMPEDE_HURT, ctx, |player| {
+                GameLog::entity(player, GameLogBody::EntityTrampled)
+            });
+            hex += direction;
+        }
+    }
+
+    /// Hurt every player in some hex
+    fn hurt_players_in_hex(
+        &mut self,
+        entities_view: &EntityView,
+        hex: AxialHex,
+        hurt: f32,
+        ctx: &ServerCtx,
+        log: impl Fn(&Entity) -> GameLog,
+    ) {
+        for player in entities_view
+            .in_hex(hex)
+            .filter(|e| has_markers!(e, Player))
+        {
+            let Some(mut player) = self.entities.get_entity(&player.entity_id) else {
+                continue;
+            };
+            player
+                .attributes
+                .motivators
+                .bump_scaled::<motivator::Hurt>(hurt);
+            ctx.log_tx.send(log(&player)).unwrap();
+            self.entities.upsert_entity(player).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_green_biome_has_wildcards() {
+        let behavior = behavior();
+        let table = behavior.wildcards.get(&Biome::Green).unwrap();
+        assert!(!table.is_empty());
+        assert!(table
+            .iter()
+            .all(|wildcard| (0.0..=1.0).contains(&wildcard.chance)));
+    }
+}
@@ -0,0 +1,153 @@
+//! Keeping a record of the weather and time of day over a match
+//!
+//! The world entity only ever holds how things are now, and its changes are buried among every
+//! other mutation, so working out what the weather was at some point means replaying the match up to
+//! it. Instead, every tick the world changes (a new time of day or new weather) is saved to the
+//! `world_history` table, so it can be looked up on its own after the fact (see `get_weather_history`).
+
+use anyhow::Context;
+use serde::Serialize;
+use sqlx::query_file;
+
+use crate::{
+    entity::world::{EntityWorld, TimeOfDay, WeatherKind},
+    Db,
+};
+
+use super::{MatchId, TickId};
+
+/// How the world was from some tick on
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[qubit::ts]
+pub struct WorldTransition {
+    /// The tick it changed during
+    pub tick_id: TickId,
+    pub day: usize,
+    pub time_of_day: TimeOfDay,
+    pub weather: WeatherKind,
+}
+
+impl WorldTransition {
+    /// Every change to the world over a match, in order
+    pub async fn load_for_match(match_id: &MatchId, db: &Db) -> anyhow::Result<Vec<Self>> {
+        let rows = query_file!("queries/get_world_history.sql", match_id)
+            .fetch_all(db)
+            .await
+            .context("Failed to load world history")?;
+        rows.into_iter()
+            .map(|row| {
+                Ok(Self {
+                    tick_id: row.tick_id as TickId,
+                    day: row.day as usize,
+                    time_of_day: row
+                        .time_of_day
+                        .parse()
+                        .with_context(|| format!("Unknown time of day {}", row.time_of_day))?,
+                    weather: row
+                        .weather
+                        .parse()
+                        .with_context(|| format!("Unknown weather {}", row.weather))?,
+                })
+            })
+            .collect()
+    }
+
+    async fn save(&self, match_id: &MatchId, db: &Db) -> anyhow::Result<()> {
+        let tick_id = self.tick_id as i64;
+        let day = self.day as i64;
+        let time_of_day = self.time_of_day.to_string();
+        let weather = self.weather.to_string();
+        query_file!(
+            "queries/add_world_history.sql",
+            match_id,
+            tick_id,
+            day,
+            time_of_day,
+            weather
+        )
+        .execute(db)
+        .await
+        .context("Failed to save world history")?;
+        Ok(())
+    }
+}
+
+/// Keeps track of how the world last was, to notice when it changes
+#[derive(Debug, Clone, Default)]
+pub struct WorldHistory {
+    /// The last change (saved or not)
+    last: Option<WorldTransition>,
+
+    /// A change that hasn't been saved yet
+    pending: Option<WorldTransition>,
+}
+
+impl WorldHistory {
+    /// Pick up where a match left off
+    pub async fn load(match_id: &MatchId, db: &Db) -> anyhow::Result<Self> {
+        Ok(Self {
+            last: WorldTransition::load_for_match(match_id, db).await?.pop(),
+            pending: None,
+        })
+    }
+
+    /// Note how the world is as of some tick, and whether that's a change worth saving
+    /// (the hour going by doesn't count, only a new time of day or new weather)
+    pub fn observe(&mut self, world: &EntityWorld, tick_id: TickId) -> bool {
+        let unchanged = self.last.as_ref().is_some_and(|last| {
+            last.time_of_day == world.time_of_day && last.weather == world.weather
+        });
+        if unchanged {
+            return false;
+        }
+
+        let transition = WorldTransition {
+            tick_id,
+            day: world.day,
+            time_of_day: world.time_of_day.clone(),
+            weather: world.weather.clone(),
+        };
+        self.last = Some(transition.clone());
+        self.pending = Some(transition);
+        true
+    }
+
+    /// Save the latest change, if it hasn't been already
+    pub async fn flush(&mut self, match_id: &MatchId, db: &Db) -> anyhow::Result<()> {
+        match self.pending.take() {
+            Some(transition) => transition.save(match_id, db).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_only_changes_are_kept() {
+        let mut world = EntityWorld::default();
+        let mut history = WorldHistory::default();
+
+        // How the world starts out is always worth knowing
+        assert!(history.observe(&world, 1));
+
+        // but not the hours going by
+        world.hour += 1;
+        assert!(!history.observe(&world, 2));
+
+        world.weather = WeatherKind::Overcast;
+        world.time_of_day = TimeOfDay::Night;
+        assert!(history.observe(&world, 3));
+        assert_eq!(
+            history.pending,
+            Some(WorldTransition {
+                tick_id: 3,
+                day: world.day,
+                time_of_day: TimeOfDay::Night,
+                weather: WeatherKind::Overcast,
+            })
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Versioning what goes over the wire
+//!
+//! Clients (the site, and anyone else watching) talk to the server through the qubit handlers in
+//! `main`, and mostly listen to the `TickEvent`s and `GameLog`s they stream. Those types change as
+//! the game does, so the protocol has a version, `PROTOCOL_VERSION`, which is bumped whenever a change
+//! would break a client built against the previous one (removing or renaming a field or kind, or
+//! changing what one means). Adding new kinds of event/log or new optional fields doesn't need a bump,
+//! clients are expected to ignore anything they don't know about.
+//!
+//! Clients say which version they speak when they subscribe to tick events, and the server lets them
+//! know which it speaks (in the `Heartbeat` every stream starts with, on `StartOfMatch`, and from
+//! `get_protocol`). Clients older than `MIN_PROTOCOL_VERSION` are turned away with a
+//! `ProtocolUnsupported` event. Any others are served, though older (or unversioned) clients are
+//! warned about, and should update when they see the server has moved on.
+
+use futures::{
+    stream::{self, BoxStream},
+    Stream, StreamExt,
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::mtch::TickEvent;
+
+/// The version of the protocol this server speaks
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The oldest version of the protocol this server will still serve
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Which version of the protocol the server speaks (see `get_protocol`)
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct ProtocolInfo {
+    pub version: u32,
+
+    /// The oldest version still served
+    pub min_supported_version: u32,
+}
+
+impl ProtocolInfo {
+    pub fn current() -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            min_supported_version: MIN_PROTOCOL_VERSION,
+        }
+    }
+}
+
+/// Whether a client speaking some version of the protocol can be served
+/// (none -> the client is from before the protocol was versioned, which is the same as version 1)
+pub fn is_supported(client_version: Option<u32>) -> bool {
+    client_version.unwrap_or(1) >= MIN_PROTOCOL_VERSION
+}
+
+/// Start a stream of tick events for a client speaking some version of the protocol
+/// (turning it away if it's too old to understand us)
+pub fn greet(
+    client_version: Option<u32>,
+    events: impl Stream<Item = TickEvent> + Send + 'static,
+) -> BoxStream<'static, TickEvent> {
+    if !is_supported(client_version) {
+        warn!("Turned away a client speaking protocol version {client_version:?}");
+        return stream::iter([TickEvent::ProtocolUnsupported {
+            protocol_version: PROTOCOL_VERSION,
+            min_supported_version: MIN_PROTOCOL_VERSION,
+        }])
+        .boxed();
+    }
+    if client_version != Some(PROTOCOL_VERSION) {
+        warn!("Serving a client speaking protocol version {client_version:?}, rather than {PROTOCOL_VERSION}");
+    }
+
+    stream::iter([TickEvent::Heartbeat {
+        protocol_version: PROTOCOL_VERSION,
+    }])
+    .chain(events)
+    .boxed()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_streams_start_with_the_protocol_version() {
+        let events = greet(
+            Some(PROTOCOL_VERSION),
+            stream::iter([TickEvent::EndOfMatch]),
+        )
+        .collect::<Vec<_>>()
+        .await;
+        assert!(matches!(
+            events.as_slice(),
+            [
+                TickEvent::Heartbeat {
+                    protocol_version: PROTOCOL_VERSION
+                },
+                TickEvent::EndOfMatch
+            ]
+        ));
+
+        // (clients from before versioning are still served while nothing has broken)
+        assert!(is_supported(None));
+        assert!(is_supported(Some(PROTOCOL_VERSION)));
+    }
+}
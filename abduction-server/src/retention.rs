@@ -0,0 +1,149 @@
+//! Pruning old logs so the db doesn't grow forever
+//!
+//! Every log a match sends is saved, and over a season that adds up (most of it ambient flavour).
+//! So logs are only kept for so long depending on their tier: ambient logs go after a few days,
+//! normal logs after a month, and highlights are kept forever, as they're what's worth looking back
+//! on. While the server is running, anything past its window is pruned every so often.
+//!
+//! The windows can be changed with `LOG_RETENTION_AMBIENT`, `LOG_RETENTION_NORMAL` and
+//! `LOG_RETENTION_HIGHLIGHT` (in days, or `forever`), and how often logs are pruned with
+//! `LOG_PRUNE_INTERVAL` (in minutes).
+
+use std::env;
+
+use anyhow::{anyhow, Context};
+use chrono::TimeDelta;
+use strum::IntoEnumIterator;
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn};
+
+use crate::{logs::LogTier, Db};
+
+/// How long logs of each tier are kept, and how often they're pruned
+#[derive(Debug, Clone)]
+pub struct LogRetention {
+    /// How long ambient logs are kept (none -> forever)
+    pub ambient: Option<TimeDelta>,
+
+    /// How long normal logs are kept (none -> forever)
+    pub normal: Option<TimeDelta>,
+
+    /// How long highlights are kept (none -> forever)
+    pub highlight: Option<TimeDelta>,
+
+    /// How long to wait between each prune
+    pub prune_interval: Duration,
+}
+
+impl Default for LogRetention {
+    fn default() -> Self {
+        Self {
+            ambient: Some(TimeDelta::days(3)),
+            normal: Some(TimeDelta::days(30)),
+            highlight: None,
+            prune_interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+impl LogRetention {
+    /// The default retention, with anything set in the environment instead
+    pub fn from_env() -> Self {
+        let mut retention = Self::default();
+        let windows = [
+            ("LOG_RETENTION_AMBIENT", &mut retention.ambient),
+            ("LOG_RETENTION_NORMAL", &mut retention.normal),
+            ("LOG_RETENTION_HIGHLIGHT", &mut retention.highlight),
+        ];
+        for (var, window) in windows {
+            let Ok(value) = env::var(var) else {
+                continue;
+            };
+            match parse_window(&value) {
+                Ok(parsed) => *window = parsed,
+                Err(err) => warn!("Ignoring {var}: {err:?}"),
+            }
+        }
+        if let Ok(value) = env::var("LOG_PRUNE_INTERVAL") {
+            match value.parse::<u64>() {
+                Ok(minutes) if minutes > 0 => {
+                    retention.prune_interval = Duration::from_secs(minutes * 60)
+                }
+                _ => warn!("Ignoring LOG_PRUNE_INTERVAL, expected a number of minutes"),
+            }
+        }
+        retention
+    }
+
+    /// How long logs of some tier are kept (none -> forever)
+    pub fn window(&self, tier: LogTier) -> Option<TimeDelta> {
+        match tier {
+            LogTier::Ambient => self.ambient,
+            LogTier::Normal => self.normal,
+            LogTier::Highlight => self.highlight,
+        }
+    }
+
+    /// Remove every log that has been kept for longer than its tier's window
+    /// (returns how many were removed)
+    pub async fn prune(&self, db: &Db) -> anyhow::Result<u64> {
+        let mut pruned = 0;
+        for tier in LogTier::iter() {
+            let Some(window) = self.window(tier) else {
+                continue;
+            };
+            let tier_name = tier.to_string();
+            let older_than = format!("-{} seconds", window.num_seconds());
+            pruned += sqlx::query_file!("queries/prune_match_logs.sql", tier_name, older_than)
+                .execute(db)
+                .await
+                .with_context(|| format!("Failed to prune {tier_name} logs"))?
+                .rows_affected();
+        }
+        Ok(pruned)
+    }
+
+    /// Prune logs every `prune_interval`, forever
+    pub async fn prune_periodically(&self, db: &Db) {
+        loop {
+            match self.prune(db).await {
+                Ok(0) => {}
+                Ok(pruned) => info!("Pruned {pruned} old logs"),
+                Err(err) => warn!("Failed to prune logs: {err:?}"),
+            }
+            sleep(self.prune_interval).await;
+        }
+    }
+}
+
+/// Parse a retention window in days (i.e `3`, or `forever` to keep them forever)
+fn parse_window(value: &str) -> anyhow::Result<Option<TimeDelta>> {
+    if value.eq_ignore_ascii_case("forever") {
+        return Ok(None);
+    }
+    let days: i64 = value
+        .parse()
+        .context("Expected a number of days, or `forever`")?;
+    if days < 0 {
+        return Err(anyhow!("Can't keep logs for a negative number of days"));
+    }
+    Ok(Some(TimeDelta::days(days)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_retention_window() {
+        assert_eq!(parse_window("3").unwrap(), Some(TimeDelta::days(3)));
+        assert_eq!(parse_window("Forever").unwrap(), None);
+        assert!(parse_window("-1").is_err());
+        assert!(parse_window("a while").is_err());
+
+        // Highlights are kept forever unless told otherwise
+        let retention = LogRetention::default();
+        assert_eq!(retention.window(LogTier::Highlight), None);
+        assert!(retention.window(LogTier::Ambient) < retention.window(LogTier::Normal));
+    }
+}
@@ -0,0 +1,291 @@
+//! Source of randomness for the game
+//!
+//! Works just like `crate::rng::rng()`, but can be seeded (per thread) so that
+//! a match plays out the same way every time (i.e in tests)
+//!
+//! To check whether someone was just unlucky, significant rolls (hazards, lightning, which action
+//! someone took) can also be audited. Each audited roll draws a seed from the game rng and rolls
+//! with that, so the last few thousand rolls can be replayed from their seed to verify them.
+//! Rolls made for things that never really happen (i.e a forecast, see `mtch::forecast`) are left
+//! out by running them `unaudited`.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::Future,
+    sync::Mutex,
+};
+
+use anyhow::Context;
+use rand::{
+    distr::{weighted::WeightedIndex, Distribution},
+    rngs::{StdRng, ThreadRng},
+    Rng, RngCore, SeedableRng,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{entity::EntityId, mtch::TickId};
+
+thread_local! {
+    /// When set, every `GameRng` on this thread draws from this instead
+    static SEEDED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+
+    /// How many ids have been made since the game rng was seeded (see `seeded_id`)
+    static SEEDED_ID_COUNT: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Seed the game rng for the current thread
+#[cfg(test)]
+pub fn seed(seed: u64) {
+    SEEDED_RNG.with_borrow_mut(|rng| *rng = Some(StdRng::seed_from_u64(seed)));
+    SEEDED_ID_COUNT.set(0);
+}
+
+/// A new id drawn from the game rng, if it's been seeded (none -> it hasn't, i.e outside of tests)
+/// Like any other id these sort in the order they're made (see `Entity::id`), only they count up
+/// from when it was seeded rather than the time
+pub fn seeded_id() -> Option<Uuid> {
+    let random_bytes: [u8; 10] =
+        SEEDED_RNG.with_borrow_mut(|seeded| seeded.as_mut().map(|rng| rng.random()))?;
+    let count = SEEDED_ID_COUNT.replace(SEEDED_ID_COUNT.get() + 1);
+    Some(uuid::Builder::from_unix_timestamp_millis(count, &random_bytes).into_uuid())
+}
+
+/// Get a handle to the game rng
+pub fn rng() -> GameRng {
+    GameRng(rand::rng())
+}
+
+/// Handle to the game rng
+/// (draws from the thread rng unless the game rng has been seeded)
+pub struct GameRng(ThreadRng);
+
+impl GameRng {
+    fn with_source<T>(&mut self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        SEEDED_RNG.with_borrow_mut(|seeded| match seeded {
+            Some(seeded) => f(seeded),
+            None => f(&mut self.0),
+        })
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.with_source(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.with_source(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        self.with_source(|rng| rng.fill_bytes(dst))
+    }
+}
+
+/// How many rolls the audit keeps (the oldest are dropped first)
+const ROLL_AUDIT_CAPACITY: usize = 10_000;
+
+/// Significant rolls made recently, when auditing is enabled
+static ROLL_AUDIT: Mutex<Option<RollAudit>> = Mutex::new(None);
+
+tokio::task_local! {
+    /// Set for tasks whose rolls shouldn't be audited (see `unaudited`)
+    static UNAUDITED: ();
+}
+
+/// What a significant roll decided
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[qubit::ts]
+#[serde(rename_all = "snake_case")]
+pub enum RollKind {
+    /// Whether a hazard at someone's hex could hurt them
+    Hazard,
+
+    /// Whether lightning started a fire somewhere
+    LightningFire,
+
+    /// Whether lightning hit someone
+    LightningStrike,
+
+    /// Which action someone took
+    ActionSample,
+
+    /// Whether someone couldn't bring themselves to loot a corpse
+    LootBalk,
+
+    /// Whether someone managed to fit a part into an escape pod
+    PodRepair,
+
+    /// Whether a raft went over on the water
+    Capsize,
+}
+
+/// A roll recorded by the audit
+#[derive(Debug, Clone, Serialize)]
+#[qubit::ts]
+pub struct AuditedRoll {
+    pub tick_id: TickId,
+    pub kind: RollKind,
+
+    /// Who the roll was for (if anyone)
+    pub entity_id: Option<EntityId>,
+
+    /// What was rolled against, i.e the chance of a yes/no roll or the weights of a sample
+    pub odds: Vec<f64>,
+
+    /// Seed (drawn from the game rng) that the roll was made with
+    /// (as a string, since it doesn't fit in a js number)
+    pub seed: String,
+
+    /// Index of what was rolled (for a yes/no roll, 1 is yes)
+    pub outcome: usize,
+}
+
+impl AuditedRoll {
+    /// Make the roll again from its seed, which should give the same outcome
+    pub fn replay(&self) -> anyhow::Result<usize> {
+        let seed = self.seed.parse().context("Parsing roll seed")?;
+        let mut rng = StdRng::seed_from_u64(seed);
+        if self.kind == RollKind::ActionSample {
+            return Ok(WeightedIndex::new(&self.odds)?.sample(&mut rng));
+        }
+        let chance = *self.odds.first().context("Missing chance")?;
+        Ok(rng.random_bool(chance) as usize)
+    }
+}
+
+#[derive(Debug, Default)]
+struct RollAudit {
+    tick_id: TickId,
+    rolls: VecDeque<AuditedRoll>,
+}
+
+impl RollAudit {
+    /// Make a roll from a new seed and record it
+    fn roll(
+        &mut self,
+        rng: &mut impl Rng,
+        kind: RollKind,
+        entity_id: Option<&EntityId>,
+        odds: Vec<f64>,
+    ) -> usize {
+        let mut roll = AuditedRoll {
+            tick_id: self.tick_id,
+            kind,
+            entity_id: entity_id.cloned(),
+            odds,
+            seed: rng.next_u64().to_string(),
+            outcome: 0,
+        };
+        roll.outcome = roll.replay().unwrap();
+        let outcome = roll.outcome;
+
+        if self.rolls.len() >= ROLL_AUDIT_CAPACITY {
+            self.rolls.pop_front();
+        }
+        self.rolls.push_back(roll);
+        outcome
+    }
+
+    fn tick_rolls(&self, tick_id: TickId) -> Vec<AuditedRoll> {
+        self.rolls
+            .iter()
+            .filter(|roll| roll.tick_id == tick_id)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Start recording significant rolls
+pub fn enable_roll_audit() {
+    ROLL_AUDIT
+        .lock()
+        .unwrap()
+        .get_or_insert_with(RollAudit::default);
+}
+
+/// Run something without auditing any of the rolls it makes
+pub async fn unaudited<F: Future>(f: F) -> F::Output {
+    UNAUDITED.scope((), f).await
+}
+
+/// Run something with the roll audit, if rolls are being audited right now
+fn with_audit<T>(f: impl FnOnce(Option<&mut RollAudit>) -> T) -> T {
+    if UNAUDITED.try_with(|_| ()).is_ok() {
+        return f(None);
+    }
+    f(ROLL_AUDIT.lock().unwrap().as_mut())
+}
+
+/// Set the tick that rolls are recorded against
+pub fn set_audit_tick(tick_id: TickId) {
+    with_audit(|audit| {
+        if let Some(audit) = audit {
+            audit.tick_id = tick_id;
+        }
+    })
+}
+
+/// The recorded rolls for some tick, or none if auditing isn't enabled
+pub fn tick_rolls(tick_id: TickId) -> Option<Vec<AuditedRoll>> {
+    let audit = ROLL_AUDIT.lock().unwrap();
+    Some(audit.as_ref()?.tick_rolls(tick_id))
+}
+
+/// Roll with some chance of success, recording the roll if auditing is enabled
+pub fn audited_bool(
+    rng: &mut impl Rng,
+    kind: RollKind,
+    entity_id: Option<&EntityId>,
+    chance: f64,
+) -> bool {
+    with_audit(|audit| match audit {
+        Some(audit) => audit.roll(rng, kind, entity_id, vec![chance]) == 1,
+        None => rng.random_bool(chance),
+    })
+}
+
+/// Pick an index by weight, recording the roll if auditing is enabled
+pub fn audited_index(
+    rng: &mut impl Rng,
+    kind: RollKind,
+    entity_id: Option<&EntityId>,
+    weights: &[usize],
+) -> usize {
+    with_audit(|audit| match audit {
+        Some(audit) => {
+            let odds = weights.iter().map(|weight| *weight as f64).collect();
+            audit.roll(rng, kind, entity_id, odds)
+        }
+        None => WeightedIndex::new(weights).unwrap().sample(rng),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_audited_rolls_replay() {
+        let mut audit = RollAudit::default();
+        let mut rng = rng();
+        let entity_id = EntityId::default();
+        for tick_id in 0..ROLL_AUDIT_CAPACITY {
+            audit.tick_id = tick_id;
+            audit.roll(&mut rng, RollKind::Hazard, Some(&entity_id), vec![0.5]);
+            audit.roll(&mut rng, RollKind::ActionSample, None, vec![1.0, 2.0, 3.0]);
+        }
+
+        // Only the latest rolls are kept
+        assert_eq!(audit.rolls.len(), ROLL_AUDIT_CAPACITY);
+        assert!(audit.tick_rolls(0).is_empty());
+
+        let rolls = audit.tick_rolls(ROLL_AUDIT_CAPACITY - 1);
+        assert_eq!(rolls.len(), 2);
+        for roll in rolls {
+            assert_eq!(roll.replay().unwrap(), roll.outcome);
+        }
+    }
+}
@@ -5,3 +5,9 @@ import { create_qubit_api } from './qbsv';
 export const { get_api, init_context, load_api } = create_qubit_api<QubitServer>('/_/rpc', {
 	browser
 });
+
+/**
+ * Which version of the server's wire protocol this site understands
+ * (bump along with `PROTOCOL_VERSION` in the server's `protocol.rs`)
+ */
+export const PROTOCOL_VERSION = 1;
@@ -1,5 +1,13 @@
-import type { AxialHex, Entity, GameLog, MatchConfig, TickEvent } from '$lib/api.gen';
+import type {
+	AxialHex,
+	Entity,
+	GameLog,
+	MatchConfig,
+	SegmentKind,
+	TickEvent
+} from '$lib/api.gen';
 import { SvelteMap } from 'svelte/reactivity';
+import { PROTOCOL_VERSION } from './api';
 import { logLevel, logMessage, type GameLogLevel } from './logs';
 
 export const LOG_BUFFER_LIMIT = 1000;
@@ -13,6 +21,15 @@ export type DecoratedLog = GameLog & {
 
 type EntityUpdateHandler = (entity: Entity) => void;
 
+export type Announcement = {
+	segment: SegmentKind;
+	quote: string;
+	tickId: number;
+};
+
+/** How many ticks an announcement stays up for */
+export const ANNOUNCEMENT_TICKS = 10;
+
 export class Game {
 	/** Map from entity ids to entity states -> only stores latest state */
 	entities: SvelteMap<string, Entity>;
@@ -22,21 +39,33 @@ export class Game {
 	logCounter: number;
 
 	tickId: number;
+
+	/** How fast the match is being played (1 is full speed, it slows down when nobody is watching) */
+	speed: number;
+
 	config: MatchConfig | null;
 	loaded: boolean;
 	waitingForStart: boolean;
 
+	/** The last segment of the show the presenter ran */
+	announcement: Announcement | null;
+
 	entityUpdateHandlers: Array<EntityUpdateHandler> = [];
 
+	/** Requests (i.e votes) waiting to take effect, by their id */
+	pendingRequests: Map<string, (tickId: number) => void> = new Map();
+
 	constructor() {
 		this.entities = new SvelteMap();
 		this.logs = $state([]);
 		this.logCounter = 0;
 
 		this.tickId = $state(0);
+		this.speed = $state(1);
 		this.loaded = $state(false);
 		this.config = $state(null);
 		this.waitingForStart = $state(false);
+		this.announcement = $state(null);
 	}
 
 	onUpdate(handler: EntityUpdateHandler) {
@@ -47,6 +76,11 @@ export class Game {
 		};
 	}
 
+	/** Wait for a request (from a mutation like `vote_poll`) to take effect, with the tick it did */
+	awaitRequest(requestId: string): Promise<number> {
+		return new Promise((resolve) => this.pendingRequests.set(requestId, resolve));
+	}
+
 	addLog(log: GameLog) {
 		// TODO: limit the size of this buffer
 		this.logs.push({
@@ -71,6 +105,24 @@ export class Game {
 	}
 
 	processEvent(event: TickEvent) {
+		if (event.kind === 'heartbeat' && event.protocol_version !== PROTOCOL_VERSION) {
+			console.warn(
+				`Server speaks protocol version ${event.protocol_version}, but we speak ${PROTOCOL_VERSION}`
+			);
+		}
+
+		if (event.kind === 'protocol_unsupported') {
+			// We're out of date, so reload to get the latest site (only once, so we don't loop forever)
+			console.warn(
+				`Server no longer supports protocol version ${PROTOCOL_VERSION} (needs at least ${event.min_supported_version})`
+			);
+			if (!sessionStorage.getItem('reloaded_for_protocol')) {
+				sessionStorage.setItem('reloaded_for_protocol', 'true');
+				location.reload();
+			}
+			return;
+		}
+
 		if (event.kind === 'start_of_match') {
 			// For now, just reload the page, as we need to do a full reset anyway
 			location.reload();
@@ -101,6 +153,23 @@ export class Game {
 			this.tickId = event.tick_id;
 		}
 
+		if (event?.kind === 'end_of_tick') {
+			this.speed = event.speed;
+		}
+
+		if (event?.kind === 'caught_up') {
+			this.tickId = event.tick_id;
+		}
+
+		if (event?.kind === 'request_applied') {
+			this.pendingRequests.get(event.request_id)?.(event.tick_id);
+			this.pendingRequests.delete(event.request_id);
+		}
+
+		if (event?.kind === 'announcement') {
+			this.announcement = { segment: event.segment, quote: event.quote, tickId: this.tickId };
+		}
+
 		if (event?.kind === 'end_of_match') {
 			console.log('End of match');
 			this.waitingForStart = true;
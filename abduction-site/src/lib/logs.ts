@@ -1,4 +1,13 @@
-import type { AxialHexDirection, GameLog, InfoTopic, MotivatorKey } from './api.gen';
+import type {
+	AxialHexDirection,
+	BarkVariant,
+	Deed,
+	GameLog,
+	InfoTopic,
+	Intervention,
+	MotivatorKey,
+	Witnessed
+} from './api.gen';
 import type { Game } from './game.svelte';
 
 /** If global, shows up everywhere, if local only if scoped to the hex/entity */
@@ -10,7 +19,17 @@ export function logLevel(log: GameLog, game: Game) {
 	if (log.involved_entities.length === 0) return 'global';
 	if (log.kind === 'entity_death') return 'global';
 	if (log.kind === 'lightning_strike') return 'global';
-	if (log.kind === 'entity_warp_in' || log.kind === 'entity_warp_out') return 'global';
+	if (log.kind === 'meteor_shower' || log.kind === 'aurora' || log.kind === 'animal_stampede')
+		return 'global';
+	if (log.kind === 'flood' || log.kind === 'flood_recede') return 'global';
+	if (
+		log.kind === 'entity_warp_in' ||
+		log.kind === 'entity_warp_out' ||
+		log.kind === 'entity_collect_remains'
+	)
+		return 'global';
+	if (log.kind === 'entity_escape_in_pod') return 'global';
+	if (log.kind === 'divine_intervention') return 'global';
 
 	// Anything done by crew is global
 	if (
@@ -75,6 +94,35 @@ function formatBark(name: string, motivator: MotivatorKey, severity: BarkSeverit
 	}
 }
 
+/** A flavoured bark, for when the content file had no line for it */
+function formatBarkVariant(name: string, motivator: MotivatorKey, variant: BarkVariant) {
+	const complaint = formatComplaint(motivator);
+	return (
+		{
+			joking: `${name} cracks a joke about ${complaint}`,
+			despairing: `${name} despairs about ${complaint}`,
+			stoic: `${name} quietly puts up with ${complaint}`,
+			nostalgic: `${name} says ${complaint} was never this bad back home`
+		} satisfies Record<BarkVariant, string>
+	)[variant];
+}
+
+function formatComplaint(motivator: MotivatorKey) {
+	return (
+		{
+			boredom: 'being bored',
+			hunger: 'being hungry',
+			hurt: 'the pain',
+			thirst: 'being thirsty',
+			sickness: 'feeling sick',
+			tiredness: 'being tired',
+			saturation: 'being soaked',
+			cold: 'the cold',
+			sadness: 'feeling down'
+		} satisfies Record<MotivatorKey, string>
+	)[motivator];
+}
+
 function formatInfoTopic(topic: InfoTopic) {
 	if (topic === 'ShelterLocation') {
 		return 'a safe location to take shelter';
@@ -83,6 +131,30 @@ function formatInfoTopic(topic: InfoTopic) {
 	}
 }
 
+function formatWitnessed(event: Witnessed, game: Game) {
+	if (event.kind === 'death') {
+		return `the death of ${game.entities.get(event.entity_id)?.name ?? 'someone'}`;
+	} else if (event.kind === 'weather') {
+		return `the ${event.weather.replaceAll('_', ' ')} they were caught out in`;
+	} else if (event.kind === 'feat') {
+		const entityName = game.entities.get(event.entity_id)?.name ?? 'someone';
+		return `the time ${entityName} became known as "${event.epithet.replaceAll('_', ' ')}"`;
+	}
+}
+
+function formatIntervention(intervention: Intervention, name: string) {
+	if (intervention.kind === 'spawn_prop') {
+		return `a ${name} appears out of nowhere`;
+	} else if (intervention.kind === 'set_motivator') {
+		const feeling = intervention.motivation > 0.5 ? 'struck by' : 'relieved of';
+		return `${name} is suddenly ${feeling} ${formatComplaint(intervention.motivator)}`;
+	} else if (intervention.kind === 'kill') {
+		return `${name} is marked for death`;
+	} else if (intervention.kind === 'set_weather') {
+		return `the weather turns to ${intervention.weather.replaceAll('_', ' ')}`;
+	}
+}
+
 export function logMessage(log: GameLog, game: Game) {
 	// Grab the full entity state for the entities associated with the log
 	const entities = log.involved_entities.map((entityId) => {
@@ -90,8 +162,10 @@ export function logMessage(log: GameLog, game: Game) {
 	});
 
 	// Get the name of the entity primarily associated with this log
-	const primaryName = entities?.[0]?.name ?? 'Someone';
-	const secondaryName = entities?.[1]?.name ?? 'Someone';
+	// (going by their nickname, if one has stuck)
+	const primaryName = entities?.[0]?.attributes.nickname ?? entities?.[0]?.name ?? 'Someone';
+	const secondaryName = entities?.[1]?.attributes.nickname ?? entities?.[1]?.name ?? 'Someone';
+	const tertiaryName = entities?.[2]?.name ?? 'something';
 
 	// Now consider the kind
 	if (log.kind === 'weather_change') {
@@ -99,7 +173,9 @@ export function logMessage(log: GameLog, game: Game) {
 	}
 
 	if (log.kind === 'time_of_day_change') {
-		return `It is now ${log.time_of_day}`;
+		// (logs from before the calendar have no day)
+		if (!log.day) return `It is now ${log.time_of_day}`;
+		return `Day ${log.day}, ${log.part_of_day}. It is now ${log.time_of_day}`;
 	}
 
 	if (log.kind === 'entity_movement') {
@@ -115,6 +191,14 @@ export function logMessage(log: GameLog, game: Game) {
 	}
 
 	if (log.kind === 'entity_motivator_bark') {
+		if (log.line) {
+			return log.line.replaceAll('{name}', primaryName);
+		}
+
+		if (log.bark_variant) {
+			return formatBarkVariant(primaryName, log.motivator, log.bark_variant);
+		}
+
 		const severity = log.motivation > 0.75 ? 'severe' : 'moderate';
 		return formatBark(primaryName, log.motivator, severity);
 	}
@@ -123,10 +207,34 @@ export function logMessage(log: GameLog, game: Game) {
 		return `${primaryName} has died`;
 	}
 
+	if (log.kind === 'entity_nicknamed') {
+		return `${entities?.[0]?.name ?? 'Someone'} is now known to everyone as ${log.nickname}`;
+	}
+
+	if (log.kind === 'spirit_wistful') {
+		return `${primaryName} drifts by, still hoping ${log.hope}`;
+	}
+
 	if (log.kind === 'hazard_hurt') {
 		return `${secondaryName} was damaged by ${primaryName}`;
 	}
 
+	if (log.kind === 'predator_stalk') {
+		return `${primaryName} picks up ${secondaryName}'s scent`;
+	}
+
+	if (log.kind === 'predator_give_up') {
+		return `${primaryName} loses interest in ${secondaryName}`;
+	}
+
+	if (log.kind === 'predator_maul') {
+		return `${primaryName} sets upon ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_kill_predator') {
+		return `${primaryName} brings down the ${secondaryName}`;
+	}
+
 	if (log.kind === 'entity_start_sleeping') {
 		return `${primaryName} lied down and closed their eyes`;
 	}
@@ -147,10 +255,74 @@ export function logMessage(log: GameLog, game: Game) {
 		return `${primaryName} spotted a lush location nearby`;
 	}
 
+	if (log.kind === 'entity_throw') {
+		return `${primaryName} throws the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_throw_hit') {
+		return `${primaryName} hits ${secondaryName} with it!`;
+	}
+
+	if (log.kind === 'entity_throw_miss') {
+		return `${primaryName} misses ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_flee') {
+		return `${primaryName} runs from ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_follow_tracks') {
+		return `${primaryName} follows ${secondaryName}'s tracks`;
+	}
+
+	if (log.kind === 'entity_lose_tracks') {
+		return `${primaryName} loses ${secondaryName}'s trail`;
+	}
+
+	if (log.kind === 'entity_strip_corpse') {
+		return `${primaryName} strips the clothes off the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_hesitate_before_loot') {
+		return `${primaryName} hesitates before going through the ${secondaryName}'s things`;
+	}
+
+	if (log.kind === 'entity_balk_at_loot') {
+		return `${primaryName} can't bring themselves to go through the ${secondaryName}'s things`;
+	}
+
+	if (log.kind === 'entity_loot_corpse') {
+		return `${primaryName} takes the ${tertiaryName} off the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_find_keepsake') {
+		return `Going through the ${secondaryName}'s things, ${primaryName} comes across ${log.description}`;
+	}
+
+	if (log.kind === 'entity_spot_prop') {
+		return `${primaryName} spots a ${secondaryName}`;
+	}
+
 	if (log.kind === 'entity_fell_in_water_source') {
 		return `${primaryName} fell into the ${secondaryName}`;
 	}
 
+	if (log.kind === 'entity_start_fishing') {
+		return `${primaryName} starts fishing in the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_keep_fishing') {
+		return `${primaryName} waits patiently for a bite`;
+	}
+
+	if (log.kind === 'entity_catch_fish') {
+		return `${primaryName} caught a ${secondaryName}!`;
+	}
+
+	if (log.kind === 'entity_stop_fishing') {
+		return `${primaryName} gives up on fishing`;
+	}
+
 	if (log.kind === 'entity_hesitate_before_consume') {
 		return `${primaryName} goes to eat ${secondaryName}, but hesitates for a second`;
 	}
@@ -159,6 +331,15 @@ export function logMessage(log: GameLog, game: Game) {
 		return `${primaryName} makes a face. The ${secondaryName} tasted horrible!`;
 	}
 
+	if (log.kind === 'entity_idle') {
+		if (log.behavior === 'whittle') return `${primaryName} whittles away at a bit of wood`;
+		if (log.behavior === 'hum') return `${primaryName} hums a tune to themselves`;
+		if (log.behavior === 'stargaze') return `${primaryName} lies back and gazes up at the stars`;
+		if (log.behavior === 'stretch') return `${primaryName} stretches out their limbs`;
+		if (log.behavior === 'doodle') return `${primaryName} draws something in the dirt with a stick`;
+		return `${primaryName} quietly watches the others`;
+	}
+
 	if (log.kind === 'entity_cold_because_of_time') {
 		return `${primaryName} shivers in the cold wind`;
 	}
@@ -171,14 +352,114 @@ export function logMessage(log: GameLog, game: Game) {
 		return `${primaryName} is getting thoroughly rained on`;
 	}
 
+	if (log.kind === 'entity_repeat') {
+		if (log.log.kind === 'entity_motivator_bark') {
+			return `${primaryName} continues to complain about ${formatComplaint(log.log.motivator)}`;
+		} else if (log.log.kind === 'entity_cold_because_of_time') {
+			return `${primaryName} is still shivering in the cold wind`;
+		} else if (log.log.kind === 'entity_warm_because_of_time') {
+			return `${primaryName} is still soaking up the sun`;
+		} else if (log.log.kind === 'entity_saturated_because_of_rain') {
+			return `${primaryName} is still getting rained on`;
+		} else if (log.log.kind === 'entity_idle') {
+			return `${primaryName} whiles away the time`;
+		}
+		return `${primaryName} keeps at it`;
+	}
+
 	if (log.kind === 'entity_hit_by_lightning') {
 		return `${primaryName} was struck by lightning!`;
 	}
 
+	if (log.kind === 'meteor_shower') {
+		return `Meteors streak across the sky and crash into the ground!`;
+	}
+
+	if (log.kind === 'entity_hit_by_meteor') {
+		return `${primaryName} was hit by ${secondaryName}!`;
+	}
+
+	if (log.kind === 'aurora') {
+		return `Ribbons of light dance across the night sky, and a calm settles over everyone`;
+	}
+
+	if (log.kind === 'animal_stampede') {
+		return `A herd of animals stampedes across the land!`;
+	}
+
+	if (log.kind === 'entity_trampled') {
+		return `${primaryName} was trampled by stampeding animals!`;
+	}
+
+	if (log.kind === 'flood') {
+		return `After all that rain, the low-lying land floods!`;
+	}
+
+	if (log.kind === 'flood_recede') {
+		return `The floodwater finally recedes`;
+	}
+
+	if (log.kind === 'entity_washed_away') {
+		return `${primaryName} is washed away by the floodwater`;
+	}
+
+	if (log.kind === 'entity_escape_flood') {
+		return `${primaryName} wades out of the floodwater to higher ground`;
+	}
+
+	if (log.kind === 'water_source_dry_up') {
+		return `The ${primaryName} has dried up in the long dry spell`;
+	}
+
+	if (log.kind === 'entity_fit_pod_part') {
+		return `${primaryName} fits the ${tertiaryName} into the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_fumble_pod_repair') {
+		return `${primaryName} tries to fit the ${tertiaryName} into the ${secondaryName}, but can't get it to go`;
+	}
+
+	if (log.kind === 'entity_escape_in_pod') {
+		return `${primaryName} gets the ${secondaryName} working and flies off in it!`;
+	}
+
+	if (log.kind === 'divine_intervention') {
+		return `Divine intervention! ${formatIntervention(log.intervention, primaryName)}`;
+	}
+
+	if (log.kind === 'entity_create_cache') {
+		return `${primaryName} hides away a stash`;
+	}
+
+	if (log.kind === 'entity_deposit_in_cache') {
+		return `${primaryName} tucks the ${secondaryName} away in their stash`;
+	}
+
+	if (log.kind === 'entity_withdraw_from_cache') {
+		return `${primaryName} takes the ${secondaryName} out of their stash`;
+	}
+
+	if (log.kind === 'entity_raid_cache') {
+		return `${primaryName} raids someone else's stash!`;
+	}
+
 	if (log.kind === 'lightning_strike') {
 		return `Lightning struck the ground and started a fire!`;
 	}
 
+	if (log.kind === 'entity_held_up_by_weather') {
+		if (log.weather === 'hurricane') return `${primaryName} can't make any headway against the wind`;
+		return `${primaryName} can barely see where they're going in the ${log.weather.replaceAll('_', ' ')}`;
+	}
+
+	if (log.kind === 'entity_slip') {
+		return `${primaryName} loses their footing and goes over`;
+	}
+
+	if (log.kind === 'fire_burn_out') {
+		return `The ${primaryName} burns itself out`;
+	}
+
 	if (log.kind === 'entity_greet') {
 		if (log.response) {
 			if (log.bond === 0) return `${primaryName} waves back at ${secondaryName}`;
@@ -194,7 +475,21 @@ export function logMessage(log: GameLog, game: Game) {
 	}
 
 	if (log.kind === 'entity_mourn_over_corpse') {
-		return `${primaryName} has a quiet vigil for ${secondaryName.replaceAll('Corpse of', '')}`;
+		const vigil = `${primaryName} has a quiet vigil for ${secondaryName.replaceAll('Corpse of', '')}`;
+		return log.remembering ? `${vigil}, remembering them as ${log.remembering}` : vigil;
+	}
+
+	if (log.kind === 'entity_keep_keepsake') {
+		return `${primaryName} keeps ${log.description} to remember ${secondaryName.replaceAll('Corpse of ', '')} by`;
+	}
+
+	if (log.kind === 'entity_hold_memorial') {
+		if (log.involved_entities.length > 1) return `${primaryName} holds a quiet memorial for ${log.name} by their body`;
+		return `${primaryName} returns to where ${log.name} died and holds a quiet memorial`;
+	}
+
+	if (log.kind === 'entity_join_memorial') {
+		return `${primaryName} joins ${secondaryName} in remembering ${log.name}`;
 	}
 
 	if (log.kind === 'entity_upset_by_death') {
@@ -245,10 +540,169 @@ export function logMessage(log: GameLog, game: Game) {
 		return `${primaryName} warps out ${secondaryName}`;
 	}
 
+	if (log.kind === 'entity_collect_remains') {
+		return `${primaryName} carries away ${log.name}, ${log.summary}`;
+	}
+
+	if (log.kind === 'entity_teleport') {
+		return `${primaryName} vanishes in a flash of light and reappears somewhere else`;
+	}
+
+	if (log.kind === 'entity_awed_by_crew') {
+		return `${primaryName} stares at ${secondaryName} in awe`;
+	}
+
+	if (log.kind === 'entity_frightened_by_crew') {
+		return `${primaryName} is terrified by ${secondaryName}`;
+	}
+
 	if (log.kind === 'entity_say_exact') {
 		return `${primaryName}: "${log.quote}"`;
 	}
 
+	if (log.kind === 'entity_search_fail') {
+		return `${primaryName} searches the ${secondaryName} but comes up empty handed`;
+	}
+
+	if (log.kind === 'entity_search_find') {
+		return `${primaryName} searches around and finds a ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_claim_hex') {
+		return `${primaryName} claims the ${secondaryName} as their own`;
+	}
+
+	if (log.kind === 'entity_warn_trespasser') {
+		return `${primaryName} tells ${secondaryName} to get off their patch`;
+	}
+
+	if (log.kind === 'entity_repair_shelter') {
+		return `${primaryName} patches up the ${secondaryName}`;
+	}
+
+	if (log.kind === 'shelter_collapse') {
+		return `The ${primaryName} falls apart`;
+	}
+
+	if (log.kind === 'entity_hurt_by_collapse') {
+		return `${primaryName} is hurt when the ${secondaryName} collapses on them!`;
+	}
+
+	if (log.kind === 'entity_thank') {
+		return `${primaryName} thanks ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_confront') {
+		return `${primaryName} confronts ${secondaryName} about what they did`;
+	}
+
+	if (log.kind === 'entity_shun') {
+		return `${primaryName} keeps their distance from ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_steal') {
+		return `${primaryName} steals the ${tertiaryName} from ${secondaryName}!`;
+	}
+
+	if (log.kind === 'entity_steal_fail') {
+		return `${primaryName} tries to steal from ${secondaryName} but gets caught!`;
+	}
+
+	if (log.kind === 'entity_start_travelling') {
+		return `${primaryName} sets off together with ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_part_ways') {
+		return `${primaryName} parts ways with ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_drop') {
+		return `${primaryName} drops their ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_heal_self') {
+		return `${primaryName} treats their wounds with the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_heal_other') {
+		return `${primaryName} treats ${secondaryName}'s wounds with the ${tertiaryName}`;
+	}
+
+	if (log.kind === 'entity_drag_other') {
+		return `${primaryName} drags ${secondaryName} out of harm's way`;
+	}
+
+	if (log.kind === 'entity_share_food') {
+		return `${primaryName} gives ${secondaryName} the ${tertiaryName} to eat`;
+	}
+
+	if (log.kind === 'entity_start_guarding') {
+		return `${primaryName} keeps watch over ${secondaryName} while they sleep`;
+	}
+
+	if (log.kind === 'entity_keep_guarding') {
+		return `${primaryName} keeps an eye out for ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_stop_guarding') {
+		return `${primaryName} stops keeping watch`;
+	}
+
+	if (log.kind === 'entity_light_campfire') {
+		return `${primaryName} gets a campfire going`;
+	}
+
+	if (log.kind === 'entity_join_gathering') {
+		return `${primaryName} settles in around the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_tell_story') {
+		return `${primaryName} tells everyone around the campfire a story ${log.about}`;
+	}
+
+	if (log.kind === 'entity_leave_gathering') {
+		return `${primaryName} gets up from the campfire`;
+	}
+
+	if (log.kind === 'entity_build_raft') {
+		return `${primaryName} lashes together a ${secondaryName} by the water`;
+	}
+
+	if (log.kind === 'entity_board_raft') {
+		return `${primaryName} climbs aboard the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_take_raft') {
+		return `${primaryName} makes off with ${tertiaryName}'s ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_paddle_raft') {
+		return `${primaryName} paddles the ${secondaryName} on across the water`;
+	}
+
+	if (log.kind === 'entity_disembark_raft') {
+		return `${primaryName} climbs off the ${secondaryName}`;
+	}
+
+	if (log.kind === 'entity_capsize') {
+		return `The ${secondaryName} goes over in the rough water, throwing ${primaryName} in`;
+	}
+
+	if (log.kind === 'entity_witness_deed') {
+		return (
+			{
+				theft: `${primaryName} saw ${secondaryName} steal something`,
+				cannibalism: `${primaryName} saw ${secondaryName} eating a corpse`,
+				strip_corpse: `${primaryName} saw ${secondaryName} strip a corpse`,
+				loot_corpse: `${primaryName} saw ${secondaryName} going through a corpse's things`,
+				abandon_ally: `${primaryName} saw ${secondaryName} abandon an ally`,
+				tend_wounds: `${primaryName} saw ${secondaryName} tend to someone's wounds`,
+				assault: `${primaryName} saw ${secondaryName} attack someone`,
+				rescue: `${primaryName} saw ${secondaryName} come to someone's rescue`
+			} satisfies Record<Deed, string>
+		)[log.deed];
+	}
+
 	if (log.kind === 'entity_ask') {
 		if (log.ask.kind === 'ask_for_info') {
 			return `${primaryName} asks ${secondaryName} whether they know of ${formatInfoTopic(log.ask.topic)}`;
@@ -261,6 +715,10 @@ export function logMessage(log: GameLog, game: Game) {
 			} else if (log.ask.topic === 'Hope') {
 				return `${primaryName} asks ${secondaryName} what they hope for the future`;
 			}
+		} else if (log.ask.kind === 'recall_event') {
+			return `${primaryName} brings up ${formatWitnessed(log.ask.event, game)} with ${secondaryName}`;
+		} else if (log.ask.kind === 'remember_lost') {
+			return `${primaryName} tells ${secondaryName} how much they miss ${log.ask.name}`;
 		}
 	}
 
@@ -275,12 +733,26 @@ export function logMessage(log: GameLog, game: Game) {
 				return `${primaryName} describes what they know`;
 			}
 		} else if (log.respond.kind === 'give_opinion') {
+			const entityName = game.entities.get(log.respond.entity_id)?.name;
+			const about = entityName ? ` about ${entityName}` : '';
 			if (log.respond.opinion === 'Neutral') {
-				return `${primaryName} shrugs and says they are ambivalent`;
+				return `${primaryName} shrugs and says they are ambivalent${about}`;
 			} else if (log.respond.opinion === 'Positive') {
-				return `${primaryName} responds positively and gives a thumbs up`;
+				return `${primaryName} responds positively${about} and gives a thumbs up`;
 			} else if (log.respond.opinion === 'Negative') {
-				return `${primaryName} frowns and shakes their head`;
+				return `${primaryName} frowns and shakes their head${about}`;
+			}
+		} else if (log.respond.kind === 'react_to_event') {
+			if (log.respond.saw_it) {
+				return `${primaryName} nods, they were there too`;
+			} else {
+				return `${primaryName} listens wide-eyed, they hadn't heard`;
+			}
+		} else if (log.respond.kind === 'console') {
+			if (log.respond.grieving_too) {
+				return `${primaryName} nods sadly, they miss them too`;
+			} else {
+				return `${primaryName} listens and offers what comfort they can`;
 			}
 		}
 	}